@@ -0,0 +1,45 @@
+// Benchmark da avaliação de fórmulas: reinterpretação do código fonte, AST pré-compilada
+// pelo motor Rhai, bytecode compilado e consulta pelo gerenciador (bytecode em cache)
+
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use plasma_simulation::formula::{FormulaEngine, FormulaManager, FunctionType, ParameterValue};
+
+fn formula_evaluation_benchmark(c: &mut Criterion) {
+    let engine = FormulaEngine::new();
+    let formula = engine.get_formula_clone("thermal_conductivity").unwrap();
+    let compiled = engine.compile_formula("thermal_conductivity").unwrap();
+    let temperature_index = compiled.parameter_index("temperature").unwrap();
+
+    let mut group = c.benchmark_group("thermal_conductivity");
+
+    group.bench_function("reparse", |b| {
+        b.iter(|| engine.validate_formula(black_box(&formula.source), &formula.parameters).unwrap())
+    });
+
+    group.bench_function("ast", |b| {
+        let mut params = HashMap::new();
+        params.insert("temperature".to_string(), ParameterValue::Float(850.0));
+        b.iter(|| engine.evaluate_formula("thermal_conductivity", black_box(&params)).unwrap())
+    });
+
+    group.bench_function("compiled", |b| {
+        let mut arguments = compiled.defaults();
+        arguments[temperature_index] = 850.0;
+        b.iter(|| compiled.evaluate(black_box(&arguments)).unwrap())
+    });
+
+    group.bench_function("manager_lookup", |b| {
+        let mut manager = FormulaManager::new();
+        manager.set_formula_for_function(FunctionType::ThermalConductivity, "thermal_conductivity").unwrap();
+        let mut params = HashMap::new();
+        params.insert("temperature".to_string(), ParameterValue::Float(850.0));
+        b.iter(|| manager.evaluate_function(FunctionType::ThermalConductivity, black_box(&params)).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, formula_evaluation_benchmark);
+criterion_main!(benches);
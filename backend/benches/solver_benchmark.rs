@@ -0,0 +1,36 @@
+// Benchmark do solucionador de calor: execução completa em malhas de tamanhos crescentes
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use plasma_simulation::simulation::{HeatSolver, PlasmaTorch, SimulationParameters};
+
+fn benchmark_parameters(nr: usize, nz: usize) -> SimulationParameters {
+    let mut params = SimulationParameters::new(1.0, 0.5, nr, nz);
+    params.time_steps = 10;
+    params.time_step = 0.5;
+    params.total_time = 5.0;
+    params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 100.0, 0.01, 5000.0));
+    params
+}
+
+fn solver_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heat_solver");
+    group.sample_size(10);
+
+    for &(nr, nz) in &[(10, 10), (20, 40), (40, 80)] {
+        let params = benchmark_parameters(nr, nz);
+        group.bench_with_input(BenchmarkId::from_parameter(format!("{}x{}", nr, nz)), &params, |b, params| {
+            b.iter(|| {
+                let mut solver = HeatSolver::new(black_box(params.clone())).unwrap();
+                solver.run(None, Arc::new(AtomicBool::new(false))).unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, solver_benchmark);
+criterion_main!(benches);
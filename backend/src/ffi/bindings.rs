@@ -5,21 +5,42 @@ use std::os::raw::{c_char, c_int, c_float, c_double};
 use std::ptr;
 use std::slice;
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::Ordering;
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicI32, AtomicIsize, Ordering};
+use std::collections::{BTreeMap, HashMap};
 use std::mem;
-use std::cell::RefCell; // Added for thread-local
+use std::cell::{Cell, RefCell}; // Added for thread-local
+use std::panic::{self, AssertUnwindSafe};
 
 use crate::simulation::{
-    SimulationParameters, SimulationResults, HeatSolver, 
-    MaterialProperties, PlasmaTorch, SimulationState, SharedSimulationState
+    SimulationParameters, SimulationResults, 
+    MaterialProperties, PlasmaTorch, SimulationState, SharedSimulationState, ProgressUpdate
 };
-use crate::models::validation::{self, ImportOptions, ReferenceData, ValidationResult, ValidationMetrics};
-use crate::formulas; // Assuming this module exists
-use crate::metrics; // Assuming this module exists
-use crate::export;  // Assuming this module exists
-use crate::reporting; // Assuming this module exists
-use crate::parametric; // Assuming this module exists
+use crate::simulation::validation::{self, ReferenceData, ValidationResult, ValidationMetrics};
+use crate::simulation::scenarios;
+use crate::simulation::teaching;
+use crate::simulation::annotations::TimelineAnnotation;
+use crate::ffi::payload::{self, PayloadFormat};
+use crate::ffi::limits::{self, OversizedRequest};
+use crate::ffi::registry::{SimulationHandle, SimulationRegistry, INVALID_SIMULATION_HANDLE};
+use crate::ffi::errors::{FFIError, FFIErrorCode};
+use crate::ffi::capabilities;
+use crate::simulation::frames::{FrameEncoding, FramePacket, SharedFrameChannel};
+use crate::simulation::snapshot::{SnapshotOptions, SnapshotPackage};
+use crate::simulation::ensemble::{EnsembleAccumulator, EnsembleStatistic};
+use crate::simulation::archive::IntegrityReport;
+use crate::simulation::materials::MaterialLibrary;
+use crate::formula::{Formula, FormulaCategory, FormulaConflictPolicy, FormulaManager, FormulaParameter, FunctionType, ParameterValue};
+use crate::simulation::physics::TorchFluxProfile;
+use crate::simulation::project::{ProjectBundle, ProjectItemKind};
+use crate::simulation::storage::{self, ResultsStorage, StorageConfig};
+use crate::simulation::export_worker::{self, ExportJobConfig};
+use crate::simulation::jobs::{JobQueue, JobStatus};
+use crate::simulation::mesh::CylindricalMesh;
+use crate::simulation::metrics::{self, calculate_headline_metrics, HeadlineMetrics, MetricsAnalyzer};
+use crate::simulation::parametric::{self as parametric_study, ParametricStudyConfig, ParametricStudyManager, ParametricStudyResult, QueuedStudy};
+#[cfg(feature = "sqlite")]
+use crate::simulation::study_store::{CaseQuery, StudyStore};
+use crate::simulation::visualization::{self, ColorScale, LineProfileData};
 
 // Estrutura para passar parâmetros de simulação através da FFI
 #[repr(C)]
@@ -48,6 +69,8 @@ pub struct FFIPlasmaTorch {
     pub power: f64,
     pub gas_flow: f64,
     pub gas_temperature: f64,
+    pub flux_profile: i32,        // 0: Gaussiano, 1: Exponencial, 2: Cartola
+    pub flux_profile_length: f64, // σ, λ ou raio (m); <= 0 usa o diâmetro da tocha
 }
 
 // Estrutura para passar informações de material através da FFI
@@ -68,25 +91,238 @@ pub struct FFIMaterialProperties {
 // Estrutura para passar informações de estado da simulação através da FFI
 #[repr(C)]
 pub struct FFISimulationState {
-    pub status: i32,  // 0: NotStarted, 1: Running, 2: Paused, 3: Completed, 4: Failed
+    pub status: i32,  // 0: NotStarted, 1: Running, 2: Paused, 3: Completed, 4: Failed, 5: Cancelled
     pub progress: f32,
     pub error_message: *const c_char,
     pub execution_time: f64,
 }
 
-// Armazenamento global para o estado da simulação
-static mut SIMULATION_STATE: Option<SharedSimulationState> = None;
+// Estrutura para passar os números principais de um passo através da FFI, sem JSON
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FFISimulationMetrics {
+    pub time_step: u64,
+    pub time: f64,              // s
+    pub max_temperature: f64,   // °C
+    pub min_temperature: f64,   // °C
+    pub mean_temperature: f64,  // °C, média ponderada pelo volume
+    pub total_energy: f64,      // J
+    pub melt_volume: f64,       // m³
+}
+
+impl From<HeadlineMetrics> for FFISimulationMetrics {
+    fn from(metrics: HeadlineMetrics) -> Self {
+        Self {
+            time_step: metrics.step as u64,
+            time: metrics.time,
+            max_temperature: metrics.max_temperature,
+            min_temperature: metrics.min_temperature,
+            mean_temperature: metrics.mean_temperature,
+            total_energy: metrics.total_energy,
+            melt_volume: metrics.melt_volume,
+        }
+    }
+}
+
+// Instâncias de simulação por handle (as funções sem handle usam a instância padrão)
+static SIMULATIONS: SimulationRegistry = SimulationRegistry::new();
+
+// Formato negociado para os payloads binários (0 = JSON, 1 = MessagePack, 2 = CBOR)
+static PAYLOAD_FORMAT: AtomicI32 = AtomicI32::new(0);
+
+// Canais de quadros em memória compartilhada, por handle; mantidos aqui (e não só na
+// instância) para que o ponteiro entregue ao frontend continue válido até o fechamento explícito
+static SHARED_FRAME_CHANNELS: Mutex<BTreeMap<SimulationHandle, Arc<SharedFrameChannel>>> = Mutex::new(BTreeMap::new());
+
+// Estatísticas de conjunto acumuladas entre execuções
+static ENSEMBLE: Mutex<Option<EnsembleAccumulator>> = Mutex::new(None);
+
+// Projeto aberto (pasta com índice, cenários, materiais, execuções e relatórios)
+static PROJECT: Mutex<Option<ProjectBundle>> = Mutex::new(None);
+
+// Fila de tarefas em segundo plano (exportações)
+static JOB_QUEUE: JobQueue = JobQueue::new();
+
+// Estudos paramétricos enviados à fila de tarefas, por identificador da tarefa
+static PARAMETRIC_STUDIES: Mutex<Vec<QueuedStudy>> = Mutex::new(Vec::new());
+
+// Banco SQLite em que os casos dos estudos paramétricos são gravados
+#[cfg(feature = "sqlite")]
+static PARAMETRIC_STORE: Mutex<Option<Arc<Mutex<StudyStore>>>> = Mutex::new(None);
+
+// Biblioteca de materiais (pré-definidos e do usuário), criada no primeiro acesso
+static MATERIAL_LIBRARY: Mutex<Option<MaterialLibrary>> = Mutex::new(None);
+
+// Gerenciador de fórmulas (pré-definidas e do usuário), criado no primeiro acesso
+static FORMULA_MANAGER: Mutex<Option<FormulaManager>> = Mutex::new(None);
 
-// Armazenamento thread-local para a última mensagem de erro específica da FFI
+// Backend de armazenamento de resultados (diretório local ou object store S3)
+static RESULTS_STORAGE: Mutex<Option<Box<dyn ResultsStorage>>> = Mutex::new(None);
+
+// Relatório de integridade da última execução carregada do projeto
+static LAST_RUN_INTEGRITY: Mutex<Option<IntegrityReport>> = Mutex::new(None);
+
+// Armazenamento thread-local para o último erro específico da FFI e a função FFI em execução
 thread_local! {
-    static LAST_ERROR: RefCell<Option<String>> = RefCell::new(None);
+    static LAST_ERROR: RefCell<Option<FFIError>> = const { RefCell::new(None) };
+    static CURRENT_FFI_FUNCTION: Cell<&'static str> = const { Cell::new("") };
 }
 
-/// Helper function to set the thread-local FFI error message.
-fn set_last_ffi_error(err_msg: String) {
+/// Records `error` as the thread-local last FFI error.
+fn record_ffi_error(error: FFIError) {
     LAST_ERROR.with(|cell| {
-        *cell.borrow_mut() = Some(err_msg);
+        *cell.borrow_mut() = Some(error);
+    });
+}
+
+/// Records an error with the given code, attributed to the FFI function being executed.
+fn set_last_ffi_error_code(code: FFIErrorCode, err_msg: String) {
+    let function = CURRENT_FFI_FUNCTION.with(Cell::get);
+    record_ffi_error(FFIError::new(code, err_msg, function));
+}
+
+/// Helper function to set the thread-local FFI error message (code `OperationFailed`).
+fn set_last_ffi_error(err_msg: String) {
+    set_last_ffi_error_code(FFIErrorCode::OperationFailed, err_msg);
+}
+
+/// Return code of the error already recorded by a helper (`OperationFailed` if none was).
+fn recorded_error_code() -> c_int {
+    LAST_ERROR.with(|cell| cell.borrow().as_ref().map_or(FFIErrorCode::OperationFailed, |error| error.code)).return_code()
+}
+
+/// Records an unknown-handle error, with the handle as context.
+fn set_unknown_simulation_error(handle: SimulationHandle) {
+    let function = CURRENT_FFI_FUNCTION.with(Cell::get);
+    record_ffi_error(FFIError::new(FFIErrorCode::UnknownHandle, unknown_simulation_error(handle), function)
+        .with_context(serde_json::json!({ "handle": handle })));
+}
+
+/// Records a structured "request too large" error as the last FFI error (JSON message,
+/// with the same object as context).
+fn report_oversized_request(error: OversizedRequest) {
+    let function = CURRENT_FFI_FUNCTION.with(Cell::get);
+    let context = serde_json::to_value(&error).unwrap_or(serde_json::Value::Null);
+    record_ffi_error(FFIError::new(FFIErrorCode::RequestTooLarge, error.to_json(), function).with_context(context));
+}
+
+/// Error code returned by `c_int` FFI functions when a panic was caught.
+pub const FFI_PANIC_ERROR_CODE: c_int = FFIErrorCode::Panic.return_code();
+
+/// Error code returned by `c_int` FFI functions when the request would allocate beyond
+/// the memory cap (see `set_ffi_memory_cap`). `get_last_error` then returns a JSON
+/// object with `"error": "request_too_large"` and suggested alternatives.
+pub const FFI_REQUEST_TOO_LARGE_ERROR_CODE: c_int = FFIErrorCode::RequestTooLarge.return_code();
+
+/// Value returned by an FFI function when a panic is caught at the boundary.
+pub(crate) trait FfiPanicDefault {
+    fn panic_default() -> Self;
+}
+
+impl FfiPanicDefault for c_int {
+    fn panic_default() -> Self {
+        FFI_PANIC_ERROR_CODE
+    }
+}
+
+impl FfiPanicDefault for () {
+    fn panic_default() -> Self {}
+}
+
+impl FfiPanicDefault for u64 {
+    fn panic_default() -> Self {
+        0
+    }
+}
+
+impl FfiPanicDefault for i64 {
+    fn panic_default() -> Self {
+        FFI_PANIC_ERROR_CODE as i64
+    }
+}
+
+impl FfiPanicDefault for c_double {
+    fn panic_default() -> Self {
+        f64::NAN
+    }
+}
+
+impl<T> FfiPanicDefault for *mut T {
+    fn panic_default() -> Self {
+        ptr::null_mut()
+    }
+}
+
+impl<T> FfiPanicDefault for *const T {
+    fn panic_default() -> Self {
+        ptr::null()
+    }
+}
+
+impl FfiPanicDefault for FFIByteBuffer {
+    fn panic_default() -> Self {
+        empty_ffi_byte_buffer()
+    }
+}
+
+impl FfiPanicDefault for FFISharedFrameChannel {
+    fn panic_default() -> Self {
+        empty_shared_frame_channel()
+    }
+}
+
+/// Extracts a readable message from a panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Runs the body of an FFI entry point, translating panics into the last-error mechanism.
+/// A panic never unwinds into the caller: the error is recorded, the state mutexes of all
+/// simulation instances are un-poisoned so later calls keep working, and a failure value
+/// is returned.
+///
+/// Errors recorded by the body are attributed to `function_name` (the outermost guard
+/// when FFI functions call each other).
+pub(crate) fn ffi_guard<R: FfiPanicDefault>(function_name: &'static str, body: impl FnOnce() -> R) -> R {
+    let outer_function = CURRENT_FFI_FUNCTION.with(|current| {
+        let outer = current.get();
+        if outer.is_empty() {
+            current.set(function_name);
+        }
+        outer
     });
+    let result = panic::catch_unwind(AssertUnwindSafe(body));
+    if outer_function.is_empty() {
+        CURRENT_FFI_FUNCTION.with(|current| current.set(""));
+    }
+    match result {
+        Ok(value) => value,
+        Err(payload) => {
+            record_ffi_error(FFIError::new(
+                FFIErrorCode::Panic,
+                format!("Panic in {}: {}", function_name, panic_message(payload.as_ref())),
+                function_name,
+            ));
+            SIMULATIONS.clear_poison();
+            R::panic_default()
+        }
+    }
+}
+
+/// Error message for a simulation handle that is not registered. The invalid handle is
+/// what the deprecated single-instance functions pass when no default simulation exists.
+fn unknown_simulation_error(handle: SimulationHandle) -> String {
+    if handle == INVALID_SIMULATION_HANDLE {
+        "Simulation not initialized. Call initialize_simulation or create_simulation first.".to_string()
+    } else {
+        format!("Unknown simulation handle {} (never created or already destroyed).", handle)
+    }
 }
 
 // Função auxiliar para converter FFISimulationParameters para SimulationParameters
@@ -111,16 +347,29 @@ fn convert_ffi_parameters(ffi_params: &FFISimulationParameters) -> SimulationPar
 }
 
 // Função auxiliar para converter FFIPlasmaTorch para PlasmaTorch
-fn convert_ffi_torch(ffi_torch: &FFIPlasmaTorch) -> PlasmaTorch {
-    PlasmaTorch::new(
+fn convert_ffi_torch(ffi_torch: &FFIPlasmaTorch, id: &str) -> Result<PlasmaTorch, String> {
+    let mut torch = PlasmaTorch::new(
+        id,
         ffi_torch.r_position,
+        0.0,
         ffi_torch.z_position,
         ffi_torch.pitch,
         ffi_torch.yaw,
         ffi_torch.power,
         ffi_torch.gas_flow,
         ffi_torch.gas_temperature,
-    )
+    );
+
+    let length = ffi_torch.flux_profile_length;
+    let length_or_diameter = if length > 0.0 { length } else { torch.diameter };
+    torch.flux_profile = match ffi_torch.flux_profile {
+        0 => TorchFluxProfile::Gaussian { sigma: if length > 0.0 { Some(length) } else { None } },
+        1 => TorchFluxProfile::Exponential { decay_length: length_or_diameter },
+        2 => TorchFluxProfile::TopHat { radius: length_or_diameter },
+        other => return Err(format!("Unknown flux profile code: {} (expected 0, 1 or 2)", other)),
+    };
+
+    Ok(torch)
 }
 
 // Função auxiliar para converter FFIMaterialProperties para MaterialProperties
@@ -168,10 +417,11 @@ fn convert_simulation_state(state: &SimulationState) -> FFISimulationState {
         crate::simulation::SimulationStatus::Paused => 2,
         crate::simulation::SimulationStatus::Completed => 3,
         crate::simulation::SimulationStatus::Failed => 4,
+        crate::simulation::SimulationStatus::Cancelled => 5,
     };
     
     let error_message = match &state.error_message {
-        Some(msg) => CString::new(msg.clone()).unwrap().into_raw(),
+        Some(msg) => into_ffi_string_ptr(CString::new(msg.clone()).unwrap()),
         None => ptr::null(),
     };
     
@@ -189,6 +439,7 @@ fn convert_simulation_state(state: &SimulationState) -> FFISimulationState {
 pub struct FFIImportOptions {
     pub input_path: *const c_char,
     pub format: *const c_char, // e.g., "CSV", "JSON"
+    pub options_json: *const c_char, // CsvImportOptions as JSON; null uses the defaults
 }
 
 // Represents a vector of f64
@@ -198,7 +449,23 @@ pub struct FFIVector_f64 {
     pub len: usize,
 }
 
-// Represents a coordinate (example, adjust as needed)
+// Represents an owned byte buffer (serialized payloads)
+#[repr(C)]
+pub struct FFIByteBuffer {
+    pub ptr: *mut u8,
+    pub len: usize,
+}
+
+// Describes a shared-memory frame channel (region owned by Rust, read in place)
+#[repr(C)]
+pub struct FFISharedFrameChannel {
+    pub ptr: *const u8,
+    pub len: usize,
+    pub nr: usize,
+    pub nz: usize,
+}
+
+// Represents a coordinate (r, theta, z for reference data points)
 #[repr(C)]
 pub struct FFICoordinate {
    pub x: f64,
@@ -214,14 +481,14 @@ pub struct FFIVector_Coordinate {
 }
 
 
-// Represents a key-value pair for maps (simplistic example)
+// Represents a key-value pair for maps
 #[repr(C)]
 pub struct FFIStringPair {
     pub key: *const c_char,
     pub value: *const c_char,
 }
 
-// Represents a map of String -> String (simplistic example)
+// Represents a map of String -> String
 #[repr(C)]
 pub struct FFIMap_String_String {
     pub pairs: *const FFIStringPair,
@@ -230,16 +497,18 @@ pub struct FFIMap_String_String {
 
 
 // Represents ReferenceData for FFI
+// All fields are owned by the struct and released by free_reference_data.
 #[repr(C)]
 pub struct FFIReferenceData {
     pub name: *const c_char,
     pub description: *const c_char,
     pub source: *const c_char,
     pub data_type: *const c_char,
-    pub coordinates: FFIVector_Coordinate, // Needs careful handling
-    pub values: FFIVector_f64,        // Needs careful handling
-    pub uncertainties: FFIVector_f64, // Needs careful handling
-    pub metadata: FFIMap_String_String, // Needs careful handling
+    pub coordinates: FFIVector_Coordinate,
+    pub values: FFIVector_f64,
+    pub uncertainties: FFIVector_f64, // Null pointer when there are no uncertainties
+    pub times: FFIVector_f64, // Null pointer when the measurements have no times
+    pub metadata: FFIMap_String_String,
 }
 
 
@@ -258,38 +527,300 @@ pub struct FFIValidationMetrics {
 }
 
 // Represents ValidationResult for FFI
+// All fields, including the nested reference data, are released by free_validation_result.
 #[repr(C)]
 pub struct FFIValidationResult {
     pub name: *const c_char,
     pub description: *const c_char,
     pub reference_data: *mut FFIReferenceData, // Pointer to nested struct
     pub metrics: FFIValidationMetrics,
-    pub simulated_values: FFIVector_f64,    // Needs careful handling
-    pub metadata: FFIMap_String_String,     // Needs careful handling
+    pub simulated_values: FFIVector_f64, // One value per reference point
+    pub metadata: FFIMap_String_String,
 }
 
 // --- Helper functions for memory management ---
 
+// Live allocation counters for objects handed to the caller (checked by run_ffi_selftest)
+static LIVE_FFI_STRINGS: AtomicIsize = AtomicIsize::new(0);
+static LIVE_FFI_VECTORS: AtomicIsize = AtomicIsize::new(0);
+static LIVE_FFI_BUFFERS: AtomicIsize = AtomicIsize::new(0);
+
+// Helper to hand a CString to the caller (must be freed with free_rust_string)
+fn into_ffi_string_ptr(c_str: CString) -> *mut c_char {
+    LIVE_FFI_STRINGS.fetch_add(1, Ordering::Relaxed);
+    c_str.into_raw()
+}
+
 // Helper to convert Vec<f64> to FFIVector_f64 (allocates memory!)
 fn vec_to_ffi_vector_f64(vec: Vec<f64>) -> FFIVector_f64 {
-    let ptr = vec.as_ptr();
-    let len = vec.len();
-    mem::forget(vec); // Prevent Rust from freeing the memory
+    // Boxed slice guarantees capacity == len, required by free_ffi_vector_f64
+    let boxed = vec.into_boxed_slice();
+    let ptr = boxed.as_ptr();
+    let len = boxed.len();
+    mem::forget(boxed); // Prevent Rust from freeing the memory
+    LIVE_FFI_VECTORS.fetch_add(1, Ordering::Relaxed);
     FFIVector_f64 { ptr, len }
 }
 
 // Helper to free memory allocated for FFIVector_f64
 #[no_mangle]
 pub extern "C" fn free_ffi_vector_f64(vec: FFIVector_f64) {
-    unsafe {
-        if !vec.ptr.is_null() { // Add null check
-            let _ = Vec::from_raw_parts(vec.ptr as *mut f64, vec.len, vec.len);
+    ffi_guard("free_ffi_vector_f64", || {
+        unsafe {
+            if !vec.ptr.is_null() { // Add null check
+                let _ = Vec::from_raw_parts(vec.ptr as *mut f64, vec.len, vec.len);
+                LIVE_FFI_VECTORS.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    })
+}
+
+// Helper to convert Vec<u8> to FFIByteBuffer (allocates memory!)
+fn vec_to_ffi_byte_buffer(vec: Vec<u8>) -> FFIByteBuffer {
+    // Boxed slice guarantees capacity == len, required by free_ffi_byte_buffer
+    let mut boxed = vec.into_boxed_slice();
+    let ptr = boxed.as_mut_ptr();
+    let len = boxed.len();
+    mem::forget(boxed);
+    LIVE_FFI_BUFFERS.fetch_add(1, Ordering::Relaxed);
+    FFIByteBuffer { ptr, len }
+}
+
+// Empty buffer used to signal errors (check get_last_error)
+fn empty_ffi_byte_buffer() -> FFIByteBuffer {
+    FFIByteBuffer { ptr: ptr::null_mut(), len: 0 }
+}
+
+// Helper to free memory allocated for FFIByteBuffer
+#[no_mangle]
+pub extern "C" fn free_ffi_byte_buffer(buffer: FFIByteBuffer) {
+    ffi_guard("free_ffi_byte_buffer", || {
+        unsafe {
+            if !buffer.ptr.is_null() {
+                let _ = Vec::from_raw_parts(buffer.ptr, buffer.len, buffer.len);
+                LIVE_FFI_BUFFERS.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    })
+}
+
+// Helper to free a string previously handed out with into_ffi_string_ptr
+fn free_ffi_string_ptr(c_str: *const c_char) {
+    if !c_str.is_null() {
+        unsafe {
+            let _ = CString::from_raw(c_str as *mut c_char);
+        }
+        LIVE_FFI_STRINGS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// Helper to read a nullable string field of an FFI struct (null maps to an empty string)
+fn read_ffi_field_str(c_str: *const c_char, field: &str) -> Result<String, String> {
+    if c_str.is_null() {
+        return Ok(String::new());
+    }
+    unsafe { CStr::from_ptr(c_str) }.to_str()
+        .map(str::to_string)
+        .map_err(|e| format!("Invalid UTF-8 in {}: {}", field, e))
+}
+
+// Helper to convert a Rust string to a CString, naming the field on interior NUL bytes
+fn ffi_field_cstring(value: &str, field: &str) -> Result<CString, String> {
+    CString::new(value).map_err(|e| format!("Invalid {} for FFI: {}", field, e))
+}
+
+// Helper to convert coordinates (r, theta, z) to FFIVector_Coordinate (allocates memory!)
+fn vec_to_ffi_vector_coordinate(coordinates: &[(f64, f64, f64)]) -> FFIVector_Coordinate {
+    let boxed: Box<[FFICoordinate]> = coordinates.iter()
+        .map(|&(x, y, z)| FFICoordinate { x, y, z })
+        .collect();
+    let ptr = boxed.as_ptr();
+    let len = boxed.len();
+    mem::forget(boxed);
+    LIVE_FFI_VECTORS.fetch_add(1, Ordering::Relaxed);
+    FFIVector_Coordinate { ptr, len }
+}
+
+// Helper to free memory allocated for FFIVector_Coordinate
+fn free_ffi_vector_coordinate(vec: FFIVector_Coordinate) {
+    if !vec.ptr.is_null() {
+        unsafe {
+            let _ = Vec::from_raw_parts(vec.ptr as *mut FFICoordinate, vec.len, vec.len);
+        }
+        LIVE_FFI_VECTORS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// Helper to convert a String -> String map to FFIMap_String_String (allocates memory!)
+// Pairs are sorted by key so the layout is deterministic.
+fn map_to_ffi_map(map: &HashMap<String, String>) -> Result<FFIMap_String_String, String> {
+    let mut entries: Vec<(&String, &String)> = map.iter().collect();
+    entries.sort();
+    // Convert every string before allocating so a failure leaks nothing
+    let mut c_pairs = Vec::with_capacity(entries.len());
+    for (key, value) in entries {
+        c_pairs.push((ffi_field_cstring(key, "metadata key")?, ffi_field_cstring(value, "metadata value")?));
+    }
+    let boxed: Box<[FFIStringPair]> = c_pairs.into_iter()
+        .map(|(key, value)| FFIStringPair {
+            key: into_ffi_string_ptr(key),
+            value: into_ffi_string_ptr(value),
+        })
+        .collect();
+    let pairs = boxed.as_ptr();
+    let len = boxed.len();
+    mem::forget(boxed);
+    LIVE_FFI_VECTORS.fetch_add(1, Ordering::Relaxed);
+    Ok(FFIMap_String_String { pairs, len })
+}
+
+// Helper to free memory allocated for FFIMap_String_String (pairs and their strings)
+fn free_ffi_map(map: FFIMap_String_String) {
+    if !map.pairs.is_null() {
+        let pairs = unsafe { Vec::from_raw_parts(map.pairs as *mut FFIStringPair, map.len, map.len) };
+        for pair in pairs {
+            free_ffi_string_ptr(pair.key);
+            free_ffi_string_ptr(pair.value);
+        }
+        LIVE_FFI_VECTORS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// Helper to read an FFIMap_String_String back into a HashMap (does not take ownership)
+fn ffi_map_to_map(map: &FFIMap_String_String) -> Result<HashMap<String, String>, String> {
+    if map.pairs.is_null() {
+        return Ok(HashMap::new());
+    }
+    unsafe { slice::from_raw_parts(map.pairs, map.len) }.iter()
+        .map(|pair| Ok((read_ffi_field_str(pair.key, "metadata key")?, read_ffi_field_str(pair.value, "metadata value")?)))
+        .collect()
+}
+
+// Converts ReferenceData to a heap-allocated FFIReferenceData (must be freed with free_reference_data)
+fn reference_data_to_ffi(data: &ReferenceData) -> Result<*mut FFIReferenceData, String> {
+    if data.values.len() != data.coordinates.len() {
+        return Err(format!("Reference data has {} coordinates but {} values", data.coordinates.len(), data.values.len()));
+    }
+    if let Some(uncertainties) = &data.uncertainties {
+        if uncertainties.len() != data.values.len() {
+            return Err(format!("Reference data has {} values but {} uncertainties", data.values.len(), uncertainties.len()));
+        }
+    }
+    if let Some(times) = &data.times {
+        if times.len() != data.values.len() {
+            return Err(format!("Reference data has {} values but {} times", data.values.len(), times.len()));
         }
     }
+    let name = ffi_field_cstring(&data.name, "name")?;
+    let description = ffi_field_cstring(&data.description, "description")?;
+    let source = ffi_field_cstring(&data.source, "source")?;
+    let data_type = ffi_field_cstring(&data.data_type, "data_type")?;
+    let metadata = map_to_ffi_map(&data.metadata)?;
+
+    let ffi_data = FFIReferenceData {
+        name: into_ffi_string_ptr(name),
+        description: into_ffi_string_ptr(description),
+        source: into_ffi_string_ptr(source),
+        data_type: into_ffi_string_ptr(data_type),
+        coordinates: vec_to_ffi_vector_coordinate(&data.coordinates),
+        values: vec_to_ffi_vector_f64(data.values.clone()),
+        // Null vector when there are no uncertainties
+        uncertainties: match &data.uncertainties {
+            Some(uncertainties) => vec_to_ffi_vector_f64(uncertainties.clone()),
+            None => FFIVector_f64 { ptr: ptr::null(), len: 0 },
+        },
+        // Null vector when the measurements have no times
+        times: match &data.times {
+            Some(times) => vec_to_ffi_vector_f64(times.clone()),
+            None => FFIVector_f64 { ptr: ptr::null(), len: 0 },
+        },
+        metadata,
+    };
+    Ok(Box::into_raw(Box::new(ffi_data)))
+}
+
+// Converts ValidationMetrics to FFIValidationMetrics (region metrics are not exposed)
+fn validation_metrics_to_ffi(metrics: &ValidationMetrics) -> FFIValidationMetrics {
+    FFIValidationMetrics {
+        mean_absolute_error: metrics.mean_absolute_error,
+        mean_squared_error: metrics.mean_squared_error,
+        root_mean_squared_error: metrics.root_mean_squared_error,
+        mean_absolute_percentage_error: metrics.mean_absolute_percentage_error,
+        r_squared: metrics.r_squared,
+        max_absolute_error: metrics.max_absolute_error,
+        mean_error: metrics.mean_error,
+        normalized_rmse: metrics.normalized_rmse,
+    }
+}
+
+// Converts ValidationResult to a heap-allocated FFIValidationResult (must be freed with free_validation_result)
+fn validation_result_to_ffi(result: &ValidationResult) -> Result<*mut FFIValidationResult, String> {
+    if result.simulated_values.len() != result.reference_data.values.len() {
+        return Err(format!(
+            "Validation result has {} simulated values for {} reference values",
+            result.simulated_values.len(),
+            result.reference_data.values.len()
+        ));
+    }
+    let name = ffi_field_cstring(&result.name, "name")?;
+    let description = ffi_field_cstring(&result.description, "description")?;
+    let metadata = map_to_ffi_map(&result.metadata)?;
+    let reference_data = match reference_data_to_ffi(&result.reference_data) {
+        Ok(reference_data) => reference_data,
+        Err(e) => {
+            free_ffi_map(metadata);
+            return Err(format!("reference data: {}", e));
+        }
+    };
+
+    let ffi_result = FFIValidationResult {
+        name: into_ffi_string_ptr(name),
+        description: into_ffi_string_ptr(description),
+        reference_data,
+        metrics: validation_metrics_to_ffi(&result.metrics),
+        simulated_values: vec_to_ffi_vector_f64(result.simulated_values.clone()),
+        metadata,
+    };
+    Ok(Box::into_raw(Box::new(ffi_result)))
 }
 
-// Similar helpers needed for FFIVector_Coordinate, FFIStringPair, FFIMap_String_String
-// ... these are non-trivial to implement correctly for FFI ...
+// Converts FFIReferenceData (owned by either side) back to ReferenceData without taking ownership
+fn ffi_to_reference_data(data: &FFIReferenceData) -> Result<ReferenceData, String> {
+    let read_vector = |vec: &FFIVector_f64| -> Vec<f64> {
+        if vec.ptr.is_null() { Vec::new() } else { unsafe { slice::from_raw_parts(vec.ptr, vec.len) }.to_vec() }
+    };
+    let coordinates = if data.coordinates.ptr.is_null() {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(data.coordinates.ptr, data.coordinates.len) }.iter()
+            .map(|c| (c.x, c.y, c.z))
+            .collect()
+    };
+    let values = read_vector(&data.values);
+    if values.len() != coordinates.len() {
+        return Err(format!("Reference data has {} coordinates but {} values", coordinates.len(), values.len()));
+    }
+    let uncertainties = if data.uncertainties.ptr.is_null() { None } else { Some(read_vector(&data.uncertainties)) };
+    if uncertainties.as_ref().is_some_and(|u| u.len() != values.len()) {
+        return Err("Reference data uncertainties must match the number of values".to_string());
+    }
+    let times = if data.times.ptr.is_null() { None } else { Some(read_vector(&data.times)) };
+    if times.as_ref().is_some_and(|t| t.len() != values.len()) {
+        return Err("Reference data times must match the number of values".to_string());
+    }
+
+    Ok(ReferenceData {
+        name: read_ffi_field_str(data.name, "name")?,
+        description: read_ffi_field_str(data.description, "description")?,
+        source: read_ffi_field_str(data.source, "source")?,
+        data_type: read_ffi_field_str(data.data_type, "data_type")?,
+        coordinates,
+        values,
+        uncertainties,
+        times,
+        metadata: ffi_map_to_map(&data.metadata)?,
+    })
+}
 
 
 // --- FFI Functions for Validation ---
@@ -317,204 +848,224 @@ fn convert_ffi_import_options(ffi_options: *const FFIImportOptions) -> Result<va
             .to_string()
     };
 
-    Ok(validation::ImportOptions { input_path, format })
+    // Sem opções, CSV com cabeçalho e as colunas r, z e value, em m e °C
+    let csv = if options.options_json.is_null() {
+        validation::CsvImportOptions::default()
+    } else {
+        let json = unsafe { CStr::from_ptr(options.options_json) }.to_str()
+            .map_err(|e| format!("Invalid UTF-8 in options_json: {}", e))?;
+        serde_json::from_str(json).map_err(|e| format!("Invalid CSV import options JSON: {}", e))?
+    };
+
+    Ok(validation::ImportOptions {
+        format: validation::ImportFormat::from_name(&format)?,
+        input_path,
+        csv,
+    })
 }
 
 
 #[no_mangle]
 pub extern "C" fn import_reference_data(options: *const FFIImportOptions) -> *mut FFIReferenceData {
-    let import_options = match convert_ffi_import_options(options) {
-        Ok(opts) => opts,
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid import options: {}", e));
-            return ptr::null_mut();
-        }
-    };
+    ffi_guard("import_reference_data", || {
+        let import_options = match convert_ffi_import_options(options) {
+            Ok(opts) => opts,
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid import options: {}", e));
+                return ptr::null_mut();
+            }
+        };
 
-    match validation::import_data(import_options) {
-        Ok(ref_data) => {
-            // TODO: Convert ref_data (Rust ReferenceData) to FFIReferenceData
-            // This requires allocating memory for strings, vectors, etc.
-            // and returning a Box::into_raw pointer.
-             println!("RUST: import_reference_data succeeded, but FFI conversion TODO.");
-             set_last_ffi_error("FFI conversion for ReferenceData not implemented".to_string());
-             ptr::null_mut() // Return null until conversion is implemented
-        }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to import reference data: {}", e));
-            ptr::null_mut()
+        match validation::import_data(&import_options) {
+            Ok(ref_data) => reference_data_to_ffi(&ref_data).unwrap_or_else(|e| {
+                set_last_ffi_error(format!("Failed to convert reference data: {}", e));
+                ptr::null_mut()
+            }),
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to import reference data: {}", e));
+                ptr::null_mut()
+            }
         }
-    }
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn free_reference_data(data: *mut FFIReferenceData) {
-    if !data.is_null() {
-        unsafe {
-             // TODO: Free all allocated memory within FFIReferenceData
-             // - Free strings (CString::from_raw)
-             // - Free vectors (using helpers like free_ffi_vector_f64)
-             // - Free maps
-             println!("RUST: free_reference_data called (STUB - Potential memory leaks!)");
-            // Placeholder: Free only the top-level struct for now
-            // Proper implementation MUST free nested allocated data (strings, vectors, maps)
-            // For example, if name was allocated with CString::into_raw:
-            // if !(*data).name.is_null() { let _ = CString::from_raw((*data).name as *mut c_char); }
-            // free_ffi_vector_f64((*data).values); // Assuming a helper exists and was used
-
-            let _ = Box::from_raw(data); // Free the main struct allocated with Box::into_raw
+    ffi_guard("free_reference_data", || {
+        if !data.is_null() {
+            // Safety: `data` must come from reference_data_to_ffi and be freed only once
+            let data = unsafe { Box::from_raw(data) };
+            free_ffi_string_ptr(data.name);
+            free_ffi_string_ptr(data.description);
+            free_ffi_string_ptr(data.source);
+            free_ffi_string_ptr(data.data_type);
+            free_ffi_vector_coordinate(data.coordinates);
+            free_ffi_vector_f64(data.values);
+            free_ffi_vector_f64(data.uncertainties);
+            free_ffi_vector_f64(data.times);
+            free_ffi_map(data.metadata);
         }
-    }
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn create_synthetic_reference_data(num_points: c_int, error_level: c_double) -> *mut FFIReferenceData {
-    if num_points <= 0 {
-         set_last_ffi_error("Number of points must be positive".to_string());
-         return ptr::null_mut();
-    }
-
-     match validation::create_synthetic(num_points as usize, error_level) {
-        Ok(ref_data) => {
-            // TODO: Convert ref_data (Rust ReferenceData) to FFIReferenceData
-             println!("RUST: create_synthetic_reference_data succeeded, but FFI conversion TODO.");
-             set_last_ffi_error("FFI conversion for ReferenceData not implemented".to_string());
-             ptr::null_mut()
-        }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to create synthetic reference data: {}", e));
-            ptr::null_mut()
+    ffi_guard("create_synthetic_reference_data", || {
+        if num_points <= 0 {
+             set_last_ffi_error_code(FFIErrorCode::InvalidArgument, "Number of points must be positive".to_string());
+             return ptr::null_mut();
         }
-     }
-}
 
-
-#[no_mangle]
-pub extern "C" fn validate_model(name: *const c_char, description: *const c_char) -> *mut FFIValidationResult {
-     let name_str = if name.is_null() {
-         "DefaultValidation".to_string()
-     } else {
-         match unsafe { CStr::from_ptr(name).to_str() } {
-             Ok(s) => s.to_string(),
-             Err(e) => {
-                 set_last_ffi_error(format!("Invalid UTF-8 in name string: {}", e));
+         let Some(shared) = SIMULATIONS.get(SIMULATIONS.default_handle()) else {
+             set_unknown_simulation_error(SIMULATIONS.default_handle());
+             return ptr::null_mut();
+         };
+         let parameters = match shared.state.lock() {
+             Ok(state) => state.parameters.clone(),
+             Err(poison_err) => {
+                 set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while reading parameters: {}", poison_err));
                  return ptr::null_mut();
              }
+         };
+
+         match validation::create_synthetic(&parameters, num_points as usize, error_level) {
+            Ok(ref_data) => reference_data_to_ffi(&ref_data).unwrap_or_else(|e| {
+                set_last_ffi_error(format!("Failed to convert reference data: {}", e));
+                ptr::null_mut()
+            }),
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to create synthetic reference data: {}", e));
+                ptr::null_mut()
+            }
          }
-     };
-     let description_str = if description.is_null() {
-         String::new()
-     } else {
-         match unsafe { CStr::from_ptr(description).to_str() } {
-             Ok(s) => s.to_string(),
-             Err(e) => {
-                 set_last_ffi_error(format!("Invalid UTF-8 in description string: {}", e));
-                 return ptr::null_mut();
+    })
+}
+
+/// Validates the results of simulation `handle` against the reference data stored in the
+/// open project under the id `name` (also the name of the validation result).
+/// Returns null if no project is open, the reference data is missing or the simulation
+/// has no results. Free the result with `free_validation_result`.
+#[no_mangle]
+pub extern "C" fn validate_model_h(handle: SimulationHandle, name: *const c_char, description: *const c_char) -> *mut FFIValidationResult {
+    ffi_guard("validate_model_h", || {
+         let name_str = if name.is_null() {
+             "DefaultValidation".to_string()
+         } else {
+             match unsafe { CStr::from_ptr(name).to_str() } {
+                 Ok(s) => s.to_string(),
+                 Err(e) => {
+                     set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in name string: {}", e));
+                     return ptr::null_mut();
+                 }
              }
-         }
-     };
-
-    // TODO: Need a way to get the required ReferenceData.
-    // This might involve loading it based on the `name_str` or having it
-    // passed differently. For now, we assume it's unavailable.
-    let reference_data: Option<ReferenceData> = None; // Placeholder
-    if reference_data.is_none() {
-         set_last_ffi_error("Reference data for validation not available or loaded.".to_string());
-         return ptr::null_mut();
-    }
-    let ref_data = reference_data.unwrap();
-
-     // Access simulation results
-     unsafe {
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized.".to_string());
+         };
+         let description_str = if description.is_null() {
+             String::new()
+         } else {
+             match unsafe { CStr::from_ptr(description).to_str() } {
+                 Ok(s) => s.to_string(),
+                 Err(e) => {
+                     set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in description string: {}", e));
+                     return ptr::null_mut();
+                 }
+             }
+         };
+
+        // Reference data stored in the open project under the validation name
+        let Some(ref_data) = with_open_project(None, |project| project.reference_data(&name_str).map(Some)) else {
             return ptr::null_mut();
-        }
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
+        };
+
+         // Access simulation results
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return ptr::null_mut();
+        };
+        let state_lock = shared.state.lock();
+        match state_lock {
             Ok(state) => {
                 if let Some(results) = &state.results {
                     // Call backend validation logic
                      match validation::validate(results, &ref_data, name_str, description_str) {
-                         Ok(val_result) => {
-                            // TODO: Convert val_result (Rust ValidationResult) to FFIValidationResult
-                             println!("RUST: validate_model succeeded, but FFI conversion TODO.");
-                             set_last_ffi_error("FFI conversion for ValidationResult not implemented".to_string());
+                         Ok(val_result) => validation_result_to_ffi(&val_result).unwrap_or_else(|e| {
+                             set_last_ffi_error(format!("Failed to convert validation result: {}", e));
                              ptr::null_mut()
-                         }
+                         }),
                          Err(e) => {
                              set_last_ffi_error(format!("Validation failed: {}", e));
                              ptr::null_mut()
                          }
                      }
                 } else {
-                    set_last_ffi_error("Simulation results not available for validation.".to_string());
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available for validation.".to_string());
                     ptr::null_mut()
                 }
             }
             Err(poison_err) => {
-                set_last_ffi_error(format!("Mutex poisoned while validating model: {}", poison_err));
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while validating model: {}", poison_err));
                 ptr::null_mut()
             }
         }
-     }
+    })
+}
+
+/// Deprecated single-instance form of `validate_model_h`, operating on the default simulation.
+#[deprecated(note = "use `validate_model_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn validate_model(name: *const c_char, description: *const c_char) -> *mut FFIValidationResult {
+    validate_model_h(SIMULATIONS.default_handle(), name, description)
 }
 
 
 #[no_mangle]
 pub extern "C" fn free_validation_result(result: *mut FFIValidationResult) {
-    if !result.is_null() {
-        unsafe {
-             // TODO: Free all allocated memory within FFIValidationResult
-             // - Free strings
-             // - Free nested FFIReferenceData (call free_reference_data)
-             // - Free vectors/maps in result and metrics
-             println!("RUST: free_validation_result called (STUB - Potential memory leaks!)");
-             // Placeholder: Free only the top-level struct for now
-             // Proper implementation MUST free nested allocated data
-              if !(*result).reference_data.is_null() {
-                 // This assumes reference_data was allocated via Box::into_raw
-                 // AND that free_reference_data correctly frees its internals.
-                 free_reference_data((*result).reference_data);
-             }
-             // Free other fields like strings, vectors, maps...
-
-             let _ = Box::from_raw(result);
+    ffi_guard("free_validation_result", || {
+        if !result.is_null() {
+            // Safety: `result` must come from validation_result_to_ffi and be freed only once
+            let result = unsafe { Box::from_raw(result) };
+            free_ffi_string_ptr(result.name);
+            free_ffi_string_ptr(result.description);
+            free_reference_data(result.reference_data);
+            free_ffi_vector_f64(result.simulated_values);
+            free_ffi_map(result.metadata);
         }
-    }
+    })
 }
 
 
 #[no_mangle]
 pub extern "C" fn generate_validation_report(output_path: *const c_char) -> c_int {
-    if output_path.is_null() {
-        set_last_ffi_error("generate_validation_report: output_path pointer was null".to_string());
-        return -1;
-    }
-
-    let path_str = match unsafe { CStr::from_ptr(output_path).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in output_path string: {}", e));
-            return -2; // Invalid input error
+    ffi_guard("generate_validation_report", || {
+        if output_path.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "generate_validation_report: output_path pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
         }
-     };
-
-    // TODO: Need a way to access the ValidationResult to generate report from.
-    // Assume it's stored globally/statefully for now.
-    let validation_result: Option<ValidationResult> = None; // Placeholder
 
-    if let Some(val_res) = validation_result {
-         match reporting::generate_validation_report(&val_res, path_str) {
-             Ok(_) => 0, // Success
-             Err(e) => {
-                 set_last_ffi_error(format!("Failed to generate validation report: {}", e));
-                 -3 // Report generation error
+        let path_str = match unsafe { CStr::from_ptr(output_path).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in output_path string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+         };
+
+        // TODO: Need a way to access the ValidationResult to generate report from.
+        // Assume it's stored globally/statefully for now.
+        let validation_result: Option<ValidationResult> = None; // Placeholder
+
+        if let Some(val_res) = validation_result {
+             match validation::write_validation_report(&val_res, &path_str) {
+                 Ok(_) => 0, // Success
+                 Err(e) => {
+                     set_last_ffi_error(format!("Failed to generate validation report: {}", e));
+                     FFIErrorCode::OperationFailed.return_code()
+                 }
              }
-         }
-    } else {
-        set_last_ffi_error("Validation result not available for report generation.".to_string());
-        -4 // Result not ready
-    }
+        } else {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Validation result not available for report generation.".to_string());
+            FFIErrorCode::NotAvailable.return_code()
+        }
+    })
 }
 
 // --- FFI Functions for Formulas (JSON based) ---
@@ -523,484 +1074,2619 @@ pub extern "C" fn generate_validation_report(output_path: *const c_char) -> c_in
 /// Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
 pub extern "C" fn get_all_formulas_json() -> *mut c_char {
-    match formulas::get_all_formulas() {
-        Ok(formulas) => {
-            match serde_json::to_string(&formulas) {
-                Ok(json_string) => {
-                    // Convert to CString and return raw pointer
-                    CString::new(json_string).map_or_else(|e| {
-                        set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
-                        ptr::null_mut()
-                    }, |c_str| c_str.into_raw())
-                }
-                Err(e) => {
-                    // Failed to serialize
-                    set_last_ffi_error(format!("Failed to serialize formulas to JSON: {}", e));
-                    ptr::null_mut()
-                }
-            }
-        }
-        Err(e) => {
-            // Failed to get formulas from the backend
-            set_last_ffi_error(format!("Failed to get formulas: {}", e));
-            ptr::null_mut()
-        }
-    }
+    ffi_guard("get_all_formulas_json", || {
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let formulas: Vec<serde_json::Value> = manager.get_engine().get_all_formulas().iter()
+                .map(|(id, formula)| formula_json_with_id(id, formula))
+                .collect::<Result<_, _>>()?;
+            Ok(json_ffi_string(&formulas))
+        })
+    })
 }
 
 /// Returns formulas for a given category as a JSON string.
 /// Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
 pub extern "C" fn get_formulas_by_category_json(category: *const c_char) -> *mut c_char {
-    if category.is_null() {
-        // Set error? Might be unnecessary if Dart checks.
-        return ptr::null_mut();
-    }
-    let category_str = match unsafe { CStr::from_ptr(category).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in category string: {}", e));
-            return ptr::null_mut();
+    ffi_guard("get_formulas_by_category_json", || {
+        let category = match read_ffi_str(category, "get_formulas_by_category_json", "category") {
+            Ok(category) => category,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        let category: FormulaCategory = match serde_json::from_value(serde_json::Value::String(category.clone())) {
+            Ok(category) => category,
+            Err(_) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Unknown formula category: {}", category));
+                return ptr::null_mut();
+            }
+        };
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let formulas: Vec<serde_json::Value> = manager.get_engine().get_formulas_by_category(category).iter()
+                .map(|(id, formula)| formula_json_with_id(id, formula))
+                .collect::<Result<_, _>>()?;
+            Ok(json_ffi_string(&formulas))
+        })
+    })
+}
+
+/// Returns a single formula by ID as a JSON string.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_formula_json(id: *const c_char) -> *mut c_char {
+    ffi_guard("get_formula_json", || {
+        let id = match read_ffi_str(id, "get_formula_json", "id") {
+            Ok(id) => id,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_formula_manager(ptr::null_mut(), |manager| {
+            // Fórmula inexistente: null, sem erro
+            match manager.get_engine().get_formula(&id) {
+                Some(formula) => Ok(json_ffi_string(&formula_json_with_id(&id, formula)?)),
+                None => Ok(ptr::null_mut()),
+            }
+        })
+    })
+}
+
+/// Saves a formula provided as a JSON string. Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn save_formula_json(formula_json: *const c_char) -> c_int {
+    ffi_guard("save_formula_json", || {
+        if formula_json.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "save_formula_json: formula_json pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
         }
-    };
 
-    match formulas::get_by_category(category_str) {
-        Ok(formulas) => {
-            match serde_json::to_string(&formulas) {
+        let json_str = match unsafe { CStr::from_ptr(formula_json).to_str() } {
+            Ok(s) => s,
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in formula JSON string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+        };
+
+        // O objeto traz o ID junto aos campos da fórmula
+        let (id, formula) = match formula_from_json_with_id(json_str) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to deserialize formula JSON: {}", e));
+                return FFIErrorCode::OperationFailed.return_code();
+            }
+        };
+
+        with_formula_manager(None, |manager| {
+            manager.get_engine_mut().add_formula(&id, formula)
+                .map(|_| Some(0))
+                .map_err(|e| format!("Failed to save formula: {}", e))
+        })
+        .unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Deletes a formula by ID. Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn delete_formula_json(id: *const c_char) -> c_int {
+    ffi_guard("delete_formula_json", || {
+        if id.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "delete_formula_json: id pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
+
+        let id_str = match unsafe { CStr::from_ptr(id).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in id string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+        };
+
+        with_formula_manager(None, |manager| {
+            if manager.get_engine_mut().remove_formula(&id_str) {
+                Ok(Some(0))
+            } else {
+                Err(format!("Failed to delete formula: formula not found: {}", id_str))
+            }
+        })
+        .unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Validates a formula source string with given parameters (as JSON).
+/// Returns validation result as a JSON string.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn validate_formula_json(source_json: *const c_char, params_json: *const c_char) -> *mut c_char {
+    ffi_guard("validate_formula_json", || {
+         if source_json.is_null() {
+             set_last_ffi_error_code(FFIErrorCode::NullPointer, "validate_formula_json: source_json pointer was null".to_string());
+             return ptr::null_mut();
+         }
+         if params_json.is_null() {
+             set_last_ffi_error_code(FFIErrorCode::NullPointer, "validate_formula_json: params_json pointer was null".to_string());
+             return ptr::null_mut();
+         }
+
+         let source_str = match unsafe { CStr::from_ptr(source_json).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in source_json string: {}", e));
+                return ptr::null_mut();
+            }
+         };
+         let parameters: Vec<FormulaParameter> = match unsafe { CStr::from_ptr(params_json).to_str() }
+             .map_err(|e| format!("Invalid UTF-8 in params_json string: {}", e))
+             .and_then(|json| serde_json::from_str(json).map_err(|e| format!("Failed to parse formula parameters JSON: {}", e)))
+         {
+             Ok(parameters) => parameters,
+             Err(e) => {
+                 set_last_ffi_error_code(FFIErrorCode::InvalidArgument, e);
+                 return ptr::null_mut();
+             }
+         };
+
+        // Resultado da validação: {isValid, error, logs}
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let error = manager.get_engine().validate_formula(&source_str, &parameters).err();
+            Ok(json_ffi_string(&serde_json::json!({
+                "isValid": error.is_none(),
+                "error": error,
+                "logs": Vec::<String>::new(),
+            })))
+        })
+    })
+}
+
+/// Evaluates a formula by ID with given parameters (JSON object of name → value).
+/// Returns `{value, execution_time_us, logs, error}` as a JSON string, where `value` is plain JSON:
+/// a number for scalar formulas, a list for profiles and a list of rows for tables.
+/// When evaluation fails (unknown formula, operation/recursion/time limit, runtime error),
+/// `value` is null and `error` is `{kind, ...}`; otherwise `error` is null.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn evaluate_formula_json(id: *const c_char, params_json: *const c_char) -> *mut c_char {
+    ffi_guard("evaluate_formula_json", || {
+        let arguments = read_ffi_str(id, "evaluate_formula_json", "id")
+            .and_then(|id| read_ffi_str(params_json, "evaluate_formula_json", "params_json").map(|json| (id, json)))
+            .and_then(|(id, json)| {
+                ParameterValue::parameters_from_json(&json)
+                    .map(|params| (id, params))
+                    .map_err(FFICallError::invalid)
+            });
+        let (id, params) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let result = manager.get_engine().evaluate(&id, &params);
+            Ok(json_ffi_string(&result.to_json()))
+        })
+    })
+}
+
+/// Registers (or replaces) lookup table `id` from a `LookupTable` JSON object
+/// (`{name, x: {parameter, unit, values}, y?, values, result_unit, method}`), usable with
+/// function type "lookup_table". Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn set_lookup_table_json(id: *const c_char, table_json: *const c_char) -> c_int {
+    ffi_guard("set_lookup_table_json", || {
+        let arguments = read_ffi_str(id, "set_lookup_table_json", "id")
+            .and_then(|id| read_ffi_str(table_json, "set_lookup_table_json", "table_json").map(|json| (id, json)));
+        let (id, json) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => return e.record(),
+        };
+        with_formula_manager(None, |manager| manager.add_table_json(&id, &json).map(|_| Some(0)))
+            .unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Returns lookup table `id` as a JSON string, or null if it does not exist.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_lookup_table_json(id: *const c_char) -> *mut c_char {
+    ffi_guard("get_lookup_table_json", || {
+        let id = match read_ffi_str(id, "get_lookup_table_json", "id") {
+            Ok(id) => id,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let table = manager.get_table(&id).ok_or_else(|| format!("Lookup table not found: {}", id))?;
+            Ok(json_ffi_string(table))
+        })
+    })
+}
+
+/// Runs the test cases attached to formula `id` (inputs, expected value and tolerance).
+/// Returns `{passed, failed, cases}` as a JSON string, where each case is
+/// `{name, expected, actual, passed, error}`. Caller must free the returned string using
+/// `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn run_formula_tests_json(id: *const c_char) -> *mut c_char {
+    ffi_guard("run_formula_tests_json", || {
+        let id = match read_ffi_str(id, "run_formula_tests_json", "id") {
+            Ok(id) => id,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let cases = manager.get_engine().run_formula_tests(&id)?;
+            let passed = cases.iter().filter(|case| case.passed).count();
+            Ok(json_ffi_string(&serde_json::json!({
+                "passed": passed,
+                "failed": cases.len() - passed,
+                "cases": cases,
+            })))
+        })
+    })
+}
+
+/// Differentiates formula `id` symbolically with respect to parameter `parameter` and
+/// registers the result as formula `<id>_d_<parameter>`, so it can be evaluated with
+/// `evaluate_formula_json`. Returns `{id, formula}` as a JSON string.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn differentiate_formula_json(id: *const c_char, parameter: *const c_char) -> *mut c_char {
+    ffi_guard("differentiate_formula_json", || {
+        let arguments = read_ffi_str(id, "differentiate_formula_json", "id")
+            .and_then(|id| read_ffi_str(parameter, "differentiate_formula_json", "parameter").map(|p| (id, p)));
+        let (id, parameter) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let derivative = manager.get_engine().differentiate(&id, &parameter)
+                .map_err(|e| format!("Failed to differentiate formula: {}", e))?;
+            let derivative_id = format!("{}_d_{}", id, parameter);
+            manager.get_engine_mut().add_formula(&derivative_id, derivative.clone())?;
+            Ok(json_ffi_string(&serde_json::json!({
+                "id": derivative_id,
+                "formula": derivative,
+            })))
+        })
+    })
+}
+
+/// Exports formulas and their function associations to a bundle file at `path`.
+/// `ids_json` is a JSON array of formula IDs; an empty array exports every formula.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn export_formulas_json(path: *const c_char, ids_json: *const c_char) -> c_int {
+    ffi_guard("export_formulas_json", || {
+        let arguments = read_ffi_str(path, "export_formulas_json", "path")
+            .and_then(|path| read_ffi_str(ids_json, "export_formulas_json", "ids_json").map(|json| (path, json)))
+            .and_then(|(path, json)| {
+                serde_json::from_str::<Vec<String>>(&json)
+                    .map(|ids| (path, ids))
+                    .map_err(|e| FFICallError::invalid(format!("Failed to parse formula IDs JSON: {}", e)))
+            });
+        let (path, ids) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => return e.record(),
+        };
+        with_formula_manager(None, |manager| {
+            manager.export_formulas_json(std::path::Path::new(&path), &ids).map(|_| Some(0))
+        }).unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Imports formulas and their function associations from the bundle file at `path`.
+/// `conflict_policy` is one of "skip", "overwrite", "rename" or "fail" and applies to IDs
+/// that already exist. Returns `{imported, skipped, renamed, mappings}` as a JSON string,
+/// or null on error (the formula manager is left unchanged).
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn import_formulas_json(path: *const c_char, conflict_policy: *const c_char) -> *mut c_char {
+    ffi_guard("import_formulas_json", || {
+        let arguments = read_ffi_str(path, "import_formulas_json", "path")
+            .and_then(|path| read_ffi_str(conflict_policy, "import_formulas_json", "conflict_policy").map(|p| (path, p)))
+            .and_then(|(path, policy)| {
+                FormulaConflictPolicy::from_string(&policy)
+                    .map(|policy| (path, policy))
+                    .ok_or_else(|| FFICallError::invalid(format!("Unknown conflict policy: {}", policy)))
+            });
+        let (path, policy) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let summary = manager.import_formulas_json(std::path::Path::new(&path), policy)?;
+            Ok(json_ffi_string(&summary))
+        })
+    })
+}
+
+/// Sets the formula (by ID) to be used for a specific function type (e.g., "conductivity").
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn set_formula_for_function_json(function_type: *const c_char, formula_id: *const c_char) -> c_int {
+    ffi_guard("set_formula_for_function_json", || {
+        if function_type.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "set_formula_for_function_json: function_type pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
+        if formula_id.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "set_formula_for_function_json: formula_id pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
+
+        let type_str = match unsafe { CStr::from_ptr(function_type).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in function_type string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+         };
+        let id_str = match unsafe { CStr::from_ptr(formula_id).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in formula_id string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+         };
+
+         with_formula_manager(None, |manager| {
+             let function_type = FunctionType::from_string(&type_str)
+                 .ok_or_else(|| format!("Failed to set formula association: unknown function type: {}", type_str))?;
+             manager.set_formula_for_function(function_type, &id_str)
+                 .map(|_| Some(0))
+                 .map_err(|e| format!("Failed to set formula association: {}", e))
+         })
+         .unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Gets the ID of the formula associated with a function type.
+/// Returns the formula ID as a string, or null if none is set or on error.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_formula_for_function_json(function_type: *const c_char) -> *mut c_char {
+    ffi_guard("get_formula_for_function_json", || {
+         if function_type.is_null() {
+             return ptr::null_mut();
+         }
+
+         let type_str = match unsafe { CStr::from_ptr(function_type).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in function_type string: {}", e));
+                return ptr::null_mut();
+            }
+         };
+
+        with_formula_manager(ptr::null_mut(), |manager| {
+            let function_type = FunctionType::from_string(&type_str)
+                .ok_or_else(|| format!("Failed to get formula association: unknown function type: {}", type_str))?;
+            // Sem associação: null, sem erro
+            match manager.get_formula_for_function(function_type) {
+                Some(formula_id) => CString::new(formula_id)
+                    .map(into_ffi_string_ptr)
+                    .map_err(|e| format!("Failed to create CString for formula ID: {}", e)),
+                None => Ok(ptr::null_mut()),
+            }
+        })
+    })
+}
+
+// --- FFI Functions for Project Bundles ---
+
+/// Error of an FFI call before it is recorded: an argument that cannot be read (null
+/// pointer, invalid UTF-8, invalid value) or a failed operation, with its code.
+struct FFICallError {
+    code: FFIErrorCode,
+    message: String,
+}
+
+impl FFICallError {
+    /// Invalid argument value (unparsable JSON, unknown name, etc.)
+    fn invalid(message: String) -> Self {
+        Self { code: FFIErrorCode::InvalidArgument, message }
+    }
+
+    /// Failed operation (I/O, serialization, etc.)
+    fn failed(message: String) -> Self {
+        Self { code: FFIErrorCode::OperationFailed, message }
+    }
+
+    /// Prefixes the message, keeping the code.
+    fn prefixed(self, prefix: &str) -> Self {
+        Self { message: format!("{}: {}", prefix, self.message), ..self }
+    }
+
+    /// Records the error as the last FFI error and returns its `c_int` code.
+    fn record(self) -> c_int {
+        set_last_ffi_error_code(self.code, self.message);
+        self.code.return_code()
+    }
+}
+
+impl From<FFICallError> for String {
+    fn from(error: FFICallError) -> Self {
+        error.message
+    }
+}
+
+/// Reads a UTF-8 C string argument, reporting null pointers and invalid UTF-8.
+fn read_ffi_str(value: *const c_char, function_name: &str, argument: &str) -> Result<String, FFICallError> {
+    if value.is_null() {
+        return Err(FFICallError {
+            code: FFIErrorCode::NullPointer,
+            message: format!("{}: {} pointer was null", function_name, argument),
+        });
+    }
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map(|s| s.to_string())
+        .map_err(|e| FFICallError {
+            code: FFIErrorCode::InvalidUtf8,
+            message: format!("Invalid UTF-8 in {} string: {}", argument, e),
+        })
+}
+
+/// Serializes a value to a JSON C string owned by the caller (null on error).
+fn json_ffi_string<T: serde::Serialize>(value: &T) -> *mut c_char {
+    match serde_json::to_string(value) {
+        Ok(json_string) => CString::new(json_string).map_or_else(|e| {
+            set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
+            ptr::null_mut()
+        }, into_ffi_string_ptr),
+        Err(e) => {
+            set_last_ffi_error(format!("Failed to serialize value to JSON: {}", e));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Runs `body` with the open project, reporting a missing project or poisoned lock.
+fn with_open_project<R>(on_error: R, body: impl FnOnce(&mut ProjectBundle) -> Result<R, String>) -> R {
+    match PROJECT.lock() {
+        Ok(mut project) => match project.as_mut() {
+            Some(bundle) => body(bundle).unwrap_or_else(|e| {
+                set_last_ffi_error(e);
+                on_error
+            }),
+            None => {
+                set_last_ffi_error_code(FFIErrorCode::NotAvailable, "No project is open.".to_string());
+                on_error
+            }
+        },
+        Err(poison_err) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while accessing project: {}", poison_err));
+            on_error
+        }
+    }
+}
+
+/// Creates a new project folder at `path` (see `simulation::project` for the layout)
+/// and makes it the open project. Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn create_project(path: *const c_char, name: *const c_char) -> c_int {
+    ffi_guard("create_project", || {
+        let opened = read_ffi_str(path, "create_project", "path")
+            .and_then(|path| read_ffi_str(name, "create_project", "name").map(|name| (path, name)))
+            .and_then(|(path, name)| ProjectBundle::create(std::path::Path::new(&path), &name).map_err(FFICallError::failed));
+        match (opened, PROJECT.lock()) {
+            (Ok(bundle), Ok(mut project)) => {
+                *project = Some(bundle);
+                0
+            }
+            (Err(e), _) => e.prefixed("Failed to create project").record(),
+            (_, Err(poison_err)) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while creating project: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
+}
+
+/// Opens the project folder at `path`, replacing any open project.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn open_project(path: *const c_char) -> c_int {
+    ffi_guard("open_project", || {
+        let opened = read_ffi_str(path, "open_project", "path")
+            .and_then(|path| ProjectBundle::open(std::path::Path::new(&path)).map_err(FFICallError::failed));
+        match (opened, PROJECT.lock()) {
+            (Ok(bundle), Ok(mut project)) => {
+                *project = Some(bundle);
+                0
+            }
+            (Err(e), _) => e.prefixed("Failed to open project").record(),
+            (_, Err(poison_err)) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while opening project: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
+}
+
+/// Writes the index of the open project. Items are written when they are added.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn save_project() -> c_int {
+    ffi_guard("save_project", || {
+        with_open_project(None, |project| project.save().map(|_| Some(0))).unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Saves and closes the open project. Returns 0 on success (or if no project is open), a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn close_project() -> c_int {
+    ffi_guard("close_project", || {
+        match PROJECT.lock() {
+            Ok(mut project) => {
+                let saved = project.as_mut().map_or(Ok(()), |bundle| bundle.save());
+                *project = None;
+                match saved {
+                    Ok(()) => 0,
+                    Err(e) => {
+                        set_last_ffi_error(format!("Failed to save project on close: {}", e));
+                        FFIErrorCode::OperationFailed.return_code()
+                    }
+                }
+            }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while closing project: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
+}
+
+/// Gets the index of the open project as JSON.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_project_index_json() -> *mut c_char {
+    ffi_guard("get_project_index_json", || {
+        with_open_project(ptr::null_mut(), |project| Ok(json_ffi_string(&project.index)))
+    })
+}
+
+/// Adds or replaces a project item from JSON. `kind`: "scenario", "material",
+/// "formula" or "reference_data"; the item id is its `id` (scenarios) or `name`.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn put_project_item_json(kind: *const c_char, item_json: *const c_char) -> c_int {
+    ffi_guard("put_project_item_json", || {
+        let arguments = read_ffi_str(kind, "put_project_item_json", "kind")
+            .and_then(|kind| read_ffi_str(item_json, "put_project_item_json", "item_json").map(|json| (kind, json)));
+        let (kind, json) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => return e.record(),
+        };
+        let parse_error = |e: serde_json::Error| format!("Failed to parse {} JSON: {}", kind, e);
+        with_open_project(None, |project| {
+            match ProjectItemKind::from_name(&kind) {
+                Some(ProjectItemKind::Scenario) => project.put_scenario(&serde_json::from_str(&json).map_err(parse_error)?),
+                Some(ProjectItemKind::Material) => project.put_material(&serde_json::from_str(&json).map_err(parse_error)?),
+                Some(ProjectItemKind::Formula) => project.put_formula(&serde_json::from_str(&json).map_err(parse_error)?),
+                Some(ProjectItemKind::ReferenceData) => project.put_reference_data(&serde_json::from_str(&json).map_err(parse_error)?),
+                _ => Err(format!("Unsupported project item kind for JSON: {}", kind)),
+            }
+            .map(|_| Some(0))
+        })
+        .unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Gets a project item as JSON. `kind`: "scenario", "material", "formula" or "reference_data".
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_project_item_json(kind: *const c_char, id: *const c_char) -> *mut c_char {
+    ffi_guard("get_project_item_json", || {
+        let arguments = read_ffi_str(kind, "get_project_item_json", "kind")
+            .and_then(|kind| read_ffi_str(id, "get_project_item_json", "id").map(|id| (kind, id)));
+        let (kind, id) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_open_project(ptr::null_mut(), |project| {
+            match ProjectItemKind::from_name(&kind) {
+                Some(ProjectItemKind::Scenario) => project.scenario(&id).map(|item| json_ffi_string(&item)),
+                Some(ProjectItemKind::Material) => project.material(&id).map(|item| json_ffi_string(&item)),
+                Some(ProjectItemKind::Formula) => project.formula(&id).map(|item| json_ffi_string(&item)),
+                Some(ProjectItemKind::ReferenceData) => project.reference_data(&id).map(|item| json_ffi_string(&item)),
+                _ => Err(format!("Unsupported project item kind for JSON: {}", kind)),
+            }
+        })
+    })
+}
+
+/// Stores the results of the completed simulation in the open project as run `run_id`.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn save_current_run_to_project_h(handle: SimulationHandle, run_id: *const c_char) -> c_int {
+    ffi_guard("save_current_run_to_project_h", || {
+        let run_id = match read_ffi_str(run_id, "save_current_run_to_project", "run_id") {
+            Ok(run_id) => run_id,
+            Err(e) => {
+                return e.record();
+            }
+        };
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => {
+                    let estimated = limits::serialized_bytes(limits::results_history_bytes(results), PayloadFormat::MessagePack);
+                    if let Err(error) = limits::check_request("save_current_run_to_project", estimated, &[
+                        ("frame_storage.retain_full_precision", "Disable full-precision history retention before running"),
+                        ("set_ffi_memory_cap", "Raise the memory cap if the device has enough memory"),
+                    ]) {
+                        report_oversized_request(error);
+                        return FFI_REQUEST_TOO_LARGE_ERROR_CODE;
+                    }
+                    with_open_project(None, |project| project.put_run(&run_id, results).map(|_| Some(0))).unwrap_or_else(recorded_error_code)
+                }
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                    FFIErrorCode::NotAvailable.return_code()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while saving run to project: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
+}
+
+/// Deprecated single-instance form of `save_current_run_to_project_h`, operating on the default simulation.
+#[deprecated(note = "use `save_current_run_to_project_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn save_current_run_to_project(run_id: *const c_char) -> c_int {
+    save_current_run_to_project_h(SIMULATIONS.default_handle(), run_id)
+}
+
+/// Makes loaded run results the current results of simulation `handle` and records their integrity
+/// report. Returns 0 if intact, 1 if frames were corrupted or missing, a negative `FFIErrorCode` on error.
+fn install_loaded_run(handle: SimulationHandle, results: SimulationResults, report: IntegrityReport) -> c_int {
+    let status = if report.is_intact() { 0 } else { 1 };
+    if let Ok(mut last) = LAST_RUN_INTEGRITY.lock() {
+        *last = Some(report);
+    }
+    let Some(shared) = SIMULATIONS.get(handle) else {
+        set_unknown_simulation_error(handle);
+        return FFIErrorCode::UnknownHandle.return_code();
+    };
+    let state_lock = shared.state.lock();
+    match state_lock {
+        Ok(mut state) => {
+            state.parameters = results.parameters.clone();
+            state.results = Some(results);
+            state.status = crate::simulation::SimulationStatus::Completed;
+            state.progress = 1.0;
+            state.error_message = None;
+            status
+        }
+        Err(poison_err) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while loading run: {}", poison_err));
+            FFIErrorCode::LockPoisoned.return_code()
+        }
+    }
+}
+
+/// Loads run `run_id` from the open project as the current simulation results, so the
+/// result, frame and metrics functions operate on it. Requires `initialize_simulation`.
+/// Returns 0 on success, 1 when the run was loaded with corrupted or missing frames
+/// (see `get_last_run_integrity_json`), a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn load_project_run_h(handle: SimulationHandle, run_id: *const c_char) -> c_int {
+    ffi_guard("load_project_run_h", || {
+        let run_id = match read_ffi_str(run_id, "load_project_run", "run_id") {
+            Ok(run_id) => run_id,
+            Err(e) => {
+                return e.record();
+            }
+        };
+        let (results, report) = match with_open_project(None, |project| project.run_with_report(&run_id).map(Some)) {
+            Some(loaded) => loaded,
+            None => return recorded_error_code(),
+        };
+        install_loaded_run(handle, results, report)
+    })
+}
+
+/// Deprecated single-instance form of `load_project_run_h`, operating on the default simulation.
+#[deprecated(note = "use `load_project_run_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn load_project_run(run_id: *const c_char) -> c_int {
+    load_project_run_h(SIMULATIONS.default_handle(), run_id)
+}
+
+/// Gets the integrity report of the last run loaded with `load_project_run` as JSON
+/// (`file_checksum_valid`, `total_frames`, `corrupted_frames`, `missing_frames`).
+/// Corrupted frames are filled with NaN; missing trailing frames are dropped.
+/// Returns null if no run was loaded. Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_last_run_integrity_json() -> *mut c_char {
+    ffi_guard("get_last_run_integrity_json", || {
+        match LAST_RUN_INTEGRITY.lock() {
+            Ok(last) => match last.as_ref() {
+                Some(report) => json_ffi_string(report),
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "No project run has been loaded.".to_string());
+                    ptr::null_mut()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while reading run integrity: {}", poison_err));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+// --- FFI Functions for Results Storage Backends ---
+
+/// Runs `body` with the configured results storage backend.
+fn with_results_storage<R>(on_error: R, body: impl FnOnce(&dyn ResultsStorage) -> Result<R, String>) -> R {
+    match RESULTS_STORAGE.lock() {
+        Ok(storage) => match storage.as_deref() {
+            Some(backend) => body(backend).unwrap_or_else(|e| {
+                set_last_ffi_error(e);
+                on_error
+            }),
+            None => {
+                set_last_ffi_error("No results storage configured. Call set_results_storage_json first.".to_string());
+                on_error
+            }
+        },
+        Err(poison_err) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while accessing results storage: {}", poison_err));
+            on_error
+        }
+    }
+}
+
+/// Configures the results storage backend from a `StorageConfig` JSON object, e.g.
+/// `{"Local":{"root":"/data/runs"}}` or `{"S3":{"endpoint":"...","bucket":"...","prefix":"..."}}`
+/// (S3 requires the `s3` feature; credentials default to `AWS_ACCESS_KEY_ID` /
+/// `AWS_SECRET_ACCESS_KEY`). Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn set_results_storage_json(config_json: *const c_char) -> c_int {
+    ffi_guard("set_results_storage_json", || {
+        let config: StorageConfig = match read_ffi_str(config_json, "set_results_storage_json", "config_json")
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| FFICallError::invalid(format!("Failed to parse storage config JSON: {}", e))))
+        {
+            Ok(config) => config,
+            Err(e) => return e.record(),
+        };
+        let backend = match storage::open_storage(&config) {
+            Ok(backend) => backend,
+            Err(e) => return FFICallError::failed(e).record(),
+        };
+        match RESULTS_STORAGE.lock() {
+            Ok(mut storage) => {
+                *storage = Some(backend);
+                0
+            }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while configuring results storage: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
+}
+
+/// Writes the results of the completed simulation to the storage backend under `key`
+/// (letters, digits, '-', '_', '.' and '/' as folder separator).
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn save_current_run_to_storage_h(handle: SimulationHandle, key: *const c_char) -> c_int {
+    ffi_guard("save_current_run_to_storage_h", || {
+        let key = match read_ffi_str(key, "save_current_run_to_storage", "key") {
+            Ok(key) => key,
+            Err(e) => {
+                return e.record();
+            }
+        };
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => {
+                    let estimated = limits::results_history_bytes(results);
+                    if let Err(error) = limits::check_request("save_current_run_to_storage", estimated, &[
+                        ("frame_storage.retain_full_precision", "Disable full-precision history retention before running"),
+                        ("set_ffi_memory_cap", "Raise the memory cap if the device has enough memory"),
+                    ]) {
+                        report_oversized_request(error);
+                        return FFI_REQUEST_TOO_LARGE_ERROR_CODE;
+                    }
+                    with_results_storage(None, |storage| storage.save_results(&key, results).map(|_| Some(0))).unwrap_or_else(recorded_error_code)
+                }
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                    FFIErrorCode::NotAvailable.return_code()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while saving run to storage: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
+}
+
+/// Deprecated single-instance form of `save_current_run_to_storage_h`, operating on the default simulation.
+#[deprecated(note = "use `save_current_run_to_storage_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn save_current_run_to_storage(key: *const c_char) -> c_int {
+    save_current_run_to_storage_h(SIMULATIONS.default_handle(), key)
+}
+
+/// Loads run `key` from the storage backend as the current simulation results.
+/// Requires `initialize_simulation`. Returns 0 on success, 1 when the run was loaded with
+/// corrupted or missing frames (see `get_last_run_integrity_json`), a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn load_run_from_storage_h(handle: SimulationHandle, key: *const c_char) -> c_int {
+    ffi_guard("load_run_from_storage_h", || {
+        let key = match read_ffi_str(key, "load_run_from_storage", "key") {
+            Ok(key) => key,
+            Err(e) => {
+                return e.record();
+            }
+        };
+        match with_results_storage(None, |storage| storage.load_results(&key).map(Some)) {
+            Some((results, report)) => install_loaded_run(handle, results, report),
+            None => recorded_error_code(),
+        }
+    })
+}
+
+/// Deprecated single-instance form of `load_run_from_storage_h`, operating on the default simulation.
+#[deprecated(note = "use `load_run_from_storage_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn load_run_from_storage(key: *const c_char) -> c_int {
+    load_run_from_storage_h(SIMULATIONS.default_handle(), key)
+}
+
+/// Lists the run keys in the storage backend as a JSON list of strings.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn list_storage_runs_json() -> *mut c_char {
+    ffi_guard("list_storage_runs_json", || {
+        with_results_storage(ptr::null_mut(), |storage| storage.list().map(|keys| json_ffi_string(&keys)))
+    })
+}
+
+/// Deletes run `key` from the storage backend.
+/// Returns 1 if deleted, 0 if it did not exist, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn delete_storage_run(key: *const c_char) -> c_int {
+    ffi_guard("delete_storage_run", || {
+        let key = match read_ffi_str(key, "delete_storage_run", "key") {
+            Ok(key) => key,
+            Err(e) => {
+                return e.record();
+            }
+        };
+        with_results_storage(None, |storage| storage.delete(&key).map(|deleted| Some(c_int::from(deleted)))).unwrap_or_else(recorded_error_code)
+    })
+}
+
+// --- FFI Functions for Metrics & Export (JSON based) ---
+
+/// Calculates simulation metrics based on the current simulation state/results.
+/// Returns metrics as a JSON string.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn calculate_metrics_json_h(handle: SimulationHandle) -> *mut c_char {
+    ffi_guard("calculate_metrics_json_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return ptr::null_mut();
+        };
+
+        // Lock state to access results
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => {
+                if let Some(results) = &state.results {
+                    // Call backend metrics calculation
+                    let mut analyzer = MetricsAnalyzer::from_results(results);
+                    match analyzer.calculate_metrics() {
+                        Ok(metrics) => json_ffi_string(metrics),
+                        Err(e) => {
+                            set_last_ffi_error(format!("Failed to calculate metrics: {}", e));
+                            ptr::null_mut()
+                        }
+                    }
+                } else {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                    ptr::null_mut()
+                }
+            }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while calculating metrics: {}", poison_err));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Deprecated single-instance form of `calculate_metrics_json_h`, operating on the default simulation.
+#[deprecated(note = "use `calculate_metrics_json_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn calculate_metrics_json() -> *mut c_char {
+    calculate_metrics_json_h(SIMULATIONS.default_handle())
+}
+
+/// Writes the headline metrics of a time step (max/min/mean temperature, total energy and
+/// melt volume) into `out_metrics`, for dashboards that do not parse the metrics JSON.
+///
+/// A negative `time_step` selects the last executed step. Returns 0 on success, or a
+/// negative `FFIErrorCode`: `NullPointer` for a null output pointer, `UnknownHandle`,
+/// `InvalidArgument` for a step out of range or not stored, `NotAvailable` without
+/// results and `LockPoisoned` on a lock error.
+#[no_mangle]
+pub extern "C" fn get_simulation_metrics(
+    handle: SimulationHandle,
+    time_step: c_int,
+    out_metrics: *mut FFISimulationMetrics,
+) -> c_int {
+    ffi_guard("get_simulation_metrics", || {
+        if out_metrics.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_simulation_metrics: out_metrics pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state = match shared.state.lock() {
+            Ok(state) => state,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while calculating metrics: {}", poison_err));
+                return FFIErrorCode::LockPoisoned.return_code();
+            }
+        };
+        let Some(results) = state.results.as_ref() else {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available.".to_string());
+            return FFIErrorCode::NotAvailable.return_code();
+        };
+        let step = usize::try_from(time_step).unwrap_or(results.executed_steps);
+        match calculate_headline_metrics(results, step) {
+            Ok(metrics) => {
+                // Safety: `out_metrics` was checked for null and points to a caller-owned struct
+                unsafe { out_metrics.write(metrics.into()) };
+                0
+            }
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid metrics step {}: {}", time_step, e));
+                FFIErrorCode::InvalidArgument.return_code()
+            }
+        }
+    })
+}
+
+/// Exports simulation results based on options provided as a JSON string.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn export_results_json_h(handle: SimulationHandle, options_json: *const c_char) -> c_int {
+    ffi_guard("export_results_json_h", || {
+         if options_json.is_null() {
+             set_last_ffi_error_code(FFIErrorCode::NullPointer, "export_results_json: options_json pointer was null".to_string());
+             return FFIErrorCode::NullPointer.return_code();
+         }
+
+         let options_str = match unsafe { CStr::from_ptr(options_json).to_str() } {
+            Ok(s) => s,
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in options_json string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+         };
+
+         // Deserialize options (assuming an ExportOptions struct exists in crate::export)
+         let options: metrics::ExportOptions = match serde_json::from_str(options_str) {
+             Ok(opts) => opts,
+             Err(e) => {
+                 set_last_ffi_error(format!("Failed to deserialize export options JSON: {}", e));
+                 return FFIErrorCode::OperationFailed.return_code();
+             }
+         };
+
+         // Access simulation results
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+             Ok(state) => {
+                 if let Some(results) = &state.results {
+                    // Call backend export function
+                    match metrics::export_results(results, &options) {
+                        Ok(_) => 0, // Success
+                        Err(e) => {
+                            set_last_ffi_error(format!("Failed to export results: {}", e));
+                            FFIErrorCode::OperationFailed.return_code()
+                        }
+                    }
+                 } else {
+                     set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available for export.".to_string());
+                     FFIErrorCode::NotAvailable.return_code()
+                 }
+             }
+             Err(poison_err) => {
+                 set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while exporting results: {}", poison_err));
+                 FFIErrorCode::LockPoisoned.return_code()
+             }
+         }
+    })
+}
+
+/// Deprecated single-instance form of `export_results_json_h`, operating on the default simulation.
+#[deprecated(note = "use `export_results_json_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn export_results_json(options_json: *const c_char) -> c_int {
+    export_results_json_h(SIMULATIONS.default_handle(), options_json)
+}
+
+// --- FFI Functions for Background Jobs (exports) ---
+
+/// Starts a background export of the completed simulation results and returns the
+/// job ID immediately. `config_json` is an `ExportJobConfig`, e.g.
+/// `{"format": "VtkSeries" | "PngFrames" | "Csv", "output_dir": "...", "stride": 1}`.
+/// The results are copied, so a new simulation may run while the export is written.
+/// Returns the job ID (> 0) or a negative `FFIErrorCode` on error
+/// (`FFI_REQUEST_TOO_LARGE_ERROR_CODE` if the copy would exceed the memory cap).
+#[no_mangle]
+pub extern "C" fn start_export_job_h(handle: SimulationHandle, config_json: *const c_char) -> i64 {
+    ffi_guard("start_export_job_h", || {
+        let config: ExportJobConfig = match read_ffi_str(config_json, "start_export_job", "config_json")
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| FFICallError::invalid(format!("Failed to parse export job config JSON: {}", e))))
+        {
+            Ok(config) => config,
+            Err(e) => return e.record() as i64,
+        };
+        submit_export_job(handle, config)
+    })
+}
+
+/// Deprecated single-instance form of `start_export_job_h`, operating on the default simulation.
+#[deprecated(note = "use `start_export_job_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn start_export_job(config_json: *const c_char) -> i64 {
+    start_export_job_h(SIMULATIONS.default_handle(), config_json)
+}
+
+/// Copia os resultados da simulação `handle` para uma tarefa em segundo plano, respeitando o
+/// limite de memória. Registra o erro e retorna o código FFI correspondente em caso de falha.
+fn results_for_job(handle: SimulationHandle, fn_name: &'static str, purpose: &str) -> Result<SimulationResults, i64> {
+    let Some(shared) = SIMULATIONS.get(handle) else {
+        set_unknown_simulation_error(handle);
+        return Err(FFIErrorCode::UnknownHandle.return_code() as i64);
+    };
+    let state = match shared.state.lock() {
+        Ok(state) => state,
+        Err(poison_err) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while starting {} job: {}", purpose, poison_err));
+            return Err(FFIErrorCode::LockPoisoned.return_code() as i64);
+        }
+    };
+    let Some(results) = state.results.as_ref() else {
+        set_last_ffi_error_code(FFIErrorCode::NotAvailable, format!("Simulation results not available for {}.", purpose));
+        return Err(FFIErrorCode::NotAvailable.return_code() as i64);
+    };
+    if let Err(error) = limits::check_request(fn_name, limits::results_history_bytes(results), &[
+        ("stride", "Export fewer time steps from a run with a shorter history"),
+        ("set_ffi_memory_cap", "Raise the memory cap if the device has enough memory"),
+    ]) {
+        report_oversized_request(error);
+        return Err(FFI_REQUEST_TOO_LARGE_ERROR_CODE as i64);
+    }
+    Ok(results.clone())
+}
+
+/// Enfileira a exportação dos resultados da simulação `handle`; retorna o identificador da
+/// tarefa ou o código de erro FFI
+fn submit_export_job(handle: SimulationHandle, config: ExportJobConfig) -> i64 {
+    let results = match results_for_job(handle, "start_export_job", "export") {
+        Ok(results) => results,
+        Err(code) => return code,
+    };
+    match export_worker::submit_export(&JOB_QUEUE, results, config) {
+        Ok(job_id) => job_id as i64,
+        Err(e) => {
+            set_last_ffi_error(format!("Failed to start export job: {}", e));
+            FFIErrorCode::OperationFailed.return_code() as i64
+        }
+    }
+}
+
+/// Enfileira a geração do relatório dos resultados da simulação `handle` em `output_path`
+fn submit_report_job(handle: SimulationHandle, output_path: String) -> i64 {
+    let results = match results_for_job(handle, "submit_job", "report generation") {
+        Ok(results) => results,
+        Err(code) => return code,
+    };
+    let submitted = JOB_QUEUE.submit("report", move |context| {
+        context.check_cancelled()?;
+        metrics::generate_report(&results, &output_path).map_err(|e| format!("Failed to generate report: {}", e))?;
+        context.set_result(serde_json::json!({ "path": output_path }));
+        Ok(Some(output_path))
+    });
+    match submitted {
+        Ok(job_id) => job_id as i64,
+        Err(e) => {
+            set_last_ffi_error(format!("Failed to start report job: {}", e));
+            FFIErrorCode::OperationFailed.return_code() as i64
+        }
+    }
+}
+
+/// Gets the status of a background job as JSON (`JobInfo`: status, progress, message, output).
+/// Returns null for an unknown job ID.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_job_status_json(job_id: u64) -> *mut c_char {
+    ffi_guard("get_job_status_json", || {
+        match JOB_QUEUE.info(job_id) {
+            Some(info) => json_ffi_string(&info),
+            None => {
+                set_last_ffi_error(format!("Unknown job ID: {}", job_id));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Gets the status of all background jobs (in submission order) as a JSON list.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_jobs_json() -> *mut c_char {
+    ffi_guard("get_jobs_json", || json_ffi_string(&JOB_QUEUE.list()))
+}
+
+/// Gets the progress (0-1) of a background job, or -1.0 for an unknown job ID.
+#[no_mangle]
+pub extern "C" fn get_job_progress(job_id: u64) -> c_double {
+    ffi_guard("get_job_progress", || {
+        JOB_QUEUE.info(job_id).map_or(-1.0, |info| info.progress as c_double)
+    })
+}
+
+/// Requests cancellation of a background job. Queued jobs never start; running exports
+/// stop before the next time step and parametric studies before their next case (keeping
+/// the cases already completed as a partial result).
+/// Returns 0 on success, `InvalidArgument` (negated) if the job is unknown or finished.
+#[no_mangle]
+pub extern "C" fn cancel_job(job_id: u64) -> c_int {
+    ffi_guard("cancel_job", || {
+        let study = PARAMETRIC_STUDIES.lock().ok()
+            .and_then(|studies| studies.iter().find(|study| study.job_id == job_id).cloned());
+        if let Some(study) = study {
+            study.handle.cancel();
+        }
+        if JOB_QUEUE.cancel(job_id) {
+            0
+        } else {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Job {} is unknown or already finished", job_id));
+            FFIErrorCode::InvalidArgument.return_code()
+        }
+    })
+}
+
+/// Forgets finished jobs (completed, failed or cancelled). Returns the number removed.
+#[no_mangle]
+pub extern "C" fn remove_finished_jobs() -> c_int {
+    ffi_guard("remove_finished_jobs", || JOB_QUEUE.remove_finished() as c_int)
+}
+
+/// Requisição de uma tarefa de geração de relatório (`submit_job` com `kind` "report")
+#[derive(serde::Deserialize)]
+struct ReportJobRequest {
+    output_path: String,
+}
+
+/// Submits a long operation on simulation `handle` as a background job and returns its
+/// job ID immediately. `kind` selects the operation and `request_json` its request:
+/// - `"parametric_study"`: a `ParametricStudyConfig` (the job ID also works with the
+///   `*_parametric_study_*` functions);
+/// - `"export"`: an `ExportJobConfig` (as `start_export_job_h`);
+/// - `"report"`: `{"output_path": "..."}` (as `generate_report_json_h`).
+///
+/// Follow the job with `get_job_status` or `get_job_status_json`, fetch its result with
+/// `get_job_result_json` and stop it with `cancel_job`.
+/// Returns the job ID (> 0) or a negative `FFIErrorCode` on error
+/// (`FFI_REQUEST_TOO_LARGE_ERROR_CODE` if copying the results would exceed the memory cap).
+#[no_mangle]
+pub extern "C" fn submit_job(handle: SimulationHandle, kind: *const c_char, request_json: *const c_char) -> i64 {
+    ffi_guard("submit_job", || {
+        let (kind, request) = match read_ffi_str(kind, "submit_job", "kind")
+            .and_then(|kind| Ok((kind, read_ffi_str(request_json, "submit_job", "request_json")?)))
+        {
+            Ok(arguments) => arguments,
+            Err(e) => return e.record() as i64,
+        };
+
+        fn parse<T: serde::de::DeserializeOwned>(kind: &str, request: &str) -> Option<T> {
+            match serde_json::from_str(request) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Failed to parse {} job request JSON: {}", kind, e));
+                    None
+                }
+            }
+        }
+
+        match kind.as_str() {
+            "parametric_study" => parse(&kind, &request).map_or(FFIErrorCode::InvalidArgument.return_code() as i64, |config| submit_parametric_study(handle, config)),
+            "export" => parse(&kind, &request).map_or(FFIErrorCode::InvalidArgument.return_code() as i64, |config| submit_export_job(handle, config)),
+            "report" => parse::<ReportJobRequest>(&kind, &request).map_or(FFIErrorCode::InvalidArgument.return_code() as i64, |request| submit_report_job(handle, request.output_path)),
+            _ => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!(
+                    "Unknown job kind '{}' (expected parametric_study, export or report)", kind
+                ));
+                FFIErrorCode::InvalidArgument.return_code() as i64
+            }
+        }
+    })
+}
+
+/// Gets the status of a background job: 0 = queued, 1 = running, 2 = completed,
+/// 3 = failed, 4 = cancelled, or `InvalidArgument` (negated) for an unknown job ID.
+#[no_mangle]
+pub extern "C" fn get_job_status(job_id: u64) -> c_int {
+    ffi_guard("get_job_status", || {
+        match JOB_QUEUE.info(job_id) {
+            Some(info) => info.status.code(),
+            None => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Unknown job ID: {}", job_id));
+                FFIErrorCode::InvalidArgument.return_code()
+            }
+        }
+    })
+}
+
+/// Gets the result of a finished background job as JSON: the `ParametricStudyResult` of
+/// a parametric study (partial if cancelled), `{"path", "format"}` of an export and
+/// `{"path"}` of a report. Jobs without a structured result return `{"output": ...}`.
+/// Returns null for an unknown job ID, while the job is queued or running, and for failed
+/// or cancelled jobs without a result (the last error then carries the job's message).
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_job_result_json(job_id: u64) -> *mut c_char {
+    ffi_guard("get_job_result_json", || {
+        let Some(info) = JOB_QUEUE.info(job_id) else {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Unknown job ID: {}", job_id));
+            return ptr::null_mut();
+        };
+        if !info.status.is_finished() {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, format!("Job {} has not finished yet", job_id));
+            return ptr::null_mut();
+        }
+        match (JOB_QUEUE.result(job_id), info.status) {
+            (Some(result), _) => json_ffi_string(&result),
+            (None, JobStatus::Completed) => json_ffi_string(&serde_json::json!({ "output": info.output })),
+            (None, _) => {
+                set_last_ffi_error(info.message.unwrap_or_else(|| format!("Job {} has no result", job_id)));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Generates a report (e.g., PDF, HTML) at the specified output path.
+/// Requires calculated metrics and results.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn generate_report_json_h(handle: SimulationHandle, output_path: *const c_char) -> c_int {
+    ffi_guard("generate_report_json_h", || {
+         if output_path.is_null() {
+             set_last_ffi_error_code(FFIErrorCode::NullPointer, "generate_report_json: output_path pointer was null".to_string());
+             return FFIErrorCode::NullPointer.return_code();
+         }
+
+         let path_str = match unsafe { CStr::from_ptr(output_path).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in output_path string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+         };
+
+        // Access simulation results and potentially calculate metrics first
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => {
+                 if let Some(results) = &state.results {
+                    // Call backend report generation function (calculates the metrics first)
+                    match metrics::generate_report(results, &path_str) {
+                        Ok(_) => 0, // Success
+                        Err(e) => {
+                            set_last_ffi_error(format!("Failed to generate report: {}", e));
+                            FFIErrorCode::OperationFailed.return_code()
+                        }
+                    }
+                 } else {
+                     set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available for report generation.".to_string());
+                     FFIErrorCode::NotAvailable.return_code()
+                 }
+            }
+             Err(poison_err) => {
+                 set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while generating report: {}", poison_err));
+                 FFIErrorCode::LockPoisoned.return_code()
+             }
+        }
+    })
+}
+
+/// Deprecated single-instance form of `generate_report_json_h`, operating on the default simulation.
+#[deprecated(note = "use `generate_report_json_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn generate_report_json(output_path: *const c_char) -> c_int {
+    generate_report_json_h(SIMULATIONS.default_handle(), output_path)
+}
+
+// --- FFI Functions for the Material Library (JSON based) ---
+
+/// Runs `body` with the material library (created with the predefined materials on first use).
+fn with_material_library<R>(on_error: R, body: impl FnOnce(&mut MaterialLibrary) -> Result<R, String>) -> R {
+    match MATERIAL_LIBRARY.lock() {
+        Ok(mut library) => body(library.get_or_insert_with(MaterialLibrary::new)).unwrap_or_else(|e| {
+            set_last_ffi_error(e);
+            on_error
+        }),
+        Err(poison_err) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while accessing material library: {}", poison_err));
+            on_error
+        }
+    }
+}
+
+/// Serializes a formula as a JSON object with its `id` alongside the formula fields.
+fn formula_json_with_id(id: &str, formula: &Formula) -> Result<serde_json::Value, String> {
+    let mut value = serde_json::to_value(formula).map_err(|e| format!("Failed to serialize formula to JSON: {}", e))?;
+    if let serde_json::Value::Object(fields) = &mut value {
+        fields.insert("id".to_string(), serde_json::Value::String(id.to_string()));
+    }
+    Ok(value)
+}
+
+/// Parses a formula JSON object carrying its `id` alongside the formula fields.
+fn formula_from_json_with_id(json: &str) -> Result<(String, Formula), String> {
+    let mut value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let id = value.as_object_mut()
+        .and_then(|fields| fields.remove("id"))
+        .and_then(|id| id.as_str().map(str::to_string))
+        .ok_or_else(|| "formula JSON must have a string \"id\" field".to_string())?;
+    let formula = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    Ok((id, formula))
+}
+
+/// Runs `body` with the formula manager (created with the predefined formulas on first use).
+fn with_formula_manager<R>(on_error: R, body: impl FnOnce(&mut FormulaManager) -> Result<R, String>) -> R {
+    match FORMULA_MANAGER.lock() {
+        Ok(mut manager) => body(manager.get_or_insert_with(FormulaManager::new)).unwrap_or_else(|e| {
+            set_last_ffi_error(e);
+            on_error
+        }),
+        Err(poison_err) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while accessing formula manager: {}", poison_err));
+            on_error
+        }
+    }
+}
+
+/// Sets the directory of user-defined materials (one `<id>.json` per material, created if
+/// missing) and loads them into the library. Returns the number of user materials loaded,
+/// or a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn set_material_library_directory(path: *const c_char) -> c_int {
+    ffi_guard("set_material_library_directory", || {
+        let path = match read_ffi_str(path, "set_material_library_directory", "path") {
+            Ok(path) => path,
+            Err(e) => {
+                return e.record();
+            }
+        };
+        with_material_library(None, |library| {
+            library.set_user_directory(std::path::Path::new(&path)).map(|count| Some(count as c_int))
+        })
+        .unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Saves (creates or replaces) user material `material_id` from a `MaterialProperties` JSON
+/// object and writes it to the user directory. Ids may contain letters, digits, '-' and '_';
+/// saving a predefined id overrides it. Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn save_material_json(material_id: *const c_char, material_json: *const c_char) -> c_int {
+    ffi_guard("save_material_json", || {
+        let arguments = read_ffi_str(material_id, "save_material_json", "material_id")
+            .and_then(|id| read_ffi_str(material_json, "save_material_json", "material_json").map(|json| (id, json)));
+        let (id, json) = match arguments {
+            Ok(arguments) => arguments,
+            Err(e) => return e.record(),
+        };
+        let material: MaterialProperties = match serde_json::from_str(&json) {
+            Ok(material) => material,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to parse material JSON: {}", e));
+                return FFIErrorCode::OperationFailed.return_code();
+            }
+        };
+        with_material_library(None, |library| library.save_user_material(&id, material).map(|_| Some(0))).unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Deletes user material `material_id` from the library and the user directory.
+/// Returns 1 if deleted, 0 if it did not exist, a negative `FFIErrorCode` on error (including predefined materials).
+#[no_mangle]
+pub extern "C" fn delete_material_json(material_id: *const c_char) -> c_int {
+    ffi_guard("delete_material_json", || {
+        let id = match read_ffi_str(material_id, "delete_material_json", "material_id") {
+            Ok(id) => id,
+            Err(e) => {
+                return e.record();
+            }
+        };
+        with_material_library(None, |library| library.delete_user_material(&id).map(|deleted| Some(c_int::from(deleted)))).unwrap_or_else(recorded_error_code)
+    })
+}
+
+/// Entrada JSON `{id, user_defined, material}` de um material da biblioteca
+fn material_entry(library: &MaterialLibrary, id: &str) -> Option<serde_json::Value> {
+    library.get_material(id).map(|material| serde_json::json!({
+        "id": id,
+        "user_defined": library.is_user_material(id),
+        "material": material,
+    }))
+}
+
+/// Entradas de todos os materiais da biblioteca, ordenadas por id
+fn material_entries(library: &MaterialLibrary) -> Vec<serde_json::Value> {
+    let mut ids = library.get_material_ids();
+    ids.sort();
+    ids.iter().filter_map(|id| material_entry(library, id)).collect()
+}
+
+/// Gets all materials of the library as a JSON list of `{id, user_defined, material}`,
+/// sorted by id. Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_all_materials_json() -> *mut c_char {
+    ffi_guard("get_all_materials_json", || {
+        with_material_library(ptr::null_mut(), |library| Ok(json_ffi_string(&material_entries(library))))
+    })
+}
+
+/// Gets the material library as JSON: `{"user_directory": path | null, "materials":
+/// [{id, user_defined, material}, ...]}`, materials sorted by id. Predefined materials are
+/// always present; user materials come from the directory set with
+/// `set_material_library_directory`. Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_material_library_json() -> *mut c_char {
+    ffi_guard("get_material_library_json", || {
+        with_material_library(ptr::null_mut(), |library| {
+            Ok(json_ffi_string(&serde_json::json!({
+                "user_directory": library.user_directory().map(|path| path.display().to_string()),
+                "materials": material_entries(library),
+            })))
+        })
+    })
+}
+
+/// Gets one material of the library as JSON `{id, user_defined, material}`. `material`
+/// is looked up by id and, failing that, by display name (e.g. "steel" or "Aço Carbono").
+/// Returns null if no material matches.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_material_json(material: *const c_char) -> *mut c_char {
+    ffi_guard("get_material_json", || {
+        let key = match read_ffi_str(material, "get_material_json", "material") {
+            Ok(key) => key,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_material_library(ptr::null_mut(), |library| {
+            let id = if library.get_material(&key).is_some() {
+                Some(key.clone())
+            } else {
+                let mut names = library.get_material_names();
+                names.sort();
+                names.into_iter().find(|(_, name)| *name == key).map(|(id, _)| id)
+            };
+            match id.as_deref().and_then(|id| material_entry(library, id)) {
+                Some(entry) => Ok(json_ffi_string(&entry)),
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Unknown material '{}'", key));
+                    Ok(ptr::null_mut())
+                }
+            }
+        })
+    })
+}
+
+// --- FFI Functions for Scenario Templates (JSON based) ---
+
+/// Gets all operational scenario templates (cold start, shutdown, trip) as a JSON string (list).
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_scenario_templates_json() -> *mut c_char {
+    ffi_guard("get_scenario_templates_json", || {
+        let templates = scenarios::get_scenario_templates();
+        match serde_json::to_string(&templates) {
+            Ok(json_string) => {
+                CString::new(json_string).map_or_else(|e| {
+                    set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
+                    ptr::null_mut()
+                }, into_ffi_string_ptr)
+            }
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to serialize scenario templates to JSON: {}", e));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Gets a specific scenario template by ID as a JSON string.
+/// Returns null if the template does not exist.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_scenario_template_json(template_id: *const c_char) -> *mut c_char {
+    ffi_guard("get_scenario_template_json", || {
+        if template_id.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_scenario_template_json: template_id pointer was null".to_string());
+            return ptr::null_mut();
+        }
+        let id_str = match unsafe { CStr::from_ptr(template_id).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in template_id string: {}", e));
+                return ptr::null_mut();
+            }
+        };
+
+        match scenarios::get_scenario_template(&id_str) {
+            Some(template) => match serde_json::to_string(&template) {
                 Ok(json_string) => {
                     CString::new(json_string).map_or_else(|e| {
                         set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
                         ptr::null_mut()
-                    }, |c_str| c_str.into_raw())
+                    }, into_ffi_string_ptr)
+                }
+                Err(e) => {
+                    set_last_ffi_error(format!("Failed to serialize scenario template to JSON: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            None => {
+                set_last_ffi_error(format!("Scenario template '{}' not found", id_str));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+// --- FFI Functions for Teaching Mode (JSON based) ---
+
+/// Gets the teaching-mode templates as a JSON list of `{id, title, description,
+/// learning_goals, expected_runtime_seconds, variants: [{label, description}]}`.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_teaching_templates_json() -> *mut c_char {
+    ffi_guard("get_teaching_templates_json", || {
+        let listing: Vec<serde_json::Value> = teaching::get_teaching_templates().iter()
+            .map(|template| serde_json::json!({
+                "id": template.id,
+                "title": template.title,
+                "description": template.description,
+                "learning_goals": template.learning_goals,
+                "expected_runtime_seconds": template.expected_runtime_seconds,
+                "variants": template.variants.iter()
+                    .map(|variant| serde_json::json!({ "label": variant.label, "description": variant.description }))
+                    .collect::<Vec<_>>(),
+            }))
+            .collect();
+        json_ffi_string(&listing)
+    })
+}
+
+/// Replaces simulation `handle` (which must not be running) with variant `variant_index`
+/// of teaching template `template_id` and starts it. `None` creates a new simulation and
+/// makes it the default one.
+fn start_teaching_template(handle: Option<SimulationHandle>, template_id: *const c_char, variant_index: c_int) -> c_int {
+    let template_id = match read_ffi_str(template_id, "run_teaching_template", "template_id") {
+        Ok(template_id) => template_id,
+        Err(e) => {
+            return e.record();
+        }
+    };
+    let parameters = match teaching::get_teaching_template(&template_id) {
+        Some(template) => match usize::try_from(variant_index).ok().and_then(|i| template.variants.into_iter().nth(i)) {
+            Some(variant) => variant.parameters,
+            None => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Teaching template '{}' has no variant {}", template_id, variant_index));
+                return FFIErrorCode::InvalidArgument.return_code();
+            }
+        },
+        None => {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Teaching template '{}' not found", template_id));
+            return FFIErrorCode::InvalidArgument.return_code();
+        }
+    };
+
+    let shared = match handle {
+        Some(handle) => {
+            let Some(previous) = SIMULATIONS.get(handle) else {
+                set_unknown_simulation_error(handle);
+                return FFIErrorCode::UnknownHandle.return_code();
+            };
+            let busy = match previous.state.lock() {
+                Ok(state) => matches!(state.status, crate::simulation::SimulationStatus::Running | crate::simulation::SimulationStatus::Paused),
+                Err(_) => true,
+            };
+            if busy {
+                set_last_ffi_error("A simulation is running. Cancel it or call destroy_simulation first.".to_string());
+                return FFIErrorCode::OperationFailed.return_code();
+            }
+            if let Err(err) = previous.join_simulation_thread() {
+                set_last_ffi_error(format!("Error during simulation cleanup: {}", err));
+                return FFIErrorCode::OperationFailed.return_code();
+            }
+            match SIMULATIONS.replace(handle, SharedSimulationState::new(parameters)) {
+                Some(shared) => {
+                    // O canal compartilhado aberto continua recebendo os quadros
+                    if let Some(channel) = shared_frame_channels().get(&handle) {
+                        shared.live_frames().attach_channel(Some(channel.clone()));
+                    }
+                    shared
                 }
-                Err(e) => {
-                    set_last_ffi_error(format!("Failed to serialize formulas to JSON: {}", e));
-                    ptr::null_mut()
+                None => {
+                    set_unknown_simulation_error(handle);
+                    return FFIErrorCode::UnknownHandle.return_code();
                 }
             }
         }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to get formulas by category: {}", e));
-            ptr::null_mut()
+        None => {
+            let handle = SIMULATIONS.insert(SharedSimulationState::new(parameters));
+            SIMULATIONS.set_default(handle);
+            match SIMULATIONS.get(handle) {
+                Some(shared) => shared,
+                None => {
+                    set_unknown_simulation_error(handle);
+                    return FFIErrorCode::UnknownHandle.return_code();
+                }
+            }
+        }
+    };
+    match shared.run_simulation() {
+        Ok(_) => 0,
+        Err(err_msg) => {
+            set_last_ffi_error(format!("Failed to start teaching template: {}", err_msg));
+            FFIErrorCode::OperationFailed.return_code()
         }
     }
 }
 
-/// Returns a single formula by ID as a JSON string.
+/// Runs variant `variant_index` of teaching template `template_id` in one call: replaces
+/// simulation `handle` (which must not be running) with the template parameters and
+/// starts it in the background, as `create_simulation` + `run_simulation_h` would.
+/// Poll `get_simulation_state_h` for progress. Returns 0 on success or a negative
+/// `FFIErrorCode`: `InvalidArgument` for an unknown template or variant, `UnknownHandle`,
+/// `OperationFailed` if the simulation is running or could not be started.
+#[no_mangle]
+pub extern "C" fn run_teaching_template_h(handle: SimulationHandle, template_id: *const c_char, variant_index: c_int) -> c_int {
+    ffi_guard("run_teaching_template_h", || start_teaching_template(Some(handle), template_id, variant_index))
+}
+
+/// Deprecated single-instance form of `run_teaching_template_h`, operating on the default
+/// simulation (created if it does not exist).
+#[deprecated(note = "use `run_teaching_template_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn run_teaching_template(template_id: *const c_char, variant_index: c_int) -> c_int {
+    ffi_guard("run_teaching_template", || {
+        let handle = SIMULATIONS.default_handle();
+        start_teaching_template((handle != INVALID_SIMULATION_HANDLE).then_some(handle), template_id, variant_index)
+    })
+}
+
+// --- FFI Functions for Convergence Monitoring (JSON based) ---
+
+/// Gets the convergence history of the completed simulation as a JSON list of records
+/// (per step: max temperature change, residual norms and limiter activations).
+/// Returns null if no results are available.
 /// Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
-pub extern "C" fn get_formula_json(id: *const c_char) -> *mut c_char {
-     if id.is_null() {
-        return ptr::null_mut();
-     }
-     let id_str = match unsafe { CStr::from_ptr(id).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in id string: {}", e));
+pub extern "C" fn get_convergence_history_json_h(handle: SimulationHandle) -> *mut c_char {
+    ffi_guard("get_convergence_history_json_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
             return ptr::null_mut();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => json_ffi_string(&results.convergence.records),
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available for convergence history.".to_string());
+                    ptr::null_mut()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while reading convergence history: {}", poison_err));
+                ptr::null_mut()
+            }
         }
-    };
+    })
+}
 
-    match formulas::get_by_id(id_str) {
-        Ok(Some(formula)) => { // Handle Option<Formula>
-             match serde_json::to_string(&formula) {
-                Ok(json_string) => {
-                    CString::new(json_string).map_or_else(|e| {
-                        set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
+/// Deprecated single-instance form of `get_convergence_history_json_h`, operating on the default simulation.
+#[deprecated(note = "use `get_convergence_history_json_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn get_convergence_history_json() -> *mut c_char {
+    get_convergence_history_json_h(SIMULATIONS.default_handle())
+}
+
+// --- FFI Functions for Timeline Annotations (JSON based) ---
+
+/// Gets the timeline annotations of the completed simulation as a JSON string (list sorted by time).
+/// Returns null if no results are available.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_annotations_json_h(handle: SimulationHandle) -> *mut c_char {
+    ffi_guard("get_annotations_json_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return ptr::null_mut();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => {
+                let results = match state.results.as_ref() {
+                    Some(results) => results,
+                    None => {
+                        set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available for annotations.".to_string());
+                        return ptr::null_mut();
+                    }
+                };
+                match serde_json::to_string(&results.annotations) {
+                    Ok(json_string) => {
+                        CString::new(json_string).map_or_else(|e| {
+                            set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
+                            ptr::null_mut()
+                        }, into_ffi_string_ptr)
+                    }
+                    Err(e) => {
+                        set_last_ffi_error(format!("Failed to serialize annotations to JSON: {}", e));
                         ptr::null_mut()
-                    }, |c_str| c_str.into_raw())
-                }
-                Err(e) => {
-                    set_last_ffi_error(format!("Failed to serialize formula to JSON: {}", e));
-                    ptr::null_mut()
+                    }
                 }
             }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while reading annotations: {}", poison_err));
+                ptr::null_mut()
+            }
         }
-        Ok(None) => { // Formula not found
-            // Return null, maybe set a specific error or let Dart handle null?
-            // set_last_ffi_error("Formula not found".to_string()); // Optional
-            ptr::null_mut()
-        }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to get formula by ID: {}", e));
-            ptr::null_mut()
-        }
-    }
+    })
 }
 
-/// Saves a formula provided as a JSON string. Returns 0 on success, negative on error.
+/// Deprecated single-instance form of `get_annotations_json_h`, operating on the default simulation.
+#[deprecated(note = "use `get_annotations_json_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn save_formula_json(formula_json: *const c_char) -> c_int {
-    if formula_json.is_null() {
-        set_last_ffi_error("save_formula_json: formula_json pointer was null".to_string());
-        return -1;
-    }
+pub extern "C" fn get_annotations_json() -> *mut c_char {
+    get_annotations_json_h(SIMULATIONS.default_handle())
+}
 
-    let json_str = match unsafe { CStr::from_ptr(formula_json).to_str() } {
-        Ok(s) => s,
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in formula JSON string: {}", e));
-            return -2; // Different error code for invalid input
+/// Adds a user annotation (JSON `TimelineAnnotation`) to the timeline of the completed simulation.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn add_annotation_json_h(handle: SimulationHandle, annotation_json: *const c_char) -> c_int {
+    ffi_guard("add_annotation_json_h", || {
+        if annotation_json.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "add_annotation_json: annotation_json pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
         }
-    };
+        let json_str = match unsafe { CStr::from_ptr(annotation_json).to_str() } {
+            Ok(s) => s,
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in annotation JSON: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+        };
+        let annotation: TimelineAnnotation = match serde_json::from_str(json_str) {
+            Ok(annotation) => annotation,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to parse annotation JSON: {}", e));
+                return FFIErrorCode::OperationFailed.return_code();
+            }
+        };
 
-    // Deserialize JSON string to Formula object
-    match serde_json::from_str::<formulas::Formula>(json_str) { // Assuming Formula struct path
-        Ok(formula) => {
-            // Call Rust function to save the formula
-            match formulas::save_formula(formula) {
-                Ok(_) => 0, // Success
-                Err(e) => {
-                    set_last_ffi_error(format!("Failed to save formula: {}", e));
-                    -3 // Error during save operation
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(mut state) => {
+                let results = match state.results.as_mut() {
+                    Some(results) => results,
+                    None => {
+                        set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available for annotations.".to_string());
+                        return FFIErrorCode::NotAvailable.return_code();
+                    }
+                };
+                match results.add_annotation(annotation) {
+                    Ok(()) => 0,
+                    Err(e) => {
+                        set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid annotation: {}", e));
+                        FFIErrorCode::InvalidArgument.return_code()
+                    }
                 }
             }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while adding annotation: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
         }
+    })
+}
+
+/// Deprecated single-instance form of `add_annotation_json_h`, operating on the default simulation.
+#[deprecated(note = "use `add_annotation_json_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn add_annotation_json(annotation_json: *const c_char) -> c_int {
+    add_annotation_json_h(SIMULATIONS.default_handle(), annotation_json)
+}
+
+// --- FFI Functions for Binary Payloads (JSON / MessagePack / CBOR) ---
+
+/// Returns the currently negotiated payload format to use for the `*_payload` functions.
+fn current_payload_format() -> PayloadFormat {
+    PayloadFormat::from_code(PAYLOAD_FORMAT.load(Ordering::Relaxed)).unwrap_or(PayloadFormat::Json)
+}
+
+/// Serializes a value with the negotiated format into an FFI byte buffer.
+fn encode_ffi_payload<T: serde::Serialize>(value: &T) -> FFIByteBuffer {
+    match payload::encode_payload(value, current_payload_format()) {
+        Ok(bytes) => vec_to_ffi_byte_buffer(bytes),
         Err(e) => {
-            set_last_ffi_error(format!("Failed to deserialize formula JSON: {}", e));
-            -4 // Error during JSON deserialization
+            set_last_ffi_error(e);
+            empty_ffi_byte_buffer()
         }
     }
 }
 
-/// Deletes a formula by ID. Returns 0 on success, negative on error.
+/// Sets the serialization format used by the `*_payload` functions
+/// (0 = JSON, 1 = MessagePack, 2 = CBOR). The schemas are the same as the JSON functions.
+/// Returns 0 on success, `InvalidArgument` (negated) for an unknown format.
 #[no_mangle]
-pub extern "C" fn delete_formula_json(id: *const c_char) -> c_int {
-    if id.is_null() {
-        set_last_ffi_error("delete_formula_json: id pointer was null".to_string());
-        return -1;
-    }
+pub extern "C" fn set_ffi_payload_format(format: c_int) -> c_int {
+    ffi_guard("set_ffi_payload_format", || {
+        match PayloadFormat::from_code(format) {
+            Some(payload_format) => {
+                PAYLOAD_FORMAT.store(payload_format.code(), Ordering::Relaxed);
+                0
+            }
+            None => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Unknown payload format code: {}", format));
+                FFIErrorCode::InvalidArgument.return_code()
+            }
+        }
+    })
+}
 
-    let id_str = match unsafe { CStr::from_ptr(id).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in id string: {}", e));
-            return -2;
+/// Gets the currently negotiated payload format code.
+#[no_mangle]
+pub extern "C" fn get_ffi_payload_format() -> c_int {
+    ffi_guard("get_ffi_payload_format", || {
+        current_payload_format().code()
+    })
+}
+
+/// Gets the complete simulation results (fields, parameters, checks, annotations)
+/// serialized with the negotiated format. Returns an empty buffer on error.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_simulation_results_payload_h(handle: SimulationHandle) -> FFIByteBuffer {
+    ffi_guard("get_simulation_results_payload_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_ffi_byte_buffer();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => {
+                    let estimated = limits::serialized_bytes(limits::results_history_bytes(results), current_payload_format());
+                    if let Err(error) = limits::check_request("get_simulation_results_payload", estimated, &[
+                        ("get_frame_packet", "Stream the temperature history one time step at a time"),
+                        ("get_pyramid_frame_packet", "Fetch a temporally downsampled history"),
+                        ("frame_storage.retain_full_precision", "Disable full-precision history retention and use quantized playback frames"),
+                    ]) {
+                        report_oversized_request(error);
+                        return empty_ffi_byte_buffer();
+                    }
+                    encode_ffi_payload(results)
+                }
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                    empty_ffi_byte_buffer()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while encoding results: {}", poison_err));
+                empty_ffi_byte_buffer()
+            }
         }
-    };
+    })
+}
 
-    match formulas::delete_formula(id_str) {
-        Ok(_) => 0, // Success
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to delete formula: {}", e));
-            -3 // Error during delete operation
+/// Deprecated single-instance form of `get_simulation_results_payload_h`, operating on the default simulation.
+#[deprecated(note = "use `get_simulation_results_payload_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn get_simulation_results_payload() -> FFIByteBuffer {
+    get_simulation_results_payload_h(SIMULATIONS.default_handle())
+}
+
+/// Gets the timeline annotations serialized with the negotiated format.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_annotations_payload_h(handle: SimulationHandle) -> FFIByteBuffer {
+    ffi_guard("get_annotations_payload_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_ffi_byte_buffer();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => encode_ffi_payload(&results.annotations),
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available for annotations.".to_string());
+                    empty_ffi_byte_buffer()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while encoding annotations: {}", poison_err));
+                empty_ffi_byte_buffer()
+            }
         }
-    }
+    })
 }
 
-/// Validates a formula source string with given parameters (as JSON).
-/// Returns validation result as a JSON string.
-/// Caller must free the returned string using `free_rust_string`.
+/// Deprecated single-instance form of `get_annotations_payload_h`, operating on the default simulation.
+#[deprecated(note = "use `get_annotations_payload_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn validate_formula_json(source_json: *const c_char, params_json: *const c_char) -> *mut c_char {
-     if source_json.is_null() {
-         set_last_ffi_error("validate_formula_json: source_json pointer was null".to_string());
-         return ptr::null_mut();
-     }
-     if params_json.is_null() {
-         set_last_ffi_error("validate_formula_json: params_json pointer was null".to_string());
-         return ptr::null_mut();
-     }
-
-     let source_str = match unsafe { CStr::from_ptr(source_json).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in source_json string: {}", e));
-            return ptr::null_mut();
+pub extern "C" fn get_annotations_payload() -> FFIByteBuffer {
+    get_annotations_payload_h(SIMULATIONS.default_handle())
+}
+
+/// Gets a lightweight snapshot package of the current simulation state (thumbnail
+/// heatmap PNG in base64, key metrics and a parameters summary) serialized with the
+/// negotiated format, for project-browser previews and crash reports.
+/// Works at any stage; the thumbnail is absent until results are available.
+/// `thumbnail_size`: largest thumbnail dimension in pixels (<= 0 uses 128).
+/// `color_scale`: 0 = blue-to-red, 1 = rainbow, 2 = grayscale.
+/// Returns an empty buffer on error.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_snapshot_package_h(handle: SimulationHandle, thumbnail_size: c_int, color_scale: c_int) -> FFIByteBuffer {
+    ffi_guard("get_snapshot_package_h", || {
+        let color_scale = match color_scale {
+            0 => ColorScale::BlueToRed,
+            1 => ColorScale::Rainbow,
+            2 => ColorScale::Grayscale,
+            _ => {
+                set_last_ffi_error(format!("Unknown color scale code: {}", color_scale));
+                return empty_ffi_byte_buffer();
+            }
+        };
+        let mut options = SnapshotOptions { color_scale, ..SnapshotOptions::default() };
+        if thumbnail_size > 0 {
+            options.thumbnail_size = thumbnail_size as usize;
+        }
+        // RGB, mais a cópia em base64 (4/3) e o payload
+        let thumbnail_bytes = (options.thumbnail_size as u64).saturating_pow(2).saturating_mul(3);
+        if let Err(error) = limits::check_request("get_snapshot_package", thumbnail_bytes.saturating_mul(3), &[
+            ("get_snapshot_package", "Request a smaller thumbnail_size (<= 0 uses 128 pixels)"),
+            ("get_frame_packet", "Render full-resolution heatmaps from frame packets on the client"),
+        ]) {
+            report_oversized_request(error);
+            return empty_ffi_byte_buffer();
         }
-     };
-     let params_str = match unsafe { CStr::from_ptr(params_json).to_str() } {
-         Ok(s) => s.to_string(),
-         Err(e) => {
-             set_last_ffi_error(format!("Invalid UTF-8 in params_json string: {}", e));
-             return ptr::null_mut();
-         }
-     };
 
-    // Call backend validation logic
-    match formulas::validate_formula(source_str, params_str) {
-        Ok(result_json) => {
-            CString::new(result_json).map_or_else(|e| {
-                set_last_ffi_error(format!("Failed to create CString for validation result JSON: {}", e));
-                ptr::null_mut()
-            }, |c_str| c_str.into_raw())
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_ffi_byte_buffer();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match SnapshotPackage::from_state(&state, &options) {
+                Ok(package) => encode_ffi_payload(&package),
+                Err(e) => {
+                    set_last_ffi_error(format!("Failed to build snapshot package: {}", e));
+                    empty_ffi_byte_buffer()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while building snapshot package: {}", poison_err));
+                empty_ffi_byte_buffer()
+            }
         }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to validate formula: {}", e));
-            ptr::null_mut()
+    })
+}
+
+/// Deprecated single-instance form of `get_snapshot_package_h`, operating on the default simulation.
+#[deprecated(note = "use `get_snapshot_package_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn get_snapshot_package(thumbnail_size: c_int, color_scale: c_int) -> FFIByteBuffer {
+    get_snapshot_package_h(SIMULATIONS.default_handle(), thumbnail_size, color_scale)
+}
+
+/// Gets the 3D (nr × ntheta × nz) temperature voxels of the completed simulation,
+/// serialized with the negotiated format as a list of fields. `time_step` selects one
+/// step; a negative value requests every executed step (full 3D history).
+/// Requests beyond the memory cap return an empty buffer with a structured
+/// "request_too_large" error (see `set_ffi_memory_cap`).
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_voxel_temperature_payload_h(handle: SimulationHandle, time_step: c_int) -> FFIByteBuffer {
+    ffi_guard("get_voxel_temperature_payload_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_ffi_byte_buffer();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => {
+                    let steps: Vec<usize> = if time_step < 0 {
+                        (0..=results.executed_steps).collect()
+                    } else {
+                        vec![time_step as usize]
+                    };
+                    let params = &results.parameters;
+                    let raw = limits::voxel_bytes(params.nr, params.ntheta, params.nz, steps.len());
+                    let estimated = raw.saturating_add(limits::serialized_bytes(raw, current_payload_format()));
+                    if let Err(error) = limits::check_request("get_voxel_temperature_payload", estimated, &[
+                        ("get_voxel_temperature_payload", "Request a single time step instead of the full history"),
+                        ("get_frame_packet", "Stream the axisymmetric 2D field and revolve it on the client"),
+                        ("get_pyramid_frame_packet", "Fetch a temporally downsampled history"),
+                    ]) {
+                        report_oversized_request(error);
+                        return empty_ffi_byte_buffer();
+                    }
+
+                    match steps.into_iter().map(|step| results.generate_3d_temperature(step)).collect::<Result<Vec<_>, _>>() {
+                        Ok(fields) => encode_ffi_payload(&fields),
+                        Err(e) => {
+                            set_last_ffi_error(format!("Failed to build voxel temperature field: {}", e));
+                            empty_ffi_byte_buffer()
+                        }
+                    }
+                }
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                    empty_ffi_byte_buffer()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while building voxel temperature field: {}", poison_err));
+                empty_ffi_byte_buffer()
+            }
         }
-    }
+    })
 }
 
-/// Evaluates a formula by ID with given parameters (as JSON).
-/// Returns evaluation result as a JSON string.
-/// Caller must free the returned string using `free_rust_string`.
+/// Deprecated single-instance form of `get_voxel_temperature_payload_h`, operating on the default simulation.
+#[deprecated(note = "use `get_voxel_temperature_payload_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn evaluate_formula_json(id: *const c_char, params_json: *const c_char) -> *mut c_char {
-     if id.is_null() {
-         set_last_ffi_error("evaluate_formula_json: id pointer was null".to_string());
-         return ptr::null_mut();
-     }
-      if params_json.is_null() {
-         set_last_ffi_error("evaluate_formula_json: params_json pointer was null".to_string());
-         return ptr::null_mut();
-     }
-
-     let id_str = match unsafe { CStr::from_ptr(id).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in id string: {}", e));
-            return ptr::null_mut();
+pub extern "C" fn get_voxel_temperature_payload(time_step: c_int) -> FFIByteBuffer {
+    get_voxel_temperature_payload_h(SIMULATIONS.default_handle(), time_step)
+}
+
+/// Sets the memory cap (bytes) applied to data requests such as full-history payloads,
+/// 3D voxel exports and ensemble buffers; 0 restores the default (512 MiB).
+/// Returns 0.
+#[no_mangle]
+pub extern "C" fn set_ffi_memory_cap(bytes: u64) -> c_int {
+    ffi_guard("set_ffi_memory_cap", || {
+        limits::set_memory_cap(bytes);
+        0
+    })
+}
+
+/// Gets the current memory cap (bytes) applied to data requests.
+#[no_mangle]
+pub extern "C" fn get_ffi_memory_cap() -> u64 {
+    ffi_guard("get_ffi_memory_cap", limits::memory_cap)
+}
+
+/// Gets a temperature frame of the completed simulation as a binary frame packet
+/// (see `simulation::frames` for the layout). `encoding`: 0 = f32, 1 = u16 quantized.
+/// Returns an empty buffer on error.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_frame_packet_h(handle: SimulationHandle, time_step: c_int, encoding: c_int) -> FFIByteBuffer {
+    ffi_guard("get_frame_packet_h", || {
+        let frame_encoding = match u8::try_from(encoding).ok().and_then(FrameEncoding::from_code) {
+            Some(frame_encoding) => frame_encoding,
+            None => {
+                set_last_ffi_error(format!("Unknown frame encoding code: {}", encoding));
+                return empty_ffi_byte_buffer();
+            }
+        };
+        if time_step < 0 {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid time step index: {}", time_step));
+            return empty_ffi_byte_buffer();
         }
-     };
-     let params_str = match unsafe { CStr::from_ptr(params_json).to_str() } {
-         Ok(s) => s.to_string(),
-         Err(e) => {
-             set_last_ffi_error(format!("Invalid UTF-8 in params_json string: {}", e));
-             return ptr::null_mut();
-         }
-     };
 
-    // Call backend evaluation logic
-     match formulas::evaluate_formula(id_str, params_str) {
-        Ok(result_json) => {
-            CString::new(result_json).map_or_else(|e| {
-                set_last_ffi_error(format!("Failed to create CString for evaluation result JSON: {}", e));
-                ptr::null_mut()
-            }, |c_str| c_str.into_raw())
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_ffi_byte_buffer();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => match FramePacket::from_results(results, time_step as usize, frame_encoding) {
+                    Ok(packet) => vec_to_ffi_byte_buffer(packet.encode()),
+                    Err(e) => {
+                        set_last_ffi_error(format!("Failed to build frame packet: {}", e));
+                        empty_ffi_byte_buffer()
+                    }
+                },
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                    empty_ffi_byte_buffer()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while building frame packet: {}", poison_err));
+                empty_ffi_byte_buffer()
+            }
         }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to evaluate formula: {}", e));
-            ptr::null_mut()
+    })
+}
+
+/// Deprecated single-instance form of `get_frame_packet_h`, operating on the default simulation.
+#[deprecated(note = "use `get_frame_packet_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn get_frame_packet(time_step: c_int, encoding: c_int) -> FFIByteBuffer {
+    get_frame_packet_h(SIMULATIONS.default_handle(), time_step, encoding)
+}
+
+/// Gets the frame nearest to (at or before) `time_step` from the temporal pyramid level
+/// with the given `stride` (1 = full history, e.g. 10 or 100 for coarse levels)
+/// as a binary frame packet, for fast timeline scrubbing.
+/// `encoding`: 0 = f32, 1 = u16 quantized. Returns an empty buffer on error.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_pyramid_frame_packet_h(handle: SimulationHandle, stride: c_int, time_step: c_int, encoding: c_int) -> FFIByteBuffer {
+    ffi_guard("get_pyramid_frame_packet_h", || {
+        let frame_encoding = match u8::try_from(encoding).ok().and_then(FrameEncoding::from_code) {
+            Some(frame_encoding) => frame_encoding,
+            None => {
+                set_last_ffi_error(format!("Unknown frame encoding code: {}", encoding));
+                return empty_ffi_byte_buffer();
+            }
+        };
+        if stride < 1 || time_step < 0 {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid pyramid request: stride {}, time step {}", stride, time_step));
+            return empty_ffi_byte_buffer();
         }
-    }
+
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_ffi_byte_buffer();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => match results.pyramid_frame(stride as usize, time_step as usize) {
+                    Ok((step, field)) => {
+                        let time = step as f64 * results.parameters.time_step;
+                        vec_to_ffi_byte_buffer(FramePacket::new(step as u64, time, field.view(), frame_encoding).encode())
+                    }
+                    Err(e) => {
+                        set_last_ffi_error(format!("Failed to get pyramid frame: {}", e));
+                        empty_ffi_byte_buffer()
+                    }
+                },
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                    empty_ffi_byte_buffer()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while reading pyramid frame: {}", poison_err));
+                empty_ffi_byte_buffer()
+            }
+        }
+    })
 }
 
-/// Sets the formula (by ID) to be used for a specific function type (e.g., "conductivity").
-/// Returns 0 on success, negative on error.
+/// Deprecated single-instance form of `get_pyramid_frame_packet_h`, operating on the default simulation.
+#[deprecated(note = "use `get_pyramid_frame_packet_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn set_formula_for_function_json(function_type: *const c_char, formula_id: *const c_char) -> c_int {
-    if function_type.is_null() {
-        set_last_ffi_error("set_formula_for_function_json: function_type pointer was null".to_string());
-        return -1;
-    }
-    if formula_id.is_null() {
-        set_last_ffi_error("set_formula_for_function_json: formula_id pointer was null".to_string());
-        return -2;
-    }
+pub extern "C" fn get_pyramid_frame_packet(stride: c_int, time_step: c_int, encoding: c_int) -> FFIByteBuffer {
+    get_pyramid_frame_packet_h(SIMULATIONS.default_handle(), stride, time_step, encoding)
+}
 
-    let type_str = match unsafe { CStr::from_ptr(function_type).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in function_type string: {}", e));
-            return -3;
+/// Gets the most recent temperature frame of simulation `handle` while it runs (and the
+/// last frame after it finishes) as a binary frame packet, so the field can be animated
+/// live. The header carries the step; compare it (or `get_live_frame_sequence`) to skip
+/// unchanged frames. `encoding`: 0 = f32, 1 = u16 quantized.
+/// Returns an empty buffer on error or if the run has not published a frame yet.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_latest_temperature_frame(handle: SimulationHandle, encoding: c_int) -> FFIByteBuffer {
+    ffi_guard("get_latest_temperature_frame", || {
+        let frame_encoding = match u8::try_from(encoding).ok().and_then(FrameEncoding::from_code) {
+            Some(frame_encoding) => frame_encoding,
+            None => {
+                set_last_ffi_error(format!("Unknown frame encoding code: {}", encoding));
+                return empty_ffi_byte_buffer();
+            }
+        };
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_ffi_byte_buffer();
+        };
+
+        // O pacote é montado com o quadro travado; a codificação ocorre após a liberação
+        let packet = shared.live_frames().with_latest(|frame| {
+            FramePacket::new(frame.step as u64, frame.time, frame.temperature.view(), frame_encoding)
+        });
+        match packet {
+            Some(packet) => vec_to_ffi_byte_buffer(packet.encode()),
+            None => {
+                set_last_ffi_error("No live frame available (simulation not started yet).".to_string());
+                empty_ffi_byte_buffer()
+            }
         }
-     };
-    let id_str = match unsafe { CStr::from_ptr(formula_id).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in formula_id string: {}", e));
-            return -4;
+    })
+}
+
+/// Gets the live frame counter of simulation `handle`, incremented whenever a new frame is
+/// published (across runs). Returns `UnknownHandle` (negated) for an unknown handle.
+#[no_mangle]
+pub extern "C" fn get_live_frame_sequence(handle: SimulationHandle) -> i64 {
+    ffi_guard("get_live_frame_sequence", || {
+        match SIMULATIONS.get(handle) {
+            Some(shared) => shared.live_frames().sequence() as i64,
+            None => {
+                set_unknown_simulation_error(handle);
+                FFIErrorCode::UnknownHandle.return_code() as i64
+            }
         }
-     };
+    })
+}
 
-     // Call backend association logic
-     match formulas::set_association(type_str, id_str) {
-         Ok(_) => 0, // Success
-         Err(e) => {
-             set_last_ffi_error(format!("Failed to set formula association: {}", e));
-             -5 // Error during association
-         }
-     }
+/// Trava o mapa de canais compartilhados; o mapa nunca fica inconsistente, então o
+/// envenenamento por pânico é ignorado
+fn shared_frame_channels() -> std::sync::MutexGuard<'static, BTreeMap<SimulationHandle, Arc<SharedFrameChannel>>> {
+    SHARED_FRAME_CHANNELS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
-/// Gets the ID of the formula associated with a function type.
-/// Returns the formula ID as a string, or null if none is set or on error.
-/// Caller must free the returned string using `free_rust_string`.
+fn empty_shared_frame_channel() -> FFISharedFrameChannel {
+    FFISharedFrameChannel { ptr: ptr::null(), len: 0, nr: 0, nz: 0 }
+}
+
+/// Opens a shared-memory channel that receives every live frame of simulation `handle`
+/// without per-frame FFI copies. Call it once and keep the returned region; it stays at
+/// the same address until `close_shared_frame_channel` or `destroy_simulation_h`, across
+/// runs. Calling it again returns the same channel (close it first to resize it after the
+/// mesh changes; frames with other dimensions are not written).
+///
+/// Layout (native-endian 64-bit words): 0 = sequence counter, 1 = step, 2 = time (f64
+/// bits), 3 = nr, 4 = nz, 5..8 reserved, then nr × nz temperatures (f64, index
+/// `i * nz + j`) from word 8. Read protocol: load the sequence; if it is 0 there is no
+/// frame yet, if it is odd a write is in progress (retry); copy or view the frame, then
+/// load the sequence again and discard the read if it changed. The sequence grows by 2 per
+/// frame. Returns a null region on error. Do not free the region.
 #[no_mangle]
-pub extern "C" fn get_formula_for_function_json(function_type: *const c_char) -> *mut c_char {
-     if function_type.is_null() {
-         // set_last_ffi_error("get_formula_for_function_json: function_type pointer was null".to_string()); // Optional error
-         return ptr::null_mut();
-     }
+pub extern "C" fn open_shared_frame_channel(handle: SimulationHandle) -> FFISharedFrameChannel {
+    ffi_guard("open_shared_frame_channel", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return empty_shared_frame_channel();
+        };
+        let (nr, nz) = match shared.get_state() {
+            Ok(state) => (state.parameters.nr, state.parameters.nz),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, e);
+                return empty_shared_frame_channel();
+            }
+        };
 
-     let type_str = match unsafe { CStr::from_ptr(function_type).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in function_type string: {}", e));
-            return ptr::null_mut();
+        let channel = shared_frame_channels()
+            .entry(handle)
+            .or_insert_with(|| Arc::new(SharedFrameChannel::new(nr, nz)))
+            .clone();
+        shared.live_frames().attach_channel(Some(channel.clone()));
+
+        let (nr, nz) = channel.dimensions();
+        FFISharedFrameChannel { ptr: channel.as_ptr(), len: channel.size_bytes(), nr, nz }
+    })
+}
+
+/// Closes the shared-memory frame channel of simulation `handle`. The region returned by
+/// `open_shared_frame_channel` is released and must not be read afterwards.
+/// Returns 0 on success, `NotAvailable` (negated) if no channel is open for the handle.
+#[no_mangle]
+pub extern "C" fn close_shared_frame_channel(handle: SimulationHandle) -> c_int {
+    ffi_guard("close_shared_frame_channel", || {
+        let Some(channel) = shared_frame_channels().remove(&handle) else {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, format!("No shared frame channel open for handle {}.", handle));
+            return FFIErrorCode::NotAvailable.return_code();
+        };
+        if let Some(shared) = SIMULATIONS.get(handle) {
+            if shared.live_frames().channel().is_some_and(|attached| Arc::ptr_eq(&attached, &channel)) {
+                shared.live_frames().attach_channel(None);
+            }
         }
-     };
+        0
+    })
+}
 
-    // Call backend logic
-    match formulas::get_association(type_str) {
-        Ok(Some(formula_id)) => { // Association found
-            CString::new(formula_id).map_or_else(|e| {
-                set_last_ffi_error(format!("Failed to create CString for formula ID: {}", e));
-                ptr::null_mut()
-            }, |c_str| c_str.into_raw())
+/// Adds the results of the completed simulation to the ensemble (e.g. successive
+/// stochastic-feed or UQ runs of the same configuration). The first call after
+/// `reset_ensemble` starts a new ensemble. Returns the number of runs in the ensemble,
+/// or a negative `FFIErrorCode` on error (results missing, different mesh or time step).
+#[no_mangle]
+pub extern "C" fn add_results_to_ensemble_h(handle: SimulationHandle) -> c_int {
+    ffi_guard("add_results_to_ensemble_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state = match shared.state.lock() {
+            Ok(state) => state,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while adding results to ensemble: {}", poison_err));
+                return FFIErrorCode::LockPoisoned.return_code();
+            }
+        };
+        let results = match state.results.as_ref() {
+            Some(results) => results,
+            None => {
+                set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
+                return FFIErrorCode::NotAvailable.return_code();
+            }
+        };
+
+        let mut ensemble = match ENSEMBLE.lock() {
+            Ok(ensemble) => ensemble,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while accessing ensemble: {}", poison_err));
+                return FFIErrorCode::LockPoisoned.return_code();
+            }
+        };
+        if ensemble.is_none() {
+            // Média e soma dos quadrados do histórico de temperatura
+            let estimated = (results.temperature.len() * 2 * std::mem::size_of::<f64>()) as u64;
+            if let Err(error) = limits::check_request("add_results_to_ensemble", estimated, &[
+                ("time_steps", "Run the ensemble members with fewer output steps or a coarser mesh"),
+                ("set_ffi_memory_cap", "Raise the memory cap if the device has enough memory"),
+            ]) {
+                report_oversized_request(error);
+                return FFI_REQUEST_TOO_LARGE_ERROR_CODE;
+            }
         }
-        Ok(None) => { // No association found
-            ptr::null_mut()
+        let added = match ensemble.as_mut() {
+            Some(accumulator) => accumulator.add(results).map(|_| accumulator.runs),
+            None => EnsembleAccumulator::new(results).map(|accumulator| {
+                *ensemble = Some(accumulator);
+                1
+            }),
+        };
+        match added {
+            Ok(runs) => runs as c_int,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to add results to ensemble: {}", e));
+                FFIErrorCode::OperationFailed.return_code()
+            }
         }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to get formula association: {}", e));
-            ptr::null_mut()
+    })
+}
+
+/// Deprecated single-instance form of `add_results_to_ensemble_h`, operating on the default simulation.
+#[deprecated(note = "use `add_results_to_ensemble_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn add_results_to_ensemble() -> c_int {
+    add_results_to_ensemble_h(SIMULATIONS.default_handle())
+}
+
+/// Discards the accumulated ensemble statistics. Returns 0 on success.
+#[no_mangle]
+pub extern "C" fn reset_ensemble() -> c_int {
+    ffi_guard("reset_ensemble", || {
+        match ENSEMBLE.lock() {
+            Ok(mut ensemble) => {
+                *ensemble = None;
+                0
+            }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while resetting ensemble: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
+}
+
+/// Gets an ensemble statistics field as a binary frame packet, in the same layout as
+/// `get_frame_packet`, for uncertainty heatmaps. `statistic`: 0 = mean, 1 = standard
+/// deviation. `encoding`: 0 = f32, 1 = u16 quantized. Returns an empty buffer on error.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
+#[no_mangle]
+pub extern "C" fn get_ensemble_frame_packet(statistic: c_int, time_step: c_int, encoding: c_int) -> FFIByteBuffer {
+    ffi_guard("get_ensemble_frame_packet", || {
+        let ensemble_statistic = match u8::try_from(statistic).ok().and_then(EnsembleStatistic::from_code) {
+            Some(ensemble_statistic) => ensemble_statistic,
+            None => {
+                set_last_ffi_error(format!("Unknown ensemble statistic code: {}", statistic));
+                return empty_ffi_byte_buffer();
+            }
+        };
+        let frame_encoding = match u8::try_from(encoding).ok().and_then(FrameEncoding::from_code) {
+            Some(frame_encoding) => frame_encoding,
+            None => {
+                set_last_ffi_error(format!("Unknown frame encoding code: {}", encoding));
+                return empty_ffi_byte_buffer();
+            }
+        };
+        if time_step < 0 {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid time step index: {}", time_step));
+            return empty_ffi_byte_buffer();
+        }
+
+        match ENSEMBLE.lock() {
+            Ok(ensemble) => match ensemble.as_ref() {
+                Some(accumulator) => match accumulator.frame_packet(ensemble_statistic, time_step as usize, frame_encoding) {
+                    Ok(packet) => vec_to_ffi_byte_buffer(packet.encode()),
+                    Err(e) => {
+                        set_last_ffi_error(format!("Failed to build ensemble frame packet: {}", e));
+                        empty_ffi_byte_buffer()
+                    }
+                },
+                None => {
+                    set_last_ffi_error("Ensemble is empty (call add_results_to_ensemble first).".to_string());
+                    empty_ffi_byte_buffer()
+                }
+            },
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while building ensemble frame packet: {}", poison_err));
+                empty_ffi_byte_buffer()
+            }
         }
-    }
+    })
 }
 
-// --- FFI Functions for Metrics & Export (JSON based) ---
-
-/// Calculates simulation metrics based on the current simulation state/results.
-/// Returns metrics as a JSON string.
-/// Caller must free the returned string using `free_rust_string`.
+/// Gets the available temporal pyramid strides (including 1 for the full history)
+/// as a JSON list. Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
-pub extern "C" fn calculate_metrics_json() -> *mut c_char {
-    unsafe {
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized.".to_string());
+pub extern "C" fn get_temporal_pyramid_strides_json_h(handle: SimulationHandle) -> *mut c_char {
+    ffi_guard("get_temporal_pyramid_strides_json_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
             return ptr::null_mut();
-        }
-
-        // Lock state to access results
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
-            Ok(state) => {
-                if let Some(results) = &state.results {
-                    // Call backend metrics calculation
-                    match metrics::calculate(results) {
-                        Ok(metrics_json) => {
-                            CString::new(metrics_json).map_or_else(|e| {
-                                set_last_ffi_error(format!("Failed to create CString for metrics JSON: {}", e));
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => match state.results.as_ref() {
+                Some(results) => {
+                    let strides = results.temporal_pyramid.as_ref()
+                        .map_or_else(|| vec![1], |pyramid| pyramid.strides());
+                    match serde_json::to_string(&strides) {
+                        Ok(json_string) => {
+                            CString::new(json_string).map_or_else(|e| {
+                                set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
                                 ptr::null_mut()
-                            }, |c_str| c_str.into_raw())
+                            }, into_ffi_string_ptr)
                         }
                         Err(e) => {
-                            set_last_ffi_error(format!("Failed to calculate metrics: {}", e));
+                            set_last_ffi_error(format!("Failed to serialize pyramid strides to JSON: {}", e));
                             ptr::null_mut()
                         }
                     }
-                } else {
-                    set_last_ffi_error("Simulation results not available (simulation not completed or results missing).".to_string());
+                }
+                None => {
+                    set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or results missing).".to_string());
                     ptr::null_mut()
                 }
-            }
+            },
             Err(poison_err) => {
-                set_last_ffi_error(format!("Mutex poisoned while calculating metrics: {}", poison_err));
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while reading pyramid strides: {}", poison_err));
                 ptr::null_mut()
             }
         }
-    }
+    })
 }
 
-/// Exports simulation results based on options provided as a JSON string.
-/// Returns 0 on success, negative on error.
+/// Deprecated single-instance form of `get_temporal_pyramid_strides_json_h`, operating on the default simulation.
+#[deprecated(note = "use `get_temporal_pyramid_strides_json_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn export_results_json(options_json: *const c_char) -> c_int {
-     if options_json.is_null() {
-         set_last_ffi_error("export_results_json: options_json pointer was null".to_string());
-         return -1;
-     }
-
-     let options_str = match unsafe { CStr::from_ptr(options_json).to_str() } {
-        Ok(s) => s,
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in options_json string: {}", e));
-            return -2; // Invalid input error
-        }
-     };
-
-     // Deserialize options (assuming an ExportOptions struct exists in crate::export)
-     let options: export::ExportOptions = match serde_json::from_str(options_str) {
-         Ok(opts) => opts,
-         Err(e) => {
-             set_last_ffi_error(format!("Failed to deserialize export options JSON: {}", e));
-             return -3; // Deserialization error
-         }
-     };
-
-     // Access simulation results
-     unsafe {
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized.".to_string());
-            return -4; // Not initialized
-        }
-
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
-             Ok(state) => {
-                 if let Some(results) = &state.results {
-                    // Call backend export function
-                    match export::export_results(results, options) {
-                        Ok(_) => 0, // Success
-                        Err(e) => {
-                            set_last_ffi_error(format!("Failed to export results: {}", e));
-                            -5 // Export error
-                        }
-                    }
-                 } else {
-                     set_last_ffi_error("Simulation results not available for export.".to_string());
-                     -6 // Results not ready
-                 }
-             }
-             Err(poison_err) => {
-                 set_last_ffi_error(format!("Mutex poisoned while exporting results: {}", poison_err));
-                 -7 // Mutex error
-             }
-         }
-     }
+pub extern "C" fn get_temporal_pyramid_strides_json() -> *mut c_char {
+    get_temporal_pyramid_strides_json_h(SIMULATIONS.default_handle())
 }
 
-/// Generates a report (e.g., PDF, HTML) at the specified output path.
-/// Requires calculated metrics and results.
-/// Returns 0 on success, negative on error.
+/// Runs a parametric study whose configuration is encoded with the negotiated format
+/// and returns the study result encoded with the same format.
+/// Caller must free the returned buffer using `free_ffi_byte_buffer`.
 #[no_mangle]
-pub extern "C" fn generate_report_json(output_path: *const c_char) -> c_int {
-     if output_path.is_null() {
-         set_last_ffi_error("generate_report_json: output_path pointer was null".to_string());
-         return -1;
-     }
-
-     let path_str = match unsafe { CStr::from_ptr(output_path).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in output_path string: {}", e));
-            return -2; // Invalid input error
-        }
-     };
-
-    // Access simulation results and potentially calculate metrics first
-     unsafe {
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized.".to_string());
-            return -3; // Not initialized
+pub extern "C" fn run_parametric_study_payload(config_ptr: *const u8, config_len: usize) -> FFIByteBuffer {
+    ffi_guard("run_parametric_study_payload", || {
+        if config_ptr.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "run_parametric_study_payload: config pointer was null".to_string());
+            return empty_ffi_byte_buffer();
         }
 
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
-            Ok(state) => {
-                 if let Some(results) = &state.results {
-                    // TODO: Decide if metrics should be calculated here or passed in.
-                    // For simplicity, let's assume report generation uses results directly
-                    // or calls metrics::calculate internally if needed.
-
-                    // Call backend report generation function
-                    match reporting::generate_report(results, path_str) { // Assuming generate_report takes results and path
-                        Ok(_) => 0, // Success
-                        Err(e) => {
-                            set_last_ffi_error(format!("Failed to generate report: {}", e));
-                            -4 // Report generation error
-                        }
-                    }
-                 } else {
-                     set_last_ffi_error("Simulation results not available for report generation.".to_string());
-                     -5 // Results not ready
-                 }
+        let config_bytes = unsafe { slice::from_raw_parts(config_ptr, config_len) };
+        let config: ParametricStudyConfig = match payload::decode_payload(config_bytes, current_payload_format()) {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to decode study config: {}", e));
+                return empty_ffi_byte_buffer();
+            }
+        };
+
+        let params = match simulation_parameters_for_study(SIMULATIONS.default_handle()) {
+            Some(params) => params,
+            None => return empty_ffi_byte_buffer(),
+        };
+
+        match ParametricStudyManager::new(config, params).run_study() {
+            Ok(study_result) => encode_ffi_payload(&study_result),
+            Err(e) => {
+                set_last_ffi_error(format!("Parametric study failed: {}", e));
+                empty_ffi_byte_buffer()
             }
-             Err(poison_err) => {
-                 set_last_ffi_error(format!("Mutex poisoned while generating report: {}", poison_err));
-                 -6 // Mutex error
-             }
         }
-     }
+    })
 }
 
 // --- FFI Functions for Parametric Studies (JSON based) ---
@@ -1009,67 +3695,63 @@ pub extern "C" fn generate_report_json(output_path: *const c_char) -> c_int {
 /// Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
 pub extern "C" fn get_predefined_studies_json() -> *mut c_char {
-    match parametric::get_predefined_studies() {
-        Ok(configs) => {
-             match serde_json::to_string(&configs) {
-                Ok(json_string) => {
-                    CString::new(json_string).map_or_else(|e| {
-                        set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
-                        ptr::null_mut()
-                    }, |c_str| c_str.into_raw())
-                }
-                Err(e) => {
-                    set_last_ffi_error(format!("Failed to serialize predefined studies to JSON: {}", e));
+    ffi_guard("get_predefined_studies_json", || {
+        let configs: Vec<ParametricStudyConfig> = parametric_study::PREDEFINED_STUDY_TYPES
+            .iter()
+            .filter_map(|study_type| ParametricStudyManager::predefined_study(study_type))
+            .collect();
+        match serde_json::to_string(&configs) {
+            Ok(json_string) => {
+                CString::new(json_string).map_or_else(|e| {
+                    set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
                     ptr::null_mut()
-                }
+                }, into_ffi_string_ptr)
+            }
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to serialize predefined studies to JSON: {}", e));
+                ptr::null_mut()
             }
         }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to get predefined studies: {}", e));
-            ptr::null_mut()
-        }
-    }
+    })
 }
 
 /// Gets a specific predefined study configuration by type name as a JSON string.
 /// Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
 pub extern "C" fn get_predefined_study_json(study_type: *const c_char) -> *mut c_char {
-    if study_type.is_null() {
-        return ptr::null_mut();
-    }
-    let type_str = match unsafe { CStr::from_ptr(study_type).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in study_type string: {}", e));
+    ffi_guard("get_predefined_study_json", || {
+        if study_type.is_null() {
             return ptr::null_mut();
         }
-    };
-
-    match parametric::get_predefined_study(type_str) {
-        Ok(Some(config)) => { // Config found
-            match serde_json::to_string(&config) {
-                Ok(json_string) => {
-                    CString::new(json_string).map_or_else(|e| {
-                        set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
+        let type_str = match unsafe { CStr::from_ptr(study_type).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in study_type string: {}", e));
+                return ptr::null_mut();
+            }
+        };
+
+        match ParametricStudyManager::predefined_study(&type_str) {
+            Some(config) => {
+                match serde_json::to_string(&config) {
+                    Ok(json_string) => {
+                        CString::new(json_string).map_or_else(|e| {
+                            set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
+                            ptr::null_mut()
+                        }, into_ffi_string_ptr)
+                    }
+                    Err(e) => {
+                        set_last_ffi_error(format!("Failed to serialize predefined study to JSON: {}", e));
                         ptr::null_mut()
-                    }, |c_str| c_str.into_raw())
-                }
-                Err(e) => {
-                    set_last_ffi_error(format!("Failed to serialize predefined study to JSON: {}", e));
-                    ptr::null_mut()
+                    }
                 }
             }
+            None => {
+                set_last_ffi_error(format!("Unknown predefined study type: {}", type_str));
+                ptr::null_mut()
+            }
         }
-        Ok(None) => { // Config not found
-            // set_last_ffi_error("Predefined study not found".to_string()); // Optional
-            ptr::null_mut()
-        }
-        Err(e) => {
-            set_last_ffi_error(format!("Failed to get predefined study: {}", e));
-            ptr::null_mut()
-        }
-    }
+    })
 }
 
 /// Runs a parametric study based on the configuration provided as a JSON string.
@@ -1077,251 +3759,583 @@ pub extern "C" fn get_predefined_study_json(study_type: *const c_char) -> *mut c
 /// Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
 pub extern "C" fn run_parametric_study_json(config_json: *const c_char) -> *mut c_char {
-     if config_json.is_null() {
-         set_last_ffi_error("run_parametric_study_json: config_json pointer was null".to_string());
-         return ptr::null_mut();
-     }
-
-     let config_str = match unsafe { CStr::from_ptr(config_json).to_str() } {
-        Ok(s) => s,
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in config_json string: {}", e));
-            return ptr::null_mut();
-        }
-     };
-
-     // Deserialize config (assuming StudyConfig struct in crate::parametric)
-     let config: parametric::StudyConfig = match serde_json::from_str(config_str) {
-         Ok(cfg) => cfg,
-         Err(e) => {
-             set_last_ffi_error(format!("Failed to deserialize study config JSON: {}", e));
+    ffi_guard("run_parametric_study_json", || {
+         if config_json.is_null() {
+             set_last_ffi_error_code(FFIErrorCode::NullPointer, "run_parametric_study_json: config_json pointer was null".to_string());
              return ptr::null_mut();
          }
-     };
 
-     // Call backend run study function (this might be long-running)
-     // TODO: Consider running this in a separate thread similar to run_simulation?
-     match parametric::run_study(config) {
-        Ok(study_result) => {
-             match serde_json::to_string(&study_result) {
-                Ok(json_string) => {
-                    CString::new(json_string).map_or_else(|e| {
-                        set_last_ffi_error(format!("Failed to create CString for study result JSON: {}", e));
+         let config_str = match unsafe { CStr::from_ptr(config_json).to_str() } {
+            Ok(s) => s,
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in config_json string: {}", e));
+                return ptr::null_mut();
+            }
+         };
+
+         let config: ParametricStudyConfig = match serde_json::from_str(config_str) {
+             Ok(cfg) => cfg,
+             Err(e) => {
+                 set_last_ffi_error(format!("Failed to deserialize study config JSON: {}", e));
+                 return ptr::null_mut();
+             }
+         };
+
+         let params = match simulation_parameters_for_study(SIMULATIONS.default_handle()) {
+             Some(params) => params,
+             None => return ptr::null_mut(),
+         };
+
+         // Blocks until the study finishes; `start_parametric_study_json` runs it in the background
+         match ParametricStudyManager::new(config, params).run_study() {
+            Ok(study_result) => {
+                 match serde_json::to_string(&study_result) {
+                    Ok(json_string) => {
+                        CString::new(json_string).map_or_else(|e| {
+                            set_last_ffi_error(format!("Failed to create CString for study result JSON: {}", e));
+                            ptr::null_mut()
+                        }, into_ffi_string_ptr)
+                    }
+                    Err(e) => {
+                        set_last_ffi_error(format!("Failed to serialize study result to JSON: {}", e));
                         ptr::null_mut()
-                    }, |c_str| c_str.into_raw())
+                    }
                 }
-                Err(e) => {
-                    set_last_ffi_error(format!("Failed to serialize study result to JSON: {}", e));
-                    ptr::null_mut()
+            }
+            Err(e) => {
+                set_last_ffi_error(format!("Parametric study failed: {}", e));
+                ptr::null_mut()
+            }
+         }
+    })
+}
+
+/// Finds a parametric study started with `start_parametric_study_json` by its handle.
+fn find_parametric_study(handle: u64, fn_name: &str) -> Option<QueuedStudy> {
+    let study = PARAMETRIC_STUDIES.lock().ok()
+        .and_then(|studies| studies.iter().find(|study| study.job_id == handle).cloned());
+    if study.is_none() {
+        set_last_ffi_error(format!("{}: unknown parametric study handle {}", fn_name, handle));
+    }
+    study
+}
+
+/// Starts a parametric study (`ParametricStudyConfig` JSON) in the background job queue,
+/// on the parameters of simulation `handle` (mesh, time step, torches and material), and
+/// returns its job handle immediately. Use `get_parametric_study_progress_json` to follow
+/// it, `cancel_parametric_study` to stop it and `get_parametric_study_result_json` to fetch
+/// the result.
+/// Returns the handle (> 0) or a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn start_parametric_study_json_h(handle: SimulationHandle, config_json: *const c_char) -> i64 {
+    ffi_guard("start_parametric_study_json_h", || {
+        let config: ParametricStudyConfig = match read_ffi_str(config_json, "start_parametric_study_json", "config_json")
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| FFICallError::invalid(format!("Failed to parse parametric study config JSON: {}", e))))
+        {
+            Ok(config) => config,
+            Err(e) => return e.record() as i64,
+        };
+
+        submit_parametric_study(handle, config)
+    })
+}
+
+/// Deprecated single-instance form of `start_parametric_study_json_h`, operating on the default simulation.
+#[deprecated(note = "use `start_parametric_study_json_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn start_parametric_study_json(config_json: *const c_char) -> i64 {
+    start_parametric_study_json_h(SIMULATIONS.default_handle(), config_json)
+}
+
+/// Enfileira um estudo paramétrico sobre os parâmetros da simulação `handle`,
+/// registrando-o para as consultas de progresso; retorna o identificador da tarefa ou o
+/// código de erro negativo já registrado
+fn submit_parametric_study(handle: SimulationHandle, config: ParametricStudyConfig) -> i64 {
+    let params = match simulation_parameters_for_study(handle) {
+        Some(params) => params,
+        None => return recorded_error_code() as i64,
+    };
+    let manager = ParametricStudyManager::new(config, params);
+    #[cfg(feature = "sqlite")]
+    let manager = attach_parametric_store(manager);
+
+    match parametric_study::submit_study(&JOB_QUEUE, manager) {
+        Ok(study) => {
+            let job_id = study.job_id;
+            match PARAMETRIC_STUDIES.lock() {
+                Ok(mut studies) => {
+                    studies.push(study);
+                    job_id as i64
+                }
+                Err(poison_err) => {
+                    study.handle.cancel();
+                    set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while registering parametric study: {}", poison_err));
+                    FFIErrorCode::LockPoisoned.return_code() as i64
                 }
             }
         }
         Err(e) => {
-            set_last_ffi_error(format!("Parametric study failed: {}", e));
-            ptr::null_mut()
+            set_last_ffi_error(format!("Failed to start parametric study: {}", e));
+            FFIErrorCode::OperationFailed.return_code() as i64
         }
-     }
+    }
 }
 
-/// Generates a report for a parametric study result provided as a JSON string.
-/// Returns 0 on success, negative on error.
+/// Clones the parameters of `handle` as the base case of a parametric study,
+/// setting the FFI error and returning `None` when they are unavailable.
+fn simulation_parameters_for_study(handle: SimulationHandle) -> Option<SimulationParameters> {
+    let params = SIMULATIONS.get(handle).map(|shared| {
+        shared.state.lock().map(|state| state.parameters.clone()).map_err(|poison_err| poison_err.to_string())
+    });
+    match params {
+        Some(Ok(params)) => Some(params),
+        Some(Err(poison_err)) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while starting parametric study: {}", poison_err));
+            None
+        }
+        None => {
+            set_unknown_simulation_error(handle);
+            None
+        }
+    }
+}
+
+/// Gets the progress of a background parametric study as JSON:
+/// `{"job": JobInfo, "progress": {"total_cases", "completed_cases", "failed_cases",
+/// "best_so_far", "results", "cancelled", "finished"}}`, where `results` holds the
+/// cases completed so far. Returns null for an unknown handle.
+/// Caller must free the returned string using `free_rust_string`.
 #[no_mangle]
-pub extern "C" fn generate_parametric_study_report_json(result_json: *const c_char, output_path: *const c_char) -> c_int {
-     if result_json.is_null() {
-         set_last_ffi_error("generate_parametric_study_report_json: result_json pointer was null".to_string());
-         return -1;
-     }
-     if output_path.is_null() {
-         set_last_ffi_error("generate_parametric_study_report_json: output_path pointer was null".to_string());
-         return -2;
-     }
-
-     let result_str = match unsafe { CStr::from_ptr(result_json).to_str() } {
-        Ok(s) => s,
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in result_json string: {}", e));
-            return -3;
+pub extern "C" fn get_parametric_study_progress_json(handle: u64) -> *mut c_char {
+    ffi_guard("get_parametric_study_progress_json", || {
+        let Some(study) = find_parametric_study(handle, "get_parametric_study_progress_json") else {
+            return ptr::null_mut();
+        };
+        json_ffi_string(&serde_json::json!({
+            "job": JOB_QUEUE.info(study.job_id),
+            "progress": study.handle.progress(),
+        }))
+    })
+}
+
+/// Requests cancellation of a background parametric study. A queued study never starts;
+/// a running study stops before its next case and keeps the cases already completed.
+/// Returns 0 on success, a negative `FFIErrorCode` on error (e.g. an unknown study handle).
+#[no_mangle]
+pub extern "C" fn cancel_parametric_study(handle: u64) -> c_int {
+    ffi_guard("cancel_parametric_study", || {
+        match find_parametric_study(handle, "cancel_parametric_study") {
+            Some(study) => {
+                study.handle.cancel();
+                JOB_QUEUE.cancel(study.job_id);
+                0
+            }
+            None => recorded_error_code(),
         }
-     };
-     let path_str = match unsafe { CStr::from_ptr(output_path).to_str() } {
-        Ok(s) => s.to_string(),
-        Err(e) => {
-            set_last_ffi_error(format!("Invalid UTF-8 in output_path string: {}", e));
-            return -4;
+    })
+}
+
+/// Gets the result of a finished background parametric study as JSON
+/// (`ParametricStudyResult`; partial if the study was cancelled). Returns null for an
+/// unknown handle or while the study is still running.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_parametric_study_result_json(handle: u64) -> *mut c_char {
+    ffi_guard("get_parametric_study_result_json", || {
+        let Some(study) = find_parametric_study(handle, "get_parametric_study_result_json") else {
+            return ptr::null_mut();
+        };
+        match study.result() {
+            Some(result) => json_ffi_string(&result),
+            None => {
+                set_last_ffi_error(format!("Parametric study {} has no result yet", handle));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Attaches the open parametric results store, if any, to a study manager.
+#[cfg(feature = "sqlite")]
+fn attach_parametric_store(mut manager: ParametricStudyManager) -> ParametricStudyManager {
+    if let Some(store) = PARAMETRIC_STORE.lock().ok().and_then(|store| store.clone()) {
+        manager.set_case_store(store);
+    }
+    manager
+}
+
+/// Runs `body` with the open parametric results store.
+#[cfg(feature = "sqlite")]
+fn with_parametric_store<R>(on_error: R, body: impl FnOnce(&mut StudyStore) -> Result<R, String>) -> R {
+    let store = match PARAMETRIC_STORE.lock() {
+        Ok(store) => store.clone(),
+        Err(poison_err) => {
+            set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while accessing parametric store: {}", poison_err));
+            return on_error;
+        }
+    };
+    let Some(store) = store else {
+        set_last_ffi_error("No parametric store open. Call open_parametric_store first.".to_string());
+        return on_error;
+    };
+    let result = match store.lock() {
+        Ok(mut store) => body(&mut store),
+        Err(poison_err) => Err(format!("Mutex poisoned while accessing parametric store: {}", poison_err)),
+    };
+    result.unwrap_or_else(|e| {
+        set_last_ffi_error(e);
+        on_error
+    })
+}
+
+/// Opens (or creates) the SQLite database at `path` in which every case of the parametric
+/// studies started afterwards is stored (parameters, metrics and timing). Requires the
+/// `sqlite` feature. Returns 0 on success, a negative `FFIErrorCode` on error.
+#[cfg(feature = "sqlite")]
+#[no_mangle]
+pub extern "C" fn open_parametric_store(path: *const c_char) -> c_int {
+    ffi_guard("open_parametric_store", || {
+        let store = match read_ffi_str(path, "open_parametric_store", "path")
+            .and_then(|path| StudyStore::open(std::path::Path::new(&path)).map_err(FFICallError::failed))
+        {
+            Ok(store) => store,
+            Err(e) => return e.record(),
+        };
+        match PARAMETRIC_STORE.lock() {
+            Ok(mut current) => {
+                *current = Some(Arc::new(Mutex::new(store)));
+                0
+            }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while opening parametric store: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
         }
-     };
+    })
+}
+
+/// Lists the studies in the parametric store as a JSON array of
+/// `{"name", "target_metric", "optimization_goal", "case_count"}`. Returns null on error.
+/// Caller must free the returned string using `free_rust_string`.
+#[cfg(feature = "sqlite")]
+#[no_mangle]
+pub extern "C" fn list_parametric_store_studies_json() -> *mut c_char {
+    ffi_guard("list_parametric_store_studies_json", || {
+        with_parametric_store(ptr::null_mut(), |store| Ok(json_ffi_string(&store.list_studies()?)))
+    })
+}
+
+/// Queries the stored cases of a study with a `CaseQuery` JSON object, e.g.
+/// `{"filters": [{"field": {"parameter": "torch_power"}, "min_value": 100}],
+/// "sort_by": "target_metric", "descending": true, "limit": 50, "offset": 0}`.
+/// Returns a JSON array of `ParametricSimulationResult`, or null on error.
+/// Caller must free the returned string using `free_rust_string`.
+#[cfg(feature = "sqlite")]
+#[no_mangle]
+pub extern "C" fn query_parametric_cases_json(study: *const c_char, query_json: *const c_char) -> *mut c_char {
+    ffi_guard("query_parametric_cases_json", || {
+        let request = read_ffi_str(study, "query_parametric_cases_json", "study").and_then(|study| {
+            let query: CaseQuery = read_ffi_str(query_json, "query_parametric_cases_json", "query_json")
+                .and_then(|json| serde_json::from_str(&json).map_err(|e| FFICallError::invalid(format!("Failed to parse case query JSON: {}", e))))?;
+            Ok((study, query))
+        });
+        let (study, query) = match request {
+            Ok(request) => request,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_parametric_store(ptr::null_mut(), |store| Ok(json_ffi_string(&store.query_cases(&study, &query)?)))
+    })
+}
+
+/// Gets the `count` best stored cases of a study by its target metric and optimization
+/// goal, as a JSON array of `ParametricSimulationResult`. Returns null on error.
+/// Caller must free the returned string using `free_rust_string`.
+#[cfg(feature = "sqlite")]
+#[no_mangle]
+pub extern "C" fn top_parametric_cases_json(study: *const c_char, count: usize) -> *mut c_char {
+    ffi_guard("top_parametric_cases_json", || {
+        let study = match read_ffi_str(study, "top_parametric_cases_json", "study") {
+            Ok(study) => study,
+            Err(e) => {
+                e.record();
+                return ptr::null_mut();
+            }
+        };
+        with_parametric_store(ptr::null_mut(), |store| Ok(json_ffi_string(&store.top_cases(&study, count)?)))
+    })
+}
 
-    // Deserialize result (assuming StudyResult struct in crate::parametric)
-     let result: parametric::StudyResult = match serde_json::from_str(result_str) {
-         Ok(res) => res,
-         Err(e) => {
-             set_last_ffi_error(format!("Failed to deserialize study result JSON: {}", e));
-             return -5;
+/// Generates a report for a parametric study result provided as a JSON string.
+/// Returns 0 on success, a negative `FFIErrorCode` on error.
+#[no_mangle]
+pub extern "C" fn generate_parametric_study_report_json(result_json: *const c_char, output_path: *const c_char) -> c_int {
+    ffi_guard("generate_parametric_study_report_json", || {
+         if result_json.is_null() {
+             set_last_ffi_error_code(FFIErrorCode::NullPointer, "generate_parametric_study_report_json: result_json pointer was null".to_string());
+             return FFIErrorCode::NullPointer.return_code();
          }
-     };
-
-    // Call backend report generation
-     match reporting::generate_parametric_report(&result, path_str) {
-         Ok(_) => 0, // Success
-         Err(e) => {
-             set_last_ffi_error(format!("Failed to generate parametric study report: {}", e));
-             -6 // Report generation error
+         if output_path.is_null() {
+             set_last_ffi_error_code(FFIErrorCode::NullPointer, "generate_parametric_study_report_json: output_path pointer was null".to_string());
+             return FFIErrorCode::NullPointer.return_code();
+         }
+
+         let result_str = match unsafe { CStr::from_ptr(result_json).to_str() } {
+            Ok(s) => s,
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in result_json string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+         };
+         let path_str = match unsafe { CStr::from_ptr(output_path).to_str() } {
+            Ok(s) => s.to_string(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidUtf8, format!("Invalid UTF-8 in output_path string: {}", e));
+                return FFIErrorCode::InvalidUtf8.return_code();
+            }
+         };
+
+         let result: ParametricStudyResult = match serde_json::from_str(result_str) {
+             Ok(res) => res,
+             Err(e) => {
+                 set_last_ffi_error(format!("Failed to deserialize study result JSON: {}", e));
+                 return FFIErrorCode::OperationFailed.return_code();
+             }
+         };
+
+         match ParametricStudyManager::generate_report(&result, &path_str) {
+             Ok(_) => 0, // Success
+             Err(e) => {
+                 set_last_ffi_error(format!("Failed to generate parametric study report: {}", e));
+                 FFIErrorCode::OperationFailed.return_code()
+             }
          }
-     }
+    })
 }
 
 // API FFI
 
-/// Inicializa a simulação com os parâmetros especificados
-#[no_mangle]
-pub extern "C" fn initialize_simulation(ffi_params: *const FFISimulationParameters) -> c_int {
-    // Check for null pointer
+/// Converts and validates FFI simulation parameters into a new (not yet registered)
+/// simulation state. Fails with `NullPointer` or `InvalidArgument` (negated).
+fn new_simulation_state(ffi_params: *const FFISimulationParameters, function_name: &str) -> Result<SharedSimulationState, c_int> {
     if ffi_params.is_null() {
-        set_last_ffi_error("initialize_simulation: ffi_params pointer was null".to_string());
-        return -1; // Null pointer error
-    }
-    
-    // Ensure simulation is not already initialized
-    unsafe {
-        if SIMULATION_STATE.is_some() {
-            set_last_ffi_error("Simulation already initialized. Call destroy_simulation first.".to_string());
-            return -2; // Already initialized error
-        }
+        set_last_ffi_error_code(FFIErrorCode::NullPointer, format!("{}: ffi_params pointer was null", function_name));
+        return Err(FFIErrorCode::NullPointer.return_code()); // Null pointer error
     }
 
     // Convert FFI parameters to Rust SimulationParameters
     let params = convert_ffi_parameters(unsafe { &*ffi_params });
-    
-    // Validate parameters before creating state
-    if let Err(validation_err) = params.validate() {
-        set_last_ffi_error(format!("Invalid simulation parameters: {}", validation_err));
-        return -3; // Invalid parameters
-    }
-    
-    // Create and store the shared state
-    unsafe {
-        SIMULATION_STATE = Some(SharedSimulationState::new(params));
+
+    // Validate parameters before creating state; torches are added afterwards and
+    // checked when the simulation runs
+    if let Err(validation_err) = params.validate_setup() {
+        set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid simulation parameters: {}", validation_err));
+        return Err(FFIErrorCode::InvalidArgument.return_code()); // Invalid parameters
     }
-    
-    0 // Success
+    Ok(SharedSimulationState::new(params))
+}
+
+/// Creates a new simulation instance with the given parameters and returns its opaque
+/// handle, to be passed to the `_h` functions. Several simulations may coexist; each must
+/// be released with `destroy_simulation_h`. Returns 0 (invalid handle) on error.
+#[no_mangle]
+pub extern "C" fn create_simulation(ffi_params: *const FFISimulationParameters) -> SimulationHandle {
+    ffi_guard("create_simulation", || {
+        match new_simulation_state(ffi_params, "create_simulation") {
+            Ok(state) => SIMULATIONS.insert(state),
+            Err(_) => INVALID_SIMULATION_HANDLE,
+        }
+    })
+}
+
+/// Gets the handle of the default simulation (created by `initialize_simulation`), so code
+/// migrating to the `_h` functions can keep using it. Returns 0 if there is none.
+#[no_mangle]
+pub extern "C" fn get_default_simulation_handle() -> SimulationHandle {
+    ffi_guard("get_default_simulation_handle", || SIMULATIONS.default_handle())
+}
+
+/// Gets the handles of all live simulations as a JSON list, in creation order.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn list_simulation_handles_json() -> *mut c_char {
+    ffi_guard("list_simulation_handles_json", || json_ffi_string(&SIMULATIONS.handles()))
+}
+
+/// Inicializa a simulação padrão com os parâmetros especificados
+#[deprecated(note = "use `create_simulation`, which returns a simulation handle")]
+#[no_mangle]
+pub extern "C" fn initialize_simulation(ffi_params: *const FFISimulationParameters) -> c_int {
+    ffi_guard("initialize_simulation", || {
+        // Lookup and creation of the default simulation happen under one registry lock,
+        // so concurrent callers cannot both create it
+        match SIMULATIONS.get_or_insert_default(|| new_simulation_state(ffi_params, "initialize_simulation")) {
+            Ok((_, true)) => 0, // Success
+            Ok((_, false)) => {
+                set_last_ffi_error("Simulation already initialized. Call destroy_simulation first.".to_string());
+                FFIErrorCode::OperationFailed.return_code()
+            }
+            Err(code) => code,
+        }
+    })
 }
 
 /// Adiciona uma tocha de plasma à simulação
+///
+/// Returns 0 on success or a negative `FFIErrorCode`: `NullPointer`, `UnknownHandle`,
+/// `OperationFailed` if the simulation already started, `LockPoisoned` and
+/// `InvalidArgument` for an unknown `flux_profile` code.
 #[no_mangle]
-pub extern "C" fn add_plasma_torch(ffi_torch: *const FFIPlasmaTorch) -> c_int {
-    if ffi_torch.is_null() {
-        set_last_ffi_error("add_plasma_torch: ffi_torch pointer was null".to_string());
-        return -1; // Null pointer error
-    }
+pub extern "C" fn add_plasma_torch_h(handle: SimulationHandle, ffi_torch: *const FFIPlasmaTorch) -> c_int {
+    ffi_guard("add_plasma_torch_h", || {
+        if ffi_torch.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "add_plasma_torch: ffi_torch pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
     
-    let torch = convert_ffi_torch(unsafe { &*ffi_torch });
+        let ffi_torch = unsafe { &*ffi_torch };
     
-    unsafe {
         // Check if state exists
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized. Call initialize_simulation first.".to_string());
-            return -2; // Not initialized error
-        }
-        
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
         // Lock the state mutex
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
+        let state_lock = shared.state.lock();
+        match state_lock {
             Ok(mut state) => {
                 // Check if simulation is already running/completed (cannot add torch then)
                 if state.status != crate::simulation::SimulationStatus::NotStarted {
                     set_last_ffi_error("Cannot add torch to a running or completed simulation.".to_string());
-                    return -3; // Cannot modify running/completed simulation
+                    return FFIErrorCode::OperationFailed.return_code();
+                }
+                // FFI torches carry no id; number them in insertion order
+                let id = format!("torch{}", state.parameters.torches.len() + 1);
+                match convert_ffi_torch(ffi_torch, &id) {
+                    Ok(torch) => {
+                        state.parameters.add_torch(torch);
+                        0 // Success
+                    }
+                    Err(e) => {
+                        set_last_ffi_error_code(FFIErrorCode::InvalidArgument, e);
+                        FFIErrorCode::InvalidArgument.return_code()
+                    }
                 }
-                state.parameters.add_torch(torch);
-                0 // Success
             }
             Err(poison_err) => {
-                set_last_ffi_error(format!("Mutex poisoned while adding torch: {}", poison_err));
-                -4 // Mutex poisoned error
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while adding torch: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
             }
         }
-    }
+    })
+}
+
+/// Deprecated single-instance form of `add_plasma_torch_h`, operating on the default simulation.
+#[deprecated(note = "use `add_plasma_torch_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn add_plasma_torch(ffi_torch: *const FFIPlasmaTorch) -> c_int {
+    add_plasma_torch_h(SIMULATIONS.default_handle(), ffi_torch)
 }
 
 /// Define as propriedades do material
 #[no_mangle]
-pub extern "C" fn set_material_properties(ffi_material: *const FFIMaterialProperties) -> c_int {
-    if ffi_material.is_null() {
-        set_last_ffi_error("set_material_properties: ffi_material pointer was null".to_string());
-        return -1; // Null pointer error
-    }
+pub extern "C" fn set_material_properties_h(handle: SimulationHandle, ffi_material: *const FFIMaterialProperties) -> c_int {
+    ffi_guard("set_material_properties_h", || {
+        if ffi_material.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "set_material_properties: ffi_material pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
     
-    // Check if name pointer is valid before converting
-    // Note: This doesn't guarantee valid UTF-8 yet, conversion handles that.
-    if unsafe { (*ffi_material).name.is_null() } {
-        set_last_ffi_error("set_material_properties: material name pointer was null".to_string());
-        return -5; // Null name pointer
-    }
+        // Check if name pointer is valid before converting
+        // Note: This doesn't guarantee valid UTF-8 yet, conversion handles that.
+        if unsafe { (*ffi_material).name.is_null() } {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "set_material_properties: material name pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
 
-    // Note: Conversion might fail if `name` is not valid UTF-8.
-    // We rely on `to_string_lossy` inside `convert_ffi_material` for now.
-    // A more robust solution might check CStr::from_ptr().to_str() first.
-    let material = convert_ffi_material(unsafe { &*ffi_material });
+        // Note: Conversion might fail if `name` is not valid UTF-8.
+        // We rely on `to_string_lossy` inside `convert_ffi_material` for now.
+        // A more robust solution might check CStr::from_ptr().to_str() first.
+        let material = convert_ffi_material(unsafe { &*ffi_material });
     
-    unsafe {
         // Check if state exists
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized. Call initialize_simulation first.".to_string());
-            return -2; // Not initialized error
-        }
-        
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
         // Lock the state mutex
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
+        let state_lock = shared.state.lock();
+        match state_lock {
             Ok(mut state) => {
                 // Check if simulation is already running/completed
                  if state.status != crate::simulation::SimulationStatus::NotStarted {
                     set_last_ffi_error("Cannot set material properties for a running or completed simulation.".to_string());
-                    return -3; // Cannot modify running/completed simulation
+                    return FFIErrorCode::OperationFailed.return_code();
                 }
                 state.parameters.material = material;
                 0 // Success
             }
             Err(poison_err) => {
-                 set_last_ffi_error(format!("Mutex poisoned while setting material: {}", poison_err));
-                -4 // Mutex poisoned error
+                 set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while setting material: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
             }
         }
-    }
+    })
+}
+
+/// Deprecated single-instance form of `set_material_properties_h`, operating on the default simulation.
+#[deprecated(note = "use `set_material_properties_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn set_material_properties(ffi_material: *const FFIMaterialProperties) -> c_int {
+    set_material_properties_h(SIMULATIONS.default_handle(), ffi_material)
 }
 
 /// Executa a simulação
 #[no_mangle]
-pub extern "C" fn run_simulation() -> c_int {
-    unsafe {
+pub extern "C" fn run_simulation_h(handle: SimulationHandle) -> c_int {
+    ffi_guard("run_simulation_h", || {
         // Check if state exists
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized. Call initialize_simulation first.".to_string());
-            return -1; // Not initialized error
-        }
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
 
         // Call the run_simulation method on the shared state
         // This method handles spawning the thread internally
-        match SIMULATION_STATE.as_ref().unwrap().run_simulation() {
+        match shared.run_simulation() {
             Ok(_) => 0, // Success (simulation started)
             Err(err_msg) => {
                 // TODO: Store err_msg using get_last_error mechanism? // DONE
                 set_last_ffi_error(format!("Failed to start simulation: {}", err_msg));
                 eprintln!("Failed to start simulation: {}", err_msg); // Keep log for server-side debugging
-                -2 // Failed to start (e.g., couldn't lock mutex, already running)
+                FFIErrorCode::OperationFailed.return_code()
             }
         }
-    }
+    })
 }
 
-/// Pausa a simulação
+/// Deprecated single-instance form of `run_simulation_h`, operating on the default simulation.
+#[deprecated(note = "use `run_simulation_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn pause_simulation() -> c_int {
-    unsafe {
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized.".to_string());
-            return -1; // Not initialized
-        }
+pub extern "C" fn run_simulation() -> c_int {
+    run_simulation_h(SIMULATIONS.default_handle())
+}
 
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
+/// Pausa a simulação
+#[no_mangle]
+pub extern "C" fn pause_simulation_h(handle: SimulationHandle) -> c_int {
+    ffi_guard("pause_simulation_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
             Ok(mut state) => {
                 match state.pause() { // Call pause() on the inner state
                     Ok(_) => 0, // Success
@@ -1329,28 +4343,36 @@ pub extern "C" fn pause_simulation() -> c_int {
                          // TODO: Store err_msg? // DONE
                          set_last_ffi_error(format!("Failed to pause simulation: {}", err_msg));
                          eprintln!("Failed to pause simulation: {}", err_msg); // Keep log
-                        -3 // e.g., Not running
+                        FFIErrorCode::OperationFailed.return_code()
                     }
                 }
             }
             Err(poison_err) => {
-                set_last_ffi_error(format!("Mutex poisoned while pausing simulation: {}", poison_err));
-                -2 // Mutex poisoned
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while pausing simulation: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
             }
         }
-    }
+    })
 }
 
-/// Retoma a simulação
+/// Deprecated single-instance form of `pause_simulation_h`, operating on the default simulation.
+#[deprecated(note = "use `pause_simulation_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn resume_simulation() -> c_int {
-     unsafe {
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized.".to_string());
-            return -1; // Not initialized
-        }
+pub extern "C" fn pause_simulation() -> c_int {
+    pause_simulation_h(SIMULATIONS.default_handle())
+}
 
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
+/// Retoma a simulação
+#[no_mangle]
+pub extern "C" fn resume_simulation_h(handle: SimulationHandle) -> c_int {
+    ffi_guard("resume_simulation_h", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
             Ok(mut state) => {
                 match state.resume() { // Call resume() on the inner state
                     Ok(_) => 0, // Success
@@ -1358,83 +4380,213 @@ pub extern "C" fn resume_simulation() -> c_int {
                          // TODO: Store err_msg? // DONE
                          set_last_ffi_error(format!("Failed to resume simulation: {}", err_msg));
                          eprintln!("Failed to resume simulation: {}", err_msg); // Keep log
-                         -3 // e.g., Not paused
+                         FFIErrorCode::OperationFailed.return_code()
                     }
                 }
             }
             Err(poison_err) => {
-                 set_last_ffi_error(format!("Mutex poisoned while resuming simulation: {}", poison_err));
-                -2 // Mutex poisoned
+                 set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while resuming simulation: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
             }
         }
-    }
+    })
+}
+
+/// Deprecated single-instance form of `resume_simulation_h`, operating on the default simulation.
+#[deprecated(note = "use `resume_simulation_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn resume_simulation() -> c_int {
+    resume_simulation_h(SIMULATIONS.default_handle())
+}
+
+/// Requests cancellation of a running (or paused) simulation without destroying it.
+///
+/// The solver stops at the end of the current time step and the instance ends with
+/// status 5 (Cancelled), keeping the partial results of the steps already integrated.
+/// Returns 0 on success or a negative `FFIErrorCode`: `UnknownHandle`, `LockPoisoned` or
+/// `OperationFailed` if the simulation is not running.
+#[no_mangle]
+pub extern "C" fn cancel_simulation(handle: SimulationHandle) -> c_int {
+    ffi_guard("cancel_simulation", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state_lock = shared.state.lock();
+        match state_lock {
+            Ok(state) => {
+                if !matches!(state.status, crate::simulation::SimulationStatus::Running | crate::simulation::SimulationStatus::Paused) {
+                    set_last_ffi_error(format!("Cannot cancel simulation in status {:?}", state.status));
+                    return FFIErrorCode::OperationFailed.return_code();
+                }
+                shared.request_cancellation();
+                0
+            }
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while cancelling simulation: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
+            }
+        }
+    })
 }
 
 /// Obtém o estado atual da simulação
 #[no_mangle]
-pub extern "C" fn get_simulation_state(ffi_state: *mut FFISimulationState) -> c_int {
-    if ffi_state.is_null() {
-        set_last_ffi_error("get_simulation_state: ffi_state pointer was null".to_string());
-        return -1; // Null pointer provided by caller
-    }
-    
-    unsafe {
-        if SIMULATION_STATE.is_none() {
-             set_last_ffi_error("Simulation not initialized.".to_string());
-            return -2; // Not initialized
+pub extern "C" fn get_simulation_state_h(handle: SimulationHandle, ffi_state: *mut FFISimulationState) -> c_int {
+    ffi_guard("get_simulation_state_h", || {
+        if ffi_state.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_simulation_state: ffi_state pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
         }
+    
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
 
         // Use the get_state method which handles locking and cloning
-        match SIMULATION_STATE.as_ref().unwrap().get_state() {
+        match shared.get_state() {
             Ok(current_state) => {
                 // Convert the cloned Rust state to the FFI struct
                 // This allocates memory for error_message if it exists.
                 let converted_state = convert_simulation_state(&current_state);
-                
+
                 // Write the converted state to the pointer provided by Dart
                 // The caller (Dart) is responsible for reading this struct
                 // and freeing the error_message pointer via free_rust_string.
-                *ffi_state = converted_state;
+                // Safety: `ffi_state` is non-null and points to a caller-owned FFISimulationState
+                unsafe { ffi_state.write(converted_state) };
                 0 // Success
             }
             Err(err_msg) => {
                 set_last_ffi_error(format!("Failed to get simulation state: {}", err_msg));
-                -3 // Mutex lock failed or other internal error from get_state
+                FFIErrorCode::OperationFailed.return_code()
             }
         }
+    })
+}
+
+/// Deprecated single-instance form of `get_simulation_state_h`, operating on the default simulation.
+#[deprecated(note = "use `get_simulation_state_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn get_simulation_state(ffi_state: *mut FFISimulationState) -> c_int {
+    get_simulation_state_h(SIMULATIONS.default_handle(), ffi_state)
+}
+
+/// Progress callback invoked from the simulation thread after every completed time step
+/// with the caller's `user_data`, the progress (0-1), the completed and total step counts
+/// and the elapsed run time (s).
+pub type FFIProgressCallback = extern "C" fn(
+    user_data: *mut c_void,
+    progress: c_float,
+    current_step: c_int,
+    total_steps: c_int,
+    elapsed_seconds: c_double,
+);
+
+/// C callback and its opaque context, moved into the simulation thread.
+struct FFIProgressTarget {
+    callback: FFIProgressCallback,
+    user_data: *mut c_void,
+}
+
+// Safety: `user_data` is never dereferenced by Rust; the caller of
+// `register_progress_callback` guarantees it may be used from the simulation thread
+// until the callback is unregistered.
+unsafe impl Send for FFIProgressTarget {}
+
+impl FFIProgressTarget {
+    fn notify(&self, update: &ProgressUpdate) {
+        (self.callback)(
+            self.user_data,
+            update.progress as c_float,
+            update.current_step.min(c_int::MAX as usize) as c_int,
+            update.total_steps.min(c_int::MAX as usize) as c_int,
+            update.elapsed_seconds,
+        );
     }
 }
 
-/// Obtém os dados de temperatura para um passo de tempo específico
+/// Registers `callback` to be invoked with `user_data` after every time step of simulation
+/// `handle`, replacing any previous callback. The callback runs on the simulation thread
+/// (from Dart, use `NativeCallable.listener`); it must return quickly and must not call
+/// `register_progress_callback` or `unregister_progress_callback` itself.
+/// Returns 0 on success or a negative `FFIErrorCode`: `NullPointer` for a null callback,
+/// `UnknownHandle` or `OperationFailed` on a lock error.
 #[no_mangle]
-pub extern "C" fn get_temperature_data(
-    time_step: c_int,
-    buffer: *mut c_float,
-    buffer_size: usize,
+pub extern "C" fn register_progress_callback(
+    handle: SimulationHandle,
+    callback: Option<FFIProgressCallback>,
+    user_data: *mut c_void,
 ) -> c_int {
-    // Check for null buffer from caller
-    if buffer.is_null() {
-        set_last_ffi_error("get_temperature_data: buffer pointer was null".to_string());
-        return -1; // Null buffer pointer
-    }
+    ffi_guard("register_progress_callback", || {
+        let Some(callback) = callback else {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "register_progress_callback: callback pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        };
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+        let target = FFIProgressTarget { callback, user_data };
+        match shared.set_progress_observer(Some(Box::new(move |update: &ProgressUpdate| target.notify(update)))) {
+            Ok(()) => 0,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to register progress callback: {}", e));
+                FFIErrorCode::OperationFailed.return_code()
+            }
+        }
+    })
+}
+
+/// Unregisters the progress callback of simulation `handle`. When this returns, no call
+/// to the callback is in progress and none will follow, so `user_data` may be released.
+/// Returns 0 on success (also when no callback was registered) or a negative `FFIErrorCode`:
+/// `UnknownHandle` or `OperationFailed` on a lock error.
+#[no_mangle]
+pub extern "C" fn unregister_progress_callback(handle: SimulationHandle) -> c_int {
+    ffi_guard("unregister_progress_callback", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+        match shared.set_progress_observer(None) {
+            Ok(()) => 0,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to unregister progress callback: {}", e));
+                FFIErrorCode::OperationFailed.return_code()
+            }
+        }
+    })
+}
+
+/// Obtém os dados de temperatura para um passo de tempo específico
+#[no_mangle]
+pub extern "C" fn get_temperature_data_h(handle: SimulationHandle, time_step: c_int, buffer: *mut c_float, buffer_size: usize) -> c_int {
+    ffi_guard("get_temperature_data_h", || {
+        // Check for null buffer from caller
+        if buffer.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_temperature_data: buffer pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
     
-    unsafe {
         // Check if simulation state exists
-        if SIMULATION_STATE.is_none() {
-            set_last_ffi_error("Simulation not initialized.".to_string());
-            return -2; // Not initialized
-        }
-        
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+    
         // Lock the state mutex
-        match SIMULATION_STATE.as_ref().unwrap().state.lock() {
+        let state_lock = shared.state.lock();
+        match state_lock {
             Ok(state) => {
                 // Check if simulation is complete and results are available
                 if !state.is_completed() || state.results.is_none() {
-                     // Consider different codes? -4 = Not completed, -5 = No results (shouldn't happen if completed)
-                     set_last_ffi_error("Simulation results not available (simulation not completed or no results stored).".to_string());
-                    return -4; // Results not available or simulation not completed
+                     set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available (simulation not completed or no results stored).".to_string());
+                    return FFIErrorCode::NotAvailable.return_code();
                 }
-                
+            
                 let results = state.results.as_ref().unwrap();
                 let params = &state.parameters;
                 let nr = params.nr;
@@ -1443,42 +4595,37 @@ pub extern "C" fn get_temperature_data(
                 // Or does the ndarray include the initial state at step 0?
                 // Let's assume the ndarray has shape (nr, nz, time_steps) or similar
                 // and valid indices are 0 to time_steps - 1.
-                
+            
                 // Validate time_step index (assuming 0-based index)
                 // Check against the actual dimension of the temperature array if possible
                 let temp_shape = results.temperature.shape();
                 if temp_shape.len() != 3 || total_steps != temp_shape[2] {
                      set_last_ffi_error(format!("Internal error: Mismatch between params.time_steps ({}) and results.temperature shape ({:?})", total_steps, temp_shape));
-                     return -9; // Internal dimension mismatch
+                     return FFIErrorCode::OperationFailed.return_code();
                 }
                 if time_step < 0 || time_step as usize >= total_steps {
-                     set_last_ffi_error(format!("Invalid time step index: {}. Must be between 0 and {}.", time_step, total_steps - 1));
-                    return -3; // Invalid time step index
+                     set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid time step index: {}. Must be between 0 and {}.", time_step, total_steps - 1));
+                    return FFIErrorCode::InvalidArgument.return_code();
                 }
 
                 let required_size = nr * nz;
                 if buffer_size < required_size {
-                     set_last_ffi_error(format!("Buffer too small: provided size {}, required size {}.", buffer_size, required_size));
-                    return -6; // Buffer too small
+                     set_last_ffi_error_code(FFIErrorCode::BufferTooSmall, format!("Buffer too small: provided size {}, required size {}.", buffer_size, required_size));
+                    return FFIErrorCode::BufferTooSmall.return_code();
                 }
 
                 // Access the temperature data (assuming ndarray)
                 // Use slice method s! macro requires ndarray import
                 use ndarray::s;
-                let temp_slice_view = match results.temperature.slice(s![.., .., time_step as usize]) {
-                     Ok(slice) => slice,
-                     Err(e) => {
-                         set_last_ffi_error(format!("Error slicing temperature data: {}", e));
-                         return -7; // Error slicing ndarray
-                     }
-                };
+                let temp_slice_view = results.temperature.slice(s![.., .., time_step as usize]);
                 // Ensure the view is contiguous or copy if necessary for safe access.
                 // Using `.as_slice()` requires the slice to be contiguous C-order.
                 // If it might not be, iterate and copy element-wise.
                 // Let's assume standard layout for now or that iteration below handles it.
 
                 // Get a mutable slice from the FFI buffer pointer
-                let buffer_slice = slice::from_raw_parts_mut(buffer, required_size);
+                // Safety: `buffer` is non-null and the caller guarantees room for `buffer_size` >= `required_size` floats
+                let buffer_slice = unsafe { slice::from_raw_parts_mut(buffer, required_size) };
 
                 // Copy data, handling potential dimension order (assuming C order [row-major] in ndarray)
                 let mut count = 0;
@@ -1490,7 +4637,7 @@ pub extern "C" fn get_temperature_data(
                          } else {
                              // Should not happen if slice dimensions are correct and loops are right
                              set_last_ffi_error(format!("Internal error: Indexing failed at [{}, {}] during temperature copy.", i, j));
-                             return -8; // Indexing error during copy
+                             return FFIErrorCode::OperationFailed.return_code();
                          }
                         count += 1;
                     }
@@ -1501,78 +4648,450 @@ pub extern "C" fn get_temperature_data(
                  required_size as c_int // Success, return number of elements written
             }
             Err(poison_err) => {
-                set_last_ffi_error(format!("Mutex poisoned while getting temperature data: {}", poison_err));
-                -5 // Mutex poisoned error
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while getting temperature data: {}", poison_err));
+                FFIErrorCode::LockPoisoned.return_code()
             }
         }
-    }
+    })
 }
 
-/// Libera os recursos da simulação, solicitando cancelamento e aguardando a thread.
+/// Deprecated single-instance form of `get_temperature_data_h`, operating on the default simulation.
+#[deprecated(note = "use `get_temperature_data_h` with a simulation handle")]
 #[no_mangle]
-pub extern "C" fn destroy_simulation() -> c_int {
-    let shared_state_option = unsafe {
-        // Take the state out of the static variable to ensure it's dropped
-        // at the end of this function, after the thread join.
-        SIMULATION_STATE.take()
-    };
+pub extern "C" fn get_temperature_data(time_step: c_int, buffer: *mut c_float, buffer_size: usize) -> c_int {
+    get_temperature_data_h(SIMULATIONS.default_handle(), time_step, buffer, buffer_size)
+}
 
-    if let Some(shared_state) = shared_state_option {
-        println!("RUST: destroy_simulation called. Requesting cancellation...");
-        // 1. Request cancellation
-        shared_state.request_cancellation();
+/// Gets the mesh geometry of simulation `handle` as JSON (`MeshInfo`): node coordinates
+/// `r_coords`/`z_coords`, control-volume faces `r_faces`/`z_faces` (nr + 1 and nz + 1
+/// values), cell sizes `radial_cell_widths`/`axial_cell_heights`, `cell_volumes[i][j]` (m³)
+/// and the grading of each direction. Uses the mesh of the results if available, otherwise
+/// the mesh the current parameters would produce, so graded grids can be drawn before a run.
+/// Returns null on error. Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_mesh_info_json(handle: SimulationHandle) -> *mut c_char {
+    ffi_guard("get_mesh_info_json", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return ptr::null_mut();
+        };
+        let state = match shared.state.lock() {
+            Ok(state) => state,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while reading mesh: {}", poison_err));
+                return ptr::null_mut();
+            }
+        };
+        let info = match &state.results {
+            Some(results) => results.mesh.info(),
+            None => {
+                let params = &state.parameters;
+                let valid = params.height > 0.0 && params.radius > 0.0 && params.nr >= 2 && params.nz >= 2 && params.ntheta >= 4
+                    && params.radial_grading.validate().is_ok() && params.axial_grading.validate().is_ok();
+                if !valid {
+                    set_last_ffi_error_code(FFIErrorCode::InvalidArgument, "Simulation parameters do not describe a valid mesh".to_string());
+                    return ptr::null_mut();
+                }
+                CylindricalMesh::new_graded(
+                    params.height, params.radius, params.nr, params.nz, params.ntheta,
+                    params.radial_grading, params.axial_grading,
+                ).info()
+            }
+        };
+        json_ffi_string(&info)
+    })
+}
 
-        // 2. Wait for the simulation thread to finish
-        match shared_state.join_simulation_thread() {
-            Ok(true) => {
-                println!("RUST: Simulation thread joined successfully.");
-                // State will be dropped automatically here
-                0 // Success
+/// Writes the temperature (°C) at an arbitrary point (r, z) and time into `out_temperature`.
+///
+/// Interpolates bilinearly in space and linearly between output time steps, acting as a
+/// virtual thermocouple. Returns 0 on success, or a negative `FFIErrorCode`: `NullPointer`,
+/// `UnknownHandle`, `InvalidArgument` for a point or time outside the simulated domain,
+/// `NotAvailable` without results and `LockPoisoned` on a lock error.
+#[no_mangle]
+pub extern "C" fn get_temperature_at_point(
+    handle: SimulationHandle,
+    r: c_double,
+    z: c_double,
+    time: c_double,
+    out_temperature: *mut c_double,
+) -> c_int {
+    ffi_guard("get_temperature_at_point", || {
+        if out_temperature.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_temperature_at_point: out_temperature pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state = match shared.state.lock() {
+            Ok(state) => state,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while probing temperature: {}", poison_err));
+                return FFIErrorCode::LockPoisoned.return_code();
             }
-            Ok(false) => {
-                println!("RUST: No simulation thread was running to join.");
-                // State will be dropped automatically here
-                0 // Success (already stopped or never started)
+        };
+        let Some(results) = state.results.as_ref() else {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available.".to_string());
+            return FFIErrorCode::NotAvailable.return_code();
+        };
+        match results.temperature_at_point(r, z, time) {
+            Ok(temperature) => {
+                unsafe { *out_temperature = temperature };
+                0
             }
-            Err(err) => {
-                eprintln!("RUST: Error joining simulation thread: {}", err);
-                set_last_ffi_error(format!("Error during simulation cleanup: {}", err));
-                // Even if join fails, the state is dropped here.
-                // Return specific error code for join failure?
-                -3 // Error joining thread
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid temperature probe: {}", e));
+                FFIErrorCode::InvalidArgument.return_code()
             }
         }
-        // `shared_state` is dropped here, releasing the Arc/Mutex/thread handle resources.
+    })
+}
 
-    } else {
-        println!("RUST: destroy_simulation called, but state was already None.");
-        set_last_ffi_error("Simulation already destroyed or never initialized.".to_string());
-        -1 // Already destroyed or never initialized
+// Samples the temperature of `time_step` along (r0, z0) -> (r1, z1); on error records it and
+// returns the matching `FFIErrorCode` return code
+fn line_profile_for(
+    handle: SimulationHandle,
+    time_step: c_int,
+    start: (f64, f64),
+    end: (f64, f64),
+    num_points: c_int,
+) -> Result<LineProfileData, c_int> {
+    let Some(shared) = SIMULATIONS.get(handle) else {
+        set_unknown_simulation_error(handle);
+        return Err(FFIErrorCode::UnknownHandle.return_code());
+    };
+    let state = shared.state.lock().map_err(|e| {
+        set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while sampling line profile: {}", e));
+        FFIErrorCode::LockPoisoned.return_code()
+    })?;
+    let Some(results) = state.results.as_ref() else {
+        set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available.".to_string());
+        return Err(FFIErrorCode::NotAvailable.return_code());
+    };
+    if time_step < 0 || time_step as usize > results.executed_steps {
+        set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid time step index: {}. Must be between 0 and {}.", time_step, results.executed_steps));
+        return Err(FFIErrorCode::InvalidArgument.return_code());
     }
+    let field = results.temperature_at(time_step as usize).map_err(|e| {
+        set_last_ffi_error_code(FFIErrorCode::NotAvailable, e);
+        FFIErrorCode::NotAvailable.return_code()
+    })?;
+    visualization::generate_line_profile_data(&field, &results.mesh, start, end, num_points.max(0) as usize, time_step as usize)
+        .map_err(|e| {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid line profile: {}", e));
+            FFIErrorCode::InvalidArgument.return_code()
+        })
+}
+
+/// Returns the temperature profile along the segment (r0, z0) -> (r1, z1) at a time step
+/// as JSON (`{start, end, points, distances, values, range, time_step}`), sampled at
+/// `num_points` (≥ 2) equally spaced points. Returns null on error (see `get_last_error`).
+#[no_mangle]
+pub extern "C" fn get_line_profile_json(
+    handle: SimulationHandle,
+    time_step: c_int,
+    r0: c_double,
+    z0: c_double,
+    r1: c_double,
+    z1: c_double,
+    num_points: c_int,
+) -> *mut c_char {
+    ffi_guard("get_line_profile_json", || {
+        match line_profile_for(handle, time_step, (r0, z0), (r1, z1), num_points) {
+            Ok(profile) => json_ffi_string(&profile),
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// Copies the temperatures of the line profile (see `get_line_profile_json`) into `buffer`.
+///
+/// Point k lies at fraction k / (num_points - 1) of the segment. Returns the number of
+/// values written, or a negative `FFIErrorCode`: `NullPointer`, `UnknownHandle`,
+/// `InvalidArgument` for an invalid time step or segment, `NotAvailable` without results,
+/// `LockPoisoned` or `BufferTooSmall`.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub extern "C" fn get_line_profile_data(
+    handle: SimulationHandle,
+    time_step: c_int,
+    r0: c_double,
+    z0: c_double,
+    r1: c_double,
+    z1: c_double,
+    num_points: c_int,
+    buffer: *mut c_float,
+    buffer_size: usize,
+) -> c_int {
+    ffi_guard("get_line_profile_data", || {
+        if buffer.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_line_profile_data: buffer pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
+        let profile = match line_profile_for(handle, time_step, (r0, z0), (r1, z1), num_points) {
+            Ok(profile) => profile,
+            Err(code) => return code,
+        };
+        if buffer_size < profile.values.len() {
+            set_last_ffi_error_code(FFIErrorCode::BufferTooSmall, format!("Buffer too small: provided size {}, required size {}.", buffer_size, profile.values.len()));
+            return FFIErrorCode::BufferTooSmall.return_code();
+        }
+
+        let buffer_slice = unsafe { slice::from_raw_parts_mut(buffer, profile.values.len()) };
+        for (target, value) in buffer_slice.iter_mut().zip(&profile.values) {
+            *target = *value as c_float;
+        }
+        profile.values.len() as c_int
+    })
+}
+
+/// Copies the heat-flux field q = -k∇T (W/m²) of a time step into two caller buffers.
+///
+/// `radial_buffer` and `axial_buffer` receive the radial and axial components at the
+/// mesh nodes, in the same row-major (nr × nz) layout as `get_temperature_data_h`.
+/// Returns the number of values written per buffer, or a negative `FFIErrorCode`:
+/// `NullPointer`, `UnknownHandle`, `InvalidArgument` for an invalid time step,
+/// `NotAvailable` without results, `LockPoisoned` or `BufferTooSmall`.
+#[no_mangle]
+pub extern "C" fn get_heat_flux_data(
+    handle: SimulationHandle,
+    time_step: c_int,
+    radial_buffer: *mut c_float,
+    axial_buffer: *mut c_float,
+    buffer_size: usize,
+) -> c_int {
+    ffi_guard("get_heat_flux_data", || {
+        if radial_buffer.is_null() || axial_buffer.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_heat_flux_data: buffer pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code();
+        }
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code();
+        };
+
+        let state = match shared.state.lock() {
+            Ok(state) => state,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while getting heat flux data: {}", poison_err));
+                return FFIErrorCode::LockPoisoned.return_code();
+            }
+        };
+        let Some(results) = state.results.as_ref() else {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available.".to_string());
+            return FFIErrorCode::NotAvailable.return_code();
+        };
+        if time_step < 0 || time_step as usize > results.executed_steps {
+            set_last_ffi_error_code(FFIErrorCode::InvalidArgument, format!("Invalid time step index: {}. Must be between 0 and {}.", time_step, results.executed_steps));
+            return FFIErrorCode::InvalidArgument.return_code();
+        }
+
+        let (radial, axial) = match results.heat_flux_at(time_step as usize) {
+            Ok(flux) => flux,
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to compute heat flux: {}", e));
+                return FFIErrorCode::OperationFailed.return_code();
+            }
+        };
+        let required_size = radial.len();
+        if buffer_size < required_size {
+            set_last_ffi_error_code(FFIErrorCode::BufferTooSmall, format!("Buffer too small: provided size {}, required size {}.", buffer_size, required_size));
+            return FFIErrorCode::BufferTooSmall.return_code();
+        }
+
+        let radial_slice = unsafe { slice::from_raw_parts_mut(radial_buffer, required_size) };
+        let axial_slice = unsafe { slice::from_raw_parts_mut(axial_buffer, required_size) };
+        for (target, value) in radial_slice.iter_mut().zip(radial.iter()) {
+            *target = *value as c_float;
+        }
+        for (target, value) in axial_slice.iter_mut().zip(axial.iter()) {
+            *target = *value as c_float;
+        }
+        required_size as c_int
+    })
+}
+
+/// Gets the sampling of `get_temperature_history` for the given strides as JSON
+/// (`HistoryLayout`): `r_indices`, `z_indices`, `steps`, the sampled `r_coords`/`z_coords`
+/// (m) and `times` (s). The buffer needs `steps.len() * r_indices.len() * z_indices.len()`
+/// floats. Returns null on error. Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_temperature_history_layout_json(handle: SimulationHandle, stride_r: usize, stride_z: usize, stride_t: usize) -> *mut c_char {
+    ffi_guard("get_temperature_history_layout_json", || {
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return ptr::null_mut();
+        };
+        let state = match shared.state.lock() {
+            Ok(state) => state,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while getting temperature history: {}", poison_err));
+                return ptr::null_mut();
+            }
+        };
+        let Some(results) = state.results.as_ref() else {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available.".to_string());
+            return ptr::null_mut();
+        };
+        match results.history_layout(stride_r, stride_z, stride_t) {
+            Ok(layout) => json_ffi_string(&layout),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Copies the whole space-time temperature history (°C), decimated by `stride_r`,
+/// `stride_z` and `stride_t` (>= 1), into `buffer` in one call, for time scrubbers.
+///
+/// Each dimension keeps indices 0, stride, 2·stride, ... plus the last one (wall, top and
+/// last executed step); see `get_temperature_history_layout_json` for the sampled indices,
+/// coordinates and times. Layout: index `(k * nr_s + i) * nz_s + j` for sampled step k,
+/// radial node i and axial node j, so each frame is contiguous.
+/// Returns the number of values written, or a negative `FFIErrorCode`: `NullPointer`,
+/// `UnknownHandle`, `InvalidArgument` for an invalid stride, `NotAvailable` without
+/// results, `LockPoisoned` or `BufferTooSmall`.
+#[no_mangle]
+pub extern "C" fn get_temperature_history(
+    handle: SimulationHandle,
+    buffer: *mut c_float,
+    buffer_size: usize,
+    stride_r: usize,
+    stride_z: usize,
+    stride_t: usize,
+) -> i64 {
+    ffi_guard("get_temperature_history", || {
+        if buffer.is_null() {
+            set_last_ffi_error_code(FFIErrorCode::NullPointer, "get_temperature_history: buffer pointer was null".to_string());
+            return FFIErrorCode::NullPointer.return_code() as i64;
+        }
+        let Some(shared) = SIMULATIONS.get(handle) else {
+            set_unknown_simulation_error(handle);
+            return FFIErrorCode::UnknownHandle.return_code() as i64;
+        };
+        let state = match shared.state.lock() {
+            Ok(state) => state,
+            Err(poison_err) => {
+                set_last_ffi_error_code(FFIErrorCode::LockPoisoned, format!("Mutex poisoned while getting temperature history: {}", poison_err));
+                return FFIErrorCode::LockPoisoned.return_code() as i64;
+            }
+        };
+        let Some(results) = state.results.as_ref() else {
+            set_last_ffi_error_code(FFIErrorCode::NotAvailable, "Simulation results not available.".to_string());
+            return FFIErrorCode::NotAvailable.return_code() as i64;
+        };
+
+        let required_size = match results.history_layout(stride_r, stride_z, stride_t) {
+            Ok(layout) => layout.len(),
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::InvalidArgument, e);
+                return FFIErrorCode::InvalidArgument.return_code() as i64;
+            }
+        };
+        if buffer_size < required_size {
+            set_last_ffi_error_code(FFIErrorCode::BufferTooSmall, format!("Buffer too small: provided size {}, required size {}.", buffer_size, required_size));
+            return FFIErrorCode::BufferTooSmall.return_code() as i64;
+        }
+        let history = match results.decimated_history(stride_r, stride_z, stride_t) {
+            Ok((_, history)) => history,
+            Err(e) => {
+                set_last_ffi_error_code(FFIErrorCode::NotAvailable, format!("Failed to read temperature history: {}", e));
+                return FFIErrorCode::NotAvailable.return_code() as i64;
+            }
+        };
+
+        // Safety: `buffer` is non-null and the caller guarantees room for `buffer_size` >= `required_size` floats
+        let buffer_slice = unsafe { slice::from_raw_parts_mut(buffer, required_size) };
+        for (target, value) in buffer_slice.iter_mut().zip(history.iter()) {
+            *target = *value as c_float;
+        }
+        required_size as i64
+    })
+}
+
+/// Libera os recursos da simulação `handle`, solicitando cancelamento e aguardando a thread.
+/// O handle deixa de ser válido, assim como o canal de quadros compartilhado, se aberto.
+#[no_mangle]
+pub extern "C" fn destroy_simulation_h(handle: SimulationHandle) -> c_int {
+    ffi_guard("destroy_simulation_h", || {
+        // Remove the instance from the registry so it is dropped at the end of this
+        // function, after the thread join (other calls may still hold it briefly).
+        let shared_state_option = SIMULATIONS.remove(handle);
+        // The shared frame channel (if open) lives until the thread's last frame is written
+        shared_frame_channels().remove(&handle);
+
+        if let Some(shared_state) = shared_state_option {
+            println!("RUST: destroy_simulation called for handle {}. Requesting cancellation...", handle);
+            // 1. Request cancellation
+            shared_state.request_cancellation();
+
+            // 2. Wait for the simulation thread to finish
+            match shared_state.join_simulation_thread() {
+                Ok(true) => {
+                    println!("RUST: Simulation thread joined successfully.");
+                    // State will be dropped automatically here
+                    0 // Success
+                }
+                Ok(false) => {
+                    println!("RUST: No simulation thread was running to join.");
+                    // State will be dropped automatically here
+                    0 // Success (already stopped or never started)
+                }
+                Err(err) => {
+                    eprintln!("RUST: Error joining simulation thread: {}", err);
+                    set_last_ffi_error(format!("Error during simulation cleanup: {}", err));
+                    // Even if join fails, the state is dropped here.
+                    // Return specific error code for join failure?
+                    FFIErrorCode::OperationFailed.return_code()
+                }
+            }
+            // `shared_state` is dropped here, releasing the registry's reference.
+
+        } else {
+            println!("RUST: destroy_simulation called, but handle {} is not registered.", handle);
+            set_last_ffi_error_code(
+                FFIErrorCode::UnknownHandle,
+                format!("Simulation already destroyed or never initialized (handle {}).", handle),
+            );
+            FFIErrorCode::UnknownHandle.return_code()
+        }
+    })
+}
+
+/// Deprecated single-instance form of `destroy_simulation_h`, operating on the default simulation.
+#[deprecated(note = "use `destroy_simulation_h` with a simulation handle")]
+#[no_mangle]
+pub extern "C" fn destroy_simulation() -> c_int {
+    destroy_simulation_h(SIMULATIONS.default_handle())
 }
 
 /// Obtém a última mensagem de erro.
-/// Checks thread-local FFI errors first, then simulation state errors.
+/// Checks thread-local FFI errors first, then the default simulation's state error
+/// (use `get_simulation_state_h` for the error of another simulation).
 /// Returns a pointer to a C string allocated by Rust.
 /// The caller (Dart) MUST call free_rust_string on the returned pointer.
 /// Returns null if no error is pending.
 #[no_mangle]
 pub extern "C" fn get_last_error() -> *mut c_char {
-    // 1. Check thread-local FFI error first
-    let ffi_error = LAST_ERROR.with(|cell| cell.borrow_mut().take()); // take() gets the value and leaves None
-
-    if let Some(err_msg) = ffi_error {
-        return CString::new(err_msg).map_or_else(|_| {
-            // Should not happen if we set valid strings, but handle allocation error
-             eprintln!("Error: Failed to create CString for FFI error message.");
-             ptr::null_mut()
-        }, |c_str| c_str.into_raw());
-    }
+    ffi_guard("get_last_error", || {
+        // 1. Check thread-local FFI error first
+        let ffi_error = LAST_ERROR.with(|cell| cell.borrow_mut().take()).map(|error| error.message); // take() gets the value and leaves None
+
+        if let Some(err_msg) = ffi_error {
+            return CString::new(err_msg).map_or_else(|_| {
+                // Should not happen if we set valid strings, but handle allocation error
+                 eprintln!("Error: Failed to create CString for FFI error message.");
+                 ptr::null_mut()
+            }, into_ffi_string_ptr);
+        }
 
-    // 2. If no FFI error, check the simulation state error
-    unsafe {
-        // Try to get the error message stored in the current simulation state
-        if let Some(shared_state) = &SIMULATION_STATE {
+        // 2. If no FFI error, check the error stored in the default simulation state
+        if let Some(shared_state) = SIMULATIONS.default_instance() {
             // Use get_state to handle locking safely
             match shared_state.get_state() {
                  Ok(state) => {
@@ -1583,7 +5102,7 @@ pub extern "C" fn get_last_error() -> *mut c_char {
                          return CString::new(sim_error_msg.clone()).map_or_else(|_| {
                              eprintln!("Error: Failed to create CString for simulation error message.");
                              ptr::null_mut()
-                         }, |c_str| c_str.into_raw());
+                         }, into_ffi_string_ptr);
                     }
                  }
                  Err(_) => {
@@ -1593,20 +5112,762 @@ pub extern "C" fn get_last_error() -> *mut c_char {
                  }
              }
         }
-    }
 
-    // No thread-local FFI error and no simulation state error found (or state inaccessible)
-    ptr::null_mut() // Return null pointer if no specific error is found
+        // No thread-local FFI error and no simulation state error found (or state inaccessible)
+        ptr::null_mut() // Return null pointer if no specific error is found
+    })
+}
+
+/// Returns the last error as JSON `{code, name, message, function, context}` and clears it.
+///
+/// `code` is an `FFIErrorCode` value, `name` its stable snake_case name (e.g.
+/// `"unknown_handle"`), `function` the FFI function that failed and
+/// `context` extra data (e.g. `{"handle": 3}`) or null. Like `get_last_error`, falls back
+/// to the error of the default simulation (code `OperationFailed`, with the handle as
+/// context) and returns null if no error is pending. Free with `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_last_error_json() -> *mut c_char {
+    ffi_guard("get_last_error_json", || {
+        let error = LAST_ERROR.with(|cell| cell.borrow_mut().take()).or_else(|| {
+            let handle = SIMULATIONS.default_handle();
+            let message = SIMULATIONS.get(handle)?.get_state().ok()?.error_message?;
+            Some(FFIError::new(FFIErrorCode::OperationFailed, message, "run_simulation_h")
+                .with_context(serde_json::json!({ "handle": handle })))
+        });
+        match error {
+            Some(error) => json_ffi_string(&error),
+            None => ptr::null_mut(),
+        }
+    })
+}
+
+/// Returns the stable name of the `FFIErrorCode` behind a value returned by an FFI
+/// function (e.g. `"ok"` for 0, `"unknown_handle"` for -4), or null if `return_code`
+/// is not a known code. Free with `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_error_code_name(return_code: i64) -> *mut c_char {
+    ffi_guard("get_error_code_name", || {
+        match FFIErrorCode::from_return_code(return_code) {
+            Some(code) => CString::new(code.name()).map_or(ptr::null_mut(), into_ffi_string_ptr),
+            None => {
+                set_last_ffi_error_code(
+                    FFIErrorCode::InvalidArgument,
+                    format!("Unknown FFI return code: {}", return_code),
+                );
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Returns the FFI API version encoded as `major * 10000 + minor * 100 + patch`.
+///
+/// The major version changes when functions are removed or change signature or return
+/// codes, the minor when functions are added. Callers should check it before binding optional symbols.
+#[no_mangle]
+pub extern "C" fn get_api_version() -> u32 {
+    capabilities::encoded_api_version()
+}
+
+/// Returns the capabilities of this backend build as JSON: API and crate versions,
+/// solver schemes, export, payload and frame formats, GPU availability, compiled
+/// features (`async`, `s3`, `sqlite`) and the optional FFI functions that are exported.
+/// Free with `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn get_capabilities_json() -> *mut c_char {
+    ffi_guard("get_capabilities_json", || json_ffi_string(&capabilities::capabilities()))
 }
 
 /// Libera a memória de uma string C alocada pelo Rust (e.g., JSON, erro)
 #[no_mangle]
 pub extern "C" fn free_rust_string(message: *mut c_char) {
-    if !message.is_null() {
+    ffi_guard("free_rust_string", || {
+        if !message.is_null() {
+            unsafe {
+                // Safety: This assumes `message` was allocated by `CString::into_raw`.
+                // This is true for strings returned by `get_last_error` and the JSON functions.
+                let _ = CString::from_raw(message);
+                LIVE_FFI_STRINGS.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    })
+}
+
+// --- FFI Self-Test ---
+
+/// Result of a single self-test check.
+#[derive(serde::Serialize)]
+struct FFISelfTestCheck {
+    name: String,
+    passed: bool,
+    detail: String,
+}
+
+/// Report returned by `run_ffi_selftest`.
+#[derive(serde::Serialize)]
+struct FFISelfTestReport {
+    passed: bool,
+    iterations: usize,
+    checks: Vec<FFISelfTestCheck>,
+    leaked_strings: isize,
+    leaked_vectors: isize,
+    leaked_buffers: isize,
+}
+
+/// Number of allocate/free cycles performed by the soak part of the self-test.
+const FFI_SELFTEST_ITERATIONS: usize = 100;
+
+/// Reference data with `points` points, uncertainties and metadata, used by the self-test.
+fn selftest_reference_data(points: usize) -> ReferenceData {
+    ReferenceData {
+        name: "selftest".to_string(),
+        description: format!("{} points", points),
+        source: "Synthetic".to_string(),
+        data_type: "Temperature".to_string(),
+        coordinates: (0..points).map(|i| (i as f64, 0.0, 0.5 * i as f64)).collect(),
+        values: (0..points).map(|i| 25.0 + i as f64).collect(),
+        uncertainties: Some(vec![0.5; points]),
+        times: Some((0..points).map(|i| 10.0 * i as f64).collect()),
+        metadata: (0..points % 4).map(|i| (format!("key{}", i), format!("value{}", i))).collect(),
+    }
+}
+
+/// Validation result over `selftest_reference_data(points)`, used by the self-test.
+fn selftest_validation_result(points: usize) -> ValidationResult {
+    let reference_data = selftest_reference_data(points);
+    ValidationResult {
+        name: "selftest".to_string(),
+        description: String::new(),
+        simulated_values: reference_data.values.iter().map(|v| v + 1.0).collect(),
+        reference_data,
+        metrics: ValidationMetrics {
+            mean_absolute_error: 1.0,
+            mean_squared_error: 1.0,
+            root_mean_squared_error: 1.0,
+            mean_absolute_percentage_error: 0.0,
+            r_squared: 1.0,
+            max_absolute_error: 1.0,
+            mean_error: 1.0,
+            normalized_rmse: 0.0,
+            region_metrics: HashMap::new(),
+        },
+        metadata: HashMap::from([("solver".to_string(), "selftest".to_string())]),
+    }
+}
+
+/// Exercises the FFI memory contracts: allocation/free pairs for strings, vectors,
+/// byte buffers and results, null/invalid inputs, and leak accounting through the
+/// live allocation counters. Returns a JSON report (`passed`, `checks`, leak counts).
+/// Counters are global, so other threads calling the FFI concurrently may show up as leaks.
+/// Caller must free the returned string using `free_rust_string`.
+#[no_mangle]
+pub extern "C" fn run_ffi_selftest() -> *mut c_char {
+    ffi_guard("run_ffi_selftest", || {
+        let mut checks = Vec::new();
+        let mut check = |name: &str, passed: bool, detail: String| {
+            checks.push(FFISelfTestCheck { name: name.to_string(), passed, detail });
+        };
+
+        let strings_before = LIVE_FFI_STRINGS.load(Ordering::Relaxed);
+        let vectors_before = LIVE_FFI_VECTORS.load(Ordering::Relaxed);
+        let buffers_before = LIVE_FFI_BUFFERS.load(Ordering::Relaxed);
+        let previous_format = PAYLOAD_FORMAT.load(Ordering::Relaxed);
+
+        // 1. Strings: JSON results and error messages
+        let mut string_failures = 0;
+        for _ in 0..FFI_SELFTEST_ITERATIONS {
+            let json = get_scenario_templates_json();
+            if json.is_null() {
+                string_failures += 1;
+            }
+            free_rust_string(json);
+
+            set_last_ffi_error("selftest error".to_string());
+            let error = get_last_error();
+            if error.is_null() || unsafe { CStr::from_ptr(error) }.to_str() != Ok("selftest error") {
+                string_failures += 1;
+            }
+            free_rust_string(error);
+        }
+        free_rust_string(ptr::null_mut());
+        check("string_alloc_free", string_failures == 0, format!("{} failures", string_failures));
+
+        // 2. Vectors
+        let mut vector_failures = 0;
+        for i in 0..FFI_SELFTEST_ITERATIONS {
+            let vector = vec_to_ffi_vector_f64((0..i).map(|v| v as f64).collect());
+            let contents = if vector.ptr.is_null() { &[][..] } else { unsafe { slice::from_raw_parts(vector.ptr, vector.len) } };
+            if vector.len != i || contents.iter().enumerate().any(|(j, &v)| v != j as f64) {
+                vector_failures += 1;
+            }
+            free_ffi_vector_f64(vector);
+        }
+        free_ffi_vector_f64(FFIVector_f64 { ptr: ptr::null(), len: 0 });
+        check("vector_alloc_free", vector_failures == 0, format!("{} failures", vector_failures));
+
+        // 3. Binary payloads in every format
+        let mut buffer_failures = 0;
+        for format in 0..3 {
+            set_ffi_payload_format(format);
+            for _ in 0..FFI_SELFTEST_ITERATIONS / 10 {
+                let buffer = encode_ffi_payload(&scenarios::get_scenario_templates());
+                if buffer.ptr.is_null() || buffer.len == 0 {
+                    buffer_failures += 1;
+                }
+                free_ffi_byte_buffer(buffer);
+            }
+        }
+        free_ffi_byte_buffer(empty_ffi_byte_buffer());
+        PAYLOAD_FORMAT.store(previous_format, Ordering::Relaxed);
+        check("byte_buffer_alloc_free", buffer_failures == 0, format!("{} failures", buffer_failures));
+
+        // 4. Reference data and validation results: null frees must be no-ops
+        free_reference_data(ptr::null_mut());
+        free_validation_result(ptr::null_mut());
+        let synthetic = create_synthetic_reference_data(0, 0.0);
+        check("reference_data_invalid_input", synthetic.is_null(),
+              "non-positive point count must be rejected".to_string());
+        if !synthetic.is_null() {
+            free_reference_data(synthetic);
+        }
+        let mut reference_failures = 0;
+        for i in 0..FFI_SELFTEST_ITERATIONS / 10 {
+            let reference = selftest_reference_data(i);
+            match reference_data_to_ffi(&reference) {
+                Ok(ffi_reference) => {
+                    let round_trip = ffi_to_reference_data(unsafe { &*ffi_reference });
+                    if round_trip.map_or(true, |data| data.values != reference.values || data.metadata != reference.metadata) {
+                        reference_failures += 1;
+                    }
+                    free_reference_data(ffi_reference);
+                }
+                Err(_) => reference_failures += 1,
+            }
+        }
+        check("reference_data_alloc_free", reference_failures == 0, format!("{} failures", reference_failures));
+        let mut result_failures = 0;
+        for i in 0..FFI_SELFTEST_ITERATIONS / 10 {
+            match validation_result_to_ffi(&selftest_validation_result(i)) {
+                Ok(ffi_result) => {
+                    let simulated = unsafe { &(*ffi_result).simulated_values };
+                    if simulated.len != i || unsafe { (*ffi_result).reference_data.is_null() } {
+                        result_failures += 1;
+                    }
+                    free_validation_result(ffi_result);
+                }
+                Err(_) => result_failures += 1,
+            }
+        }
+        check("validation_result_alloc_free", result_failures == 0, format!("{} failures", result_failures));
+
+        // 5. Bad inputs must fail cleanly with an error message
+        type BadInput = (&'static str, fn() -> bool);
+        let bad_inputs: Vec<BadInput> = vec![
+            ("scenario_template_null_id", || get_scenario_template_json(ptr::null()).is_null()),
+            ("annotation_null_json", || add_annotation_json_h(SIMULATIONS.default_handle(), ptr::null()) < 0),
+            ("annotation_invalid_json", || {
+                let invalid = CString::new("not json").unwrap();
+                add_annotation_json_h(SIMULATIONS.default_handle(), invalid.as_ptr()) < 0
+            }),
+            ("payload_unknown_format", || set_ffi_payload_format(99) < 0),
+            ("frame_packet_negative_step", || get_frame_packet_h(SIMULATIONS.default_handle(), -1, 0).ptr.is_null()),
+            ("frame_packet_unknown_encoding", || get_frame_packet_h(SIMULATIONS.default_handle(), 0, 7).ptr.is_null()),
+            ("study_payload_null_config", || run_parametric_study_payload(ptr::null(), 0).ptr.is_null()),
+            ("temperature_data_null_buffer", || get_temperature_data_h(SIMULATIONS.default_handle(), 0, ptr::null_mut(), 0) < 0),
+            ("temperature_probe_null_output", || get_temperature_at_point(SIMULATIONS.default_handle(), 0.0, 0.0, 0.0, ptr::null_mut()) < 0),
+            ("line_profile_null_buffer", || get_line_profile_data(SIMULATIONS.default_handle(), 0, 0.0, 0.0, 0.0, 0.0, 2, ptr::null_mut(), 0) < 0),
+            ("heat_flux_null_buffer", || get_heat_flux_data(SIMULATIONS.default_handle(), 0, ptr::null_mut(), ptr::null_mut(), 0) < 0),
+            ("metrics_null_output", || get_simulation_metrics(SIMULATIONS.default_handle(), -1, ptr::null_mut()) < 0),
+            ("history_null_buffer", || get_temperature_history(SIMULATIONS.default_handle(), ptr::null_mut(), 0, 1, 1, 1) < 0),
+            ("job_null_kind", || submit_job(SIMULATIONS.default_handle(), ptr::null(), ptr::null()) < 0),
+            ("job_unknown_kind", || {
+                let kind = CString::new("unknown").unwrap();
+                let request = CString::new("{}").unwrap();
+                submit_job(SIMULATIONS.default_handle(), kind.as_ptr(), request.as_ptr()) < 0
+            }),
+            ("job_result_unknown_id", || get_job_result_json(u64::MAX).is_null()),
+        ];
+        for (name, run) in bad_inputs {
+            let passed = run();
+            let error = LAST_ERROR.with(|cell| cell.borrow_mut().take()).map(|error| error.message);
+            check(name, passed && error.is_some(), error.unwrap_or_else(|| "no error message set".to_string()));
+        }
+
+        // 6. Results of the current simulation, if available
+        let default_handle = SIMULATIONS.default_handle();
+        let results_available = SIMULATIONS.get(default_handle)
+            .and_then(|shared| shared.state.lock().ok().map(|state| state.results.is_some()))
+            .unwrap_or(false);
+        if results_available {
+            let payload = get_simulation_results_payload_h(default_handle);
+            let frame = get_frame_packet_h(default_handle, 0, 1);
+            let passed = !payload.ptr.is_null() && FramePacket::decode(unsafe {
+                if frame.ptr.is_null() { &[][..] } else { slice::from_raw_parts(frame.ptr, frame.len) }
+            }).is_ok();
+            free_ffi_byte_buffer(payload);
+            free_ffi_byte_buffer(frame);
+            check("results_payload_and_frame", passed, String::new());
+        } else {
+            check("results_payload_and_frame", true, "skipped: no simulation results available".to_string());
+        }
+
+        // 7. Leak accounting
+        let leaked_strings = LIVE_FFI_STRINGS.load(Ordering::Relaxed) - strings_before;
+        let leaked_vectors = LIVE_FFI_VECTORS.load(Ordering::Relaxed) - vectors_before;
+        let leaked_buffers = LIVE_FFI_BUFFERS.load(Ordering::Relaxed) - buffers_before;
+        check("no_leaks", leaked_strings == 0 && leaked_vectors == 0 && leaked_buffers == 0,
+              format!("strings {}, vectors {}, buffers {}", leaked_strings, leaked_vectors, leaked_buffers));
+
+        LAST_ERROR.with(|cell| *cell.borrow_mut() = None);
+
+        let report = FFISelfTestReport {
+            passed: checks.iter().all(|c| c.passed),
+            iterations: FFI_SELFTEST_ITERATIONS,
+            checks,
+            leaked_strings,
+            leaked_vectors,
+            leaked_buffers,
+        };
+        match serde_json::to_string(&report) {
+            Ok(json_string) => CString::new(json_string).map_or_else(|e| {
+                set_last_ffi_error(format!("Failed to create CString for JSON: {}", e));
+                ptr::null_mut()
+            }, into_ffi_string_ptr),
+            Err(e) => {
+                set_last_ffi_error(format!("Failed to serialize self-test report to JSON: {}", e));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::solver::HeatSolver;
+
+    fn take_last_error() -> Option<String> {
+        LAST_ERROR.with(|cell| cell.borrow_mut().take()).map(|error| error.message)
+    }
+
+    #[test]
+    fn test_ffi_guard_translates_panics() {
+        let code: c_int = ffi_guard("test_int", || panic!("boom"));
+        assert_eq!(code, FFI_PANIC_ERROR_CODE);
+        assert_eq!(take_last_error().as_deref(), Some("Panic in test_int: boom"));
+
+        let pointer: *mut c_char = ffi_guard("test_ptr", || panic!("{}", String::from("formatted")));
+        assert!(pointer.is_null());
+        assert!(take_last_error().unwrap().contains("formatted"));
+
+        let buffer: FFIByteBuffer = ffi_guard("test_buffer", || panic!("boom"));
+        assert!(buffer.ptr.is_null() && buffer.len == 0);
+        assert!(take_last_error().unwrap().contains("test_buffer"));
+
+        // Sem pânico o valor é repassado sem registrar erro
+        assert_eq!(ffi_guard("test_ok", || 7 as c_int), 7);
+        assert!(take_last_error().is_none());
+    }
+
+    #[test]
+    fn test_last_error_json_is_structured() {
+        assert_eq!(pause_simulation_h(987_654), FFIErrorCode::UnknownHandle.return_code());
+        let json_ptr = get_last_error_json();
+        assert!(!json_ptr.is_null());
+        let error: serde_json::Value = serde_json::from_str(unsafe { CStr::from_ptr(json_ptr) }.to_str().unwrap()).unwrap();
+        free_rust_string(json_ptr);
+        assert_eq!(error["code"], FFIErrorCode::UnknownHandle.as_i32());
+        assert_eq!(error["name"], "unknown_handle");
+        assert_eq!(error["function"], "pause_simulation_h");
+        assert_eq!(error["context"]["handle"], 987_654);
+        assert!(error["message"].as_str().unwrap().contains("987654"));
+
+        // Erros dentro de chamadas aninhadas são atribuídos à função externa; o erro é consumido
+        let code: c_int = ffi_guard("outer_function", || {
+            let _ = get_temperature_at_point(987_654, 0.0, 0.0, 0.0, ptr::null_mut());
+            -1
+        });
+        assert_eq!(code, -1);
+        let error = LAST_ERROR.with(|cell| cell.borrow_mut().take()).unwrap();
+        assert_eq!(error.code, FFIErrorCode::NullPointer);
+        assert_eq!(error.function, "outer_function");
+        assert!(take_last_error().is_none());
+    }
+
+    #[test]
+    fn test_error_return_codes_are_consistent() {
+        // Um handle desconhecido retorna o mesmo código em todas as funções
+        let unknown = 987_655;
+        let mut metrics = FFISimulationMetrics::default();
+        let mut temperature = 0.0;
+        let codes = [
+            run_simulation_h(unknown),
+            pause_simulation_h(unknown),
+            resume_simulation_h(unknown),
+            cancel_simulation(unknown),
+            destroy_simulation_h(unknown),
+            get_simulation_metrics(unknown, -1, &mut metrics),
+            get_temperature_at_point(unknown, 0.0, 0.0, 0.0, &mut temperature),
+            close_shared_frame_channel(unknown),
+            add_results_to_ensemble_h(unknown),
+            get_live_frame_sequence(unknown) as c_int,
+        ];
+        let expected = [-4, -4, -4, -4, -4, -4, -4, -5, -4, -4];
+        assert_eq!(codes, expected);
+        take_last_error();
+
+        // O nome do código é obtido a partir do valor retornado
+        let name = |code: i64| {
+            let ptr = get_error_code_name(code);
+            let name = (!ptr.is_null()).then(|| unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string());
+            free_rust_string(ptr);
+            name
+        };
+        assert_eq!(name(0).as_deref(), Some("ok"));
+        assert_eq!(name(FFIErrorCode::UnknownHandle.return_code() as i64).as_deref(), Some("unknown_handle"));
+        assert_eq!(name(FFI_REQUEST_TOO_LARGE_ERROR_CODE as i64).as_deref(), Some("request_too_large"));
+        assert_eq!(name(-42), None);
+        let error = LAST_ERROR.with(|cell| cell.borrow_mut().take()).unwrap();
+        assert_eq!(error.code, FFIErrorCode::InvalidArgument);
+    }
+
+    #[test]
+    fn test_simulation_handles_are_independent() {
+        let ffi_params = FFISimulationParameters {
+            height: 1.0,
+            radius: 0.5,
+            nr: 10,
+            nz: 10,
+            initial_temperature: 25.0,
+            ambient_temperature: 25.0,
+            convection_coefficient: 10.0,
+            enable_convection: true,
+            enable_radiation: false,
+            total_time: 10.0,
+            time_step: 1.0,
+            time_steps: 10,
+        };
+        let first = create_simulation(&ffi_params);
+        let second = create_simulation(&ffi_params);
+        assert_ne!(first, INVALID_SIMULATION_HANDLE);
+        assert_ne!(second, INVALID_SIMULATION_HANDLE);
+        assert_ne!(first, second);
+        assert_eq!(create_simulation(ptr::null()), INVALID_SIMULATION_HANDLE);
+        assert!(take_last_error().unwrap().contains("create_simulation"));
+
+        // Destruir uma instância não afeta a outra; o handle destruído deixa de ser válido
+        assert_eq!(destroy_simulation_h(first), 0);
+        assert!(destroy_simulation_h(first) < 0);
+        assert!(take_last_error().is_some());
+        assert!(calculate_metrics_json_h(first).is_null());
+        assert!(take_last_error().unwrap().contains(&format!("handle {}", first)));
+        assert!(SIMULATIONS.get(second).is_some());
+
+        // Callback de progresso: registro, validação e remoção
+        extern "C" fn count_progress(user_data: *mut c_void, _: c_float, _: c_int, _: c_int, _: c_double) {
+            unsafe { &*(user_data as *const AtomicIsize) }.fetch_add(1, Ordering::Relaxed);
+        }
+        let calls = AtomicIsize::new(0);
+        let user_data = &calls as *const AtomicIsize as *mut c_void;
+        assert_eq!(register_progress_callback(second, None, user_data), FFIErrorCode::NullPointer.return_code());
+        assert_eq!(register_progress_callback(first, Some(count_progress), user_data), FFIErrorCode::UnknownHandle.return_code());
+        assert_eq!(register_progress_callback(second, Some(count_progress), user_data), 0);
+        assert!(SIMULATIONS.get(second).unwrap().has_progress_observer());
+        assert_eq!(unregister_progress_callback(second), 0);
+        assert!(!SIMULATIONS.get(second).unwrap().has_progress_observer());
+        take_last_error();
+
+        assert_eq!(destroy_simulation_h(second), 0);
+    }
+
+    #[test]
+    fn test_add_torch_rejects_unknown_flux_profile() {
+        let ffi_params = FFISimulationParameters {
+            height: 1.0,
+            radius: 0.5,
+            nr: 5,
+            nz: 5,
+            initial_temperature: 25.0,
+            ambient_temperature: 25.0,
+            convection_coefficient: 10.0,
+            enable_convection: false,
+            enable_radiation: false,
+            total_time: 10.0,
+            time_step: 1.0,
+            time_steps: 10,
+        };
+        let handle = create_simulation(&ffi_params);
+        let mut ffi_torch = FFIPlasmaTorch {
+            r_position: 0.0,
+            z_position: 0.5,
+            pitch: 180.0,
+            yaw: 0.0,
+            power: 100.0,
+            gas_flow: 0.01,
+            gas_temperature: 5000.0,
+            flux_profile: 2,
+            flux_profile_length: 0.1,
+        };
+        assert_eq!(add_plasma_torch_h(handle, &ffi_torch), 0);
+
+        // Códigos de perfil desconhecidos são rejeitados em vez de virarem gaussianos
+        ffi_torch.flux_profile = 7;
+        assert_eq!(add_plasma_torch_h(handle, &ffi_torch), FFIErrorCode::InvalidArgument.return_code());
+        let error = LAST_ERROR.with(|cell| cell.borrow_mut().take()).unwrap();
+        assert_eq!(error.code, FFIErrorCode::InvalidArgument);
+        assert!(error.message.contains("Unknown flux profile code: 7"));
+
+        let torches = SIMULATIONS.get(handle).unwrap().state.lock().unwrap().parameters.torches.clone();
+        assert_eq!(torches.len(), 1);
+        assert!(matches!(torches[0].flux_profile, TorchFluxProfile::TopHat { radius } if radius == 0.1));
+        assert_eq!(destroy_simulation_h(handle), 0);
+    }
+
+    #[test]
+    fn test_mesh_info_json_before_run() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 4);
+        params.radial_grading = crate::simulation::mesh::MeshGrading::Geometric { ratio: 1.3 };
+        let handle = SIMULATIONS.insert(SharedSimulationState::new(params));
+
+        let info = get_mesh_info_json(handle);
+        let json: serde_json::Value = serde_json::from_str(unsafe { CStr::from_ptr(info) }.to_str().unwrap()).unwrap();
+        free_rust_string(info);
+        assert_eq!(json["r_coords"].as_array().unwrap().len(), 5);
+        assert_eq!(json["z_faces"].as_array().unwrap().len(), 5);
+        assert_eq!(json["cell_volumes"][4].as_array().unwrap().len(), 4);
+        assert!(json["radial_grading"]["Geometric"].is_object());
+
+        assert_eq!(destroy_simulation_h(handle), 0);
+        assert!(get_mesh_info_json(handle).is_null());
+        take_last_error();
+    }
+
+    #[test]
+    fn test_material_library_endpoints() {
+        let steel = CString::new("steel").unwrap();
+        let by_id = get_material_json(steel.as_ptr());
+        let entry: serde_json::Value = serde_json::from_str(unsafe { CStr::from_ptr(by_id) }.to_str().unwrap()).unwrap();
+        free_rust_string(by_id);
+        assert_eq!(entry["id"], "steel");
+        assert_eq!(entry["user_defined"], false);
+
+        // Busca pelo nome de exibição
+        let name = CString::new(entry["material"]["name"].as_str().unwrap()).unwrap();
+        let by_name = get_material_json(name.as_ptr());
+        assert!(unsafe { CStr::from_ptr(by_name) }.to_str().unwrap().contains("\"id\":\"steel\""));
+        free_rust_string(by_name);
+
+        let unknown = CString::new("unobtainium").unwrap();
+        assert!(get_material_json(unknown.as_ptr()).is_null());
+        assert!(take_last_error().unwrap().contains("unobtainium"));
+
+        let library = get_material_library_json();
+        let json: serde_json::Value = serde_json::from_str(unsafe { CStr::from_ptr(library) }.to_str().unwrap()).unwrap();
+        free_rust_string(library);
+        let ids: Vec<&str> = json["materials"].as_array().unwrap().iter().map(|entry| entry["id"].as_str().unwrap()).collect();
+        assert!(ids.contains(&"steel") && ids.contains(&"copper"));
+        assert!(ids.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_generic_job_submission() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 4, 4);
+        params.time_steps = 3;
+        params.time_step = 1.0;
+        params.total_time = 3.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        let results = HeatSolver::new(params.clone()).unwrap()
+            .run(None, Arc::new(std::sync::atomic::AtomicBool::new(false))).unwrap();
+        let handle = SIMULATIONS.insert(SharedSimulationState::new(params));
+        SIMULATIONS.get(handle).unwrap().state.lock().unwrap().results = Some(results);
+
+        let kind = CString::new("export").unwrap();
+        let dir = std::env::temp_dir().join(format!("plasma_generic_job_{}", std::process::id()));
+        let request = CString::new(serde_json::json!({ "format": "Csv", "output_dir": dir }).to_string()).unwrap();
+        let job_id = submit_job(handle, kind.as_ptr(), request.as_ptr());
+        assert!(job_id > 0, "{:?}", take_last_error());
+        let job_id = job_id as u64;
+
+        let info = JOB_QUEUE.wait(job_id, std::time::Duration::from_secs(10)).unwrap();
+        assert_eq!(get_job_status(job_id), 2, "{:?}", info.message);
+        let result = get_job_result_json(job_id);
+        let json: serde_json::Value = serde_json::from_str(unsafe { CStr::from_ptr(result) }.to_str().unwrap()).unwrap();
+        free_rust_string(result);
+        assert_eq!(json["format"], "Csv");
+        assert!(json["path"].as_str().unwrap().ends_with(".csv"));
+        assert_eq!(cancel_job(job_id), FFIErrorCode::InvalidArgument.return_code());
+
+        // Requisição inválida e tarefa desconhecida
+        let invalid = CString::new("{\"format\": 3}").unwrap();
+        assert_eq!(submit_job(handle, kind.as_ptr(), invalid.as_ptr()), FFIErrorCode::InvalidArgument.return_code() as i64);
+        assert!(take_last_error().unwrap().contains("export job request"));
+        assert_eq!(get_job_status(u64::MAX), FFIErrorCode::InvalidArgument.return_code());
+        take_last_error();
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(destroy_simulation_h(handle), 0);
+    }
+
+    #[test]
+    fn test_simulation_metrics_struct() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 4, 4);
+        params.time_steps = 3;
+        params.time_step = 1.0;
+        params.total_time = 3.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        let handle = SIMULATIONS.insert(SharedSimulationState::new(params.clone()));
+
+        let mut metrics = FFISimulationMetrics::default();
+        assert_eq!(get_simulation_metrics(handle, -1, &mut metrics), FFIErrorCode::NotAvailable.return_code());
+        take_last_error();
+
+        let results = HeatSolver::new(params).unwrap()
+            .run(None, Arc::new(std::sync::atomic::AtomicBool::new(false))).unwrap();
+        let expected = calculate_headline_metrics(&results, results.executed_steps).unwrap();
+        SIMULATIONS.get(handle).unwrap().state.lock().unwrap().results = Some(results);
+
+        // Passo negativo: último passo executado
+        assert_eq!(get_simulation_metrics(handle, -1, &mut metrics), 0);
+        assert_eq!(metrics.time_step, 3);
+        assert_eq!(metrics.max_temperature, expected.max_temperature);
+        assert_eq!(metrics.total_energy, expected.total_energy);
+        assert!(metrics.min_temperature <= metrics.mean_temperature && metrics.mean_temperature <= metrics.max_temperature);
+
+        assert_eq!(get_simulation_metrics(handle, 0, &mut metrics), 0);
+        assert_eq!(metrics.time_step, 0);
+        assert_eq!(get_simulation_metrics(handle, 4, &mut metrics), FFIErrorCode::InvalidArgument.return_code());
+        take_last_error();
+
+        assert_eq!(destroy_simulation_h(handle), 0);
+        assert_eq!(get_simulation_metrics(handle, -1, &mut metrics), FFIErrorCode::UnknownHandle.return_code());
+        take_last_error();
+    }
+
+    #[test]
+    fn test_shared_frame_channel_lifecycle() {
+        let handle = SIMULATIONS.insert(SharedSimulationState::new(SimulationParameters::new(1.0, 0.5, 3, 4)));
+        assert!(open_shared_frame_channel(INVALID_SIMULATION_HANDLE).ptr.is_null());
+        take_last_error();
+
+        let channel = open_shared_frame_channel(handle);
+        assert!(!channel.ptr.is_null());
+        assert_eq!((channel.nr, channel.nz, channel.len), (3, 4, (8 + 12) * 8));
+        // Reabrir devolve a mesma região
+        assert_eq!(open_shared_frame_channel(handle).ptr, channel.ptr);
+
+        let mut frame = crate::simulation::frames::LiveFrame::new(3, 4);
+        frame.step = 7;
+        frame.temperature.fill(321.0);
+        SIMULATIONS.get(handle).unwrap().live_frames().publish(&mut frame);
+        let words = unsafe { slice::from_raw_parts(channel.ptr as *const u64, channel.len / 8) };
+        assert_eq!((words[0], words[1]), (2, 7));
+        assert_eq!(f64::from_bits(words[8 + 11]), 321.0);
+
+        assert_eq!(close_shared_frame_channel(handle), 0);
+        assert_eq!(close_shared_frame_channel(handle), FFIErrorCode::NotAvailable.return_code());
+        assert!(SIMULATIONS.get(handle).unwrap().live_frames().channel().is_none());
+        take_last_error();
+        assert_eq!(destroy_simulation_h(handle), 0);
+    }
+
+    #[test]
+    fn test_ffi_selftest_passes() {
+        let report_ptr = run_ffi_selftest();
+        assert!(!report_ptr.is_null());
+        let report: serde_json::Value = serde_json::from_str(
+            unsafe { CStr::from_ptr(report_ptr) }.to_str().unwrap()
+        ).unwrap();
+        free_rust_string(report_ptr);
+
+        // O contador de vazamentos é global e pode ser afetado por testes em paralelo
+        for check in report["checks"].as_array().unwrap() {
+            if check["name"] != "no_leaks" {
+                assert_eq!(check["passed"], true, "{}", check);
+            }
+        }
+        assert_eq!(report["iterations"], FFI_SELFTEST_ITERATIONS);
+    }
+
+    #[test]
+    fn test_reference_data_round_trip() {
+        let mut reference = selftest_reference_data(3);
+        let ffi_reference = reference_data_to_ffi(&reference).unwrap();
+        let converted = ffi_to_reference_data(unsafe { &*ffi_reference }).unwrap();
+        assert_eq!(converted.name, reference.name);
+        assert_eq!(converted.coordinates, reference.coordinates);
+        assert_eq!(converted.uncertainties, reference.uncertainties);
+        assert_eq!(converted.times, reference.times);
+        assert_eq!(converted.metadata, reference.metadata);
+        free_reference_data(ffi_reference);
+
+        // Sem incertezas e instantes os vetores são nulos; a conversão de volta preserva a ausência
+        reference.uncertainties = None;
+        reference.times = None;
+        let ffi_reference = reference_data_to_ffi(&reference).unwrap();
+        assert!(unsafe { (*ffi_reference).uncertainties.ptr.is_null() && (*ffi_reference).times.ptr.is_null() });
+        let converted = ffi_to_reference_data(unsafe { &*ffi_reference }).unwrap();
+        assert!(converted.uncertainties.is_none() && converted.times.is_none());
+        free_reference_data(ffi_reference);
+
+        // Tamanhos inconsistentes e bytes nulos internos são rejeitados
+        reference.values.pop();
+        assert!(reference_data_to_ffi(&reference).is_err());
+        reference.values.push(0.0);
+        reference.metadata.insert("bad\0key".to_string(), String::new());
+        assert!(reference_data_to_ffi(&reference).is_err());
+    }
+
+    #[test]
+    fn test_import_reference_data_with_csv_options() {
+        let path = std::env::temp_dir().join(format!("ffi_import_{}.csv", std::process::id()));
+        std::fs::write(&path, "tempo;raio;altura;T\n0;10;20;30\n1;10;20;35\n").unwrap();
+        let input_path = CString::new(path.to_str().unwrap()).unwrap();
+        let format = CString::new("CSV").unwrap();
+        let options_json = CString::new(r#"{
+            "delimiter": ";",
+            "columns": {"r": "raio", "z": "altura", "time": 0, "value": 3, "uncertainty": null},
+            "units": {"length": "Centimeter", "time": "Minute", "temperature": "Celsius"}
+        }"#).unwrap();
+        let mut options = FFIImportOptions { input_path: input_path.as_ptr(), format: format.as_ptr(), options_json: options_json.as_ptr() };
+
+        let ffi_reference = import_reference_data(&options);
+        assert!(!ffi_reference.is_null(), "{:?}", take_last_error());
+        let reference = ffi_to_reference_data(unsafe { &*ffi_reference }).unwrap();
+        free_reference_data(ffi_reference);
+        assert_eq!(reference.coordinates, vec![(0.1, 0.0, 0.2); 2]);
+        assert_eq!(reference.values, vec![30.0, 35.0]);
+        assert_eq!(reference.times, Some(vec![0.0, 60.0]));
+
+        // Sem opções valem as colunas padrão, ausentes neste arquivo; JSON inválido é recusado
+        options.options_json = ptr::null();
+        assert!(import_reference_data(&options).is_null());
+        assert!(take_last_error().is_some());
+        let invalid = CString::new("{\"delimiter\": 3}").unwrap();
+        options.options_json = invalid.as_ptr();
+        assert!(import_reference_data(&options).is_null());
+        assert!(take_last_error().unwrap().contains("CSV import options"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_validation_result_conversion() {
+        let mut result = selftest_validation_result(4);
+        let ffi_result = validation_result_to_ffi(&result).unwrap();
         unsafe {
-            // Safety: This assumes `message` was allocated by `CString::into_raw`.
-            // This is true for strings returned by `get_last_error` and the JSON functions.
-            let _ = CString::from_raw(message);
+            assert_eq!(CStr::from_ptr((*ffi_result).name).to_str(), Ok("selftest"));
+            assert_eq!((*ffi_result).metrics.root_mean_squared_error, 1.0);
+            assert_eq!(slice::from_raw_parts((*ffi_result).simulated_values.ptr, 4), &result.simulated_values[..]);
+            assert_eq!(ffi_map_to_map(&(*ffi_result).metadata).unwrap(), result.metadata);
+            let reference = ffi_to_reference_data(&*(*ffi_result).reference_data).unwrap();
+            assert_eq!(reference.values, result.reference_data.values);
         }
+        free_validation_result(ffi_result);
+
+        // Falha na conversão dos dados de referência não retorna resultado parcial
+        result.reference_data.name = "bad\0name".to_string();
+        assert!(validation_result_to_ffi(&result).is_err());
+        result.reference_data.name.clear();
+        result.simulated_values.pop();
+        assert!(validation_result_to_ffi(&result).is_err());
     }
 }
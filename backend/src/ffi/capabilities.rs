@@ -0,0 +1,118 @@
+// Versão da API FFI e descoberta das capacidades compiladas no backend
+//
+// O frontend consulta a versão e as capacidades antes de usar funções opcionais, de modo
+// que builds diferentes do backend (com ou sem as features `sqlite`, `s3` e `async`)
+// possam ser usados sem falhas por símbolos ausentes. A versão segue o versionamento
+// semântico: a versão maior muda quando funções são removidas ou têm a assinatura ou os
+// códigos de retorno alterados; a menor, quando funções são acrescentadas.
+
+use serde::Serialize;
+
+use super::payload::PayloadFormat;
+use crate::simulation::export_worker::ExportJobFormat;
+use crate::simulation::frames::FrameEncoding;
+use crate::simulation::solver::SolverScheme;
+
+/// Versão maior da API FFI
+pub const FFI_API_VERSION_MAJOR: u32 = 2;
+/// Versão menor da API FFI
+pub const FFI_API_VERSION_MINOR: u32 = 0;
+/// Versão de correção da API FFI
+pub const FFI_API_VERSION_PATCH: u32 = 0;
+
+/// Versão da API codificada em um inteiro (maior·10000 + menor·100 + correção)
+pub fn encoded_api_version() -> u32 {
+    FFI_API_VERSION_MAJOR * 10_000 + FFI_API_VERSION_MINOR * 100 + FFI_API_VERSION_PATCH
+}
+
+/// Estrutura que representa as features opcionais compiladas
+#[derive(Debug, Clone, Serialize)]
+pub struct CompiledFeatures {
+    /// API assíncrona (feature `async`)
+    pub r#async: bool,
+    /// Armazenamento em object stores S3 (feature `s3`)
+    pub s3: bool,
+    /// Banco SQLite dos estudos paramétricos (feature `sqlite`)
+    pub sqlite: bool,
+}
+
+/// Estrutura que representa as capacidades do backend
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    /// Versão da API FFI ("maior.menor.correção")
+    pub api_version: String,
+    /// Versão do crate do backend
+    pub backend_version: String,
+    /// Esquemas de integração temporal do solucionador
+    pub solver_schemes: Vec<SolverScheme>,
+    /// Formatos das exportações em segundo plano
+    pub export_formats: Vec<ExportJobFormat>,
+    /// Tipos de tarefa aceitos por `submit_job`
+    pub job_kinds: Vec<&'static str>,
+    /// Formatos dos payloads binários (`set_ffi_payload_format`)
+    pub payload_formats: Vec<&'static str>,
+    /// Codificações dos pacotes de quadros
+    pub frame_encodings: Vec<FrameEncoding>,
+    /// Indica se há aceleração por GPU (nenhum backend de GPU é compilado atualmente)
+    pub gpu_available: bool,
+    /// Paralelismo disponível na CPU (threads)
+    pub cpu_threads: usize,
+    /// Features opcionais compiladas
+    pub features: CompiledFeatures,
+    /// Funções FFI opcionais presentes neste build
+    pub optional_functions: Vec<&'static str>,
+}
+
+/// Funções FFI exportadas apenas com a feature `sqlite`
+const SQLITE_FUNCTIONS: [&str; 4] = [
+    "open_parametric_store",
+    "list_parametric_store_studies_json",
+    "query_parametric_cases_json",
+    "top_parametric_cases_json",
+];
+
+/// Levanta as capacidades deste build
+pub fn capabilities() -> Capabilities {
+    let mut optional_functions = Vec::new();
+    if cfg!(feature = "sqlite") {
+        optional_functions.extend(SQLITE_FUNCTIONS);
+    }
+
+    Capabilities {
+        api_version: format!("{}.{}.{}", FFI_API_VERSION_MAJOR, FFI_API_VERSION_MINOR, FFI_API_VERSION_PATCH),
+        backend_version: env!("CARGO_PKG_VERSION").to_string(),
+        solver_schemes: vec![SolverScheme::Explicit, SolverScheme::Adi],
+        export_formats: vec![ExportJobFormat::VtkSeries, ExportJobFormat::PngFrames, ExportJobFormat::Csv],
+        job_kinds: vec!["parametric_study", "export", "report"],
+        payload_formats: [PayloadFormat::Json, PayloadFormat::MessagePack, PayloadFormat::Cbor]
+            .iter()
+            .map(PayloadFormat::name)
+            .collect(),
+        frame_encodings: vec![FrameEncoding::F32, FrameEncoding::U16],
+        gpu_available: false,
+        cpu_threads: rayon::current_num_threads(),
+        features: CompiledFeatures {
+            r#async: cfg!(feature = "async"),
+            s3: cfg!(feature = "s3"),
+            sqlite: cfg!(feature = "sqlite"),
+        },
+        optional_functions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_json() {
+        let json = serde_json::to_value(capabilities()).unwrap();
+        assert_eq!(json["api_version"], "2.0.0");
+        assert_eq!(encoded_api_version(), 20_000);
+        assert_eq!(json["solver_schemes"], serde_json::json!(["Explicit", "Adi"]));
+        assert_eq!(json["payload_formats"], serde_json::json!(["json", "msgpack", "cbor"]));
+        assert_eq!(json["features"]["async"], cfg!(feature = "async"));
+        assert_eq!(json["features"]["sqlite"], cfg!(feature = "sqlite"));
+        assert_eq!(json["optional_functions"].as_array().unwrap().is_empty(), !cfg!(feature = "sqlite"));
+    }
+}
@@ -1,9 +1,4 @@
 // Conversões de tipos entre Dart e Rust
 
-use std::ffi::{c_void, CStr, CString};
-use std::os::raw::{c_char, c_int, c_float};
-use std::ptr;
-use std::slice;
-
 // Este arquivo conterá funções auxiliares para conversão de tipos entre Dart e Rust
 // que serão implementadas conforme necessário durante o desenvolvimento do frontend
@@ -0,0 +1,164 @@
+// Erros estruturados da FFI (código, mensagem, função e contexto)
+//
+// Cada função FFI que falha registra o último erro da thread chamadora. Além da
+// mensagem (retornada por `get_last_error`, mantida por compatibilidade), o erro tem
+// um código documentado em `FFIErrorCode`, o nome da função FFI em que ocorreu e um
+// contexto opcional em JSON (por exemplo, o handle desconhecido).
+//
+// Todas as funções que retornam `c_int` ou `i64` seguem o mesmo contrato: 0 (ou um valor
+// não negativo documentado, como um ID ou uma contagem) em caso de sucesso e, em caso de
+// falha, o código do erro registrado negado (`-(FFIErrorCode as c_int)`, ver
+// `FFIErrorCode::return_code`). Um handle desconhecido, por exemplo, retorna sempre -4.
+
+use std::os::raw::c_int;
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+/// Códigos de erro da FFI (campo `code` de `get_last_error_json`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FFIErrorCode {
+    /// Nenhum erro (reservado no contrato C; nunca registrado como último erro)
+    Ok = 0,
+    /// Argumento inválido (fora do intervalo, formato ou valor não aceito)
+    InvalidArgument = 1,
+    /// Ponteiro nulo recebido onde um valor era obrigatório
+    NullPointer = 2,
+    /// String recebida não é UTF-8 válido
+    InvalidUtf8 = 3,
+    /// Handle de simulação não registrado (nunca criado ou já destruído)
+    UnknownHandle = 4,
+    /// Dado solicitado ainda não disponível (sem resultados, projeto não aberto, etc.)
+    NotAvailable = 5,
+    /// Mutex envenenado por um pânico anterior
+    LockPoisoned = 6,
+    /// Buffer fornecido pelo chamador é pequeno demais
+    BufferTooSmall = 7,
+    /// Requisição acima do limite de memória (contexto com alternativas sugeridas)
+    RequestTooLarge = 8,
+    /// Pânico capturado na fronteira FFI
+    Panic = 9,
+    /// Falha da operação solicitada (E/S, serialização, validação do modelo, etc.)
+    OperationFailed = 10,
+}
+
+impl FFIErrorCode {
+    /// Valor numérico do código
+    pub fn as_i32(self) -> i32 {
+        self as i32
+    }
+
+    /// Valor retornado pelas funções `c_int`/`i64` da FFI: o código negado (0 para `Ok`)
+    pub const fn return_code(self) -> c_int {
+        -(self as c_int)
+    }
+
+    /// Código correspondente a um valor retornado pela FFI (`None` se não for um código conhecido)
+    pub fn from_return_code(value: i64) -> Option<Self> {
+        [
+            FFIErrorCode::Ok,
+            FFIErrorCode::InvalidArgument,
+            FFIErrorCode::NullPointer,
+            FFIErrorCode::InvalidUtf8,
+            FFIErrorCode::UnknownHandle,
+            FFIErrorCode::NotAvailable,
+            FFIErrorCode::LockPoisoned,
+            FFIErrorCode::BufferTooSmall,
+            FFIErrorCode::RequestTooLarge,
+            FFIErrorCode::Panic,
+            FFIErrorCode::OperationFailed,
+        ].into_iter().find(|code| code.return_code() as i64 == value)
+    }
+
+    /// Nome estável do código (snake_case)
+    pub fn name(self) -> &'static str {
+        match self {
+            FFIErrorCode::Ok => "ok",
+            FFIErrorCode::InvalidArgument => "invalid_argument",
+            FFIErrorCode::NullPointer => "null_pointer",
+            FFIErrorCode::InvalidUtf8 => "invalid_utf8",
+            FFIErrorCode::UnknownHandle => "unknown_handle",
+            FFIErrorCode::NotAvailable => "not_available",
+            FFIErrorCode::LockPoisoned => "lock_poisoned",
+            FFIErrorCode::BufferTooSmall => "buffer_too_small",
+            FFIErrorCode::RequestTooLarge => "request_too_large",
+            FFIErrorCode::Panic => "panic",
+            FFIErrorCode::OperationFailed => "operation_failed",
+        }
+    }
+}
+
+impl Serialize for FFIErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.as_i32())
+    }
+}
+
+/// Estrutura que representa o último erro de uma chamada FFI
+///
+/// Serializada como `{code, name, message, function, context}`, com `name` o nome estável do código.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FFIError {
+    /// Código do erro
+    pub code: FFIErrorCode,
+    /// Mensagem legível (a mesma retornada por `get_last_error`)
+    pub message: String,
+    /// Função FFI em que o erro ocorreu (vazia fora de uma chamada FFI)
+    pub function: String,
+    /// Contexto adicional (nulo se não houver)
+    pub context: Value,
+}
+
+impl FFIError {
+    /// Cria um erro sem contexto
+    pub fn new(code: FFIErrorCode, message: String, function: &str) -> Self {
+        Self { code, message, function: function.to_string(), context: Value::Null }
+    }
+
+    /// Acrescenta o contexto do erro
+    pub fn with_context(mut self, context: Value) -> Self {
+        self.context = context;
+        self
+    }
+}
+
+impl Serialize for FFIError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("FFIError", 5)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("name", self.code.name())?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("function", &self.function)?;
+        state.serialize_field("context", &self.context)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_json_shape() {
+        let error = FFIError::new(FFIErrorCode::UnknownHandle, "Unknown simulation handle 7".to_string(), "pause_simulation_h")
+            .with_context(serde_json::json!({ "handle": 7 }));
+        let json: Value = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], 4);
+        assert_eq!(json["name"], "unknown_handle");
+        assert_eq!(json["message"], "Unknown simulation handle 7");
+        assert_eq!(json["function"], "pause_simulation_h");
+        assert_eq!(json["context"]["handle"], 7);
+
+        let plain = serde_json::to_value(FFIError::new(FFIErrorCode::Panic, "boom".to_string(), "")).unwrap();
+        assert!(plain["context"].is_null());
+        assert_eq!(FFIErrorCode::BufferTooSmall.name(), "buffer_too_small");
+
+        // Valores retornados pelas funções `c_int`
+        assert_eq!(FFIErrorCode::UnknownHandle.return_code(), -4);
+        assert_eq!(FFIErrorCode::from_return_code(-4), Some(FFIErrorCode::UnknownHandle));
+        assert_eq!(FFIErrorCode::from_return_code(0), Some(FFIErrorCode::Ok));
+        assert_eq!(FFIErrorCode::from_return_code(-42), None);
+    }
+}
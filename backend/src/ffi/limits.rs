@@ -0,0 +1,133 @@
+// Limite de memória das APIs de dados FFI (requisições grandes retornam erro estruturado)
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::payload::PayloadFormat;
+use crate::simulation::solver::SimulationResults;
+
+/// Limite padrão de alocação de uma requisição FFI (512 MiB)
+pub const DEFAULT_MEMORY_CAP_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Identificador do erro estruturado de requisição grande demais
+pub const REQUEST_TOO_LARGE: &str = "request_too_large";
+
+// Limite corrente (configurável pelo aplicativo)
+static MEMORY_CAP_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MEMORY_CAP_BYTES);
+
+/// Retorna o limite de alocação corrente (bytes)
+pub fn memory_cap() -> u64 {
+    MEMORY_CAP_BYTES.load(Ordering::Relaxed)
+}
+
+/// Define o limite de alocação (bytes); zero restaura o padrão
+pub fn set_memory_cap(bytes: u64) {
+    let cap = if bytes == 0 { DEFAULT_MEMORY_CAP_BYTES } else { bytes };
+    MEMORY_CAP_BYTES.store(cap, Ordering::Relaxed);
+}
+
+/// Estrutura que representa uma alternativa sugerida a uma requisição grande demais
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuggestedAlternative {
+    /// Função FFI (ou configuração) sugerida
+    pub function: String,
+    /// Descrição da alternativa
+    pub description: String,
+}
+
+/// Estrutura que representa o erro estruturado de uma requisição acima do limite
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OversizedRequest {
+    /// Sempre `request_too_large`
+    pub error: String,
+    /// Função FFI que recusou a requisição
+    pub request: String,
+    /// Alocação estimada (bytes)
+    pub estimated_bytes: u64,
+    /// Limite corrente (bytes)
+    pub limit_bytes: u64,
+    /// Alternativas por streaming ou subamostragem
+    pub alternatives: Vec<SuggestedAlternative>,
+}
+
+impl OversizedRequest {
+    /// Serializa o erro em JSON (mensagem de `get_last_error`)
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| format!("{{\"error\":\"{}\"}}", REQUEST_TOO_LARGE))
+    }
+}
+
+/// Verifica se a alocação estimada de uma requisição cabe no limite corrente
+pub fn check_request(request: &str, estimated_bytes: u64, alternatives: &[(&str, &str)]) -> Result<(), OversizedRequest> {
+    let limit_bytes = memory_cap();
+    if estimated_bytes <= limit_bytes {
+        return Ok(());
+    }
+    Err(OversizedRequest {
+        error: REQUEST_TOO_LARGE.to_string(),
+        request: request.to_string(),
+        estimated_bytes,
+        limit_bytes,
+        alternatives: alternatives.iter()
+            .map(|(function, description)| SuggestedAlternative {
+                function: function.to_string(),
+                description: description.to_string(),
+            })
+            .collect(),
+    })
+}
+
+/// Bytes dos campos em precisão total dos resultados (históricos de temperatura,
+/// entalpia e frações de fase)
+pub fn results_history_bytes(results: &SimulationResults) -> u64 {
+    let mut values = results.temperature.len() + results.enthalpy.len();
+    if let Some(info) = &results.phase_change_info {
+        values += info.melt_fraction.as_ref().map_or(0, |history| history.len());
+        values += info.vapor_fraction.as_ref().map_or(0, |history| history.len());
+    }
+    values as u64 * std::mem::size_of::<f64>() as u64
+}
+
+/// Estima o tamanho serializado de dados em `f64` ocupando `raw_bytes` em memória
+///
+/// Em JSON cada número ocupa cerca de 20 caracteres (2,5× os 8 bytes); MessagePack e
+/// CBOR usam 9 bytes por número. O buffer serializado coexiste com os dados originais.
+pub fn serialized_bytes(raw_bytes: u64, format: PayloadFormat) -> u64 {
+    match format {
+        PayloadFormat::Json => raw_bytes / 2 * 5,
+        PayloadFormat::MessagePack | PayloadFormat::Cbor => raw_bytes / 8 * 9,
+    }
+}
+
+/// Bytes de um campo volumétrico (nr × nθ × nz) em `f64` por `steps` passos
+pub fn voxel_bytes(nr: usize, ntheta: usize, nz: usize, steps: usize) -> u64 {
+    (nr as u64)
+        .saturating_mul(ntheta as u64)
+        .saturating_mul(nz as u64)
+        .saturating_mul(steps as u64)
+        .saturating_mul(std::mem::size_of::<f64>() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oversized_request_reports_alternatives() {
+        assert!(check_request("small", 1024, &[]).is_ok());
+
+        let huge = voxel_bytes(500, 360, 500, 1000);
+        assert!(huge > DEFAULT_MEMORY_CAP_BYTES);
+        let error = check_request(
+            "get_voxel_temperature_payload",
+            serialized_bytes(huge, PayloadFormat::Json),
+            &[("get_frame_packet", "Stream 2D frames one time step at a time")],
+        ).unwrap_err();
+        assert_eq!(error.limit_bytes, DEFAULT_MEMORY_CAP_BYTES);
+        assert_eq!(error.estimated_bytes, huge / 2 * 5);
+
+        let parsed: OversizedRequest = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(parsed.error, REQUEST_TOO_LARGE);
+        assert_eq!(parsed.alternatives[0].function, "get_frame_packet");
+    }
+}
@@ -2,11 +2,10 @@
 
 pub mod bindings;
 pub mod conversions;
+pub mod payload;
+pub mod limits;
+pub mod registry;
+pub mod errors;
+pub mod capabilities;
 
 // Re-exportar estruturas principais
-pub use bindings::{
-    FFISimulationParameters,
-    FFIPlasmaTorch,
-    FFIMaterialProperties,
-    FFISimulationState,
-};
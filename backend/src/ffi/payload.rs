@@ -0,0 +1,101 @@
+// Codificação de payloads FFI em JSON, MessagePack ou CBOR com os mesmos esquemas
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::os::raw::c_int;
+
+/// Enumeração que representa o formato de serialização dos payloads FFI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadFormat {
+    /// JSON (UTF-8)
+    Json,
+    /// MessagePack (mapas com nomes de campos, mesmo esquema do JSON)
+    MessagePack,
+    /// CBOR (RFC 8949)
+    Cbor,
+}
+
+impl PayloadFormat {
+    /// Converte o código FFI (0 = JSON, 1 = MessagePack, 2 = CBOR) no formato
+    pub fn from_code(code: c_int) -> Option<Self> {
+        match code {
+            0 => Some(PayloadFormat::Json),
+            1 => Some(PayloadFormat::MessagePack),
+            2 => Some(PayloadFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Retorna o código FFI do formato
+    pub fn code(&self) -> c_int {
+        match self {
+            PayloadFormat::Json => 0,
+            PayloadFormat::MessagePack => 1,
+            PayloadFormat::Cbor => 2,
+        }
+    }
+
+    /// Retorna o nome do formato
+    pub fn name(&self) -> &'static str {
+        match self {
+            PayloadFormat::Json => "json",
+            PayloadFormat::MessagePack => "msgpack",
+            PayloadFormat::Cbor => "cbor",
+        }
+    }
+}
+
+/// Serializa um valor no formato especificado
+pub fn encode_payload<T: Serialize>(value: &T, format: PayloadFormat) -> Result<Vec<u8>, String> {
+    match format {
+        PayloadFormat::Json => serde_json::to_vec(value)
+            .map_err(|e| format!("Failed to serialize payload to JSON: {}", e)),
+        // `to_vec_named` preserva os nomes dos campos, mantendo o esquema do JSON
+        PayloadFormat::MessagePack => rmp_serde::to_vec_named(value)
+            .map_err(|e| format!("Failed to serialize payload to MessagePack: {}", e)),
+        PayloadFormat::Cbor => {
+            let mut buffer = Vec::new();
+            ciborium::ser::into_writer(value, &mut buffer)
+                .map_err(|e| format!("Failed to serialize payload to CBOR: {}", e))?;
+            Ok(buffer)
+        }
+    }
+}
+
+/// Desserializa um valor a partir de bytes no formato especificado
+pub fn decode_payload<T: DeserializeOwned>(bytes: &[u8], format: PayloadFormat) -> Result<T, String> {
+    match format {
+        PayloadFormat::Json => serde_json::from_slice(bytes)
+            .map_err(|e| format!("Failed to deserialize JSON payload: {}", e)),
+        PayloadFormat::MessagePack => rmp_serde::from_slice(bytes)
+            .map_err(|e| format!("Failed to deserialize MessagePack payload: {}", e)),
+        PayloadFormat::Cbor => ciborium::de::from_reader(bytes)
+            .map_err(|e| format!("Failed to deserialize CBOR payload: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::annotations::{AnnotationKind, TimelineAnnotation};
+
+    #[test]
+    fn test_payload_round_trip_all_formats() {
+        let annotations = vec![
+            TimelineAnnotation::new(10.0, AnnotationKind::TapEvent, "Vazamento"),
+            TimelineAnnotation::new(20.0, AnnotationKind::Custom("Inspeção".to_string()), "Inspeção visual"),
+        ];
+
+        for code in 0..3 {
+            let format = PayloadFormat::from_code(code).unwrap();
+            assert_eq!(format.code(), code);
+
+            let bytes = encode_payload(&annotations, format).unwrap();
+            let decoded: Vec<TimelineAnnotation> = decode_payload(&bytes, format).unwrap();
+            assert_eq!(decoded.len(), 2);
+            assert_eq!(decoded[1].kind, AnnotationKind::Custom("Inspeção".to_string()));
+        }
+
+        assert!(PayloadFormat::from_code(3).is_none());
+    }
+}
@@ -0,0 +1,227 @@
+// Registro das instâncias de simulação acessadas pela FFI
+//
+// Cada simulação criada recebe um handle opaco `u64` (nunca zero), usado pelas funções
+// FFI com sufixo `_h`. As funções antigas de instância única operam sobre a instância
+// padrão, criada por `initialize_simulation`. As instâncias são compartilhadas por `Arc`,
+// de modo que o travamento do registro dura apenas a consulta. O mapa fica sob um
+// `RwLock`: consultas de várias threads (por exemplo, isolates Dart distintos) ocorrem em
+// paralelo, e só criação, substituição e remoção de instâncias são exclusivas. Nenhum
+// estado global é mutável fora desses travamentos, sem `static mut` nem `unsafe`.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::simulation::SharedSimulationState;
+
+/// Handle opaco de uma instância de simulação
+pub type SimulationHandle = u64;
+
+/// Handle inválido (retornado em caso de erro; nenhuma instância o recebe)
+pub const INVALID_SIMULATION_HANDLE: SimulationHandle = 0;
+
+/// Estrutura que representa o registro de instâncias de simulação
+///
+/// Pode ser usada como `static` (construtor `const`).
+pub struct SimulationRegistry {
+    next_handle: AtomicU64,
+    default_handle: AtomicU64,
+    instances: RwLock<BTreeMap<SimulationHandle, Arc<SharedSimulationState>>>,
+}
+
+impl SimulationRegistry {
+    /// Cria um registro vazio
+    pub const fn new() -> Self {
+        Self {
+            next_handle: AtomicU64::new(1),
+            default_handle: AtomicU64::new(INVALID_SIMULATION_HANDLE),
+            instances: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Trava o mapa de instâncias para leitura; o mapa nunca fica inconsistente, então o
+    /// envenenamento por pânico é ignorado
+    fn instances(&self) -> RwLockReadGuard<'_, BTreeMap<SimulationHandle, Arc<SharedSimulationState>>> {
+        self.instances.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Trava o mapa de instâncias para escrita
+    fn instances_mut(&self) -> RwLockWriteGuard<'_, BTreeMap<SimulationHandle, Arc<SharedSimulationState>>> {
+        self.instances.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Registra uma instância e retorna seu handle
+    pub fn insert(&self, state: SharedSimulationState) -> SimulationHandle {
+        let handle = self.next_handle.fetch_add(1, Ordering::Relaxed);
+        self.instances_mut().insert(handle, Arc::new(state));
+        handle
+    }
+
+    /// Substitui a instância de um handle existente, retornando a nova instância
+    pub fn replace(&self, handle: SimulationHandle, state: SharedSimulationState) -> Option<Arc<SharedSimulationState>> {
+        let mut instances = self.instances_mut();
+        let instance = instances.get_mut(&handle)?;
+        *instance = Arc::new(state);
+        Some(instance.clone())
+    }
+
+    /// Obtém a instância de um handle
+    pub fn get(&self, handle: SimulationHandle) -> Option<Arc<SharedSimulationState>> {
+        self.instances().get(&handle).cloned()
+    }
+
+    /// Remove a instância de um handle (e a desmarca como padrão)
+    pub fn remove(&self, handle: SimulationHandle) -> Option<Arc<SharedSimulationState>> {
+        let removed = self.instances_mut().remove(&handle);
+        let _ = self.default_handle.compare_exchange(handle, INVALID_SIMULATION_HANDLE, Ordering::Relaxed, Ordering::Relaxed);
+        removed
+    }
+
+    /// Handles registrados, em ordem de criação
+    pub fn handles(&self) -> Vec<SimulationHandle> {
+        self.instances().keys().copied().collect()
+    }
+
+    /// Handle da instância padrão (`INVALID_SIMULATION_HANDLE` se não houver)
+    pub fn default_handle(&self) -> SimulationHandle {
+        self.default_handle.load(Ordering::Relaxed)
+    }
+
+    /// Define a instância padrão usada pelas funções de instância única
+    pub fn set_default(&self, handle: SimulationHandle) {
+        self.default_handle.store(handle, Ordering::Relaxed);
+    }
+
+    /// Instância padrão, se registrada
+    pub fn default_instance(&self) -> Option<Arc<SharedSimulationState>> {
+        self.get(self.default_handle())
+    }
+
+    /// Retorna o handle da instância padrão ou, se não houver, registra a criada por
+    /// `create` e a marca como padrão, tudo sob um único travamento de escrita (duas
+    /// threads concorrentes nunca criam duas instâncias padrão). O `bool` indica se a
+    /// instância foi criada agora; erros de `create` são repassados sem registrar nada
+    pub fn get_or_insert_default<E>(
+        &self,
+        create: impl FnOnce() -> Result<SharedSimulationState, E>,
+    ) -> Result<(SimulationHandle, bool), E> {
+        let mut instances = self.instances_mut();
+        let current = self.default_handle();
+        if instances.contains_key(&current) {
+            return Ok((current, false));
+        }
+        let state = create()?;
+        let handle = self.next_handle.fetch_add(1, Ordering::Relaxed);
+        instances.insert(handle, Arc::new(state));
+        self.set_default(handle);
+        Ok((handle, true))
+    }
+
+    /// Limpa o envenenamento dos mutexes de todas as instâncias após um pânico capturado
+    pub fn clear_poison(&self) {
+        self.instances.clear_poison();
+        for shared in self.instances().values() {
+            shared.clear_poison();
+        }
+    }
+}
+
+impl Default for SimulationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::SimulationParameters;
+
+    #[test]
+    fn test_registry_handles_and_default_instance() {
+        let registry = SimulationRegistry::new();
+        let parameters = SimulationParameters::new(1.0, 0.5, 4, 4);
+        let first = registry.insert(SharedSimulationState::new(parameters.clone()));
+        let second = registry.insert(SharedSimulationState::new(parameters.clone()));
+        assert_ne!(first, INVALID_SIMULATION_HANDLE);
+        assert_ne!(first, second);
+        assert_eq!(registry.handles(), vec![first, second]);
+        assert!(registry.get(INVALID_SIMULATION_HANDLE).is_none());
+
+        // Sem instância padrão até que uma seja marcada
+        assert!(registry.default_instance().is_none());
+        registry.set_default(first);
+        assert!(registry.default_instance().is_some());
+
+        // Substituição só para handles existentes; remoção desmarca a padrão
+        assert!(registry.replace(second, SharedSimulationState::new(parameters.clone())).is_some());
+        assert!(registry.replace(99, SharedSimulationState::new(parameters)).is_none());
+        assert!(registry.remove(first).is_some());
+        assert!(registry.remove(first).is_none());
+        assert_eq!(registry.default_handle(), INVALID_SIMULATION_HANDLE);
+        assert_eq!(registry.handles(), vec![second]);
+    }
+
+    #[test]
+    fn test_get_or_insert_default_creates_once() {
+        let registry = Arc::new(SimulationRegistry::new());
+        let created = Arc::new(AtomicU64::new(0));
+        let workers: Vec<_> = (0..8)
+            .map(|_| {
+                let (registry, created) = (registry.clone(), created.clone());
+                std::thread::spawn(move || {
+                    registry.get_or_insert_default(|| {
+                        created.fetch_add(1, Ordering::Relaxed);
+                        Ok::<_, ()>(SharedSimulationState::new(SimulationParameters::new(1.0, 0.5, 4, 4)))
+                    }).unwrap()
+                })
+            })
+            .collect();
+        let results: Vec<(SimulationHandle, bool)> = workers.into_iter().map(|worker| worker.join().unwrap()).collect();
+
+        // Uma única instância criada; todas as threads recebem o mesmo handle padrão
+        assert_eq!(created.load(Ordering::Relaxed), 1);
+        assert_eq!(results.iter().filter(|(_, inserted)| *inserted).count(), 1);
+        assert!(results.iter().all(|(handle, _)| *handle == registry.default_handle()));
+        assert_eq!(registry.handles().len(), 1);
+
+        // Falha na criação não registra nada; após a remoção, uma nova padrão é criada
+        assert!(registry.remove(registry.default_handle()).is_some());
+        assert_eq!(registry.get_or_insert_default(|| Err("inválido")), Err("inválido"));
+        assert!(registry.handles().is_empty());
+        let (handle, inserted) = registry
+            .get_or_insert_default(|| Ok::<_, ()>(SharedSimulationState::new(SimulationParameters::new(1.0, 0.5, 4, 4))))
+            .unwrap();
+        assert!(inserted);
+        assert_eq!(registry.default_handle(), handle);
+    }
+
+    #[test]
+    fn test_registry_concurrent_access() {
+        let registry = Arc::new(SimulationRegistry::new());
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let registry = registry.clone();
+                std::thread::spawn(move || {
+                    let mut handles = Vec::new();
+                    for _ in 0..25 {
+                        let handle = registry.insert(SharedSimulationState::new(SimulationParameters::new(1.0, 0.5, 4, 4)));
+                        assert!(registry.get(handle).is_some());
+                        handles.push(handle);
+                    }
+                    for handle in handles.iter().step_by(2) {
+                        assert!(registry.remove(*handle).is_some());
+                    }
+                    handles
+                })
+            })
+            .collect();
+        let mut created: Vec<SimulationHandle> = workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect();
+
+        // Handles únicos entre threads; apenas os não removidos continuam registrados
+        created.sort();
+        created.dedup();
+        assert_eq!(created.len(), 100);
+        assert_eq!(registry.handles().len(), 48);
+    }
+}
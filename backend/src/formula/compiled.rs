@@ -0,0 +1,304 @@
+// Compilação de fórmulas algébricas para bytecode de pilha
+//
+// Fórmulas usadas em laços por célula (propriedades, fontes) são avaliadas milhões de vezes
+// por execução. O subconjunto algébrico da linguagem (números, parâmetros, `let`, `return`,
+// operadores aritméticos, parênteses, funções matemáticas e constantes físicas) é traduzido
+// uma única vez para uma sequência de instruções sobre uma pilha de `f64`, sem escopo,
+// valores dinâmicos nem buscas por nome durante a avaliação. Fórmulas fora desse subconjunto
+// (condicionais, laços, strings) continuam sendo avaliadas pelo motor Rhai. Todas as
+// operações do bytecode são em ponto flutuante, inclusive entre literais inteiros.
+
+use std::collections::HashMap;
+
+use super::engine::{Formula, ParameterType, ParameterValue};
+use super::expression::{self, BinaryOp, Expr};
+
+/// Instrução do bytecode de uma fórmula compilada
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    /// Empilha uma constante
+    Constant(f64),
+    /// Empilha o valor de uma variável
+    Load(usize),
+    /// Desempilha o topo para uma variável
+    Store(usize),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Neg,
+    /// Função matemática de um argumento
+    Unary(fn(f64) -> f64),
+    /// Função matemática de dois argumentos
+    Binary(fn(f64, f64) -> f64),
+}
+
+/// Funções matemáticas de um argumento registradas no motor
+fn unary_function(name: &str) -> Option<fn(f64) -> f64> {
+    let function: fn(f64) -> f64 = match name {
+        "sin" => f64::sin,
+        "cos" => f64::cos,
+        "tan" => f64::tan,
+        "asin" => f64::asin,
+        "acos" => f64::acos,
+        "atan" => f64::atan,
+        "sinh" => f64::sinh,
+        "cosh" => f64::cosh,
+        "tanh" => f64::tanh,
+        "exp" => f64::exp,
+        "log" => f64::ln,
+        "log10" => f64::log10,
+        "sqrt" => f64::sqrt,
+        "abs" => f64::abs,
+        "floor" => f64::floor,
+        "ceil" => f64::ceil,
+        "round" => f64::round,
+        _ => return None,
+    };
+    Some(function)
+}
+
+/// Funções matemáticas de dois argumentos registradas no motor
+fn binary_function(name: &str) -> Option<fn(f64, f64) -> f64> {
+    let function: fn(f64, f64) -> f64 = match name {
+        "pow" => f64::powf,
+        "atan2" => f64::atan2,
+        _ => return None,
+    };
+    Some(function)
+}
+
+/// Tradutor da árvore de expressões para bytecode
+struct Emitter {
+    code: Vec<Instruction>,
+    variables: HashMap<String, usize>,
+}
+
+impl Emitter {
+    fn emit(&mut self, expr: &Expr) -> Result<(), String> {
+        match expr {
+            Expr::Number(value) => self.code.push(Instruction::Constant(*value)),
+            Expr::Var(name) => {
+                if let Some(&slot) = self.variables.get(name) {
+                    self.code.push(Instruction::Load(slot));
+                } else if let Some(value) = expression::constant(name) {
+                    self.code.push(Instruction::Constant(value));
+                } else {
+                    return Err(format!("Variável não declarada: {}", name));
+                }
+            }
+            Expr::Neg(a) => {
+                self.emit(a)?;
+                self.code.push(Instruction::Neg);
+            }
+            Expr::Binary(op, a, b) => {
+                self.emit(a)?;
+                self.emit(b)?;
+                self.code.push(match op {
+                    BinaryOp::Add => Instruction::Add,
+                    BinaryOp::Sub => Instruction::Sub,
+                    BinaryOp::Mul => Instruction::Mul,
+                    BinaryOp::Div => Instruction::Div,
+                    BinaryOp::Rem => Instruction::Rem,
+                    BinaryOp::Pow => Instruction::Pow,
+                });
+            }
+            Expr::Call(name, arguments) => {
+                for argument in arguments {
+                    self.emit(argument)?;
+                }
+                let instruction = match arguments.len() {
+                    1 => unary_function(name).map(Instruction::Unary),
+                    2 => binary_function(name).map(Instruction::Binary),
+                    _ => None,
+                };
+                self.code.push(instruction.ok_or_else(|| format!("Função não suportada: {}", name))?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa uma fórmula compilada para bytecode
+///
+/// Os argumentos são passados por posição, na ordem de `parameter_names`; `defaults`
+/// fornece um vetor inicial com os valores padrão, a ser ajustado a cada avaliação.
+#[derive(Debug, Clone)]
+pub struct CompiledFormula {
+    /// Nomes dos parâmetros, na ordem dos argumentos
+    parameter_names: Vec<String>,
+    /// Valores padrão dos parâmetros
+    defaults: Vec<f64>,
+    /// Instruções do programa
+    code: Vec<Instruction>,
+    /// Número de variáveis (parâmetros e `let`)
+    slot_count: usize,
+    /// Profundidade máxima da pilha de avaliação
+    max_stack: usize,
+}
+
+impl CompiledFormula {
+    /// Compila uma fórmula do subconjunto algébrico
+    ///
+    /// Todos os parâmetros devem ser numéricos; retorna erro para construções não
+    /// suportadas, caso em que a fórmula deve ser avaliada pelo motor Rhai.
+    pub fn compile(formula: &Formula) -> Result<Self, String> {
+        let mut variables = HashMap::new();
+        let mut defaults = Vec::with_capacity(formula.parameters.len());
+        for (slot, param) in formula.parameters.iter().enumerate() {
+            let default = match (&param.param_type, &param.default_value) {
+                (ParameterType::Float | ParameterType::Integer, ParameterValue::Float(value)) => *value,
+                (ParameterType::Float | ParameterType::Integer, ParameterValue::Integer(value)) => *value as f64,
+                _ => return Err(format!("Parâmetro não numérico não suportado na compilação: {}", param.name)),
+            };
+            variables.insert(param.name.clone(), slot);
+            defaults.push(default);
+        }
+
+        let program = expression::parse_program(&formula.source)
+            .map_err(|e| format!("Fórmula '{}': {}", formula.name, e))?;
+        let mut emitter = Emitter { code: Vec::new(), variables };
+        let mut slot_count = formula.parameters.len();
+        for (name, value) in &program.bindings {
+            emitter.emit(value)?;
+            emitter.code.push(Instruction::Store(slot_count));
+            emitter.variables.insert(name.clone(), slot_count);
+            slot_count += 1;
+        }
+        emitter.emit(&program.result)
+            .map_err(|e| format!("Fórmula '{}': {}", formula.name, e))?;
+
+        let mut depth: usize = 0;
+        let mut max_stack = 0;
+        for instruction in &emitter.code {
+            match instruction {
+                Instruction::Constant(_) | Instruction::Load(_) => depth += 1,
+                Instruction::Store(_) | Instruction::Add | Instruction::Sub | Instruction::Mul
+                | Instruction::Div | Instruction::Rem | Instruction::Pow | Instruction::Binary(_) => depth -= 1,
+                Instruction::Neg | Instruction::Unary(_) => {}
+            }
+            max_stack = max_stack.max(depth);
+        }
+
+        Ok(Self {
+            parameter_names: formula.parameters.iter().map(|p| p.name.clone()).collect(),
+            defaults,
+            code: emitter.code,
+            slot_count,
+            max_stack,
+        })
+    }
+
+    /// Nomes dos parâmetros, na ordem dos argumentos
+    pub fn parameter_names(&self) -> &[String] {
+        &self.parameter_names
+    }
+
+    /// Posição de um parâmetro no vetor de argumentos
+    pub fn parameter_index(&self, name: &str) -> Option<usize> {
+        self.parameter_names.iter().position(|n| n == name)
+    }
+
+    /// Vetor de argumentos com os valores padrão
+    pub fn defaults(&self) -> Vec<f64> {
+        self.defaults.clone()
+    }
+
+    /// Vetor de argumentos com os valores padrão substituídos pelos valores fornecidos
+    pub fn arguments(&self, values: &HashMap<String, ParameterValue>) -> Result<Vec<f64>, String> {
+        let mut arguments = self.defaults();
+        for (name, value) in values {
+            if let Some(index) = self.parameter_index(name) {
+                arguments[index] = match value {
+                    ParameterValue::Float(v) => *v,
+                    ParameterValue::Integer(v) => *v as f64,
+                    other => return Err(format!("Valor não numérico para o parâmetro {}: {}", name, other)),
+                };
+            }
+        }
+        Ok(arguments)
+    }
+
+    /// Avalia a fórmula com os argumentos na ordem de `parameter_names`
+    pub fn evaluate(&self, arguments: &[f64]) -> Result<f64, String> {
+        if arguments.len() != self.parameter_names.len() {
+            return Err(format!(
+                "Número de argumentos incorreto: esperado {}, recebido {}",
+                self.parameter_names.len(),
+                arguments.len()
+            ));
+        }
+        let mut slots = vec![0.0; self.slot_count];
+        slots[..arguments.len()].copy_from_slice(arguments);
+        let mut stack: Vec<f64> = Vec::with_capacity(self.max_stack);
+        for instruction in &self.code {
+            match *instruction {
+                Instruction::Constant(value) => stack.push(value),
+                Instruction::Load(slot) => stack.push(slots[slot]),
+                Instruction::Store(slot) => slots[slot] = stack.pop().unwrap_or(0.0),
+                Instruction::Neg => {
+                    let top = stack.last_mut().expect("pilha vazia");
+                    *top = -*top;
+                }
+                Instruction::Unary(function) => {
+                    let top = stack.last_mut().expect("pilha vazia");
+                    *top = function(*top);
+                }
+                binary => {
+                    let b = stack.pop().expect("pilha vazia");
+                    let a = stack.last_mut().expect("pilha vazia");
+                    *a = match binary {
+                        Instruction::Add => *a + b,
+                        Instruction::Sub => *a - b,
+                        Instruction::Mul => *a * b,
+                        Instruction::Div => *a / b,
+                        Instruction::Rem => *a % b,
+                        Instruction::Pow => a.powf(b),
+                        Instruction::Binary(function) => function(*a, b),
+                        _ => unreachable!(),
+                    };
+                }
+            }
+        }
+        stack.pop().ok_or_else(|| "Fórmula sem valor de retorno".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formula::engine::FormulaEngine;
+
+    #[test]
+    fn test_compiled_formula_matches_interpreter() {
+        let engine = FormulaEngine::new();
+        let formula = engine.get_formula("thermal_conductivity").unwrap();
+        let compiled = CompiledFormula::compile(formula).unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("temperature".to_string(), ParameterValue::Float(100.0));
+        params.insert("k2".to_string(), ParameterValue::Float(0.3));
+        let interpreted = match engine.evaluate_formula("thermal_conductivity", &params).unwrap().value {
+            ParameterValue::Float(value) => value,
+            other => panic!("Tipo de resultado inesperado: {}", other),
+        };
+        let arguments = compiled.arguments(&params).unwrap();
+        assert!((compiled.evaluate(&arguments).unwrap() - interpreted).abs() < 1e-12);
+
+        // Precedência, potência, funções e constantes
+        let mut formula = formula.clone();
+        formula.source = "let x = -k0 ** 2.0 / 4.0; return pow(x, 2.0) * 0.0 + sqrt(abs(x)) + 2.0 * PI % 1.0;".to_string();
+        let compiled = CompiledFormula::compile(&formula).unwrap();
+        let value = compiled.evaluate(&compiled.defaults()).unwrap();
+        let expected = (45.0_f64.powf(2.0) / 4.0).sqrt() + (2.0 * std::f64::consts::PI) % 1.0;
+        assert!((value - expected).abs() < 1e-9);
+
+        // Condicionais ficam para o interpretador
+        formula.source = "if temperature > 0.0 { k0 } else { k1 }".to_string();
+        assert!(CompiledFormula::compile(&formula).is_err());
+        formula.source = "k0 + undefined".to_string();
+        assert!(CompiledFormula::compile(&formula).is_err());
+    }
+}
@@ -0,0 +1,541 @@
+// Árvore de expressões do subconjunto algébrico das fórmulas
+//
+// O subconjunto compreende números, parâmetros, declarações `let`, `return`, operadores
+// aritméticos (+, -, *, /, %, **), parênteses, funções matemáticas registradas no motor e
+// constantes físicas. A árvore é usada pela compilação para bytecode e pela derivação
+// simbólica; fórmulas fora do subconjunto (condicionais, laços, strings) são rejeitadas.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Funções matemáticas de um argumento registradas no motor
+pub const UNARY_FUNCTIONS: [&str; 17] = [
+    "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh",
+    "exp", "log", "log10", "sqrt", "abs", "floor", "ceil", "round",
+];
+
+/// Funções matemáticas de dois argumentos registradas no motor
+pub const BINARY_FUNCTIONS: [&str; 2] = ["pow", "atan2"];
+
+/// Constantes físicas disponíveis nas fórmulas
+pub fn constant(name: &str) -> Option<f64> {
+    match name {
+        "PI" => Some(std::f64::consts::PI),
+        "E" => Some(std::f64::consts::E),
+        "STEFAN_BOLTZMANN" => Some(5.67e-8),
+        "GRAVITY" => Some(9.81),
+        _ => None,
+    }
+}
+
+/// Operador binário
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl BinaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::Pow => "**",
+        }
+    }
+}
+
+/// Nó da árvore de expressões
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Constante numérica
+    Number(f64),
+    /// Parâmetro, variável `let` ou constante física
+    Var(String),
+    /// Negação
+    Neg(Box<Expr>),
+    /// Operação binária
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /// Chamada de função matemática
+    Call(String, Vec<Expr>),
+}
+
+/// Programa: declarações `let` seguidas da expressão do resultado
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    /// Declarações `let`, em ordem
+    pub bindings: Vec<(String, Expr)>,
+    /// Expressão do resultado
+    pub result: Expr,
+}
+
+/// Token da linguagem das fórmulas
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+/// Divide o código fonte em tokens, ignorando espaços e comentários de linha
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_') {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                i += 1;
+                if i < chars.len() && (chars[i] == '+' || chars[i] == '-') {
+                    i += 1;
+                }
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().filter(|&&d| d != '_').collect();
+            let value = text.parse::<f64>().map_err(|_| format!("Número inválido: {}", text))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            let symbol = match (c, chars.get(i + 1)) {
+                ('*', Some('*')) => "**",
+                ('+', _) => "+",
+                ('-', _) => "-",
+                ('*', _) => "*",
+                ('/', _) => "/",
+                ('%', _) => "%",
+                ('(', _) => "(",
+                (')', _) => ")",
+                (',', _) => ",",
+                (';', _) => ";",
+                ('=', Some('=')) => return Err("Comparações não são suportadas".to_string()),
+                ('=', _) => "=",
+                _ => return Err(format!("Símbolo não suportado: '{}'", c)),
+            };
+            i += symbol.len();
+            tokens.push(Token::Symbol(symbol));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Analisador descendente recursivo
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn accept(&mut self, symbol: &'static str) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> Result<(), String> {
+        if self.accept(symbol) {
+            Ok(())
+        } else {
+            Err(format!("'{}' esperado", symbol))
+        }
+    }
+
+    /// Programa: declarações `let`, terminando em `return expr` ou em uma expressão
+    fn program(&mut self) -> Result<Program, String> {
+        let mut bindings = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err("Fórmula sem valor de retorno".to_string()),
+                Some(Token::Ident(word)) if word == "let" => {
+                    self.position += 1;
+                    let name = match self.next() {
+                        Some(Token::Ident(name)) => name,
+                        _ => return Err("Nome de variável esperado após 'let'".to_string()),
+                    };
+                    self.expect("=")?;
+                    let value = self.expression()?;
+                    self.expect(";")?;
+                    bindings.push((name, value));
+                }
+                Some(Token::Ident(word)) if word == "return" => {
+                    self.position += 1;
+                    return self.finish(bindings);
+                }
+                Some(_) => return self.finish(bindings),
+            }
+        }
+    }
+
+    /// Expressão final; nada pode segui-la, exceto ';'
+    fn finish(&mut self, bindings: Vec<(String, Expr)>) -> Result<Program, String> {
+        let result = self.expression()?;
+        self.accept(";");
+        match self.peek() {
+            None => Ok(Program { bindings, result }),
+            Some(token) => Err(format!("Construção não suportada: {:?}", token)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        loop {
+            let op = if self.accept("+") {
+                BinaryOp::Add
+            } else if self.accept("-") {
+                BinaryOp::Sub
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.accept("*") {
+                BinaryOp::Mul
+            } else if self.accept("/") {
+                BinaryOp::Div
+            } else if self.accept("%") {
+                BinaryOp::Rem
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.accept("-") {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else if self.accept("+") {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    /// Potência `a ** b`, associativa à direita
+    fn power(&mut self) -> Result<Expr, String> {
+        let base = self.primary()?;
+        if self.accept("**") {
+            Ok(Expr::Binary(BinaryOp::Pow, Box::new(base), Box::new(self.unary()?)))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Expr::Number(value)),
+            Some(Token::Symbol("(")) => {
+                let inner = self.expression()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) => {
+                if !self.accept("(") {
+                    return Ok(Expr::Var(name));
+                }
+                let arity = if UNARY_FUNCTIONS.contains(&name.as_str()) {
+                    1
+                } else if BINARY_FUNCTIONS.contains(&name.as_str()) {
+                    2
+                } else {
+                    return Err(format!("Função não suportada: {}", name));
+                };
+                let mut arguments = vec![self.expression()?];
+                while arguments.len() < arity {
+                    self.expect(",")?;
+                    arguments.push(self.expression()?);
+                }
+                self.expect(")")?;
+                Ok(Expr::Call(name, arguments))
+            }
+            other => Err(format!("Expressão esperada, encontrado {:?}", other)),
+        }
+    }
+}
+
+/// Analisa o código fonte de uma fórmula do subconjunto algébrico
+pub fn parse_program(source: &str) -> Result<Program, String> {
+    Parser { tokens: tokenize(source)?, position: 0 }.program()
+}
+
+// Construtores com simplificação das identidades triviais
+
+fn number(value: f64) -> Expr {
+    Expr::Number(value)
+}
+
+fn is_number(expr: &Expr, value: f64) -> bool {
+    matches!(expr, Expr::Number(v) if *v == value)
+}
+
+fn neg(a: Expr) -> Expr {
+    match a {
+        Expr::Number(v) => number(-v),
+        Expr::Neg(inner) => *inner,
+        a => Expr::Neg(Box::new(a)),
+    }
+}
+
+fn binary(op: BinaryOp, a: Expr, b: Expr) -> Expr {
+    if let (Expr::Number(x), Expr::Number(y)) = (&a, &b) {
+        let value = match op {
+            BinaryOp::Add => x + y,
+            BinaryOp::Sub => x - y,
+            BinaryOp::Mul => x * y,
+            BinaryOp::Div => x / y,
+            BinaryOp::Rem => x % y,
+            BinaryOp::Pow => x.powf(*y),
+        };
+        if value.is_finite() {
+            return number(value);
+        }
+    }
+    match op {
+        BinaryOp::Add if is_number(&a, 0.0) => b,
+        BinaryOp::Add | BinaryOp::Sub if is_number(&b, 0.0) => a,
+        BinaryOp::Sub if is_number(&a, 0.0) => neg(b),
+        BinaryOp::Mul if is_number(&a, 0.0) || is_number(&b, 0.0) => number(0.0),
+        BinaryOp::Mul if is_number(&a, 1.0) => b,
+        BinaryOp::Mul | BinaryOp::Div if is_number(&b, 1.0) => a,
+        BinaryOp::Div if is_number(&a, 0.0) => number(0.0),
+        BinaryOp::Pow if is_number(&b, 1.0) => a,
+        BinaryOp::Pow if is_number(&b, 0.0) => number(1.0),
+        _ => Expr::Binary(op, Box::new(a), Box::new(b)),
+    }
+}
+
+fn add(a: Expr, b: Expr) -> Expr {
+    binary(BinaryOp::Add, a, b)
+}
+
+fn sub(a: Expr, b: Expr) -> Expr {
+    binary(BinaryOp::Sub, a, b)
+}
+
+fn mul(a: Expr, b: Expr) -> Expr {
+    binary(BinaryOp::Mul, a, b)
+}
+
+fn div(a: Expr, b: Expr) -> Expr {
+    binary(BinaryOp::Div, a, b)
+}
+
+fn pow(a: Expr, b: Expr) -> Expr {
+    binary(BinaryOp::Pow, a, b)
+}
+
+fn call(name: &str, arguments: Vec<Expr>) -> Expr {
+    Expr::Call(name.to_string(), arguments)
+}
+
+impl Expr {
+    /// Derivada da expressão em relação a `variable`
+    ///
+    /// `derivatives` associa variáveis `let` já derivadas ao nome da variável que guarda
+    /// sua derivada; demais variáveis (outros parâmetros, constantes) têm derivada nula.
+    pub fn derivative(&self, variable: &str, derivatives: &HashMap<String, String>) -> Result<Expr, String> {
+        let d = |e: &Expr| e.derivative(variable, derivatives);
+        let result = match self {
+            Expr::Number(_) => number(0.0),
+            Expr::Var(name) => match derivatives.get(name) {
+                Some(derivative) => Expr::Var(derivative.clone()),
+                None if name == variable => number(1.0),
+                None => number(0.0),
+            },
+            Expr::Neg(a) => neg(d(a)?),
+            Expr::Binary(op, a, b) => {
+                let (a, b) = (a.as_ref().clone(), b.as_ref().clone());
+                let (da, db) = (d(&a)?, d(&b)?);
+                match op {
+                    BinaryOp::Add => add(da, db),
+                    BinaryOp::Sub => sub(da, db),
+                    BinaryOp::Mul => add(mul(da, b), mul(a, db)),
+                    BinaryOp::Div => div(sub(mul(da, b.clone()), mul(a, db)), pow(b, number(2.0))),
+                    BinaryOp::Rem => {
+                        if !is_number(&db, 0.0) {
+                            return Err("Derivada de '%' com divisor variável não suportada".to_string());
+                        }
+                        da
+                    }
+                    BinaryOp::Pow => power_derivative(a, b, da, db),
+                }
+            }
+            Expr::Call(name, arguments) => {
+                let a = arguments[0].clone();
+                let da = d(&a)?;
+                let inner = match name.as_str() {
+                    "sin" => call("cos", vec![a]),
+                    "cos" => neg(call("sin", vec![a])),
+                    "tan" => div(number(1.0), pow(call("cos", vec![a]), number(2.0))),
+                    "asin" => div(number(1.0), call("sqrt", vec![sub(number(1.0), pow(a, number(2.0)))])),
+                    "acos" => neg(div(number(1.0), call("sqrt", vec![sub(number(1.0), pow(a, number(2.0)))]))),
+                    "atan" => div(number(1.0), add(number(1.0), pow(a, number(2.0)))),
+                    "sinh" => call("cosh", vec![a]),
+                    "cosh" => call("sinh", vec![a]),
+                    "tanh" => div(number(1.0), pow(call("cosh", vec![a]), number(2.0))),
+                    "exp" => call("exp", vec![a]),
+                    "log" => div(number(1.0), a),
+                    "log10" => div(number(1.0), mul(a, number(std::f64::consts::LN_10))),
+                    "sqrt" => div(number(1.0), mul(number(2.0), call("sqrt", vec![a]))),
+                    "abs" => div(a.clone(), call("abs", vec![a])),
+                    "floor" | "ceil" | "round" => number(0.0),
+                    "pow" => {
+                        let b = arguments[1].clone();
+                        let db = d(&b)?;
+                        return Ok(power_derivative(a, b, da, db));
+                    }
+                    "atan2" => {
+                        // d atan2(y, x) = (x·dy - y·dx) / (x² + y²)
+                        let x = arguments[1].clone();
+                        let dx = d(&x)?;
+                        let denominator = add(pow(x.clone(), number(2.0)), pow(a.clone(), number(2.0)));
+                        return Ok(div(sub(mul(x, da), mul(a, dx)), denominator));
+                    }
+                    other => return Err(format!("Derivada da função '{}' não suportada", other)),
+                };
+                mul(inner, da)
+            }
+        };
+        Ok(result)
+    }
+}
+
+/// Derivada de a^b: b·a^(b-1)·a' com expoente constante; a^b·(b'·ln a + b·a'/a) no caso geral
+fn power_derivative(a: Expr, b: Expr, da: Expr, db: Expr) -> Expr {
+    if is_number(&db, 0.0) {
+        let exponent = sub(b.clone(), number(1.0));
+        mul(mul(b, pow(a, exponent)), da)
+    } else {
+        let general = add(mul(db, call("log", vec![a.clone()])), div(mul(b.clone(), da), a.clone()));
+        mul(pow(a, b), general)
+    }
+}
+
+impl Program {
+    /// Deriva o programa em relação a `variable`
+    ///
+    /// Cada declaração `let x` ganha uma declaração da sua derivada, nomeada
+    /// `d_x_d_<variável>`, usada pela regra da cadeia nas expressões seguintes.
+    pub fn derivative(&self, variable: &str) -> Result<Program, String> {
+        let mut derivatives = HashMap::new();
+        let mut bindings = Vec::with_capacity(2 * self.bindings.len());
+        for (name, value) in &self.bindings {
+            let derivative = value.derivative(variable, &derivatives)?;
+            // Redeclarações sombreiam a derivada anterior
+            derivatives.remove(name);
+            bindings.push((name.clone(), value.clone()));
+            if !is_number(&derivative, 0.0) {
+                let derivative_name = format!("d_{}_d_{}", name, variable);
+                bindings.push((derivative_name.clone(), derivative));
+                derivatives.insert(name.clone(), derivative_name);
+            }
+        }
+        let result = self.result.derivative(variable, &derivatives)?;
+        Ok(Program { bindings, result })
+    }
+
+    /// Código fonte Rhai equivalente
+    pub fn to_source(&self) -> Result<String, String> {
+        let mut source = String::new();
+        for (name, value) in &self.bindings {
+            source.push_str(&format!("let {} = {};\n", name, value.to_source()?));
+        }
+        source.push_str(&format!("return {};", self.result.to_source()?));
+        Ok(source)
+    }
+}
+
+impl Expr {
+    /// Código fonte Rhai da expressão, com parênteses explícitos e números em ponto flutuante
+    pub fn to_source(&self) -> Result<String, String> {
+        let text = match self {
+            Expr::Number(value) => {
+                if !value.is_finite() {
+                    return Err(format!("Constante não finita na expressão: {}", value));
+                }
+                let mut literal = format!("{:?}", value.abs());
+                if !literal.contains('.') {
+                    literal = match literal.find('e') {
+                        Some(position) => format!("{}.0{}", &literal[..position], &literal[position..]),
+                        None => format!("{}.0", literal),
+                    };
+                }
+                if value.is_sign_negative() && *value != 0.0 { format!("(-{})", literal) } else { literal }
+            }
+            Expr::Var(name) => name.clone(),
+            Expr::Neg(a) => format!("(-{})", a.to_source()?),
+            Expr::Binary(op, a, b) => format!("({} {} {})", a.to_source()?, op.symbol(), b.to_source()?),
+            Expr::Call(name, arguments) => {
+                let arguments = arguments.iter().map(Expr::to_source).collect::<Result<Vec<_>, _>>()?;
+                format!("{}({})", name, arguments.join(", "))
+            }
+        };
+        Ok(text)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_source() {
+            Ok(source) => write!(f, "{}", source),
+            Err(_) => write!(f, "<expressão não finita>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_print_round_trip() {
+        let program = parse_program("let x = -a ** 2.0 / 4.0; // comentário\nreturn pow(x, 2.0) + sin(b) * 1e-3;").unwrap();
+        assert_eq!(program.bindings.len(), 1);
+        let reparsed = parse_program(&program.to_source().unwrap()).unwrap();
+        assert_eq!(reparsed, program);
+        assert!(parse_program("if a > 0.0 { a } else { b }").is_err());
+        assert!(parse_program("foo(a)").is_err());
+    }
+}
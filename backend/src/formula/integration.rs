@@ -1,9 +1,82 @@
 // Implementação da integração do motor de fórmulas com o solucionador
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use ndarray::Array2;
 use serde::{Deserialize, Serialize};
 
-use super::engine::{FormulaEngine, Formula, ParameterValue, FormulaCategory};
+use super::engine::{FormulaEngine, Formula, FormulaParameter, ParameterType, ParameterValue, FormulaCategory};
+use super::plasma::{self, PlasmaProperty};
+use super::script::{ScriptLimits, ScriptSandbox};
+use super::table::LookupTable;
+use super::units;
+use crate::simulation::mesh::CylindricalMesh;
+use crate::simulation::physics::PlasmaTorch;
+
+/// Variáveis fornecidas pelo solucionador a uma fonte volumétrica S(r, z, t, T), com suas unidades
+pub const VOLUMETRIC_SOURCE_VARIABLES: [(&str, &str); 4] = [
+    ("r", "m"),
+    ("z", "m"),
+    ("t", "s"),
+    ("temperature", "°C"),
+];
+
+/// Versão do formato dos pacotes de fórmulas
+pub const FORMULA_BUNDLE_VERSION: u32 = 1;
+
+/// Estrutura que representa um pacote de fórmulas e suas associações, para troca entre máquinas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormulaBundle {
+    /// Versão do formato
+    pub format_version: u32,
+    /// Fórmulas, por ID
+    pub formulas: BTreeMap<String, Formula>,
+    /// Associações de tipos de função às fórmulas do pacote
+    #[serde(default)]
+    pub function_mappings: BTreeMap<String, String>,
+}
+
+/// Enumeração que representa o tratamento de IDs já existentes na importação de fórmulas
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FormulaConflictPolicy {
+    /// Mantém a fórmula existente e ignora a do pacote
+    Skip,
+    /// Substitui a fórmula existente
+    Overwrite,
+    /// Importa a fórmula com um novo ID (`<id>_2`, `<id>_3`, ...)
+    Rename,
+    /// Rejeita a importação inteira
+    Fail,
+}
+
+impl FormulaConflictPolicy {
+    /// Cria a política a partir de uma string
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s {
+            "skip" => Some(FormulaConflictPolicy::Skip),
+            "overwrite" => Some(FormulaConflictPolicy::Overwrite),
+            "rename" => Some(FormulaConflictPolicy::Rename),
+            "fail" => Some(FormulaConflictPolicy::Fail),
+            _ => None,
+        }
+    }
+}
+
+/// Estrutura que representa o resultado da importação de um pacote de fórmulas
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FormulaImportSummary {
+    /// IDs importados (IDs finais, após renomeação)
+    pub imported: Vec<String>,
+    /// IDs ignorados por já existirem
+    pub skipped: Vec<String>,
+    /// Fórmulas renomeadas (ID do pacote → ID registrado)
+    pub renamed: BTreeMap<String, String>,
+    /// Tipos de função associados
+    pub mappings: Vec<String>,
+}
 
 /// Estrutura que representa um gerenciador de fórmulas para o solucionador
 pub struct FormulaManager {
@@ -11,6 +84,10 @@ pub struct FormulaManager {
     engine: FormulaEngine,
     /// Mapeamento de funções para fórmulas
     function_mappings: HashMap<String, String>,
+    /// Ambiente restrito de scripts do usuário, quando habilitado
+    scripting: Option<ScriptSandbox>,
+    /// Tabelas de interpolação, por ID
+    tables: HashMap<String, LookupTable>,
 }
 
 /// Enumeração que representa os tipos de funções que podem ser substituídas por fórmulas
@@ -30,22 +107,91 @@ pub enum FunctionType {
     Emissivity,
     /// Condição de contorno
     BoundaryCondition,
+    /// Fonte volumétrica adicional S(r, z, t, T) da equação de calor
+    VolumetricSource,
+    /// Função do usuário escrita como script Rhai (condicionais, laços), executada com limites
+    UserScript,
+    /// Tabela de interpolação 1D/2D de dados medidos
+    LookupTable,
+    /// Lei de controle da potência das tochas a partir das temperaturas das sondas
+    PowerControl,
 }
 
-impl FunctionType {
-    /// Converte o tipo de função para uma string
-    pub fn to_string(&self) -> String {
-        match self {
-            FunctionType::ThermalConductivity => "thermal_conductivity".to_string(),
-            FunctionType::SpecificHeat => "specific_heat".to_string(),
-            FunctionType::Density => "density".to_string(),
-            FunctionType::HeatSource => "heat_source".to_string(),
-            FunctionType::ConvectionCoefficient => "convection_coefficient".to_string(),
-            FunctionType::Emissivity => "emissivity".to_string(),
-            FunctionType::BoundaryCondition => "boundary_condition".to_string(),
+/// Estrutura que representa uma fonte volumétrica definida por uma fórmula do usuário
+///
+/// A fórmula é avaliada pelo solucionador em cada célula a cada passo, com as variáveis
+/// `r`, `z`, `t` e `temperature` (T^n) da célula; os demais parâmetros da fórmula recebem
+/// os valores de `parameters` ou seus valores padrão. O resultado é a densidade de
+/// potência em W/m³, somada aos demais termos fonte.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormulaSourceTerm {
+    /// Fórmula da fonte (categoria `HeatSource`)
+    pub formula: Formula,
+    /// Valores dos parâmetros constantes da fórmula
+    #[serde(default)]
+    pub parameters: HashMap<String, f64>,
+}
+
+impl FormulaSourceTerm {
+    /// Cria uma fonte a partir de uma fórmula, com os parâmetros nos valores padrão
+    pub fn new(formula: Formula) -> Self {
+        Self {
+            formula,
+            parameters: HashMap::new(),
         }
     }
-    
+
+    /// Valida a categoria da fórmula e os valores dos parâmetros
+    pub fn validate(&self) -> Result<(), String> {
+        if self.formula.category != FormulaCategory::HeatSource {
+            return Err(format!(
+                "Fonte volumétrica requer fórmula da categoria {:?}, encontrado {:?}",
+                FormulaCategory::HeatSource,
+                self.formula.category
+            ));
+        }
+        for (name, value) in &self.parameters {
+            if VOLUMETRIC_SOURCE_VARIABLES.iter().any(|(variable, _)| variable == name) {
+                return Err(format!("Parâmetro '{}' da fonte volumétrica é fornecido pelo solucionador", name));
+            }
+            if !self.formula.parameters.iter().any(|param| &param.name == name) {
+                return Err(format!("Parâmetro '{}' não declarado pela fórmula da fonte volumétrica", name));
+            }
+            if !value.is_finite() {
+                return Err(format!("Parâmetro '{}' da fonte volumétrica deve ser finito", name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Valores dos parâmetros constantes no formato do motor de fórmulas
+    pub fn constants(&self) -> HashMap<String, ParameterValue> {
+        self.parameters.iter()
+            .map(|(name, &value)| (name.clone(), ParameterValue::Float(value)))
+            .collect()
+    }
+}
+
+/// Nome do tipo de função (o aceito por `FunctionType::from_string`)
+impl fmt::Display for FunctionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FunctionType::ThermalConductivity => "thermal_conductivity",
+            FunctionType::SpecificHeat => "specific_heat",
+            FunctionType::Density => "density",
+            FunctionType::HeatSource => "heat_source",
+            FunctionType::ConvectionCoefficient => "convection_coefficient",
+            FunctionType::Emissivity => "emissivity",
+            FunctionType::BoundaryCondition => "boundary_condition",
+            FunctionType::VolumetricSource => "volumetric_source",
+            FunctionType::UserScript => "user_script",
+            FunctionType::LookupTable => "lookup_table",
+            FunctionType::PowerControl => "power_control",
+        })
+    }
+}
+
+impl FunctionType {
     /// Cria um tipo de função a partir de uma string
     pub fn from_string(s: &str) -> Option<Self> {
         match s {
@@ -56,6 +202,10 @@ impl FunctionType {
             "convection_coefficient" => Some(FunctionType::ConvectionCoefficient),
             "emissivity" => Some(FunctionType::Emissivity),
             "boundary_condition" => Some(FunctionType::BoundaryCondition),
+            "volumetric_source" => Some(FunctionType::VolumetricSource),
+            "user_script" => Some(FunctionType::UserScript),
+            "lookup_table" => Some(FunctionType::LookupTable),
+            "power_control" => Some(FunctionType::PowerControl),
             _ => None,
         }
     }
@@ -67,19 +217,81 @@ impl FunctionType {
             FunctionType::SpecificHeat |
             FunctionType::Density |
             FunctionType::Emissivity => FormulaCategory::MaterialProperty,
-            FunctionType::HeatSource => FormulaCategory::HeatSource,
+            FunctionType::HeatSource |
+            FunctionType::VolumetricSource => FormulaCategory::HeatSource,
             FunctionType::ConvectionCoefficient |
             FunctionType::BoundaryCondition => FormulaCategory::BoundaryCondition,
+            FunctionType::UserScript |
+            FunctionType::LookupTable => FormulaCategory::Utility,
+            FunctionType::PowerControl => FormulaCategory::PhysicalModel,
+        }
+    }
+
+    /// Unidade esperada do resultado das fórmulas do tipo de função
+    ///
+    /// Retorna `None` quando o resultado pode ter mais de uma dimensão (condições de
+    /// contorno podem prescrever temperatura, fluxo ou coeficiente; scripts e tabelas são genéricos).
+    pub fn expected_unit(&self) -> Option<&'static str> {
+        match self {
+            FunctionType::ThermalConductivity => Some("W/(m·K)"),
+            FunctionType::SpecificHeat => Some("J/(kg·K)"),
+            FunctionType::Density => Some("kg/m³"),
+            FunctionType::HeatSource |
+            FunctionType::VolumetricSource => Some("W/m³"),
+            FunctionType::ConvectionCoefficient => Some("W/(m²·K)"),
+            FunctionType::Emissivity => Some(""),
+            FunctionType::PowerControl => Some("kW"),
+            FunctionType::BoundaryCondition |
+            FunctionType::UserScript |
+            FunctionType::LookupTable => None,
+        }
+    }
+
+    /// Verifica se a unidade do resultado de uma fórmula é compatível com o tipo de função
+    pub fn check_result_unit(&self, formula: &Formula) -> Result<(), String> {
+        match self.expected_unit() {
+            Some(expected) => units::check_compatible(&formula.result_unit, expected)
+                .map_err(|e| format!("Resultado da fórmula '{}' para {:?}: {}", formula.name, self, e)),
+            None => formula.result_dimension().map(|_| ()),
         }
     }
 }
 
+/// Acrescenta à fórmula, como parâmetros, as variáveis do solucionador que ela não declara
+fn add_solver_variables(formula: &mut Formula, variables: &[(&str, &str)], description: &str) {
+    for (variable, unit) in variables {
+        if !formula.parameters.iter().any(|param| param.name == *variable) {
+            formula.parameters.push(FormulaParameter {
+                name: variable.to_string(),
+                description: description.to_string(),
+                param_type: ParameterType::Float,
+                default_value: ParameterValue::Float(0.0),
+                unit: unit.to_string(),
+                min_value: None,
+                max_value: None,
+            });
+        }
+    }
+}
+
+impl Default for FormulaManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FormulaManager {
-    /// Cria uma nova instância do gerenciador de fórmulas
+    /// Cria uma nova instância do gerenciador de fórmulas, com a biblioteca de plasma registrada
     pub fn new() -> Self {
+        let mut engine = FormulaEngine::new();
+        for (id, formula) in plasma::plasma_formulas() {
+            engine.add_formula(id, formula).expect("Fórmula da biblioteca de plasma inválida");
+        }
         Self {
-            engine: FormulaEngine::new(),
+            engine,
             function_mappings: HashMap::new(),
+            scripting: None,
+            tables: HashMap::new(),
         }
     }
     
@@ -93,8 +305,80 @@ impl FormulaManager {
         &mut self.engine
     }
     
+    /// Habilita scripts do usuário, executados com os limites fornecidos
+    pub fn enable_scripting(&mut self, limits: ScriptLimits) -> Result<(), String> {
+        self.scripting = Some(ScriptSandbox::new(limits)?);
+        Ok(())
+    }
+
+    /// Obtém o ambiente de scripts, quando habilitado
+    pub fn get_scripting(&self) -> Option<&ScriptSandbox> {
+        self.scripting.as_ref()
+    }
+
+    /// Registra um script do usuário no ambiente restrito
+    ///
+    /// O script usa a mesma interface de parâmetros das fórmulas e deve ser da categoria
+    /// `Utility`. Requer `enable_scripting`.
+    pub fn register_script(&mut self, script_id: &str, formula: Formula) -> Result<(), String> {
+        let sandbox = self.scripting.as_mut()
+            .ok_or_else(|| "Scripts do usuário não estão habilitados".to_string())?;
+        if formula.category != FunctionType::UserScript.to_category() {
+            return Err(format!(
+                "Categoria do script incompatível: esperado {:?}, encontrado {:?}",
+                FunctionType::UserScript.to_category(),
+                formula.category
+            ));
+        }
+        formula.result_dimension()?;
+        sandbox.add_script(script_id, formula)
+    }
+
+    /// Registra (ou substitui) uma tabela de interpolação
+    pub fn add_table(&mut self, table_id: &str, table: LookupTable) -> Result<(), String> {
+        table.validate()?;
+        self.tables.insert(table_id.to_string(), table);
+        Ok(())
+    }
+
+    /// Registra (ou substitui) uma tabela de interpolação a partir de JSON
+    pub fn add_table_json(&mut self, table_id: &str, json: &str) -> Result<(), String> {
+        self.add_table(table_id, LookupTable::from_json(json)?)
+    }
+
+    /// Remove uma tabela de interpolação
+    pub fn remove_table(&mut self, table_id: &str) -> bool {
+        self.tables.remove(table_id).is_some()
+    }
+
+    /// Obtém uma tabela de interpolação
+    pub fn get_table(&self, table_id: &str) -> Option<&LookupTable> {
+        self.tables.get(table_id)
+    }
+
     /// Define uma fórmula para um tipo de função
+    ///
+    /// Para `FunctionType::UserScript`, o identificador é o de um script registrado por
+    /// `register_script`; para `FunctionType::LookupTable`, o de uma tabela registrada por
+    /// `add_table`.
     pub fn set_formula_for_function(&mut self, function_type: FunctionType, formula_id: &str) -> Result<(), String> {
+        if function_type == FunctionType::LookupTable {
+            if !self.tables.contains_key(formula_id) {
+                return Err(format!("Tabela não encontrada: {}", formula_id));
+            }
+            self.function_mappings.insert(function_type.to_string(), formula_id.to_string());
+            return Ok(());
+        }
+        if function_type == FunctionType::UserScript {
+            let sandbox = self.scripting.as_ref()
+                .ok_or_else(|| "Scripts do usuário não estão habilitados".to_string())?;
+            if sandbox.get_script(formula_id).is_none() {
+                return Err(format!("Script não encontrado: {}", formula_id));
+            }
+            self.function_mappings.insert(function_type.to_string(), formula_id.to_string());
+            return Ok(());
+        }
+
         // Verificar se a fórmula existe
         if self.engine.get_formula(formula_id).is_none() {
             return Err(format!("Fórmula não encontrada: {}", formula_id));
@@ -109,6 +393,9 @@ impl FormulaManager {
                 formula.category
             ));
         }
+
+        // Verificar a dimensão do resultado
+        function_type.check_result_unit(formula)?;
         
         // Definir o mapeamento
         self.function_mappings.insert(function_type.to_string(), formula_id.to_string());
@@ -132,12 +419,164 @@ impl FormulaManager {
         let formula_id = self.get_formula_for_function(function_type)
             .ok_or_else(|| format!("Nenhuma fórmula definida para a função: {:?}", function_type))?;
         
-        // Avaliar a fórmula
-        let result = self.engine.evaluate_formula(&formula_id, parameters)?;
+        // Tabelas são interpoladas diretamente
+        if function_type == FunctionType::LookupTable {
+            let table = self.tables.get(&formula_id)
+                .ok_or_else(|| format!("Tabela não encontrada: {}", formula_id))?;
+            return table.evaluate(parameters).map(ParameterValue::Float);
+        }
         
-        Ok(result.value)
+        // Scripts do usuário são avaliados no ambiente restrito
+        if function_type == FunctionType::UserScript {
+            let result = self.scripting.as_ref()
+                .ok_or_else(|| "Scripts do usuário não estão habilitados".to_string())?
+                .evaluate(&formula_id, parameters)?;
+            return Ok(result.value);
+        }
+        
+        // Fórmulas algébricas (ex.: condutividade e calor específico consultados a cada
+        // célula) usam o bytecode em cache; as demais, o motor Rhai
+        if let Some(compiled) = self.engine.compiled_formula(&formula_id) {
+            if let Ok(arguments) = compiled.arguments(parameters) {
+                return compiled.evaluate(&arguments).map(ParameterValue::Float);
+            }
+        }
+        Ok(self.engine.evaluate_formula(&formula_id, parameters)?.value)
     }
     
+    /// Valida uma fórmula para um tipo de função
+    ///
+    /// Combina a validação do motor (compilação, avaliação e unidades dos parâmetros) com a
+    /// análise dimensional do resultado frente à unidade esperada para o tipo de função.
+    pub fn validate_formula_for_function(&self, formula: &Formula, function_type: FunctionType) -> Result<(), String> {
+        if formula.category != function_type.to_category() {
+            return Err(format!(
+                "Categoria da fórmula incompatível: esperado {:?}, encontrado {:?}",
+                function_type.to_category(),
+                formula.category
+            ));
+        }
+        function_type.check_result_unit(formula)?;
+        self.engine.validate_formula(&formula.source, &formula.parameters)
+    }
+
+    /// Registra uma fórmula como fonte volumétrica S(r, z, t, T) do solucionador
+    ///
+    /// As variáveis da célula (`r`, `z`, `t`, `temperature`) não declaradas pela fórmula
+    /// são acrescentadas como parâmetros, para que estejam no escopo da avaliação.
+    pub fn register_volumetric_source(&mut self, formula_id: &str, mut formula: Formula) -> Result<(), String> {
+        add_solver_variables(&mut formula, &VOLUMETRIC_SOURCE_VARIABLES, "Variável da célula fornecida pelo solucionador");
+        self.engine.add_formula(formula_id, formula)?;
+        self.set_formula_for_function(FunctionType::VolumetricSource, formula_id)
+    }
+
+    /// Registra uma fórmula como lei de controle da potência das tochas
+    ///
+    /// `variables` são as variáveis fornecidas pelo solucionador a cada passo (nome e
+    /// unidade); as não declaradas pela fórmula são acrescentadas como parâmetros.
+    pub fn register_power_control(&mut self, formula_id: &str, mut formula: Formula, variables: &[(&str, &str)]) -> Result<(), String> {
+        add_solver_variables(&mut formula, variables, "Variável do controle fornecida pelo solucionador");
+        self.engine.add_formula(formula_id, formula)?;
+        self.set_formula_for_function(FunctionType::PowerControl, formula_id)
+    }
+
+    /// Cria um gerenciador com a fórmula de uma fonte volumétrica registrada
+    pub fn from_source_term(term: &FormulaSourceTerm) -> Result<Self, String> {
+        term.validate()?;
+        let mut manager = Self::new();
+        manager.register_volumetric_source("volumetric_source", term.formula.clone())?;
+        Ok(manager)
+    }
+
+    /// Avalia a fonte volumétrica registrada em cada célula da malha (W/m³)
+    ///
+    /// `temperature` é o campo T^n (°C), `time` o tempo da simulação (s) e `constants`
+    /// os valores dos demais parâmetros da fórmula.
+    pub fn evaluate_volumetric_source(
+        &self,
+        mesh: &CylindricalMesh,
+        temperature: &Array2<f64>,
+        time: f64,
+        constants: &HashMap<String, ParameterValue>,
+    ) -> Result<Array2<f64>, String> {
+        let mut source = Array2::<f64>::zeros(temperature.dim());
+
+        // Fórmulas algébricas são avaliadas pelo bytecode; as demais, pelo motor Rhai
+        let formula_id = self.get_formula_for_function(FunctionType::VolumetricSource)
+            .ok_or_else(|| format!("Nenhuma fórmula definida para a função: {:?}", FunctionType::VolumetricSource))?;
+        if let Some(compiled) = self.engine.compiled_formula(&formula_id) {
+            let mut arguments = compiled.arguments(constants)?;
+            let indices = ["r", "z", "t", "temperature"].map(|name| compiled.parameter_index(name));
+            for ((i, j), value) in source.indexed_iter_mut() {
+                for (index, variable) in indices.iter().zip([mesh.r_coords[i], mesh.z_coords[j], time, temperature[[i, j]]]) {
+                    if let Some(index) = index {
+                        arguments[*index] = variable;
+                    }
+                }
+                *value = compiled.evaluate(&arguments)?;
+                if !value.is_finite() {
+                    return Err(format!("Fonte volumétrica não finita na célula ({}, {})", i, j));
+                }
+            }
+            return Ok(source);
+        }
+
+        let mut parameters = constants.clone();
+        parameters.insert("t".to_string(), ParameterValue::Float(time));
+        for ((i, j), value) in source.indexed_iter_mut() {
+            parameters.insert("r".to_string(), ParameterValue::Float(mesh.r_coords[i]));
+            parameters.insert("z".to_string(), ParameterValue::Float(mesh.z_coords[j]));
+            parameters.insert("temperature".to_string(), ParameterValue::Float(temperature[[i, j]]));
+            *value = match self.evaluate_function(FunctionType::VolumetricSource, &parameters)? {
+                ParameterValue::Float(q) => q,
+                ParameterValue::Integer(q) => q as f64,
+                other => return Err(format!("Fonte volumétrica deve retornar um número, obtido {}", other)),
+            };
+            if !value.is_finite() {
+                return Err(format!("Fonte volumétrica não finita na célula ({}, {})", i, j));
+            }
+        }
+        Ok(source)
+    }
+    
+    /// ID da fórmula de plasma usada por uma tocha para uma propriedade
+    ///
+    /// Usa a seleção da tocha (`plasma_formulas`) ou, na falta dela, a fórmula padrão da
+    /// biblioteca para o gás da tocha. A fórmula deve ser da categoria `Plasma`.
+    pub fn torch_formula(&self, torch: &PlasmaTorch, property: PlasmaProperty) -> Result<String, String> {
+        let formula_id = torch.plasma_formulas.get(&property).cloned()
+            .or_else(|| property.default_formula(&torch.gas_type))
+            .ok_or_else(|| format!("Nenhuma fórmula de {:?} para o gás '{}' da tocha {}", property, torch.gas_type, torch.id))?;
+        let formula = self.engine.get_formula(&formula_id)
+            .ok_or_else(|| format!("Fórmula não encontrada: {}", formula_id))?;
+        if formula.category != FormulaCategory::Plasma {
+            return Err(format!(
+                "Fórmula '{}' da tocha {} não é da categoria {:?}",
+                formula_id, torch.id, FormulaCategory::Plasma
+            ));
+        }
+        Ok(formula_id)
+    }
+
+    /// Avalia uma propriedade de plasma para uma tocha
+    ///
+    /// A fórmula recebe a temperatura do plasma `temperature` (K) e a potência (kW) e a
+    /// vazão de gás (kg/s) da tocha; os demais parâmetros usam os valores padrão.
+    pub fn evaluate_torch_property(&self, torch: &PlasmaTorch, property: PlasmaProperty, temperature: f64) -> Result<f64, String> {
+        let formula_id = self.torch_formula(torch, property)?;
+        let mut parameters = HashMap::new();
+        parameters.insert("temperature".to_string(), ParameterValue::Float(temperature));
+        parameters.insert("power".to_string(), ParameterValue::Float(torch.power));
+        parameters.insert("gas_flow".to_string(), ParameterValue::Float(torch.gas_flow));
+        if property == PlasmaProperty::OutletEnthalpy {
+            let efficiency = self.evaluate_torch_property(torch, PlasmaProperty::TorchEfficiency, temperature)?;
+            parameters.insert("efficiency".to_string(), ParameterValue::Float(efficiency));
+        }
+        let result = self.engine.evaluate_formula(&formula_id, &parameters)?;
+        result.value.as_f64()
+            .ok_or_else(|| format!("Fórmula '{}' deve retornar um número, obtido {}", formula_id, result.value))
+    }
+
     /// Obtém todas as fórmulas compatíveis com um tipo de função
     pub fn get_compatible_formulas(&self, function_type: FunctionType) -> Vec<(String, Formula)> {
         let category = function_type.to_category();
@@ -162,6 +601,123 @@ impl FormulaManager {
         serde_json::to_string_pretty(&export_data).unwrap_or_else(|_| "{}".to_string())
     }
     
+    /// Exporta fórmulas e suas associações para um arquivo de pacote JSON
+    ///
+    /// Com `ids` vazio, exporta todas as fórmulas do motor. Somente as associações que
+    /// apontam para fórmulas exportadas são incluídas.
+    pub fn export_formulas_json(&self, path: &Path, ids: &[String]) -> Result<(), String> {
+        let mut formulas = BTreeMap::new();
+        if ids.is_empty() {
+            formulas.extend(self.engine.get_all_formulas());
+        } else {
+            for id in ids {
+                let formula = self.engine.get_formula_clone(id)
+                    .ok_or_else(|| format!("Fórmula não encontrada: {}", id))?;
+                formulas.insert(id.clone(), formula);
+            }
+        }
+        let function_mappings = self.function_mappings.iter()
+            .filter(|(_, id)| formulas.contains_key(*id))
+            .map(|(function, id)| (function.clone(), id.clone()))
+            .collect();
+        let bundle = FormulaBundle {
+            format_version: FORMULA_BUNDLE_VERSION,
+            formulas,
+            function_mappings,
+        };
+        let content = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| format!("Erro ao serializar pacote de fórmulas: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Erro ao gravar pacote de fórmulas {:?}: {}", path, e))
+    }
+
+    /// Importa fórmulas e associações de um arquivo de pacote JSON
+    ///
+    /// IDs já existentes são tratados conforme `policy`; as associações do pacote seguem as
+    /// fórmulas renomeadas e são ignoradas para fórmulas não importadas. A importação é
+    /// atômica: em caso de erro, o gerenciador permanece inalterado.
+    pub fn import_formulas_json(&mut self, path: &Path, policy: FormulaConflictPolicy) -> Result<FormulaImportSummary, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Erro ao ler pacote de fórmulas {:?}: {}", path, e))?;
+        let bundle: FormulaBundle = serde_json::from_str(&content)
+            .map_err(|e| format!("Pacote de fórmulas inválido {:?}: {}", path, e))?;
+        if bundle.format_version > FORMULA_BUNDLE_VERSION {
+            return Err(format!("Versão do formato de pacote de fórmulas não suportada: {}", bundle.format_version));
+        }
+
+        // IDs finais de cada fórmula do pacote
+        let mut summary = FormulaImportSummary::default();
+        let mut targets = Vec::new();
+        let bundle_ids: Vec<String> = bundle.formulas.keys().cloned().collect();
+        for (id, formula) in bundle.formulas {
+            let target = if self.engine.get_formula(&id).is_none() {
+                id.clone()
+            } else {
+                match policy {
+                    FormulaConflictPolicy::Skip => {
+                        summary.skipped.push(id);
+                        continue;
+                    }
+                    FormulaConflictPolicy::Overwrite => id.clone(),
+                    FormulaConflictPolicy::Rename => {
+                        let renamed = (2..)
+                            .map(|n| format!("{}_{}", id, n))
+                            .find(|candidate| {
+                                self.engine.get_formula(candidate).is_none()
+                                    && !bundle_ids.contains(candidate)
+                            })
+                            .unwrap();
+                        summary.renamed.insert(id.clone(), renamed.clone());
+                        renamed
+                    }
+                    FormulaConflictPolicy::Fail => return Err(format!("Fórmula já existente: {}", id)),
+                }
+            };
+            summary.imported.push(target.clone());
+            targets.push((id, target, formula));
+        }
+
+        // Aplicar sobre uma cópia do estado, restaurada em caso de erro
+        let previous_formulas: Vec<(String, Option<Formula>)> = targets.iter()
+            .map(|(_, target, _)| (target.clone(), self.engine.get_formula_clone(target)))
+            .collect();
+        let previous_mappings = self.function_mappings.clone();
+        let result = self.apply_bundle(targets, &bundle.function_mappings, &mut summary);
+        if result.is_err() {
+            for (id, formula) in previous_formulas {
+                self.engine.remove_formula(&id);
+                if let Some(formula) = formula {
+                    self.engine.add_formula(&id, formula)?;
+                }
+            }
+            self.function_mappings = previous_mappings;
+        }
+        result.map(|_| summary)
+    }
+
+    /// Registra as fórmulas de um pacote e as associações correspondentes
+    fn apply_bundle(
+        &mut self,
+        targets: Vec<(String, String, Formula)>,
+        mappings: &BTreeMap<String, String>,
+        summary: &mut FormulaImportSummary,
+    ) -> Result<(), String> {
+        let mut registered = HashMap::new();
+        for (original, target, formula) in targets {
+            self.engine.add_formula(&target, formula)
+                .map_err(|e| format!("Fórmula '{}': {}", original, e))?;
+            registered.insert(original, target);
+        }
+        for (function, id) in mappings {
+            let function_type = FunctionType::from_string(function)
+                .ok_or_else(|| format!("Tipo de função desconhecido: {}", function))?;
+            if let Some(target) = registered.get(id) {
+                self.set_formula_for_function(function_type, target)?;
+                summary.mappings.push(function.clone());
+            }
+        }
+        Ok(())
+    }
+
     /// Importa as configurações do gerenciador de fórmulas a partir de JSON
     pub fn import_from_json(&mut self, json: &str) -> Result<(), String> {
         let import_data: serde_json::Value = serde_json::from_str(json)
@@ -172,8 +728,15 @@ impl FormulaManager {
             for (key, value) in mappings {
                 if let Some(value_str) = value.as_str() {
                     if let Some(function_type) = FunctionType::from_string(key) {
-                        // Verificar se a fórmula existe
-                        if self.engine.get_formula(value_str).is_none() {
+                        // Verificar se a fórmula (ou o script) existe
+                        let exists = match function_type {
+                            FunctionType::UserScript => {
+                                self.scripting.as_ref().is_some_and(|sandbox| sandbox.get_script(value_str).is_some())
+                            }
+                            FunctionType::LookupTable => self.tables.contains_key(value_str),
+                            _ => self.engine.get_formula(value_str).is_some(),
+                        };
+                        if !exists {
                             return Err(format!("Fórmula não encontrada: {}", value_str));
                         }
                         
@@ -251,6 +814,19 @@ mod tests {
             }
             _ => panic!("Tipo de resultado inesperado"),
         }
+        
+        // O bytecode em cache acompanha a substituição e a remoção da fórmula
+        assert!(manager.get_engine().compiled_formula("thermal_conductivity").is_some());
+        let mut formula = manager.get_engine().get_formula_clone("thermal_conductivity").unwrap();
+        formula.source = "2.0 * k0".to_string();
+        manager.get_engine_mut().add_formula("thermal_conductivity", formula.clone()).unwrap();
+        assert_eq!(manager.evaluate_function(FunctionType::ThermalConductivity, &params).unwrap().as_f64(), Some(90.0));
+        formula.source = "if temperature > 50.0 { k0 } else { k1 }".to_string();
+        manager.get_engine_mut().add_formula("thermal_conductivity", formula).unwrap();
+        assert!(manager.get_engine().compiled_formula("thermal_conductivity").is_none());
+        assert_eq!(manager.evaluate_function(FunctionType::ThermalConductivity, &params).unwrap().as_f64(), Some(45.0));
+        manager.get_engine_mut().remove_formula("thermal_conductivity");
+        assert!(manager.get_engine().compiled_formula("thermal_conductivity").is_none());
     }
     
     #[test]
@@ -264,6 +840,198 @@ mod tests {
         assert!(formulas.iter().any(|(id, _)| id == "thermal_conductivity"));
     }
     
+    #[test]
+    fn test_result_units_checked_against_function_type() {
+        let mut manager = FormulaManager::new();
+        let mut formula = manager.get_engine().get_formula_clone("thermal_conductivity").unwrap();
+        assert!(manager.validate_formula_for_function(&formula, FunctionType::ThermalConductivity).is_ok());
+
+        // Resultado em W/(m²·K) não é uma condutividade
+        formula.result_unit = "W/(m²·K)".to_string();
+        let error = manager.validate_formula_for_function(&formula, FunctionType::ThermalConductivity).unwrap_err();
+        assert!(error.contains("incompatível"));
+        manager.get_engine_mut().add_formula("k_wrong_units", formula).unwrap();
+        assert!(manager.set_formula_for_function(FunctionType::ThermalConductivity, "k_wrong_units").is_err());
+
+        // Unidade de parâmetro desconhecida
+        let mut formula = manager.get_engine().get_formula_clone("thermal_conductivity").unwrap();
+        formula.parameters[0].unit = "furlong".to_string();
+        assert!(manager.validate_formula_for_function(&formula, FunctionType::ThermalConductivity).is_err());
+    }
+
+    #[test]
+    fn test_volumetric_source_evaluated_per_cell() {
+        let formula = Formula {
+            name: "Aquecimento experimental".to_string(),
+            description: "Fonte linear em r e no tempo, nula acima de 500 °C".to_string(),
+            source: r#"
+                if temperature > 500.0 { return 0.0; }
+                return q0 * r * (1.0 + t);
+            "#.to_string(),
+            ast: None,
+            parameters: vec![FormulaParameter {
+                name: "q0".to_string(),
+                description: "Intensidade".to_string(),
+                param_type: ParameterType::Float,
+                default_value: ParameterValue::Float(1.0),
+                unit: "W/m⁴".to_string(),
+                min_value: None,
+                max_value: None,
+            }],
+            category: FormulaCategory::HeatSource,
+            result_unit: "W/m³".to_string(),
+            tests: Vec::new(),
+        };
+        let mut term = FormulaSourceTerm::new(formula);
+        term.parameters.insert("q0".to_string(), 1000.0);
+        let manager = FormulaManager::from_source_term(&term).unwrap();
+        assert_eq!(manager.get_formula_for_function(FunctionType::VolumetricSource), Some("volumetric_source".to_string()));
+
+        let mesh = CylindricalMesh::new(1.0, 0.5, 3, 3, 4);
+        let mut temperature = Array2::<f64>::from_elem((3, 3), 25.0);
+        temperature[[2, 2]] = 800.0;
+        let source = manager.evaluate_volumetric_source(&mesh, &temperature, 1.0, &term.constants()).unwrap();
+        assert!((source[[2, 0]] - 1000.0 * mesh.r_coords[2] * 2.0).abs() < 1e-9);
+        assert_eq!(source[[0, 1]], 0.0);
+        assert_eq!(source[[2, 2]], 0.0);
+
+        // Variáveis da célula não podem ser fixadas como parâmetros
+        term.parameters.insert("temperature".to_string(), 100.0);
+        assert!(term.validate().is_err());
+    }
+    
+    #[test]
+    fn test_user_script_function() {
+        let script = Formula {
+            name: "Potência por etapas".to_string(),
+            description: "Potência da tocha definida por etapas de tempo".to_string(),
+            source: r#"
+                let stages = [[0.0, 100.0], [60.0, 250.0], [120.0, 400.0]];
+                let power = 0.0;
+                for stage in stages {
+                    if t >= stage[0] { power = stage[1]; }
+                }
+                power
+            "#.to_string(),
+            ast: None,
+            parameters: vec![FormulaParameter {
+                name: "t".to_string(),
+                description: "Tempo".to_string(),
+                param_type: ParameterType::Float,
+                default_value: ParameterValue::Float(0.0),
+                unit: "s".to_string(),
+                min_value: None,
+                max_value: None,
+            }],
+            category: FormulaCategory::Utility,
+            result_unit: "kW".to_string(),
+            tests: Vec::new(),
+        };
+
+        // Scripts exigem habilitação explícita
+        let mut manager = FormulaManager::new();
+        assert!(manager.register_script("stages", script.clone()).is_err());
+        assert!(manager.set_formula_for_function(FunctionType::UserScript, "stages").is_err());
+
+        manager.enable_scripting(ScriptLimits::default()).unwrap();
+        manager.register_script("stages", script).unwrap();
+        manager.set_formula_for_function(FunctionType::UserScript, "stages").unwrap();
+        let mut params = HashMap::new();
+        params.insert("t".to_string(), ParameterValue::Float(90.0));
+        let power = manager.evaluate_function(FunctionType::UserScript, &params).unwrap();
+        assert_eq!(power.as_f64(), Some(250.0));
+    }
+
+    #[test]
+    fn test_plasma_library_selectable_per_torch() {
+        let manager = FormulaManager::new();
+        assert_eq!(manager.get_engine().get_formulas_by_category(FormulaCategory::Plasma).len(), 6);
+        for (_, formula) in manager.get_engine().get_formulas_by_category(FormulaCategory::Plasma) {
+            formula.result_dimension().unwrap();
+        }
+
+        let mut torch = PlasmaTorch::new("t1", 0.0, 0.0, 0.5, 0.0, 0.0, 100.0, 0.01, 25.0);
+        assert_eq!(manager.torch_formula(&torch, PlasmaProperty::Enthalpy).unwrap(), "argon_plasma_enthalpy");
+
+        // Entalpia cresce com a temperatura; a ionização domina acima de 15000 K
+        let h_cold = manager.evaluate_torch_property(&torch, PlasmaProperty::Enthalpy, 5000.0).unwrap();
+        let h_hot = manager.evaluate_torch_property(&torch, PlasmaProperty::Enthalpy, 20000.0).unwrap();
+        assert!(h_cold > 0.0 && h_hot > 3.0e7 && h_hot > h_cold);
+        let sigma = manager.evaluate_torch_property(&torch, PlasmaProperty::ElectricalConductivity, 10000.0).unwrap();
+        assert!(sigma > 1.0e3 && sigma < 2.0e3);
+
+        // Na condição de referência, η = η0 e h = η·P/G
+        let efficiency = manager.evaluate_torch_property(&torch, PlasmaProperty::TorchEfficiency, 0.0).unwrap();
+        assert!((efficiency - 0.7).abs() < 1e-12);
+        let outlet = manager.evaluate_torch_property(&torch, PlasmaProperty::OutletEnthalpy, 0.0).unwrap();
+        assert!((outlet - 0.7 * 100.0e3 / 0.01).abs() < 1e-3);
+
+        // Seleção por tocha
+        torch.gas_type = "N2".to_string();
+        assert_eq!(manager.torch_formula(&torch, PlasmaProperty::Enthalpy).unwrap(), "nitrogen_plasma_enthalpy");
+        torch.plasma_formulas.insert(PlasmaProperty::Enthalpy, "argon_plasma_enthalpy".to_string());
+        assert_eq!(manager.torch_formula(&torch, PlasmaProperty::Enthalpy).unwrap(), "argon_plasma_enthalpy");
+        torch.plasma_formulas.insert(PlasmaProperty::Enthalpy, "thermal_conductivity".to_string());
+        assert!(manager.torch_formula(&torch, PlasmaProperty::Enthalpy).is_err());
+        torch.gas_type = "CO2".to_string();
+        assert!(manager.torch_formula(&torch, PlasmaProperty::ElectricalConductivity).is_err());
+    }
+
+    #[test]
+    fn test_lookup_table_function() {
+        let mut manager = FormulaManager::new();
+        assert!(manager.set_formula_for_function(FunctionType::LookupTable, "k_measured").is_err());
+        manager.add_table_json("k_measured", r#"{
+            "name": "Condutividade medida",
+            "x": {"parameter": "temperature", "unit": "°C", "values": [20.0, 500.0, 1000.0]},
+            "values": [1.2, 1.5, 2.1],
+            "result_unit": "W/(m·K)",
+            "method": "cubic"
+        }"#).unwrap();
+        manager.set_formula_for_function(FunctionType::LookupTable, "k_measured").unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("temperature".to_string(), ParameterValue::Float(500.0));
+        let k = manager.evaluate_function(FunctionType::LookupTable, &params).unwrap();
+        assert!((k.as_f64().unwrap() - 1.5).abs() < 1e-12);
+        assert!(manager.add_table_json("bad", r#"{"name": "x", "x": {"parameter": "t", "values": [1.0, 0.0]}, "values": [1.0, 2.0]}"#).is_err());
+    }
+
+    #[test]
+    fn test_formula_bundle_round_trip() {
+        let path = std::env::temp_dir().join(format!("formula_bundle_{}.json", std::process::id()));
+        let mut source = FormulaManager::new();
+        let mut formula = source.get_engine().get_formula_clone("thermal_conductivity").unwrap();
+        formula.name = "Condutividade do refratário".to_string();
+        source.get_engine_mut().add_formula("refractory_k", formula).unwrap();
+        source.set_formula_for_function(FunctionType::ThermalConductivity, "refractory_k").unwrap();
+        source.export_formulas_json(&path, &["refractory_k".to_string()]).unwrap();
+
+        // Máquina de destino sem a fórmula
+        let mut target = FormulaManager::new();
+        let summary = target.import_formulas_json(&path, FormulaConflictPolicy::Fail).unwrap();
+        assert_eq!(summary.imported, vec!["refractory_k".to_string()]);
+        assert_eq!(target.get_formula_for_function(FunctionType::ThermalConductivity), Some("refractory_k".to_string()));
+
+        // Conflitos
+        assert!(target.import_formulas_json(&path, FormulaConflictPolicy::Fail).is_err());
+        let summary = target.import_formulas_json(&path, FormulaConflictPolicy::Skip).unwrap();
+        assert_eq!(summary.skipped, vec!["refractory_k".to_string()]);
+        let summary = target.import_formulas_json(&path, FormulaConflictPolicy::Rename).unwrap();
+        assert_eq!(summary.renamed["refractory_k"], "refractory_k_2");
+        assert_eq!(target.get_formula_for_function(FunctionType::ThermalConductivity), Some("refractory_k_2".to_string()));
+        assert_eq!(target.get_engine().get_formula("refractory_k_2").unwrap().name, "Condutividade do refratário");
+
+        // Pacote com fórmula inválida não altera o gerenciador
+        let mut bundle: FormulaBundle = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        bundle.formulas.get_mut("refractory_k").unwrap().source = "k0 +".to_string();
+        fs::write(&path, serde_json::to_string(&bundle).unwrap()).unwrap();
+        assert!(target.import_formulas_json(&path, FormulaConflictPolicy::Overwrite).is_err());
+        assert!(target.get_engine().get_formula("refractory_k").unwrap().ast.is_some());
+        assert_eq!(target.get_formula_for_function(FunctionType::ThermalConductivity), Some("refractory_k_2".to_string()));
+        fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_export_import_json() {
         let mut manager = FormulaManager::new();
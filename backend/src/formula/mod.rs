@@ -1,7 +1,13 @@
 // Módulo de fórmulas para o simulador de fornalha de plasma
 
+pub mod compiled;
 pub mod engine;
+pub mod expression;
 pub mod integration;
+pub mod plasma;
+pub mod script;
+pub mod table;
+pub mod units;
 
 // Re-exportar tipos principais
 pub use engine::{
@@ -11,10 +17,24 @@ pub use engine::{
     ParameterType, 
     ParameterValue, 
     FormulaCategory,
-    FormulaResult
+    FormulaResult,
+    FormulaError,
+    EvaluationLimits,
+    FormulaTestCase,
+    FormulaTestOutcome
 };
 
 pub use integration::{
+    FormulaBundle,
+    FormulaConflictPolicy,
+    FormulaImportSummary,
     FormulaManager,
+    FormulaSourceTerm,
     FunctionType
 };
+
+pub use compiled::CompiledFormula;
+pub use plasma::PlasmaProperty;
+pub use script::{ScriptLimits, ScriptSandbox};
+pub use table::{InterpolationMethod, LookupTable, TableAxis};
+pub use units::Dimension;
@@ -0,0 +1,210 @@
+// Biblioteca de fórmulas de propriedades de plasma
+//
+// Correlações simplificadas para gases de tocha (argônio e nitrogênio) e para o
+// desempenho da tocha, registradas em todo `FormulaManager` na categoria `Plasma`.
+// As propriedades de estado usam a temperatura absoluta (K): a entalpia soma o termo
+// sensível às energias de dissociação e ionização, distribuídas por sigmoides centradas
+// nas temperaturas em que cada processo ocorre; a condutividade elétrica segue uma
+// sigmoide de ionização ajustada a valores tabelados em 1 atm. A eficiência térmica da
+// tocha é uma correlação de potência em vazão e potência, limitada a [0, 1].
+
+use serde::{Deserialize, Serialize};
+
+use super::engine::{Formula, FormulaCategory, FormulaParameter, ParameterType, ParameterValue};
+
+/// Enumeração que representa as propriedades de plasma selecionáveis por tocha
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PlasmaProperty {
+    /// Entalpia específica do gás de plasma (J/kg)
+    Enthalpy,
+    /// Condutividade elétrica do gás de plasma (S/m)
+    ElectricalConductivity,
+    /// Eficiência térmica da tocha (fração da potência elétrica transferida ao gás)
+    TorchEfficiency,
+    /// Entalpia média do gás na saída da tocha (J/kg)
+    OutletEnthalpy,
+}
+
+/// Gases de plasma com correlações na biblioteca
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlasmaGas {
+    Argon,
+    Nitrogen,
+}
+
+impl PlasmaGas {
+    /// Identifica o gás pelo tipo de gás da tocha ("Ar", "argon", "N2", "nitrogênio", ...)
+    fn from_gas_type(gas_type: &str) -> Option<Self> {
+        match gas_type.trim().to_lowercase().as_str() {
+            "ar" | "argon" | "argônio" | "argonio" => Some(PlasmaGas::Argon),
+            "n2" | "nitrogen" | "nitrogênio" | "nitrogenio" => Some(PlasmaGas::Nitrogen),
+            _ => None,
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            PlasmaGas::Argon => "argon",
+            PlasmaGas::Nitrogen => "nitrogen",
+        }
+    }
+}
+
+impl PlasmaProperty {
+    /// ID da fórmula da biblioteca usada por padrão para uma tocha com o gás informado
+    ///
+    /// Propriedades de estado dependem do gás e retornam `None` para gases sem correlação;
+    /// as correlações da tocha independem do gás.
+    pub fn default_formula(&self, gas_type: &str) -> Option<String> {
+        match self {
+            PlasmaProperty::Enthalpy => PlasmaGas::from_gas_type(gas_type)
+                .map(|gas| format!("{}_plasma_enthalpy", gas.prefix())),
+            PlasmaProperty::ElectricalConductivity => PlasmaGas::from_gas_type(gas_type)
+                .map(|gas| format!("{}_electrical_conductivity", gas.prefix())),
+            PlasmaProperty::TorchEfficiency => Some("torch_thermal_efficiency".to_string()),
+            PlasmaProperty::OutletEnthalpy => Some("torch_outlet_enthalpy".to_string()),
+        }
+    }
+}
+
+/// Parâmetro numérico de uma fórmula da biblioteca
+fn parameter(name: &str, description: &str, default: f64, unit: &str) -> FormulaParameter {
+    FormulaParameter {
+        name: name.to_string(),
+        description: description.to_string(),
+        param_type: ParameterType::Float,
+        default_value: ParameterValue::Float(default),
+        unit: unit.to_string(),
+        min_value: None,
+        max_value: None,
+    }
+}
+
+/// Fórmula da categoria `Plasma`
+fn plasma_formula(name: &str, description: &str, source: &str, parameters: Vec<FormulaParameter>, result_unit: &str) -> Formula {
+    Formula {
+        name: name.to_string(),
+        description: description.to_string(),
+        source: source.to_string(),
+        ast: None,
+        parameters,
+        category: FormulaCategory::Plasma,
+        result_unit: result_unit.to_string(),
+        tests: Vec::new(),
+    }
+}
+
+/// Fórmulas da biblioteca de plasma, por ID
+pub fn plasma_formulas() -> Vec<(&'static str, Formula)> {
+    let temperature = || parameter("temperature", "Temperatura do plasma", 10000.0, "K");
+    vec![
+        (
+            "argon_plasma_enthalpy",
+            plasma_formula(
+                "Entalpia do Plasma de Argônio",
+                "Entalpia específica do argônio a 1 atm: termo sensível monoatômico e ionização (15,76 eV)",
+                r#"
+                    let ionized = 1.0 / (1.0 + exp(-(temperature - t_ion) / w_ion));
+                    return cp * (temperature - 298.15) + h_ion * ionized;
+                "#,
+                vec![
+                    temperature(),
+                    parameter("cp", "Calor específico do gás neutro", 520.3, "J/(kg·K)"),
+                    parameter("h_ion", "Energia de ionização por massa", 3.806e7, "J/kg"),
+                    parameter("t_ion", "Temperatura de meia ionização", 15000.0, "K"),
+                    parameter("w_ion", "Largura da faixa de ionização", 1500.0, "K"),
+                ],
+                "J/kg",
+            ),
+        ),
+        (
+            "nitrogen_plasma_enthalpy",
+            plasma_formula(
+                "Entalpia do Plasma de Nitrogênio",
+                "Entalpia específica do nitrogênio a 1 atm: termo sensível, dissociação (9,79 eV) e ionização (14,53 eV)",
+                r#"
+                    let dissociated = 1.0 / (1.0 + exp(-(temperature - t_diss) / w_diss));
+                    let ionized = 1.0 / (1.0 + exp(-(temperature - t_ion) / w_ion));
+                    return cp * (temperature - 298.15) + h_diss * dissociated + h_ion * ionized;
+                "#,
+                vec![
+                    temperature(),
+                    parameter("cp", "Calor específico médio do gás", 1250.0, "J/(kg·K)"),
+                    parameter("h_diss", "Energia de dissociação por massa", 3.373e7, "J/kg"),
+                    parameter("t_diss", "Temperatura de meia dissociação", 7000.0, "K"),
+                    parameter("w_diss", "Largura da faixa de dissociação", 800.0, "K"),
+                    parameter("h_ion", "Energia de ionização por massa", 1.001e8, "J/kg"),
+                    parameter("t_ion", "Temperatura de meia ionização", 15000.0, "K"),
+                    parameter("w_ion", "Largura da faixa de ionização", 1500.0, "K"),
+                ],
+                "J/kg",
+            ),
+        ),
+        (
+            "argon_electrical_conductivity",
+            plasma_formula(
+                "Condutividade Elétrica do Plasma de Argônio",
+                "Condutividade elétrica do argônio a 1 atm, sigmoide de ionização",
+                "sigma_max / (1.0 + exp(-(temperature - t_half) / width))",
+                vec![
+                    temperature(),
+                    parameter("sigma_max", "Condutividade de saturação", 1.0e4, "S/m"),
+                    parameter("t_half", "Temperatura de meia condutividade", 13000.0, "K"),
+                    parameter("width", "Largura da transição", 1500.0, "K"),
+                ],
+                "S/m",
+            ),
+        ),
+        (
+            "nitrogen_electrical_conductivity",
+            plasma_formula(
+                "Condutividade Elétrica do Plasma de Nitrogênio",
+                "Condutividade elétrica do nitrogênio a 1 atm, sigmoide de ionização",
+                "sigma_max / (1.0 + exp(-(temperature - t_half) / width))",
+                vec![
+                    temperature(),
+                    parameter("sigma_max", "Condutividade de saturação", 1.0e4, "S/m"),
+                    parameter("t_half", "Temperatura de meia condutividade", 13500.0, "K"),
+                    parameter("width", "Largura da transição", 1700.0, "K"),
+                ],
+                "S/m",
+            ),
+        ),
+        (
+            "torch_thermal_efficiency",
+            plasma_formula(
+                "Eficiência Térmica da Tocha",
+                "Fração da potência elétrica transferida ao gás: η = η0·(G/G_ref)^a·(P/P_ref)^b, limitada a [0, 1]",
+                r#"
+                    let eta = eta0 * pow(gas_flow / flow_ref, a) * pow(power / power_ref, b);
+                    if eta > 1.0 { 1.0 } else if eta < 0.0 { 0.0 } else { eta }
+                "#,
+                vec![
+                    parameter("power", "Potência elétrica da tocha", 100.0, "kW"),
+                    parameter("gas_flow", "Vazão de gás", 0.01, "kg/s"),
+                    parameter("eta0", "Eficiência de referência", 0.7, ""),
+                    parameter("power_ref", "Potência de referência", 100.0, "kW"),
+                    parameter("flow_ref", "Vazão de referência", 0.01, "kg/s"),
+                    parameter("a", "Expoente da vazão", 0.15, ""),
+                    parameter("b", "Expoente da potência", -0.1, ""),
+                ],
+                "",
+            ),
+        ),
+        (
+            "torch_outlet_enthalpy",
+            plasma_formula(
+                "Entalpia Média na Saída da Tocha",
+                "Entalpia média do gás na saída da tocha: h = η·P/G",
+                "efficiency * power * 1000.0 / gas_flow",
+                vec![
+                    parameter("power", "Potência elétrica da tocha", 100.0, "kW"),
+                    parameter("gas_flow", "Vazão de gás", 0.01, "kg/s"),
+                    parameter("efficiency", "Eficiência térmica da tocha", 0.7, ""),
+                ],
+                "J/kg",
+            ),
+        ),
+    ]
+}
@@ -0,0 +1,213 @@
+// Scripts Rhai do usuário para funções que exigem lógica além de expressões algébricas
+//
+// Scripts podem usar condicionais, laços e funções próprias, com a mesma interface de
+// parâmetros das fórmulas (`Formula`/`FormulaParameter`). Como o código vem do usuário, é
+// executado em um motor separado e restrito: número de operações, profundidade de chamadas
+// e expressões e tamanho de strings, arrays e mapas são limitados, a importação de módulos
+// e `eval` são desabilitadas. Um script que excede um limite termina com erro em vez de
+// travar o solucionador.
+
+use rhai::{Dynamic, Engine, Scope};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::engine::{self, Formula, FormulaResult, ParameterValue};
+
+/// Estrutura que representa os limites de execução de um script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptLimits {
+    /// Número máximo de operações por avaliação
+    pub max_operations: u64,
+    /// Profundidade máxima de chamadas de função
+    pub max_call_levels: usize,
+    /// Profundidade máxima de expressões (também usada para funções)
+    pub max_expr_depth: usize,
+    /// Tamanho máximo de strings (caracteres)
+    pub max_string_size: usize,
+    /// Tamanho máximo de arrays (elementos)
+    pub max_array_size: usize,
+    /// Tamanho máximo de mapas (entradas)
+    pub max_map_size: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: 1_000_000,
+            max_call_levels: 32,
+            max_expr_depth: 64,
+            max_string_size: 10_000,
+            max_array_size: 100_000,
+            max_map_size: 1_000,
+        }
+    }
+}
+
+impl ScriptLimits {
+    /// Valida os limites
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_operations == 0 {
+            return Err("O número máximo de operações deve ser positivo".to_string());
+        }
+        if self.max_call_levels == 0 || self.max_expr_depth == 0 {
+            return Err("As profundidades máximas de chamadas e expressões devem ser positivas".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa o ambiente restrito de execução de scripts do usuário
+pub struct ScriptSandbox {
+    /// Motor Rhai com os limites aplicados
+    engine: Engine,
+    /// Scripts registrados, compilados
+    scripts: HashMap<String, Formula>,
+    /// Limites de execução
+    limits: ScriptLimits,
+    /// Buffer de logs
+    log_buffer: Arc<Mutex<Vec<String>>>,
+}
+
+impl ScriptSandbox {
+    /// Cria um ambiente de execução com os limites fornecidos
+    pub fn new(limits: ScriptLimits) -> Result<Self, String> {
+        limits.validate()?;
+        let mut engine = Engine::new();
+        engine.set_max_operations(limits.max_operations);
+        engine.set_max_call_levels(limits.max_call_levels);
+        engine.set_max_expr_depths(limits.max_expr_depth, limits.max_expr_depth);
+        engine.set_max_string_size(limits.max_string_size);
+        engine.set_max_array_size(limits.max_array_size);
+        engine.set_max_map_size(limits.max_map_size);
+        engine.set_max_modules(0);
+        engine.disable_symbol("eval");
+
+        let log_buffer = Arc::new(Mutex::new(Vec::new()));
+        engine::register_builtin_functions(&mut engine, log_buffer.clone());
+
+        Ok(Self {
+            engine,
+            scripts: HashMap::new(),
+            limits,
+            log_buffer,
+        })
+    }
+
+    /// Limites de execução do ambiente
+    pub fn limits(&self) -> &ScriptLimits {
+        &self.limits
+    }
+
+    /// Compila e registra um script
+    pub fn add_script(&mut self, id: &str, mut formula: Formula) -> Result<(), String> {
+        for param in &formula.parameters {
+            param.dimension()?;
+        }
+        let ast = self.engine.compile(&formula.source)
+            .map_err(|e| format!("Erro ao compilar o script '{}': {}", id, e))?;
+        formula.ast = Some(ast);
+        self.scripts.insert(id.to_string(), formula);
+        Ok(())
+    }
+
+    /// Remove um script
+    pub fn remove_script(&mut self, id: &str) -> bool {
+        self.scripts.remove(id).is_some()
+    }
+
+    /// Obtém um script registrado
+    pub fn get_script(&self, id: &str) -> Option<&Formula> {
+        self.scripts.get(id)
+    }
+
+    /// Avalia um script com os parâmetros fornecidos
+    ///
+    /// Assim como nas fórmulas, somente os parâmetros declarados entram no escopo, com
+    /// os valores padrão para os não fornecidos.
+    pub fn evaluate(&self, id: &str, parameters: &HashMap<String, ParameterValue>) -> Result<FormulaResult, String> {
+        let script = self.scripts.get(id)
+            .ok_or_else(|| format!("Script não encontrado: {}", id))?;
+        let ast = script.ast.as_ref()
+            .ok_or_else(|| format!("Script não compilado: {}", id))?;
+
+        let mut scope = Scope::new();
+        for param in &script.parameters {
+            let value = parameters.get(&param.name).unwrap_or(&param.default_value);
+            scope.push(param.name.clone(), value.to_dynamic());
+        }
+
+        if let Ok(mut buffer) = self.log_buffer.lock() {
+            buffer.clear();
+        }
+        let start_time = std::time::Instant::now();
+        let value = self.engine.eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+            .map_err(|e| format!("Erro ao avaliar o script '{}': {}", id, e))?;
+        let execution_time_us = start_time.elapsed().as_micros();
+        let logs = self.log_buffer.lock().map(|buffer| buffer.clone()).unwrap_or_default();
+
+        Ok(FormulaResult {
+            value: ParameterValue::from_dynamic(&value)?,
+            execution_time_us,
+            logs,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formula::engine::{FormulaCategory, FormulaParameter, ParameterType};
+
+    fn script(source: &str) -> Formula {
+        Formula {
+            name: "Script".to_string(),
+            description: String::new(),
+            source: source.to_string(),
+            ast: None,
+            parameters: vec![FormulaParameter {
+                name: "n".to_string(),
+                description: "Número de termos".to_string(),
+                param_type: ParameterType::Integer,
+                default_value: ParameterValue::Integer(10),
+                unit: String::new(),
+                min_value: None,
+                max_value: None,
+            }],
+            category: FormulaCategory::Utility,
+            result_unit: String::new(),
+            tests: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_script_sandbox_limits() {
+        let mut sandbox = ScriptSandbox::new(ScriptLimits::default()).unwrap();
+        sandbox.add_script("sum", script(r#"
+            let total = 0.0;
+            for i in 0..n {
+                if i % 2 == 0 { total += sqrt(i.to_float()); } else { continue; }
+            }
+            print("ok");
+            total
+        "#)).unwrap();
+        let mut params = HashMap::new();
+        params.insert("n".to_string(), ParameterValue::Integer(5));
+        let result = sandbox.evaluate("sum", &params).unwrap();
+        let expected = 0.0 + 2.0_f64.sqrt() + 2.0;
+        assert!((result.value.as_f64().unwrap() - expected).abs() < 1e-12);
+        assert_eq!(result.logs, vec!["ok".to_string()]);
+
+        // Laço infinito é interrompido pelo limite de operações
+        sandbox.add_script("forever", script("let x = 0; loop { x += 1; }")).unwrap();
+        assert!(sandbox.evaluate("forever", &HashMap::new()).is_err());
+
+        // Recursão limitada pela profundidade de chamadas
+        sandbox.add_script("recursive", script("fn f(x) { f(x + 1) } f(n)")).unwrap();
+        assert!(sandbox.evaluate("recursive", &HashMap::new()).is_err());
+
+        // `eval` desabilitado
+        assert!(sandbox.add_script("eval", script(r#"eval("1 + 1")"#)).is_err());
+    }
+}
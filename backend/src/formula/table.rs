@@ -0,0 +1,259 @@
+// Tabelas de interpolação usadas no lugar de fórmulas
+//
+// Curvas de propriedades medidas (ex.: condutividade × temperatura) podem ser usadas
+// diretamente, sem ajuste de uma expressão: a "fórmula" é uma tabela de uma ou duas
+// entradas, editável em JSON, interpolada linearmente ou por splines cúbicos naturais
+// (produto tensorial em duas dimensões). Fora do intervalo tabelado o valor é mantido
+// constante no extremo mais próximo.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::engine::ParameterValue;
+use super::units;
+
+/// Enumeração que representa o método de interpolação de uma tabela
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterpolationMethod {
+    /// Interpolação linear por trechos
+    #[default]
+    Linear,
+    /// Spline cúbico natural
+    Cubic,
+}
+
+/// Estrutura que representa um eixo de uma tabela: parâmetro de entrada e pontos tabelados
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableAxis {
+    /// Nome do parâmetro de entrada (ex.: "temperature")
+    pub parameter: String,
+    /// Unidade do parâmetro
+    #[serde(default)]
+    pub unit: String,
+    /// Pontos do eixo, estritamente crescentes
+    pub values: Vec<f64>,
+}
+
+/// Estrutura que representa uma tabela de interpolação de uma ou duas entradas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupTable {
+    /// Nome da tabela
+    pub name: String,
+    /// Descrição da tabela
+    #[serde(default)]
+    pub description: String,
+    /// Primeiro eixo
+    pub x: TableAxis,
+    /// Segundo eixo (tabelas 2D)
+    #[serde(default)]
+    pub y: Option<TableAxis>,
+    /// Valores tabelados; em 2D, por linhas de x (índice i·ny + j)
+    pub values: Vec<f64>,
+    /// Unidade do resultado
+    #[serde(default)]
+    pub result_unit: String,
+    /// Método de interpolação
+    #[serde(default)]
+    pub method: InterpolationMethod,
+}
+
+/// Derivadas segundas do spline cúbico natural pelos pontos (xs, ys)
+fn spline_second_derivatives(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut second = vec![0.0; n];
+    if n < 3 {
+        return second;
+    }
+    // Sistema tridiagonal resolvido pelo algoritmo de Thomas
+    let mut diagonal = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        let h0 = xs[i] - xs[i - 1];
+        let h1 = xs[i + 1] - xs[i];
+        diagonal[i] = 2.0 * (h0 + h1);
+        rhs[i] = 6.0 * ((ys[i + 1] - ys[i]) / h1 - (ys[i] - ys[i - 1]) / h0);
+        if i > 1 {
+            let factor = h0 / diagonal[i - 1];
+            diagonal[i] -= factor * h0;
+            rhs[i] -= factor * rhs[i - 1];
+        }
+    }
+    for i in (1..n - 1).rev() {
+        let h1 = xs[i + 1] - xs[i];
+        second[i] = (rhs[i] - h1 * second[i + 1]) / diagonal[i];
+    }
+    second
+}
+
+/// Interpola (xs, ys) em x, mantendo os extremos fora do intervalo
+fn interpolate(xs: &[f64], ys: &[f64], x: f64, method: InterpolationMethod) -> f64 {
+    let n = xs.len();
+    if x <= xs[0] {
+        return ys[0];
+    }
+    if x >= xs[n - 1] {
+        return ys[n - 1];
+    }
+    let i = xs.partition_point(|&v| v <= x) - 1;
+    let h = xs[i + 1] - xs[i];
+    let t = (x - xs[i]) / h;
+    let linear = ys[i] + t * (ys[i + 1] - ys[i]);
+    match method {
+        InterpolationMethod::Linear => linear,
+        InterpolationMethod::Cubic => {
+            let second = spline_second_derivatives(xs, ys);
+            linear - h * h * t * (1.0 - t) * ((2.0 - t) * second[i] + (1.0 + t) * second[i + 1]) / 6.0
+        }
+    }
+}
+
+impl TableAxis {
+    fn validate(&self, label: &str) -> Result<(), String> {
+        if self.parameter.trim().is_empty() {
+            return Err(format!("Eixo {} sem parâmetro de entrada", label));
+        }
+        if self.values.len() < 2 {
+            return Err(format!("Eixo {} ({}) requer ao menos 2 pontos", label, self.parameter));
+        }
+        if self.values.iter().any(|v| !v.is_finite()) || self.values.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(format!("Pontos do eixo {} ({}) devem ser finitos e estritamente crescentes", label, self.parameter));
+        }
+        units::parse_unit(&self.unit)
+            .map(|_| ())
+            .map_err(|e| format!("Eixo {} ({}): {}", label, self.parameter, e))
+    }
+}
+
+impl LookupTable {
+    /// Cria uma tabela 1D
+    pub fn new_1d(name: &str, x: TableAxis, values: Vec<f64>, result_unit: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::new(),
+            x,
+            y: None,
+            values,
+            result_unit: result_unit.to_string(),
+            method: InterpolationMethod::default(),
+        }
+    }
+
+    /// Cria uma tabela 2D com os valores por linhas de x
+    pub fn new_2d(name: &str, x: TableAxis, y: TableAxis, values: Vec<f64>, result_unit: &str) -> Self {
+        Self {
+            y: Some(y),
+            ..Self::new_1d(name, x, values, result_unit)
+        }
+    }
+
+    /// Lê uma tabela de JSON e a valida
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let table: LookupTable = serde_json::from_str(json)
+            .map_err(|e| format!("Erro ao analisar JSON da tabela: {}", e))?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Valida os eixos, o número de valores e as unidades
+    pub fn validate(&self) -> Result<(), String> {
+        self.x.validate("x")?;
+        let expected = match &self.y {
+            Some(y) => {
+                y.validate("y")?;
+                if y.parameter == self.x.parameter {
+                    return Err(format!("Eixos da tabela '{}' usam o mesmo parâmetro", self.name));
+                }
+                self.x.values.len() * y.values.len()
+            }
+            None => self.x.values.len(),
+        };
+        if self.values.len() != expected {
+            return Err(format!(
+                "Tabela '{}' com {} valores, esperado {}",
+                self.name,
+                self.values.len(),
+                expected
+            ));
+        }
+        if self.values.iter().any(|v| !v.is_finite()) {
+            return Err(format!("Valores da tabela '{}' devem ser finitos", self.name));
+        }
+        units::parse_unit(&self.result_unit).map(|_| ())
+    }
+
+    /// Nomes dos parâmetros de entrada
+    pub fn parameter_names(&self) -> Vec<String> {
+        std::iter::once(&self.x).chain(self.y.as_ref()).map(|axis| axis.parameter.clone()).collect()
+    }
+
+    /// Interpola a tabela nos pontos (x) ou (x, y)
+    pub fn interpolate(&self, x: f64, y: Option<f64>) -> f64 {
+        match (&self.y, y) {
+            (Some(y_axis), Some(y)) => {
+                let ny = y_axis.values.len();
+                let column: Vec<f64> = self.values.chunks(ny)
+                    .map(|row| interpolate(&y_axis.values, row, y, self.method))
+                    .collect();
+                interpolate(&self.x.values, &column, x, self.method)
+            }
+            _ => interpolate(&self.x.values, &self.values, x, self.method),
+        }
+    }
+
+    /// Avalia a tabela com os parâmetros fornecidos (todos os eixos são obrigatórios)
+    pub fn evaluate(&self, parameters: &HashMap<String, ParameterValue>) -> Result<f64, String> {
+        let coordinate = |axis: &TableAxis| {
+            parameters.get(&axis.parameter)
+                .and_then(ParameterValue::as_f64)
+                .ok_or_else(|| format!("Parâmetro numérico '{}' ausente para a tabela '{}'", axis.parameter, self.name))
+        };
+        let x = coordinate(&self.x)?;
+        let y = self.y.as_ref().map(coordinate).transpose()?;
+        Ok(self.interpolate(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn axis(parameter: &str, values: &[f64]) -> TableAxis {
+        TableAxis { parameter: parameter.to_string(), unit: String::new(), values: values.to_vec() }
+    }
+
+    #[test]
+    fn test_lookup_table_interpolation() {
+        // Tabela 1D de y = x²: linear nos trechos, spline exato nos nós e limitado nos extremos
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let mut table = LookupTable::new_1d("quadrado", axis("x", &xs), xs.iter().map(|x| x * x).collect(), "");
+        table.validate().unwrap();
+        assert!((table.interpolate(1.5, None) - 2.5).abs() < 1e-12);
+        assert_eq!(table.interpolate(-1.0, None), 0.0);
+        assert_eq!(table.interpolate(10.0, None), 16.0);
+        table.method = InterpolationMethod::Cubic;
+        assert!((table.interpolate(3.0, None) - 9.0).abs() < 1e-12);
+        assert!((table.interpolate(2.5, None) - 6.25).abs() < 0.05);
+
+        // Tabela 2D de z = x + 10·y: bilinear exata
+        let table = LookupTable::new_2d(
+            "plano",
+            axis("x", &[0.0, 1.0, 2.0]),
+            axis("y", &[0.0, 1.0]),
+            vec![0.0, 10.0, 1.0, 11.0, 2.0, 12.0],
+            "",
+        );
+        table.validate().unwrap();
+        let mut params = HashMap::new();
+        params.insert("x".to_string(), ParameterValue::Float(1.5));
+        params.insert("y".to_string(), ParameterValue::Float(0.25));
+        assert!((table.evaluate(&params).unwrap() - 4.0).abs() < 1e-12);
+        params.remove("y");
+        assert!(table.evaluate(&params).is_err());
+
+        // JSON editável e validado
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(LookupTable::from_json(&json).unwrap().values.len(), 6);
+        assert!(LookupTable::from_json(&json.replace("12.0", "12.0,13.0")).is_err());
+    }
+}
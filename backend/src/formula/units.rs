@@ -0,0 +1,314 @@
+// Análise dimensional das unidades declaradas nas fórmulas
+//
+// As unidades são escritas como texto (ex.: "W/(m·K)", "kg/m³", "W/(m·K)/100°C") e
+// reduzidas às dimensões de base do SI usadas pelo simulador: comprimento, massa, tempo,
+// temperatura, quantidade de matéria e corrente elétrica. Prefixos e fatores numéricos não alteram a
+// dimensão; °C e K são tratados como a mesma dimensão de temperatura (diferenças).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Div, Mul};
+
+/// Estrutura que representa a dimensão de uma grandeza pelos expoentes das dimensões de base
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Dimension {
+    /// Expoente do comprimento (m)
+    pub length: i32,
+    /// Expoente da massa (kg)
+    pub mass: i32,
+    /// Expoente do tempo (s)
+    pub time: i32,
+    /// Expoente da temperatura (K)
+    pub temperature: i32,
+    /// Expoente da quantidade de matéria (mol)
+    pub amount: i32,
+    /// Expoente da corrente elétrica (A)
+    #[serde(default)]
+    pub current: i32,
+}
+
+impl Dimension {
+    /// Grandeza adimensional
+    pub const DIMENSIONLESS: Dimension = Dimension::new(0, 0, 0, 0, 0);
+
+    /// Cria uma dimensão a partir dos expoentes (m, kg, s, K, mol)
+    pub const fn new(length: i32, mass: i32, time: i32, temperature: i32, amount: i32) -> Self {
+        Self { length, mass, time, temperature, amount, current: 0 }
+    }
+
+    /// Dimensão com o expoente da corrente elétrica (A) substituído
+    pub const fn with_current(self, current: i32) -> Self {
+        Self { current, ..self }
+    }
+
+    /// Potência inteira da dimensão
+    pub fn powi(self, exponent: i32) -> Dimension {
+        Dimension::new(
+            self.length * exponent,
+            self.mass * exponent,
+            self.time * exponent,
+            self.temperature * exponent,
+            self.amount * exponent,
+        )
+        .with_current(self.current * exponent)
+    }
+
+    /// Verifica se a grandeza é adimensional
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Dimension::DIMENSIONLESS
+    }
+}
+
+/// Produto de duas dimensões
+impl Mul for Dimension {
+    type Output = Dimension;
+
+    fn mul(self, other: Dimension) -> Dimension {
+        Dimension::new(
+            self.length + other.length,
+            self.mass + other.mass,
+            self.time + other.time,
+            self.temperature + other.temperature,
+            self.amount + other.amount,
+        )
+        .with_current(self.current + other.current)
+    }
+}
+
+/// Quociente de duas dimensões
+impl Div for Dimension {
+    type Output = Dimension;
+
+    fn div(self, other: Dimension) -> Dimension {
+        self * other.powi(-1)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let factors: Vec<String> = [
+            ("m", self.length),
+            ("kg", self.mass),
+            ("s", self.time),
+            ("K", self.temperature),
+            ("mol", self.amount),
+            ("A", self.current),
+        ]
+        .iter()
+        .filter(|(_, exponent)| *exponent != 0)
+        .map(|(symbol, exponent)| {
+            if *exponent == 1 { symbol.to_string() } else { format!("{}^{}", symbol, exponent) }
+        })
+        .collect();
+        if factors.is_empty() {
+            write!(f, "1")
+        } else {
+            write!(f, "{}", factors.join("·"))
+        }
+    }
+}
+
+/// Dimensão de um símbolo de unidade conhecido
+fn unit_dimension(symbol: &str) -> Option<Dimension> {
+    let watt = Dimension::new(2, 1, -3, 0, 0);
+    let joule = Dimension::new(2, 1, -2, 0, 0);
+    let volt = watt.with_current(-1);
+    let dimension = match symbol {
+        "m" | "cm" | "mm" | "km" => Dimension::new(1, 0, 0, 0, 0),
+        "kg" | "g" | "t" => Dimension::new(0, 1, 0, 0, 0),
+        "s" | "min" | "h" => Dimension::new(0, 0, 1, 0, 0),
+        "K" | "°C" => Dimension::new(0, 0, 0, 1, 0),
+        "mol" | "kmol" => Dimension::new(0, 0, 0, 0, 1),
+        "W" | "kW" | "MW" => watt,
+        "J" | "kJ" | "MJ" => joule,
+        "N" => Dimension::new(1, 1, -2, 0, 0),
+        "Pa" | "kPa" | "MPa" | "bar" => Dimension::new(-1, 1, -2, 0, 0),
+        "A" | "kA" => Dimension::DIMENSIONLESS.with_current(1),
+        "V" | "kV" => volt,
+        "Ω" | "ohm" => volt.with_current(-2),
+        "S" => volt.powi(-1).with_current(2),
+        "%" | "rad" | "°" => Dimension::DIMENSIONLESS,
+        _ => return None,
+    };
+    Some(dimension)
+}
+
+/// Converte um expoente sobrescrito (ex.: "⁻²") ou após '^' em inteiro
+fn superscript_digit(c: char) -> Option<i32> {
+    "⁰¹²³⁴⁵⁶⁷⁸⁹".chars().position(|s| s == c).map(|d| d as i32)
+}
+
+/// Analisador descendente das expressões de unidade
+struct UnitParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    source: &'a str,
+}
+
+impl<'a> UnitParser<'a> {
+    fn error(&self, message: &str) -> String {
+        format!("Unidade inválida '{}': {}", self.source, message)
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    /// Expressão: grupos separados por '·', '*' ou '/'; a divisão se aplica ao grupo seguinte
+    fn expression(&mut self) -> Result<Dimension, String> {
+        let mut dimension = self.group()?;
+        loop {
+            self.skip_spaces();
+            match self.chars.peek() {
+                Some('·') | Some('*') | Some('.') => {
+                    self.chars.next();
+                    dimension = dimension * self.group()?;
+                }
+                Some('/') => {
+                    self.chars.next();
+                    dimension = dimension / self.group()?;
+                }
+                _ => return Ok(dimension),
+            }
+        }
+    }
+
+    /// Grupo: fatores justapostos (ex.: "100°C"), multiplicados entre si
+    fn group(&mut self) -> Result<Dimension, String> {
+        self.skip_spaces();
+        let mut dimension = self.factor()?;
+        loop {
+            match self.chars.peek() {
+                Some(&c) if c == '(' || c.is_ascii_digit() || c.is_alphabetic() || c == '°' || c == '%' => {
+                    dimension = dimension * self.factor()?;
+                }
+                _ => return Ok(dimension),
+            }
+        }
+    }
+
+    /// Fator: símbolo, número ou expressão entre parênteses, seguido de expoente opcional
+    fn factor(&mut self) -> Result<Dimension, String> {
+        let base = match self.chars.peek() {
+            Some('(') => {
+                self.chars.next();
+                let inner = self.expression()?;
+                self.skip_spaces();
+                if self.chars.next() != Some(')') {
+                    return Err(self.error("parêntese não fechado"));
+                }
+                inner
+            }
+            Some(c) if c.is_ascii_digit() => {
+                while self.chars.peek().is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+                    self.chars.next();
+                }
+                Dimension::DIMENSIONLESS
+            }
+            Some(&c) if c.is_alphabetic() || c == '°' || c == '%' => {
+                let mut symbol = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_alphabetic() || (symbol.is_empty() && (c == '°' || c == '%')) {
+                        symbol.push(c);
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                unit_dimension(&symbol).ok_or_else(|| self.error(&format!("símbolo desconhecido '{}'", symbol)))?
+            }
+            _ => return Err(self.error("fator esperado")),
+        };
+        Ok(base.powi(self.exponent()?))
+    }
+
+    /// Expoente sobrescrito ("m³", "s⁻¹") ou com circunflexo ("m^3", "s^-1"); padrão 1
+    fn exponent(&mut self) -> Result<i32, String> {
+        if self.chars.peek() == Some(&'^') {
+            self.chars.next();
+            let mut text = String::new();
+            while let Some(&c) = self.chars.peek() {
+                if c.is_ascii_digit() || (text.is_empty() && c == '-') {
+                    text.push(c);
+                    self.chars.next();
+                } else {
+                    break;
+                }
+            }
+            return text.parse().map_err(|_| self.error("expoente inválido"));
+        }
+        let mut sign = 1;
+        let mut value = None;
+        while let Some(&c) = self.chars.peek() {
+            if c == '⁻' && value.is_none() {
+                sign = -1;
+            } else if let Some(digit) = superscript_digit(c) {
+                value = Some(value.unwrap_or(0) * 10 + digit);
+            } else {
+                break;
+            }
+            self.chars.next();
+        }
+        match value {
+            Some(value) => Ok(sign * value),
+            None if sign < 0 => Err(self.error("expoente inválido")),
+            None => Ok(1),
+        }
+    }
+}
+
+/// Reduz uma unidade escrita como texto às dimensões de base
+///
+/// Texto vazio, "1" ou "-" representam grandezas adimensionais.
+pub fn parse_unit(unit: &str) -> Result<Dimension, String> {
+    let trimmed = unit.trim();
+    if trimmed.is_empty() || trimmed == "-" {
+        return Ok(Dimension::DIMENSIONLESS);
+    }
+    let mut parser = UnitParser { chars: trimmed.chars().peekable(), source: unit };
+    let dimension = parser.expression()?;
+    parser.skip_spaces();
+    if parser.chars.peek().is_some() {
+        return Err(parser.error("caracteres inesperados"));
+    }
+    Ok(dimension)
+}
+
+/// Verifica se duas unidades têm a mesma dimensão
+pub fn check_compatible(unit: &str, expected: &str) -> Result<(), String> {
+    let actual = parse_unit(unit)?;
+    let required = parse_unit(expected)?;
+    if actual != required {
+        return Err(format!(
+            "Unidade '{}' ({}) incompatível com '{}' ({})",
+            unit, actual, expected, required
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_units_to_base_dimensions() {
+        assert_eq!(parse_unit("W/(m·K)").unwrap(), Dimension::new(1, 1, -3, -1, 0));
+        assert_eq!(parse_unit("J/(kg·K)").unwrap(), Dimension::new(2, 0, -2, -1, 0));
+        assert_eq!(parse_unit("kg/m³").unwrap(), parse_unit("kg*m^-3").unwrap());
+        assert_eq!(parse_unit("W/m³").unwrap(), parse_unit("J/(m³·s)").unwrap());
+        assert_eq!(parse_unit("W/(m·K)/100°C").unwrap(), Dimension::new(1, 1, -3, -2, 0));
+        assert_eq!(parse_unit("W/(m·K)/(100°C)²").unwrap(), Dimension::new(1, 1, -3, -3, 0));
+        assert_eq!(parse_unit("s⁻¹").unwrap(), Dimension::new(0, 0, -1, 0, 0));
+        assert!(parse_unit("").unwrap().is_dimensionless());
+        assert!(parse_unit("%").unwrap().is_dimensionless());
+        assert_eq!(parse_unit("S/m").unwrap(), parse_unit("1/(Ω·m)").unwrap());
+        assert_eq!(parse_unit("V·A").unwrap(), parse_unit("W").unwrap());
+
+        assert!(parse_unit("W/(m·K").is_err());
+        assert!(parse_unit("furlong").is_err());
+        assert!(check_compatible("W/(m²·K)", "W/(m·K)").is_err());
+        assert!(check_compatible("°C", "K").is_ok());
+    }
+}
@@ -1,15 +1,12 @@
 // Biblioteca principal do simulador de fornalha de plasma
 // Expõe a API FFI para o frontend Flutter
 
-mod simulation;
-mod plugins;
-mod formula;
-mod errors;
-mod logging;
+pub mod simulation;
+pub mod formula;
 mod ffi;
 
-use std::ffi::{c_void, CStr, CString};
-use std::os::raw::{c_char, c_int};
+// As funções FFI (`initialize_simulation`, `run_simulation`, `get_last_error`, ...)
+// ficam em `ffi::bindings`
 
 // Inicializa o logger
 pub fn init_logger() {
@@ -22,71 +19,3 @@ pub fn init_logger() {
 pub struct SimulationContext {
     // Será implementado conforme o desenvolvimento das features
 }
-
-// API FFI
-
-/// Inicializa a simulação e retorna um ponteiro para o contexto
-#[no_mangle]
-pub extern "C" fn initialize_simulation() -> *mut c_void {
-    log::info!("Inicializando simulação");
-    
-    // Por enquanto, retorna um ponteiro nulo
-    // Será implementado na feature de simulação básica
-    std::ptr::null_mut()
-}
-
-/// Define os parâmetros da simulação
-#[no_mangle]
-pub extern "C" fn set_simulation_parameters(
-    _ctx: *mut c_void,
-    _params: *const c_void,
-) -> c_int {
-    // Será implementado na feature de simulação básica
-    0 // Sucesso
-}
-
-/// Executa a simulação
-#[no_mangle]
-pub extern "C" fn run_simulation(
-    _ctx: *mut c_void,
-    _progress_callback: extern "C" fn(f32),
-) -> c_int {
-    // Será implementado na feature de simulação básica
-    0 // Sucesso
-}
-
-/// Obtém os dados de temperatura para um passo de tempo específico
-#[no_mangle]
-pub extern "C" fn get_temperature_data(
-    _ctx: *mut c_void,
-    _time_step: c_int,
-    _buffer: *mut f32,
-    _buffer_size: usize,
-) -> c_int {
-    // Será implementado na feature de simulação básica
-    0 // Sucesso
-}
-
-/// Libera os recursos da simulação
-#[no_mangle]
-pub extern "C" fn destroy_simulation(_ctx: *mut c_void) {
-    // Será implementado na feature de simulação básica
-    log::info!("Finalizando simulação");
-}
-
-/// Obtém a última mensagem de erro
-#[no_mangle]
-pub extern "C" fn get_last_error() -> *const c_char {
-    let error_msg = CString::new("Nenhum erro").unwrap();
-    error_msg.into_raw()
-}
-
-/// Libera a memória de uma string retornada por get_last_error
-#[no_mangle]
-pub extern "C" fn free_error_message(message: *mut c_char) {
-    if !message.is_null() {
-        unsafe {
-            let _ = CString::from_raw(message);
-        }
-    }
-}
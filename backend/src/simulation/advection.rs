@@ -0,0 +1,253 @@
+// Implementação do transporte advectivo por um campo de velocidade prescrito (espaço livre do forno)
+//
+// O campo de velocidade é dado no plano r-z (perfil analítico ou importado de arquivo) e
+// o termo -ρ·(u·∇h) é discretizado por diferenças a montante (upwind) com a entalpia do
+// passo anterior, entrando na equação da entalpia como termo fonte volumétrico. Nas
+// fronteiras de entrada o gradiente é nulo (não há fluxo advectivo vindo de fora).
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::simulation::mesh::CylindricalMesh;
+
+/// Estrutura que representa uma amostra de velocidade importada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VelocitySample {
+    /// Posição radial (m)
+    pub r: f64,
+    /// Posição axial (m)
+    pub z: f64,
+    /// Velocidade radial (m/s)
+    pub radial_velocity: f64,
+    /// Velocidade axial (m/s)
+    pub axial_velocity: f64,
+}
+
+/// Enumeração que representa o campo de velocidade prescrito
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum VelocityField {
+    /// Corrente ascendente com perfil parabólico: u_z = w₀·(1 - (r/R)²)
+    Updraft { centerline_velocity: f64 },
+    /// Escoamento em redemoinho (vórtice de Rankine) com ascensão uniforme
+    ///
+    /// Em simetria axial a componente tangencial não transporta calor; apenas
+    /// `axial_velocity` entra no termo advectivo. `swirl_velocity` é a velocidade
+    /// tangencial máxima, em `core_radius`, mantida para pós-processamento.
+    Swirl { swirl_velocity: f64, core_radius: f64, axial_velocity: f64 },
+    /// Amostras importadas, atribuídas aos nós pela amostra mais próxima
+    Imported { samples: Vec<VelocitySample> },
+}
+
+impl VelocityField {
+    /// Carrega amostras de um arquivo CSV com colunas r, z, u_r, u_z (m, m/s)
+    ///
+    /// Linhas vazias, comentários (`#`) e um cabeçalho não numérico são ignorados.
+    pub fn from_csv(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Erro ao ler campo de velocidade {}: {}", path.display(), e))?;
+        let mut samples = Vec::new();
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let values: Result<Vec<f64>, _> = line.split([',', ';']).map(|v| v.trim().parse::<f64>()).collect();
+            match values {
+                Ok(values) if values.len() == 4 => samples.push(VelocitySample {
+                    r: values[0],
+                    z: values[1],
+                    radial_velocity: values[2],
+                    axial_velocity: values[3],
+                }),
+                Ok(_) => return Err(format!("Linha {} do campo de velocidade deve ter 4 colunas (r, z, u_r, u_z)", line_number + 1)),
+                Err(_) if samples.is_empty() => continue,
+                Err(_) => return Err(format!("Valor inválido na linha {} do campo de velocidade", line_number + 1)),
+            }
+        }
+        if samples.is_empty() {
+            return Err(format!("Campo de velocidade sem amostras: {}", path.display()));
+        }
+        Ok(VelocityField::Imported { samples })
+    }
+
+    /// Valida o campo
+    pub fn validate(&self) -> Result<(), String> {
+        let finite = match self {
+            VelocityField::Updraft { centerline_velocity } => centerline_velocity.is_finite(),
+            VelocityField::Swirl { swirl_velocity, core_radius, axial_velocity } => {
+                if *core_radius <= 0.0 {
+                    return Err("Raio do núcleo do redemoinho deve ser positivo".to_string());
+                }
+                swirl_velocity.is_finite() && axial_velocity.is_finite()
+            }
+            VelocityField::Imported { samples } => {
+                if samples.is_empty() {
+                    return Err("Campo de velocidade importado sem amostras".to_string());
+                }
+                samples.iter().all(|s| s.r.is_finite() && s.z.is_finite() && s.radial_velocity.is_finite() && s.axial_velocity.is_finite())
+            }
+        };
+        if !finite {
+            return Err("Campo de velocidade com valores inválidos".to_string());
+        }
+        Ok(())
+    }
+
+    /// Componentes (u_r, u_z) em um ponto do domínio de raio `radius` (m/s)
+    pub fn velocity_at(&self, r: f64, z: f64, radius: f64) -> (f64, f64) {
+        match self {
+            VelocityField::Updraft { centerline_velocity } => {
+                let ratio = (r / radius).min(1.0);
+                (0.0, centerline_velocity * (1.0 - ratio * ratio))
+            }
+            VelocityField::Swirl { axial_velocity, .. } => (0.0, *axial_velocity),
+            VelocityField::Imported { samples } => {
+                samples.iter()
+                    .min_by(|a, b| {
+                        let da = (a.r - r).powi(2) + (a.z - z).powi(2);
+                        let db = (b.r - r).powi(2) + (b.z - z).powi(2);
+                        da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .map_or((0.0, 0.0), |nearest| (nearest.radial_velocity, nearest.axial_velocity))
+            }
+        }
+    }
+
+    /// Velocidade tangencial em um raio (m/s); nula exceto no redemoinho
+    pub fn tangential_velocity_at(&self, r: f64) -> f64 {
+        match self {
+            VelocityField::Swirl { swirl_velocity, core_radius, .. } => {
+                if r <= *core_radius {
+                    swirl_velocity * r / core_radius
+                } else {
+                    swirl_velocity * core_radius / r.max(1e-12)
+                }
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Estrutura que representa a configuração do transporte advectivo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvectionConfig {
+    /// Campo de velocidade prescrito
+    pub velocity: VelocityField,
+    /// Faixa axial (m) em que o escoamento existe (ex.: espaço livre acima da carga);
+    /// None aplica em todo o domínio
+    #[serde(default)]
+    pub axial_range: Option<(f64, f64)>,
+}
+
+impl AdvectionConfig {
+    /// Cria a configuração aplicada a todo o domínio
+    pub fn new(velocity: VelocityField) -> Self {
+        Self { velocity, axial_range: None }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        self.velocity.validate()?;
+        if let Some((start, end)) = self.axial_range {
+            if end <= start || !start.is_finite() || !end.is_finite() {
+                return Err("Faixa axial do escoamento deve ter fim maior que o início".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Componentes (u_r, u_z) nos nós da malha (m/s), nulas fora da faixa axial
+    pub fn velocity_at_nodes(&self, mesh: &CylindricalMesh) -> (Array2<f64>, Array2<f64>) {
+        let mut radial = Array2::<f64>::zeros((mesh.nr, mesh.nz));
+        let mut axial = Array2::<f64>::zeros((mesh.nr, mesh.nz));
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                let (r, z) = (mesh.r_coords[i], mesh.z_coords[j]);
+                if self.axial_range.is_some_and(|(start, end)| z < start || z > end) {
+                    continue;
+                }
+                let (u_r, u_z) = self.velocity.velocity_at(r, z, mesh.radius);
+                radial[[i, j]] = u_r;
+                axial[[i, j]] = u_z;
+            }
+        }
+        (radial, axial)
+    }
+}
+
+/// Derivada a montante de um campo nodal na direção de índice variável (diferença atrasada
+/// para velocidade positiva, adiantada para negativa; nula sem vizinho a montante)
+fn upwind_derivative(velocity: f64, center: f64, previous: Option<(f64, f64)>, next: Option<(f64, f64)>) -> f64 {
+    let neighbour = if velocity > 0.0 { previous } else { next };
+    match neighbour {
+        Some((value, spacing)) if velocity > 0.0 => (center - value) / spacing,
+        Some((value, spacing)) => (value - center) / spacing,
+        None => 0.0,
+    }
+}
+
+/// Calcula o termo fonte advectivo -ρ·(u·∇h) (W/m³) com diferenças a montante
+pub fn calculate_advection_source(
+    mesh: &CylindricalMesh,
+    radial_velocity: &Array2<f64>,
+    axial_velocity: &Array2<f64>,
+    enthalpy: &Array2<f64>,
+    density: &Array2<f64>,
+) -> Array2<f64> {
+    Array2::from_shape_fn((mesh.nr, mesh.nz), |(i, j)| {
+        let (u_r, u_z) = (radial_velocity[[i, j]], axial_velocity[[i, j]]);
+        if u_r == 0.0 && u_z == 0.0 {
+            return 0.0;
+        }
+        let h = enthalpy[[i, j]];
+        let west = if i > 0 { Some((enthalpy[[i - 1, j]], mesh.radial_spacing(i - 1))) } else { None };
+        let east = if i + 1 < mesh.nr { Some((enthalpy[[i + 1, j]], mesh.radial_spacing(i))) } else { None };
+        let south = if j > 0 { Some((enthalpy[[i, j - 1]], mesh.axial_spacing(j - 1))) } else { None };
+        let north = if j + 1 < mesh.nz { Some((enthalpy[[i, j + 1]], mesh.axial_spacing(j))) } else { None };
+        let transport = u_r * upwind_derivative(u_r, h, west, east) + u_z * upwind_derivative(u_z, h, south, north);
+        -density[[i, j]] * transport
+    })
+}
+
+/// Taxa de Courant de cada nó, |u_r|/Δr + |u_z|/Δz (1/s); o passo explícito estável é limitado por 1/taxa
+pub fn courant_rate(mesh: &CylindricalMesh, radial_velocity: &Array2<f64>, axial_velocity: &Array2<f64>, i: usize, j: usize) -> f64 {
+    let dr = if i + 1 < mesh.nr { mesh.radial_spacing(i) } else { mesh.radial_spacing(i - 1) };
+    let dz = if j + 1 < mesh.nz { mesh.axial_spacing(j) } else { mesh.axial_spacing(j - 1) };
+    radial_velocity[[i, j]].abs() / dr + axial_velocity[[i, j]].abs() / dz
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_upwind_advection_of_updraft() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 5, 11, 4);
+        let mut config = AdvectionConfig::new(VelocityField::Updraft { centerline_velocity: 2.0 });
+        config.axial_range = Some((0.5, 1.0));
+        assert!(config.validate().is_ok());
+        let (radial, axial) = config.velocity_at_nodes(&mesh);
+        assert_eq!(axial[[0, 2]], 0.0);
+        assert_relative_eq!(axial[[0, 8]], 2.0, epsilon = 1e-12);
+        assert_eq!(axial[[4, 8]], 0.0);
+
+        // Entalpia crescente com a altura: o escoamento ascendente traz gás mais frio de baixo
+        let enthalpy = Array2::from_shape_fn((5, 11), |(_, j)| 1000.0 * j as f64);
+        let density = Array2::from_elem((5, 11), 0.5);
+        let source = calculate_advection_source(&mesh, &radial, &axial, &enthalpy, &density);
+        assert_relative_eq!(source[[0, 8]], -0.5 * 2.0 * 1000.0 / mesh.axial_spacing(7), epsilon = 1e-9);
+        assert_eq!(source[[0, 2]], 0.0);
+        assert!(courant_rate(&mesh, &radial, &axial, 0, 8) > 0.0);
+
+        // Campo importado pela amostra mais próxima
+        let path = std::env::temp_dir().join(format!("velocity_field_{}.csv", std::process::id()));
+        fs::write(&path, "r,z,u_r,u_z\n0.0,0.0,0.1,1.0\n0.5,1.0,-0.2,3.0\n").unwrap();
+        let imported = VelocityField::from_csv(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!(imported.velocity_at(0.4, 0.9, 0.5), (-0.2, 3.0));
+        assert!(VelocityField::Swirl { swirl_velocity: 5.0, core_radius: 0.0, axial_velocity: 1.0 }.validate().is_err());
+    }
+}
@@ -0,0 +1,192 @@
+// Implementação de alarmes operacionais avaliados sobre os resultados da simulação
+
+use ndarray::s;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::metrics::molten_pool_temperature;
+use crate::simulation::solver::SimulationResults;
+
+/// Enumeração que representa a severidade de um alarme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlarmSeverity {
+    /// Informativo
+    Info,
+    /// Atenção
+    Warning,
+    /// Crítico
+    Critical,
+}
+
+/// Enumeração que representa a condição de disparo de um alarme
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AlarmCondition {
+    /// Temperatura máxima acima do limite (°C)
+    MaxTemperatureAbove(f64),
+    /// Temperatura mínima abaixo do limite (°C)
+    MinTemperatureBelow(f64),
+    /// Temperatura média abaixo do limite (°C)
+    MeanTemperatureBelow(f64),
+    /// Taxa de aquecimento local acima do limite (°C/h)
+    HeatingRateAbove(f64),
+    /// Taxa de resfriamento local acima do limite (°C/h)
+    CoolingRateAbove(f64),
+    /// Temperatura média mássica do banho fundido acima da temperatura de vazamento
+    TapTemperatureReached {
+        /// Temperatura de vazamento (°C)
+        tapping_temperature: f64,
+        /// Margem de confiança (°C)
+        confidence_margin: f64,
+        /// Fração fundida mínima para pertencer ao banho (0-1)
+        melt_fraction_threshold: f64,
+    },
+}
+
+/// Estrutura que representa uma regra de alarme
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlarmRule {
+    /// Identificador da regra
+    pub id: String,
+    /// Descrição da regra
+    pub description: String,
+    /// Condição de disparo
+    pub condition: AlarmCondition,
+    /// Severidade do alarme
+    pub severity: AlarmSeverity,
+}
+
+/// Estrutura que representa um alarme disparado durante a simulação
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlarmEvent {
+    /// Identificador da regra que disparou
+    pub rule_id: String,
+    /// Descrição da regra
+    pub description: String,
+    /// Severidade do alarme
+    pub severity: AlarmSeverity,
+    /// Tempo do primeiro disparo (s)
+    pub time: f64,
+    /// Passo de tempo do primeiro disparo
+    pub step: usize,
+    /// Valor observado que violou o limite
+    pub value: f64,
+}
+
+impl AlarmRule {
+    /// Cria uma nova regra de alarme
+    pub fn new(id: &str, description: &str, condition: AlarmCondition, severity: AlarmSeverity) -> Self {
+        Self {
+            id: id.to_string(),
+            description: description.to_string(),
+            condition,
+            severity,
+        }
+    }
+}
+
+/// Avalia as regras de alarme sobre o histórico de resultados
+///
+/// Cada regra gera no máximo um evento, correspondente ao primeiro passo em que
+/// a condição foi violada.
+pub fn evaluate_alarms(rules: &[AlarmRule], results: &SimulationResults) -> Vec<AlarmEvent> {
+    let mut events = Vec::new();
+    let dt = results.parameters.time_step;
+    let available_steps = results.temperature.shape()[2];
+    let n_steps = (results.executed_steps + 1).min(available_steps);
+
+    for rule in rules {
+        for step in 0..n_steps {
+            let field = results.temperature.slice(s![.., .., step]);
+
+            let value = match rule.condition {
+                AlarmCondition::MaxTemperatureAbove(limit) => {
+                    let max = field.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                    if max > limit { Some(max) } else { None }
+                }
+                AlarmCondition::MinTemperatureBelow(limit) => {
+                    let min = field.iter().cloned().fold(f64::INFINITY, f64::min);
+                    if min < limit { Some(min) } else { None }
+                }
+                AlarmCondition::MeanTemperatureBelow(limit) => {
+                    let mean = field.iter().sum::<f64>() / field.len().max(1) as f64;
+                    if mean < limit { Some(mean) } else { None }
+                }
+                AlarmCondition::HeatingRateAbove(limit) | AlarmCondition::CoolingRateAbove(limit) => {
+                    if step == 0 || dt <= 0.0 {
+                        None
+                    } else {
+                        let previous = results.temperature.slice(s![.., .., step - 1]);
+                        let heating = matches!(rule.condition, AlarmCondition::HeatingRateAbove(_));
+                        let mut max_rate = f64::NEG_INFINITY;
+                        for (&t_new, &t_old) in field.iter().zip(previous.iter()) {
+                            // Taxa em °C/h
+                            let rate = (t_new - t_old) / dt * 3600.0;
+                            let rate = if heating { rate } else { -rate };
+                            max_rate = max_rate.max(rate);
+                        }
+                        if max_rate > limit { Some(max_rate) } else { None }
+                    }
+                }
+                AlarmCondition::TapTemperatureReached { tapping_temperature, confidence_margin, melt_fraction_threshold } => {
+                    molten_pool_temperature(results, step, melt_fraction_threshold)
+                        .map(|(temperature, _)| temperature)
+                        .filter(|&temperature| temperature >= tapping_temperature + confidence_margin)
+                }
+            };
+
+            if let Some(value) = value {
+                events.push(AlarmEvent {
+                    rule_id: rule.id.clone(),
+                    description: rule.description.clone(),
+                    severity: rule.severity,
+                    time: step as f64 * dt,
+                    step,
+                    value,
+                });
+                break;
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+    use crate::simulation::solver::SimulationParameters;
+
+    fn create_test_results() -> SimulationResults {
+        let mut params = SimulationParameters::new(1.0, 0.5, 3, 3);
+        params.time_step = 60.0;
+        params.time_steps = 2;
+
+        // Aquecimento de 0 -> 100 -> 400 °C em passos de 60 s
+        let mut temperature = Array3::<f64>::zeros((3, 3, 3));
+        temperature.slice_mut(s![.., .., 1]).fill(100.0);
+        temperature.slice_mut(s![.., .., 2]).fill(400.0);
+
+        SimulationResults::for_tests(params, temperature)
+    }
+
+    #[test]
+    fn test_alarm_first_violation() {
+        let results = create_test_results();
+        let rules = vec![
+            AlarmRule::new("hot", "Temperatura alta", AlarmCondition::MaxTemperatureAbove(300.0), AlarmSeverity::Critical),
+            AlarmRule::new("rate", "Aquecimento rápido", AlarmCondition::HeatingRateAbove(10000.0), AlarmSeverity::Warning),
+            AlarmRule::new("cool", "Resfriamento rápido", AlarmCondition::CoolingRateAbove(1.0), AlarmSeverity::Warning),
+        ];
+
+        let events = evaluate_alarms(&rules, &results);
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(events[0].rule_id, "hot");
+        assert_eq!(events[0].step, 2);
+
+        // 300 °C em 60 s = 18000 °C/h
+        assert_eq!(events[1].rule_id, "rate");
+        assert_eq!(events[1].step, 2);
+        assert!((events[1].value - 18000.0).abs() < 1e-6);
+    }
+}
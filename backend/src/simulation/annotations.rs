@@ -0,0 +1,217 @@
+// Implementação de anotações na linha do tempo da simulação (eventos e notas)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::simulation::alarms::{AlarmEvent, AlarmSeverity};
+
+/// Enumeração que representa o tipo de uma anotação da linha do tempo
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnnotationKind {
+    /// Vazamento do banho fundido
+    TapEvent,
+    /// Mudança de potência das tochas
+    PowerChange,
+    /// Alarme disparado
+    Alarm,
+    /// Carga de material adicionada
+    FeedAdded,
+    /// Nota livre
+    Note,
+    /// Tipo definido pelo usuário
+    Custom(String),
+}
+
+/// Enumeração que representa a origem de uma anotação
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnnotationSource {
+    /// Gerada automaticamente pelo solucionador
+    Solver,
+    /// Adicionada pelo usuário ou por código externo
+    User,
+}
+
+/// Estrutura que representa uma anotação com marca de tempo na linha do tempo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineAnnotation {
+    /// Tempo da anotação (s)
+    pub time: f64,
+    /// Passo de tempo correspondente (opcional)
+    #[serde(default)]
+    pub step: Option<usize>,
+    /// Tipo da anotação
+    pub kind: AnnotationKind,
+    /// Título curto exibido no marcador
+    pub title: String,
+    /// Descrição detalhada
+    #[serde(default)]
+    pub description: String,
+    /// Origem da anotação
+    #[serde(default = "default_annotation_source")]
+    pub source: AnnotationSource,
+    /// Metadados adicionais (chave-valor)
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// Origem padrão para anotações desserializadas sem origem explícita
+fn default_annotation_source() -> AnnotationSource {
+    AnnotationSource::User
+}
+
+impl TimelineAnnotation {
+    /// Cria uma nova anotação de usuário
+    pub fn new(time: f64, kind: AnnotationKind, title: &str) -> Self {
+        Self {
+            time,
+            step: None,
+            kind,
+            title: title.to_string(),
+            description: String::new(),
+            source: AnnotationSource::User,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Cria uma nova anotação gerada pelo solucionador em um passo de tempo
+    pub fn from_solver(step: usize, time: f64, kind: AnnotationKind, title: &str) -> Self {
+        Self {
+            step: Some(step),
+            source: AnnotationSource::Solver,
+            ..Self::new(time, kind, title)
+        }
+    }
+
+    /// Cria uma anotação a partir de um alarme disparado
+    pub fn from_alarm(event: &AlarmEvent) -> Self {
+        let severity = match event.severity {
+            AlarmSeverity::Info => "info",
+            AlarmSeverity::Warning => "warning",
+            AlarmSeverity::Critical => "critical",
+        };
+
+        Self::from_solver(event.step, event.time, AnnotationKind::Alarm, &event.description)
+            .with_description(&format!("Regra '{}' violada com valor {:.2}", event.rule_id, event.value))
+            .with_metadata("rule_id", &event.rule_id)
+            .with_metadata("severity", severity)
+    }
+
+    /// Define a descrição da anotação
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Adiciona um metadado à anotação
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Valida a anotação
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.time.is_finite() || self.time < 0.0 {
+            return Err(format!("Tempo da anotação inválido: {}", self.time));
+        }
+        if self.title.trim().is_empty() {
+            return Err("Título da anotação não pode ser vazio".to_string());
+        }
+        Ok(())
+    }
+
+    /// Retorna o rótulo legível do tipo da anotação
+    pub fn kind_label(&self) -> String {
+        match &self.kind {
+            AnnotationKind::TapEvent => "Vazamento".to_string(),
+            AnnotationKind::PowerChange => "Mudança de potência".to_string(),
+            AnnotationKind::Alarm => "Alarme".to_string(),
+            AnnotationKind::FeedAdded => "Carga adicionada".to_string(),
+            AnnotationKind::Note => "Nota".to_string(),
+            AnnotationKind::Custom(label) => label.clone(),
+        }
+    }
+}
+
+/// Insere uma anotação mantendo a lista ordenada por tempo
+///
+/// Anotações com o mesmo tempo preservam a ordem de inserção.
+pub fn insert_annotation(annotations: &mut Vec<TimelineAnnotation>, annotation: TimelineAnnotation) {
+    let index = annotations.partition_point(|a| a.time <= annotation.time);
+    annotations.insert(index, annotation);
+}
+
+/// Gera a seção de linha do tempo do relatório em Markdown
+pub fn render_annotations_markdown(annotations: &[TimelineAnnotation]) -> String {
+    let mut output = String::from("## Linha do Tempo\n\n");
+
+    if annotations.is_empty() {
+        output.push_str("Nenhum evento registrado.\n\n");
+        return output;
+    }
+
+    output.push_str("| Tempo (s) | Passo | Tipo | Evento | Origem |\n");
+    output.push_str("|---|---|---|---|---|\n");
+
+    for annotation in annotations {
+        let step = annotation.step.map_or("-".to_string(), |s| s.to_string());
+        let source = match annotation.source {
+            AnnotationSource::Solver => "Solucionador",
+            AnnotationSource::User => "Usuário",
+        };
+        let event = if annotation.description.is_empty() {
+            annotation.title.clone()
+        } else {
+            format!("{} — {}", annotation.title, annotation.description)
+        };
+
+        output.push_str(&format!(
+            "| {:.1} | {} | {} | {} | {} |\n",
+            annotation.time,
+            step,
+            annotation.kind_label(),
+            event.replace('|', "\\|"),
+            source
+        ));
+    }
+
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotations_sorted_and_rendered() {
+        let mut annotations = Vec::new();
+        insert_annotation(&mut annotations, TimelineAnnotation::new(600.0, AnnotationKind::TapEvent, "Vazamento 1"));
+        insert_annotation(&mut annotations, TimelineAnnotation::new(0.0, AnnotationKind::FeedAdded, "Carga inicial"));
+        insert_annotation(&mut annotations, TimelineAnnotation::from_solver(
+            5, 300.0, AnnotationKind::PowerChange, "Potência 100 -> 200 kW",
+        ));
+
+        let times: Vec<f64> = annotations.iter().map(|a| a.time).collect();
+        assert_eq!(times, vec![0.0, 300.0, 600.0]);
+        assert_eq!(annotations[1].source, AnnotationSource::Solver);
+
+        let alarm = AlarmEvent {
+            rule_id: "hot".to_string(),
+            description: "Temperatura alta".to_string(),
+            severity: AlarmSeverity::Critical,
+            time: 120.0,
+            step: 2,
+            value: 1650.0,
+        };
+        let alarm_annotation = TimelineAnnotation::from_alarm(&alarm);
+        assert_eq!(alarm_annotation.kind, AnnotationKind::Alarm);
+        assert_eq!(alarm_annotation.metadata.get("severity").map(String::as_str), Some("critical"));
+
+        let report = render_annotations_markdown(&annotations);
+        assert!(report.contains("Vazamento 1"));
+        assert!(report.contains("| 300.0 | 5 | Mudança de potência |"));
+
+        assert!(TimelineAnnotation::new(-1.0, AnnotationKind::Note, "x").validate().is_err());
+        assert!(TimelineAnnotation::new(1.0, AnnotationKind::Note, " ").validate().is_err());
+    }
+}
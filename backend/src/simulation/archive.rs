@@ -0,0 +1,306 @@
+// Implementação do arquivo de resultados com somas de verificação por quadro e do arquivo inteiro
+//
+// Layout (inteiros little-endian):
+//
+// | Bloco      | Conteúdo                                                                 |
+// |------------|--------------------------------------------------------------------------|
+// | Cabeçalho  | `PHTRES01`, versão u16, flags u16, nr u32, nz u32, quadros u32           |
+// | Metadados  | tamanho u32, `SimulationResults` sem históricos (MessagePack), CRC-32    |
+// | Quadros    | índice u32, campos f64 (T, H, fração fundida, fração vaporizada), CRC-32 |
+// | Final      | `PHTEND01`, CRC-32 de todos os bytes anteriores                          |
+//
+// Todos os quadros têm o mesmo tamanho, de modo que um quadro corrompido não impede a
+// leitura dos seguintes; quadros ausentes (arquivo truncado) encurtam o histórico.
+
+use ndarray::{s, Array2, Array3};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::simulation::solver::{PhaseChangeInfo, SimulationResults};
+
+/// Assinatura do início do arquivo
+pub const ARCHIVE_MAGIC: &[u8; 8] = b"PHTRES01";
+
+/// Assinatura do bloco final
+const ARCHIVE_END_MAGIC: &[u8; 8] = b"PHTEND01";
+
+/// Versão atual do formato
+pub const ARCHIVE_VERSION: u16 = 1;
+
+const FLAG_MELT_FRACTION: u16 = 1;
+const FLAG_VAPOR_FRACTION: u16 = 2;
+const FIXED_HEADER_LEN: usize = 8 + 2 + 2 + 4 + 4 + 4 + 4;
+
+/// Tabela do CRC-32 (polinômio refletido 0xEDB88320)
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut crc = n as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[n] = crc;
+        n += 1;
+    }
+    table
+};
+
+/// CRC-32 de um bloco de bytes (mesmo algoritmo do PNG e do zlib)
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// Estrutura que representa o relatório de integridade de um arquivo lido
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Soma de verificação do arquivo inteiro confere (e o bloco final está presente)
+    pub file_checksum_valid: bool,
+    /// Número de quadros declarados no cabeçalho
+    pub total_frames: usize,
+    /// Quadros com soma de verificação inválida (campos preenchidos com NaN)
+    pub corrupted_frames: Vec<usize>,
+    /// Quadros ausentes no fim do arquivo (arquivo truncado)
+    pub missing_frames: Vec<usize>,
+}
+
+impl IntegrityReport {
+    /// Indica se o arquivo foi lido sem nenhum dano
+    pub fn is_intact(&self) -> bool {
+        self.file_checksum_valid && self.corrupted_frames.is_empty() && self.missing_frames.is_empty()
+    }
+}
+
+/// Campos por quadro conforme as flags do cabeçalho
+fn field_count(flags: u16) -> usize {
+    2 + usize::from(flags & FLAG_MELT_FRACTION != 0) + usize::from(flags & FLAG_VAPOR_FRACTION != 0)
+}
+
+/// Serializa os resultados no formato de arquivo com somas de verificação
+pub fn encode_results_archive(results: &SimulationResults) -> Result<Vec<u8>, String> {
+    let (nr, nz, frames) = results.temperature.dim();
+    let melt = results.phase_change_info.as_ref().and_then(|info| info.melt_fraction.as_ref());
+    let vapor = results.phase_change_info.as_ref().and_then(|info| info.vapor_fraction.as_ref());
+    let flags = if melt.is_some() { FLAG_MELT_FRACTION } else { 0 } | if vapor.is_some() { FLAG_VAPOR_FRACTION } else { 0 };
+
+    // Metadados: resultados sem os históricos volumosos, gravados nos quadros
+    let metadata = SimulationResults {
+        parameters: results.parameters.clone(),
+        mesh: results.mesh.clone(),
+        temperature: Array3::zeros((0, 0, 0)),
+        enthalpy: Array3::zeros((0, 0, 0)),
+        execution_time: results.execution_time,
+        phase_change_info: results.phase_change_info.as_ref().map(|_| PhaseChangeInfo { melt_fraction: None, vapor_fraction: None }),
+        executed_steps: results.executed_steps,
+        energy_source_checks: results.energy_source_checks.clone(),
+        annotations: results.annotations.clone(),
+        playback_frames: results.playback_frames.clone(),
+        temporal_pyramid: results.temporal_pyramid.clone(),
+        schedule_violations: results.schedule_violations.clone(),
+        time_step_sequence: results.time_step_sequence.clone(),
+        stop_reason: results.stop_reason.clone(),
+        averaged_fields: results.averaged_fields.clone(),
+        convergence: results.convergence.clone(),
+        moisture: results.moisture.clone(),
+        reactions: results.reactions.clone(),
+        bed_level: results.bed_level.clone(),
+        mass_balance: results.mass_balance.clone(),
+        controlled_power_history: results.controlled_power_history.clone(),
+    };
+    let metadata = rmp_serde::to_vec_named(&metadata)
+        .map_err(|e| format!("Erro ao serializar metadados dos resultados: {}", e))?;
+
+    let frame_len = 4 + field_count(flags) * nr * nz * 8 + 4;
+    let mut bytes = Vec::with_capacity(FIXED_HEADER_LEN + metadata.len() + 8 + frames * frame_len + 12);
+    bytes.extend_from_slice(ARCHIVE_MAGIC);
+    bytes.extend_from_slice(&ARCHIVE_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&flags.to_le_bytes());
+    for value in [nr, nz, frames, metadata.len()] {
+        bytes.extend_from_slice(&u32::try_from(value).map_err(|_| "Resultados grandes demais para o arquivo".to_string())?.to_le_bytes());
+    }
+    bytes.extend_from_slice(&metadata);
+    bytes.extend_from_slice(&crc32(&bytes).to_le_bytes());
+
+    for k in 0..frames {
+        let start = bytes.len();
+        bytes.extend_from_slice(&(k as u32).to_le_bytes());
+        let fields = [Some(&results.temperature), Some(&results.enthalpy), melt, vapor];
+        for field in fields.iter().flatten() {
+            for &value in field.slice(s![.., .., k]).iter() {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let checksum = crc32(&bytes[start..]);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+    }
+
+    let checksum = crc32(&bytes);
+    bytes.extend_from_slice(ARCHIVE_END_MAGIC);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    Ok(bytes)
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+/// Indica se os bytes começam com a assinatura do arquivo de resultados
+pub fn is_results_archive(bytes: &[u8]) -> bool {
+    bytes.starts_with(ARCHIVE_MAGIC)
+}
+
+/// Lê um arquivo de resultados, verificando as somas de verificação
+///
+/// Quadros corrompidos são preenchidos com NaN e quadros ausentes no fim encurtam o
+/// histórico (`executed_steps` passa a ser o último passo recuperado). Falha apenas
+/// quando o cabeçalho ou os metadados estão danificados ou nenhum quadro é recuperável.
+pub fn decode_results_archive(bytes: &[u8]) -> Result<(SimulationResults, IntegrityReport), String> {
+    if bytes.len() < FIXED_HEADER_LEN || !is_results_archive(bytes) {
+        return Err("Arquivo de resultados inválido (assinatura ausente)".to_string());
+    }
+    let version = read_u16(bytes, 8);
+    if version != ARCHIVE_VERSION {
+        return Err(format!("Versão do arquivo de resultados não suportada: {}", version));
+    }
+    let flags = read_u16(bytes, 10);
+    let nr = read_u32(bytes, 12) as usize;
+    let nz = read_u32(bytes, 16) as usize;
+    let frames = read_u32(bytes, 20) as usize;
+    let metadata_len = read_u32(bytes, 24) as usize;
+    let header_end = FIXED_HEADER_LEN + metadata_len + 4;
+    if bytes.len() < header_end || crc32(&bytes[..header_end - 4]) != read_u32(bytes, header_end - 4) {
+        return Err("Cabeçalho do arquivo de resultados corrompido".to_string());
+    }
+    let mut results: SimulationResults = rmp_serde::from_slice(&bytes[FIXED_HEADER_LEN..header_end - 4])
+        .map_err(|e| format!("Metadados do arquivo de resultados inválidos: {}", e))?;
+
+    let mut report = IntegrityReport { total_frames: frames, ..IntegrityReport::default() };
+    let cells = nr * nz;
+    let fields = field_count(flags);
+    let frame_len = 4 + fields * cells * 8 + 4;
+    let mut histories: Vec<Array3<f64>> = (0..fields).map(|_| Array3::from_elem((nr, nz, frames), f64::NAN)).collect();
+
+    for k in 0..frames {
+        let start = header_end + k * frame_len;
+        if start + frame_len > bytes.len() {
+            report.missing_frames.push(k);
+            continue;
+        }
+        let frame = &bytes[start..start + frame_len];
+        if read_u32(frame, 0) as usize != k || crc32(&frame[..frame_len - 4]) != read_u32(frame, frame_len - 4) {
+            report.corrupted_frames.push(k);
+            continue;
+        }
+        for (f, history) in histories.iter_mut().enumerate() {
+            let values: Vec<f64> = frame[4 + f * cells * 8..4 + (f + 1) * cells * 8]
+                .chunks_exact(8)
+                .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+                .collect();
+            let field = Array2::from_shape_vec((nr, nz), values).map_err(|e| e.to_string())?;
+            history.slice_mut(s![.., .., k]).assign(&field);
+        }
+    }
+
+    let trailer = header_end + frames * frame_len;
+    report.file_checksum_valid = bytes.len() == trailer + 12
+        && &bytes[trailer..trailer + 8] == ARCHIVE_END_MAGIC
+        && crc32(&bytes[..trailer]) == read_u32(bytes, trailer + 8);
+
+    // Quadros ausentes no fim: mantém o prefixo recuperado
+    let available = frames - report.missing_frames.len();
+    if available == 0 || report.corrupted_frames.len() == available {
+        return Err("Nenhum quadro do arquivo de resultados pôde ser recuperado".to_string());
+    }
+    if available < frames {
+        for history in histories.iter_mut() {
+            *history = history.slice(s![.., .., 0..available]).to_owned();
+        }
+        results.executed_steps = results.executed_steps.saturating_sub(frames - available);
+    }
+
+    let mut histories = histories.into_iter();
+    results.temperature = histories.next().unwrap();
+    results.enthalpy = histories.next().unwrap();
+    if let Some(info) = results.phase_change_info.as_mut() {
+        if flags & FLAG_MELT_FRACTION != 0 {
+            info.melt_fraction = histories.next();
+        }
+        if flags & FLAG_VAPOR_FRACTION != 0 {
+            info.vapor_fraction = histories.next();
+        }
+    }
+    Ok((results, report))
+}
+
+/// Grava os resultados em um arquivo com somas de verificação (gravação atômica)
+pub fn save_results_archive(path: &Path, results: &SimulationResults) -> Result<(), String> {
+    let bytes = encode_results_archive(results)?;
+    let temporary = path.with_extension("tmp");
+    fs::write(&temporary, bytes).map_err(|e| format!("Erro ao gravar {:?}: {}", temporary, e))?;
+    fs::rename(&temporary, path).map_err(|e| format!("Erro ao gravar {:?}: {}", path, e))
+}
+
+/// Lê um arquivo de resultados, verificando as somas de verificação
+pub fn load_results_archive(path: &Path) -> Result<(SimulationResults, IntegrityReport), String> {
+    let bytes = fs::read(path).map_err(|e| format!("Erro ao ler {:?}: {}", path, e))?;
+    decode_results_archive(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PlasmaTorch;
+    use crate::simulation::solver::{HeatSolver, SimulationParameters};
+    use std::sync::{Arc, atomic::AtomicBool};
+
+    #[test]
+    fn test_archive_detects_corrupted_and_missing_frames() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        let bytes = encode_results_archive(&results).unwrap();
+
+        let (intact, report) = decode_results_archive(&bytes).unwrap();
+        assert!(report.is_intact());
+        assert_eq!(intact.temperature, results.temperature);
+        assert_eq!(intact.executed_steps, results.executed_steps);
+
+        // Um byte alterado no quadro 2 corrompe apenas esse quadro
+        let frames = results.temperature.dim().2;
+        let frame_len = 4 + field_count(read_u16(&bytes, 10)) * 25 * 8 + 4;
+        let header_end = bytes.len() - 12 - frames * frame_len;
+        let mut damaged = bytes.clone();
+        damaged[header_end + 2 * frame_len + 10] ^= 0xFF;
+        let (recovered, report) = decode_results_archive(&damaged).unwrap();
+        assert!(!report.file_checksum_valid);
+        assert_eq!(report.corrupted_frames, vec![2]);
+        assert!(recovered.temperature[[0, 0, 2]].is_nan());
+        assert_eq!(recovered.temperature[[0, 0, 3]], results.temperature[[0, 0, 3]]);
+
+        // Arquivo truncado no meio do último quadro
+        let truncated = &bytes[..header_end + 5 * frame_len + 7];
+        let (partial, report) = decode_results_archive(truncated).unwrap();
+        assert_eq!(report.missing_frames, vec![5]);
+        assert_eq!(partial.executed_steps, 4);
+        assert_eq!(partial.temperature.dim().2, 5);
+
+        damaged[20] ^= 0xFF;
+        assert!(decode_results_archive(&damaged).is_err());
+    }
+}
@@ -0,0 +1,327 @@
+// Implementação da API assíncrona (futures) para simulações, estudos e exportações
+//
+// Disponível apenas com a feature `async`. As tarefas são executadas em threads
+// dedicadas e expostas como `Future`, com cancelamento cooperativo e fluxo de progresso.
+
+use futures::channel::{mpsc, oneshot};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+use crate::simulation::metrics::{ExportOptions, MetricsAnalyzer};
+use crate::simulation::parametric::{ParametricStudyManager, ParametricStudyResult};
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Fluxo de progresso de uma tarefa assíncrona (valores entre 0 e 1)
+pub type ProgressStream = mpsc::UnboundedReceiver<f32>;
+
+/// Intervalo de consulta do progresso de um estudo paramétrico em execução
+const STUDY_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Estrutura que permite cancelar uma tarefa assíncrona a partir de outra thread ou tarefa
+#[derive(Debug, Clone)]
+pub struct CancelHandle {
+    /// Sinalizador de cancelamento compartilhado com a tarefa
+    flag: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    /// Solicita o cancelamento da tarefa
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Indica se o cancelamento foi solicitado
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+/// Estrutura que representa uma tarefa em execução como `Future`
+///
+/// Descartar a tarefa solicita o cancelamento do trabalho em andamento.
+pub struct AsyncTask<T> {
+    /// Canal que recebe o resultado da thread de trabalho
+    receiver: oneshot::Receiver<Result<T, String>>,
+    /// Sinalizador de cancelamento
+    cancel_flag: Arc<AtomicBool>,
+}
+
+impl<T> AsyncTask<T> {
+    /// Retorna um identificador para cancelar a tarefa
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle { flag: self.cancel_flag.clone() }
+    }
+
+    /// Solicita o cancelamento da tarefa
+    pub fn cancel(&self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+    }
+}
+
+impl<T> Future for AsyncTask<T> {
+    type Output = Result<T, String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(Ok(result)) => Poll::Ready(result),
+            Poll::Ready(Err(_)) => Poll::Ready(Err("Tarefa assíncrona encerrada sem resultado".to_string())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for AsyncTask<T> {
+    fn drop(&mut self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Estrutura que representa uma tarefa assíncrona com fluxo de progresso
+pub struct AsyncRun<T> {
+    /// Tarefa que produz o resultado final
+    pub task: AsyncTask<T>,
+    /// Fluxo de progresso (encerrado quando a tarefa termina)
+    pub progress: ProgressStream,
+}
+
+/// Executa uma função bloqueante em uma thread dedicada e a expõe como tarefa assíncrona
+///
+/// A função recebe o sinalizador de cancelamento e o emissor de progresso.
+pub fn spawn_task<T, F>(name: &str, work: F) -> Result<AsyncRun<T>, String>
+where
+    T: Send + 'static,
+    F: FnOnce(Arc<AtomicBool>, mpsc::UnboundedSender<f32>) -> Result<T, String> + Send + 'static,
+{
+    let (result_tx, result_rx) = oneshot::channel();
+    let (progress_tx, progress_rx) = mpsc::unbounded();
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let worker_flag = cancel_flag.clone();
+
+    thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || {
+            let result = work(worker_flag, progress_tx);
+            // O receptor pode ter sido descartado; nesse caso o resultado é ignorado
+            let _ = result_tx.send(result);
+        })
+        .map_err(|e| format!("Erro ao iniciar thread da tarefa '{}': {}", name, e))?;
+
+    Ok(AsyncRun {
+        task: AsyncTask { receiver: result_rx, cancel_flag },
+        progress: progress_rx,
+    })
+}
+
+/// Executa uma simulação de forma assíncrona
+///
+/// O progresso é enviado a cada passo de tempo e o cancelamento interrompe o
+/// laço do solucionador no passo seguinte.
+pub fn run_simulation_async(params: SimulationParameters) -> Result<AsyncRun<SimulationResults>, String> {
+    let mut solver = HeatSolver::new(params)?;
+
+    spawn_task("plasma-simulation", move |cancel_flag, progress| {
+        let callback = |value: f32| {
+            // Um fluxo descartado não cancela a simulação
+            let _ = progress.unbounded_send(value);
+            true
+        };
+        solver.run(Some(&callback), cancel_flag)
+    })
+}
+
+/// Executa um estudo paramétrico de forma assíncrona
+///
+/// O progresso (casos concluídos ou falhos sobre o total) é enviado a cada caso e o
+/// cancelamento é repassado ao estudo, que termina antes do próximo caso e retorna o
+/// resultado com os casos concluídos (metadado `cancelled`).
+pub fn run_study_async(mut manager: ParametricStudyManager) -> Result<AsyncRun<ParametricStudyResult>, String> {
+    let handle = manager.handle();
+
+    spawn_task("plasma-parametric-study", move |cancel_flag, progress| {
+        let finished = AtomicBool::new(false);
+        thread::scope(|scope| {
+            // Repassa cancelamento e progresso enquanto o estudo executa nesta thread
+            scope.spawn(|| {
+                let mut reported = 0;
+                loop {
+                    if cancel_flag.load(Ordering::Relaxed) && !handle.is_cancelled() {
+                        handle.cancel();
+                    }
+                    let state = handle.progress();
+                    let processed = state.completed_cases + state.failed_cases;
+                    if processed > reported && state.total_cases > 0 {
+                        reported = processed;
+                        let _ = progress.unbounded_send(processed as f32 / state.total_cases as f32);
+                    }
+                    if finished.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    thread::sleep(STUDY_POLL_INTERVAL);
+                }
+            });
+
+            let result = manager.run_study();
+            finished.store(true, Ordering::Relaxed);
+            result
+        })
+    })
+}
+
+/// Exporta os resultados de uma análise de métricas de forma assíncrona
+///
+/// O progresso é enviado a cada linha radial escrita e o cancelamento interrompe a
+/// exportação com erro, deixando o arquivo incompleto.
+pub fn export_results_async(analyzer: MetricsAnalyzer, options: ExportOptions) -> Result<AsyncRun<()>, String> {
+    spawn_task("plasma-export", move |cancel_flag, progress| {
+        let callback = |value: f32| {
+            let _ = progress.unbounded_send(value);
+            !cancel_flag.load(Ordering::Relaxed)
+        };
+        analyzer.export_results_with_progress(&options, Some(&callback))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::StreamExt;
+    use std::collections::HashMap;
+    use crate::simulation::metrics::ExportFormat;
+    use crate::simulation::parametric::{
+        OptimizationGoal, ParametricParameter, ParametricStudyConfig, SamplingMethod, ScaleType,
+    };
+    use crate::simulation::physics::PlasmaTorch;
+    use crate::simulation::state::SimulationState;
+
+    fn create_test_parameters(time_steps: usize) -> SimulationParameters {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = time_steps;
+        params.time_step = 1.0;
+        params.total_time = time_steps as f64;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        params
+    }
+
+    #[test]
+    fn test_async_simulation_reports_progress() {
+        let run = run_simulation_async(create_test_parameters(4)).unwrap();
+        let AsyncRun { task, progress } = run;
+
+        let results = block_on(task).unwrap();
+        let values: Vec<f32> = block_on(progress.collect());
+
+        assert_eq!(results.executed_steps, 4);
+        assert_eq!(values.len(), 4);
+        assert!((values[3] - 1.0).abs() < 1e-6);
+    }
+
+    fn create_test_study(time_steps: usize) -> ParametricStudyManager {
+        let parameter = ParametricParameter {
+            name: "torch_power".to_string(),
+            description: "Potência da tocha de plasma".to_string(),
+            unit: "kW".to_string(),
+            min_value: 5.0,
+            max_value: 15.0,
+            num_points: 4,
+            scale_type: ScaleType::Linear,
+            specific_values: None,
+            categories: None,
+            category_presets: HashMap::new(),
+        };
+        let config = ParametricStudyConfig {
+            name: "Estudo assíncrono".to_string(),
+            description: "Estudo paramétrico para testes da API assíncrona".to_string(),
+            parameters: vec![parameter],
+            target_metric: "max_temperature".to_string(),
+            optimization_goal: OptimizationGoal::Maximize,
+            sampling_method: SamplingMethod::Grid,
+            design: None,
+            derived_parameters: Vec::new(),
+            constraints: Vec::new(),
+            early_stopping: Vec::new(),
+            max_simulations: 10,
+            max_execution_time: None,
+            use_parallel: false,
+            metadata: HashMap::new(),
+        };
+        ParametricStudyManager::new(config, create_test_parameters(time_steps))
+    }
+
+    #[test]
+    fn test_async_study_reports_progress() {
+        let AsyncRun { task, progress } = run_study_async(create_test_study(2)).unwrap();
+
+        let result = block_on(task).unwrap();
+        let values: Vec<f32> = block_on(progress.collect());
+
+        assert_eq!(result.total_simulations, 4);
+        assert!(!values.is_empty());
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!((values[values.len() - 1] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_async_study_cancellation_is_forwarded() {
+        let AsyncRun { task, mut progress } = run_study_async(create_test_study(1000)).unwrap();
+
+        // Cancela depois do primeiro caso concluído
+        let first = block_on(progress.next()).unwrap();
+        assert!(first > 0.0 && first < 1.0);
+        task.cancel();
+
+        let result = block_on(task).unwrap();
+        assert!(result.total_simulations < 4);
+        assert_eq!(result.metadata.get("cancelled").map(String::as_str), Some("true"));
+    }
+
+    #[test]
+    fn test_async_export_reports_incremental_progress() {
+        let mut solver = HeatSolver::new(create_test_parameters(2)).unwrap();
+        let results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        let mut state = SimulationState::new(create_test_parameters(2));
+        state.complete(results);
+
+        let path = std::env::temp_dir().join(format!("plasma_async_export_{}.csv", std::process::id()));
+        let options = ExportOptions {
+            format: ExportFormat::CSV,
+            output_path: path.to_string_lossy().into_owned(),
+            include_metrics: false,
+            include_temperature: true,
+            include_gradient: false,
+            include_heat_flux: false,
+            include_metadata: false,
+            time_steps: None,
+        };
+        let AsyncRun { task, progress } = export_results_async(MetricsAnalyzer::new(state), options).unwrap();
+
+        block_on(task).unwrap();
+        let values: Vec<f32> = block_on(progress.collect());
+        std::fs::remove_file(&path).unwrap();
+
+        // Uma notificação por linha radial da malha e o fim da exportação
+        assert_eq!(values.len(), 6);
+        assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!((values[5] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_async_task_cancellation() {
+        let run = spawn_task("test-cancel", |cancel_flag, _progress| {
+            while !cancel_flag.load(Ordering::Relaxed) {
+                thread::yield_now();
+            }
+            Err::<(), String>("Simulation cancelled".to_string())
+        }).unwrap();
+
+        let handle = run.task.cancel_handle();
+        handle.cancel();
+        assert!(handle.is_cancelled());
+        assert!(block_on(run.task).is_err());
+    }
+}
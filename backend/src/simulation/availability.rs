@@ -0,0 +1,384 @@
+// Implementação do modo estocástico de campanha (Monte Carlo) para estudos de disponibilidade
+
+use log::info;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+use crate::simulation::physics::PowerSchedule;
+use crate::simulation::random::SplitMix64;
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Estrutura que representa as taxas de falha usadas na amostragem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureRates {
+    /// Taxa de desarme de cada tocha (falhas por hora de operação)
+    pub torch_trip_rate: f64,
+    /// Tempo de religamento após um desarme (s)
+    pub torch_restart_time: f64,
+    /// Taxa de interrupção da alimentação de carga (interrupções por hora)
+    pub feed_interruption_rate: f64,
+    /// Duração de cada interrupção da alimentação (s)
+    pub feed_interruption_duration: f64,
+}
+
+impl FailureRates {
+    /// Valida as taxas de falha
+    pub fn validate(&self) -> Result<(), String> {
+        if self.torch_trip_rate < 0.0 || self.feed_interruption_rate < 0.0 {
+            return Err("Taxas de falha não podem ser negativas".to_string());
+        }
+        if self.torch_restart_time < 0.0 || self.feed_interruption_duration < 0.0 {
+            return Err("Durações das falhas não podem ser negativas".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa a configuração de um estudo de disponibilidade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityStudyConfig {
+    /// Parâmetros base da campanha (tochas com programação nominal)
+    pub base_parameters: SimulationParameters,
+    /// Taxas de falha
+    pub failure_rates: FailureRates,
+    /// Número de réplicas
+    pub replicates: usize,
+    /// Semente do gerador pseudoaleatório (réplicas reprodutíveis)
+    pub seed: u64,
+    /// Vazão nominal de carga processada (kg/h)
+    pub feed_rate: f64,
+    /// Fração mínima da potência solicitada para que a carga seja processada (0-1)
+    pub min_power_fraction: f64,
+    /// Temperatura média a partir da qual o tempo em temperatura é contabilizado (°C)
+    pub target_temperature: f64,
+}
+
+impl AvailabilityStudyConfig {
+    /// Cria uma nova configuração com valores padrão
+    pub fn new(base_parameters: SimulationParameters, failure_rates: FailureRates, replicates: usize) -> Self {
+        Self {
+            base_parameters,
+            failure_rates,
+            replicates,
+            seed: 42,
+            feed_rate: 1000.0,
+            min_power_fraction: 0.5,
+            target_temperature: 1000.0,
+        }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        self.base_parameters.validate()?;
+        self.failure_rates.validate()?;
+        if self.replicates == 0 {
+            return Err("Número de réplicas deve ser positivo".to_string());
+        }
+        if self.feed_rate < 0.0 {
+            return Err("Vazão de carga não pode ser negativa".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.min_power_fraction) {
+            return Err("Fração mínima de potência deve estar entre 0 e 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa um intervalo de indisponibilidade amostrado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutageEvent {
+    /// ID da tocha desarmada (None para interrupção da alimentação)
+    pub torch_id: Option<String>,
+    /// Início da indisponibilidade (s)
+    pub start_time: f64,
+    /// Fim da indisponibilidade (s)
+    pub end_time: f64,
+}
+
+/// Estrutura que representa o resultado de uma réplica
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicateOutcome {
+    /// Índice da réplica
+    pub replicate: usize,
+    /// Eventos amostrados
+    pub outages: Vec<OutageEvent>,
+    /// Massa de carga processada (kg)
+    pub throughput: f64,
+    /// Tempo com a temperatura média acima do alvo (s)
+    pub time_at_temperature: f64,
+    /// Disponibilidade das tochas (fração da energia solicitada efetivamente entregue)
+    pub torch_availability: f64,
+    /// Temperatura média final (°C)
+    pub final_mean_temperature: f64,
+}
+
+/// Estrutura que resume a distribuição de uma grandeza entre as réplicas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionSummary {
+    /// Média
+    pub mean: f64,
+    /// Desvio padrão amostral
+    pub std_dev: f64,
+    /// Mínimo
+    pub min: f64,
+    /// Percentil 10
+    pub p10: f64,
+    /// Mediana
+    pub p50: f64,
+    /// Percentil 90
+    pub p90: f64,
+    /// Máximo
+    pub max: f64,
+}
+
+impl DistributionSummary {
+    /// Calcula o resumo a partir das amostras
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self { mean: 0.0, std_dev: 0.0, min: 0.0, p10: 0.0, p50: 0.0, p90: 0.0, max: 0.0 };
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        // Percentil por interpolação linear entre as amostras ordenadas
+        let percentile = |p: f64| {
+            let position = p * (sorted.len() - 1) as f64;
+            let index = position.floor() as usize;
+            let next = (index + 1).min(sorted.len() - 1);
+            sorted[index] + (position - index as f64) * (sorted[next] - sorted[index])
+        };
+
+        Self {
+            mean,
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            p10: percentile(0.1),
+            p50: percentile(0.5),
+            p90: percentile(0.9),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Estrutura que representa o resultado de um estudo de disponibilidade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityStudyResult {
+    /// Resultados das réplicas, em ordem
+    pub outcomes: Vec<ReplicateOutcome>,
+    /// Distribuição da massa processada (kg)
+    pub throughput: DistributionSummary,
+    /// Distribuição do tempo em temperatura (s)
+    pub time_at_temperature: DistributionSummary,
+    /// Distribuição da disponibilidade das tochas (0-1)
+    pub torch_availability: DistributionSummary,
+}
+
+/// Amostra os intervalos de indisponibilidade de um processo de Poisson
+fn sample_outages(rng: &mut SplitMix64, rate_per_hour: f64, duration: f64, total_time: f64, torch_id: Option<&str>) -> Vec<OutageEvent> {
+    let mut outages = Vec::new();
+    if rate_per_hour <= 0.0 {
+        return outages;
+    }
+    let rate = rate_per_hour / 3600.0;
+    let mut time = rng.exponential(rate);
+    while time < total_time {
+        outages.push(OutageEvent {
+            torch_id: torch_id.map(|id| id.to_string()),
+            start_time: time,
+            end_time: time + duration,
+        });
+        // Novas falhas só ocorrem após o retorno à operação
+        time += duration + rng.exponential(rate);
+    }
+    outages
+}
+
+/// Indica se algum intervalo de indisponibilidade contém o instante
+fn is_down(outages: &[OutageEvent], time: f64) -> bool {
+    outages.iter().any(|o| time >= o.start_time && time < o.end_time)
+}
+
+/// Estrutura que representa o executor do estudo de disponibilidade
+pub struct AvailabilityStudy {
+    /// Configuração do estudo
+    config: AvailabilityStudyConfig,
+}
+
+impl AvailabilityStudy {
+    /// Cria um novo estudo
+    pub fn new(config: AvailabilityStudyConfig) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    /// Executa todas as réplicas em paralelo
+    pub fn run(&self, cancel_flag: Arc<AtomicBool>) -> Result<AvailabilityStudyResult, String> {
+        info!("Iniciando estudo de disponibilidade com {} réplicas", self.config.replicates);
+
+        let outcomes = (0..self.config.replicates).into_par_iter()
+            .map(|replicate| {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    return Err("Estudo de disponibilidade cancelado".to_string());
+                }
+                self.run_replicate(replicate, cancel_flag.clone())
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let collect = |f: fn(&ReplicateOutcome) -> f64| outcomes.iter().map(f).collect::<Vec<f64>>();
+        Ok(AvailabilityStudyResult {
+            throughput: DistributionSummary::from_samples(&collect(|o| o.throughput)),
+            time_at_temperature: DistributionSummary::from_samples(&collect(|o| o.time_at_temperature)),
+            torch_availability: DistributionSummary::from_samples(&collect(|o| o.torch_availability)),
+            outcomes,
+        })
+    }
+
+    /// Amostra as falhas e executa uma réplica
+    pub fn run_replicate(&self, replicate: usize, cancel_flag: Arc<AtomicBool>) -> Result<ReplicateOutcome, String> {
+        let base = &self.config.base_parameters;
+        let rates = &self.config.failure_rates;
+        let dt = base.time_step;
+        let total_time = base.time_steps as f64 * dt;
+        let mut rng = SplitMix64::new(self.config.seed ^ (replicate as u64).wrapping_mul(0xA24B_AED4_963E_E407));
+
+        let torch_outages: Vec<Vec<OutageEvent>> = base.torches.iter()
+            .map(|torch| sample_outages(&mut rng, rates.torch_trip_rate, rates.torch_restart_time, total_time, Some(&torch.id)))
+            .collect();
+        let feed_outages = sample_outages(&mut rng, rates.feed_interruption_rate, rates.feed_interruption_duration, total_time, None);
+
+        // Programações amostradas nos instantes dos passos, com potência nula durante os desarmes
+        let mut params = base.clone();
+        let mut requested_energy = 0.0;
+        let mut delivered_energy = 0.0;
+        let mut processing_time = 0.0;
+        for step in 0..base.time_steps {
+            let time = step as f64 * dt;
+            let requested: f64 = base.torches.iter().map(|t| t.requested_power(time).max(0.0)).sum();
+            let delivered: f64 = base.torches.iter().zip(torch_outages.iter())
+                .filter(|(_, outages)| !is_down(outages, time))
+                .map(|(t, _)| t.requested_power(time).max(0.0))
+                .sum();
+            requested_energy += requested * dt;
+            delivered_energy += delivered * dt;
+
+            let power_fraction = if requested > 0.0 { delivered / requested } else { 1.0 };
+            if !is_down(&feed_outages, time) && power_fraction >= self.config.min_power_fraction {
+                processing_time += dt;
+            }
+        }
+        for (torch, outages) in params.torches.iter_mut().zip(torch_outages.iter()) {
+            if outages.is_empty() {
+                continue;
+            }
+            let points = (0..=base.time_steps)
+                .map(|step| {
+                    let time = step as f64 * dt;
+                    let power = if is_down(outages, time) { 0.0 } else { torch.requested_power(time) };
+                    (time, power)
+                })
+                .collect();
+            torch.power_schedule = Some(PowerSchedule::new(points));
+        }
+
+        let results = HeatSolver::new(params)?.run(None, cancel_flag)?;
+        let (time_at_temperature, final_mean_temperature) = self.temperature_statistics(&results)?;
+
+        let mut outages: Vec<OutageEvent> = torch_outages.into_iter().flatten().chain(feed_outages).collect();
+        outages.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(ReplicateOutcome {
+            replicate,
+            outages,
+            throughput: self.config.feed_rate * processing_time / 3600.0,
+            time_at_temperature,
+            torch_availability: if requested_energy > 0.0 { delivered_energy / requested_energy } else { 1.0 },
+            final_mean_temperature,
+        })
+    }
+
+    /// Calcula o tempo com temperatura média acima do alvo e a temperatura média final
+    fn temperature_statistics(&self, results: &SimulationResults) -> Result<(f64, f64), String> {
+        let dt = results.parameters.time_step;
+        let mut time_at_temperature = 0.0;
+        let mut mean = results.parameters.initial_temperature;
+        for step in 1..=results.executed_steps {
+            let field = results.temperature_at(step)?;
+            mean = field.iter().sum::<f64>() / field.len().max(1) as f64;
+            if mean >= self.config.target_temperature {
+                time_at_temperature += dt;
+            }
+        }
+        Ok((time_at_temperature, mean))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use crate::simulation::physics::PlasmaTorch;
+
+    fn create_config(trip_rate: f64, feed_rate: f64) -> AvailabilityStudyConfig {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 6;
+        params.time_step = 1.0;
+        params.total_time = 6.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+
+        let rates = FailureRates {
+            torch_trip_rate: trip_rate,
+            torch_restart_time: 2.0,
+            feed_interruption_rate: feed_rate,
+            feed_interruption_duration: 1.0,
+        };
+        let mut config = AvailabilityStudyConfig::new(params, rates, 4);
+        config.feed_rate = 3600.0;
+        config.target_temperature = -100.0;
+        config
+    }
+
+    #[test]
+    fn test_availability_without_failures() {
+        let study = AvailabilityStudy::new(create_config(0.0, 0.0)).unwrap();
+        let result = study.run(Arc::new(AtomicBool::new(false))).unwrap();
+
+        assert_eq!(result.outcomes.len(), 4);
+        assert!(result.outcomes.iter().all(|o| o.outages.is_empty()));
+        // 3600 kg/h durante 6 s
+        assert_relative_eq!(result.throughput.mean, 6.0, epsilon = 1e-9);
+        assert_relative_eq!(result.throughput.std_dev, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(result.torch_availability.min, 1.0);
+        assert_relative_eq!(result.time_at_temperature.p50, 6.0);
+    }
+
+    #[test]
+    fn test_availability_with_frequent_failures_is_reproducible() {
+        // Uma falha por segundo em média: desarmes praticamente certos
+        let config = create_config(3600.0, 3600.0);
+        let study = AvailabilityStudy::new(config.clone()).unwrap();
+        let result = study.run(Arc::new(AtomicBool::new(false))).unwrap();
+
+        assert!(result.torch_availability.mean < 1.0);
+        assert!(result.throughput.mean < 6.0);
+        assert!(result.outcomes.iter().any(|o| o.outages.iter().any(|e| e.torch_id.is_none())));
+
+        // Mesma semente, mesmas amostras
+        let again = AvailabilityStudy::new(config).unwrap().run(Arc::new(AtomicBool::new(false))).unwrap();
+        for (a, b) in result.outcomes.iter().zip(again.outcomes.iter()) {
+            assert_eq!(a.outages.len(), b.outages.len());
+            assert_relative_eq!(a.throughput, b.throughput);
+        }
+
+        let summary = DistributionSummary::from_samples(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_relative_eq!(summary.p50, 3.0);
+        assert_relative_eq!(summary.p10, 1.4, epsilon = 1e-12);
+    }
+}
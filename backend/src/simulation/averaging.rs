@@ -0,0 +1,254 @@
+// Implementação das médias temporais e por ciclo do campo de temperatura (acumuladas durante a execução)
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+/// Enumeração que representa uma janela de média temporal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AveragingWindow {
+    /// Janela fixa [início, fim]
+    Window {
+        /// Nome da saída
+        name: String,
+        /// Início da janela (s)
+        start_time: f64,
+        /// Fim da janela (s)
+        end_time: f64,
+    },
+    /// Ciclos consecutivos de período fixo (tochas pulsadas, alimentação cíclica)
+    Cycles {
+        /// Nome da saída (cada ciclo recebe o sufixo `#k`)
+        name: String,
+        /// Início do primeiro ciclo (s)
+        start_time: f64,
+        /// Período do ciclo (s)
+        period: f64,
+    },
+}
+
+impl AveragingWindow {
+    /// Valida a janela
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            AveragingWindow::Window { name, start_time, end_time } => {
+                if *start_time < 0.0 || end_time <= start_time {
+                    return Err(format!("Janela de média {}: intervalo inválido [{}, {}]", name, start_time, end_time));
+                }
+            }
+            AveragingWindow::Cycles { name, start_time, period } => {
+                if *start_time < 0.0 || *period <= 0.0 {
+                    return Err(format!("Média por ciclo {}: início e período devem ser positivos", name));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa um campo médio sobre uma janela ou ciclo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AveragedField {
+    /// Nome da saída
+    pub name: String,
+    /// Índice do ciclo (None para janelas fixas)
+    pub cycle: Option<usize>,
+    /// Início do intervalo (s)
+    pub start_time: f64,
+    /// Fim do intervalo (s)
+    pub end_time: f64,
+    /// Quadros acumulados
+    pub samples: usize,
+    /// Indica se o intervalo foi inteiramente simulado
+    pub complete: bool,
+    /// Temperatura média (°C)
+    pub mean: Array2<f64>,
+    /// Temperatura mínima no intervalo (°C)
+    pub min: Array2<f64>,
+    /// Temperatura máxima no intervalo (°C)
+    pub max: Array2<f64>,
+    /// Amplitude da oscilação, (máx - mín)/2 (°C)
+    pub amplitude: Array2<f64>,
+}
+
+/// Acumulador de soma, mínimo e máximo de um intervalo
+#[derive(Debug, Clone)]
+struct IntervalAccumulator {
+    cycle: Option<usize>,
+    start_time: f64,
+    end_time: f64,
+    samples: usize,
+    sum: Array2<f64>,
+    min: Array2<f64>,
+    max: Array2<f64>,
+}
+
+impl IntervalAccumulator {
+    fn new(cycle: Option<usize>, start_time: f64, end_time: f64, field: &Array2<f64>) -> Self {
+        Self {
+            cycle,
+            start_time,
+            end_time,
+            samples: 0,
+            sum: Array2::zeros(field.dim()),
+            min: Array2::from_elem(field.dim(), f64::INFINITY),
+            max: Array2::from_elem(field.dim(), f64::NEG_INFINITY),
+        }
+    }
+
+    fn add(&mut self, field: &Array2<f64>) {
+        self.samples += 1;
+        self.sum += field;
+        self.min.zip_mut_with(field, |m, &t| *m = m.min(t));
+        self.max.zip_mut_with(field, |m, &t| *m = m.max(t));
+    }
+
+    fn finish(self, name: &str, final_time: f64) -> AveragedField {
+        let mean = &self.sum / self.samples.max(1) as f64;
+        let amplitude = (&self.max - &self.min) / 2.0;
+        AveragedField {
+            name: match self.cycle {
+                Some(cycle) => format!("{}#{}", name, cycle),
+                None => name.to_string(),
+            },
+            cycle: self.cycle,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            samples: self.samples,
+            complete: final_time >= self.end_time - 1e-9,
+            mean,
+            min: self.min,
+            max: self.max,
+            amplitude,
+        }
+    }
+}
+
+/// Estado de uma janela durante a execução
+#[derive(Debug, Clone)]
+struct WindowState {
+    window: AveragingWindow,
+    current: Option<IntervalAccumulator>,
+    finished: Vec<AveragedField>,
+}
+
+/// Estrutura que representa o acumulador das médias temporais
+///
+/// Os quadros são acumulados à medida que são produzidos, sem manter o histórico; a
+/// média é a média aritmética dos quadros de saída dentro do intervalo (passo fixo).
+/// Um quadro exatamente no limite entre ciclos pertence ao ciclo seguinte.
+#[derive(Debug, Clone)]
+pub struct AveragingAccumulator {
+    windows: Vec<WindowState>,
+    last_time: f64,
+}
+
+impl AveragingAccumulator {
+    /// Cria o acumulador para as janelas informadas
+    pub fn new(windows: &[AveragingWindow]) -> Self {
+        Self {
+            windows: windows.iter()
+                .map(|window| WindowState { window: window.clone(), current: None, finished: Vec::new() })
+                .collect(),
+            last_time: 0.0,
+        }
+    }
+
+    /// Indica se não há janelas configuradas
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Acumula o quadro do instante `time`
+    pub fn record(&mut self, time: f64, field: &Array2<f64>) {
+        const EPS: f64 = 1e-9;
+        self.last_time = time;
+        for state in &mut self.windows {
+            match &state.window {
+                AveragingWindow::Window { name, start_time, end_time } => {
+                    if time < start_time - EPS || time > end_time + EPS {
+                        if time > end_time + EPS {
+                            if let Some(current) = state.current.take() {
+                                state.finished.push(current.finish(name, time));
+                            }
+                        }
+                        continue;
+                    }
+                    state.current
+                        .get_or_insert_with(|| IntervalAccumulator::new(None, *start_time, *end_time, field))
+                        .add(field);
+                }
+                AveragingWindow::Cycles { name, start_time, period } => {
+                    if time < start_time - EPS {
+                        continue;
+                    }
+                    let cycle = ((time - start_time + EPS) / period).floor() as usize;
+                    if state.current.as_ref().is_some_and(|current| current.cycle != Some(cycle)) {
+                        let current = state.current.take().unwrap();
+                        state.finished.push(current.finish(name, time));
+                    }
+                    let cycle_start = start_time + cycle as f64 * period;
+                    state.current
+                        .get_or_insert_with(|| IntervalAccumulator::new(Some(cycle), cycle_start, cycle_start + period, field))
+                        .add(field);
+                }
+            }
+        }
+    }
+
+    /// Encerra a acumulação e retorna os campos médios (intervalos parciais são marcados incompletos)
+    pub fn finish(&self) -> Vec<AveragedField> {
+        let mut fields = Vec::new();
+        for state in &self.windows {
+            let name = match &state.window {
+                AveragingWindow::Window { name, .. } | AveragingWindow::Cycles { name, .. } => name,
+            };
+            fields.extend(state.finished.iter().cloned());
+            if let Some(current) = &state.current {
+                fields.push(current.clone().finish(name, self.last_time));
+            }
+        }
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_window_and_cycle_averages() {
+        let windows = vec![
+            AveragingWindow::Window { name: "final".to_string(), start_time: 2.0, end_time: 4.0 },
+            AveragingWindow::Cycles { name: "pulso".to_string(), start_time: 0.0, period: 4.0 },
+        ];
+        let mut accumulator = AveragingAccumulator::new(&windows);
+
+        // Sinal pulsado de período 4 s: 0, 10, 20, 10, 0, 10, ...
+        let signal = [0.0, 10.0, 20.0, 10.0, 0.0, 10.0, 20.0, 10.0, 0.0, 10.0];
+        for (step, value) in signal.iter().enumerate() {
+            accumulator.record(step as f64, &Array2::from_elem((2, 2), *value));
+        }
+        let fields = accumulator.finish();
+        assert_eq!(fields.len(), 4);
+
+        let window = &fields[0];
+        assert_eq!(window.samples, 3);
+        assert!(window.complete);
+        assert_relative_eq!(window.mean[[0, 0]], 10.0, epsilon = 1e-12);
+
+        let first_cycle = &fields[1];
+        assert_eq!((first_cycle.name.as_str(), first_cycle.samples), ("pulso#0", 4));
+        assert_relative_eq!(first_cycle.mean[[1, 1]], 10.0, epsilon = 1e-12);
+        assert_relative_eq!(first_cycle.amplitude[[1, 1]], 10.0, epsilon = 1e-12);
+        assert!(first_cycle.complete);
+
+        // Último ciclo (t = 8 e 9 s) ainda em andamento
+        let last_cycle = &fields[3];
+        assert_eq!(last_cycle.cycle, Some(2));
+        assert_eq!(last_cycle.samples, 2);
+        assert!(!last_cycle.complete);
+
+        assert!(AveragingWindow::Cycles { name: "x".to_string(), start_time: 0.0, period: 0.0 }.validate().is_err());
+    }
+}
@@ -0,0 +1,248 @@
+// Implementação do consumo do leito e do rebaixamento do nível (fronteira superior móvel)
+//
+// A cada passo, a célula do topo de cada coluna radial é desativada quando está
+// consumida (fração vaporizada ou convertida pelas reações acima do limite); assim a
+// superfície do leito desce coluna a coluna. As células consumidas deixam de participar
+// da solução: a potência depositada nelas passa à célula exposta da mesma coluna e elas
+// recebem o estado dessa célula, de modo que a nova superfície fica sem fluxo
+// condutivo para o espaço liberado (o calor que as atravessa sai com os gases).
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::mesh::CylindricalMesh;
+
+/// Estrutura que representa a configuração do consumo do leito
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BedConsumptionConfig {
+    /// Fração vaporizada a partir da qual uma célula é consumida
+    #[serde(default = "default_consumption_threshold")]
+    pub vapor_fraction_threshold: f64,
+    /// Fração convertida pelas reações a partir da qual uma célula é consumida
+    /// (None: a conversão não consome células)
+    #[serde(default = "default_conversion_threshold")]
+    pub conversion_threshold: Option<f64>,
+}
+
+fn default_consumption_threshold() -> f64 {
+    0.99
+}
+
+fn default_conversion_threshold() -> Option<f64> {
+    Some(default_consumption_threshold())
+}
+
+impl Default for BedConsumptionConfig {
+    fn default() -> Self {
+        Self {
+            vapor_fraction_threshold: default_consumption_threshold(),
+            conversion_threshold: default_conversion_threshold(),
+        }
+    }
+}
+
+impl BedConsumptionConfig {
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        let valid = |t: f64| t > 0.0 && t <= 1.0;
+        if !valid(self.vapor_fraction_threshold) || !self.conversion_threshold.is_none_or(valid) {
+            return Err("Limites de consumo do leito devem estar em (0, 1]".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa a evolução do nível do leito de uma execução
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BedLevelInfo {
+    /// Altura média do leito (ponderada pela área das colunas) ao fim de cada passo (m), a partir do passo 0
+    pub height_history: Vec<f64>,
+    /// Altura do leito de cada coluna radial ao fim da execução (m)
+    pub column_heights: Vec<f64>,
+    /// Células consumidas ao fim da execução
+    pub consumed: Array2<bool>,
+    /// Volume consumido do leito (m³)
+    pub consumed_volume: f64,
+}
+
+/// Estrutura que acompanha as células consumidas durante a execução
+#[derive(Debug, Clone)]
+pub struct BedLevelModel {
+    config: BedConsumptionConfig,
+    consumed: Array2<bool>,
+    height_history: Vec<f64>,
+}
+
+impl BedLevelModel {
+    /// Cria o modelo com o leito intacto, registrando a altura inicial
+    pub fn new(config: &BedConsumptionConfig, mesh: &CylindricalMesh) -> Self {
+        let mut model = Self {
+            config: config.clone(),
+            consumed: Array2::from_elem((mesh.nr, mesh.nz), false),
+            height_history: Vec::new(),
+        };
+        model.record_step(mesh);
+        model
+    }
+
+    /// Índice da célula exposta (topo ativo) de uma coluna, se restar leito
+    pub fn top_cell(&self, i: usize) -> Option<usize> {
+        (0..self.consumed.ncols()).rev().find(|&j| !self.consumed[[i, j]])
+    }
+
+    /// Indica se a célula foi consumida
+    pub fn is_consumed(&self, i: usize, j: usize) -> bool {
+        self.consumed[[i, j]]
+    }
+
+    /// Desativa, a partir do topo de cada coluna, as células consumidas; retorna quantas foram desativadas
+    pub fn update(&mut self, vapor_fraction: Option<&Array2<f64>>, conversion: Option<&Array2<f64>>) -> usize {
+        let is_spent = |i: usize, j: usize| {
+            vapor_fraction.is_some_and(|f| f[[i, j]] >= self.config.vapor_fraction_threshold)
+                || match (conversion, self.config.conversion_threshold) {
+                    (Some(c), Some(threshold)) => c[[i, j]] >= threshold,
+                    _ => false,
+                }
+        };
+        let mut deactivated = Vec::new();
+        for i in 0..self.consumed.nrows() {
+            let mut top = self.top_cell(i);
+            while let Some(j) = top {
+                if !is_spent(i, j) {
+                    break;
+                }
+                deactivated.push((i, j));
+                top = j.checked_sub(1);
+            }
+        }
+        for &(i, j) in &deactivated {
+            self.consumed[[i, j]] = true;
+        }
+        deactivated.len()
+    }
+
+    /// Transfere o termo fonte das células consumidas para a célula exposta da coluna,
+    /// conservando a potência (W/m³ → W → W/m³); sem leito restante a potência é descartada
+    pub fn fold_source(&self, mesh: &CylindricalMesh, source: &mut Array2<f64>) {
+        for i in 0..mesh.nr {
+            let top = self.top_cell(i);
+            let mut power = 0.0;
+            for j in 0..mesh.nz {
+                if self.consumed[[i, j]] {
+                    power += source[[i, j]] * mesh.cell_volumes[[i, j]];
+                    source[[i, j]] = 0.0;
+                }
+            }
+            if let Some(j) = top {
+                source[[i, j]] += power / mesh.cell_volumes[[i, j]];
+            }
+        }
+    }
+
+    /// Copia para as células consumidas o valor da célula exposta da mesma coluna
+    pub fn fill_consumed(&self, field: &mut Array2<f64>) {
+        for i in 0..field.nrows() {
+            if let Some(top) = self.top_cell(i) {
+                let value = field[[i, top]];
+                for j in top + 1..field.ncols() {
+                    field[[i, j]] = value;
+                }
+            }
+        }
+    }
+
+    /// Altura do leito de cada coluna: face superior da célula exposta (m)
+    pub fn column_heights(&self, mesh: &CylindricalMesh) -> Vec<f64> {
+        (0..mesh.nr)
+            .map(|i| match self.top_cell(i) {
+                Some(j) if j + 1 < mesh.nz => (mesh.z_coords[j] + mesh.z_coords[j + 1]) / 2.0,
+                Some(_) => mesh.height,
+                None => 0.0,
+            })
+            .collect()
+    }
+
+    /// Altura média do leito, ponderada pela área das colunas (m)
+    pub fn bed_height(&self, mesh: &CylindricalMesh) -> f64 {
+        let areas: Vec<f64> = (0..mesh.nr).map(|i| mesh.axial_face_area(i)).collect();
+        let total_area: f64 = areas.iter().sum();
+        self.column_heights(mesh).iter().zip(&areas).map(|(h, a)| h * a).sum::<f64>() / total_area
+    }
+
+    /// Registra a altura do leito ao fim de um passo de saída
+    pub fn record_step(&mut self, mesh: &CylindricalMesh) {
+        let height = self.bed_height(mesh);
+        self.height_history.push(height);
+    }
+
+    /// Retoma as células consumidas e o histórico de uma execução anterior
+    pub fn resume(&mut self, info: &BedLevelInfo, history_len: usize) {
+        if info.consumed.dim() == self.consumed.dim() {
+            self.consumed.assign(&info.consumed);
+        }
+        self.height_history = info.height_history[..history_len.min(info.height_history.len())].to_vec();
+    }
+
+    /// Resultado do consumo do leito
+    pub fn finish(&self, mesh: &CylindricalMesh) -> BedLevelInfo {
+        let consumed_volume = self.consumed.iter()
+            .zip(mesh.cell_volumes.iter())
+            .filter(|(&consumed, _)| consumed)
+            .map(|(_, &volume)| volume)
+            .sum();
+        BedLevelInfo {
+            height_history: self.height_history.clone(),
+            column_heights: self.column_heights(mesh),
+            consumed: self.consumed.clone(),
+            consumed_volume,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_bed_consumption_from_top() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 3, 4, 4);
+        let mut model = BedLevelModel::new(&BedConsumptionConfig::default(), &mesh);
+        assert_relative_eq!(model.bed_height(&mesh), 1.0, epsilon = 1e-12);
+
+        // Coluna 0: duas células do topo vaporizadas; coluna 1: célula interna vaporizada
+        // sob uma célula intacta não é consumida
+        let mut vapor = Array2::<f64>::zeros((3, 4));
+        vapor[[0, 3]] = 1.0;
+        vapor[[0, 2]] = 1.0;
+        vapor[[1, 3]] = 0.995;
+        vapor[[1, 1]] = 1.0;
+        assert_eq!(model.update(Some(&vapor), None), 3);
+        assert_eq!(model.top_cell(0), Some(1));
+        assert_eq!(model.top_cell(1), Some(2));
+        assert!(model.is_consumed(0, 2) && !model.is_consumed(1, 1));
+
+        let heights = model.column_heights(&mesh);
+        assert_relative_eq!(heights[0], (mesh.z_coords[1] + mesh.z_coords[2]) / 2.0, epsilon = 1e-12);
+        assert_relative_eq!(heights[1], (mesh.z_coords[2] + mesh.z_coords[3]) / 2.0, epsilon = 1e-12);
+        assert_relative_eq!(heights[2], 1.0, epsilon = 1e-12);
+        model.record_step(&mesh);
+        assert!(model.finish(&mesh).height_history[1] < 1.0);
+
+        // A potência das células consumidas vai para a superfície exposta
+        let mut source = Array2::<f64>::ones((3, 4));
+        let power = (&source * &mesh.cell_volumes).sum();
+        model.fold_source(&mesh, &mut source);
+        assert_relative_eq!((&source * &mesh.cell_volumes).sum(), power, max_relative = 1e-12);
+        assert_eq!(source[[0, 3]], 0.0);
+        assert!(source[[0, 1]] > 1.0);
+
+        // As células consumidas recebem o estado da célula exposta
+        let mut field = Array2::from_shape_fn((3, 4), |(_, j)| j as f64);
+        model.fill_consumed(&mut field);
+        assert_eq!(field[[0, 3]], 1.0);
+        assert_eq!(field[[1, 3]], 2.0);
+        assert_eq!(field[[2, 3]], 3.0);
+        assert!(BedConsumptionConfig { vapor_fraction_threshold: 0.0, conversion_threshold: None }.validate().is_err());
+    }
+}
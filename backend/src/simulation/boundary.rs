@@ -0,0 +1,557 @@
+// Implementação das condições de contorno das fronteiras do domínio cilíndrico
+//
+// Cada fronteira (parede lateral, topo, base e eixo) recebe uma condição independente.
+// Todas são escritas como uma troca linear com a célula da fronteira,
+// Q = G·(T_ref - T) + q·A (W): a condição de Dirichlet usa a condutância da meia célula
+// até a face, a de Robin a condutância h·A da superfície e a de Neumann apenas o fluxo
+// prescrito. Assim a mesma forma entra no esquema explícito e na diagonal do ADI.
+//
+// A parede composta (refratário, isolante e carcaça) soma as resistências de condução
+// das camadas à resistência convectiva externa, referidas à área da face interna; na
+// parede lateral as camadas são cascas cilíndricas.
+//
+// A camisa de refrigeração troca com a água pela efetividade ε = 1 - exp(-NTU) de um
+// trocador com temperatura de parede uniforme, NTU = h·A/(ṁ·cp) sobre a área total da
+// camisa; a troca de cada face é referida à temperatura de entrada do refrigerante.
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::mesh::CylindricalMesh;
+
+/// Enumeração que representa a condição de contorno de uma fronteira
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BoundaryCondition {
+    /// Temperatura prescrita na face (°C)
+    Dirichlet {
+        /// Temperatura da face (°C)
+        temperature: f64,
+    },
+    /// Fluxo de calor prescrito, positivo entrando no domínio (W/m²; zero = adiabática)
+    Neumann {
+        /// Fluxo de calor (W/m²)
+        heat_flux: f64,
+    },
+    /// Troca convectiva com um meio externo: q = h·(T_ref - T)
+    Robin {
+        /// Coeficiente de troca (W/(m²·K))
+        coefficient: f64,
+        /// Temperatura do meio externo (°C)
+        reference_temperature: f64,
+    },
+    /// Parede composta por camadas com perda convectiva externa
+    Layered(LayeredWall),
+    /// Camisa de refrigeração a água
+    CoolingJacket(CoolingJacket),
+}
+
+/// Calor específico da água de refrigeração (J/(kg·K))
+pub const WATER_SPECIFIC_HEAT: f64 = 4186.0;
+
+/// Estrutura que representa a camisa de refrigeração de uma fronteira
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoolingJacket {
+    /// Temperatura de entrada do refrigerante (°C)
+    pub inlet_temperature: f64,
+    /// Vazão mássica do refrigerante (kg/s)
+    pub mass_flow_rate: f64,
+    /// Coeficiente de troca entre a parede e o refrigerante (W/(m²·K))
+    pub heat_transfer_coefficient: f64,
+    /// Calor específico do refrigerante (J/(kg·K))
+    #[serde(default = "default_coolant_specific_heat")]
+    pub coolant_specific_heat: f64,
+}
+
+fn default_coolant_specific_heat() -> f64 {
+    WATER_SPECIFIC_HEAT
+}
+
+impl CoolingJacket {
+    /// Cria a camisa a água com a entrada, a vazão e o coeficiente informados
+    pub fn new(inlet_temperature: f64, mass_flow_rate: f64, heat_transfer_coefficient: f64) -> Self {
+        Self {
+            inlet_temperature,
+            mass_flow_rate,
+            heat_transfer_coefficient,
+            coolant_specific_heat: default_coolant_specific_heat(),
+        }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.inlet_temperature.is_finite() {
+            return Err("Temperatura de entrada do refrigerante inválida".to_string());
+        }
+        for (name, value) in [
+            ("Vazão do refrigerante", self.mass_flow_rate),
+            ("Coeficiente de troca da camisa", self.heat_transfer_coefficient),
+            ("Calor específico do refrigerante", self.coolant_specific_heat),
+        ] {
+            if value <= 0.0 || !value.is_finite() {
+                return Err(format!("{} deve ser positivo", name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Taxa de capacidade térmica do refrigerante ṁ·cp (W/K)
+    pub fn capacity_rate(&self) -> f64 {
+        self.mass_flow_rate * self.coolant_specific_heat
+    }
+
+    /// Coeficiente efetivo (W/(m²·K)), referido à temperatura de entrada, de uma camisa
+    /// de área total `jacket_area`
+    pub fn effective_coefficient(&self, jacket_area: f64) -> f64 {
+        let capacity = self.capacity_rate();
+        let ntu = self.heat_transfer_coefficient * jacket_area / capacity;
+        capacity * (1.0 - (-ntu).exp()) / jacket_area
+    }
+
+    /// Temperatura de saída do refrigerante para a potência absorvida (°C)
+    pub fn outlet_temperature(&self, absorbed_power: f64) -> f64 {
+        self.inlet_temperature + absorbed_power / self.capacity_rate()
+    }
+}
+
+/// Estrutura que representa uma camada da parede composta
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WallLayer {
+    /// Nome da camada (ex.: refratário, isolante, carcaça de aço)
+    pub name: String,
+    /// Espessura (m)
+    pub thickness: f64,
+    /// Condutividade térmica (W/(m·K))
+    pub thermal_conductivity: f64,
+}
+
+impl WallLayer {
+    /// Cria uma camada com a espessura e a condutividade informadas
+    pub fn new(name: &str, thickness: f64, thermal_conductivity: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            thickness,
+            thermal_conductivity,
+        }
+    }
+}
+
+/// Estrutura que representa a parede composta, da face interna para a externa
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayeredWall {
+    /// Camadas, da face quente (interna) para a carcaça
+    pub layers: Vec<WallLayer>,
+    /// Coeficiente de convecção externo da carcaça (W/(m²·K))
+    pub external_coefficient: f64,
+    /// Temperatura ambiente externa (°C)
+    pub ambient_temperature: f64,
+}
+
+impl LayeredWall {
+    /// Cria a parede refratário + isolante + carcaça de aço
+    pub fn refractory_insulation_shell(
+        refractory: (f64, f64),
+        insulation: (f64, f64),
+        shell: (f64, f64),
+        external_coefficient: f64,
+        ambient_temperature: f64,
+    ) -> Self {
+        Self {
+            layers: vec![
+                WallLayer::new("refratário", refractory.0, refractory.1),
+                WallLayer::new("isolante", insulation.0, insulation.1),
+                WallLayer::new("carcaça", shell.0, shell.1),
+            ],
+            external_coefficient,
+            ambient_temperature,
+        }
+    }
+
+    /// Valida as camadas e a troca externa
+    pub fn validate(&self) -> Result<(), String> {
+        for layer in &self.layers {
+            if layer.thickness <= 0.0 || !layer.thickness.is_finite() {
+                return Err(format!("Espessura da camada {} deve ser positiva", layer.name));
+            }
+            if layer.thermal_conductivity <= 0.0 || !layer.thermal_conductivity.is_finite() {
+                return Err(format!("Condutividade da camada {} deve ser positiva", layer.name));
+            }
+        }
+        if self.external_coefficient <= 0.0 || !self.external_coefficient.is_finite() {
+            return Err("Coeficiente de convecção externo da parede deve ser positivo".to_string());
+        }
+        if !self.ambient_temperature.is_finite() {
+            return Err("Temperatura ambiente externa da parede inválida".to_string());
+        }
+        Ok(())
+    }
+
+    /// Resistências em série (m²·K/W) referidas à área da face interna: uma por camada,
+    /// seguida da convecção externa
+    ///
+    /// Com `inner_radius` as camadas são cascas cilíndricas a partir desse raio; sem ele,
+    /// placas planas.
+    pub fn resistances(&self, inner_radius: Option<f64>) -> Vec<f64> {
+        let mut resistances = Vec::with_capacity(self.layers.len() + 1);
+        let mut radius = inner_radius.unwrap_or(0.0);
+        for layer in &self.layers {
+            let outer = radius + layer.thickness;
+            resistances.push(match inner_radius {
+                Some(inner) => inner * (outer / radius).ln() / layer.thermal_conductivity,
+                None => layer.thickness / layer.thermal_conductivity,
+            });
+            radius = outer;
+        }
+        let area_ratio = inner_radius.map_or(1.0, |inner| inner / radius);
+        resistances.push(area_ratio / self.external_coefficient);
+        resistances
+    }
+
+    /// Resistência total da face interna ao ambiente (m²·K/W)
+    pub fn total_resistance(&self, inner_radius: Option<f64>) -> f64 {
+        self.resistances(inner_radius).iter().sum()
+    }
+
+    /// Temperaturas das interfaces (°C), da face interna na temperatura `inner_temperature`
+    /// até a superfície externa da carcaça
+    pub fn interface_temperatures(&self, inner_temperature: f64, inner_radius: Option<f64>) -> Vec<f64> {
+        let resistances = self.resistances(inner_radius);
+        let flux = (inner_temperature - self.ambient_temperature) / resistances.iter().sum::<f64>();
+        let mut temperature = inner_temperature;
+        let mut temperatures = vec![temperature];
+        for resistance in &resistances[..self.layers.len()] {
+            temperature -= flux * resistance;
+            temperatures.push(temperature);
+        }
+        temperatures
+    }
+}
+
+impl Default for BoundaryCondition {
+    fn default() -> Self {
+        BoundaryCondition::Neumann { heat_flux: 0.0 }
+    }
+}
+
+impl BoundaryCondition {
+    /// Valida a condição
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            BoundaryCondition::Dirichlet { temperature } if !temperature.is_finite() => {
+                Err("Temperatura prescrita da fronteira inválida".to_string())
+            }
+            BoundaryCondition::Neumann { heat_flux } if !heat_flux.is_finite() => {
+                Err("Fluxo prescrito da fronteira inválido".to_string())
+            }
+            BoundaryCondition::Robin { coefficient, reference_temperature }
+                if *coefficient < 0.0 || !coefficient.is_finite() || !reference_temperature.is_finite() =>
+            {
+                Err("Coeficiente de troca da fronteira deve ser não negativo e a temperatura de referência finita".to_string())
+            }
+            BoundaryCondition::Layered(wall) => wall.validate(),
+            BoundaryCondition::CoolingJacket(jacket) => jacket.validate(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Condutância (W/K) e termo independente (W) da troca pela face
+    ///
+    /// `conductivity` é a condutividade da célula, usada na condição de Dirichlet.
+    pub fn exchange(&self, conductivity: f64, face: &BoundaryFace) -> (f64, f64) {
+        match *self {
+            BoundaryCondition::Dirichlet { temperature } => {
+                let conductance = conductivity * face.area / face.distance;
+                (conductance, conductance * temperature)
+            }
+            BoundaryCondition::Neumann { heat_flux } => (0.0, heat_flux * face.area),
+            BoundaryCondition::Robin { coefficient, reference_temperature } => {
+                let conductance = coefficient * face.area;
+                (conductance, conductance * reference_temperature)
+            }
+            BoundaryCondition::Layered(ref wall) => {
+                let conductance = face.area / wall.total_resistance(face.inner_radius);
+                (conductance, conductance * wall.ambient_temperature)
+            }
+            BoundaryCondition::CoolingJacket(ref jacket) => {
+                let conductance = jacket.effective_coefficient(face.total_area) * face.area;
+                (conductance, conductance * jacket.inlet_temperature)
+            }
+        }
+    }
+}
+
+/// Enumeração que representa as fronteiras do domínio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundarySide {
+    /// Parede lateral (r = R)
+    Wall,
+    /// Topo (z = H)
+    Top,
+    /// Base (z = 0)
+    Bottom,
+    /// Eixo (r = 0)
+    Axis,
+}
+
+/// Estrutura que representa a geometria da face de uma célula em uma fronteira
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundaryFace {
+    /// Área da face (m²)
+    pub area: f64,
+    /// Distância do nó à face usada na condição de Dirichlet (m)
+    pub distance: f64,
+    /// Raio da face, se cilíndrica (parede lateral)
+    pub inner_radius: Option<f64>,
+    /// Área total da fronteira (m²)
+    pub total_area: f64,
+}
+
+impl BoundarySide {
+    /// Todas as fronteiras
+    pub const ALL: [BoundarySide; 4] = [BoundarySide::Wall, BoundarySide::Top, BoundarySide::Bottom, BoundarySide::Axis];
+
+    /// Indica se a fronteira é radial (eixo e parede) ou axial (base e topo)
+    pub fn is_radial(self) -> bool {
+        matches!(self, BoundarySide::Wall | BoundarySide::Axis)
+    }
+
+    /// Indica se a célula (i, j) toca a fronteira
+    pub fn contains(self, mesh: &CylindricalMesh, i: usize, j: usize) -> bool {
+        match self {
+            BoundarySide::Wall => i == mesh.nr - 1,
+            BoundarySide::Top => j == mesh.nz - 1,
+            BoundarySide::Bottom => j == 0,
+            BoundarySide::Axis => i == 0,
+        }
+    }
+
+    /// Área da face da célula (i, j) na fronteira (m²)
+    fn face_area(self, mesh: &CylindricalMesh, i: usize, j: usize) -> f64 {
+        match self {
+            BoundarySide::Wall => 2.0 * std::f64::consts::PI * mesh.radius * mesh.axial_cell_height(j),
+            BoundarySide::Top | BoundarySide::Bottom => mesh.axial_face_area(i),
+            BoundarySide::Axis => mesh.radial_face_area(0, j),
+        }
+    }
+
+    /// Área total da fronteira (m²)
+    pub fn total_area(self, mesh: &CylindricalMesh) -> f64 {
+        match self {
+            BoundarySide::Wall | BoundarySide::Axis => (0..mesh.nz).map(|j| self.face_area(mesh, 0, j)).sum(),
+            BoundarySide::Top | BoundarySide::Bottom => (0..mesh.nr).map(|i| self.face_area(mesh, i, 0)).sum(),
+        }
+    }
+
+    /// Geometria da face da célula (i, j) na fronteira
+    pub fn face(self, mesh: &CylindricalMesh, i: usize, j: usize) -> BoundaryFace {
+        let distance = match self {
+            BoundarySide::Wall => mesh.radial_spacing(mesh.nr - 2),
+            BoundarySide::Top => mesh.axial_spacing(mesh.nz - 2),
+            BoundarySide::Bottom => mesh.axial_spacing(0),
+            BoundarySide::Axis => mesh.radial_spacing(0),
+        } / 2.0;
+        BoundaryFace {
+            area: self.face_area(mesh, i, j),
+            distance,
+            inner_radius: (self == BoundarySide::Wall).then_some(mesh.radius),
+            total_area: self.total_area(mesh),
+        }
+    }
+}
+
+/// Estrutura que representa as condições de contorno de todas as fronteiras
+///
+/// O padrão mantém todas as fronteiras adiabáticas.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BoundaryConditions {
+    /// Parede lateral (r = R)
+    #[serde(default)]
+    pub wall: BoundaryCondition,
+    /// Topo (z = H)
+    #[serde(default)]
+    pub top: BoundaryCondition,
+    /// Base (z = 0)
+    #[serde(default)]
+    pub bottom: BoundaryCondition,
+    /// Eixo (r = 0); sem face física, a troca se dá pela face externa do volume do eixo,
+    /// representando um núcleo central (eletrodo ou tubo). Adiabática = simetria
+    #[serde(default)]
+    pub axis: BoundaryCondition,
+}
+
+/// Estrutura que representa a troca de uma célula com as fronteiras, separada por direção
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BoundaryExchange {
+    /// Condutância (W/K) e termo independente (W) das fronteiras radiais (eixo e parede)
+    pub radial: (f64, f64),
+    /// Condutância (W/K) e termo independente (W) das fronteiras axiais (base e topo)
+    pub axial: (f64, f64),
+}
+
+impl BoundaryExchange {
+    /// Condutância total com as fronteiras (W/K)
+    pub fn conductance(&self) -> f64 {
+        self.radial.0 + self.axial.0
+    }
+
+    /// Calor recebido das fronteiras na temperatura `temperature` (W)
+    pub fn heat_flow(&self, temperature: f64) -> f64 {
+        self.radial.1 + self.axial.1 - self.conductance() * temperature
+    }
+}
+
+impl BoundaryConditions {
+    /// Valida as condições de todas as fronteiras
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, condition) in [("parede", &self.wall), ("topo", &self.top), ("base", &self.bottom), ("eixo", &self.axis)] {
+            condition.validate().map_err(|e| format!("Fronteira {}: {}", name, e))?;
+        }
+        Ok(())
+    }
+
+    /// Indica se todas as fronteiras são adiabáticas
+    pub fn is_adiabatic(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Condição de uma fronteira
+    pub fn condition(&self, side: BoundarySide) -> &BoundaryCondition {
+        match side {
+            BoundarySide::Wall => &self.wall,
+            BoundarySide::Top => &self.top,
+            BoundarySide::Bottom => &self.bottom,
+            BoundarySide::Axis => &self.axis,
+        }
+    }
+
+    /// Troca da célula (i, j) com as fronteiras que ela toca
+    pub fn exchange(&self, mesh: &CylindricalMesh, conductivity: &Array2<f64>, i: usize, j: usize) -> BoundaryExchange {
+        let mut exchange = BoundaryExchange::default();
+        for side in BoundarySide::ALL {
+            if !side.contains(mesh, i, j) {
+                continue;
+            }
+            let (conductance, constant) = self.condition(side).exchange(conductivity[[i, j]], &side.face(mesh, i, j));
+            let total = if side.is_radial() { &mut exchange.radial } else { &mut exchange.axial };
+            total.0 += conductance;
+            total.1 += constant;
+        }
+        exchange
+    }
+
+    /// Calor recebido pelo domínio através de uma fronteira no estado informado (W)
+    pub fn side_heat_flow(&self, side: BoundarySide, mesh: &CylindricalMesh, conductivity: &Array2<f64>, temperature: &Array2<f64>) -> f64 {
+        let condition = self.condition(side);
+        let mut total = 0.0;
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                if side.contains(mesh, i, j) {
+                    let (conductance, constant) = condition.exchange(conductivity[[i, j]], &side.face(mesh, i, j));
+                    total += constant - conductance * temperature[[i, j]];
+                }
+            }
+        }
+        total
+    }
+
+    /// Calor total recebido pelo domínio através das fronteiras no estado informado (W)
+    pub fn total_heat_flow(&self, mesh: &CylindricalMesh, conductivity: &Array2<f64>, temperature: &Array2<f64>) -> f64 {
+        BoundarySide::ALL.iter()
+            .map(|&side| self.side_heat_flow(side, mesh, conductivity, temperature))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_boundary_exchange_per_face() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 3, 3, 4);
+        let conductivity = Array2::<f64>::from_elem((3, 3), 2.0);
+        let temperature = Array2::<f64>::from_elem((3, 3), 100.0);
+
+        // Padrão adiabático: nenhuma troca
+        let adiabatic = BoundaryConditions::default();
+        assert!(adiabatic.is_adiabatic());
+        assert_eq!(adiabatic.total_heat_flow(&mesh, &conductivity, &temperature), 0.0);
+
+        // Fluxo prescrito no topo integra a área do topo; Robin na parede perde h·A·ΔT
+        let conditions = BoundaryConditions {
+            wall: BoundaryCondition::Robin { coefficient: 10.0, reference_temperature: 25.0 },
+            top: BoundaryCondition::Neumann { heat_flux: 1000.0 },
+            ..BoundaryConditions::default()
+        };
+        let top_area = std::f64::consts::PI * 0.5 * 0.5;
+        let wall_area: f64 = (0..3).map(|j| 2.0 * std::f64::consts::PI * 0.5 * mesh.axial_cell_height(j)).sum();
+        assert_relative_eq!(
+            conditions.total_heat_flow(&mesh, &conductivity, &temperature),
+            1000.0 * top_area - 10.0 * wall_area * 75.0,
+            max_relative = 1e-12
+        );
+
+        // Dirichlet na base: condutância da meia célula, nula na temperatura prescrita
+        let dirichlet = BoundaryConditions { bottom: BoundaryCondition::Dirichlet { temperature: 100.0 }, ..BoundaryConditions::default() };
+        let exchange = dirichlet.exchange(&mesh, &conductivity, 1, 0);
+        assert_relative_eq!(exchange.axial.0, 2.0 * mesh.axial_face_area(1) / 0.25, max_relative = 1e-12);
+        assert_eq!(exchange.radial, (0.0, 0.0));
+        assert_relative_eq!(exchange.heat_flow(100.0), 0.0, epsilon = 1e-9);
+        assert!(BoundaryCondition::Robin { coefficient: -1.0, reference_temperature: 25.0 }.validate().is_err());
+    }
+
+    #[test]
+    fn test_layered_wall_resistance_network() {
+        let wall = LayeredWall::refractory_insulation_shell((0.2, 1.5), (0.1, 0.1), (0.01, 45.0), 10.0, 25.0);
+
+        // Placas planas: resistências em série somadas à convecção externa
+        let planar = 0.2 / 1.5 + 0.1 / 0.1 + 0.01 / 45.0 + 1.0 / 10.0;
+        assert_relative_eq!(wall.total_resistance(None), planar, max_relative = 1e-12);
+
+        // Cascas cilíndricas referidas à face interna de raio 0,5 m
+        let expected = 0.5 * (0.7f64 / 0.5).ln() / 1.5 + 0.5 * (0.8f64 / 0.7).ln() / 0.1
+            + 0.5 * (0.81f64 / 0.8).ln() / 45.0 + 0.5 / 0.81 / 10.0;
+        assert_relative_eq!(wall.total_resistance(Some(0.5)), expected, max_relative = 1e-12);
+
+        // O isolante concentra a queda de temperatura e a carcaça fica próxima do ambiente
+        let temperatures = wall.interface_temperatures(1200.0, None);
+        assert_eq!(temperatures.len(), 4);
+        assert!(temperatures[1] - temperatures[2] > temperatures[0] - temperatures[1]);
+        let flux = (1200.0 - 25.0) / planar;
+        assert_relative_eq!(temperatures[3] - 25.0, flux / 10.0, max_relative = 1e-9);
+
+        // A troca pela face equivale a uma condutância área / resistência total
+        let face = BoundaryFace { area: 3.0, distance: 0.1, inner_radius: None, total_area: 3.0 };
+        let (conductance, constant) = BoundaryCondition::Layered(wall.clone()).exchange(2.0, &face);
+        assert_relative_eq!(conductance, 3.0 / planar, max_relative = 1e-12);
+        assert_relative_eq!(constant, conductance * 25.0, max_relative = 1e-12);
+        assert!(LayeredWall { external_coefficient: 0.0, ..wall }.validate().is_err());
+    }
+
+    #[test]
+    fn test_cooling_jacket_effectiveness() {
+        let jacket = CoolingJacket::new(30.0, 0.5, 500.0);
+        let capacity = 0.5 * WATER_SPECIFIC_HEAT;
+
+        // Vazão alta: o coeficiente efetivo tende ao coeficiente de troca
+        assert_relative_eq!(jacket.effective_coefficient(1e-3), 500.0, max_relative = 1e-3);
+        // Área grande: a potência fica limitada pela capacidade do refrigerante
+        assert_relative_eq!(jacket.effective_coefficient(1e4) * 1e4, capacity, max_relative = 1e-9);
+        assert_relative_eq!(jacket.outlet_temperature(capacity * 10.0), 40.0, max_relative = 1e-12);
+
+        // Camisa na parede inteira: potência absorvida igual a ε·ṁ·cp·(T - T_in)
+        let mesh = CylindricalMesh::new(1.0, 0.5, 3, 3, 4);
+        let conductivity = Array2::<f64>::from_elem((3, 3), 2.0);
+        let temperature = Array2::<f64>::from_elem((3, 3), 330.0);
+        let conditions = BoundaryConditions { wall: BoundaryCondition::CoolingJacket(jacket.clone()), ..BoundaryConditions::default() };
+        let area = BoundarySide::Wall.total_area(&mesh);
+        let effectiveness = 1.0 - (-500.0 * area / capacity).exp();
+        assert_relative_eq!(
+            -conditions.side_heat_flow(BoundarySide::Wall, &mesh, &conductivity, &temperature),
+            effectiveness * capacity * 300.0,
+            max_relative = 1e-9
+        );
+        assert_eq!(conditions.side_heat_flow(BoundarySide::Top, &mesh, &conductivity, &temperature), 0.0);
+        assert!(CoolingJacket::new(30.0, 0.0, 500.0).validate().is_err());
+    }
+}
@@ -0,0 +1,223 @@
+// Implementação do cálculo dos números adimensionais do caso configurado
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::materials::{MaterialProperties, STEFAN_BOLTZMANN};
+use crate::simulation::mesh::MeshGrading;
+use crate::simulation::solver::{SimulationParameters, SolverScheme};
+
+/// Limite de estabilidade do Fourier da célula para o esquema explícito bidimensional
+pub const EXPLICIT_FOURIER_LIMIT: f64 = 0.5;
+
+/// Biot abaixo do qual o corpo responde como capacitância concentrada
+pub const LUMPED_BIOT_LIMIT: f64 = 0.1;
+
+/// Stefan abaixo do qual o calor latente domina a mudança de fase
+pub const LATENT_DOMINATED_STEFAN: f64 = 0.1;
+
+/// Enumeração que representa a fronteira avaliada pelo número de Biot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BiotBoundary {
+    /// Parede lateral (comprimento característico: raio)
+    SideWall,
+    /// Base e topo (comprimento característico: meia altura)
+    EndFaces,
+}
+
+/// Estrutura que representa o número de Biot em uma fronteira
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BiotNumber {
+    /// Fronteira avaliada
+    pub boundary: BiotBoundary,
+    /// Comprimento característico (m)
+    pub characteristic_length: f64,
+    /// Coeficiente de troca efetivo (convecção + radiação linearizada) (W/(m²·K))
+    pub heat_transfer_coefficient: f64,
+    /// Número de Biot h·L/k
+    pub value: f64,
+}
+
+/// Estrutura que representa os números adimensionais de um material do caso
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialDimensionlessNumbers {
+    /// Nome do material
+    pub material: String,
+    /// Difusividade térmica na temperatura de referência (m²/s)
+    pub thermal_diffusivity: f64,
+    /// Fourier da célula por passo: α·dt·(1/dr² + 1/dz²), com os menores espaçamentos da malha
+    pub cell_fourier: f64,
+    /// Fourier global da simulação: α·t_total / R²
+    pub global_fourier: f64,
+    /// Números de Biot nas fronteiras
+    pub biot: Vec<BiotNumber>,
+    /// Número de Stefan cp·(T_fusão - T_inicial) / L, quando há mudança de fase
+    pub stefan: Option<f64>,
+}
+
+/// Estrutura que representa o relatório de números adimensionais
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionlessReport {
+    /// Temperatura de referência das propriedades (°C)
+    pub reference_temperature: f64,
+    /// Números por material (principal primeiro, depois as zonas)
+    pub materials: Vec<MaterialDimensionlessNumbers>,
+    /// Avisos derivados dos números calculados
+    pub warnings: Vec<String>,
+}
+
+/// Coeficiente de radiação linearizado εσ(T² + T∞²)(T + T∞) (W/(m²·K))
+fn linearized_radiation_coefficient(emissivity: f64, temperature: f64, ambient_temperature: f64) -> f64 {
+    let t = temperature + 273.15;
+    let t_inf = ambient_temperature + 273.15;
+    emissivity * STEFAN_BOLTZMANN * (t * t + t_inf * t_inf) * (t + t_inf)
+}
+
+/// Calcula os números adimensionais de um material
+fn material_numbers(params: &SimulationParameters, material: &MaterialProperties, reference_temperature: f64) -> MaterialDimensionlessNumbers {
+    let density = material.get_density(reference_temperature);
+    let specific_heat = material.get_specific_heat(reference_temperature);
+    let conductivity = material.get_thermal_conductivity(reference_temperature);
+    let diffusivity = if density > 0.0 && specific_heat > 0.0 {
+        conductivity / (density * specific_heat)
+    } else {
+        0.0
+    };
+
+    // Menor espaçamento em cada direção (malhas graduadas limitam o passo pela célula mais fina)
+    let min_spacing = |grading: MeshGrading, length: f64, n: usize| {
+        let coords = grading.coordinates(length, n.max(2));
+        coords.windows(2).into_iter().map(|w| w[1] - w[0]).fold(f64::INFINITY, f64::min)
+    };
+    let dr = min_spacing(params.radial_grading, params.radius, params.nr);
+    let dz = min_spacing(params.axial_grading, params.height, params.nz);
+    let cell_fourier = diffusivity * params.time_step * (1.0 / (dr * dr) + 1.0 / (dz * dz));
+    let global_fourier = diffusivity * params.total_time / (params.radius * params.radius);
+
+    let mut h = 0.0;
+    if params.enable_convection {
+        h += params.convection_coefficient;
+    }
+    if params.enable_radiation {
+        h += linearized_radiation_coefficient(material.get_emissivity(reference_temperature), reference_temperature, params.ambient_temperature);
+    }
+    let biot = [(BiotBoundary::SideWall, params.radius), (BiotBoundary::EndFaces, params.height / 2.0)]
+        .iter()
+        .map(|&(boundary, length)| BiotNumber {
+            boundary,
+            characteristic_length: length,
+            heat_transfer_coefficient: h,
+            value: if conductivity > 0.0 { h * length / conductivity } else { f64::INFINITY },
+        })
+        .collect();
+
+    let stefan = match (params.enable_phase_changes, material.melting_point, material.latent_heat_fusion) {
+        (true, Some(melting_point), Some(latent_heat)) if latent_heat > 0.0 => {
+            Some(specific_heat * (melting_point - params.initial_temperature).abs() / latent_heat)
+        }
+        _ => None,
+    };
+
+    MaterialDimensionlessNumbers {
+        material: material.name.clone(),
+        thermal_diffusivity: diffusivity,
+        cell_fourier,
+        global_fourier,
+        biot,
+        stefan,
+    }
+}
+
+/// Calcula os números adimensionais do caso e os avisos correspondentes
+///
+/// As propriedades são avaliadas na temperatura inicial; o coeficiente de radiação
+/// linearizado usa a mesma temperatura, representando o início do aquecimento.
+pub fn calculate_dimensionless_numbers(params: &SimulationParameters) -> DimensionlessReport {
+    let reference_temperature = params.initial_temperature;
+    let mut materials = vec![material_numbers(params, &params.material, reference_temperature)];
+    if let Some(zones) = &params.material_zones {
+        materials.extend(zones.iter().map(|(_, material)| material_numbers(params, material, reference_temperature)));
+    }
+
+    let mut warnings = Vec::new();
+    for numbers in &materials {
+        if params.solver_scheme == SolverScheme::Explicit
+            && params.adaptive_time_step.is_none()
+            && numbers.cell_fourier > EXPLICIT_FOURIER_LIMIT
+        {
+            warnings.push(format!(
+                "{}: Fourier da célula {:.2} acima do limite explícito {:.2}; reduza o passo de tempo, use ADI ou o passo adaptativo",
+                numbers.material, numbers.cell_fourier, EXPLICIT_FOURIER_LIMIT
+            ));
+        }
+        if numbers.global_fourier < 0.01 {
+            warnings.push(format!(
+                "{}: Fourier global {:.3} — o calor penetra pouco no domínio durante a simulação",
+                numbers.material, numbers.global_fourier
+            ));
+        }
+        for biot in &numbers.biot {
+            if biot.value < LUMPED_BIOT_LIMIT {
+                warnings.push(format!(
+                    "{}: Biot {:.3} em {:?} — gradientes internos pequenos (capacitância concentrada)",
+                    numbers.material, biot.value, biot.boundary
+                ));
+            }
+        }
+        if let Some(stefan) = numbers.stefan {
+            if stefan < LATENT_DOMINATED_STEFAN {
+                warnings.push(format!(
+                    "{}: Stefan {:.3} — calor latente dominante; frentes de fusão exigem passos de tempo menores",
+                    numbers.material, stefan
+                ));
+            }
+        }
+    }
+
+    for message in &warnings {
+        warn!("{}", message);
+    }
+
+    DimensionlessReport {
+        reference_temperature,
+        materials,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_dimensionless_numbers() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 11, 11);
+        params.material = MaterialProperties::new("Teste", 1000.0, 1000.0, 10.0);
+        params.material.melting_point = Some(1025.0);
+        params.material.latent_heat_fusion = Some(2.0e6);
+        params.enable_radiation = false;
+        params.convection_coefficient = 20.0;
+        params.time_step = 80.0;
+        params.total_time = 1000.0;
+
+        let report = calculate_dimensionless_numbers(&params);
+        let numbers = &report.materials[0];
+
+        // α = 1e-5 m²/s; dr = 0,05 m; dz = 0,1 m
+        assert_relative_eq!(numbers.thermal_diffusivity, 1e-5, epsilon = 1e-15);
+        assert_relative_eq!(numbers.cell_fourier, 1e-5 * 80.0 * (400.0 + 100.0), epsilon = 1e-12);
+        assert_relative_eq!(numbers.global_fourier, 0.04, epsilon = 1e-12);
+        assert_relative_eq!(numbers.biot[0].value, 20.0 * 0.5 / 10.0, epsilon = 1e-12);
+        assert_relative_eq!(numbers.stefan.unwrap(), 1000.0 * 1000.0 / 2.0e6, epsilon = 1e-12);
+
+        // Fourier da célula 0,4: sem aviso; acima do limite explícito: aviso (exceto com ADI)
+        assert!(!report.warnings.iter().any(|w| w.contains("limite explícito")));
+        params.time_step = 200.0;
+        let report = calculate_dimensionless_numbers(&params);
+        assert!(report.warnings.iter().any(|w| w.contains("limite explícito")));
+        params.solver_scheme = SolverScheme::Adi;
+        let report = calculate_dimensionless_numbers(&params);
+        assert!(!report.warnings.iter().any(|w| w.contains("limite explícito")));
+    }
+}
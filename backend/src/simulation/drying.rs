@@ -0,0 +1,461 @@
+// Implementação do assistente de programação de secagem e encharque do refratário
+
+use ndarray::s;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, atomic::AtomicBool};
+
+use crate::simulation::alarms::{evaluate_alarms, AlarmCondition, AlarmEvent, AlarmRule, AlarmSeverity};
+use crate::simulation::parametric::{nelder_mead, NelderMeadOptions};
+use crate::simulation::physics::PowerSchedule;
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Estrutura que representa os limites operacionais do refratário durante a secagem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefractoryLimits {
+    /// Taxa máxima de aquecimento permitida (°C/h)
+    pub max_heating_rate: f64,
+    /// Temperatura máxima permitida no revestimento (°C)
+    pub max_temperature: f64,
+    /// Temperaturas de patamar de encharque (°C), em ordem crescente
+    pub hold_temperatures: Vec<f64>,
+    /// Duração de cada patamar de encharque (s)
+    pub hold_duration: f64,
+    /// Temperatura média a ser atingida ao fim do aquecimento (°C)
+    pub target_temperature: f64,
+}
+
+/// Estrutura que representa a configuração do assistente de secagem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryingAdvisorConfig {
+    /// Limites do refratário
+    pub limits: RefractoryLimits,
+    /// Potência máxima disponível por tocha (kW)
+    pub max_power: f64,
+    /// Taxa mínima de rampa de potência avaliada (kW/h)
+    pub min_ramp_rate: f64,
+    /// Taxa máxima de rampa de potência avaliada (kW/h)
+    pub max_ramp_rate: f64,
+    /// Tolerância da temperatura média em torno de cada patamar de encharque (°C)
+    pub hold_tolerance: f64,
+    /// Opções do otimizador Nelder-Mead que conduz a busca
+    pub optimizer: NelderMeadOptions,
+    /// Tempo adicional simulado após o fim da programação (s)
+    pub settle_time: f64,
+}
+
+/// Estrutura que representa um patamar de encharque de uma programação
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoakHold {
+    /// Temperatura do patamar (°C)
+    pub temperature: f64,
+    /// Potência mantida durante o patamar (kW)
+    pub power: f64,
+    /// Início do patamar (s)
+    pub start_time: f64,
+    /// Fim do patamar (s)
+    pub end_time: f64,
+}
+
+/// Estrutura que representa uma programação de potência proposta pelo assistente
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryingScheduleProposal {
+    /// Taxa de rampa de potência utilizada (kW/h)
+    pub ramp_rate: f64,
+    /// Patamares de encharque da programação, em ordem de temperatura
+    pub holds: Vec<SoakHold>,
+    /// Programação de potência aplicada a todas as tochas
+    pub schedule: PowerSchedule,
+    /// Tempo para atingir a temperatura alvo (s), se atingida
+    pub completion_time: Option<f64>,
+    /// Alarmes de violação dos limites (taxa de aquecimento, temperatura máxima e patamares)
+    pub violations: Vec<AlarmEvent>,
+    /// Soma das violações relativas dos limites (zero quando todos são respeitados)
+    pub constraint_violation: f64,
+    /// Indica se a programação respeita todos os limites e atinge o alvo
+    pub feasible: bool,
+    /// Número de simulações avaliadas durante a busca
+    pub evaluated_candidates: usize,
+}
+
+impl DryingAdvisorConfig {
+    /// Cria uma nova configuração com valores padrão para a busca
+    pub fn new(limits: RefractoryLimits, max_power: f64) -> Self {
+        Self {
+            limits,
+            max_power,
+            min_ramp_rate: 1.0,
+            max_ramp_rate: 500.0,
+            hold_tolerance: 25.0,
+            optimizer: NelderMeadOptions { max_evaluations: 40, ..Default::default() },
+            settle_time: 3600.0,
+        }
+    }
+}
+
+/// Estrutura que representa o assistente de programação de secagem
+///
+/// A programação tem uma rampa de potência comum e um nível de potência por patamar de
+/// encharque. A busca pelo método de Nelder-Mead minimiza o tempo para atingir a
+/// temperatura alvo; cada candidato é uma simulação completa, e a taxa de aquecimento,
+/// a temperatura máxima e os patamares são verificados no campo simulado como restrições
+/// (penalidade proporcional à violação relativa).
+pub struct DryingScheduleAdvisor {
+    /// Parâmetros base da simulação (material, malha e tochas)
+    base_parameters: SimulationParameters,
+    /// Configuração da busca
+    config: DryingAdvisorConfig,
+    /// Temperaturas de patamar entre a temperatura inicial e o alvo, em ordem crescente
+    hold_temperatures: Vec<f64>,
+}
+
+impl DryingScheduleAdvisor {
+    /// Cria um novo assistente para os parâmetros base e a configuração especificados
+    pub fn new(base_parameters: SimulationParameters, config: DryingAdvisorConfig) -> Result<Self, String> {
+        if config.limits.max_heating_rate <= 0.0 {
+            return Err("Taxa máxima de aquecimento deve ser positiva".to_string());
+        }
+        if config.max_power <= 0.0 {
+            return Err("Potência máxima deve ser positiva".to_string());
+        }
+        if config.min_ramp_rate <= 0.0 || config.max_ramp_rate < config.min_ramp_rate {
+            return Err("Intervalo de taxas de rampa inválido".to_string());
+        }
+        if config.hold_tolerance <= 0.0 {
+            return Err("Tolerância dos patamares deve ser positiva".to_string());
+        }
+        if config.limits.target_temperature <= base_parameters.initial_temperature {
+            return Err("Temperatura alvo deve ser maior que a temperatura inicial".to_string());
+        }
+        config.optimizer.validate()?;
+
+        let t0 = base_parameters.initial_temperature;
+        let mut hold_temperatures: Vec<f64> = config.limits.hold_temperatures.iter()
+            .copied()
+            .filter(|&t| t > t0 && t < config.limits.target_temperature)
+            .collect();
+        hold_temperatures.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(Self {
+            base_parameters,
+            config,
+            hold_temperatures,
+        })
+    }
+
+    /// Constrói a programação de potência para uma taxa de rampa (kW/h) e as potências
+    /// dos patamares (kW, uma por temperatura de patamar)
+    ///
+    /// A potência sobe linearmente até cada nível de patamar, é mantida por
+    /// `hold_duration` e, após o último patamar, sobe até a potência máxima.
+    pub fn build_schedule(&self, ramp_rate: f64, hold_powers: &[f64]) -> (PowerSchedule, Vec<SoakHold>) {
+        let mut points = vec![(0.0, 0.0)];
+        let mut holds = Vec::with_capacity(hold_powers.len());
+        let mut time = 0.0;
+        let mut power = 0.0;
+
+        for (&temperature, &hold_power) in self.hold_temperatures.iter().zip(hold_powers) {
+            let hold_power = hold_power.clamp(power, self.config.max_power);
+            time += (hold_power - power) / ramp_rate * 3600.0;
+            points.push((time, hold_power));
+            let start_time = time;
+            time += self.config.limits.hold_duration;
+            points.push((time, hold_power));
+            holds.push(SoakHold { temperature, power: hold_power, start_time, end_time: time });
+            power = hold_power;
+        }
+
+        time += (self.config.max_power - power) / ramp_rate * 3600.0;
+        points.push((time, self.config.max_power));
+
+        (PowerSchedule::new(points), holds)
+    }
+
+    /// Avalia uma programação executando a simulação completa e verificando os limites
+    pub fn evaluate_schedule(&self, ramp_rate: f64, hold_powers: &[f64]) -> Result<DryingScheduleProposal, String> {
+        let (schedule, holds) = self.build_schedule(ramp_rate, hold_powers);
+        let schedule_end = schedule.points.last().map_or(0.0, |p| p.0);
+
+        let mut params = self.base_parameters.clone();
+        for torch in &mut params.torches {
+            torch.set_power_schedule(schedule.clone());
+        }
+        params.total_time = schedule_end + self.config.settle_time;
+        params.time_steps = (params.total_time / params.time_step).ceil().max(1.0) as usize;
+
+        let mut solver = HeatSolver::new(params)?;
+        let results = solver.run(None, Arc::new(AtomicBool::new(false)))?;
+
+        let limits = &self.config.limits;
+        let rules = vec![
+            AlarmRule::new(
+                "max_heating_rate",
+                "Taxa de aquecimento do refratário acima do limite",
+                AlarmCondition::HeatingRateAbove(limits.max_heating_rate),
+                AlarmSeverity::Critical,
+            ),
+            AlarmRule::new(
+                "max_temperature",
+                "Temperatura do refratário acima do limite",
+                AlarmCondition::MaxTemperatureAbove(limits.max_temperature),
+                AlarmSeverity::Critical,
+            ),
+        ];
+        let mut violations = evaluate_alarms(&rules, &results);
+
+        let history = FieldHistory::from_results(&results);
+        let mut constraint_violation = (history.peak_heating_rate() / limits.max_heating_rate - 1.0).max(0.0)
+            + (history.peak_temperature() / limits.max_temperature - 1.0).max(0.0);
+        for hold in &holds {
+            if let Some((event, violation)) = self.check_hold(hold, &history) {
+                violations.push(event);
+                constraint_violation += violation;
+            }
+        }
+
+        let completion_time = history.mean.iter()
+            .position(|&mean| mean >= limits.target_temperature)
+            .map(|step| step as f64 * history.dt);
+        let feasible = constraint_violation == 0.0 && violations.is_empty() && completion_time.is_some();
+
+        Ok(DryingScheduleProposal {
+            ramp_rate,
+            holds,
+            schedule,
+            completion_time,
+            violations,
+            constraint_violation,
+            feasible,
+            evaluated_candidates: 1,
+        })
+    }
+
+    /// Procura a programação mais rápida que respeita os limites
+    ///
+    /// As variáveis de decisão, em [0, 1], são a taxa de rampa (escala logarítmica entre
+    /// `min_ramp_rate` e `max_ramp_rate`) e a fração da potência restante até a máxima
+    /// usada em cada patamar, o que mantém os níveis dos patamares crescentes. Retorna a
+    /// programação viável mais rápida avaliada ou, se nenhuma for viável, a de menor
+    /// violação.
+    pub fn find_fastest_schedule(&self) -> Result<DryingScheduleProposal, String> {
+        let dimensions = 1 + self.hold_temperatures.len();
+        let penalty_scale = self.longest_schedule() + self.config.settle_time;
+
+        let mut best: Option<(f64, DryingScheduleProposal)> = None;
+        let mut evaluated = 0;
+        let mut failure = None;
+        nelder_mead(dimensions, &self.config.optimizer, |point| {
+            if evaluated >= self.config.optimizer.max_evaluations {
+                return None;
+            }
+            let (ramp_rate, hold_powers) = self.decode_point(point);
+            let proposal = match self.evaluate_schedule(ramp_rate, &hold_powers) {
+                Ok(proposal) => proposal,
+                Err(e) => {
+                    failure = Some(e);
+                    return None;
+                }
+            };
+            evaluated += 1;
+
+            // Tempo até o alvo, penalizado pela violação relativa dos limites
+            let elapsed = proposal.completion_time.unwrap_or(penalty_scale);
+            let missed_target = if proposal.completion_time.is_some() { 0.0 } else { 1.0 };
+            let objective = elapsed + penalty_scale * (proposal.constraint_violation + missed_target);
+            if best.as_ref().is_none_or(|(value, _)| objective < *value) {
+                best = Some((objective, proposal));
+            }
+            Some(objective)
+        });
+        if let Some(e) = failure {
+            return Err(format!("Erro na simulação {} da busca de secagem: {}", evaluated + 1, e));
+        }
+
+        best.map(|(_, proposal)| DryingScheduleProposal { evaluated_candidates: evaluated, ..proposal })
+            .ok_or_else(|| "Nenhuma programação de secagem avaliada".to_string())
+    }
+
+    /// Converte um ponto em [0, 1]^d na taxa de rampa e nas potências dos patamares
+    fn decode_point(&self, point: &[f64]) -> (f64, Vec<f64>) {
+        let (min_rate, max_rate) = (self.config.min_ramp_rate, self.config.max_ramp_rate);
+        let ramp_rate = min_rate * (max_rate / min_rate).powf(point[0]);
+
+        let mut power = 0.0;
+        let hold_powers = point[1..].iter()
+            .map(|&fraction| {
+                power += fraction * (self.config.max_power - power);
+                power
+            })
+            .collect();
+        (ramp_rate, hold_powers)
+    }
+
+    /// Duração da programação mais lenta possível (rampa mínima até a potência máxima)
+    fn longest_schedule(&self) -> f64 {
+        self.config.max_power / self.config.min_ramp_rate * 3600.0
+            + self.hold_temperatures.len() as f64 * self.config.limits.hold_duration
+    }
+
+    /// Verifica um patamar no campo simulado
+    ///
+    /// Durante o patamar a temperatura média não pode passar da temperatura do patamar
+    /// mais a tolerância, e ao fim do patamar deve tê-la atingido menos a tolerância.
+    /// Retorna o alarme e a violação relativa (em unidades da tolerância).
+    fn check_hold(&self, hold: &SoakHold, history: &FieldHistory) -> Option<(AlarmEvent, f64)> {
+        let tolerance = self.config.hold_tolerance;
+        let first = (hold.start_time / history.dt).ceil() as usize;
+        let last = ((hold.end_time / history.dt).floor() as usize).min(history.mean.len().saturating_sub(1));
+        if first > last {
+            return None;
+        }
+
+        let (peak_step, peak) = (first..=last)
+            .map(|step| (step, history.mean[step]))
+            .fold((first, f64::NEG_INFINITY), |best, current| if current.1 > best.1 { current } else { best });
+        let overshoot = (peak - hold.temperature - tolerance).max(0.0);
+        let shortfall = (hold.temperature - tolerance - history.mean[last]).max(0.0);
+        if overshoot == 0.0 && shortfall == 0.0 {
+            return None;
+        }
+
+        let (description, step, value) = if overshoot > 0.0 {
+            ("Temperatura média acima do patamar de encharque", peak_step, peak)
+        } else {
+            ("Patamar de encharque não atingido", last, history.mean[last])
+        };
+        let event = AlarmEvent {
+            rule_id: format!("hold_{}", hold.temperature),
+            description: description.to_string(),
+            severity: AlarmSeverity::Critical,
+            time: step as f64 * history.dt,
+            step,
+            value,
+        };
+        Some((event, (overshoot + shortfall) / tolerance))
+    }
+}
+
+/// Estrutura que representa o histórico do campo de temperatura usado nas restrições
+struct FieldHistory {
+    /// Passo de tempo (s)
+    dt: f64,
+    /// Temperatura média por passo (°C)
+    mean: Vec<f64>,
+    /// Temperatura máxima por passo (°C)
+    max: Vec<f64>,
+    /// Maior taxa de aquecimento local por passo (°C/h)
+    heating_rate: Vec<f64>,
+}
+
+impl FieldHistory {
+    fn from_results(results: &SimulationResults) -> Self {
+        let n_steps = (results.executed_steps + 1).min(results.temperature.shape()[2]);
+        let dt = results.parameters.time_step;
+        let mut history = Self { dt, mean: Vec::new(), max: Vec::new(), heating_rate: Vec::new() };
+
+        for step in 0..n_steps {
+            let field = results.temperature.slice(s![.., .., step]);
+            history.mean.push(field.iter().sum::<f64>() / field.len().max(1) as f64);
+            history.max.push(field.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+            let rate = if step == 0 || dt <= 0.0 {
+                0.0
+            } else {
+                let previous = results.temperature.slice(s![.., .., step - 1]);
+                field.iter().zip(previous.iter())
+                    .map(|(&t_new, &t_old)| (t_new - t_old) / dt * 3600.0)
+                    .fold(0.0, f64::max)
+            };
+            history.heating_rate.push(rate);
+        }
+
+        history
+    }
+
+    fn peak_heating_rate(&self) -> f64 {
+        self.heating_rate.iter().copied().fold(0.0, f64::max)
+    }
+
+    fn peak_temperature(&self) -> f64 {
+        self.max.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PlasmaTorch;
+
+    fn create_test_advisor(hold_temperatures: Vec<f64>) -> DryingScheduleAdvisor {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.ntheta = 4;
+        params.time_step = 60.0;
+        params.enable_radiation = false;
+        params.enable_convection = false;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.9, 180.0, 0.0, 100.0, 0.01, 5000.0));
+
+        let limits = RefractoryLimits {
+            max_heating_rate: 300.0,
+            max_temperature: 1500.0,
+            hold_temperatures,
+            hold_duration: 1800.0,
+            target_temperature: 180.0,
+        };
+
+        let mut config = DryingAdvisorConfig::new(limits, 100.0);
+        config.min_ramp_rate = 20.0;
+        config.max_ramp_rate = 400.0;
+        DryingScheduleAdvisor::new(params, config).unwrap()
+    }
+
+    #[test]
+    fn test_build_schedule_with_holds() {
+        // Patamares fora do intervalo (25 °C -> 180 °C) são ignorados
+        let advisor = create_test_advisor(vec![120.0, 70.0, 500.0]);
+        let (schedule, holds) = advisor.build_schedule(10.0, &[30.0, 60.0]);
+
+        // Início + (rampa, patamar) por temperatura de encharque + rampa final
+        assert_eq!(schedule.points.len(), 6);
+        assert_eq!(holds.len(), 2);
+        assert_eq!(holds[0].temperature, 70.0);
+        assert!((holds[0].start_time - 3.0 * 3600.0).abs() < 1e-9);
+        assert!((holds[0].end_time - holds[0].start_time - 1800.0).abs() < 1e-9);
+        assert_eq!(schedule.points[3].1, 60.0);
+        assert_eq!(schedule.points[5].1, 100.0);
+
+        // Potências de patamar decrescentes são limitadas ao nível anterior
+        let (_, holds) = advisor.build_schedule(10.0, &[60.0, 30.0]);
+        assert_eq!(holds[1].power, 60.0);
+
+        // Rampas mais rápidas encurtam a programação
+        let (fast, _) = advisor.build_schedule(20.0, &[30.0, 60.0]);
+        assert!(fast.points[5].0 < schedule.points[5].0);
+    }
+
+    #[test]
+    fn test_hold_violation_is_flagged() {
+        let advisor = create_test_advisor(vec![70.0]);
+
+        // Potência de patamar baixa demais: o campo não atinge o patamar durante o encharque
+        let proposal = advisor.evaluate_schedule(50.0, &[20.0]).unwrap();
+        assert!(proposal.completion_time.is_some());
+        assert!(!proposal.feasible);
+        assert!(proposal.constraint_violation > 0.0);
+        assert!(proposal.violations.iter().any(|event| event.rule_id == "hold_70"));
+
+        let proposal = advisor.evaluate_schedule(50.0, &[40.0]).unwrap();
+        assert!(proposal.feasible);
+        assert!(proposal.violations.is_empty());
+    }
+
+    #[test]
+    fn test_find_fastest_schedule_respects_limits() {
+        let advisor = create_test_advisor(vec![70.0]);
+        let reference = advisor.evaluate_schedule(20.0, &[30.0]).unwrap();
+        assert!(reference.feasible);
+
+        let best = advisor.find_fastest_schedule().unwrap();
+        assert!(best.feasible);
+        assert!(best.violations.is_empty());
+        assert!(best.evaluated_candidates <= advisor.config.optimizer.max_evaluations);
+        assert!(best.completion_time.unwrap() < reference.completion_time.unwrap());
+    }
+}
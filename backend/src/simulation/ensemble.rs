@@ -0,0 +1,176 @@
+// Implementação das estatísticas de conjunto (média e desvio padrão por passo) entre execuções
+
+use ndarray::{s, Array2, Array3};
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::frames::{FrameEncoding, FramePacket};
+use crate::simulation::solver::SimulationResults;
+
+/// Enumeração que representa a estatística de conjunto de um campo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnsembleStatistic {
+    /// Temperatura média entre as execuções (°C)
+    Mean,
+    /// Desvio padrão amostral da temperatura entre as execuções (°C)
+    StdDev,
+}
+
+impl EnsembleStatistic {
+    /// Converte o código FFI (0 = média, 1 = desvio padrão) na estatística
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(EnsembleStatistic::Mean),
+            1 => Some(EnsembleStatistic::StdDev),
+            _ => None,
+        }
+    }
+}
+
+/// Estrutura que representa o acumulador das estatísticas de conjunto
+///
+/// As execuções são adicionadas uma a uma (algoritmo de Welford), sem manter os
+/// históricos anteriores em memória. Todas devem usar a mesma malha e o mesmo passo de
+/// tempo; o conjunto cobre os passos comuns a todas as execuções.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleAccumulator {
+    /// Número de execuções acumuladas
+    pub runs: usize,
+    /// Passo de tempo das execuções (s)
+    pub time_step: f64,
+    /// Média corrente (nr, nz, passos + 1)
+    mean: Array3<f64>,
+    /// Soma dos quadrados dos desvios (nr, nz, passos + 1)
+    m2: Array3<f64>,
+}
+
+impl EnsembleAccumulator {
+    /// Cria um acumulador a partir da primeira execução
+    pub fn new(first: &SimulationResults) -> Result<Self, String> {
+        if !first.has_full_precision_history() {
+            return Err("Estatísticas de conjunto requerem histórico em precisão total".to_string());
+        }
+        let steps = first.executed_steps + 1;
+        let mean = first.temperature.slice(s![.., .., 0..steps]).to_owned();
+        Ok(Self {
+            runs: 1,
+            time_step: first.parameters.time_step,
+            m2: Array3::zeros(mean.dim()),
+            mean,
+        })
+    }
+
+    /// Adiciona uma execução ao conjunto
+    pub fn add(&mut self, results: &SimulationResults) -> Result<(), String> {
+        if !results.has_full_precision_history() {
+            return Err("Estatísticas de conjunto requerem histórico em precisão total".to_string());
+        }
+        let (nr, nz, steps) = self.mean.dim();
+        if results.mesh.nr != nr || results.mesh.nz != nz {
+            return Err("Execuções do conjunto devem usar a mesma malha".to_string());
+        }
+        if (results.parameters.time_step - self.time_step).abs() > 1e-12 {
+            return Err("Execuções do conjunto devem usar o mesmo passo de tempo".to_string());
+        }
+
+        // Passos comuns: execuções mais curtas truncam o conjunto
+        let steps = steps.min(results.executed_steps + 1);
+        if steps < self.mean.dim().2 {
+            self.mean = self.mean.slice(s![.., .., 0..steps]).to_owned();
+            self.m2 = self.m2.slice(s![.., .., 0..steps]).to_owned();
+        }
+
+        self.runs += 1;
+        let n = self.runs as f64;
+        let values = results.temperature.slice(s![.., .., 0..steps]);
+        ndarray::Zip::from(&mut self.mean)
+            .and(&mut self.m2)
+            .and(&values)
+            .for_each(|mean, m2, &x| {
+                let delta = x - *mean;
+                *mean += delta / n;
+                *m2 += delta * (x - *mean);
+            });
+        Ok(())
+    }
+
+    /// Cria o acumulador com todas as execuções informadas
+    pub fn from_results(runs: &[SimulationResults]) -> Result<Self, String> {
+        let (first, rest) = runs.split_first().ok_or("Conjunto sem execuções")?;
+        let mut accumulator = Self::new(first)?;
+        for results in rest {
+            accumulator.add(results)?;
+        }
+        Ok(accumulator)
+    }
+
+    /// Número de passos executados comuns a todas as execuções
+    pub fn executed_steps(&self) -> usize {
+        self.mean.dim().2 - 1
+    }
+
+    /// Retorna o campo da estatística em um passo
+    pub fn field(&self, statistic: EnsembleStatistic, step: usize) -> Result<Array2<f64>, String> {
+        if step > self.executed_steps() {
+            return Err(format!("Passo {} fora do conjunto ({} passos)", step, self.executed_steps()));
+        }
+        let mean = self.mean.slice(s![.., .., step]);
+        Ok(match statistic {
+            EnsembleStatistic::Mean => mean.to_owned(),
+            EnsembleStatistic::StdDev => {
+                // Desvio amostral; nulo com uma única execução
+                let divisor = (self.runs.max(2) - 1) as f64;
+                let m2 = self.m2.slice(s![.., .., step]);
+                m2.mapv(|v| (v.max(0.0) / divisor).sqrt())
+            }
+        })
+    }
+
+    /// Retorna a estatística de um passo como pacote de quadro, no mesmo formato dos
+    /// quadros de temperatura (ver `simulation::frames`)
+    pub fn frame_packet(&self, statistic: EnsembleStatistic, step: usize, encoding: FrameEncoding) -> Result<FramePacket, String> {
+        let field = self.field(statistic, step)?;
+        Ok(FramePacket::new(step as u64, step as f64 * self.time_step, field.view(), encoding))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PlasmaTorch;
+    use crate::simulation::solver::{HeatSolver, SimulationParameters};
+    use approx::assert_relative_eq;
+    use std::sync::{Arc, atomic::AtomicBool};
+
+    fn run_with_power(power: f64, time_steps: usize) -> SimulationResults {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = time_steps;
+        params.time_step = 1.0;
+        params.total_time = time_steps as f64;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, power, 0.01, 5000.0));
+        HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap()
+    }
+
+    #[test]
+    fn test_ensemble_mean_and_std_dev() {
+        let runs = vec![run_with_power(8.0, 5), run_with_power(10.0, 5), run_with_power(12.0, 4)];
+        let ensemble = EnsembleAccumulator::from_results(&runs).unwrap();
+        assert_eq!(ensemble.runs, 3);
+        assert_eq!(ensemble.executed_steps(), 4);
+
+        let values: Vec<f64> = runs.iter().map(|r| r.temperature[[0, 0, 4]]).collect();
+        let mean = values.iter().sum::<f64>() / 3.0;
+        let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 2.0).sqrt();
+        assert_relative_eq!(ensemble.field(EnsembleStatistic::Mean, 4).unwrap()[[0, 0]], mean, epsilon = 1e-9);
+        assert_relative_eq!(ensemble.field(EnsembleStatistic::StdDev, 4).unwrap()[[0, 0]], std_dev, epsilon = 1e-9);
+        assert!(std_dev > 0.0);
+
+        // Estado inicial idêntico em todas as execuções
+        let initial = ensemble.field(EnsembleStatistic::StdDev, 0).unwrap();
+        assert!(initial.iter().all(|&v| v.abs() < 1e-9));
+
+        let packet = ensemble.frame_packet(EnsembleStatistic::StdDev, 4, FrameEncoding::F32).unwrap();
+        let decoded = FramePacket::decode(&packet.encode()).unwrap();
+        assert_eq!(decoded.header.step, 4);
+        assert!(ensemble.field(EnsembleStatistic::Mean, 5).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+// Implementação das exportações em segundo plano (séries VTK, quadros de animação e CSV)
+//
+// As exportações percorrem o histórico passo a passo, gravando diretamente em disco,
+// reportando progresso e verificando o cancelamento entre passos (ver `simulation::jobs`).
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::simulation::jobs::{JobContext, JobId, JobQueue};
+use crate::simulation::snapshot::{encode_png_rgb, field_range, render_field_rgb};
+use crate::simulation::solver::SimulationResults;
+use crate::simulation::visualization::ColorScale;
+
+/// Enumeração que representa o formato de uma exportação em segundo plano
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportJobFormat {
+    /// Um arquivo VTK legado por passo e um índice `.vtk.series` (ParaView)
+    VtkSeries,
+    /// Quadros PNG do mapa de calor com escala de cores fixa (animação)
+    PngFrames,
+    /// Arquivo CSV único em formato longo (tempo, r, z, temperatura)
+    Csv,
+}
+
+/// Estrutura que representa a configuração de uma exportação
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportJobConfig {
+    /// Formato de saída
+    pub format: ExportJobFormat,
+    /// Diretório de saída (criado se necessário)
+    pub output_dir: String,
+    /// Prefixo dos arquivos gerados
+    #[serde(default = "default_base_name")]
+    pub base_name: String,
+    /// Intervalo entre passos exportados
+    #[serde(default = "default_stride")]
+    pub stride: usize,
+    /// Maior dimensão dos quadros PNG (pixels)
+    #[serde(default = "default_frame_size")]
+    pub frame_size: usize,
+    /// Escala de cores dos quadros PNG
+    #[serde(default = "default_color_scale")]
+    pub color_scale: ColorScale,
+}
+
+fn default_base_name() -> String {
+    "temperature".to_string()
+}
+
+fn default_stride() -> usize {
+    1
+}
+
+fn default_frame_size() -> usize {
+    512
+}
+
+fn default_color_scale() -> ColorScale {
+    ColorScale::BlueToRed
+}
+
+impl ExportJobConfig {
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        if self.output_dir.trim().is_empty() {
+            return Err("Diretório de saída da exportação não informado".to_string());
+        }
+        if self.base_name.trim().is_empty() || self.base_name.contains(['/', '\\']) {
+            return Err(format!("Prefixo de arquivo inválido: {}", self.base_name));
+        }
+        if self.stride == 0 {
+            return Err("Intervalo entre passos exportados deve ser positivo".to_string());
+        }
+        if self.frame_size == 0 || self.frame_size > 4096 {
+            return Err("Dimensão dos quadros deve estar entre 1 e 4096 pixels".to_string());
+        }
+        Ok(())
+    }
+
+    /// Passos exportados (sempre inclui o último passo executado)
+    fn steps(&self, results: &SimulationResults) -> Vec<usize> {
+        let mut steps: Vec<usize> = (0..=results.executed_steps).step_by(self.stride).collect();
+        if steps.last() != Some(&results.executed_steps) {
+            steps.push(results.executed_steps);
+        }
+        steps
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> String {
+    format!("Erro ao gravar {}: {}", path.display(), e)
+}
+
+/// Executa uma exportação, reportando progresso e verificando o cancelamento entre passos
+///
+/// Retorna o arquivo principal gerado (índice da série, diretório dos quadros ou CSV).
+pub fn run_export(results: &SimulationResults, config: &ExportJobConfig, context: &JobContext) -> Result<PathBuf, String> {
+    config.validate()?;
+    let dir = PathBuf::from(&config.output_dir);
+    fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+    let steps = config.steps(results);
+    let time_step = results.parameters.time_step;
+    let mesh = &results.mesh;
+
+    let output = match config.format {
+        ExportJobFormat::VtkSeries => {
+            let mut series = Vec::with_capacity(steps.len());
+            for (n, &step) in steps.iter().enumerate() {
+                context.check_cancelled()?;
+                let field = results.temperature_at(step)?;
+                let name = format!("{}_{:05}.vtk", config.base_name, step);
+                let path = dir.join(&name);
+                let mut file = BufWriter::new(File::create(&path).map_err(|e| io_error(&path, e))?);
+                // Grade estruturada no plano r-z (x = r, z = altura)
+                let mut write = || -> std::io::Result<()> {
+                    writeln!(file, "# vtk DataFile Version 3.0")?;
+                    writeln!(file, "Plasma Furnace Simulation t={}", step as f64 * time_step)?;
+                    writeln!(file, "ASCII\nDATASET STRUCTURED_GRID")?;
+                    writeln!(file, "DIMENSIONS {} 1 {}", mesh.nr, mesh.nz)?;
+                    writeln!(file, "POINTS {} float", mesh.nr * mesh.nz)?;
+                    for j in 0..mesh.nz {
+                        for i in 0..mesh.nr {
+                            writeln!(file, "{} 0 {}", mesh.r_coords[i], mesh.z_coords[j])?;
+                        }
+                    }
+                    writeln!(file, "POINT_DATA {}\nSCALARS temperature float 1\nLOOKUP_TABLE default", mesh.nr * mesh.nz)?;
+                    for j in 0..mesh.nz {
+                        for i in 0..mesh.nr {
+                            writeln!(file, "{}", field[[i, j]])?;
+                        }
+                    }
+                    file.flush()
+                };
+                write().map_err(|e| io_error(&path, e))?;
+                series.push(serde_json::json!({ "name": name, "time": step as f64 * time_step }));
+                context.set_progress((n + 1) as f32 / (steps.len() + 1) as f32);
+            }
+            let index = dir.join(format!("{}.vtk.series", config.base_name));
+            let json = serde_json::json!({ "file-series-version": "1.0", "files": series });
+            fs::write(&index, json.to_string()).map_err(|e| io_error(&index, e))?;
+            index
+        }
+        ExportJobFormat::PngFrames => {
+            // Escala fixa em todo o histórico para que as cores sejam comparáveis entre quadros
+            let mut range = (f64::INFINITY, f64::NEG_INFINITY);
+            for &step in &steps {
+                context.check_cancelled()?;
+                let (min, max) = field_range(&results.temperature_at(step)?);
+                range = (range.0.min(min), range.1.max(max));
+            }
+            for (n, &step) in steps.iter().enumerate() {
+                context.check_cancelled()?;
+                let field = results.temperature_at(step)?;
+                let (width, height, pixels) = render_field_rgb(&field, config.frame_size, range, config.color_scale);
+                let path = dir.join(format!("{}_{:05}.png", config.base_name, step));
+                fs::write(&path, encode_png_rgb(width, height, &pixels)).map_err(|e| io_error(&path, e))?;
+                context.set_progress((n + 1) as f32 / steps.len() as f32);
+            }
+            dir
+        }
+        ExportJobFormat::Csv => {
+            let path = dir.join(format!("{}.csv", config.base_name));
+            let mut file = BufWriter::new(File::create(&path).map_err(|e| io_error(&path, e))?);
+            writeln!(file, "time,r,z,temperature").map_err(|e| io_error(&path, e))?;
+            for (n, &step) in steps.iter().enumerate() {
+                context.check_cancelled()?;
+                let field = results.temperature_at(step)?;
+                let time = step as f64 * time_step;
+                for i in 0..mesh.nr {
+                    for j in 0..mesh.nz {
+                        writeln!(file, "{},{},{},{}", time, mesh.r_coords[i], mesh.z_coords[j], field[[i, j]])
+                            .map_err(|e| io_error(&path, e))?;
+                    }
+                }
+                context.set_progress((n + 1) as f32 / steps.len() as f32);
+            }
+            file.flush().map_err(|e| io_error(&path, e))?;
+            path
+        }
+    };
+    Ok(output)
+}
+
+/// Enfileira uma exportação dos resultados na fila de tarefas
+pub fn submit_export(queue: &JobQueue, results: SimulationResults, config: ExportJobConfig) -> Result<JobId, String> {
+    config.validate()?;
+    queue.submit("export", move |context| {
+        let path = run_export(&results, &config, context)?.display().to_string();
+        context.set_result(serde_json::json!({ "path": path, "format": config.format }));
+        Ok(Some(path))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::jobs::JobStatus;
+    use crate::simulation::physics::PlasmaTorch;
+    use crate::simulation::solver::{HeatSolver, SimulationParameters};
+    use std::sync::{Arc, atomic::AtomicBool};
+    use std::time::Duration;
+
+    #[test]
+    fn test_background_exports() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("plasma_export_worker_{}", std::process::id()));
+        let config = |format| ExportJobConfig {
+            format,
+            output_dir: dir.display().to_string(),
+            base_name: "t".to_string(),
+            stride: 2,
+            frame_size: 32,
+            color_scale: ColorScale::Grayscale,
+        };
+
+        let queue = JobQueue::new();
+        let vtk = submit_export(&queue, results.clone(), config(ExportJobFormat::VtkSeries)).unwrap();
+        let png = submit_export(&queue, results.clone(), config(ExportJobFormat::PngFrames)).unwrap();
+        let csv = submit_export(&queue, results, config(ExportJobFormat::Csv)).unwrap();
+        for id in [vtk, png, csv] {
+            let info = queue.wait(id, Duration::from_secs(10)).unwrap();
+            assert_eq!(info.status, JobStatus::Completed, "{:?}", info.message);
+            assert_eq!(queue.result(id).unwrap()["path"].as_str(), info.output.as_deref());
+        }
+
+        // Passos 0, 2, 4 e o último (5)
+        let series: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("t.vtk.series")).unwrap()).unwrap();
+        assert_eq!(series["files"].as_array().unwrap().len(), 4);
+        assert!(fs::read(dir.join("t_00005.png")).unwrap().starts_with(&[0x89, b'P', b'N', b'G']));
+        let csv_lines = fs::read_to_string(dir.join("t.csv")).unwrap().lines().count();
+        assert_eq!(csv_lines, 1 + 4 * 25);
+
+        let mut invalid = config(ExportJobFormat::Csv);
+        invalid.stride = 0;
+        assert!(invalid.validate().is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,809 @@
+// Implementação do pacote binário de quadros de temperatura para transmissão (FFI e streaming)
+//
+// Layout do pacote (little-endian), compartilhado por todos os consumidores:
+//
+// | Offset | Tamanho | Campo                                     |
+// |--------|---------|-------------------------------------------|
+// | 0      | 4       | Assinatura `PHTF`                         |
+// | 4      | 1       | Versão do formato                         |
+// | 5      | 1       | Codificação (0 = f32, 1 = u16 quantizado) |
+// | 6      | 2       | Reservado (zero)                          |
+// | 8      | 8       | Passo de tempo (u64)                      |
+// | 16     | 8       | Tempo (s, f64)                            |
+// | 24     | 8       | Temperatura mínima (°C, f64)              |
+// | 32     | 8       | Temperatura máxima (°C, f64)              |
+// | 40     | 4       | nr (u32)                                  |
+// | 44     | 4       | nz (u32)                                  |
+// | 48     | ...     | nr × nz valores, índice `i * nz + j`      |
+//
+// O canal compartilhado (`SharedFrameChannel`) usa outro layout, de palavras de 64 bits
+// nativas, lido diretamente na memória pelo frontend:
+//
+// | Palavra | Campo                                                     |
+// |---------|-----------------------------------------------------------|
+// | 0       | Contador de sequência (ímpar = escrita em andamento)      |
+// | 1       | Passo de tempo (u64)                                      |
+// | 2       | Tempo (s, bits de f64)                                    |
+// | 3       | nr (u64)                                                  |
+// | 4       | nz (u64)                                                  |
+// | 5..8    | Reservado (zero)                                          |
+// | 8       | nr × nz temperaturas (bits de f64), índice `i * nz + j`   |
+
+use ndarray::{s, Array2, Array3, ArrayView2};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::simulation::solver::SimulationResults;
+
+/// Assinatura dos pacotes de quadro
+pub const FRAME_PACKET_MAGIC: [u8; 4] = *b"PHTF";
+
+/// Versão atual do formato de pacote
+pub const FRAME_PACKET_VERSION: u8 = 1;
+
+/// Tamanho do cabeçalho do pacote (bytes)
+pub const FRAME_HEADER_SIZE: usize = 48;
+
+/// Enumeração que representa a codificação do payload de um quadro
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameEncoding {
+    /// Temperaturas em ponto flutuante de 32 bits
+    F32,
+    /// Temperaturas quantizadas em 16 bits com escala mín/máx do quadro
+    U16,
+}
+
+impl FrameEncoding {
+    /// Converte o código do cabeçalho na codificação
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(FrameEncoding::F32),
+            1 => Some(FrameEncoding::U16),
+            _ => None,
+        }
+    }
+
+    /// Retorna o código do cabeçalho
+    pub fn code(&self) -> u8 {
+        match self {
+            FrameEncoding::F32 => 0,
+            FrameEncoding::U16 => 1,
+        }
+    }
+
+    /// Retorna o tamanho de cada valor do payload (bytes)
+    pub fn value_size(&self) -> usize {
+        match self {
+            FrameEncoding::F32 => 4,
+            FrameEncoding::U16 => 2,
+        }
+    }
+}
+
+/// Estrutura que representa o cabeçalho de um quadro
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameHeader {
+    /// Passo de tempo
+    pub step: u64,
+    /// Tempo (s)
+    pub time: f64,
+    /// Temperatura mínima do quadro (°C)
+    pub min_temperature: f64,
+    /// Temperatura máxima do quadro (°C)
+    pub max_temperature: f64,
+    /// Número de nós na direção radial
+    pub nr: u32,
+    /// Número de nós na direção axial
+    pub nz: u32,
+    /// Codificação do payload
+    pub encoding: FrameEncoding,
+}
+
+/// Estrutura que representa a configuração de armazenamento dos quadros do histórico
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameStorageConfig {
+    /// Armazenar quadros de reprodução quantizados em 16 bits
+    pub quantize_playback: bool,
+    /// Manter o histórico completo em precisão total para análise
+    ///
+    /// Quando desabilitado (requer `quantize_playback`), apenas o estado final é
+    /// mantido em precisão total e os demais quadros vêm da reprodução quantizada.
+    pub retain_full_precision: bool,
+}
+
+impl Default for FrameStorageConfig {
+    fn default() -> Self {
+        Self {
+            quantize_playback: false,
+            retain_full_precision: true,
+        }
+    }
+}
+
+/// Estrutura que representa um quadro quantizado em 16 bits com escala mín/máx própria
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantizedFrame {
+    /// Temperatura mínima do quadro (°C)
+    pub min_temperature: f64,
+    /// Temperatura máxima do quadro (°C)
+    pub max_temperature: f64,
+    /// Valores quantizados, índice `i * nz + j`
+    pub data: Vec<u16>,
+}
+
+impl QuantizedFrame {
+    /// Quantiza um campo de temperatura
+    pub fn from_field(field: ArrayView2<f64>) -> Self {
+        let (min_temperature, max_temperature) = if field.is_empty() {
+            (0.0, 0.0)
+        } else {
+            (
+                field.iter().cloned().fold(f64::INFINITY, f64::min),
+                field.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            )
+        };
+
+        Self {
+            min_temperature,
+            max_temperature,
+            data: field.iter().map(|&t| quantize_u16(t, min_temperature, max_temperature)).collect(),
+        }
+    }
+
+    /// Reconstrói o campo de temperatura (nr, nz)
+    pub fn to_field(&self, nr: usize, nz: usize) -> Result<Array2<f64>, String> {
+        let values = self.data.iter()
+            .map(|&q| dequantize_u16(q, self.min_temperature, self.max_temperature))
+            .collect();
+        Array2::from_shape_vec((nr, nz), values)
+            .map_err(|e| format!("Dimensões do quadro quantizado inválidas: {}", e))
+    }
+}
+
+/// Estrutura que representa o histórico de quadros quantizados para reprodução
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantizedFrameHistory {
+    /// Número de nós na direção radial
+    pub nr: usize,
+    /// Número de nós na direção axial
+    pub nz: usize,
+    /// Passo de tempo entre quadros (s)
+    pub time_step: f64,
+    /// Quadros, um por passo de tempo (incluindo o estado inicial)
+    pub frames: Vec<QuantizedFrame>,
+}
+
+impl QuantizedFrameHistory {
+    /// Cria um histórico vazio
+    pub fn new(nr: usize, nz: usize, time_step: f64) -> Self {
+        Self {
+            nr,
+            nz,
+            time_step,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Quantiza um histórico completo (nr, nz, passos)
+    pub fn from_history(history: &Array3<f64>, time_step: f64) -> Self {
+        let (nr, nz, steps) = history.dim();
+        let mut quantized = Self::new(nr, nz, time_step);
+        for step in 0..steps {
+            quantized.push(history.slice(s![.., .., step]));
+        }
+        quantized
+    }
+
+    /// Adiciona um quadro ao histórico
+    pub fn push(&mut self, field: ArrayView2<f64>) {
+        self.frames.push(QuantizedFrame::from_field(field));
+    }
+
+    /// Retorna o número de quadros
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Indica se o histórico está vazio
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Reconstrói o campo de temperatura de um passo
+    pub fn frame(&self, step: usize) -> Result<Array2<f64>, String> {
+        let frame = self.frames.get(step)
+            .ok_or_else(|| format!("Quadro {} fora dos limites [0, {})", step, self.frames.len()))?;
+        frame.to_field(self.nr, self.nz)
+    }
+
+    /// Memória ocupada pelos valores quantizados (bytes)
+    pub fn memory_bytes(&self) -> usize {
+        self.frames.iter()
+            .map(|f| f.data.len() * std::mem::size_of::<u16>() + 2 * std::mem::size_of::<f64>())
+            .sum()
+    }
+}
+
+/// Estrutura que representa um nível da pirâmide temporal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyramidLevel {
+    /// Intervalo entre quadros do nível (passos)
+    pub stride: usize,
+    /// Quadros quantizados nos passos múltiplos de `stride` (incluindo o passo 0)
+    pub frames: QuantizedFrameHistory,
+}
+
+impl PyramidLevel {
+    /// Retorna o passo do quadro com o índice informado
+    pub fn step_of(&self, index: usize) -> usize {
+        index * self.stride
+    }
+}
+
+/// Estrutura que representa a pirâmide temporal de quadros para navegação rápida
+///
+/// O nível de intervalo 1 é o próprio histórico; os níveis grossos armazenam cópias
+/// quantizadas de cada N-ésimo quadro, contíguas em memória.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemporalPyramid {
+    /// Níveis grossos, em ordem crescente de intervalo
+    pub levels: Vec<PyramidLevel>,
+}
+
+impl TemporalPyramid {
+    /// Cria uma pirâmide vazia com os intervalos informados (intervalos <= 1 são ignorados)
+    pub fn new(nr: usize, nz: usize, time_step: f64, strides: &[usize]) -> Self {
+        let mut strides: Vec<usize> = strides.iter().cloned().filter(|&s| s > 1).collect();
+        strides.sort_unstable();
+        strides.dedup();
+
+        Self {
+            levels: strides.into_iter()
+                .map(|stride| PyramidLevel {
+                    stride,
+                    frames: QuantizedFrameHistory::new(nr, nz, time_step * stride as f64),
+                })
+                .collect(),
+        }
+    }
+
+    /// Registra o quadro de um passo de tempo nos níveis cujo intervalo o divide
+    pub fn record(&mut self, step: usize, field: ArrayView2<f64>) {
+        for level in &mut self.levels {
+            if step.is_multiple_of(level.stride) && level.frames.len() == step / level.stride {
+                level.frames.push(field);
+            }
+        }
+    }
+
+    /// Retorna os intervalos disponíveis (incluindo o intervalo 1 do histórico completo)
+    pub fn strides(&self) -> Vec<usize> {
+        std::iter::once(1).chain(self.levels.iter().map(|l| l.stride)).collect()
+    }
+
+    /// Retorna o nível com o intervalo informado
+    pub fn level(&self, stride: usize) -> Option<&PyramidLevel> {
+        self.levels.iter().find(|l| l.stride == stride)
+    }
+
+    /// Escolhe o menor intervalo que mantém no máximo `max_frames` quadros na janela visível
+    pub fn select_stride(&self, visible_steps: usize, max_frames: usize) -> usize {
+        let max_frames = max_frames.max(1);
+        self.strides().into_iter()
+            .find(|&stride| visible_steps / stride <= max_frames)
+            .unwrap_or_else(|| self.levels.last().map_or(1, |l| l.stride))
+    }
+
+    /// Retorna o quadro grosso mais próximo (anterior ou igual) ao passo informado
+    pub fn nearest_frame(&self, stride: usize, step: usize) -> Result<(usize, Array2<f64>), String> {
+        let level = self.level(stride)
+            .ok_or_else(|| format!("Nível da pirâmide temporal com intervalo {} não existe", stride))?;
+        if level.frames.is_empty() {
+            return Err(format!("Nível da pirâmide temporal com intervalo {} está vazio", stride));
+        }
+
+        let index = (step / stride).min(level.frames.len() - 1);
+        Ok((level.step_of(index), level.frames.frame(index)?))
+    }
+}
+
+/// Estrutura que representa um quadro de temperatura pronto para transmissão
+#[derive(Debug, Clone)]
+pub struct FramePacket {
+    /// Cabeçalho do quadro
+    pub header: FrameHeader,
+    /// Campo de temperatura (nr, nz), reconstruído no caso quantizado
+    pub temperature: Array2<f64>,
+}
+
+/// Quantiza um valor para 16 bits na escala [min, max]
+pub fn quantize_u16(value: f64, min: f64, max: f64) -> u16 {
+    let range = max - min;
+    if range <= 0.0 || !range.is_finite() {
+        return 0;
+    }
+    (((value - min) / range).clamp(0.0, 1.0) * u16::MAX as f64).round() as u16
+}
+
+/// Reconstrói um valor quantizado em 16 bits na escala [min, max]
+pub fn dequantize_u16(value: u16, min: f64, max: f64) -> f64 {
+    min + (max - min) * value as f64 / u16::MAX as f64
+}
+
+impl FramePacket {
+    /// Cria um quadro a partir de um campo de temperatura
+    pub fn new(step: u64, time: f64, temperature: ArrayView2<f64>, encoding: FrameEncoding) -> Self {
+        let (nr, nz) = temperature.dim();
+        let min_temperature = temperature.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max_temperature = temperature.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let (min_temperature, max_temperature) = if temperature.is_empty() {
+            (0.0, 0.0)
+        } else {
+            (min_temperature, max_temperature)
+        };
+
+        Self {
+            header: FrameHeader {
+                step,
+                time,
+                min_temperature,
+                max_temperature,
+                nr: nr as u32,
+                nz: nz as u32,
+                encoding,
+            },
+            temperature: temperature.to_owned(),
+        }
+    }
+
+    /// Cria um quadro a partir de um passo do histórico de resultados
+    pub fn from_results(results: &SimulationResults, step: usize, encoding: FrameEncoding) -> Result<Self, String> {
+        let field = results.temperature_at(step)?;
+        Ok(Self::new(step as u64, step as f64 * results.parameters.time_step, field.view(), encoding))
+    }
+
+    /// Serializa o quadro no formato binário
+    pub fn encode(&self) -> Vec<u8> {
+        let header = &self.header;
+        let count = self.temperature.len();
+        let mut bytes = Vec::with_capacity(FRAME_HEADER_SIZE + count * header.encoding.value_size());
+
+        bytes.extend_from_slice(&FRAME_PACKET_MAGIC);
+        bytes.push(FRAME_PACKET_VERSION);
+        bytes.push(header.encoding.code());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&header.step.to_le_bytes());
+        bytes.extend_from_slice(&header.time.to_le_bytes());
+        bytes.extend_from_slice(&header.min_temperature.to_le_bytes());
+        bytes.extend_from_slice(&header.max_temperature.to_le_bytes());
+        bytes.extend_from_slice(&header.nr.to_le_bytes());
+        bytes.extend_from_slice(&header.nz.to_le_bytes());
+
+        // Iteração em ordem lógica (i, j), independente do layout de memória do array
+        for &value in self.temperature.iter() {
+            match header.encoding {
+                FrameEncoding::F32 => bytes.extend_from_slice(&(value as f32).to_le_bytes()),
+                FrameEncoding::U16 => {
+                    let q = quantize_u16(value, header.min_temperature, header.max_temperature);
+                    bytes.extend_from_slice(&q.to_le_bytes());
+                }
+            }
+        }
+
+        bytes
+    }
+
+    /// Desserializa um quadro a partir do formato binário
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < FRAME_HEADER_SIZE {
+            return Err(format!("Pacote de quadro muito curto: {} bytes", bytes.len()));
+        }
+        if bytes[0..4] != FRAME_PACKET_MAGIC {
+            return Err("Assinatura do pacote de quadro inválida".to_string());
+        }
+        if bytes[4] != FRAME_PACKET_VERSION {
+            return Err(format!("Versão do pacote de quadro não suportada: {}", bytes[4]));
+        }
+        let encoding = FrameEncoding::from_code(bytes[5])
+            .ok_or_else(|| format!("Codificação de quadro desconhecida: {}", bytes[5]))?;
+
+        let read_u32 = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        let read_u64 = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let read_f64 = |offset: usize| f64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+
+        let header = FrameHeader {
+            step: read_u64(8),
+            time: read_f64(16),
+            min_temperature: read_f64(24),
+            max_temperature: read_f64(32),
+            nr: read_u32(40),
+            nz: read_u32(44),
+            encoding,
+        };
+
+        let nr = header.nr as usize;
+        let nz = header.nz as usize;
+        // Dimensões vêm do pacote: um cabeçalho malformado não pode estourar o cálculo
+        let expected = nr.checked_mul(nz)
+            .and_then(|cells| cells.checked_mul(encoding.value_size()))
+            .and_then(|payload| payload.checked_add(FRAME_HEADER_SIZE))
+            .ok_or_else(|| format!("Dimensões do quadro inválidas: {} x {}", nr, nz))?;
+        if bytes.len() != expected {
+            return Err(format!("Tamanho do pacote inválido: esperado {} bytes, recebido {}", expected, bytes.len()));
+        }
+
+        let payload = &bytes[FRAME_HEADER_SIZE..];
+        let mut temperature = Array2::<f64>::zeros((nr, nz));
+        for (index, value) in temperature.iter_mut().enumerate() {
+            *value = match encoding {
+                FrameEncoding::F32 => {
+                    let offset = index * 4;
+                    f32::from_le_bytes(payload[offset..offset + 4].try_into().unwrap()) as f64
+                }
+                FrameEncoding::U16 => {
+                    let offset = index * 2;
+                    let q = u16::from_le_bytes(payload[offset..offset + 2].try_into().unwrap());
+                    dequantize_u16(q, header.min_temperature, header.max_temperature)
+                }
+            };
+        }
+
+        Ok(Self { header, temperature })
+    }
+}
+
+/// Estrutura que representa o quadro de temperatura mais recente de uma execução
+#[derive(Debug, Clone)]
+pub struct LiveFrame {
+    /// Passo de tempo do quadro
+    pub step: usize,
+    /// Tempo do quadro (s)
+    pub time: f64,
+    /// Campo de temperatura (nr, nz)
+    pub temperature: Array2<f64>,
+}
+
+impl LiveFrame {
+    /// Cria um quadro vazio com as dimensões da malha, usado como buffer de escrita
+    pub fn new(nr: usize, nz: usize) -> Self {
+        Self { step: 0, time: 0.0, temperature: Array2::zeros((nr, nz)) }
+    }
+}
+
+/// Estrutura que representa o buffer duplo de quadros ao vivo de uma execução
+///
+/// O solucionador preenche seu próprio quadro de escrita e o publica trocando-o com o
+/// quadro publicado, de modo que o laço de simulação só trava o buffer durante a troca
+/// e os leitores (FFI) nunca observam um quadro parcialmente escrito.
+#[derive(Debug, Default)]
+pub struct LiveFrameBuffer {
+    published: Mutex<Option<LiveFrame>>,
+    sequence: AtomicU64,
+    channel: Mutex<Option<Arc<SharedFrameChannel>>>,
+}
+
+impl LiveFrameBuffer {
+    /// Cria um buffer sem quadro publicado
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publica o quadro de escrita; em troca, `frame` recebe o quadro publicado anterior
+    /// (reaproveitado como próximo buffer de escrita)
+    pub fn publish(&self, frame: &mut LiveFrame) {
+        if let Some(channel) = self.channel() {
+            channel.write(frame.step as u64, frame.time, frame.temperature.view());
+        }
+        let Ok(mut published) = self.published.lock() else {
+            return;
+        };
+        match published.as_mut() {
+            Some(current) if current.temperature.dim() == frame.temperature.dim() => std::mem::swap(current, frame),
+            _ => *published = Some(frame.clone()),
+        }
+        self.sequence.fetch_add(1, Ordering::Release);
+    }
+
+    /// Número de quadros publicados (muda a cada publicação)
+    pub fn sequence(&self) -> u64 {
+        self.sequence.load(Ordering::Acquire)
+    }
+
+    /// Aplica `read` ao quadro publicado mais recente, se houver, sem copiá-lo
+    pub fn with_latest<R>(&self, read: impl FnOnce(&LiveFrame) -> R) -> Option<R> {
+        self.published.lock().ok()?.as_ref().map(read)
+    }
+
+    /// Descarta o quadro publicado (nova execução)
+    pub fn clear(&self) {
+        if let Ok(mut published) = self.published.lock() {
+            *published = None;
+        }
+    }
+
+    /// Conecta (ou desconecta, com `None`) o canal compartilhado que recebe uma cópia de
+    /// cada quadro publicado; o quadro mais recente, se houver, é escrito já
+    pub fn attach_channel(&self, channel: Option<Arc<SharedFrameChannel>>) {
+        if let Some(channel) = &channel {
+            self.with_latest(|frame| channel.write(frame.step as u64, frame.time, frame.temperature.view()));
+        }
+        if let Ok(mut current) = self.channel.lock() {
+            *current = channel;
+        }
+    }
+
+    /// Canal compartilhado conectado, se houver
+    pub fn channel(&self) -> Option<Arc<SharedFrameChannel>> {
+        self.channel.lock().ok()?.clone()
+    }
+}
+
+/// Número de palavras de 64 bits do cabeçalho do canal compartilhado
+pub const SHARED_FRAME_HEADER_WORDS: usize = 8;
+
+/// Número máximo de tentativas de leitura consistente do canal
+const SHARED_FRAME_READ_ATTEMPTS: usize = 64;
+
+/// Estrutura que representa um canal de quadros em memória compartilhada
+///
+/// A região tem endereço e tamanho fixos durante toda a vida do canal, de modo que o
+/// frontend obtém o ponteiro uma única vez e lê os quadros sem cópias pela FFI. A
+/// consistência segue o protocolo de seqlock: o escritor torna o contador ímpar antes de
+/// escrever e par ao terminar; o leitor descarta a leitura se o contador estava ímpar ou
+/// mudou durante a leitura.
+pub struct SharedFrameChannel {
+    nr: usize,
+    nz: usize,
+    words: Box<[AtomicU64]>,
+    writer: Mutex<()>,
+}
+
+impl SharedFrameChannel {
+    /// Cria um canal para campos (nr, nz), ainda sem quadro
+    pub fn new(nr: usize, nz: usize) -> Self {
+        let words: Box<[AtomicU64]> = (0..SHARED_FRAME_HEADER_WORDS + nr * nz).map(|_| AtomicU64::new(0)).collect();
+        words[3].store(nr as u64, Ordering::Relaxed);
+        words[4].store(nz as u64, Ordering::Relaxed);
+        Self { nr, nz, words, writer: Mutex::new(()) }
+    }
+
+    /// Dimensões (nr, nz) dos campos do canal
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.nr, self.nz)
+    }
+
+    /// Endereço do início da região (cabeçalho)
+    pub fn as_ptr(&self) -> *const u8 {
+        self.words.as_ptr() as *const u8
+    }
+
+    /// Tamanho da região em bytes
+    pub fn size_bytes(&self) -> usize {
+        self.words.len() * std::mem::size_of::<u64>()
+    }
+
+    /// Contador de sequência atual (par = estável; metade = quadros escritos)
+    pub fn sequence(&self) -> u64 {
+        self.words[0].load(Ordering::Acquire)
+    }
+
+    /// Escreve um quadro; retorna `false` (sem escrever) se as dimensões não coincidem
+    pub fn write(&self, step: u64, time: f64, temperature: ArrayView2<f64>) -> bool {
+        if temperature.dim() != (self.nr, self.nz) {
+            return false;
+        }
+        let _writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let sequence = self.words[0].load(Ordering::Relaxed);
+        self.words[0].store(sequence.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        self.words[1].store(step, Ordering::Relaxed);
+        self.words[2].store(time.to_bits(), Ordering::Relaxed);
+        for (word, value) in self.words[SHARED_FRAME_HEADER_WORDS..].iter().zip(temperature.iter()) {
+            word.store(value.to_bits(), Ordering::Relaxed);
+        }
+
+        self.words[0].store(sequence.wrapping_add(2), Ordering::Release);
+        true
+    }
+
+    /// Lê o quadro mais recente de forma consistente: (sequência, passo, tempo, campo).
+    /// Retorna `None` se nenhum quadro foi escrito ou se o escritor não deu trégua.
+    pub fn read(&self) -> Option<(u64, u64, f64, Array2<f64>)> {
+        let mut temperature = Array2::<f64>::zeros((self.nr, self.nz));
+        for _ in 0..SHARED_FRAME_READ_ATTEMPTS {
+            let before = self.words[0].load(Ordering::Acquire);
+            if before == 0 {
+                return None;
+            }
+            if before % 2 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let step = self.words[1].load(Ordering::Relaxed);
+            let time = f64::from_bits(self.words[2].load(Ordering::Relaxed));
+            for (value, word) in temperature.iter_mut().zip(self.words[SHARED_FRAME_HEADER_WORDS..].iter()) {
+                *value = f64::from_bits(word.load(Ordering::Relaxed));
+            }
+
+            fence(Ordering::Acquire);
+            if self.words[0].load(Ordering::Relaxed) == before {
+                return Some((before, step, time, temperature));
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Debug for SharedFrameChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedFrameChannel")
+            .field("nr", &self.nr)
+            .field("nz", &self.nz)
+            .field("sequence", &self.sequence())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_packet_round_trip() {
+        let mut field = Array2::<f64>::zeros((3, 4));
+        for ((i, j), value) in field.indexed_iter_mut() {
+            *value = 25.0 + 100.0 * i as f64 + 7.5 * j as f64;
+        }
+
+        let packet = FramePacket::new(12, 360.0, field.view(), FrameEncoding::F32);
+        let bytes = packet.encode();
+        assert_eq!(bytes.len(), FRAME_HEADER_SIZE + 12 * 4);
+
+        let decoded = FramePacket::decode(&bytes).unwrap();
+        assert_eq!(decoded.header, packet.header);
+        assert_eq!(decoded.header.max_temperature, 25.0 + 200.0 + 22.5);
+        assert!(decoded.temperature.iter().zip(field.iter()).all(|(a, b)| (a - b).abs() < 1e-4));
+
+        // Quantização: erro máximo de meio degrau da escala
+        let quantized = FramePacket::new(12, 360.0, field.view(), FrameEncoding::U16).encode();
+        assert_eq!(quantized.len(), FRAME_HEADER_SIZE + 12 * 2);
+        let decoded = FramePacket::decode(&quantized).unwrap();
+        let step = (decoded.header.max_temperature - decoded.header.min_temperature) / u16::MAX as f64;
+        assert!(decoded.temperature.iter().zip(field.iter()).all(|(a, b)| (a - b).abs() <= 0.5 * step + 1e-9));
+
+        // Pacotes corrompidos
+        assert!(FramePacket::decode(&bytes[..FRAME_HEADER_SIZE - 1]).is_err());
+        assert!(FramePacket::decode(&bytes[..bytes.len() - 1]).is_err());
+
+        // Dimensões cujo tamanho de dados estoura o cálculo
+        let mut oversized = bytes.clone();
+        oversized[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        oversized[44..48].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(FramePacket::decode(&oversized).unwrap_err().contains("Dimensões"));
+    }
+
+    #[test]
+    fn test_quantized_history_memory_and_accuracy() {
+        let mut history = Array3::<f64>::zeros((10, 20, 5));
+        for ((i, j, k), value) in history.indexed_iter_mut() {
+            *value = 25.0 + 50.0 * k as f64 + i as f64 * j as f64;
+        }
+
+        let quantized = QuantizedFrameHistory::from_history(&history, 1.0);
+        assert_eq!(quantized.len(), 5);
+
+        // Valores u16 ocupam 1/4 dos f64 (mais a escala por quadro)
+        let full_bytes = history.len() * std::mem::size_of::<f64>();
+        assert!(quantized.memory_bytes() * 4 <= full_bytes + 5 * 64);
+
+        for k in 0..5 {
+            let frame = quantized.frame(k).unwrap();
+            let original = history.slice(s![.., .., k]);
+            let scale = quantized.frames[k].max_temperature - quantized.frames[k].min_temperature;
+            let tolerance = 0.5 * scale / u16::MAX as f64 + 1e-9;
+            assert!(frame.iter().zip(original.iter()).all(|(a, b)| (a - b).abs() <= tolerance));
+        }
+        assert!(quantized.frame(5).is_err());
+    }
+
+    #[test]
+    fn test_live_frame_double_buffer() {
+        let buffer = LiveFrameBuffer::new();
+        assert!(buffer.with_latest(|frame| frame.step).is_none());
+
+        let mut back = LiveFrame::new(2, 3);
+        for step in 1..=3 {
+            back.step = step;
+            back.time = step as f64 * 0.5;
+            back.temperature.fill(100.0 * step as f64);
+            buffer.publish(&mut back);
+        }
+        assert_eq!(buffer.sequence(), 3);
+        assert_eq!(buffer.with_latest(|frame| (frame.step, frame.temperature[[1, 2]])), Some((3, 300.0)));
+        // O buffer de escrita devolvido é o quadro publicado anterior
+        assert_eq!(back.step, 2);
+
+        buffer.clear();
+        assert!(buffer.with_latest(|frame| frame.step).is_none());
+    }
+
+    #[test]
+    fn test_shared_frame_channel_seqlock() {
+        let channel = Arc::new(SharedFrameChannel::new(2, 3));
+        assert_eq!(channel.size_bytes(), (SHARED_FRAME_HEADER_WORDS + 6) * 8);
+        assert!(channel.read().is_none());
+
+        // Quadros com outras dimensões são ignorados
+        assert!(!channel.write(1, 0.5, Array2::<f64>::zeros((3, 2)).view()));
+
+        let buffer = LiveFrameBuffer::new();
+        buffer.attach_channel(Some(channel.clone()));
+        let mut back = LiveFrame::new(2, 3);
+        back.step = 4;
+        back.time = 2.0;
+        back.temperature.fill(150.0);
+        back.temperature[[1, 2]] = 175.0;
+        buffer.publish(&mut back);
+
+        let (sequence, step, time, field) = channel.read().unwrap();
+        assert_eq!((sequence, step, time), (2, 4, 2.0));
+        assert_eq!(field[[1, 2]], 175.0);
+        assert_eq!(field[[0, 0]], 150.0);
+
+        // Cabeçalho visível na memória crua, como o frontend o lê
+        let words = unsafe { std::slice::from_raw_parts(channel.as_ptr() as *const u64, channel.size_bytes() / 8) };
+        assert_eq!(&words[..5], &[2, 4, 2.0f64.to_bits(), 2, 3]);
+
+        // Leituras concorrentes nunca observam quadros misturados
+        let writer = {
+            let channel = channel.clone();
+            std::thread::spawn(move || {
+                for step in 5..2_000u64 {
+                    channel.write(step, step as f64, Array2::from_elem((2, 3), step as f64).view());
+                }
+            })
+        };
+        for _ in 0..2_000 {
+            if let Some((_, step, _, field)) = channel.read().filter(|frame| frame.1 >= 5) {
+                assert!(field.iter().all(|value| *value == step as f64));
+            }
+        }
+        writer.join().unwrap();
+
+        buffer.attach_channel(None);
+        buffer.publish(&mut back);
+        assert_eq!(channel.read().unwrap().1, 1_999);
+    }
+
+    #[test]
+    fn test_temporal_pyramid_levels() {
+        let mut pyramid = TemporalPyramid::new(2, 2, 0.5, &[100, 10, 1, 10]);
+        assert_eq!(pyramid.strides(), vec![1, 10, 100]);
+
+        for step in 0..=250 {
+            let field = Array2::<f64>::from_elem((2, 2), step as f64);
+            pyramid.record(step, field.view());
+        }
+
+        assert_eq!(pyramid.level(10).unwrap().frames.len(), 26);
+        assert_eq!(pyramid.level(100).unwrap().frames.len(), 3);
+        assert_eq!(pyramid.level(100).unwrap().frames.time_step, 50.0);
+
+        let (step, frame) = pyramid.nearest_frame(10, 137).unwrap();
+        assert_eq!(step, 130);
+        assert!((frame[[0, 0]] - 130.0).abs() < 1e-9);
+
+        // Passos além do último quadro retornam o último disponível
+        assert_eq!(pyramid.nearest_frame(100, 10_000).unwrap().0, 200);
+        assert!(pyramid.nearest_frame(5, 0).is_err());
+
+        assert_eq!(pyramid.select_stride(200, 500), 1);
+        assert_eq!(pyramid.select_stride(20_000, 500), 100);
+        assert_eq!(pyramid.select_stride(3_000, 500), 10);
+    }
+}
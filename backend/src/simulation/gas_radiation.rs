@@ -0,0 +1,231 @@
+// Implementação das propriedades radiativas de gases (H2O/CO2) pelo modelo da soma ponderada de gases cinzas
+//
+// Preparado para o modelo de gás do espaço livre acima da carga: a emissividade e a
+// absortividade efetivas são calculadas a partir da composição e da temperatura em vez
+// de um valor constante.
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::materials::STEFAN_BOLTZMANN;
+
+/// Número de gases cinzas do modelo
+const GRAY_GASES: usize = 3;
+
+/// Coeficientes de Smith, Shen e Friedman (1982) para uma razão pw/pc
+///
+/// Cada gás cinza tem coeficiente de absorção κ ((atm·m)⁻¹) e coeficientes b1..b4 do
+/// polinômio do peso a(T) = b1·10⁻¹ + b2·10⁻⁴·T + b3·10⁻⁷·T² + b4·10⁻¹¹·T³ (T em K).
+struct WsggCoefficients {
+    /// Razão entre as pressões parciais de H2O e CO2
+    ratio: f64,
+    /// Coeficientes de absorção (1/(atm·m))
+    kappa: [f64; GRAY_GASES],
+    /// Coeficientes dos pesos
+    b: [[f64; 4]; GRAY_GASES],
+}
+
+/// Conjuntos de coeficientes tabelados (válidos entre 600 K e 2400 K)
+#[allow(clippy::approx_constant)] // 2.718 é um coeficiente ajustado, não a constante e
+const WSGG_TABLES: [WsggCoefficients; 2] = [
+    WsggCoefficients {
+        ratio: 1.0,
+        kappa: [0.4303, 7.055, 178.1],
+        b: [
+            [5.150, -2.303, 0.9779, -1.494],
+            [0.7749, 3.399, -2.297, 3.770],
+            [1.907, -1.824, 0.5608, -0.5122],
+        ],
+    },
+    WsggCoefficients {
+        ratio: 2.0,
+        kappa: [0.4201, 6.516, 131.9],
+        b: [
+            [6.508, -5.551, 3.029, -5.353],
+            [-0.2504, 6.112, -3.882, 6.528],
+            [2.718, -3.118, 1.221, -1.612],
+        ],
+    },
+];
+
+/// Faixa de temperatura de validade dos coeficientes (K)
+const WSGG_TEMPERATURE_RANGE: (f64, f64) = (600.0, 2400.0);
+
+impl WsggCoefficients {
+    /// Peso do gás cinza `i` na temperatura (K)
+    fn weight(&self, i: usize, temperature_kelvin: f64) -> f64 {
+        let t = temperature_kelvin;
+        let b = self.b[i];
+        b[0] * 1e-1 + b[1] * 1e-4 * t + b[2] * 1e-7 * t * t + b[3] * 1e-11 * t * t * t
+    }
+}
+
+/// Estrutura que representa a composição do gás (frações molares)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasComposition {
+    /// Fração molar de vapor d'água (0-1)
+    pub h2o: f64,
+    /// Fração molar de dióxido de carbono (0-1)
+    pub co2: f64,
+}
+
+impl GasComposition {
+    /// Cria uma nova composição
+    pub fn new(h2o: f64, co2: f64) -> Self {
+        Self { h2o, co2 }
+    }
+
+    /// Valida as frações molares
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.h2o) || !(0.0..=1.0).contains(&self.co2) {
+            return Err("Frações molares de H2O e CO2 devem estar entre 0 e 1".to_string());
+        }
+        if self.h2o + self.co2 > 1.0 + 1e-9 {
+            return Err("Soma das frações molares de H2O e CO2 não pode exceder 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa as propriedades radiativas de um volume de gás
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasRadiationModel {
+    /// Composição do gás
+    pub composition: GasComposition,
+    /// Pressão total (atm)
+    pub pressure: f64,
+    /// Comprimento médio do feixe (m)
+    pub path_length: f64,
+}
+
+/// Calcula o comprimento médio do feixe de um volume de gás (m)
+///
+/// Aproximação de Hottel para volumes arbitrários: L = 3,6·V / A.
+pub fn mean_beam_length(volume: f64, surface_area: f64) -> f64 {
+    if surface_area <= 0.0 {
+        return 0.0;
+    }
+    3.6 * volume / surface_area
+}
+
+impl GasRadiationModel {
+    /// Cria um novo modelo à pressão atmosférica
+    pub fn new(composition: GasComposition, path_length: f64) -> Self {
+        Self {
+            composition,
+            pressure: 1.0,
+            path_length,
+        }
+    }
+
+    /// Valida os parâmetros do modelo
+    pub fn validate(&self) -> Result<(), String> {
+        self.composition.validate()?;
+        if self.pressure <= 0.0 {
+            return Err("Pressão total do gás deve ser positiva".to_string());
+        }
+        if self.path_length < 0.0 {
+            return Err("Comprimento do feixe não pode ser negativo".to_string());
+        }
+        Ok(())
+    }
+
+    /// Soma das pressões parciais de H2O e CO2 (atm)
+    fn participating_pressure(&self) -> f64 {
+        (self.composition.h2o + self.composition.co2) * self.pressure
+    }
+
+    /// Pesos e coeficientes de absorção interpolados pela razão pw/pc
+    ///
+    /// Razões fora de [1, 2] usam o conjunto tabelado mais próximo.
+    fn gray_gases(&self, temperature_kelvin: f64) -> [(f64, f64); GRAY_GASES] {
+        let ratio = if self.composition.co2 > 0.0 {
+            self.composition.h2o / self.composition.co2
+        } else {
+            f64::INFINITY
+        };
+        let (low, high) = (&WSGG_TABLES[0], &WSGG_TABLES[1]);
+        let fraction = ((ratio - low.ratio) / (high.ratio - low.ratio)).clamp(0.0, 1.0);
+        let t = temperature_kelvin.clamp(WSGG_TEMPERATURE_RANGE.0, WSGG_TEMPERATURE_RANGE.1);
+
+        let mut gases = [(0.0, 0.0); GRAY_GASES];
+        for (i, gas) in gases.iter_mut().enumerate() {
+            let weight = low.weight(i, t) + fraction * (high.weight(i, t) - low.weight(i, t));
+            let kappa = low.kappa[i] + fraction * (high.kappa[i] - low.kappa[i]);
+            *gas = (weight.max(0.0), kappa);
+        }
+        gases
+    }
+
+    /// Soma ponderada dos gases cinzas com pesos avaliados na temperatura informada (K)
+    fn weighted_sum(&self, weight_temperature_kelvin: f64) -> f64 {
+        let optical_path = self.participating_pressure() * self.path_length;
+        if optical_path <= 0.0 {
+            return 0.0;
+        }
+        self.gray_gases(weight_temperature_kelvin).iter()
+            .map(|(weight, kappa)| weight * (1.0 - (-kappa * optical_path).exp()))
+            .sum::<f64>()
+            .clamp(0.0, 1.0)
+    }
+
+    /// Calcula a emissividade efetiva do gás a uma temperatura (°C)
+    pub fn emissivity(&self, gas_temperature: f64) -> f64 {
+        self.weighted_sum(gas_temperature + 273.15)
+    }
+
+    /// Calcula a absortividade do gás para a radiação de uma superfície (°C)
+    ///
+    /// Aproximação usual do modelo: os pesos são avaliados na temperatura da superfície.
+    pub fn absorptivity(&self, surface_temperature: f64) -> f64 {
+        self.weighted_sum(surface_temperature + 273.15)
+    }
+
+    /// Calcula o fluxo líquido de radiação do gás para uma superfície cinza (W/m²)
+    ///
+    /// Usa a correção de Hottel para superfícies não negras: ε_eff = (ε_s + 1) / 2.
+    pub fn net_flux_to_surface(&self, gas_temperature: f64, surface_temperature: f64, surface_emissivity: f64) -> f64 {
+        let t_gas = gas_temperature + 273.15;
+        let t_surface = surface_temperature + 273.15;
+        let effective_emissivity = (surface_emissivity.clamp(0.0, 1.0) + 1.0) / 2.0;
+        effective_emissivity * STEFAN_BOLTZMANN
+            * (self.emissivity(gas_temperature) * t_gas.powi(4) - self.absorptivity(surface_temperature) * t_surface.powi(4))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_wsgg_emissivity_trends() {
+        // Gás de combustão típico: 18% H2O, 9% CO2 (pw/pc = 2)
+        let model = GasRadiationModel::new(GasComposition::new(0.18, 0.09), 1.0);
+        assert!(model.validate().is_ok());
+
+        let eps_1000 = model.emissivity(1000.0);
+        assert!(eps_1000 > 0.1 && eps_1000 < 0.4);
+        // Emissividade diminui com a temperatura e aumenta com o caminho óptico
+        assert!(model.emissivity(1800.0) < eps_1000);
+        let thicker = GasRadiationModel { path_length: 3.0, ..model.clone() };
+        assert!(thicker.emissivity(1000.0) > eps_1000);
+
+        // Mesma temperatura: absortividade igual à emissividade
+        assert_relative_eq!(model.absorptivity(1000.0), eps_1000, epsilon = 1e-12);
+
+        // Gás transparente
+        let transparent = GasRadiationModel::new(GasComposition::new(0.0, 0.0), 1.0);
+        assert_eq!(transparent.emissivity(1000.0), 0.0);
+        assert!(GasComposition::new(0.7, 0.5).validate().is_err());
+    }
+
+    #[test]
+    fn test_net_flux_direction() {
+        let model = GasRadiationModel::new(GasComposition::new(0.1, 0.1), mean_beam_length(8.0, 24.0));
+        assert_relative_eq!(model.path_length, 1.2, epsilon = 1e-12);
+
+        assert!(model.net_flux_to_surface(1400.0, 800.0, 0.8) > 0.0);
+        assert!(model.net_flux_to_surface(600.0, 1200.0, 0.8) < 0.0);
+        assert_relative_eq!(model.net_flux_to_surface(1000.0, 1000.0, 0.8), 0.0, epsilon = 1e-6);
+    }
+}
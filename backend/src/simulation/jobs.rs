@@ -0,0 +1,341 @@
+// Implementação da fila de tarefas em segundo plano (identificadores, progresso e cancelamento)
+//
+// As tarefas são executadas em ordem por uma thread de trabalho criada no primeiro envio,
+// de modo que chamadas FFI longas (exportações, estudos) retornam imediatamente um
+// identificador que pode ser consultado e cancelado.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Identificador de uma tarefa
+pub type JobId = u64;
+
+/// Enumeração que representa o estado de uma tarefa
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobStatus {
+    /// Aguardando a thread de trabalho
+    Queued,
+    /// Em execução
+    Running,
+    /// Concluída com sucesso
+    Completed,
+    /// Encerrada com erro
+    Failed,
+    /// Cancelada antes ou durante a execução
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Código numérico do estado (FFI): 0 = na fila, 1 = em execução, 2 = concluída,
+    /// 3 = com erro, 4 = cancelada
+    pub fn code(&self) -> i32 {
+        match self {
+            JobStatus::Queued => 0,
+            JobStatus::Running => 1,
+            JobStatus::Completed => 2,
+            JobStatus::Failed => 3,
+            JobStatus::Cancelled => 4,
+        }
+    }
+
+    /// Indica se a tarefa terminou (com ou sem sucesso)
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled)
+    }
+}
+
+/// Estrutura que representa o estado consultável de uma tarefa
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobInfo {
+    /// Identificador da tarefa
+    pub id: JobId,
+    /// Tipo da tarefa (ex.: "export")
+    pub kind: String,
+    /// Estado atual
+    pub status: JobStatus,
+    /// Progresso (0-1)
+    pub progress: f32,
+    /// Mensagem de erro ou de cancelamento
+    pub message: Option<String>,
+    /// Resultado textual da tarefa (ex.: caminho gerado)
+    pub output: Option<String>,
+    /// Tempo de execução (s)
+    pub elapsed_seconds: f64,
+}
+
+/// Registro compartilhado entre a fila e a thread de trabalho
+struct JobRecord {
+    cancel: AtomicBool,
+    info: Mutex<JobInfo>,
+    result: Mutex<Option<Value>>,
+}
+
+impl JobRecord {
+    fn update(&self, update: impl FnOnce(&mut JobInfo)) {
+        if let Ok(mut info) = self.info.lock() {
+            update(&mut info);
+        }
+    }
+
+    fn snapshot(&self) -> Option<JobInfo> {
+        self.info.lock().ok().map(|info| info.clone())
+    }
+}
+
+/// Estrutura passada ao trabalho de uma tarefa para reportar progresso e consultar o cancelamento
+pub struct JobContext {
+    record: Arc<JobRecord>,
+}
+
+impl JobContext {
+    /// Atualiza o progresso da tarefa (0-1)
+    pub fn set_progress(&self, progress: f32) {
+        self.record.update(|info| info.progress = progress.clamp(0.0, 1.0));
+    }
+
+    /// Indica se o cancelamento foi solicitado
+    pub fn is_cancelled(&self) -> bool {
+        self.record.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Define o resultado estruturado da tarefa (consultado com `JobQueue::result`)
+    pub fn set_result(&self, result: Value) {
+        if let Ok(mut current) = self.record.result.lock() {
+            *current = Some(result);
+        }
+    }
+
+    /// Retorna erro se o cancelamento foi solicitado (para uso com `?` nos laços do trabalho)
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err("Tarefa cancelada".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Trabalho de uma tarefa; retorna o resultado textual opcional
+pub type JobWork = Box<dyn FnOnce(&JobContext) -> Result<Option<String>, String> + Send>;
+
+/// Tarefa enviada à thread de trabalho: registro e trabalho a executar
+type QueuedJob = (Arc<JobRecord>, JobWork);
+
+/// Estrutura que representa a fila de tarefas em segundo plano
+///
+/// Pode ser usada como `static` (construtor `const`). As tarefas terminadas permanecem
+/// consultáveis até `remove_finished`.
+pub struct JobQueue {
+    next_id: AtomicU64,
+    jobs: Mutex<Vec<(JobId, Arc<JobRecord>)>>,
+    sender: Mutex<Option<Sender<QueuedJob>>>,
+}
+
+impl JobQueue {
+    /// Cria uma fila vazia (a thread de trabalho é criada no primeiro envio)
+    pub const fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            jobs: Mutex::new(Vec::new()),
+            sender: Mutex::new(None),
+        }
+    }
+
+    /// Enfileira uma tarefa e retorna seu identificador
+    pub fn submit<F>(&self, kind: &str, work: F) -> Result<JobId, String>
+    where
+        F: FnOnce(&JobContext) -> Result<Option<String>, String> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let record = Arc::new(JobRecord {
+            cancel: AtomicBool::new(false),
+            info: Mutex::new(JobInfo {
+                id,
+                kind: kind.to_string(),
+                status: JobStatus::Queued,
+                progress: 0.0,
+                message: None,
+                output: None,
+                elapsed_seconds: 0.0,
+            }),
+            result: Mutex::new(None),
+        });
+
+        let mut sender = self.sender.lock().map_err(|e| format!("Fila de tarefas indisponível: {}", e))?;
+        let mut job = (record.clone(), Box::new(work) as JobWork);
+        // Reinicia a thread de trabalho se ela tiver sido encerrada
+        for _ in 0..2 {
+            if sender.is_none() {
+                *sender = Some(Self::spawn_worker()?);
+            }
+            match sender.as_ref().unwrap().send(job) {
+                Ok(()) => {
+                    self.jobs.lock().map_err(|e| format!("Fila de tarefas indisponível: {}", e))?.push((id, record));
+                    return Ok(id);
+                }
+                Err(mpsc::SendError(returned)) => {
+                    job = returned;
+                    *sender = None;
+                }
+            }
+        }
+        Err("Não foi possível iniciar a thread de tarefas".to_string())
+    }
+
+    /// Cria a thread de trabalho que executa as tarefas em ordem
+    fn spawn_worker() -> Result<Sender<QueuedJob>, String> {
+        let (sender, receiver) = mpsc::channel::<QueuedJob>();
+        thread::Builder::new()
+            .name("plasma-jobs".to_string())
+            .spawn(move || {
+                for (record, work) in receiver {
+                    if record.cancel.load(Ordering::Relaxed) {
+                        record.update(|info| {
+                            info.status = JobStatus::Cancelled;
+                            info.message = Some("Tarefa cancelada antes do início".to_string());
+                        });
+                        continue;
+                    }
+                    record.update(|info| info.status = JobStatus::Running);
+                    let start = Instant::now();
+                    let context = JobContext { record: record.clone() };
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| work(&context)))
+                        .unwrap_or_else(|_| Err("Pânico durante a execução da tarefa".to_string()));
+                    let cancelled = record.cancel.load(Ordering::Relaxed);
+                    record.update(|info| {
+                        info.elapsed_seconds = start.elapsed().as_secs_f64();
+                        match result {
+                            Ok(output) => {
+                                info.status = JobStatus::Completed;
+                                info.progress = 1.0;
+                                info.output = output;
+                            }
+                            Err(message) => {
+                                info.status = if cancelled { JobStatus::Cancelled } else { JobStatus::Failed };
+                                info.message = Some(message);
+                            }
+                        }
+                    });
+                }
+            })
+            .map_err(|e| format!("Erro ao iniciar thread de tarefas: {}", e))?;
+        Ok(sender)
+    }
+
+    fn record(&self, id: JobId) -> Option<Arc<JobRecord>> {
+        let jobs = self.jobs.lock().ok()?;
+        jobs.iter().find(|(job_id, _)| *job_id == id).map(|(_, record)| record.clone())
+    }
+
+    /// Retorna o estado de uma tarefa
+    pub fn info(&self, id: JobId) -> Option<JobInfo> {
+        self.record(id)?.snapshot()
+    }
+
+    /// Retorna o resultado estruturado de uma tarefa, se ela o definiu (disponível também
+    /// para tarefas canceladas que guardaram um resultado parcial)
+    pub fn result(&self, id: JobId) -> Option<Value> {
+        self.record(id)?.result.lock().ok()?.clone()
+    }
+
+    /// Retorna o estado de todas as tarefas, em ordem de envio
+    pub fn list(&self) -> Vec<JobInfo> {
+        match self.jobs.lock() {
+            Ok(jobs) => jobs.iter().filter_map(|(_, record)| record.snapshot()).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Solicita o cancelamento de uma tarefa; retorna false se ela não existe ou já terminou
+    pub fn cancel(&self, id: JobId) -> bool {
+        match self.record(id) {
+            Some(record) => {
+                let finished = record.snapshot().is_none_or(|info| info.status.is_finished());
+                if !finished {
+                    record.cancel.store(true, Ordering::Relaxed);
+                }
+                !finished
+            }
+            None => false,
+        }
+    }
+
+    /// Aguarda o fim de uma tarefa por até `timeout`, retornando seu estado
+    pub fn wait(&self, id: JobId, timeout: Duration) -> Option<JobInfo> {
+        let start = Instant::now();
+        loop {
+            let info = self.info(id)?;
+            if info.status.is_finished() || start.elapsed() >= timeout {
+                return Some(info);
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// Remove as tarefas terminadas; retorna quantas foram removidas
+    pub fn remove_finished(&self) -> usize {
+        match self.jobs.lock() {
+            Ok(mut jobs) => {
+                let before = jobs.len();
+                jobs.retain(|(_, record)| record.snapshot().is_some_and(|info| !info.status.is_finished()));
+                before - jobs.len()
+            }
+            Err(_) => 0,
+        }
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_queue_progress_and_cancellation() {
+        let queue = JobQueue::new();
+        let done = queue.submit("test", |context| {
+            context.set_progress(0.5);
+            context.set_result(serde_json::json!({ "valor": 42 }));
+            Ok(Some("saida".to_string()))
+        }).unwrap();
+
+        // Tarefa longa cancelada durante a execução; a seguinte é cancelada ainda na fila
+        let (started_tx, started_rx) = mpsc::channel();
+        let long = queue.submit("test", move |context| {
+            let _ = started_tx.send(());
+            while !context.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            context.check_cancelled().map(|_| None)
+        }).unwrap();
+        let queued = queue.submit("test", |_| Ok(None)).unwrap();
+
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(queue.cancel(queued));
+        assert!(queue.cancel(long));
+
+        let info = queue.wait(done, Duration::from_secs(5)).unwrap();
+        assert_eq!(info.status, JobStatus::Completed);
+        assert_eq!((info.progress, info.output.as_deref()), (1.0, Some("saida")));
+        assert_eq!(info.status.code(), 2);
+        assert_eq!(queue.result(done).unwrap()["valor"], 42);
+        assert!(queue.result(long).is_none());
+        assert_eq!(queue.wait(long, Duration::from_secs(5)).unwrap().status, JobStatus::Cancelled);
+        assert_eq!(queue.wait(queued, Duration::from_secs(5)).unwrap().status, JobStatus::Cancelled);
+
+        assert!(!queue.cancel(done));
+        assert_eq!(queue.remove_finished(), 3);
+        assert!(queue.info(done).is_none());
+    }
+}
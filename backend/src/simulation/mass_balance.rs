@@ -0,0 +1,229 @@
+// Implementação do balanço de massa das espécies da carga (umidade, voláteis, carvão e cinzas)
+//
+// A massa de cada célula é dividida como na análise imediata: água (modelo de umidade),
+// matéria volátil ainda não liberada, carbono fixo (carvão, formado ou a formar) e cinzas
+// inertes. A água evaporada, os voláteis liberados pela pirólise e o carvão queimado
+// deixam a carga como gás de exaustão, cuja massa acumulada e vazão são registradas a
+// cada passo de saída.
+
+use ndarray::{Array2, Zip};
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::materials::MaterialProperties;
+use crate::simulation::mesh::CylindricalMesh;
+use crate::simulation::moisture::MoistureModel;
+use crate::simulation::physics::reactions::ReactionModel;
+
+/// Estrutura que representa o balanço de massa das espécies de uma execução
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MassBalance {
+    /// Massa de água remanescente em cada célula ao fim da execução (kg)
+    pub moisture_mass: Array2<f64>,
+    /// Massa de matéria volátil ainda não liberada em cada célula (kg)
+    pub volatile_mass: Array2<f64>,
+    /// Massa de carbono fixo (carvão formado ou a formar, não queimado) em cada célula (kg)
+    pub char_mass: Array2<f64>,
+    /// Massa de cinzas de cada célula (kg), inerte
+    pub ash_mass: Array2<f64>,
+    /// Massa total de água evaporada (kg)
+    pub evaporated_water: f64,
+    /// Massa total de voláteis liberados pela pirólise (kg)
+    pub released_volatiles: f64,
+    /// Massa total de carvão queimado (kg)
+    pub burned_char: f64,
+    /// Massa acumulada de gás de exaustão ao fim de cada passo (kg), a partir do passo 1
+    pub off_gas_history: Vec<f64>,
+    /// Vazão média de gás de exaustão em cada passo (kg/s), a partir do passo 1
+    pub off_gas_flow_history: Vec<f64>,
+}
+
+impl MassBalance {
+    /// Massa total de gás de exaustão liberada pela carga (kg)
+    pub fn off_gas_mass(&self) -> f64 {
+        self.evaporated_water + self.released_volatiles + self.burned_char
+    }
+
+    /// Massa total das espécies que permanecem na carga (kg)
+    pub fn remaining_mass(&self) -> f64 {
+        self.moisture_mass.sum() + self.volatile_mass.sum() + self.char_mass.sum() + self.ash_mass.sum()
+    }
+}
+
+/// Estrutura que acompanha a liberação de gás de exaustão durante a execução
+#[derive(Debug, Clone)]
+pub struct MassBalanceTracker {
+    /// Massa de cinzas de cada célula (kg)
+    ash_mass: Array2<f64>,
+    /// Duração de um passo de saída (s)
+    time_step: f64,
+    off_gas_history: Vec<f64>,
+}
+
+impl MassBalanceTracker {
+    /// Cria o acompanhamento com as cinzas dos materiais das células
+    ///
+    /// O teor de cinzas (base seca) é aplicado à massa seca de cada célula na temperatura
+    /// de referência.
+    pub fn new(
+        mesh: &CylindricalMesh,
+        cell_materials: &[MaterialProperties],
+        cell_material_index: &Array2<usize>,
+        reference_temperature: f64,
+        time_step: f64,
+    ) -> Self {
+        let dry_densities: Vec<f64> = cell_materials.iter()
+            .map(|m| {
+                let dry_fraction = 1.0 - (m.moisture_content / 100.0).clamp(0.0, 1.0);
+                m.get_density(reference_temperature) * dry_fraction * (m.ash_content / 100.0).clamp(0.0, 1.0)
+            })
+            .collect();
+        let mut ash_mass = Array2::<f64>::zeros(cell_material_index.dim());
+        Zip::from(&mut ash_mass)
+            .and(cell_material_index)
+            .and(&mesh.cell_volumes)
+            .for_each(|mass, &idx, &volume| *mass = dry_densities[idx] * volume);
+        Self {
+            ash_mass,
+            time_step,
+            off_gas_history: Vec::new(),
+        }
+    }
+
+    /// Massas de voláteis não liberados e de carbono fixo de cada célula (kg)
+    fn solid_species(&self, reactions: Option<&ReactionModel>) -> (Array2<f64>, Array2<f64>) {
+        let Some(reactions) = reactions else {
+            return (Array2::zeros(self.ash_mass.dim()), Array2::zeros(self.ash_mass.dim()));
+        };
+        let char_yield = reactions.char_yield();
+        let reactive_mass = reactions.reactive_mass();
+        let volatile_mass = Zip::from(reactions.conversion())
+            .and(&reactive_mass)
+            .map_collect(|&conversion, &mass| (1.0 - conversion) * mass * (1.0 - char_yield));
+        let char_mass = match reactions.burned_fraction() {
+            Some(burned) => Zip::from(burned)
+                .and(&reactive_mass)
+                .map_collect(|&burned, &mass| (1.0 - burned) * mass * char_yield),
+            None => reactive_mass.mapv(|mass| mass * char_yield),
+        };
+        (volatile_mass, char_mass)
+    }
+
+    /// Massas liberadas como gás: água evaporada, voláteis e carvão queimado (kg)
+    fn released(moisture: Option<&MoistureModel>, reactions: Option<&ReactionModel>) -> (f64, f64, f64) {
+        let evaporated_water = moisture.map_or(0.0, |model| model.evaporated_water());
+        let (released_volatiles, burned_char) = reactions.map_or((0.0, 0.0), |model| {
+            ((1.0 - model.char_yield()) * model.converted_mass(), model.burned_char_mass())
+        });
+        (evaporated_water, released_volatiles, burned_char)
+    }
+
+    /// Retoma o registro do gás de exaustão a partir do histórico de uma execução anterior
+    pub fn resume_history(&mut self, history: &[f64]) {
+        self.off_gas_history = history.to_vec();
+    }
+
+    /// Registra a massa acumulada de gás de exaustão ao fim de um passo de saída
+    pub fn record_step(&mut self, moisture: Option<&MoistureModel>, reactions: Option<&ReactionModel>) {
+        let (water, volatiles, char) = Self::released(moisture, reactions);
+        self.off_gas_history.push(water + volatiles + char);
+    }
+
+    /// Resultado do balanço de massa
+    pub fn finish(&self, moisture: Option<&MoistureModel>, reactions: Option<&ReactionModel>) -> MassBalance {
+        let (volatile_mass, char_mass) = self.solid_species(reactions);
+        let (evaporated_water, released_volatiles, burned_char) = Self::released(moisture, reactions);
+        let off_gas_flow_history = self.off_gas_history.iter()
+            .scan(0.0, |previous, &total| {
+                let flow = (total - *previous) / self.time_step;
+                *previous = total;
+                Some(flow)
+            })
+            .collect();
+        MassBalance {
+            moisture_mass: moisture.map_or_else(|| Array2::zeros(self.ash_mass.dim()), |model| model.water_mass()),
+            volatile_mass,
+            char_mass,
+            ash_mass: self.ash_mass.clone(),
+            evaporated_water,
+            released_volatiles,
+            burned_char,
+            off_gas_history: self.off_gas_history.clone(),
+            off_gas_flow_history,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::moisture::MoistureEvaporationConfig;
+    use crate::simulation::physics::reactions::{ArrheniusKinetics, CharOxidationConfig, ReactionConfig};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_mass_balance_conserves_species_mass() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 3, 3, 4);
+        let mut biomass = MaterialProperties::new("Biomassa", 800.0, 1500.0, 0.5);
+        biomass.moisture_content = 20.0;
+        biomass.ash_content = 5.0;
+        let materials = vec![biomass];
+        let index = Array2::<usize>::zeros((3, 3));
+        let kinetics = ArrheniusKinetics { pre_exponential_factor: 1e8, activation_energy: 1.2e5, reaction_order: 1.0 };
+        let mut config = ReactionConfig::new(kinetics.clone(), 0.0);
+        config.reactive_fraction = 0.75;
+        config.char_oxidation = Some(CharOxidationConfig::new(kinetics, 1.0));
+
+        let mut moisture = MoistureModel::new(&MoistureEvaporationConfig::default(), &mesh, &materials, &index, |t, m| m.specific_heat * t);
+        let mut reactions = ReactionModel::new(&config, &mesh, &materials, &index, 25.0);
+        let mut tracker = MassBalanceTracker::new(&mesh, &materials, &index, 25.0, 2.0);
+        let initial = tracker.finish(Some(&moisture), Some(&reactions));
+        assert_eq!(initial.off_gas_mass(), 0.0);
+        let total_mass = (&mesh.cell_volumes * 800.0).sum();
+        assert_relative_eq!(initial.ash_mass.sum(), total_mass * 0.8 * 0.05, max_relative = 1e-12);
+
+        // Secagem completa e reação parcial com queima do carvão
+        let mut enthalpy = Array2::<f64>::from_elem((3, 3), 1e7);
+        moisture.apply(&mut enthalpy);
+        reactions.advance(&Array2::<f64>::from_elem((3, 3), 500.0), 2.0);
+        tracker.record_step(Some(&moisture), Some(&reactions));
+        let balance = tracker.finish(Some(&moisture), Some(&reactions));
+
+        assert!(balance.evaporated_water > 0.0 && balance.released_volatiles > 0.0 && balance.burned_char > 0.0);
+        assert_relative_eq!(
+            balance.remaining_mass() + balance.off_gas_mass(),
+            initial.remaining_mass(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(balance.off_gas_history[0], balance.off_gas_mass(), max_relative = 1e-12);
+        assert_relative_eq!(balance.off_gas_flow_history[0], balance.off_gas_mass() / 2.0, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_solid_species_split_by_conversion() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 2, 2, 4);
+        let materials = vec![MaterialProperties::new("Carga", 1000.0, 1500.0, 0.5)];
+        let index = Array2::<usize>::zeros((2, 2));
+        let kinetics = ArrheniusKinetics { pre_exponential_factor: 1e8, activation_energy: 1.2e5, reaction_order: 1.0 };
+        let mut config = ReactionConfig::new(kinetics, 0.0);
+        config.reactive_fraction = 0.5;
+        let mut reactions = ReactionModel::new(&config, &mesh, &materials, &index, 25.0);
+        let tracker = MassBalanceTracker::new(&mesh, &materials, &index, 25.0, 1.0);
+
+        // Sem reação: toda a massa reativa é volátil e não há carvão
+        let (volatile_mass, char_mass) = tracker.solid_species(None);
+        assert_eq!(volatile_mass.sum(), 0.0);
+        let (volatile_mass, char_mass_initial) = tracker.solid_species(Some(&reactions));
+        assert_relative_eq!(volatile_mass.sum(), reactions.reactive_mass().sum(), max_relative = 1e-12);
+        assert_eq!(char_mass.sum() + char_mass_initial.sum(), 0.0);
+
+        // Conversão parcial: os voláteis remanescentes somam a massa não convertida, célula a célula
+        reactions.advance(&Array2::<f64>::from_elem((2, 2), 500.0), 5.0);
+        let (volatile_mass, _) = tracker.solid_species(Some(&reactions));
+        let reactive_mass = reactions.reactive_mass();
+        for ((i, j), &mass) in volatile_mass.indexed_iter() {
+            let conversion = reactions.conversion()[[i, j]];
+            assert!(conversion > 0.0 && conversion < 1.0);
+            assert_relative_eq!(mass, (1.0 - conversion) * reactive_mass[[i, j]], max_relative = 1e-12);
+        }
+    }
+}
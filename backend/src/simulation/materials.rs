@@ -1,8 +1,12 @@
 // Implementação expandida para propriedades de materiais com suporte a mudanças de fase
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::f64::consts::PI;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::simulation::packed_bed;
 
 /// Constante de Stefan-Boltzmann (W/(m²·K⁴))
 pub const STEFAN_BOLTZMANN: f64 = 5.67e-8;
@@ -38,6 +42,256 @@ pub struct MaterialProperties {
     pub density_coefficients: Option<Vec<f64>>,
     /// Temperatura de referência para os coeficientes (°C)
     pub reference_temperature: Option<f64>,
+    /// Lei de viscosidade da fase líquida em função da temperatura (opcional)
+    #[serde(default)]
+    pub viscosity_model: Option<ViscosityModel>,
+    /// Temperatura limite de dano do revestimento (°C); materiais com limite definido
+    /// são tratados como parede (cadinho/refratário) na zona termicamente afetada
+    #[serde(default)]
+    pub damage_threshold: Option<f64>,
+    /// Tabelas lineares por partes das propriedades em função da temperatura (opcional)
+    #[serde(default)]
+    pub property_tables: Option<PropertyTables>,
+    /// Porosidade do leito de partículas (0 = sólido contínuo); com porosidade as
+    /// propriedades acima são as das partículas e os métodos `get_*` retornam as do leito
+    #[serde(default)]
+    pub porosity: f64,
+    /// Diâmetro médio das partículas do leito (m), usado na radiação entre partículas e
+    /// na troca com o gás intersticial
+    #[serde(default)]
+    pub particle_diameter: Option<f64>,
+    /// Teor de cinzas (% em massa, base seca), usado no balanço de massa das espécies
+    #[serde(default)]
+    pub ash_content: f64,
+}
+
+/// Enumeração que representa as leis de viscosidade dinâmica da fase líquida (escória)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ViscosityModel {
+    /// Constante (Pa·s)
+    Constant(f64),
+    /// Arrhenius: η = A·exp(B / T), com T em K (A em Pa·s, B em K)
+    Arrhenius {
+        /// Fator pré-exponencial (Pa·s)
+        a: f64,
+        /// Energia de ativação dividida pela constante dos gases (K)
+        b: f64,
+    },
+    /// Vogel-Fulcher-Tammann: log10(η) = A + B / (T - T0), com T em K
+    VogelFulcherTammann {
+        /// Constante A (log10 Pa·s)
+        a: f64,
+        /// Constante B (K)
+        b: f64,
+        /// Temperatura T0 (K)
+        t0: f64,
+    },
+}
+
+impl ViscosityModel {
+    /// Calcula a viscosidade dinâmica (Pa·s) para uma temperatura (°C)
+    pub fn evaluate(&self, temperature: f64) -> f64 {
+        let temperature_kelvin = temperature + 273.15;
+        match self {
+            ViscosityModel::Constant(value) => *value,
+            ViscosityModel::Arrhenius { a, b } => a * (b / temperature_kelvin.max(1.0)).exp(),
+            ViscosityModel::VogelFulcherTammann { a, b, t0 } => {
+                let delta = temperature_kelvin - t0;
+                if delta <= 0.0 {
+                    f64::INFINITY
+                } else {
+                    10f64.powf(a + b / delta)
+                }
+            }
+        }
+    }
+}
+
+/// Estrutura que representa uma tabela de propriedade linear por partes em função da temperatura
+///
+/// Entre os pontos o valor é interpolado linearmente; fora da faixa tabelada é mantido
+/// constante no valor da extremidade mais próxima.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyTable {
+    /// Temperaturas dos pontos, estritamente crescentes (°C)
+    pub temperatures: Vec<f64>,
+    /// Valores da propriedade em cada temperatura
+    pub values: Vec<f64>,
+}
+
+impl PropertyTable {
+    /// Cria uma tabela a partir de pares (temperatura, valor), ordenando pela temperatura
+    pub fn new(mut points: Vec<(f64, f64)>) -> Result<Self, String> {
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        let table = Self {
+            temperatures: points.iter().map(|p| p.0).collect(),
+            values: points.iter().map(|p| p.1).collect(),
+        };
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Valida a tabela
+    pub fn validate(&self) -> Result<(), String> {
+        if self.temperatures.is_empty() || self.temperatures.len() != self.values.len() {
+            return Err("Tabela de propriedade deve ter ao menos um ponto e o mesmo número de temperaturas e valores".to_string());
+        }
+        if self.temperatures.iter().chain(self.values.iter()).any(|v| !v.is_finite()) {
+            return Err("Tabela de propriedade contém valores não finitos".to_string());
+        }
+        if self.temperatures.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("Temperaturas da tabela de propriedade devem ser estritamente crescentes".to_string());
+        }
+        Ok(())
+    }
+
+    /// Interpola o valor da propriedade na temperatura (°C)
+    pub fn evaluate(&self, temperature: f64) -> f64 {
+        let n = self.temperatures.len();
+        if n == 0 {
+            return 0.0;
+        }
+        if temperature <= self.temperatures[0] {
+            return self.values[0];
+        }
+        if temperature >= self.temperatures[n - 1] {
+            return self.values[n - 1];
+        }
+        let upper = self.temperatures.partition_point(|&t| t <= temperature);
+        let (t0, t1) = (self.temperatures[upper - 1], self.temperatures[upper]);
+        let (v0, v1) = (self.values[upper - 1], self.values[upper]);
+        v0 + (v1 - v0) * (temperature - t0) / (t1 - t0)
+    }
+}
+
+/// Estrutura que representa as tabelas de propriedades de um material
+///
+/// Propriedades com tabela têm precedência sobre os coeficientes polinomiais e os
+/// valores constantes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PropertyTables {
+    /// Condutividade térmica (W/(m·K))
+    #[serde(default)]
+    pub thermal_conductivity: Option<PropertyTable>,
+    /// Capacidade térmica específica (J/(kg·K))
+    #[serde(default)]
+    pub specific_heat: Option<PropertyTable>,
+    /// Densidade (kg/m³)
+    #[serde(default)]
+    pub density: Option<PropertyTable>,
+    /// Emissividade (0-1)
+    #[serde(default)]
+    pub emissivity: Option<PropertyTable>,
+}
+
+impl PropertyTables {
+    /// Valida as tabelas definidas
+    pub fn validate(&self) -> Result<(), String> {
+        let named = [
+            ("thermal_conductivity", &self.thermal_conductivity),
+            ("specific_heat", &self.specific_heat),
+            ("density", &self.density),
+            ("emissivity", &self.emissivity),
+        ];
+        for (name, table) in named {
+            if let Some(table) = table {
+                table.validate().map_err(|e| format!("{}: {}", name, e))?;
+                if table.values.iter().any(|&v| v < 0.0) {
+                    return Err(format!("{}: valores tabelados devem ser não negativos", name));
+                }
+            }
+        }
+        if let Some(table) = &self.emissivity {
+            if table.values.iter().any(|&v| v > 1.0) {
+                return Err("emissivity: valores tabelados devem estar entre 0 e 1".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Lê as tabelas de um JSON no formato `{"thermal_conductivity": {"temperatures": [...], "values": [...]}, ...}`
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let tables: Self = serde_json::from_str(content)
+            .map_err(|e| format!("Erro ao ler tabelas de propriedades JSON: {}", e))?;
+        tables.validate()?;
+        Ok(tables)
+    }
+
+    /// Lê as tabelas de um CSV com cabeçalho
+    ///
+    /// A primeira coluna é a temperatura (`temperature`/`T` em °C, ou `temperature_k`/`T(K)`
+    /// em K); as demais são `k`/`thermal_conductivity`, `cp`/`specific_heat`,
+    /// `rho`/`density` e `emissivity`/`eps`. Células vazias são ignoradas, de modo que cada
+    /// propriedade pode ter seus próprios pontos.
+    pub fn from_csv(content: &str) -> Result<Self, String> {
+        let mut lines = content.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let header: Vec<String> = lines.next()
+            .ok_or("CSV de propriedades vazio")?
+            .split(',')
+            .map(|h| h.trim().to_lowercase())
+            .collect();
+        let kelvin = match header.first().map(String::as_str) {
+            Some("temperature") | Some("t") | Some("t(c)") | Some("temperature_c") => false,
+            Some("temperature_k") | Some("t(k)") => true,
+            other => return Err(format!("Primeira coluna do CSV deve ser a temperatura, encontrado {:?}", other)),
+        };
+
+        // (índice da coluna, propriedade, pontos (T, valor))
+        type PropertyColumn<'a> = (usize, &'a str, Vec<(f64, f64)>);
+        let mut columns: Vec<PropertyColumn> = Vec::new();
+        for (index, name) in header.iter().enumerate().skip(1) {
+            let property = match name.as_str() {
+                "k" | "thermal_conductivity" => "thermal_conductivity",
+                "cp" | "specific_heat" => "specific_heat",
+                "rho" | "density" => "density",
+                "eps" | "emissivity" => "emissivity",
+                other => return Err(format!("Coluna de propriedade desconhecida no CSV: {}", other)),
+            };
+            columns.push((index, property, Vec::new()));
+        }
+
+        for (line_number, line) in lines.enumerate() {
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            let parse = |cell: &str| cell.parse::<f64>()
+                .map_err(|_| format!("Valor inválido na linha {} do CSV: {}", line_number + 2, cell));
+            let temperature = parse(cells[0])? - if kelvin { 273.15 } else { 0.0 };
+            for (index, _, points) in columns.iter_mut() {
+                match cells.get(*index) {
+                    Some(cell) if !cell.is_empty() => points.push((temperature, parse(cell)?)),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut tables = Self::default();
+        for (_, property, points) in columns {
+            if points.is_empty() {
+                continue;
+            }
+            let table = Some(PropertyTable::new(points).map_err(|e| format!("{}: {}", property, e))?);
+            match property {
+                "thermal_conductivity" => tables.thermal_conductivity = table,
+                "specific_heat" => tables.specific_heat = table,
+                "density" => tables.density = table,
+                _ => tables.emissivity = table,
+            }
+        }
+        tables.validate()?;
+        Ok(tables)
+    }
+
+    /// Lê as tabelas de um arquivo `.json` ou `.csv`
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Erro ao ler {}: {}", path.display(), e))?;
+        match path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref() {
+            Some("json") => Self::from_json(&content),
+            Some("csv") => Self::from_csv(&content),
+            _ => Err(format!("Formato de tabela de propriedades não suportado: {}", path.display())),
+        }
+    }
 }
 
 impl MaterialProperties {
@@ -58,11 +312,74 @@ impl MaterialProperties {
             thermal_conductivity_coefficients: None,
             density_coefficients: None,
             reference_temperature: None,
+            viscosity_model: None,
+            damage_threshold: None,
+            property_tables: None,
+            porosity: 0.0,
+            particle_diameter: None,
+            ash_content: 0.0,
         }
     }
 
     /// Calcula a capacidade térmica específica para uma temperatura específica
+    ///
+    /// Em leitos porosos retorna a capacidade do leito por massa aparente.
     pub fn get_specific_heat(&self, temperature: f64) -> f64 {
+        if self.porosity > 0.0 {
+            return packed_bed::effective_specific_heat(
+                self.solid_density(temperature),
+                self.solid_specific_heat(temperature),
+                self.porosity,
+                temperature,
+            );
+        }
+        self.solid_specific_heat(temperature)
+    }
+
+    /// Calcula a condutividade térmica para uma temperatura específica
+    ///
+    /// Em leitos porosos retorna a condutividade efetiva do leito.
+    pub fn get_thermal_conductivity(&self, temperature: f64) -> f64 {
+        if self.porosity > 0.0 {
+            return packed_bed::effective_conductivity(
+                self.solid_thermal_conductivity(temperature),
+                self.porosity,
+                self.particle_diameter,
+                self.get_emissivity(temperature),
+                temperature,
+            );
+        }
+        self.solid_thermal_conductivity(temperature)
+    }
+
+    /// Calcula a densidade para uma temperatura específica
+    ///
+    /// Em leitos porosos retorna a densidade aparente do leito.
+    pub fn get_density(&self, temperature: f64) -> f64 {
+        if self.porosity > 0.0 {
+            return packed_bed::effective_density(self.solid_density(temperature), self.porosity, temperature);
+        }
+        self.solid_density(temperature)
+    }
+
+    /// Valida a porosidade e o diâmetro das partículas do leito
+    pub fn validate_packed_bed(&self) -> Result<(), String> {
+        if !(0.0..1.0).contains(&self.porosity) {
+            return Err(format!("Porosidade de {} deve estar em [0, 1)", self.name));
+        }
+        if let Some(diameter) = self.particle_diameter {
+            if diameter <= 0.0 || !diameter.is_finite() {
+                return Err(format!("Diâmetro das partículas de {} deve ser positivo", self.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Capacidade térmica específica do material das partículas (sem porosidade)
+    pub fn solid_specific_heat(&self, temperature: f64) -> f64 {
+        if let Some(table) = self.property_tables.as_ref().and_then(|tables| tables.specific_heat.as_ref()) {
+            return table.evaluate(temperature);
+        }
         if let Some(coeffs) = &self.specific_heat_coefficients {
             if let Some(t_ref) = self.reference_temperature {
                 // Temperatura normalizada
@@ -81,8 +398,11 @@ impl MaterialProperties {
         self.specific_heat
     }
 
-    /// Calcula a condutividade térmica para uma temperatura específica
-    pub fn get_thermal_conductivity(&self, temperature: f64) -> f64 {
+    /// Condutividade térmica do material das partículas (sem porosidade)
+    pub fn solid_thermal_conductivity(&self, temperature: f64) -> f64 {
+        if let Some(table) = self.property_tables.as_ref().and_then(|tables| tables.thermal_conductivity.as_ref()) {
+            return table.evaluate(temperature);
+        }
         if let Some(coeffs) = &self.thermal_conductivity_coefficients {
             if let Some(t_ref) = self.reference_temperature {
                 // Temperatura normalizada
@@ -101,8 +421,11 @@ impl MaterialProperties {
         self.thermal_conductivity
     }
 
-    /// Calcula a densidade para uma temperatura específica
-    pub fn get_density(&self, temperature: f64) -> f64 {
+    /// Densidade do material das partículas (sem porosidade)
+    pub fn solid_density(&self, temperature: f64) -> f64 {
+        if let Some(table) = self.property_tables.as_ref().and_then(|tables| tables.density.as_ref()) {
+            return table.evaluate(temperature);
+        }
         if let Some(coeffs) = &self.density_coefficients {
             if let Some(t_ref) = self.reference_temperature {
                 // Temperatura normalizada
@@ -121,8 +444,28 @@ impl MaterialProperties {
         self.density
     }
 
+    /// Calcula a emissividade para uma temperatura específica
+    pub fn get_emissivity(&self, temperature: f64) -> f64 {
+        match self.property_tables.as_ref().and_then(|tables| tables.emissivity.as_ref()) {
+            Some(table) => table.evaluate(temperature),
+            None => self.emissivity,
+        }
+    }
+
+    /// Define as tabelas de propriedades, validando-as
+    pub fn set_property_tables(&mut self, tables: PropertyTables) -> Result<(), String> {
+        tables.validate()?;
+        self.property_tables = Some(tables);
+        Ok(())
+    }
+
+    /// Calcula a viscosidade dinâmica da fase líquida (Pa·s), se houver lei definida
+    pub fn get_viscosity(&self, temperature: f64) -> Option<f64> {
+        self.viscosity_model.as_ref().map(|model| model.evaluate(temperature))
+    }
+
     /// Calcula a capacidade térmica efetiva considerando mudanças de fase
-    pub fn effective_specific_heat(&self, temperature: f64, delta_t: f64) -> f64 {
+    pub fn effective_specific_heat(&self, temperature: f64, _delta_t: f64) -> f64 {
         let mut c_eff = self.get_specific_heat(temperature);
 
         // Adicionar efeito da mudança de fase (fusão)
@@ -168,9 +511,165 @@ impl MaterialProperties {
     }
 }
 
-/// Biblioteca de materiais pré-definidos
+/// Estrutura que representa a configuração do cache de propriedades por faixas de temperatura
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyCacheConfig {
+    /// Temperatura mínima tabelada (°C)
+    pub min_temperature: f64,
+    /// Temperatura máxima tabelada (°C)
+    pub max_temperature: f64,
+    /// Largura inicial das faixas de temperatura (°C)
+    pub bucket_width: f64,
+    /// Largura mínima das faixas durante o refinamento (°C)
+    pub min_bucket_width: f64,
+    /// Erro relativo máximo admitido na interpolação (0-1)
+    pub max_relative_error: f64,
+}
+
+impl Default for PropertyCacheConfig {
+    fn default() -> Self {
+        Self {
+            min_temperature: -50.0,
+            max_temperature: 5000.0,
+            bucket_width: 10.0,
+            min_bucket_width: 0.5,
+            max_relative_error: 1e-4,
+        }
+    }
+}
+
+/// Estrutura que representa o cache de propriedades de um material
+///
+/// As propriedades são tabeladas em faixas uniformes de temperatura na criação do
+/// solucionador e interpoladas linearmente durante a execução. A largura das faixas
+/// é reduzida até que o erro nos pontos médios respeite `max_relative_error`.
+/// Temperaturas fora do intervalo tabelado são avaliadas diretamente no material.
+#[derive(Debug, Clone)]
+pub struct PropertyCache {
+    /// Material de origem (usado fora do intervalo tabelado)
+    material: MaterialProperties,
+    /// Temperatura do primeiro nó da tabela (°C)
+    min_temperature: f64,
+    /// Largura das faixas (°C)
+    bucket_width: f64,
+    /// Densidade tabelada (kg/m³)
+    density: Vec<f64>,
+    /// Capacidade térmica específica tabelada (J/(kg·K))
+    specific_heat: Vec<f64>,
+    /// Condutividade térmica tabelada (W/(m·K))
+    thermal_conductivity: Vec<f64>,
+}
+
+impl PropertyCache {
+    /// Cria o cache para um material com a configuração especificada
+    pub fn new(material: &MaterialProperties, config: &PropertyCacheConfig) -> Result<Self, String> {
+        if config.max_temperature <= config.min_temperature {
+            return Err("Intervalo de temperatura do cache inválido".to_string());
+        }
+        if config.bucket_width <= 0.0 || config.min_bucket_width <= 0.0 {
+            return Err("Largura das faixas do cache deve ser positiva".to_string());
+        }
+
+        let mut bucket_width = config.bucket_width;
+        loop {
+            let cache = Self::build(material, config.min_temperature, config.max_temperature, bucket_width);
+            if bucket_width <= config.min_bucket_width || cache.max_midpoint_error() <= config.max_relative_error {
+                return Ok(cache);
+            }
+            bucket_width = (bucket_width / 2.0).max(config.min_bucket_width);
+        }
+    }
+
+    /// Tabela as propriedades com a largura de faixa especificada
+    fn build(material: &MaterialProperties, min_temperature: f64, max_temperature: f64, bucket_width: f64) -> Self {
+        let n_nodes = ((max_temperature - min_temperature) / bucket_width).ceil() as usize + 1;
+        let temperatures: Vec<f64> = (0..n_nodes).map(|n| min_temperature + n as f64 * bucket_width).collect();
+
+        Self {
+            material: material.clone(),
+            min_temperature,
+            bucket_width,
+            density: temperatures.iter().map(|&t| material.get_density(t)).collect(),
+            specific_heat: temperatures.iter().map(|&t| material.get_specific_heat(t)).collect(),
+            thermal_conductivity: temperatures.iter().map(|&t| material.get_thermal_conductivity(t)).collect(),
+        }
+    }
+
+    /// Calcula o maior erro relativo de interpolação nos pontos médios das faixas
+    pub fn max_midpoint_error(&self) -> f64 {
+        let mut max_error: f64 = 0.0;
+        for n in 0..self.density.len().saturating_sub(1) {
+            let t = self.min_temperature + (n as f64 + 0.5) * self.bucket_width;
+            let pairs = [
+                (self.get_density(t), self.material.get_density(t)),
+                (self.get_specific_heat(t), self.material.get_specific_heat(t)),
+                (self.get_thermal_conductivity(t), self.material.get_thermal_conductivity(t)),
+            ];
+            for (cached, exact) in pairs {
+                if exact.abs() > 1e-12 {
+                    max_error = max_error.max(((cached - exact) / exact).abs());
+                }
+            }
+        }
+        max_error
+    }
+
+    /// Retorna a largura das faixas efetivamente utilizada (°C)
+    pub fn bucket_width(&self) -> f64 {
+        self.bucket_width
+    }
+
+    /// Retorna o material de origem do cache
+    pub fn material(&self) -> &MaterialProperties {
+        &self.material
+    }
+
+    /// Interpola um valor tabelado, retornando None fora do intervalo
+    fn interpolate(&self, table: &[f64], temperature: f64) -> Option<f64> {
+        let position = (temperature - self.min_temperature) / self.bucket_width;
+        if position.is_nan() || position < 0.0 || position > (table.len() - 1) as f64 {
+            return None;
+        }
+        let index = (position.floor() as usize).min(table.len() - 2);
+        let fraction = position - index as f64;
+        Some(table[index] + fraction * (table[index + 1] - table[index]))
+    }
+
+    /// Obtém a densidade para uma temperatura (kg/m³)
+    pub fn get_density(&self, temperature: f64) -> f64 {
+        self.interpolate(&self.density, temperature)
+            .unwrap_or_else(|| self.material.get_density(temperature))
+    }
+
+    /// Obtém a capacidade térmica específica para uma temperatura (J/(kg·K))
+    pub fn get_specific_heat(&self, temperature: f64) -> f64 {
+        self.interpolate(&self.specific_heat, temperature)
+            .unwrap_or_else(|| self.material.get_specific_heat(temperature))
+    }
+
+    /// Obtém a condutividade térmica para uma temperatura (W/(m·K))
+    pub fn get_thermal_conductivity(&self, temperature: f64) -> f64 {
+        self.interpolate(&self.thermal_conductivity, temperature)
+            .unwrap_or_else(|| self.material.get_thermal_conductivity(temperature))
+    }
+}
+
+/// Biblioteca de materiais pré-definidos e de materiais do usuário
+///
+/// Os materiais do usuário são gravados como `<id>.json` no diretório configurado por
+/// `set_user_directory`, um arquivo por material.
 pub struct MaterialLibrary {
     materials: HashMap<String, MaterialProperties>,
+    /// Diretório dos materiais do usuário
+    user_directory: Option<PathBuf>,
+    /// IDs dos materiais carregados ou gravados pelo usuário
+    user_materials: HashSet<String>,
+}
+
+impl Default for MaterialLibrary {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MaterialLibrary {
@@ -178,6 +677,8 @@ impl MaterialLibrary {
     pub fn new() -> Self {
         let mut library = Self {
             materials: HashMap::new(),
+            user_directory: None,
+            user_materials: HashSet::new(),
         };
         
         // Adicionar materiais pré-definidos
@@ -204,6 +705,12 @@ impl MaterialLibrary {
             thermal_conductivity_coefficients: Some(vec![45.0, -0.05, 0.0, 0.0]),
             density_coefficients: Some(vec![7850.0, -0.5, 0.0, 0.0]),
             reference_temperature: Some(25.0),
+            viscosity_model: None,
+            damage_threshold: None,
+            property_tables: None,
+            porosity: 0.0,
+            particle_diameter: None,
+            ash_content: 0.0,
         };
         self.materials.insert("steel".to_string(), steel);
         
@@ -223,6 +730,12 @@ impl MaterialLibrary {
             thermal_conductivity_coefficients: Some(vec![237.0, -0.05, 0.0, 0.0]),
             density_coefficients: Some(vec![2700.0, -0.1, 0.0, 0.0]),
             reference_temperature: Some(25.0),
+            viscosity_model: None,
+            damage_threshold: None,
+            property_tables: None,
+            porosity: 0.0,
+            particle_diameter: None,
+            ash_content: 0.0,
         };
         self.materials.insert("aluminum".to_string(), aluminum);
         
@@ -242,6 +755,12 @@ impl MaterialLibrary {
             thermal_conductivity_coefficients: Some(vec![401.0, -0.06, 0.0, 0.0]),
             density_coefficients: Some(vec![8960.0, -0.5, 0.0, 0.0]),
             reference_temperature: Some(25.0),
+            viscosity_model: None,
+            damage_threshold: None,
+            property_tables: None,
+            porosity: 0.0,
+            particle_diameter: None,
+            ash_content: 0.0,
         };
         self.materials.insert("copper".to_string(), copper);
         
@@ -261,6 +780,12 @@ impl MaterialLibrary {
             thermal_conductivity_coefficients: Some(vec![1.4, -0.001, 0.0, 0.0]),
             density_coefficients: None,
             reference_temperature: Some(25.0),
+            viscosity_model: None,
+            damage_threshold: Some(600.0),
+            property_tables: None,
+            porosity: 0.0,
+            particle_diameter: None,
+            ash_content: 0.0,
         };
         self.materials.insert("concrete".to_string(), concrete);
         
@@ -280,6 +805,12 @@ impl MaterialLibrary {
             thermal_conductivity_coefficients: None,
             density_coefficients: None,
             reference_temperature: None,
+            viscosity_model: None,
+            damage_threshold: None,
+            property_tables: None,
+            porosity: 0.0,
+            particle_diameter: None,
+            ash_content: 0.0,
         };
         self.materials.insert("wood".to_string(), wood);
         
@@ -299,6 +830,12 @@ impl MaterialLibrary {
             thermal_conductivity_coefficients: None,
             density_coefficients: None,
             reference_temperature: None,
+            viscosity_model: None,
+            damage_threshold: None,
+            property_tables: None,
+            porosity: 0.0,
+            particle_diameter: None,
+            ash_content: 0.0,
         };
         self.materials.insert("glass".to_string(), glass);
     }
@@ -334,6 +871,139 @@ impl MaterialLibrary {
             .map(|(id, material)| (id.clone(), material.name.clone()))
             .collect()
     }
+
+    /// Cria a biblioteca com os materiais pré-definidos e os do diretório do usuário
+    pub fn with_user_directory(directory: &Path) -> Result<Self, String> {
+        let mut library = Self::new();
+        library.set_user_directory(directory)?;
+        Ok(library)
+    }
+
+    /// Define o diretório dos materiais do usuário (criado se necessário) e carrega seus materiais
+    ///
+    /// Retorna o número de materiais carregados; materiais do usuário substituem
+    /// pré-definidos de mesmo ID.
+    pub fn set_user_directory(&mut self, directory: &Path) -> Result<usize, String> {
+        fs::create_dir_all(directory)
+            .map_err(|e| format!("Erro ao criar diretório de materiais {:?}: {}", directory, e))?;
+        let entries = fs::read_dir(directory)
+            .map_err(|e| format!("Erro ao ler diretório de materiais {:?}: {}", directory, e))?;
+        let mut loaded = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| format!("Erro ao ler diretório de materiais: {}", e))?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let id = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(id) => id.to_string(),
+                None => continue,
+            };
+            let content = fs::read_to_string(&path).map_err(|e| format!("Erro ao ler material {:?}: {}", path, e))?;
+            let material: MaterialProperties = serde_json::from_str(&content)
+                .map_err(|e| format!("Material inválido {:?}: {}", path, e))?;
+            validate_user_material(&material).map_err(|e| format!("Material inválido {:?}: {}", path, e))?;
+            loaded.push((id, material));
+        }
+
+        // Substitui os materiais do diretório anterior apenas se todos foram lidos
+        for id in self.user_materials.drain() {
+            self.materials.remove(&id);
+        }
+        self.restore_predefined_materials();
+        let count = loaded.len();
+        for (id, material) in loaded {
+            self.user_materials.insert(id.clone());
+            self.materials.insert(id, material);
+        }
+        self.user_directory = Some(directory.to_path_buf());
+        Ok(count)
+    }
+
+    /// Diretório dos materiais do usuário, se configurado
+    pub fn user_directory(&self) -> Option<&Path> {
+        self.user_directory.as_deref()
+    }
+
+    /// Indica se o material foi definido pelo usuário
+    pub fn is_user_material(&self, id: &str) -> bool {
+        self.user_materials.contains(id)
+    }
+
+    /// Grava um material do usuário em disco e o adiciona (ou atualiza) na biblioteca
+    pub fn save_user_material(&mut self, id: &str, material: MaterialProperties) -> Result<(), String> {
+        let path = self.user_material_path(id)?;
+        validate_user_material(&material)?;
+        let content = serde_json::to_string_pretty(&material)
+            .map_err(|e| format!("Erro ao serializar material {}: {}", id, e))?;
+        fs::write(&path, content).map_err(|e| format!("Erro ao gravar material {:?}: {}", path, e))?;
+        self.user_materials.insert(id.to_string());
+        self.materials.insert(id.to_string(), material);
+        Ok(())
+    }
+
+    /// Remove um material do usuário do disco e da biblioteca
+    ///
+    /// Retorna false se o material não existe; materiais pré-definidos não podem ser removidos
+    /// (um material do usuário que substituía um pré-definido restaura o original).
+    pub fn delete_user_material(&mut self, id: &str) -> Result<bool, String> {
+        if !self.user_materials.contains(id) {
+            return if self.materials.contains_key(id) {
+                Err(format!("Material pré-definido não pode ser removido: {}", id))
+            } else {
+                Ok(false)
+            };
+        }
+        let path = self.user_material_path(id)?;
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Erro ao remover material {:?}: {}", path, e))?;
+        }
+        self.user_materials.remove(id);
+        self.materials.remove(id);
+        self.restore_predefined_materials();
+        Ok(true)
+    }
+
+    /// Reinsere os materiais pré-definidos ausentes sem substituir os do usuário
+    fn restore_predefined_materials(&mut self) {
+        for (id, material) in Self::new().materials {
+            if !self.user_materials.contains(&id) {
+                self.materials.entry(id).or_insert(material);
+            }
+        }
+    }
+
+    /// Caminho do arquivo de um material do usuário
+    fn user_material_path(&self, id: &str) -> Result<PathBuf, String> {
+        let directory = self.user_directory.as_ref()
+            .ok_or_else(|| "Diretório de materiais do usuário não configurado".to_string())?;
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("ID de material inválido (use letras, números, '-' ou '_'): {}", id));
+        }
+        Ok(directory.join(format!("{}.json", id)))
+    }
+}
+
+/// Valida as propriedades básicas de um material do usuário
+fn validate_user_material(material: &MaterialProperties) -> Result<(), String> {
+    if material.name.trim().is_empty() {
+        return Err("Nome do material não informado".to_string());
+    }
+    for (label, value) in [
+        ("Densidade", material.density),
+        ("Capacidade térmica específica", material.specific_heat),
+        ("Condutividade térmica", material.thermal_conductivity),
+    ] {
+        if value <= 0.0 || !value.is_finite() {
+            return Err(format!("{} de {} deve ser positiva", label, material.name));
+        }
+    }
+    if !(0.0..=1.0).contains(&material.emissivity) {
+        return Err(format!("Emissividade de {} deve estar entre 0 e 1", material.name));
+    }
+    if let Some(tables) = &material.property_tables {
+        tables.validate()?;
+    }
+    material.validate_packed_bed()
 }
 
 #[cfg(test)]
@@ -393,4 +1063,117 @@ mod tests {
         assert_relative_eq!(steel.density, 7850.0);
         assert!(steel.melting_point.is_some());
     }
+
+    #[test]
+    fn test_viscosity_models() {
+        let mut material = MaterialProperties::new("Slag", 2800.0, 1000.0, 1.5);
+        assert!(material.get_viscosity(1500.0).is_none());
+        
+        material.viscosity_model = Some(ViscosityModel::Arrhenius { a: 1e-4, b: 15000.0 });
+        let hot = material.get_viscosity(1600.0).unwrap();
+        let cold = material.get_viscosity(1300.0).unwrap();
+        assert!(cold > hot);
+        assert_relative_eq!(hot, 1e-4 * (15000.0_f64 / 1873.15).exp(), epsilon = 1e-12);
+        
+        material.viscosity_model = Some(ViscosityModel::VogelFulcherTammann { a: -2.0, b: 4000.0, t0: 800.0 });
+        assert_relative_eq!(material.get_viscosity(1726.85).unwrap(), 10f64.powf(-2.0 + 4000.0 / 1200.0), epsilon = 1e-9);
+        assert!(material.get_viscosity(500.0).unwrap().is_infinite());
+    }
+
+    #[test]
+    fn test_tabulated_properties_override_constants() {
+        let csv = "temperature,k,cp,rho,emissivity\n\
+                   25,50,450,7850,0.3\n\
+                   525,40,,7750,\n\
+                   1025,30,650,7650,0.7\n";
+        let tables = PropertyTables::from_csv(csv).unwrap();
+        assert_eq!(tables.specific_heat.as_ref().unwrap().temperatures, vec![25.0, 1025.0]);
+
+        let mut material = MaterialProperties::new("Tabulated", 7000.0, 500.0, 45.0);
+        material.thermal_conductivity_coefficients = Some(vec![45.0, 1.0]);
+        material.reference_temperature = Some(25.0);
+        material.set_property_tables(tables.clone()).unwrap();
+
+        // Interpolação linear e extremidades constantes
+        assert_relative_eq!(material.get_thermal_conductivity(275.0), 45.0);
+        assert_relative_eq!(material.get_thermal_conductivity(2000.0), 30.0);
+        assert_relative_eq!(material.get_specific_heat(525.0), 550.0);
+        assert_relative_eq!(material.get_density(-100.0), 7850.0);
+        assert_relative_eq!(material.get_emissivity(775.0), 0.6);
+
+        let json = serde_json::to_string(&tables).unwrap();
+        assert_eq!(PropertyTables::from_json(&json).unwrap(), tables);
+
+        // Temperaturas em K e tabelas inválidas
+        let kelvin = PropertyTables::from_csv("T(K),k\n298.15,50\n1298.15,30").unwrap();
+        assert_relative_eq!(kelvin.thermal_conductivity.unwrap().evaluate(525.0), 40.0, epsilon = 1e-9);
+        assert!(PropertyTables::from_csv("temperature,eps\n25,1.5").is_err());
+        assert!(PropertyTable::new(vec![(25.0, 1.0), (25.0, 2.0)]).is_err());
+    }
+
+    #[test]
+    fn test_property_cache_accuracy() {
+        let library = MaterialLibrary::new();
+        let steel = library.get_material("steel").unwrap();
+        
+        let config = PropertyCacheConfig {
+            min_temperature: 0.0,
+            max_temperature: 2000.0,
+            bucket_width: 100.0,
+            min_bucket_width: 1.0,
+            max_relative_error: 1e-6,
+        };
+        let cache = PropertyCache::new(steel, &config).unwrap();
+        
+        // Propriedades lineares são reproduzidas exatamente
+        for &t in &[0.0, 37.5, 812.3, 2000.0] {
+            assert_relative_eq!(cache.get_density(t), steel.get_density(t), epsilon = 1e-6);
+            assert_relative_eq!(cache.get_specific_heat(t), steel.get_specific_heat(t), epsilon = 1e-6);
+            assert_relative_eq!(cache.get_thermal_conductivity(t), steel.get_thermal_conductivity(t), epsilon = 1e-6);
+        }
+        
+        // Fora do intervalo tabelado o material é avaliado diretamente
+        assert_relative_eq!(cache.get_density(2500.0), steel.get_density(2500.0));
+        
+        // Propriedades não lineares forçam o refinamento das faixas
+        let mut material = MaterialProperties::new("Quadratic", 1000.0, 1500.0, 0.5);
+        material.specific_heat_coefficients = Some(vec![1500.0, 0.0, 50.0]);
+        material.reference_temperature = Some(0.0);
+        let cache = PropertyCache::new(&material, &config).unwrap();
+        assert!(cache.bucket_width() < 100.0);
+        assert!(cache.max_midpoint_error() <= 1e-6 || cache.bucket_width() <= 1.0);
+    }
+
+    #[test]
+    fn test_user_material_library_persistence() {
+        let dir = std::env::temp_dir().join(format!("plasma_materials_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut library = MaterialLibrary::with_user_directory(&dir).unwrap();
+        let predefined = library.get_material_ids().len();
+
+        let mut custom = MaterialProperties::new("Escória", 2800.0, 900.0, 1.5);
+        custom.emissivity = 0.85;
+        library.save_user_material("slag", custom).unwrap();
+        let mut steel = library.get_material_clone("steel").unwrap();
+        steel.density = 7900.0;
+        library.save_user_material("steel", steel).unwrap();
+        assert!(library.save_user_material("../escape", MaterialProperties::new("X", 1.0, 1.0, 1.0)).is_err());
+        assert!(library.save_user_material("zero", MaterialProperties::new("X", 0.0, 1.0, 1.0)).is_err());
+
+        // Recarregado do disco em uma nova biblioteca
+        let mut reloaded = MaterialLibrary::with_user_directory(&dir).unwrap();
+        assert_eq!(reloaded.get_material_ids().len(), predefined + 1);
+        assert!(reloaded.is_user_material("slag"));
+        assert_relative_eq!(reloaded.get_material("slag").unwrap().emissivity, 0.85);
+        assert_relative_eq!(reloaded.get_material("steel").unwrap().density, 7900.0);
+
+        // Remover a substituição restaura o pré-definido; pré-definidos não podem ser removidos
+        assert!(reloaded.delete_user_material("steel").unwrap());
+        assert_relative_eq!(reloaded.get_material("steel").unwrap().density, MaterialLibrary::new().get_material("steel").unwrap().density);
+        assert!(reloaded.delete_user_material("steel").is_err());
+        assert!(reloaded.delete_user_material("slag").unwrap());
+        assert!(!reloaded.delete_user_material("slag").unwrap());
+        assert!(!dir.join("slag.json").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
@@ -4,6 +4,101 @@ use ndarray::{Array1, Array2, Array3};
 use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
 
+use super::physics::PlasmaTorch;
+
+/// Enumeração que representa o tratamento do termo singular 1/r no eixo de simetria (r=0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default)]
+pub enum AxisTreatment {
+    /// Volume finito integrado: o nó do eixo é um cilindro de raio dr/2 com uma única
+    /// face radial, conservativo para condutividade variável
+    #[default]
+    FiniteVolume,
+    /// Regra de L'Hôpital: (1/r)·∂/∂r(k·r·∂T/∂r) → 2·k·∂²T/∂r² em r=0, com simetria
+    /// T(-dr) = T(dr) e condutividade avaliada no próprio nó
+    LHopital,
+}
+
+
+/// Enumeração que representa a distribuição dos nós ao longo de uma direção da malha
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
+pub enum MeshGrading {
+    /// Espaçamento constante
+    #[default]
+    Uniform,
+    /// Progressão geométrica Δ_{k+1} = ratio·Δ_k: ratio > 1 concentra os nós no início
+    /// (eixo/base, junto à tocha), ratio < 1 no fim (parede lateral/topo)
+    Geometric {
+        /// Razão entre espaçamentos sucessivos
+        ratio: f64,
+    },
+    /// Tangente hiperbólica: concentra os nós nas duas extremidades (eixo e parede,
+    /// base e topo); maior `stretching` concentra mais
+    Hyperbolic {
+        /// Fator de estiramento β (> 0)
+        stretching: f64,
+    },
+}
+
+
+impl MeshGrading {
+    /// Valida os parâmetros da distribuição
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            MeshGrading::Uniform => Ok(()),
+            MeshGrading::Geometric { ratio } if ratio.is_finite() && ratio > 0.0 => Ok(()),
+            MeshGrading::Geometric { ratio } => Err(format!("Razão geométrica da malha deve ser positiva: {}", ratio)),
+            MeshGrading::Hyperbolic { stretching } if stretching.is_finite() && stretching > 0.0 => Ok(()),
+            MeshGrading::Hyperbolic { stretching } => {
+                Err(format!("Fator de estiramento hiperbólico da malha deve ser positivo: {}", stretching))
+            }
+        }
+    }
+
+    /// Gera `n` coordenadas crescentes de 0 a `length` com esta distribuição
+    pub fn coordinates(&self, length: f64, n: usize) -> Array1<f64> {
+        let last = (n - 1) as f64;
+        let mut coords = match *self {
+            MeshGrading::Geometric { ratio } if (ratio - 1.0).abs() > 1e-12 => {
+                let total = ratio.powf(last) - 1.0;
+                Array1::from_iter((0..n).map(|k| length * (ratio.powf(k as f64) - 1.0) / total))
+            }
+            MeshGrading::Hyperbolic { stretching } => {
+                let scale = stretching.tanh();
+                Array1::from_iter((0..n).map(|k| {
+                    let xi = k as f64 / last;
+                    length / 2.0 * (1.0 + (stretching * (2.0 * xi - 1.0)).tanh() / scale)
+                }))
+            }
+            _ => Array1::linspace(0.0, length, n),
+        };
+        // Extremidades exatas, independentemente de arredondamentos
+        coords[0] = 0.0;
+        coords[n - 1] = length;
+        coords
+    }
+}
+
+/// Localiza o intervalo [x_k, x_{k+1}] que contém `x` e a fração dentro dele
+///
+/// Valores fora do intervalo das coordenadas são limitados às extremidades.
+fn locate(coords: &Array1<f64>, x: f64) -> (usize, f64) {
+    let n = coords.len();
+    if n < 2 {
+        return (0, 0.0);
+    }
+    let x = x.clamp(coords[0], coords[n - 1]);
+    let upper = coords.as_slice().map_or_else(
+        || coords.iter().position(|&c| c > x).unwrap_or(n),
+        |slice| slice.partition_point(|&c| c <= x),
+    );
+    let index = upper.saturating_sub(1).min(n - 2);
+    let width = coords[index + 1] - coords[index];
+    let fraction = if width > 0.0 { ((x - coords[index]) / width).clamp(0.0, 1.0) } else { 0.0 };
+    (index, fraction)
+}
+
 /// Estrutura que representa a malha de discretização cilíndrica com suporte a geometria avançada
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CylindricalMesh {
@@ -23,9 +118,9 @@ pub struct CylindricalMesh {
     pub z_coords: Array1<f64>,
     /// Coordenadas angulares dos nós (radianos) - para visualização 3D
     pub theta_coords: Array1<f64>,
-    /// Tamanho do passo radial (m)
+    /// Tamanho médio do passo radial, R/(nr-1) (m); em malhas graduadas use `radial_spacing`
     pub dr: f64,
-    /// Tamanho do passo axial (m)
+    /// Tamanho médio do passo axial, H/(nz-1) (m); em malhas graduadas use `axial_spacing`
     pub dz: f64,
     /// Tamanho do passo angular (radianos)
     pub dtheta: f64,
@@ -33,17 +128,41 @@ pub struct CylindricalMesh {
     pub cell_volumes: Array2<f64>,
     /// Mapa de zonas (opcional) - identifica diferentes zonas no cilindro
     pub zone_map: Option<Array2<usize>>,
+    /// Distribuição dos nós na direção radial
+    #[serde(default)]
+    pub radial_grading: MeshGrading,
+    /// Distribuição dos nós na direção axial
+    #[serde(default)]
+    pub axial_grading: MeshGrading,
 }
 
 impl CylindricalMesh {
-    /// Cria uma nova malha cilíndrica com dimensões e número de nós especificados
+    /// Cria uma nova malha cilíndrica uniforme com dimensões e número de nós especificados
     pub fn new(height: f64, radius: f64, nr: usize, nz: usize, ntheta: usize) -> Self {
+        Self::new_graded(height, radius, nr, nz, ntheta, MeshGrading::Uniform, MeshGrading::Uniform)
+    }
+
+    /// Cria uma nova malha cilíndrica com distribuições de nós independentes em r e z
+    ///
+    /// As faces dos volumes de controle ficam nos pontos médios entre nós vizinhos,
+    /// de modo que volumes e áreas seguem o espaçamento local.
+    pub fn new_graded(
+        height: f64,
+        radius: f64,
+        nr: usize,
+        nz: usize,
+        ntheta: usize,
+        radial_grading: MeshGrading,
+        axial_grading: MeshGrading,
+    ) -> Self {
         // Validação de entrada
         assert!(height > 0.0, "Altura deve ser positiva");
         assert!(radius > 0.0, "Raio deve ser positivo");
         assert!(nr >= 2, "Número de nós radiais deve ser pelo menos 2");
         assert!(nz >= 2, "Número de nós axiais deve ser pelo menos 2");
         assert!(ntheta >= 4, "Número de nós angulares deve ser pelo menos 4");
+        assert!(radial_grading.validate().is_ok(), "Distribuição radial inválida");
+        assert!(axial_grading.validate().is_ok(), "Distribuição axial inválida");
 
         // Calcular tamanhos de passo
         let dr = radius / (nr as f64 - 1.0);
@@ -51,33 +170,12 @@ impl CylindricalMesh {
         let dtheta = 2.0 * PI / ntheta as f64;
 
         // Criar coordenadas
-        let r_coords = Array1::linspace(0.0, radius, nr);
-        let z_coords = Array1::linspace(0.0, height, nz);
+        let r_coords = radial_grading.coordinates(radius, nr);
+        let z_coords = axial_grading.coordinates(height, nz);
         let theta_coords = Array1::linspace(0.0, 2.0 * PI * (1.0 - 1.0 / ntheta as f64), ntheta);
 
-        // Calcular volumes dos elementos
-        let mut cell_volumes = Array2::<f64>::zeros((nr, nz));
-        for i in 0..nr {
-            for j in 0..nz {
-                // Para o eixo central (r=0), usamos um volume especial
-                if i == 0 {
-                    let r_outer = r_coords[i + 1];
-                    cell_volumes[[i, j]] = PI * r_outer * r_outer * dz / 4.0;
-                } else if i == nr - 1 {
-                    // Para a borda externa
-                    let r_inner = r_coords[i - 1];
-                    let r_center = r_coords[i];
-                    cell_volumes[[i, j]] = PI * (r_center * r_center - r_inner * r_inner) * dz / 2.0;
-                } else {
-                    // Para nós internos
-                    let r_inner = r_coords[i - 1];
-                    let r_outer = r_coords[i + 1];
-                    cell_volumes[[i, j]] = PI * (r_outer * r_outer - r_inner * r_inner) * dz / 4.0;
-                }
-            }
-        }
-
-        Self {
+        // Calcular volumes dos volumes de controle (anéis entre as faces r_{i-1/2} e r_{i+1/2})
+        let mut mesh = Self {
             height,
             radius,
             nr,
@@ -89,8 +187,57 @@ impl CylindricalMesh {
             dr,
             dz,
             dtheta,
-            cell_volumes,
+            cell_volumes: Array2::<f64>::zeros((nr, nz)),
             zone_map: None,
+            radial_grading,
+            axial_grading,
+        };
+
+        for i in 0..nr {
+            // No eixo (r=0) o volume é o cilindro até a primeira face; na borda, meia célula
+            let ring_area = mesh.axial_face_area(i);
+            for j in 0..nz {
+                mesh.cell_volumes[[i, j]] = ring_area * mesh.axial_cell_height(j);
+            }
+        }
+        mesh
+    }
+
+    /// Retorna o espaçamento entre os nós radiais `i` e `i + 1` (m)
+    pub fn radial_spacing(&self, i: usize) -> f64 {
+        self.r_coords[i + 1] - self.r_coords[i]
+    }
+
+    /// Retorna o espaçamento entre os nós axiais `j` e `j + 1` (m)
+    pub fn axial_spacing(&self, j: usize) -> f64 {
+        self.z_coords[j + 1] - self.z_coords[j]
+    }
+
+    /// Retorna o maior espaçamento entre o nó `(i, j)` e seus vizinhos em r e em z (m)
+    ///
+    /// É o comprimento resolvido localmente pela malha; reproduz (`dr`, `dz`) em malhas uniformes.
+    pub fn local_spacing(&self, i: usize, j: usize) -> (f64, f64) {
+        let radial = self.radial_spacing(i.saturating_sub(1)).max(self.radial_spacing(i.min(self.nr - 2)));
+        let axial = self.axial_spacing(j.saturating_sub(1)).max(self.axial_spacing(j.min(self.nz - 2)));
+        (radial, axial)
+    }
+
+    /// Retorna a posição da face radial entre os nós `i` e `i + 1` (m)
+    pub fn radial_face_position(&self, i: usize) -> f64 {
+        (self.r_coords[i] + self.r_coords[i + 1]) / 2.0
+    }
+
+    /// Retorna a altura do volume de controle do nó axial `j` (m)
+    ///
+    /// Distância entre as faces nos pontos médios; base e topo usam o espaçamento
+    /// adjacente, o que reproduz `dz` em malhas uniformes.
+    pub fn axial_cell_height(&self, j: usize) -> f64 {
+        if j == 0 {
+            self.axial_spacing(0)
+        } else if j == self.nz - 1 {
+            self.axial_spacing(self.nz - 2)
+        } else {
+            (self.z_coords[j + 1] - self.z_coords[j - 1]) / 2.0
         }
     }
 
@@ -100,6 +247,63 @@ impl CylindricalMesh {
         self.zone_map = Some(zone_map);
     }
 
+    /// Distribui a potência das tochas sobre as células da malha (W/m³)
+    ///
+    /// Cada tocha deposita sua potência com o seu perfil de fluxo (gaussiano por padrão)
+    /// no plano r-z centrado em sua posição. Os pesos são normalizados pelos volumes das
+    /// células, de modo que a integral do campo resultante é igual à soma das potências
+    /// (kW -> W). A resolução mínima da pegada é o espaçamento local no nó mais próximo
+    /// da tocha, e uma pegada que não alcança nenhum nó é depositada nesse nó.
+    pub fn distribute_torch_heat(&self, torches: &[PlasmaTorch]) -> Array2<f64> {
+        let mut heat = Array2::<f64>::zeros((self.nr, self.nz));
+
+        for torch in torches {
+            let power = torch.power * 1000.0;
+            if power <= 0.0 {
+                continue;
+            }
+
+            let (ti, tj) = self.nearest_node_index(torch.r_position, torch.z_position);
+            let (local_dr, local_dz) = self.local_spacing(ti, tj);
+            let resolution = local_dr.max(local_dz);
+
+            let mut weights = Array2::<f64>::zeros((self.nr, self.nz));
+            let mut weighted_volume = 0.0;
+
+            for i in 0..self.nr {
+                let dr = self.r_coords[i] - torch.r_position;
+                for j in 0..self.nz {
+                    let dz = self.z_coords[j] - torch.z_position;
+                    let w = torch.flux_profile.weight((dr * dr + dz * dz).sqrt(), torch.diameter, resolution);
+                    weights[[i, j]] = w;
+                    weighted_volume += w * self.cell_volumes[[i, j]];
+                }
+            }
+
+            if weighted_volume > 0.0 {
+                heat.scaled_add(power / weighted_volume, &weights);
+            } else {
+                heat[[ti, tj]] += power / self.cell_volumes[[ti, tj]];
+            }
+        }
+
+        heat
+    }
+
+    /// Retorna a área da face radial entre os nós `i` e `i + 1` na linha axial `j` (m²)
+    ///
+    /// A face fica em r_{i+1/2} = (r_i + r_{i+1})/2 e tem a altura do volume de controle.
+    pub fn radial_face_area(&self, i: usize, j: usize) -> f64 {
+        2.0 * PI * self.radial_face_position(i) * self.axial_cell_height(j)
+    }
+
+    /// Retorna a área da face axial (anel) do volume de controle do nó radial `i` (m²)
+    pub fn axial_face_area(&self, i: usize) -> f64 {
+        let r_inner = if i == 0 { 0.0 } else { self.radial_face_position(i - 1) };
+        let r_outer = if i == self.nr - 1 { self.radius } else { self.radial_face_position(i) };
+        PI * (r_outer * r_outer - r_inner * r_inner)
+    }
+
     /// Retorna o volume total do cilindro
     pub fn total_volume(&self) -> f64 {
         PI * self.radius * self.radius * self.height
@@ -107,30 +311,38 @@ impl CylindricalMesh {
 
     /// Retorna o índice do nó mais próximo às coordenadas dadas
     pub fn nearest_node_index(&self, r: f64, z: f64) -> (usize, usize) {
-        let i = (r / self.dr).round() as usize;
-        let j = (z / self.dz).round() as usize;
+        let (i, fr) = locate(&self.r_coords, r);
+        let (j, fz) = locate(&self.z_coords, z);
         
-        // Limitar aos índices válidos
-        let i = i.min(self.nr - 1);
-        let j = j.min(self.nz - 1);
+        // Nó mais próximo dentro do intervalo localizado
+        let i = if fr >= 0.5 { i + 1 } else { i };
+        let j = if fz >= 0.5 { j + 1 } else { j };
         
         (i, j)
     }
 
+    /// Interpola bilinearmente um campo nodal (nr, nz) nas coordenadas (r, z)
+    ///
+    /// Coordenadas fora do domínio são limitadas à fronteira mais próxima.
+    pub fn interpolate(&self, field: &Array2<f64>, r: f64, z: f64) -> f64 {
+        let (i, fr) = locate(&self.r_coords, r);
+        let (j, fz) = locate(&self.z_coords, z);
+
+        let (i1, j1) = ((i + 1).min(self.nr - 1), (j + 1).min(self.nz - 1));
+        field[[i, j]] * (1.0 - fr) * (1.0 - fz)
+            + field[[i1, j]] * fr * (1.0 - fz)
+            + field[[i, j1]] * (1.0 - fr) * fz
+            + field[[i1, j1]] * fr * fz
+    }
+
     /// Retorna o índice do nó mais próximo às coordenadas 3D dadas
     pub fn nearest_node_index_3d(&self, r: f64, theta: f64, z: f64) -> (usize, usize, usize) {
-        let i = (r / self.dr).round() as usize;
+        let (i, j) = self.nearest_node_index(r, z);
         
         // Normalizar theta para [0, 2π)
         let normalized_theta = theta % (2.0 * PI);
         let k = (normalized_theta / self.dtheta).round() as usize % self.ntheta;
         
-        let j = (z / self.dz).round() as usize;
-        
-        // Limitar aos índices válidos
-        let i = i.min(self.nr - 1);
-        let j = j.min(self.nz - 1);
-        
         (i, k, j)
     }
 
@@ -155,7 +367,7 @@ impl CylindricalMesh {
     }
 
     /// Cria um array 3D para visualização 3D (r, theta, z)
-    pub fn create_3d_temperature_array(&self, time_step: usize) -> Array3<f64> {
+    pub fn create_3d_temperature_array(&self, _time_step: usize) -> Array3<f64> {
         Array3::<f64>::zeros((self.nr, self.ntheta, self.nz))
     }
 
@@ -168,12 +380,98 @@ impl CylindricalMesh {
     pub fn get_node_zone(&self, i: usize, j: usize) -> Option<usize> {
         self.zone_map.as_ref().map(|zones| zones[[i, j]])
     }
+
+    /// Retorna a descrição geométrica da malha (nós, faces, tamanhos e volumes das células)
+    pub fn info(&self) -> MeshInfo {
+        let mut r_faces = vec![0.0];
+        r_faces.extend((0..self.nr - 1).map(|i| self.radial_face_position(i)));
+        r_faces.push(self.radius);
+        let mut z_faces = vec![0.0];
+        z_faces.extend((0..self.nz - 1).map(|j| (self.z_coords[j] + self.z_coords[j + 1]) / 2.0));
+        z_faces.push(self.height);
+
+        MeshInfo {
+            nr: self.nr,
+            nz: self.nz,
+            height: self.height,
+            radius: self.radius,
+            radial_grading: self.radial_grading,
+            axial_grading: self.axial_grading,
+            r_coords: self.r_coords.to_vec(),
+            z_coords: self.z_coords.to_vec(),
+            radial_cell_widths: r_faces.windows(2).map(|face| face[1] - face[0]).collect(),
+            axial_cell_heights: (0..self.nz).map(|j| self.axial_cell_height(j)).collect(),
+            r_faces,
+            z_faces,
+            cell_volumes: self.cell_volumes.outer_iter().map(|row| row.to_vec()).collect(),
+            total_volume: self.total_volume(),
+        }
+    }
+}
+
+/// Estrutura que representa a geometria de uma malha para visualização no frontend
+///
+/// Os nós são os pontos em que o campo é calculado; as faces delimitam os volumes de
+/// controle (r_{i±1/2}, z_{j±1/2}), de modo que malhas graduadas são desenhadas com a
+/// célula real de cada nó.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeshInfo {
+    /// Número de nós radiais
+    pub nr: usize,
+    /// Número de nós axiais
+    pub nz: usize,
+    /// Altura do cilindro (m)
+    pub height: f64,
+    /// Raio do cilindro (m)
+    pub radius: f64,
+    /// Distribuição dos nós na direção radial
+    pub radial_grading: MeshGrading,
+    /// Distribuição dos nós na direção axial
+    pub axial_grading: MeshGrading,
+    /// Coordenadas radiais dos nós (m)
+    pub r_coords: Vec<f64>,
+    /// Coordenadas axiais dos nós (m)
+    pub z_coords: Vec<f64>,
+    /// Posições das faces radiais (nr + 1 valores, de 0 ao raio) (m)
+    pub r_faces: Vec<f64>,
+    /// Posições das faces axiais (nz + 1 valores, de 0 à altura) (m)
+    pub z_faces: Vec<f64>,
+    /// Largura radial do volume de controle de cada nó (m)
+    pub radial_cell_widths: Vec<f64>,
+    /// Altura do volume de controle de cada nó axial, como usada nos volumes (m)
+    pub axial_cell_heights: Vec<f64>,
+    /// Volumes de controle [i][j] (m³)
+    pub cell_volumes: Vec<Vec<f64>>,
+    /// Volume total do cilindro (m³)
+    pub total_volume: f64,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
+    use crate::simulation::physics::TorchFluxProfile;
+
+    #[test]
+    fn test_mesh_info_graded_geometry() {
+        let mesh = CylindricalMesh::new_graded(2.0, 1.0, 6, 5, 8, MeshGrading::Geometric { ratio: 1.5 }, MeshGrading::Uniform);
+        let info = mesh.info();
+        assert_eq!((info.r_coords.len(), info.r_faces.len(), info.radial_cell_widths.len()), (6, 7, 6));
+        assert_eq!((info.z_coords.len(), info.z_faces.len(), info.axial_cell_heights.len()), (5, 6, 5));
+        assert_eq!(info.r_faces[0], 0.0);
+        assert_relative_eq!(info.r_faces[6], 1.0);
+        assert_relative_eq!(info.radial_cell_widths.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+
+        // Malha graduada: células radiais crescem ao afastar-se do eixo
+        assert!(info.radial_cell_widths[4] > info.radial_cell_widths[1]);
+        assert_relative_eq!(info.axial_cell_heights[2], 0.5);
+
+        // Volumes coincidem com os da malha, como anéis entre as faces radiais
+        assert_eq!((info.cell_volumes.len(), info.cell_volumes[0].len()), (6, 5));
+        let ring = PI * (info.r_faces[3].powi(2) - info.r_faces[2].powi(2)) * info.axial_cell_heights[1];
+        assert_relative_eq!(info.cell_volumes[2][1], ring, epsilon = 1e-12);
+        assert_relative_eq!(info.total_volume, PI * 2.0);
+    }
 
     #[test]
     fn test_mesh_creation_with_theta() {
@@ -235,4 +533,128 @@ mod tests {
         assert_eq!(mesh.get_node_zone(2, 2), Some(0)); // Zona inferior
         assert_eq!(mesh.get_node_zone(2, 7), Some(1)); // Zona superior
     }
+
+    #[test]
+    fn test_cell_volumes_are_consistent_with_faces() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 11, 4, 8);
+        
+        // A soma dos anéis cobre exatamente a seção do cilindro
+        let section: f64 = (0..mesh.nr).map(|i| mesh.axial_face_area(i)).sum();
+        assert_relative_eq!(section, PI * 0.25, epsilon = 1e-12);
+        
+        // Volume do eixo: cilindro de raio dr/2; volumes internos: 2π·r·dr·dz
+        assert_relative_eq!(mesh.cell_volumes[[0, 0]], PI * (mesh.dr / 2.0).powi(2) * mesh.dz, epsilon = 1e-12);
+        let i = 4;
+        assert_relative_eq!(mesh.cell_volumes[[i, 0]], 2.0 * PI * mesh.r_coords[i] * mesh.dr * mesh.dz, epsilon = 1e-12);
+        assert_relative_eq!(mesh.radial_face_area(0, 0), PI * mesh.dr * mesh.dz, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_graded_mesh() {
+        let mesh = CylindricalMesh::new_graded(
+            1.0, 0.5, 11, 9, 8,
+            MeshGrading::Geometric { ratio: 1.2 },
+            MeshGrading::Hyperbolic { stretching: 2.0 },
+        );
+        
+        // Geométrica: espaçamentos crescem com razão constante a partir do eixo
+        assert_relative_eq!(mesh.r_coords[mesh.nr - 1], 0.5);
+        assert_relative_eq!(mesh.radial_spacing(1) / mesh.radial_spacing(0), 1.2, epsilon = 1e-12);
+        // Hiperbólica: simétrica, nós concentrados na base e no topo
+        assert_relative_eq!(mesh.axial_spacing(0), mesh.axial_spacing(mesh.nz - 2), epsilon = 1e-12);
+        assert!(mesh.axial_spacing(0) < mesh.axial_spacing(mesh.nz / 2));
+        
+        // Os anéis continuam cobrindo a seção e as faces seguem o espaçamento local
+        let section: f64 = (0..mesh.nr).map(|i| mesh.axial_face_area(i)).sum();
+        assert_relative_eq!(section, PI * 0.25, epsilon = 1e-12);
+        let interior: f64 = (1..mesh.nz - 1).map(|j| mesh.axial_cell_height(j)).sum();
+        assert_relative_eq!(interior, (1.0 + mesh.z_coords[mesh.nz - 2] - mesh.z_coords[1]) / 2.0, epsilon = 1e-12);
+        
+        // Localização e interpolação usam as coordenadas reais
+        assert_eq!(mesh.nearest_node_index(mesh.r_coords[3] + 1e-6, mesh.z_coords[2]), (3, 2));
+        let field = Array2::from_shape_fn((mesh.nr, mesh.nz), |(i, j)| 2.0 * mesh.r_coords[i] + mesh.z_coords[j]);
+        assert_relative_eq!(mesh.interpolate(&field, 0.123, 0.456), 2.0 * 0.123 + 0.456, epsilon = 1e-12);
+        
+        assert!(MeshGrading::Geometric { ratio: 0.0 }.validate().is_err());
+        assert!(MeshGrading::Hyperbolic { stretching: -1.0 }.validate().is_err());
+    }
+
+    #[test]
+    fn test_distribute_torch_heat_conserves_power() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 10, 20, 8);
+        let torches = vec![
+            PlasmaTorch::new("torch1", 0.0, 0.0, 0.8, 180.0, 0.0, 100.0, 0.01, 5000.0),
+            PlasmaTorch::new("torch2", 0.3, 90.0, 0.5, 180.0, 0.0, 50.0, 0.01, 5000.0),
+        ];
+        
+        let heat = mesh.distribute_torch_heat(&torches);
+        let total: f64 = heat.iter().zip(mesh.cell_volumes.iter()).map(|(q, v)| q * v).sum();
+        
+        assert_relative_eq!(total, 150_000.0, epsilon = 1e-6);
+        assert!(heat.iter().all(|&q| q >= 0.0));
+    }
+
+    #[test]
+    fn test_torch_heat_uses_local_spacing() {
+        let uniform = CylindricalMesh::new(1.0, 0.5, 10, 20, 8);
+        assert_relative_eq!(uniform.local_spacing(0, 0).0, uniform.dr, epsilon = 1e-12);
+        assert_relative_eq!(uniform.local_spacing(4, 19).1, uniform.dz, epsilon = 1e-12);
+
+        // Nós concentrados junto ao eixo e à base: a pegada mínima segue o espaçamento
+        // junto à tocha, bem menor que o espaçamento médio
+        let mesh = CylindricalMesh::new_graded(
+            1.0, 0.5, 12, 16, 8,
+            MeshGrading::Geometric { ratio: 1.4 },
+            MeshGrading::Geometric { ratio: 1.3 },
+        );
+        let (local_dr, local_dz) = mesh.local_spacing(0, 0);
+        let resolution = local_dr.max(local_dz);
+        assert!(resolution < mesh.dr.min(mesh.dz));
+
+        let heat_with_sigma = |sigma: f64| {
+            let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.0, 180.0, 0.0, 100.0, 0.01, 5000.0);
+            torch.flux_profile = TorchFluxProfile::Gaussian { sigma: Some(sigma) };
+            mesh.distribute_torch_heat(&[torch])
+        };
+        let narrow = heat_with_sigma(1e-6);
+        assert_eq!(narrow, heat_with_sigma(resolution));
+        assert!(narrow[[0, 0]] > heat_with_sigma(mesh.dr.max(mesh.dz))[[0, 0]]);
+        let total: f64 = narrow.iter().zip(mesh.cell_volumes.iter()).map(|(q, v)| q * v).sum();
+        assert_relative_eq!(total, 100_000.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_torch_flux_profiles() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 10, 20, 8);
+        let with_profile = |profile: TorchFluxProfile| {
+            let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 180.0, 0.0, 100.0, 0.01, 5000.0);
+            torch.flux_profile = profile;
+            mesh.distribute_torch_heat(&[torch])
+        };
+
+        let profiles = [
+            TorchFluxProfile::Gaussian { sigma: Some(0.1) },
+            TorchFluxProfile::Exponential { decay_length: 0.1 },
+            TorchFluxProfile::TopHat { radius: 0.2 },
+            TorchFluxProfile::TopHat { radius: 1e-6 },
+        ];
+        for profile in profiles {
+            let heat = with_profile(profile.clone());
+            let total: f64 = heat.iter().zip(mesh.cell_volumes.iter()).map(|(q, v)| q * v).sum();
+            assert_relative_eq!(total, 100_000.0, epsilon = 1e-6);
+            assert!(profile.validate().is_ok());
+        }
+
+        // Cartola: densidade uniforme dentro do raio e nula fora
+        let top_hat = with_profile(TorchFluxProfile::TopHat { radius: 0.2 });
+        let (center_i, center_j) = mesh.nearest_node_index(0.0, 0.5);
+        assert_relative_eq!(top_hat[[center_i, center_j]], top_hat[[center_i + 1, center_j]], epsilon = 1e-9);
+        assert_eq!(top_hat[[9, center_j]], 0.0);
+
+        // Decaimento exponencial espalha mais que a gaussiana de mesmo comprimento
+        let gaussian = with_profile(TorchFluxProfile::Gaussian { sigma: Some(0.1) });
+        let exponential = with_profile(TorchFluxProfile::Exponential { decay_length: 0.1 });
+        assert!(exponential[[9, center_j]] > gaussian[[9, center_j]]);
+        assert!(TorchFluxProfile::Exponential { decay_length: 0.0 }.validate().is_err());
+    }
 }
@@ -1,14 +1,20 @@
 // Implementação do módulo de métricas e exportação para o simulador de fornalha de plasma
 
-use ndarray::{Array1, Array2, Array3, ArrayView3};
+use ndarray::{Array2, Array3};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, Write};
+use std::io::Write;
 use std::path::Path;
 
 use crate::simulation::state::SimulationState;
 use crate::simulation::mesh::CylindricalMesh;
+use crate::simulation::solver::{resolve_cell_materials, SimulationResults};
+use crate::simulation::annotations::render_annotations_markdown;
+use crate::simulation::boundary::{BoundaryCondition, BoundarySide};
+
+/// Simplificação do fluxo de calor: condutividade térmica constante (W/(m·K))
+const THERMAL_CONDUCTIVITY: f64 = 50.0;
 
 /// Estrutura que representa as métricas calculadas a partir dos resultados da simulação
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,12 +29,18 @@ pub struct SimulationMetrics {
     pub std_temperature: f64,
     /// Gradiente máximo de temperatura (°C/m)
     pub max_gradient: f64,
+    /// Gradiente médio de temperatura (°C/m)
+    pub avg_gradient: f64,
     /// Fluxo de calor máximo (W/m²)
     pub max_heat_flux: f64,
+    /// Fluxo de calor médio (W/m²)
+    pub avg_heat_flux: f64,
     /// Energia total no sistema (J)
     pub total_energy: f64,
     /// Taxa de aquecimento média (°C/s)
     pub avg_heating_rate: f64,
+    /// Fração da energia fornecida pelas tochas retida no material (0 a 1)
+    pub energy_efficiency: f64,
     /// Métricas por região
     pub region_metrics: HashMap<String, RegionMetrics>,
     /// Métricas temporais
@@ -55,9 +67,9 @@ pub struct RegionMetrics {
 /// Estrutura que representa as métricas temporais
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemporalMetrics {
-    /// Tempo para atingir 50% da temperatura máxima (s)
+    /// Tempo para a temperatura média atingir 50% do seu pico (s)
     pub time_to_half_max: f64,
-    /// Tempo para atingir 90% da temperatura máxima (s)
+    /// Tempo para a temperatura média atingir 90% do seu pico (s)
     pub time_to_90_percent_max: f64,
     /// Taxa de aquecimento máxima (°C/s)
     pub max_heating_rate: f64,
@@ -66,7 +78,7 @@ pub struct TemporalMetrics {
 }
 
 /// Enumeração que representa os formatos de exportação suportados
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExportFormat {
     /// Formato CSV (Comma-Separated Values)
     CSV,
@@ -77,7 +89,7 @@ pub enum ExportFormat {
 }
 
 /// Estrutura que representa as opções de exportação
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportOptions {
     /// Formato de exportação
     pub format: ExportFormat,
@@ -97,10 +109,632 @@ pub struct ExportOptions {
     pub time_steps: Option<Vec<usize>>,
 }
 
+/// Estrutura que acompanha o progresso de uma exportação em unidades de trabalho
+struct ExportProgress<'a> {
+    /// Callback de progresso (retorna `false` para cancelar)
+    callback: Option<&'a dyn Fn(f32) -> bool>,
+    /// Número total de unidades de trabalho
+    total: usize,
+    /// Número de unidades concluídas
+    done: usize,
+}
+
+impl<'a> ExportProgress<'a> {
+    fn new(callback: Option<&'a dyn Fn(f32) -> bool>, total: usize) -> Self {
+        Self { callback, total, done: 0 }
+    }
+
+    /// Conclui uma unidade de trabalho e reporta a fração concluída
+    fn advance(&mut self) -> Result<(), String> {
+        self.done += 1;
+        self.report(self.done as f32 / self.total.max(1) as f32)
+    }
+
+    /// Reporta o fim da exportação
+    fn finish(&self) -> Result<(), String> {
+        self.report(1.0)
+    }
+
+    fn report(&self, fraction: f32) -> Result<(), String> {
+        match self.callback {
+            Some(callback) if !callback(fraction.min(1.0)) => Err("Exportação cancelada".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Estrutura que representa a configuração da previsão de temperatura de vazamento
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapTemperatureConfig {
+    /// Temperatura de vazamento desejada (°C)
+    pub tapping_temperature: f64,
+    /// Margem de confiança acima da temperatura de vazamento (°C)
+    pub confidence_margin: f64,
+    /// Fração fundida mínima para uma célula pertencer ao banho (0-1)
+    pub melt_fraction_threshold: f64,
+    /// Número de pontos finais usados na extrapolação
+    pub fit_window: usize,
+}
+
+impl Default for TapTemperatureConfig {
+    fn default() -> Self {
+        Self {
+            tapping_temperature: 1550.0,
+            confidence_margin: 25.0,
+            melt_fraction_threshold: 0.99,
+            fit_window: 10,
+        }
+    }
+}
+
+/// Estrutura que representa a previsão do instante de vazamento
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapTemperaturePrediction {
+    /// Tempos avaliados (s)
+    pub times: Vec<f64>,
+    /// Temperatura média mássica do banho fundido (°C), None sem banho
+    pub pool_temperature: Vec<Option<f64>>,
+    /// Massa do banho fundido (kg)
+    pub pool_mass: Vec<f64>,
+    /// Primeiro instante em que o banho superou a temperatura de vazamento com margem (s)
+    pub tap_time: Option<f64>,
+    /// Instante previsto por extrapolação linear, quando ainda não atingido (s)
+    pub predicted_tap_time: Option<f64>,
+    /// Taxa de aquecimento recente do banho (°C/s)
+    pub pool_heating_rate: f64,
+}
+
+/// Calcula a temperatura média mássica e a massa do banho fundido em um passo
+///
+/// Retorna None quando nenhuma célula possui fração fundida acima do limite.
+pub fn molten_pool_temperature(
+    results: &SimulationResults,
+    step: usize,
+    melt_fraction_threshold: f64,
+) -> Option<(f64, f64)> {
+    let melt_fraction = results.phase_change_info.as_ref()?.melt_fraction.as_ref()?;
+    if step >= melt_fraction.shape()[2] || step >= results.temperature.shape()[2] {
+        return None;
+    }
+
+    let material = &results.parameters.material;
+    let mut mass = 0.0;
+    let mut weighted_temperature = 0.0;
+
+    for i in 0..results.mesh.nr {
+        for j in 0..results.mesh.nz {
+            if melt_fraction[[i, j, step]] < melt_fraction_threshold {
+                continue;
+            }
+            let temperature = results.temperature[[i, j, step]];
+            let cell_mass = material.get_density(temperature) * results.mesh.cell_volumes[[i, j]];
+            mass += cell_mass;
+            weighted_temperature += cell_mass * temperature;
+        }
+    }
+
+    if mass > 0.0 {
+        Some((weighted_temperature / mass, mass))
+    } else {
+        None
+    }
+}
+
+/// Calcula a evolução da temperatura do banho e prevê o instante de vazamento
+pub fn calculate_tap_temperature_prediction(
+    results: &SimulationResults,
+    config: &TapTemperatureConfig,
+) -> Result<TapTemperaturePrediction, String> {
+    if results.phase_change_info.as_ref().and_then(|p| p.melt_fraction.as_ref()).is_none() {
+        return Err("Resultados sem fração fundida; habilite mudanças de fase".to_string());
+    }
+
+    let dt = results.parameters.time_step;
+    let n_steps = (results.executed_steps + 1).min(results.temperature.shape()[2]);
+    let threshold = config.tapping_temperature + config.confidence_margin;
+
+    let mut times = Vec::with_capacity(n_steps);
+    let mut pool_temperature = Vec::with_capacity(n_steps);
+    let mut pool_mass = Vec::with_capacity(n_steps);
+    let mut tap_time = None;
+
+    for step in 0..n_steps {
+        let time = step as f64 * dt;
+        let pool = molten_pool_temperature(results, step, config.melt_fraction_threshold);
+
+        if tap_time.is_none() {
+            if let Some((temperature, _)) = pool {
+                if temperature >= threshold {
+                    tap_time = Some(time);
+                }
+            }
+        }
+
+        times.push(time);
+        pool_temperature.push(pool.map(|(t, _)| t));
+        pool_mass.push(pool.map_or(0.0, |(_, m)| m));
+    }
+
+    // Ajuste linear sobre os últimos pontos com banho presente
+    let recent: Vec<(f64, f64)> = times.iter()
+        .zip(pool_temperature.iter())
+        .filter_map(|(&t, temp)| temp.map(|v| (t, v)))
+        .rev()
+        .take(config.fit_window.max(2))
+        .collect();
+
+    let mut pool_heating_rate = 0.0;
+    let mut predicted_tap_time = None;
+
+    if recent.len() >= 2 {
+        let n = recent.len() as f64;
+        let mean_t = recent.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_v = recent.iter().map(|p| p.1).sum::<f64>() / n;
+        let cov = recent.iter().map(|p| (p.0 - mean_t) * (p.1 - mean_v)).sum::<f64>();
+        let var = recent.iter().map(|p| (p.0 - mean_t).powi(2)).sum::<f64>();
+
+        if var > 0.0 {
+            pool_heating_rate = cov / var;
+            if tap_time.is_none() && pool_heating_rate > 0.0 {
+                let (last_time, last_value) = recent[0];
+                predicted_tap_time = Some(last_time + (threshold - last_value) / pool_heating_rate);
+            }
+        }
+    }
+
+    Ok(TapTemperaturePrediction {
+        times,
+        pool_temperature,
+        pool_mass,
+        tap_time,
+        predicted_tap_time: tap_time.or(predicted_tap_time),
+        pool_heating_rate,
+    })
+}
+
+/// Estrutura que representa a configuração do indicador de fluidez da escória
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlagFluidityConfig {
+    /// Viscosidade máxima para a escória ser vazável (Pa·s)
+    pub max_tappable_viscosity: f64,
+    /// Fração fundida mínima para uma célula pertencer ao banho (0-1)
+    pub melt_fraction_threshold: f64,
+}
+
+impl Default for SlagFluidityConfig {
+    fn default() -> Self {
+        Self {
+            max_tappable_viscosity: 1.0,
+            melt_fraction_threshold: 0.99,
+        }
+    }
+}
+
+/// Estrutura que representa o indicador de fluidez da escória em um passo de tempo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlagFluidityResult {
+    /// Passo de tempo avaliado
+    pub step: usize,
+    /// Índice de fluidez por célula (0 = sólido ou imóvel, 1 = vazável)
+    pub fluidity_field: Array2<f64>,
+    /// Viscosidade por célula fundida (Pa·s), infinita fora do banho
+    pub viscosity_field: Array2<f64>,
+    /// Massa do banho fundido (kg)
+    pub molten_mass: f64,
+    /// Massa vazável, com viscosidade abaixo do limite (kg)
+    pub tappable_mass: f64,
+    /// Fração vazável em relação à massa fundida (0-1)
+    pub tappable_fraction: f64,
+}
+
+/// Calcula o campo de fluidez da escória e a fração vazável em um passo
+///
+/// Requer uma lei de viscosidade em `MaterialProperties::viscosity_model` e a
+/// fração fundida nos resultados; a fluidez é η_max/η limitada a 1.
+pub fn calculate_slag_fluidity(
+    results: &SimulationResults,
+    step: usize,
+    config: &SlagFluidityConfig,
+) -> Result<SlagFluidityResult, String> {
+    let material = &results.parameters.material;
+    if material.viscosity_model.is_none() {
+        return Err(format!("Material {} não possui lei de viscosidade", material.name));
+    }
+    let melt_fraction = results.phase_change_info.as_ref()
+        .and_then(|p| p.melt_fraction.as_ref())
+        .ok_or_else(|| "Resultados sem fração fundida; habilite mudanças de fase".to_string())?;
+    if step >= results.temperature.shape()[2] || step >= melt_fraction.shape()[2] {
+        return Err(format!("Passo de tempo {} fora dos limites", step));
+    }
+
+    let (nr, nz) = (results.mesh.nr, results.mesh.nz);
+    let mut fluidity_field = Array2::<f64>::zeros((nr, nz));
+    let mut viscosity_field = Array2::<f64>::from_elem((nr, nz), f64::INFINITY);
+    let mut molten_mass = 0.0;
+    let mut tappable_mass = 0.0;
+
+    for i in 0..nr {
+        for j in 0..nz {
+            if melt_fraction[[i, j, step]] < config.melt_fraction_threshold {
+                continue;
+            }
+
+            let temperature = results.temperature[[i, j, step]];
+            let viscosity = material.get_viscosity(temperature).unwrap_or(f64::INFINITY);
+            let cell_mass = material.get_density(temperature) * results.mesh.cell_volumes[[i, j]];
+
+            viscosity_field[[i, j]] = viscosity;
+            fluidity_field[[i, j]] = if viscosity > 0.0 {
+                (config.max_tappable_viscosity / viscosity).min(1.0)
+            } else {
+                1.0
+            };
+
+            molten_mass += cell_mass;
+            if viscosity <= config.max_tappable_viscosity {
+                tappable_mass += cell_mass;
+            }
+        }
+    }
+
+    let tappable_fraction = if molten_mass > 0.0 { tappable_mass / molten_mass } else { 0.0 };
+
+    Ok(SlagFluidityResult {
+        step,
+        fluidity_field,
+        viscosity_field,
+        molten_mass,
+        tappable_mass,
+        tappable_fraction,
+    })
+}
+
+/// Calcula a evolução temporal da fração vazável ao longo de todos os passos executados
+pub fn calculate_tappable_fraction_history(
+    results: &SimulationResults,
+    config: &SlagFluidityConfig,
+) -> Result<Vec<(f64, f64)>, String> {
+    let n_steps = (results.executed_steps + 1).min(results.temperature.shape()[2]);
+    let dt = results.parameters.time_step;
+
+    (0..n_steps)
+        .map(|step| calculate_slag_fluidity(results, step, config)
+            .map(|r| (step as f64 * dt, r.tappable_fraction)))
+        .collect()
+}
+
+/// Enumeração que representa a região da parede do recipiente
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WallRegion {
+    /// Parede lateral (mais próxima de r = R)
+    SideWall,
+    /// Fundo (mais próximo de z = 0)
+    Bottom,
+    /// Topo (mais próximo de z = H)
+    Top,
+}
+
+/// Estrutura que representa a zona termicamente afetada de uma região de parede
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatAffectedZone {
+    /// Identificador da zona de material (nome do material principal para a zona base)
+    pub zone: String,
+    /// Região da parede
+    pub region: WallRegion,
+    /// Temperatura limite de dano do material (°C)
+    pub damage_threshold: f64,
+    /// Profundidade máxima, a partir da face quente, em que o limite foi excedido (m)
+    pub max_depth: f64,
+    /// Instante em que a profundidade máxima foi atingida (s), None se nunca excedido
+    pub time_of_max_depth: Option<f64>,
+    /// Espessura da parede na região (m)
+    pub wall_thickness: f64,
+    /// Maior temperatura registrada na região (°C)
+    pub peak_temperature: f64,
+}
+
+/// Célula de parede com a região e a distância até a face quente
+struct WallCell {
+    i: usize,
+    j: usize,
+    /// Índice da entrada correspondente no resultado
+    entry: usize,
+    /// Distância entre o centro da célula e o centro da célula não-parede mais próxima (m)
+    depth: f64,
+}
+
+/// Calcula a profundidade da zona termicamente afetada nas paredes do recipiente
+///
+/// Células cujo material define `damage_threshold` pertencem à parede (cadinho ou
+/// refratário). A face quente é a interface com as células de outros materiais, e a
+/// profundidade de cada célula é a menor distância, ao longo da linha radial ou da
+/// coluna axial, até uma célula fora da parede. Cada célula é atribuída à região da
+/// fronteira externa mais próxima (lateral, fundo ou topo).
+pub fn calculate_heat_affected_zones(results: &SimulationResults) -> Result<Vec<HeatAffectedZone>, String> {
+    let params = &results.parameters;
+    let mesh = &results.mesh;
+    let (materials, material_index) = resolve_cell_materials(params);
+    if materials.iter().all(|m| m.damage_threshold.is_none()) {
+        return Err("Nenhum material com temperatura limite de dano definida".to_string());
+    }
+    let zone_names: Vec<String> = std::iter::once(params.material.name.clone())
+        .chain(params.material_zones.iter().flatten().map(|(id, _)| id.clone()))
+        .collect();
+
+    let (nr, nz) = (mesh.nr, mesh.nz);
+    let is_wall = |i: usize, j: usize| materials[material_index[[i, j]]].damage_threshold.is_some();
+
+    let mut zones: Vec<HeatAffectedZone> = Vec::new();
+    let mut cells: Vec<WallCell> = Vec::new();
+    for i in 0..nr {
+        for j in 0..nz {
+            if !is_wall(i, j) {
+                continue;
+            }
+            let (r, z) = (mesh.r_coords[i], mesh.z_coords[j]);
+            let row = (0..nr).filter(|&k| !is_wall(k, j)).map(|k| (mesh.r_coords[k] - r).abs());
+            let column = (0..nz).filter(|&k| !is_wall(i, k)).map(|k| (mesh.z_coords[k] - z).abs());
+            let depth = match row.chain(column).reduce(f64::min) {
+                Some(depth) => depth,
+                // Sem face quente alinhada à célula
+                None => continue,
+            };
+
+            let boundary_distances = [
+                (WallRegion::SideWall, mesh.radius - r),
+                (WallRegion::Bottom, z),
+                (WallRegion::Top, mesh.height - z),
+            ];
+            let region = boundary_distances.iter()
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(region, _)| *region)
+                .unwrap_or(WallRegion::SideWall);
+
+            let material_idx = material_index[[i, j]];
+            let zone = zone_names.get(material_idx).cloned().unwrap_or_default();
+            let entry = match zones.iter().position(|h| h.zone == zone && h.region == region) {
+                Some(entry) => entry,
+                None => {
+                    zones.push(HeatAffectedZone {
+                        zone,
+                        region,
+                        damage_threshold: materials[material_idx].damage_threshold.unwrap_or(f64::INFINITY),
+                        max_depth: 0.0,
+                        time_of_max_depth: None,
+                        wall_thickness: 0.0,
+                        peak_temperature: f64::NEG_INFINITY,
+                    });
+                    zones.len() - 1
+                }
+            };
+            zones[entry].wall_thickness = zones[entry].wall_thickness.max(depth);
+            cells.push(WallCell { i, j, entry, depth });
+        }
+    }
+
+    let dt = params.time_step;
+    for step in 0..=results.executed_steps {
+        let field = results.temperature_at(step)?;
+        for cell in &cells {
+            let zone = &mut zones[cell.entry];
+            let temperature = field[[cell.i, cell.j]];
+            zone.peak_temperature = zone.peak_temperature.max(temperature);
+            if temperature > zone.damage_threshold && (zone.time_of_max_depth.is_none() || cell.depth > zone.max_depth) {
+                zone.max_depth = cell.depth;
+                zone.time_of_max_depth = Some(step as f64 * dt);
+            }
+        }
+    }
+
+    Ok(zones)
+}
+
+/// Estrutura que representa o calor absorvido pela camisa de refrigeração de uma fronteira
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoolantHeatReport {
+    /// Fronteira refrigerada
+    pub boundary: BoundarySide,
+    /// Potência absorvida pelo refrigerante em cada passo (W), a partir do passo 0
+    pub power_history: Vec<f64>,
+    /// Temperatura de saída do refrigerante em cada passo (°C), a partir do passo 0
+    pub outlet_temperature_history: Vec<f64>,
+    /// Calor total absorvido pelo refrigerante ao longo da execução (J)
+    pub absorbed_heat: f64,
+    /// Maior potência absorvida (W)
+    pub peak_power: f64,
+}
+
+/// Calcula o calor absorvido pelo refrigerante de cada fronteira com camisa de refrigeração
+///
+/// A potência de cada passo é avaliada no campo de temperatura do início do passo, como
+/// na integração explícita das fronteiras, e integrada com o passo de saída.
+pub fn calculate_coolant_heat(results: &SimulationResults) -> Result<Vec<CoolantHeatReport>, String> {
+    let params = &results.parameters;
+    let jackets: Vec<_> = BoundarySide::ALL.iter()
+        .filter_map(|&side| match params.boundary_conditions.condition(side) {
+            BoundaryCondition::CoolingJacket(jacket) => Some((side, jacket)),
+            _ => None,
+        })
+        .collect();
+    if jackets.is_empty() {
+        return Err("Nenhuma fronteira com camisa de refrigeração".to_string());
+    }
+    let mesh = &results.mesh;
+    let (materials, material_index) = resolve_cell_materials(params);
+
+    let mut reports: Vec<CoolantHeatReport> = jackets.iter()
+        .map(|&(side, _)| CoolantHeatReport {
+            boundary: side,
+            power_history: Vec::new(),
+            outlet_temperature_history: Vec::new(),
+            absorbed_heat: 0.0,
+            peak_power: 0.0,
+        })
+        .collect();
+    for step in 0..=results.executed_steps {
+        let field = results.temperature_at(step)?;
+        let mut conductivity = Array2::<f64>::zeros(field.dim());
+        for ((i, j), k) in conductivity.indexed_iter_mut() {
+            *k = materials[material_index[[i, j]]].get_thermal_conductivity(field[[i, j]]);
+        }
+        for (report, &(side, jacket)) in reports.iter_mut().zip(&jackets) {
+            let power = -params.boundary_conditions.side_heat_flow(side, mesh, &conductivity, &field);
+            if step < results.executed_steps {
+                report.absorbed_heat += power * params.time_step;
+            }
+            report.peak_power = report.peak_power.max(power);
+            report.power_history.push(power);
+            report.outlet_temperature_history.push(jacket.outlet_temperature(power));
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Estrutura que representa os números principais de um passo (painéis resumidos)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeadlineMetrics {
+    /// Passo de tempo
+    pub step: usize,
+    /// Tempo (s)
+    pub time: f64,
+    /// Temperatura máxima (°C)
+    pub max_temperature: f64,
+    /// Temperatura mínima (°C)
+    pub min_temperature: f64,
+    /// Temperatura média ponderada pelo volume das células (°C)
+    pub mean_temperature: f64,
+    /// Energia total armazenada na carga, Σ ρ·h·V (J)
+    pub total_energy: f64,
+    /// Volume fundido, Σ f·V (m³); zero sem mudança de fase
+    pub melt_volume: f64,
+}
+
+/// Índice de um passo em um histórico que pode ter mantido apenas o estado final
+fn stored_step_index(stored_steps: usize, executed_steps: usize, step: usize) -> Option<usize> {
+    if stored_steps > executed_steps {
+        Some(step)
+    } else if step == executed_steps && stored_steps > 0 {
+        Some(stored_steps - 1)
+    } else {
+        None
+    }
+}
+
+/// Calcula os números principais (temperaturas extremas e média, energia e volume fundido)
+/// de um passo
+///
+/// A energia usa a entalpia específica e a densidade do material de cada célula na
+/// temperatura do passo.
+pub fn calculate_headline_metrics(results: &SimulationResults, step: usize) -> Result<HeadlineMetrics, String> {
+    let temperature = results.temperature_at(step)?;
+    let enthalpy_step = stored_step_index(results.enthalpy.shape()[2], results.executed_steps, step)
+        .ok_or_else(|| format!("Entalpia do passo {} não disponível: histórico em precisão total descartado", step))?;
+    let melt_fraction = match results.phase_change_info.as_ref().and_then(|info| info.melt_fraction.as_ref()) {
+        Some(melt_fraction) => {
+            let melt_step = stored_step_index(melt_fraction.shape()[2], results.executed_steps, step)
+                .ok_or_else(|| format!("Fração fundida do passo {} não disponível", step))?;
+            Some((melt_fraction, melt_step))
+        }
+        None => None,
+    };
+
+    let mesh = &results.mesh;
+    let (materials, material_index) = resolve_cell_materials(&results.parameters);
+    let mut metrics = HeadlineMetrics {
+        step,
+        time: step as f64 * results.parameters.time_step,
+        max_temperature: f64::NEG_INFINITY,
+        min_temperature: f64::INFINITY,
+        mean_temperature: 0.0,
+        total_energy: 0.0,
+        melt_volume: 0.0,
+    };
+    let mut volume = 0.0;
+    for ((i, j), &t) in temperature.indexed_iter() {
+        let cell_volume = mesh.cell_volumes[[i, j]];
+        let density = materials[material_index[[i, j]]].get_density(t);
+        metrics.max_temperature = metrics.max_temperature.max(t);
+        metrics.min_temperature = metrics.min_temperature.min(t);
+        metrics.mean_temperature += t * cell_volume;
+        metrics.total_energy += density * results.enthalpy[[i, j, enthalpy_step]] * cell_volume;
+        if let Some((melt_fraction, melt_step)) = melt_fraction {
+            metrics.melt_volume += melt_fraction[[i, j, melt_step]] * cell_volume;
+        }
+        volume += cell_volume;
+    }
+    if volume > 0.0 {
+        metrics.mean_temperature /= volume;
+    }
+
+    Ok(metrics)
+}
+
+/// Exporta os resultados de uma simulação, com as métricas calculadas
+pub fn export_results(results: &SimulationResults, options: &ExportOptions) -> Result<(), String> {
+    let mut analyzer = MetricsAnalyzer::from_results(results);
+    analyzer.calculate_metrics()?;
+    analyzer.export_results(options)
+}
+
+/// Gera o relatório em Markdown dos resultados de uma simulação
+pub fn generate_report(results: &SimulationResults, output_path: &str) -> Result<(), String> {
+    let mut analyzer = MetricsAnalyzer::from_results(results);
+    analyzer.calculate_metrics()?;
+    analyzer.generate_report(output_path)
+}
+
+/// Histórico de temperatura dos resultados de um estado de simulação, com cada passo
+/// disponível achatado na ordem (r, θ, z) da malha
+pub(crate) struct TemperatureHistory {
+    /// Malha dos resultados (ou dos parâmetros, se ainda não houver resultados)
+    pub(crate) mesh: CylindricalMesh,
+    /// Campos de temperatura por passo disponível
+    pub(crate) temperatures: Vec<Vec<f64>>,
+    /// Instantes dos passos disponíveis (s)
+    pub(crate) times: Vec<f64>,
+}
+
+impl TemperatureHistory {
+    /// Extrai o histórico dos resultados do estado; passos cujo quadro não está mais
+    /// disponível são omitidos
+    pub(crate) fn from_state(state: &SimulationState) -> Self {
+        let Some(results) = state.get_results() else {
+            let params = &state.parameters;
+            return Self {
+                mesh: CylindricalMesh::new_graded(
+                    params.height,
+                    params.radius,
+                    params.nr,
+                    params.nz,
+                    params.ntheta,
+                    params.radial_grading,
+                    params.axial_grading,
+                ),
+                temperatures: Vec::new(),
+                times: Vec::new(),
+            };
+        };
+
+        let mut temperatures = Vec::new();
+        let mut times = Vec::new();
+        for step in 0..=results.executed_steps {
+            if let Ok(field) = results.generate_3d_temperature(step) {
+                temperatures.push(field.iter().copied().collect());
+                times.push(step as f64 * results.parameters.time_step);
+            }
+        }
+
+        Self { mesh: results.mesh.clone(), temperatures, times }
+    }
+}
+
 /// Estrutura que representa o analisador de métricas
 pub struct MetricsAnalyzer {
     /// Estado da simulação
     simulation_state: SimulationState,
+    /// Histórico de temperatura extraído dos resultados
+    history: TemperatureHistory,
     /// Métricas calculadas
     metrics: Option<SimulationMetrics>,
 }
@@ -109,15 +743,23 @@ impl MetricsAnalyzer {
     /// Cria um novo analisador de métricas
     pub fn new(simulation_state: SimulationState) -> Self {
         Self {
+            history: TemperatureHistory::from_state(&simulation_state),
             simulation_state,
             metrics: None,
         }
     }
     
+    /// Cria um analisador para os resultados de uma simulação concluída
+    pub fn from_results(results: &SimulationResults) -> Self {
+        let mut state = SimulationState::new(results.parameters.clone());
+        state.complete(results.clone());
+        Self::new(state)
+    }
+    
     /// Calcula as métricas a partir do estado da simulação
     pub fn calculate_metrics(&mut self) -> Result<&SimulationMetrics, String> {
-        let mesh = &self.simulation_state.mesh;
-        let temperature_history = &self.simulation_state.temperature_history;
+        let mesh = &self.history.mesh;
+        let temperature_history = &self.history.temperatures;
         
         if temperature_history.is_empty() {
             return Err("Histórico de temperatura vazio".to_string());
@@ -146,19 +788,25 @@ impl MetricsAnalyzer {
         
         // Calcular gradiente e fluxo de calor
         let max_gradient = self.calculate_max_gradient(final_temperature, mesh);
-        let max_heat_flux = self.calculate_max_heat_flux(final_temperature, mesh);
+        let avg_gradient = self.calculate_avg_gradient(final_temperature, mesh);
+        let max_heat_flux = THERMAL_CONDUCTIVITY * max_gradient;
+        let avg_heat_flux = THERMAL_CONDUCTIVITY * avg_gradient;
         
         // Calcular energia total
         let total_energy = self.calculate_total_energy(final_temperature, mesh);
         
         // Calcular taxa de aquecimento média
-        let avg_heating_rate = self.calculate_avg_heating_rate(temperature_history, &self.simulation_state.time_steps);
+        let avg_heating_rate = self.calculate_avg_heating_rate(temperature_history, &self.history.times);
+        
+        // Calcular eficiência energética
+        let initial_energy = self.calculate_total_energy(&temperature_history[0], mesh);
+        let energy_efficiency = self.calculate_energy_efficiency(total_energy - initial_energy, &self.history.times);
         
         // Calcular métricas por região
         let region_metrics = self.calculate_region_metrics(final_temperature, mesh);
         
         // Calcular métricas temporais
-        let temporal_metrics = self.calculate_temporal_metrics(temperature_history, &self.simulation_state.time_steps, max_temp);
+        let temporal_metrics = self.calculate_temporal_metrics(temperature_history, &self.history.times);
         
         // Criar objeto de métricas
         let metrics = SimulationMetrics {
@@ -167,9 +815,12 @@ impl MetricsAnalyzer {
             avg_temperature: avg_temp,
             std_temperature: std_temp,
             max_gradient,
+            avg_gradient,
             max_heat_flux,
+            avg_heat_flux,
             total_energy,
             avg_heating_rate,
+            energy_efficiency,
             region_metrics,
             temporal_metrics,
         };
@@ -181,19 +832,33 @@ impl MetricsAnalyzer {
     
     /// Calcula o gradiente máximo de temperatura
     fn calculate_max_gradient(&self, temperature: &[f64], mesh: &CylindricalMesh) -> f64 {
+        self.calculate_local_gradients(temperature, mesh).into_iter().fold(0.0, f64::max)
+    }
+    
+    /// Calcula o gradiente médio de temperatura
+    fn calculate_avg_gradient(&self, temperature: &[f64], mesh: &CylindricalMesh) -> f64 {
+        let gradients = self.calculate_local_gradients(temperature, mesh);
+        if gradients.is_empty() {
+            return 0.0;
+        }
+        gradients.iter().sum::<f64>() / gradients.len() as f64
+    }
+    
+    /// Calcula, para cada célula, o maior gradiente (em módulo) entre as direções r, θ e z
+    fn calculate_local_gradients(&self, temperature: &[f64], mesh: &CylindricalMesh) -> Vec<f64> {
         let temp_3d = self.reshape_to_3d(temperature, mesh);
         let (nr, ntheta, nz) = (mesh.nr, mesh.ntheta, mesh.nz);
         
-        let mut max_gradient = 0.0;
+        let mut gradients = Vec::with_capacity(nr * ntheta * nz);
         
         for i in 0..nr {
             for j in 0..ntheta {
                 for k in 0..nz {
-                    let mut local_max_gradient = 0.0;
+                    let mut local_max_gradient: f64 = 0.0;
                     
                     // Gradiente radial
                     if i < nr - 1 {
-                        let dr = mesh.dr;
+                        let dr = mesh.radial_spacing(i);
                         let grad_r = (temp_3d[[i+1, j, k]] - temp_3d[[i, j, k]]) / dr;
                         local_max_gradient = local_max_gradient.max(grad_r.abs());
                     }
@@ -208,26 +873,17 @@ impl MetricsAnalyzer {
                     
                     // Gradiente axial
                     if k < nz - 1 {
-                        let dz = mesh.dz;
+                        let dz = mesh.axial_spacing(k);
                         let grad_z = (temp_3d[[i, j, k+1]] - temp_3d[[i, j, k]]) / dz;
                         local_max_gradient = local_max_gradient.max(grad_z.abs());
                     }
                     
-                    max_gradient = max_gradient.max(local_max_gradient);
+                    gradients.push(local_max_gradient);
                 }
             }
         }
         
-        max_gradient
-    }
-    
-    /// Calcula o fluxo de calor máximo
-    fn calculate_max_heat_flux(&self, temperature: &[f64], mesh: &CylindricalMesh) -> f64 {
-        // Simplificação: assumindo condutividade térmica constante de 50 W/(m·K)
-        const THERMAL_CONDUCTIVITY: f64 = 50.0;
-        
-        // O fluxo de calor é proporcional ao gradiente de temperatura
-        THERMAL_CONDUCTIVITY * self.calculate_max_gradient(temperature, mesh)
+        gradients
     }
     
     /// Calcula a energia total no sistema
@@ -242,17 +898,10 @@ impl MetricsAnalyzer {
         let mut total_energy = 0.0;
         
         for i in 0..nr {
-            let r = mesh.r_coords[i];
-            let dr = mesh.dr;
-            
             for j in 0..ntheta {
-                let dtheta = mesh.dtheta;
-                
                 for k in 0..nz {
-                    let dz = mesh.dz;
-                    
-                    // Volume do elemento em coordenadas cilíndricas
-                    let volume = r * dr * dtheta * dz;
+                    // Volume de controle do nó (fatia angular do anel), que segue o espaçamento local
+                    let volume = mesh.cell_volumes[[i, k]] / ntheta as f64;
                     
                     // Energia = densidade * volume * calor específico * temperatura
                     let temp = temp_3d[[i, j, k]];
@@ -266,6 +915,25 @@ impl MetricsAnalyzer {
         total_energy
     }
     
+    /// Calcula a fração da energia fornecida pelas tochas que ficou retida no material
+    fn calculate_energy_efficiency(&self, energy_gain: f64, time_steps: &[f64]) -> f64 {
+        let torches = &self.simulation_state.parameters.torches;
+        
+        // Energia fornecida (J), com a potência (kW) de cada tocha no início de cada intervalo
+        let supplied_energy: f64 = time_steps.windows(2)
+            .map(|interval| {
+                let power_kw: f64 = torches.iter().map(|torch| torch.requested_power(interval[0])).sum();
+                power_kw * 1000.0 * (interval[1] - interval[0])
+            })
+            .sum();
+        
+        if supplied_energy <= 0.0 {
+            return 0.0;
+        }
+        
+        (energy_gain / supplied_energy).clamp(0.0, 1.0)
+    }
+    
     /// Calcula a taxa de aquecimento média
     fn calculate_avg_heating_rate(&self, temperature_history: &[Vec<f64>], time_steps: &[f64]) -> f64 {
         if temperature_history.len() < 2 || time_steps.len() < 2 {
@@ -304,21 +972,14 @@ impl MetricsAnalyzer {
             let mut total_volume = 0.0;
             
             for i in start_r..end_r {
-                let r = mesh.r_coords[i];
-                let dr = mesh.dr;
-                
                 for j in 0..mesh.ntheta {
-                    let dtheta = mesh.dtheta;
-                    
                     for k in 0..mesh.nz {
-                        let dz = mesh.dz;
-                        
                         let temp = temp_3d[[i, j, k]];
                         min_temp = min_temp.min(temp);
                         max_temp = max_temp.max(temp);
                         
-                        // Volume do elemento em coordenadas cilíndricas
-                        let volume = r * dr * dtheta * dz;
+                        // Volume de controle do nó (fatia angular do anel), que segue o espaçamento local
+                        let volume = mesh.cell_volumes[[i, k]] / mesh.ntheta as f64;
                         total_volume += volume;
                         
                         sum_temp += temp * volume; // Ponderado pelo volume
@@ -348,7 +1009,7 @@ impl MetricsAnalyzer {
     }
     
     /// Calcula as métricas temporais
-    fn calculate_temporal_metrics(&self, temperature_history: &[Vec<f64>], time_steps: &[f64], max_temp: f64) -> TemporalMetrics {
+    fn calculate_temporal_metrics(&self, temperature_history: &[Vec<f64>], time_steps: &[f64]) -> TemporalMetrics {
         let n_steps = temperature_history.len();
         
         if n_steps < 2 || time_steps.len() < n_steps {
@@ -367,6 +1028,10 @@ impl MetricsAnalyzer {
             avg_temps.push(avg);
         }
         
+        // Os limiares são relativos ao pico da própria temperatura média: comparada à
+        // célula mais quente, a média de um campo não uniforme nunca chegaria a 90%
+        let max_temp = avg_temps.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        
         // Tempo para atingir 50% da temperatura máxima
         let half_max = max_temp * 0.5;
         let mut time_to_half_max = 0.0;
@@ -406,7 +1071,7 @@ impl MetricsAnalyzer {
         }
         
         // Taxa de aquecimento máxima
-        let mut max_heating_rate = 0.0;
+        let mut max_heating_rate: f64 = 0.0;
         for i in 1..n_steps {
             let dt = time_steps[i] - time_steps[i-1];
             if dt > 0.0 {
@@ -417,7 +1082,7 @@ impl MetricsAnalyzer {
         
         // Tempo de estabilização (quando a taxa de variação cai abaixo de 1% da taxa máxima)
         let threshold = max_heating_rate * 0.01;
-        let mut stabilization_time = time_steps.last().unwrap_or(&0.0).clone();
+        let mut stabilization_time = *time_steps.last().unwrap_or(&0.0);
         
         for i in 1..n_steps {
             let dt = time_steps[i] - time_steps[i-1];
@@ -460,15 +1125,27 @@ impl MetricsAnalyzer {
     
     /// Exporta os resultados da simulação para um arquivo
     pub fn export_results(&self, options: &ExportOptions) -> Result<(), String> {
+        self.export_results_with_progress(options, None)
+    }
+    
+    /// Exporta os resultados reportando o progresso (entre 0 e 1) a cada linha radial escrita
+    ///
+    /// O callback retorna `false` para cancelar; nesse caso a exportação termina com erro
+    /// e o arquivo fica incompleto.
+    pub fn export_results_with_progress(
+        &self,
+        options: &ExportOptions,
+        progress_callback: Option<&dyn Fn(f32) -> bool>,
+    ) -> Result<(), String> {
         match options.format {
-            ExportFormat::CSV => self.export_to_csv(options),
-            ExportFormat::JSON => self.export_to_json(options),
-            ExportFormat::VTK => self.export_to_vtk(options),
+            ExportFormat::CSV => self.export_to_csv(options, progress_callback),
+            ExportFormat::JSON => self.export_to_json(options, progress_callback),
+            ExportFormat::VTK => self.export_to_vtk(options, progress_callback),
         }
     }
     
     /// Exporta os resultados para um arquivo CSV
-    fn export_to_csv(&self, options: &ExportOptions) -> Result<(), String> {
+    fn export_to_csv(&self, options: &ExportOptions, progress_callback: Option<&dyn Fn(f32) -> bool>) -> Result<(), String> {
         let path = Path::new(&options.output_path);
         let mut file = File::create(path).map_err(|e| format!("Erro ao criar arquivo CSV: {}", e))?;
         
@@ -493,16 +1170,17 @@ impl MetricsAnalyzer {
         // Determinar quais passos de tempo exportar
         let time_steps = match &options.time_steps {
             Some(steps) => steps.clone(),
-            None => vec![self.simulation_state.temperature_history.len() - 1], // Último passo por padrão
+            None => vec![self.history.temperatures.len() - 1], // Último passo por padrão
         };
+        let mut progress = ExportProgress::new(progress_callback, time_steps.len() * self.history.mesh.nr);
         
         for &step in &time_steps {
-            if step >= self.simulation_state.temperature_history.len() {
+            if step >= self.history.temperatures.len() {
                 continue;
             }
             
-            let temperature = &self.simulation_state.temperature_history[step];
-            let mesh = &self.simulation_state.mesh;
+            let temperature = &self.history.temperatures[step];
+            let mesh = &self.history.mesh;
             let temp_3d = self.reshape_to_3d(temperature, mesh);
             
             // Escrever dados
@@ -513,7 +1191,7 @@ impl MetricsAnalyzer {
                     let theta = j as f64 * mesh.dtheta;
                     
                     for k in 0..mesh.nz {
-                        let z = k as f64 * mesh.dz;
+                        let z = mesh.z_coords[k];
                         
                         let mut line = format!("{},{},{}", r, theta, z);
                         
@@ -549,6 +1227,7 @@ impl MetricsAnalyzer {
                         file.write_all(line.as_bytes()).map_err(|e| format!("Erro ao escrever dados CSV: {}", e))?;
                     }
                 }
+                progress.advance()?;
             }
         }
         
@@ -621,25 +1300,25 @@ impl MetricsAnalyzer {
                  z_max,{}\n\
                  time_steps,{}\n\
                  total_time,{}\n",
-                self.simulation_state.mesh.nr,
-                self.simulation_state.mesh.ntheta,
-                self.simulation_state.mesh.nz,
-                self.simulation_state.mesh.r_min,
-                self.simulation_state.mesh.r_max,
+                self.history.mesh.nr,
+                self.history.mesh.ntheta,
+                self.history.mesh.nz,
+                0.0,
+                self.history.mesh.radius,
                 0.0, // z_min
-                self.simulation_state.mesh.nz as f64 * self.simulation_state.mesh.dz, // z_max
-                self.simulation_state.temperature_history.len(),
-                self.simulation_state.time_steps.last().unwrap_or(&0.0)
+                self.history.mesh.height, // z_max
+                self.history.temperatures.len(),
+                self.history.times.last().unwrap_or(&0.0)
             );
             
             file.write_all(metadata.as_bytes()).map_err(|e| format!("Erro ao escrever metadados CSV: {}", e))?;
         }
         
-        Ok(())
+        progress.finish()
     }
     
     /// Exporta os resultados para um arquivo JSON
-    fn export_to_json(&self, options: &ExportOptions) -> Result<(), String> {
+    fn export_to_json(&self, options: &ExportOptions, progress_callback: Option<&dyn Fn(f32) -> bool>) -> Result<(), String> {
         let path = Path::new(&options.output_path);
         let file = File::create(path).map_err(|e| format!("Erro ao criar arquivo JSON: {}", e))?;
         
@@ -650,16 +1329,16 @@ impl MetricsAnalyzer {
         if options.include_metadata {
             let mut metadata = serde_json::Map::new();
             
-            metadata.insert("nr".to_string(), serde_json::Value::Number(serde_json::Number::from(self.simulation_state.mesh.nr)));
-            metadata.insert("ntheta".to_string(), serde_json::Value::Number(serde_json::Number::from(self.simulation_state.mesh.ntheta)));
-            metadata.insert("nz".to_string(), serde_json::Value::Number(serde_json::Number::from(self.simulation_state.mesh.nz)));
-            metadata.insert("r_min".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(self.simulation_state.mesh.r_min).unwrap()));
-            metadata.insert("r_max".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(self.simulation_state.mesh.r_max).unwrap()));
+            metadata.insert("nr".to_string(), serde_json::Value::Number(serde_json::Number::from(self.history.mesh.nr)));
+            metadata.insert("ntheta".to_string(), serde_json::Value::Number(serde_json::Number::from(self.history.mesh.ntheta)));
+            metadata.insert("nz".to_string(), serde_json::Value::Number(serde_json::Number::from(self.history.mesh.nz)));
+            metadata.insert("r_min".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(0.0).unwrap()));
+            metadata.insert("r_max".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(self.history.mesh.radius).unwrap()));
             metadata.insert("z_min".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(0.0).unwrap()));
-            metadata.insert("z_max".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(self.simulation_state.mesh.nz as f64 * self.simulation_state.mesh.dz).unwrap()));
-            metadata.insert("time_steps".to_string(), serde_json::Value::Number(serde_json::Number::from(self.simulation_state.temperature_history.len())));
+            metadata.insert("z_max".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(self.history.mesh.height).unwrap()));
+            metadata.insert("time_steps".to_string(), serde_json::Value::Number(serde_json::Number::from(self.history.temperatures.len())));
             
-            if let Some(total_time) = self.simulation_state.time_steps.last() {
+            if let Some(total_time) = self.history.times.last() {
                 metadata.insert("total_time".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*total_time).unwrap()));
             }
             
@@ -676,23 +1355,25 @@ impl MetricsAnalyzer {
         // Determinar quais passos de tempo exportar
         let time_steps = match &options.time_steps {
             Some(steps) => steps.clone(),
-            None => vec![self.simulation_state.temperature_history.len() - 1], // Último passo por padrão
+            None => vec![self.history.temperatures.len() - 1], // Último passo por padrão
         };
         
         // Adicionar dados de temperatura, gradiente e fluxo de calor
         let mut results = Vec::new();
+        let passes = options.include_temperature as usize + (options.include_gradient || options.include_heat_flux) as usize;
+        let mut progress = ExportProgress::new(progress_callback, time_steps.len() * passes * self.history.mesh.nr);
         
         for &step in &time_steps {
-            if step >= self.simulation_state.temperature_history.len() {
+            if step >= self.history.temperatures.len() {
                 continue;
             }
             
-            let temperature = &self.simulation_state.temperature_history[step];
-            let mesh = &self.simulation_state.mesh;
+            let temperature = &self.history.temperatures[step];
+            let mesh = &self.history.mesh;
             let temp_3d = self.reshape_to_3d(temperature, mesh);
             
-            let time = if step < self.simulation_state.time_steps.len() {
-                self.simulation_state.time_steps[step]
+            let time = if step < self.history.times.len() {
+                self.history.times[step]
             } else {
                 0.0
             };
@@ -709,7 +1390,7 @@ impl MetricsAnalyzer {
                         for k in 0..mesh.nz {
                             let r = mesh.r_coords[i];
                             let theta = j as f64 * mesh.dtheta;
-                            let z = k as f64 * mesh.dz;
+                            let z = mesh.z_coords[k];
                             
                             let mut point_data = serde_json::Map::new();
                             point_data.insert("r".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(r).unwrap()));
@@ -720,6 +1401,7 @@ impl MetricsAnalyzer {
                             temp_data.push(serde_json::Value::Object(point_data));
                         }
                     }
+                    progress.advance()?;
                 }
                 
                 step_data.insert("temperature_data".to_string(), serde_json::Value::Array(temp_data));
@@ -736,7 +1418,7 @@ impl MetricsAnalyzer {
                         for k in 0..mesh.nz {
                             let r = mesh.r_coords[i];
                             let theta = j as f64 * mesh.dtheta;
-                            let z = k as f64 * mesh.dz;
+                            let z = mesh.z_coords[k];
                             
                             // Calcular gradientes
                             let (grad_r, grad_theta, grad_z) = self.calculate_gradient_at_point(i, j, k, &temp_3d, mesh);
@@ -770,6 +1452,7 @@ impl MetricsAnalyzer {
                             gradient_data.push(serde_json::Value::Object(point_data));
                         }
                     }
+                    progress.advance()?;
                 }
                 
                 if options.include_gradient {
@@ -790,29 +1473,31 @@ impl MetricsAnalyzer {
         serde_json::to_writer_pretty(file, &serde_json::Value::Object(export_data))
             .map_err(|e| format!("Erro ao escrever arquivo JSON: {}", e))?;
         
-        Ok(())
+        progress.finish()
     }
     
     /// Exporta os resultados para um arquivo VTK
-    fn export_to_vtk(&self, options: &ExportOptions) -> Result<(), String> {
+    fn export_to_vtk(&self, options: &ExportOptions, progress_callback: Option<&dyn Fn(f32) -> bool>) -> Result<(), String> {
         let path = Path::new(&options.output_path);
         let mut file = File::create(path).map_err(|e| format!("Erro ao criar arquivo VTK: {}", e))?;
         
         // Determinar quais passos de tempo exportar
         let time_steps = match &options.time_steps {
             Some(steps) => steps.clone(),
-            None => vec![self.simulation_state.temperature_history.len() - 1], // Último passo por padrão
+            None => vec![self.history.temperatures.len() - 1], // Último passo por padrão
         };
         
         // Usar apenas o primeiro passo de tempo para simplificar
         let step = time_steps[0];
-        if step >= self.simulation_state.temperature_history.len() {
+        if step >= self.history.temperatures.len() {
             return Err(format!("Passo de tempo {} fora dos limites", step));
         }
         
-        let temperature = &self.simulation_state.temperature_history[step];
-        let mesh = &self.simulation_state.mesh;
+        let temperature = &self.history.temperatures[step];
+        let mesh = &self.history.mesh;
         let temp_3d = self.reshape_to_3d(temperature, mesh);
+        let sections = 1 + options.include_temperature as usize + options.include_gradient as usize + options.include_heat_flux as usize;
+        let mut progress = ExportProgress::new(progress_callback, sections * mesh.nr);
         
         // Escrever cabeçalho VTK
         let header = "# vtk DataFile Version 3.0\nPlasma Furnace Simulation Results\nASCII\nDATASET STRUCTURED_GRID\n";
@@ -836,12 +1521,13 @@ impl MetricsAnalyzer {
                 let y = r * theta.sin();
                 
                 for k in 0..mesh.nz {
-                    let z = k as f64 * mesh.dz;
+                    let z = mesh.z_coords[k];
                     
                     let point = format!("{} {} {}\n", x, y, z);
                     file.write_all(point.as_bytes()).map_err(|e| format!("Erro ao escrever ponto VTK: {}", e))?;
                 }
             }
+            progress.advance()?;
         }
         
         // Escrever dados de ponto
@@ -860,6 +1546,7 @@ impl MetricsAnalyzer {
                         file.write_all(temp.as_bytes()).map_err(|e| format!("Erro ao escrever temperatura VTK: {}", e))?;
                     }
                 }
+                progress.advance()?;
             }
         }
         
@@ -882,6 +1569,7 @@ impl MetricsAnalyzer {
                         file.write_all(grad.as_bytes()).map_err(|e| format!("Erro ao escrever gradiente VTK: {}", e))?;
                     }
                 }
+                progress.advance()?;
             }
         }
         
@@ -912,10 +1600,11 @@ impl MetricsAnalyzer {
                         file.write_all(flux.as_bytes()).map_err(|e| format!("Erro ao escrever fluxo de calor VTK: {}", e))?;
                     }
                 }
+                progress.advance()?;
             }
         }
         
-        Ok(())
+        progress.finish()
     }
     
     /// Calcula o gradiente de temperatura em um ponto específico
@@ -924,14 +1613,14 @@ impl MetricsAnalyzer {
         
         // Gradiente radial
         let grad_r = if i < nr - 1 && i > 0 {
-            // Diferença central
-            (temp_3d[[i+1, j, k]] - temp_3d[[i-1, j, k]]) / (2.0 * mesh.dr)
+            // Diferença central (entre os vizinhos, com o espaçamento local)
+            (temp_3d[[i+1, j, k]] - temp_3d[[i-1, j, k]]) / (mesh.r_coords[i + 1] - mesh.r_coords[i - 1])
         } else if i < nr - 1 {
             // Diferença avançada
-            (temp_3d[[i+1, j, k]] - temp_3d[[i, j, k]]) / mesh.dr
+            (temp_3d[[i+1, j, k]] - temp_3d[[i, j, k]]) / mesh.radial_spacing(i)
         } else if i > 0 {
             // Diferença atrasada
-            (temp_3d[[i, j, k]] - temp_3d[[i-1, j, k]]) / mesh.dr
+            (temp_3d[[i, j, k]] - temp_3d[[i-1, j, k]]) / mesh.radial_spacing(i - 1)
         } else {
             0.0
         };
@@ -967,14 +1656,14 @@ impl MetricsAnalyzer {
         
         // Gradiente axial
         let grad_z = if k < nz - 1 && k > 0 {
-            // Diferença central
-            (temp_3d[[i, j, k+1]] - temp_3d[[i, j, k-1]]) / (2.0 * mesh.dz)
+            // Diferença central (entre os vizinhos, com o espaçamento local)
+            (temp_3d[[i, j, k+1]] - temp_3d[[i, j, k-1]]) / (mesh.z_coords[k + 1] - mesh.z_coords[k - 1])
         } else if k < nz - 1 {
             // Diferença avançada
-            (temp_3d[[i, j, k+1]] - temp_3d[[i, j, k]]) / mesh.dz
+            (temp_3d[[i, j, k+1]] - temp_3d[[i, j, k]]) / mesh.axial_spacing(k)
         } else if k > 0 {
             // Diferença atrasada
-            (temp_3d[[i, j, k]] - temp_3d[[i, j, k-1]]) / mesh.dz
+            (temp_3d[[i, j, k]] - temp_3d[[i, j, k-1]]) / mesh.axial_spacing(k - 1)
         } else {
             0.0
         };
@@ -999,7 +1688,7 @@ impl MetricsAnalyzer {
         file.write_all(header.as_bytes()).map_err(|e| format!("Erro ao escrever cabeçalho do relatório: {}", e))?;
         
         // Escrever informações da simulação
-        let mesh = &self.simulation_state.mesh;
+        let mesh = &self.history.mesh;
         let simulation_info = format!(
             "## Informações da Simulação\n\n\
              - Dimensões da malha: {} x {} x {}\n\
@@ -1009,10 +1698,10 @@ impl MetricsAnalyzer {
              - Passos de tempo: {}\n\
              - Tempo total: {:.2} s\n\n",
             mesh.nr, mesh.ntheta, mesh.nz,
-            mesh.r_min, mesh.r_max,
-            mesh.nz as f64 * mesh.dz,
-            self.simulation_state.temperature_history.len(),
-            self.simulation_state.time_steps.last().unwrap_or(&0.0)
+            0.0, mesh.radius,
+            mesh.height,
+            self.history.temperatures.len(),
+            self.history.times.last().unwrap_or(&0.0)
         );
         
         file.write_all(simulation_info.as_bytes()).map_err(|e| format!("Erro ao escrever informações da simulação: {}", e))?;
@@ -1078,6 +1767,12 @@ impl MetricsAnalyzer {
             file.write_all(region_metrics.as_bytes()).map_err(|e| format!("Erro ao escrever métricas da região {}: {}", name, e))?;
         }
         
+        // Escrever linha do tempo de eventos, se houver resultados do solucionador
+        if let Some(results) = self.simulation_state.get_results() {
+            let timeline = render_annotations_markdown(&results.annotations);
+            file.write_all(timeline.as_bytes()).map_err(|e| format!("Erro ao escrever linha do tempo: {}", e))?;
+        }
+        
         // Escrever conclusões
         let conclusions = format!(
             "## Conclusões\n\n\
@@ -1088,7 +1783,7 @@ impl MetricsAnalyzer {
              O fluxo de calor máximo foi de {:.2} W/m², localizado na região de maior gradiente de temperatura ({:.2} °C/m). \
              A energia total armazenada no sistema foi de {:.2e} J.\n\n",
             metrics.max_temperature,
-            self.simulation_state.time_steps.last().unwrap_or(&0.0),
+            self.history.times.last().unwrap_or(&0.0),
             metrics.avg_temperature,
             metrics.std_temperature,
             if metrics.std_temperature / metrics.avg_temperature < 0.1 { "relativamente uniforme" } else { "não uniforme" },
@@ -1108,38 +1803,29 @@ impl MetricsAnalyzer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::simulation::mesh::CylindricalMesh;
+    use crate::simulation::mesh::MeshGrading;
+    use crate::simulation::solver::SimulationParameters;
     use crate::simulation::state::SimulationState;
     
     fn create_test_simulation_state() -> SimulationState {
-        // Criar malha de teste
-        let mesh = CylindricalMesh::new(10, 8, 10, 0.1, 1.0);
-        
-        // Criar estado de simulação
-        let mut state = SimulationState::new(mesh);
-        
-        // Adicionar dados de temperatura
-        let mut temperature = vec![25.0; mesh.total_cells()];
-        state.temperature_history.push(temperature.clone());
-        
-        // Adicionar mais um passo de tempo com temperatura mais alta no centro
-        for i in 0..mesh.nr {
-            for j in 0..mesh.ntheta {
-                for k in 0..mesh.nz {
-                    let idx = i * mesh.ntheta * mesh.nz + j * mesh.nz + k;
-                    let r = mesh.r_coords[i];
-                    
-                    // Temperatura mais alta no centro, diminuindo com o raio
-                    temperature[idx] = 500.0 * (1.0 - r / mesh.r_max) + 25.0;
-                }
-            }
+        // Malha de teste: 10 x 10 células, 8 setores angulares, dois passos de 10 s
+        let mut params = SimulationParameters::new(1.0, 0.1, 10, 10);
+        params.ntheta = 8;
+        params.time_step = 10.0;
+        params.time_steps = 1;
+        params.total_time = 10.0;
+        
+        let mut results = SimulationResults::for_tests(params.clone(), Array3::from_elem((10, 10, 2), 25.0));
+        
+        // Segundo passo com temperatura mais alta no centro, diminuindo com o raio
+        for i in 0..params.nr {
+            let r = results.mesh.r_coords[i];
+            results.temperature.slice_mut(ndarray::s![i, .., 1]).fill(500.0 * (1.0 - r / params.radius) + 25.0);
         }
+        results.enthalpy = results.temperature.clone();
         
-        state.temperature_history.push(temperature);
-        
-        // Adicionar passos de tempo
-        state.time_steps = vec![0.0, 10.0];
-        
+        let mut state = SimulationState::new(params);
+        state.complete(results);
         state
     }
     
@@ -1171,6 +1857,34 @@ mod tests {
         assert!(center_temp > periphery_temp);
     }
     
+    #[test]
+    fn test_graded_mesh_gradients_and_volumes() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 9, 7);
+        params.ntheta = 8;
+        params.radial_grading = MeshGrading::Geometric { ratio: 1.3 };
+        params.axial_grading = MeshGrading::Hyperbolic { stretching: 1.5 };
+        let results = SimulationResults::for_tests(params.clone(), Array3::from_elem((9, 7, 1), 1.0));
+        let mut state = SimulationState::new(params);
+        state.complete(results);
+        let analyzer = MetricsAnalyzer::new(state);
+        let mesh = analyzer.history.mesh.clone();
+
+        // Campo linear: as diferenças com o espaçamento local o reproduzem em todos os nós
+        let temp_3d = Array3::from_shape_fn((mesh.nr, mesh.ntheta, mesh.nz), |(i, _, k)| {
+            100.0 * mesh.r_coords[i] + 50.0 * mesh.z_coords[k]
+        });
+        for (i, k) in [(0, 0), (3, 2), (mesh.nr - 1, mesh.nz - 1)] {
+            let (grad_r, grad_theta, grad_z) = analyzer.calculate_gradient_at_point(i, 1, k, &temp_3d, &mesh);
+            assert!((grad_r - 100.0).abs() < 1e-9 && grad_theta.abs() < 1e-9 && (grad_z - 50.0).abs() < 1e-9);
+        }
+        let flat: Vec<f64> = temp_3d.iter().copied().collect();
+        assert!((analyzer.calculate_max_gradient(&flat, &mesh) - 100.0).abs() < 1e-9);
+
+        // A energia usa os volumes de controle da malha, que seguem o espaçamento local
+        let energy = analyzer.calculate_total_energy(&vec![1.0; flat.len()], &mesh);
+        assert!((energy / (7800.0 * 500.0) - mesh.cell_volumes.sum()).abs() < 1e-12);
+    }
+    
     #[test]
     fn test_export_csv() {
         let state = create_test_simulation_state();
@@ -1202,6 +1916,47 @@ mod tests {
         std::fs::remove_file(path).unwrap();
     }
     
+    #[test]
+    fn test_export_progress_and_cancellation() {
+        let analyzer = MetricsAnalyzer::new(create_test_simulation_state());
+        let path = std::env::temp_dir().join(format!("plasma_export_progress_{}.vtk", std::process::id()));
+        let options = ExportOptions {
+            format: ExportFormat::VTK,
+            output_path: path.to_string_lossy().into_owned(),
+            include_metrics: false,
+            include_temperature: true,
+            include_gradient: true,
+            include_heat_flux: false,
+            include_metadata: false,
+            time_steps: None,
+        };
+        
+        // Pontos, temperatura e gradiente: uma notificação por linha radial de cada seção
+        let values = std::cell::RefCell::new(Vec::new());
+        let record = |value: f32| {
+            values.borrow_mut().push(value);
+            true
+        };
+        analyzer.export_results_with_progress(&options, Some(&record)).unwrap();
+        let values = values.into_inner();
+        let nr = analyzer.history.mesh.nr;
+        assert_eq!(values.len(), 3 * nr + 1);
+        assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!((values[values.len() - 1] - 1.0).abs() < 1e-6);
+        
+        // O callback interrompe a exportação ao retornar false
+        let calls = std::cell::Cell::new(0);
+        let cancel = |_: f32| {
+            calls.set(calls.get() + 1);
+            calls.get() < 2
+        };
+        let error = analyzer.export_results_with_progress(&options, Some(&cancel)).unwrap_err();
+        assert!(error.contains("cancelada"));
+        assert_eq!(calls.get(), 2);
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+    
     #[test]
     fn test_export_json() {
         let state = create_test_simulation_state();
@@ -1252,4 +2007,179 @@ mod tests {
         // Limpar
         std::fs::remove_file(path).unwrap();
     }
+
+    #[test]
+    fn test_tap_temperature_prediction() {
+        use crate::simulation::solver::{PhaseChangeInfo, SimulationParameters};
+        
+        let mut params = SimulationParameters::new(1.0, 0.5, 3, 3);
+        params.time_step = 10.0;
+        
+        // Banho totalmente fundido aquecendo 1500 -> 1520 -> 1540 -> 1560 °C
+        let mut temperature = Array3::<f64>::zeros((3, 3, 4));
+        for step in 0..4 {
+            temperature.slice_mut(ndarray::s![.., .., step]).fill(1500.0 + 20.0 * step as f64);
+        }
+        let melt_fraction = Array3::<f64>::ones((3, 3, 4));
+        
+        let mut results = SimulationResults::for_tests(params, temperature);
+        results.phase_change_info = Some(PhaseChangeInfo { melt_fraction: Some(melt_fraction), vapor_fraction: None });
+        
+        let mut config = TapTemperatureConfig {
+            tapping_temperature: 1530.0,
+            confidence_margin: 5.0,
+            ..Default::default()
+        };
+        
+        let prediction = calculate_tap_temperature_prediction(&results, &config).unwrap();
+        assert_eq!(prediction.tap_time, Some(20.0));
+        assert!((prediction.pool_heating_rate - 2.0).abs() < 1e-9);
+        
+        // Alvo ainda não atingido: extrapolação linear a partir do último ponto
+        config.tapping_temperature = 1600.0;
+        config.confidence_margin = 0.0;
+        let prediction = calculate_tap_temperature_prediction(&results, &config).unwrap();
+        assert!(prediction.tap_time.is_none());
+        assert!((prediction.predicted_tap_time.unwrap() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_slag_fluidity_tappable_fraction() {
+        use crate::simulation::materials::ViscosityModel;
+        use crate::simulation::solver::{PhaseChangeInfo, SimulationParameters};
+        
+        let mut params = SimulationParameters::new(1.0, 0.5, 2, 2);
+        params.material.viscosity_model = Some(ViscosityModel::Arrhenius { a: 1e-4, b: 15000.0 });
+        
+        // Metade das células fundidas: uma quente (vazável) e uma fria (viscosa)
+        let mut temperature = Array3::<f64>::zeros((2, 2, 1));
+        temperature[[0, 0, 0]] = 1700.0;
+        temperature[[0, 1, 0]] = 1200.0;
+        let mut melt_fraction = Array3::<f64>::zeros((2, 2, 1));
+        melt_fraction[[0, 0, 0]] = 1.0;
+        melt_fraction[[0, 1, 0]] = 1.0;
+        
+        let mut results = SimulationResults::for_tests(params, temperature);
+        results.phase_change_info = Some(PhaseChangeInfo { melt_fraction: Some(melt_fraction), vapor_fraction: None });
+        
+        let config = SlagFluidityConfig { max_tappable_viscosity: 0.5, melt_fraction_threshold: 0.99 };
+        let fluidity = calculate_slag_fluidity(&results, 0, &config).unwrap();
+        
+        // η(1700 °C) ≈ 0.2 Pa·s e η(1200 °C) ≈ 2.6 Pa·s
+        assert_eq!(fluidity.fluidity_field[[0, 0]], 1.0);
+        assert!(fluidity.fluidity_field[[0, 1]] < 1.0);
+        assert_eq!(fluidity.fluidity_field[[1, 0]], 0.0);
+        assert!(fluidity.tappable_fraction > 0.0 && fluidity.tappable_fraction < 1.0);
+    }
+
+    #[test]
+    fn test_heat_affected_zone_depth() {
+        use crate::simulation::materials::MaterialProperties;
+        use crate::simulation::solver::SimulationParameters;
+        
+        // Carga no interior (i < 4, j > 0) e revestimento na lateral (i >= 4) e no fundo (j = 0)
+        let mut params = SimulationParameters::new(1.0, 0.5, 6, 6);
+        params.add_material_zone("charge".to_string(), MaterialProperties::new("Carga", 7850.0, 490.0, 45.0));
+        let mut lining = MaterialProperties::new("Refratário", 2300.0, 880.0, 1.5);
+        lining.damage_threshold = Some(600.0);
+        params.add_material_zone("lining".to_string(), lining);
+        params.set_zone_map(Array2::from_shape_fn((6, 6), |(i, j)| if i >= 4 || j == 0 { 1 } else { 0 }));
+        
+        let mut temperature = Array3::<f64>::from_elem((6, 6, 2), 25.0);
+        for j in 1..6 {
+            temperature[[4, j, 1]] = 800.0;
+            temperature[[5, j, 1]] = 500.0;
+        }
+        for i in 0..4 {
+            temperature[[i, 0, 1]] = 700.0;
+        }
+        
+        let results = SimulationResults::for_tests(params, temperature);
+        
+        let zones = calculate_heat_affected_zones(&results).unwrap();
+        let side = zones.iter().find(|z| z.region == WallRegion::SideWall).unwrap();
+        assert_eq!(side.zone, "lining");
+        assert!((side.max_depth - 0.1).abs() < 1e-9);
+        assert!((side.wall_thickness - 0.2).abs() < 1e-9);
+        assert_eq!(side.time_of_max_depth, Some(1.0));
+        assert_eq!(side.peak_temperature, 800.0);
+        
+        let bottom = zones.iter().find(|z| z.region == WallRegion::Bottom).unwrap();
+        assert!((bottom.max_depth - 0.2).abs() < 1e-9);
+        
+        // Sem materiais de parede, a métrica não se aplica
+        let mut plain = results.clone();
+        plain.parameters.zone_map = None;
+        assert!(calculate_heat_affected_zones(&plain).is_err());
+    }
+
+    #[test]
+    fn test_coolant_heat_from_cooling_jacket() {
+        use crate::simulation::boundary::{CoolingJacket, WATER_SPECIFIC_HEAT};
+        use crate::simulation::solver::SimulationParameters;
+
+        let mut params = SimulationParameters::new(1.0, 0.5, 3, 3);
+        params.time_step = 2.0;
+        let jacket = CoolingJacket::new(30.0, 0.5, 500.0);
+        params.boundary_conditions.wall = BoundaryCondition::CoolingJacket(jacket);
+
+        let temperature = Array3::<f64>::from_elem((3, 3, 3), 330.0);
+        let mut results = SimulationResults::for_tests(params, temperature);
+
+        // Parede uniforme: potência ε·ṁ·cp·(T - T_in) constante em todos os passos
+        let capacity = 0.5 * WATER_SPECIFIC_HEAT;
+        let area = BoundarySide::Wall.total_area(&results.mesh);
+        let power = (1.0 - (-500.0 * area / capacity).exp()) * capacity * 300.0;
+        let reports = calculate_coolant_heat(&results).unwrap();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.boundary, BoundarySide::Wall);
+        assert_eq!(report.power_history.len(), 3);
+        assert!((report.peak_power - power).abs() < 1e-6 * power);
+        assert!((report.absorbed_heat - 2.0 * 2.0 * power).abs() < 1e-6 * power);
+        assert!((report.outlet_temperature_history[0] - (30.0 + power / capacity)).abs() < 1e-9);
+
+        // Sem camisa de refrigeração, a métrica não se aplica
+        results.parameters.boundary_conditions = Default::default();
+        assert!(calculate_coolant_heat(&results).is_err());
+    }
+
+    #[test]
+    fn test_headline_metrics() {
+        use crate::simulation::materials::MaterialProperties;
+        use crate::simulation::solver::{PhaseChangeInfo, SimulationParameters};
+
+        let mut params = SimulationParameters::new(1.0, 0.5, 3, 3);
+        params.time_step = 5.0;
+        params.set_material(MaterialProperties::new("Carga", 2000.0, 1000.0, 1.0));
+
+        // Campo final com um ponto quente de 1200 °C sobre 400 °C; metade de uma célula fundida
+        let mut temperature = Array3::<f64>::from_elem((3, 3, 3), 400.0);
+        temperature[[1, 2, 2]] = 1200.0;
+        let mut melt_fraction = Array3::<f64>::zeros((3, 3, 3));
+        melt_fraction[[1, 2, 2]] = 0.5;
+        let mut results = SimulationResults::for_tests(params, temperature);
+        results.enthalpy = Array3::<f64>::from_elem((3, 3, 3), 4.0e5);
+        results.phase_change_info = Some(PhaseChangeInfo { melt_fraction: Some(melt_fraction), vapor_fraction: None });
+
+        let volumes = results.mesh.cell_volumes.clone();
+        let total_volume = volumes.sum();
+        let metrics = calculate_headline_metrics(&results, 2).unwrap();
+        assert_eq!(metrics.step, 2);
+        assert_eq!(metrics.time, 10.0);
+        assert_eq!(metrics.max_temperature, 1200.0);
+        assert_eq!(metrics.min_temperature, 400.0);
+        let mean = 400.0 + 800.0 * volumes[[1, 2]] / total_volume;
+        assert!((metrics.mean_temperature - mean).abs() < 1e-9);
+        assert!((metrics.total_energy - 2000.0 * 4.0e5 * total_volume).abs() < 1e-6 * metrics.total_energy);
+        assert!((metrics.melt_volume - 0.5 * volumes[[1, 2]]).abs() < 1e-12);
+
+        // Apenas o estado final mantido: passos intermediários indisponíveis
+        results.enthalpy = Array3::<f64>::from_elem((3, 3, 1), 4.0e5);
+        results.phase_change_info = None;
+        assert!(calculate_headline_metrics(&results, 2).is_ok());
+        assert_eq!(calculate_headline_metrics(&results, 2).unwrap().melt_volume, 0.0);
+        assert!(calculate_headline_metrics(&results, 1).is_err());
+        assert!(calculate_headline_metrics(&results, 3).is_err());
+    }
 }
@@ -0,0 +1,263 @@
+// Implementação de misturas de materiais (propriedades efetivas de combinações ponderadas)
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::materials::{MaterialLibrary, MaterialProperties, PropertyTable, PropertyTables};
+
+/// Enumeração que representa a regra de mistura da condutividade térmica
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConductivityMixingRule {
+    /// Camadas paralelas ao fluxo (média aritmética por volume, limite superior)
+    Parallel,
+    /// Camadas em série com o fluxo (média harmônica por volume, limite inferior)
+    Serial,
+    /// Média geométrica por volume (meios granulares aleatórios)
+    Geometric,
+}
+
+/// Estrutura que representa um componente da mistura
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MixtureComponent {
+    /// Propriedades do material componente
+    pub material: MaterialProperties,
+    /// Fração mássica (normalizada ao construir a mistura)
+    pub mass_fraction: f64,
+}
+
+/// Estrutura que representa uma mistura de materiais (ex.: 60% RSU + 40% solo)
+///
+/// As frações são mássicas. A densidade combina os volumes específicos, a capacidade
+/// térmica e os calores latentes são médias mássicas, e a condutividade e a
+/// emissividade usam as frações volumétricas na temperatura avaliada. Quando algum
+/// componente depende da temperatura, o material resultante recebe tabelas de
+/// propriedades amostradas em `table_range`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialMixture {
+    /// Nome do material resultante
+    pub name: String,
+    /// Componentes da mistura
+    pub components: Vec<MixtureComponent>,
+    /// Regra de mistura da condutividade térmica
+    pub conductivity_rule: ConductivityMixingRule,
+    /// Faixa de temperatura das tabelas geradas (°C)
+    pub table_range: (f64, f64),
+    /// Número de pontos das tabelas geradas
+    pub table_points: usize,
+}
+
+impl MaterialMixture {
+    /// Cria uma mistura vazia
+    pub fn new(name: &str, conductivity_rule: ConductivityMixingRule) -> Self {
+        Self {
+            name: name.to_string(),
+            components: Vec::new(),
+            conductivity_rule,
+            table_range: (0.0, 2000.0),
+            table_points: 41,
+        }
+    }
+
+    /// Adiciona um componente com a fração mássica informada
+    pub fn add_component(&mut self, material: MaterialProperties, mass_fraction: f64) {
+        self.components.push(MixtureComponent { material, mass_fraction });
+    }
+
+    /// Adiciona um material da biblioteca pelo identificador
+    pub fn add_library_component(&mut self, library: &MaterialLibrary, id: &str, mass_fraction: f64) -> Result<(), String> {
+        let material = library.get_material_clone(id)
+            .ok_or_else(|| format!("Material não encontrado na biblioteca: {}", id))?;
+        self.add_component(material, mass_fraction);
+        Ok(())
+    }
+
+    /// Valida a mistura
+    pub fn validate(&self) -> Result<(), String> {
+        if self.components.is_empty() {
+            return Err(format!("Mistura {} sem componentes", self.name));
+        }
+        for component in &self.components {
+            if component.mass_fraction <= 0.0 || !component.mass_fraction.is_finite() {
+                return Err(format!("Fração mássica de {} deve ser positiva", component.material.name));
+            }
+            if component.material.density <= 0.0 {
+                return Err(format!("Densidade de {} deve ser positiva", component.material.name));
+            }
+        }
+        if self.table_points < 2 || self.table_range.1 <= self.table_range.0 || !self.table_range.1.is_finite() {
+            return Err("Faixa das tabelas da mistura deve ter ao menos dois pontos e fim maior que o início".to_string());
+        }
+        Ok(())
+    }
+
+    /// Frações mássicas normalizadas
+    fn mass_fractions(&self) -> Vec<f64> {
+        let total: f64 = self.components.iter().map(|c| c.mass_fraction).sum();
+        self.components.iter().map(|c| c.mass_fraction / total).collect()
+    }
+
+    /// Frações volumétricas na temperatura (°C)
+    pub fn volume_fractions(&self, temperature: f64) -> Vec<f64> {
+        let specific_volumes: Vec<f64> = self.mass_fractions().iter().zip(&self.components)
+            .map(|(w, c)| w / c.material.get_density(temperature).max(1e-12))
+            .collect();
+        let total: f64 = specific_volumes.iter().sum();
+        specific_volumes.iter().map(|v| v / total).collect()
+    }
+
+    /// Densidade efetiva (kg/m³): 1/ρ = Σ wᵢ/ρᵢ
+    pub fn density_at(&self, temperature: f64) -> f64 {
+        let specific_volume: f64 = self.mass_fractions().iter().zip(&self.components)
+            .map(|(w, c)| w / c.material.get_density(temperature).max(1e-12))
+            .sum();
+        1.0 / specific_volume
+    }
+
+    /// Capacidade térmica específica efetiva (J/(kg·K)): cp = Σ wᵢ·cpᵢ
+    pub fn specific_heat_at(&self, temperature: f64) -> f64 {
+        self.mass_fractions().iter().zip(&self.components)
+            .map(|(w, c)| w * c.material.get_specific_heat(temperature))
+            .sum()
+    }
+
+    /// Condutividade térmica efetiva (W/(m·K)) pela regra de mistura
+    pub fn thermal_conductivity_at(&self, temperature: f64) -> f64 {
+        let phi = self.volume_fractions(temperature);
+        let k: Vec<f64> = self.components.iter().map(|c| c.material.get_thermal_conductivity(temperature)).collect();
+        match self.conductivity_rule {
+            ConductivityMixingRule::Parallel => phi.iter().zip(&k).map(|(p, k)| p * k).sum(),
+            ConductivityMixingRule::Serial => 1.0 / phi.iter().zip(&k).map(|(p, k)| p / k.max(1e-12)).sum::<f64>(),
+            ConductivityMixingRule::Geometric => phi.iter().zip(&k).map(|(p, k)| p * k.max(1e-12).ln()).sum::<f64>().exp(),
+        }
+    }
+
+    /// Emissividade efetiva: média por fração volumétrica (área exposta)
+    pub fn emissivity_at(&self, temperature: f64) -> f64 {
+        self.volume_fractions(temperature).iter().zip(&self.components)
+            .map(|(p, c)| p * c.material.get_emissivity(temperature))
+            .sum::<f64>()
+            .clamp(0.0, 1.0)
+    }
+
+    /// Indica se algum componente tem propriedades dependentes da temperatura
+    fn is_temperature_dependent(&self) -> bool {
+        self.components.iter().any(|c| {
+            let m = &c.material;
+            m.property_tables.is_some()
+                || (m.reference_temperature.is_some()
+                    && (m.specific_heat_coefficients.is_some()
+                        || m.thermal_conductivity_coefficients.is_some()
+                        || m.density_coefficients.is_some()))
+        })
+    }
+
+    /// Média mássica de uma transição de fase entre os componentes que a possuem:
+    /// (temperatura média ponderada, calor latente da mistura)
+    fn mixed_transition(&self, transition: impl Fn(&MaterialProperties) -> (Option<f64>, Option<f64>)) -> (Option<f64>, Option<f64>) {
+        let mut weight = 0.0;
+        let mut temperature = 0.0;
+        let mut latent_heat = 0.0;
+        for (w, c) in self.mass_fractions().iter().zip(&self.components) {
+            if let (Some(t), Some(l)) = transition(&c.material) {
+                weight += w;
+                temperature += w * t;
+                latent_heat += w * l;
+            }
+        }
+        if weight > 0.0 {
+            (Some(temperature / weight), Some(latent_heat))
+        } else {
+            (None, None)
+        }
+    }
+
+    /// Constrói as propriedades efetivas, utilizáveis como `SimulationParameters.material`
+    pub fn build(&self) -> Result<MaterialProperties, String> {
+        self.validate()?;
+        let reference = 25.0;
+        let mut material = MaterialProperties::new(
+            &self.name,
+            self.density_at(reference),
+            self.specific_heat_at(reference),
+            self.thermal_conductivity_at(reference),
+        );
+        material.emissivity = self.emissivity_at(reference);
+        let fractions = self.mass_fractions();
+        material.moisture_content = fractions.iter().zip(&self.components)
+            .map(|(w, c)| w * c.material.moisture_content)
+            .sum();
+        material.ash_content = fractions.iter().zip(&self.components)
+            .map(|(w, c)| w * c.material.ash_content)
+            .sum();
+        let (melting_point, latent_heat_fusion) = self.mixed_transition(|m| (m.melting_point, m.latent_heat_fusion));
+        material.melting_point = melting_point;
+        material.latent_heat_fusion = latent_heat_fusion;
+        let (vaporization_point, latent_heat_vaporization) =
+            self.mixed_transition(|m| (m.vaporization_point, m.latent_heat_vaporization));
+        material.vaporization_point = vaporization_point;
+        material.latent_heat_vaporization = latent_heat_vaporization;
+        // O revestimento é danificado quando o componente mais sensível atinge seu limite
+        material.damage_threshold = self.components.iter()
+            .filter_map(|c| c.material.damage_threshold)
+            .fold(None, |min: Option<f64>, t| Some(min.map_or(t, |m| m.min(t))));
+
+        if self.is_temperature_dependent() {
+            let (start, end) = self.table_range;
+            let temperatures: Vec<f64> = (0..self.table_points)
+                .map(|n| start + (end - start) * n as f64 / (self.table_points - 1) as f64)
+                .collect();
+            let table = |property: &dyn Fn(f64) -> f64| {
+                PropertyTable::new(temperatures.iter().map(|&t| (t, property(t))).collect())
+            };
+            material.set_property_tables(PropertyTables {
+                thermal_conductivity: Some(table(&|t| self.thermal_conductivity_at(t))?),
+                specific_heat: Some(table(&|t| self.specific_heat_at(t))?),
+                density: Some(table(&|t| self.density_at(t))?),
+                emissivity: Some(table(&|t| self.emissivity_at(t))?),
+            })?;
+        }
+        Ok(material)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_mixture_rules_and_bounds() {
+        let waste = MaterialProperties::new("RSU", 500.0, 2000.0, 0.2);
+        let soil = MaterialProperties::new("Solo", 1500.0, 800.0, 1.0);
+        let mixture_with = |rule| {
+            let mut mixture = MaterialMixture::new("Blend", rule);
+            mixture.add_component(waste.clone(), 60.0);
+            mixture.add_component(soil.clone(), 40.0);
+            mixture.build().unwrap()
+        };
+
+        let parallel = mixture_with(ConductivityMixingRule::Parallel);
+        let serial = mixture_with(ConductivityMixingRule::Serial);
+        let geometric = mixture_with(ConductivityMixingRule::Geometric);
+
+        // Volumes específicos: 0,6/500 + 0,4/1500 → φ_RSU = 0,0012 / 0,0014667 = 9/11
+        let phi = 9.0 / 11.0;
+        assert_relative_eq!(parallel.density, 1.0 / (0.6 / 500.0 + 0.4 / 1500.0), epsilon = 1e-9);
+        assert_relative_eq!(parallel.specific_heat, 0.6 * 2000.0 + 0.4 * 800.0, epsilon = 1e-9);
+        assert_relative_eq!(parallel.thermal_conductivity, phi * 0.2 + (1.0 - phi) * 1.0, epsilon = 1e-9);
+        assert_relative_eq!(serial.thermal_conductivity, 1.0 / (phi / 0.2 + (1.0 - phi) / 1.0), epsilon = 1e-9);
+        assert!(serial.thermal_conductivity < geometric.thermal_conductivity);
+        assert!(geometric.thermal_conductivity < parallel.thermal_conductivity);
+        assert!(parallel.property_tables.is_none());
+
+        // Componente dependente da temperatura gera tabelas
+        let library = MaterialLibrary::new();
+        let mut mixture = MaterialMixture::new("Aço + vidro", ConductivityMixingRule::Parallel);
+        mixture.add_library_component(&library, "steel", 0.5).unwrap();
+        mixture.add_library_component(&library, "glass", 0.5).unwrap();
+        let blend = mixture.build().unwrap();
+        assert!(blend.property_tables.is_some());
+        assert_relative_eq!(blend.get_specific_heat(500.0), mixture.specific_heat_at(500.0), epsilon = 1e-6);
+        assert!(mixture.add_library_component(&library, "unobtainium", 0.1).is_err());
+        assert!(MaterialMixture::new("Vazia", ConductivityMixingRule::Serial).build().is_err());
+    }
+}
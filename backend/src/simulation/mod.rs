@@ -1,11 +1,72 @@
-// Módulo de estudos paramétricos para o simulador de fornalha de plasma
+// Módulo de simulação do simulador de fornalha de plasma
 
+pub mod materials;
+pub mod mesh;
+pub mod metrics;
+pub mod physics;
+pub mod solver;
+pub mod state;
+pub mod validation;
+pub mod visualization;
 pub mod parametric;
+pub mod alarms;
+pub mod scenarios;
+pub mod drying;
+pub mod annotations;
+pub mod frames;
+pub mod rerun;
+pub mod results_pool;
+pub mod property_import;
+pub mod gas_radiation;
+pub mod availability;
+pub mod sensor_placement;
+pub mod dimensionless;
+pub mod sensitivity;
+pub(crate) mod random;
+pub mod synthetic;
+pub mod snapshot;
+pub mod averaging;
+pub mod ensemble;
+pub mod project;
+pub mod jobs;
+pub mod export_worker;
+pub mod mixture;
+pub mod archive;
+pub mod storage;
+pub mod moisture;
+pub mod teaching;
+pub mod advection;
+pub mod packed_bed;
+pub mod bed_level;
+pub mod mass_balance;
+pub mod boundary;
+pub mod power_control;
+pub mod surrogate;
+pub mod study_store;
+#[cfg(feature = "async")]
+pub mod async_api;
 
 // Re-exportar tipos principais
+pub use materials::MaterialProperties;
+pub use physics::PlasmaTorch;
+pub use solver::{HeatSolver, SimulationParameters, SimulationResults};
+pub use state::{ProgressUpdate, SharedSimulationState, SimulationState, SimulationStatus};
 pub use parametric::{
     ParametricParameter,
     ScaleType,
+    SamplingMethod,
+    SobolIndices,
+    DesignGenerator,
+    ExperimentDesign,
+    DerivedParameter,
+    ParameterConstraint,
+    EarlyStoppingRule,
+    NelderMeadOptions,
+    BayesianOptimizationOptions,
+    StudySurrogate,
+    ParametricStudyProgress,
+    ParametricStudyHandle,
+    QueuedStudy,
     ParametricStudyConfig,
     OptimizationGoal,
     ParametricSimulationResult,
@@ -0,0 +1,184 @@
+// Implementação do modelo de evaporação da umidade (secagem da carga em torno de 100 °C)
+//
+// Acoplado por divisão de operadores: após cada atualização da entalpia, a entalpia
+// acima da correspondente à temperatura de evaporação é consumida como calor latente
+// enquanto a célula ainda contém água, mantendo a célula no patamar de evaporação até
+// secar.
+
+use ndarray::{Array2, Zip};
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::materials::MaterialProperties;
+use crate::simulation::mesh::CylindricalMesh;
+
+/// Calor latente de vaporização da água a 100 °C (J/kg)
+pub const WATER_LATENT_HEAT: f64 = 2.257e6;
+
+/// Estrutura que representa a configuração do modelo de evaporação da umidade
+///
+/// A umidade inicial de cada célula é o `moisture_content` (% em massa, base úmida)
+/// do seu material.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoistureEvaporationConfig {
+    /// Temperatura de evaporação (°C)
+    #[serde(default = "default_evaporation_temperature")]
+    pub evaporation_temperature: f64,
+    /// Calor latente de vaporização da água (J/kg)
+    #[serde(default = "default_latent_heat")]
+    pub latent_heat: f64,
+}
+
+fn default_evaporation_temperature() -> f64 {
+    100.0
+}
+
+fn default_latent_heat() -> f64 {
+    WATER_LATENT_HEAT
+}
+
+impl Default for MoistureEvaporationConfig {
+    fn default() -> Self {
+        Self {
+            evaporation_temperature: default_evaporation_temperature(),
+            latent_heat: default_latent_heat(),
+        }
+    }
+}
+
+impl MoistureEvaporationConfig {
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.evaporation_temperature.is_finite() {
+            return Err("Temperatura de evaporação da umidade inválida".to_string());
+        }
+        if self.latent_heat <= 0.0 || !self.latent_heat.is_finite() {
+            return Err("Calor latente de evaporação da umidade deve ser positivo".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa o resultado da secagem de uma execução
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoistureInfo {
+    /// Umidade remanescente ao fim da execução (kg de água / kg de material)
+    pub remaining_moisture: Array2<f64>,
+    /// Massa inicial de água na carga (kg)
+    pub initial_water_mass: f64,
+    /// Massa total de água evaporada (kg)
+    pub evaporated_water: f64,
+    /// Massa acumulada de água evaporada ao fim de cada passo (kg), a partir do passo 1
+    pub evaporation_history: Vec<f64>,
+}
+
+/// Estrutura que acompanha a umidade de cada célula durante a execução
+#[derive(Debug, Clone)]
+pub struct MoistureModel {
+    config: MoistureEvaporationConfig,
+    /// Umidade remanescente (kg de água / kg de material)
+    moisture: Array2<f64>,
+    /// Massa de material de cada célula (kg)
+    cell_mass: Array2<f64>,
+    /// Entalpia específica de cada célula na temperatura de evaporação (J/kg)
+    evaporation_enthalpy: Array2<f64>,
+    initial_water_mass: f64,
+    evaporation_history: Vec<f64>,
+}
+
+impl MoistureModel {
+    /// Cria o modelo com a umidade inicial dos materiais das células
+    ///
+    /// `enthalpy_at` converte temperatura em entalpia específica para um material.
+    pub fn new(
+        config: &MoistureEvaporationConfig,
+        mesh: &CylindricalMesh,
+        cell_materials: &[MaterialProperties],
+        cell_material_index: &Array2<usize>,
+        enthalpy_at: impl Fn(f64, &MaterialProperties) -> f64,
+    ) -> Self {
+        let t_evap = config.evaporation_temperature;
+        let evaporation_enthalpies: Vec<f64> = cell_materials.iter().map(|m| enthalpy_at(t_evap, m)).collect();
+        let moisture = cell_material_index.mapv(|idx| (cell_materials[idx].moisture_content / 100.0).clamp(0.0, 1.0));
+        let evaporation_enthalpy = cell_material_index.mapv(|idx| evaporation_enthalpies[idx]);
+        let mut cell_mass = Array2::<f64>::zeros(cell_material_index.dim());
+        Zip::from(&mut cell_mass)
+            .and(cell_material_index)
+            .and(&mesh.cell_volumes)
+            .for_each(|mass, &idx, &volume| *mass = cell_materials[idx].get_density(t_evap) * volume);
+        let initial_water_mass = (&moisture * &cell_mass).sum();
+        Self {
+            config: config.clone(),
+            moisture,
+            cell_mass,
+            evaporation_enthalpy,
+            initial_water_mass,
+            evaporation_history: Vec::new(),
+        }
+    }
+
+    /// Consome como calor latente a entalpia acima do patamar de evaporação das células úmidas
+    pub fn apply(&mut self, enthalpy: &mut Array2<f64>) {
+        let latent_heat = self.config.latent_heat;
+        Zip::from(enthalpy)
+            .and(&mut self.moisture)
+            .and(&self.evaporation_enthalpy)
+            .for_each(|h, m, &h_evap| {
+                if *m > 0.0 && *h > h_evap {
+                    let evaporated = ((*h - h_evap) / latent_heat).min(*m);
+                    *h -= evaporated * latent_heat;
+                    *m -= evaporated;
+                }
+            });
+    }
+
+    /// Considera secas as células acima da temperatura de evaporação (retomada de execução)
+    pub fn mark_dry_above(&mut self, temperature: &Array2<f64>) {
+        let t_evap = self.config.evaporation_temperature;
+        Zip::from(&mut self.moisture).and(temperature).for_each(|m, &t| {
+            if t > t_evap + 1e-6 {
+                *m = 0.0;
+            }
+        });
+    }
+
+    /// Umidade remanescente de cada célula (kg de água / kg de material)
+    pub fn moisture(&self) -> &Array2<f64> {
+        &self.moisture
+    }
+
+    /// Massa de água remanescente em cada célula (kg)
+    pub fn water_mass(&self) -> Array2<f64> {
+        &self.moisture * &self.cell_mass
+    }
+
+    /// Restaura a umidade remanescente (rejeição de subpassos)
+    pub fn set_moisture(&mut self, moisture: &Array2<f64>) {
+        self.moisture.assign(moisture);
+    }
+
+    /// Massa de água evaporada desde o início (kg)
+    pub fn evaporated_water(&self) -> f64 {
+        (self.initial_water_mass - (&self.moisture * &self.cell_mass).sum()).max(0.0)
+    }
+
+    /// Retoma o registro da massa evaporada a partir do histórico de uma execução anterior
+    pub fn resume_history(&mut self, history: &[f64]) {
+        self.evaporation_history = history.to_vec();
+    }
+
+    /// Registra a massa evaporada acumulada ao fim de um passo de saída
+    pub fn record_step(&mut self) {
+        let evaporated = self.evaporated_water();
+        self.evaporation_history.push(evaporated);
+    }
+
+    /// Resultado da secagem
+    pub fn finish(&self) -> MoistureInfo {
+        MoistureInfo {
+            remaining_moisture: self.moisture.clone(),
+            initial_water_mass: self.initial_water_mass,
+            evaporated_water: self.evaporated_water(),
+            evaporation_history: self.evaporation_history.clone(),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+// Implementação do modelo de leito poroso (propriedades efetivas de leitos de partículas)
+//
+// Materiais com porosidade ε > 0 são tratados como leito de partículas com gás (ar)
+// nos interstícios: densidade e capacidade térmica são médias volumétricas das fases,
+// a condutividade efetiva combina o modelo de Zehner-Schlünder para o leito estagnado
+// com a radiação entre partículas, e o escoamento opcional de gás pelo leito troca calor
+// com as partículas pela correlação de Wakao-Kaguei.
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::materials::{MaterialProperties, STEFAN_BOLTZMANN};
+use crate::simulation::physics::jet_impingement::GasTransportProperties;
+use crate::simulation::physics::GAS_CONSTANT;
+
+/// Massa molar do ar (kg/mol)
+const AIR_MOLAR_MASS: f64 = 0.028965;
+
+/// Densidade do ar a 1 atm na temperatura (°C), em kg/m³
+pub fn air_density(temperature: f64) -> f64 {
+    101325.0 * AIR_MOLAR_MASS / (GAS_CONSTANT * (temperature + 273.15).max(1.0))
+}
+
+/// Densidade aparente do leito (kg/m³): (1-ε)·ρₛ + ε·ρ_g
+pub fn effective_density(solid_density: f64, porosity: f64, temperature: f64) -> f64 {
+    (1.0 - porosity) * solid_density + porosity * air_density(temperature)
+}
+
+/// Capacidade térmica específica do leito por massa aparente (J/(kg·K))
+///
+/// (ρ·cp)_ef = (1-ε)·ρₛ·cpₛ + ε·ρ_g·cp_g, dividida pela densidade aparente.
+pub fn effective_specific_heat(solid_density: f64, solid_specific_heat: f64, porosity: f64, temperature: f64) -> f64 {
+    let gas = GasTransportProperties::for_gas("air");
+    let gas_density = air_density(temperature);
+    let heat_capacity = (1.0 - porosity) * solid_density * solid_specific_heat + porosity * gas_density * gas.specific_heat;
+    heat_capacity / effective_density(solid_density, porosity, temperature).max(1e-12)
+}
+
+/// Condutividade do leito estagnado pelo modelo de Zehner-Schlünder (W/(m·K))
+///
+/// Partículas esféricas: B = 1,25·((1-ε)/ε)^(10/9) e κ = kₛ/k_g.
+pub fn zehner_schlunder_conductivity(solid_conductivity: f64, gas_conductivity: f64, porosity: f64) -> f64 {
+    if porosity <= 0.0 {
+        return solid_conductivity;
+    }
+    if porosity >= 1.0 {
+        return gas_conductivity;
+    }
+    let b = 1.25 * ((1.0 - porosity) / porosity).powf(10.0 / 9.0);
+    let mut kappa = (solid_conductivity / gas_conductivity.max(1e-12)).max(1e-9);
+    // O limite κ → B é regular; afasta-se da singularidade numérica
+    if (1.0 - b / kappa).abs() < 1e-6 {
+        kappa *= 1.0 + 1e-5;
+    }
+    let denominator = 1.0 - b / kappa;
+    let core = (1.0 - 1.0 / kappa) * b / (denominator * denominator) * (kappa / b).ln()
+        - (b + 1.0) / 2.0
+        - (b - 1.0) / denominator;
+    let sqrt_solid = (1.0 - porosity).sqrt();
+    gas_conductivity * (1.0 - sqrt_solid + 2.0 * sqrt_solid / denominator * core)
+}
+
+/// Contribuição radiativa entre partículas (W/(m·K)): 4·σ·(e/(2-e))·d·T³
+pub fn radiative_conductivity(particle_diameter: f64, emissivity: f64, temperature: f64) -> f64 {
+    let kelvin = (temperature + 273.15).max(0.0);
+    let emissivity = emissivity.clamp(0.0, 1.0);
+    4.0 * STEFAN_BOLTZMANN * emissivity / (2.0 - emissivity) * particle_diameter * kelvin.powi(3)
+}
+
+/// Condutividade efetiva do leito (W/(m·K)): leito estagnado mais radiação entre partículas,
+/// esta apenas quando o diâmetro das partículas é conhecido
+pub fn effective_conductivity(
+    solid_conductivity: f64,
+    porosity: f64,
+    particle_diameter: Option<f64>,
+    emissivity: f64,
+    temperature: f64,
+) -> f64 {
+    let gas_conductivity = GasTransportProperties::for_gas("air").thermal_conductivity_at(temperature);
+    zehner_schlunder_conductivity(solid_conductivity, gas_conductivity, porosity)
+        + particle_diameter.map_or(0.0, |d| radiative_conductivity(d, emissivity, temperature))
+}
+
+/// Estrutura que representa o escoamento de gás pelos interstícios do leito
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BedGasExchange {
+    /// Velocidade superficial do gás (m/s)
+    pub superficial_velocity: f64,
+    /// Temperatura do gás que atravessa o leito (°C)
+    pub gas_temperature: f64,
+    /// Tipo de gás (mesmos nomes de `PlasmaTorch::gas_type`)
+    #[serde(default = "default_gas_type")]
+    pub gas_type: String,
+}
+
+fn default_gas_type() -> String {
+    "air".to_string()
+}
+
+impl BedGasExchange {
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        if self.superficial_velocity < 0.0 || !self.superficial_velocity.is_finite() {
+            return Err("Velocidade superficial do gás no leito não pode ser negativa".to_string());
+        }
+        if !self.gas_temperature.is_finite() {
+            return Err("Temperatura do gás no leito inválida".to_string());
+        }
+        Ok(())
+    }
+
+    /// Coeficiente volumétrico de troca gás-partícula (W/(m³·K)) de um leito
+    ///
+    /// Wakao-Kaguei: Nu = 2 + 1,1·Re^0,6·Pr^(1/3), com Re pela velocidade superficial e o
+    /// diâmetro das partículas, e área específica a = 6·(1-ε)/d.
+    pub fn volumetric_coefficient(&self, porosity: f64, particle_diameter: f64, temperature: f64) -> f64 {
+        if porosity <= 0.0 || particle_diameter <= 0.0 {
+            return 0.0;
+        }
+        let gas = GasTransportProperties::for_gas(&self.gas_type);
+        let film_temperature = (self.gas_temperature + temperature) / 2.0;
+        let reynolds = air_density(film_temperature) * self.superficial_velocity * particle_diameter / gas.viscosity_at(film_temperature);
+        let nusselt = 2.0 + 1.1 * reynolds.powf(0.6) * gas.prandtl_at(film_temperature).powf(1.0 / 3.0);
+        let h = nusselt * gas.thermal_conductivity_at(film_temperature) / particle_diameter;
+        h * 6.0 * (1.0 - porosity) / particle_diameter
+    }
+}
+
+/// Calcula o termo fonte da troca de calor com o gás intersticial (W/m³)
+///
+/// Apenas células de materiais porosos com diâmetro de partícula definido trocam calor.
+pub fn calculate_bed_gas_exchange_source(
+    exchange: &BedGasExchange,
+    cell_materials: &[MaterialProperties],
+    cell_material_index: &Array2<usize>,
+    temperature: &Array2<f64>,
+) -> Array2<f64> {
+    Array2::from_shape_fn(temperature.dim(), |(i, j)| {
+        let material = &cell_materials[cell_material_index[[i, j]]];
+        match material.particle_diameter {
+            Some(diameter) if material.porosity > 0.0 => {
+                let t = temperature[[i, j]];
+                exchange.volumetric_coefficient(material.porosity, diameter, t) * (exchange.gas_temperature - t)
+            }
+            _ => 0.0,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_packed_bed_effective_properties() {
+        // Limites: leito sem poros é o sólido; conduz menos que o sólido e mais que o gás
+        assert_relative_eq!(zehner_schlunder_conductivity(1.0, 0.025, 0.0), 1.0);
+        let k_bed = zehner_schlunder_conductivity(1.0, 0.025, 0.4);
+        assert!(k_bed > 0.025 && k_bed < 0.6 * 1.0 + 0.4 * 0.025);
+        assert!(zehner_schlunder_conductivity(1.0, 0.025, 0.6) < k_bed);
+        // κ = B não é singular
+        let b = 1.25 * (0.6f64 / 0.4).powf(10.0 / 9.0);
+        assert!(zehner_schlunder_conductivity(b * 0.025, 0.025, 0.4).is_finite());
+
+        let mut waste = MaterialProperties::new("RSU triturado", 900.0, 1800.0, 0.3);
+        let solid_conductivity = waste.get_thermal_conductivity(25.0);
+        waste.porosity = 0.5;
+        waste.particle_diameter = Some(0.02);
+        assert!(waste.validate_packed_bed().is_ok());
+        assert_relative_eq!(waste.get_density(25.0), 450.0 + 0.5 * air_density(25.0), epsilon = 1e-9);
+        assert!(waste.get_thermal_conductivity(25.0) < solid_conductivity);
+        // A radiação entre partículas domina em altas temperaturas
+        assert!(waste.get_thermal_conductivity(1200.0) > 2.0 * waste.get_thermal_conductivity(25.0));
+
+        // Gás mais quente que o leito aquece as partículas
+        let exchange = BedGasExchange { superficial_velocity: 0.5, gas_temperature: 800.0, gas_type: default_gas_type() };
+        let source = calculate_bed_gas_exchange_source(&exchange, &[waste.clone()], &Array2::zeros((2, 2)), &Array2::from_elem((2, 2), 25.0));
+        assert!(source.iter().all(|&q| q > 0.0));
+        waste.porosity = 1.0;
+        assert!(waste.validate_packed_bed().is_err());
+    }
+}
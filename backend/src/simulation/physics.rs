@@ -1,79 +1,28 @@
 // Implementação aprimorada para suporte a múltiplas tochas e configurações avançadas
 
-use ndarray::{Array2, Axis};
+use ndarray::Array2;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::f64::consts::PI;
 
-/// Constante de Stefan-Boltzmann (W/(m²·K⁴))
-pub const STEFAN_BOLTZMANN: f64 = 5.67e-8;
+use crate::formula::PlasmaProperty;
+use crate::simulation::materials::MaterialProperties;
 
-/// Estrutura que representa as propriedades do material
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MaterialProperties {
-    /// Nome do material
-    pub name: String,
-    /// Densidade (kg/m³)
-    pub density: f64,
-    /// Conteúdo de umidade (%)
-    pub moisture_content: f64,
-    /// Capacidade térmica específica (J/(kg·K))
-    pub specific_heat: f64,
-    /// Condutividade térmica (W/(m·K))
-    pub thermal_conductivity: f64,
-    /// Emissividade (0-1)
-    pub emissivity: f64,
-    /// Temperatura de fusão (°C)
-    pub melting_point: Option<f64>,
-    /// Calor latente de fusão (J/kg)
-    pub latent_heat_fusion: Option<f64>,
-    /// Temperatura de vaporização (°C)
-    pub vaporization_point: Option<f64>,
-    /// Calor latente de vaporização (J/kg)
-    pub latent_heat_vaporization: Option<f64>,
-}
+pub mod jet_impingement;
+pub mod participating_media;
+pub mod reactions;
 
-impl MaterialProperties {
-    /// Cria uma nova instância de propriedades de material com valores padrão
-    pub fn new(name: &str, density: f64, specific_heat: f64, thermal_conductivity: f64) -> Self {
-        Self {
-            name: name.to_string(),
-            density,
-            moisture_content: 0.0,
-            specific_heat,
-            thermal_conductivity,
-            emissivity: 0.9,
-            melting_point: None,
-            latent_heat_fusion: None,
-            vaporization_point: None,
-            latent_heat_vaporization: None,
-        }
-    }
+/// Constante de Stefan-Boltzmann (W/(m²·K⁴))
+pub const STEFAN_BOLTZMANN: f64 = 5.67e-8;
 
-    /// Calcula a capacidade térmica efetiva considerando mudanças de fase
-    pub fn effective_specific_heat(&self, temperature: f64, delta_t: f64) -> f64 {
-        let mut c_eff = self.specific_heat;
+/// Constante universal dos gases (J/(mol·K))
+pub const GAS_CONSTANT: f64 = 8.314;
 
-        // Adicionar efeito da mudança de fase (fusão)
-        if let (Some(mp), Some(lhf)) = (self.melting_point, self.latent_heat_fusion) {
-            // Intervalo de suavização para a mudança de fase
-            let phase_change_interval = 10.0;
-            if (temperature - mp).abs() < phase_change_interval {
-                c_eff += lhf / (2.0 * phase_change_interval);
-            }
-        }
+/// Taxa de espalhamento da meia-largura de um jato redondo turbulento (adimensional)
+pub const JET_SPREAD_RATE: f64 = 0.11;
 
-        // Adicionar efeito da mudança de fase (vaporização)
-        if let (Some(vp), Some(lhv)) = (self.vaporization_point, self.latent_heat_vaporization) {
-            // Intervalo de suavização para a mudança de fase
-            let phase_change_interval = 10.0;
-            if (temperature - vp).abs() < phase_change_interval {
-                c_eff += lhv / (2.0 * phase_change_interval);
-            }
-        }
-
-        c_eff
-    }
-}
+/// Comprimento do núcleo potencial do jato em diâmetros de bocal (adimensional)
+pub const JET_POTENTIAL_CORE_LENGTH: f64 = 6.2;
 
 /// Estrutura que representa uma tocha de plasma com configuração avançada
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,10 +51,342 @@ pub struct PlasmaTorch {
     pub length: f64,
     /// Tipo de gás (ar, argônio, etc.)
     pub gas_type: String,
+    /// Horas de operação acumuladas no início da simulação (h)
+    #[serde(default)]
+    pub operating_hours: f64,
+    /// Modelo de degradação por erosão dos eletrodos (opcional)
+    #[serde(default)]
+    pub degradation: Option<TorchDegradationModel>,
+    /// Programação de potência ao longo do tempo (opcional)
+    #[serde(default)]
+    pub power_schedule: Option<PowerSchedule>,
+    /// Dinâmica de partida (atraso de ignição, rampa, potência mínima estável) (opcional)
+    #[serde(default)]
+    pub startup: Option<TorchStartupDynamics>,
+    /// Perfil de deposição da potência no plano r-z
+    #[serde(default)]
+    pub flux_profile: TorchFluxProfile,
+    /// Fórmulas de plasma selecionadas para a tocha, por propriedade (as ausentes usam
+    /// as fórmulas padrão da biblioteca para o gás)
+    #[serde(default)]
+    pub plasma_formulas: HashMap<PlasmaProperty, String>,
+}
+
+/// Enumeração que representa o perfil de deposição da potência de uma tocha
+///
+/// O perfil é função da distância d ao ponto da tocha no plano r-z e é normalizado
+/// pelos volumes das células. Comprimentos menores que o espaçamento da malha são
+/// ampliados até ele, para que a pegada seja resolvida.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TorchFluxProfile {
+    /// Gaussiano exp(-d²/(2·σ²)); sem `sigma` usa o diâmetro da tocha
+    Gaussian { sigma: Option<f64> },
+    /// Decaimento exponencial exp(-d/λ)
+    Exponential { decay_length: f64 },
+    /// Uniforme dentro do raio (cartola)
+    TopHat { radius: f64 },
+}
+
+impl Default for TorchFluxProfile {
+    fn default() -> Self {
+        TorchFluxProfile::Gaussian { sigma: None }
+    }
+}
+
+impl TorchFluxProfile {
+    /// Valida os comprimentos característicos do perfil
+    pub fn validate(&self) -> Result<(), String> {
+        let length = match self {
+            TorchFluxProfile::Gaussian { sigma } => match sigma {
+                Some(sigma) => *sigma,
+                None => return Ok(()),
+            },
+            TorchFluxProfile::Exponential { decay_length } => *decay_length,
+            TorchFluxProfile::TopHat { radius } => *radius,
+        };
+        if length <= 0.0 || !length.is_finite() {
+            return Err("Comprimento característico do perfil de fluxo deve ser positivo".to_string());
+        }
+        Ok(())
+    }
+
+    /// Peso não normalizado do perfil à distância `distance` (m)
+    ///
+    /// `diameter` é o diâmetro da tocha e `resolution`, o menor comprimento resolvido pela malha.
+    pub fn weight(&self, distance: f64, diameter: f64, resolution: f64) -> f64 {
+        match self {
+            TorchFluxProfile::Gaussian { sigma } => {
+                let sigma = sigma.unwrap_or(diameter).max(resolution);
+                (-distance * distance / (2.0 * sigma * sigma)).exp()
+            }
+            TorchFluxProfile::Exponential { decay_length } => (-distance / decay_length.max(resolution)).exp(),
+            TorchFluxProfile::TopHat { radius } => {
+                if distance <= radius.max(resolution / 2.0) { 1.0 } else { 0.0 }
+            }
+        }
+    }
+}
+
+/// Programação de potência de uma tocha definida por pontos (tempo, potência)
+///
+/// A potência é interpolada linearmente entre os pontos e mantida constante
+/// fora do intervalo definido.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerSchedule {
+    /// Pontos (tempo (s), potência (kW)) ordenados por tempo
+    pub points: Vec<(f64, f64)>,
+}
+
+impl PowerSchedule {
+    /// Cria uma nova programação a partir dos pontos fornecidos (ordenados automaticamente)
+    pub fn new(mut points: Vec<(f64, f64)>) -> Self {
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        Self { points }
+    }
+
+    /// Cria uma programação com potência constante
+    pub fn constant(power: f64) -> Self {
+        Self::new(vec![(0.0, power)])
+    }
+
+    /// Cria uma rampa linear de `start_power` até `end_power` entre `start_time` e `end_time`
+    pub fn ramp(start_time: f64, start_power: f64, end_time: f64, end_power: f64) -> Self {
+        Self::new(vec![(start_time, start_power), (end_time, end_power)])
+    }
+
+    /// Adiciona um ponto à programação
+    pub fn add_point(&mut self, time: f64, power: f64) {
+        self.points.push((time, power));
+        self.points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    /// Retorna a potência programada para o tempo informado (kW)
+    pub fn power_at(&self, time: f64) -> Option<f64> {
+        if self.points.is_empty() {
+            return None;
+        }
+
+        let first = self.points[0];
+        if time <= first.0 {
+            return Some(first.1);
+        }
+
+        for window in self.points.windows(2) {
+            let (t0, p0) = window[0];
+            let (t1, p1) = window[1];
+            if time <= t1 {
+                if (t1 - t0).abs() < 1e-12 {
+                    return Some(p1);
+                }
+                return Some(p0 + (p1 - p0) * (time - t0) / (t1 - t0));
+            }
+        }
+
+        Some(self.points[self.points.len() - 1].1)
+    }
+}
+
+/// Estrutura que representa as restrições de partida e operação de uma tocha real
+///
+/// Aplicadas sobre qualquer programação de potência: a tocha só acende após o atraso
+/// de ignição, acende na potência mínima estável, varia a potência respeitando a taxa
+/// máxima de rampa e se apaga se a potência solicitada cair abaixo do mínimo estável.
+/// O desligamento (potência solicitada nula) é imediato.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TorchStartupDynamics {
+    /// Atraso entre a solicitação de potência e o estabelecimento do arco (s)
+    pub ignition_delay: f64,
+    /// Taxa máxima de variação da potência (kW/s), sem limite se None
+    pub max_ramp_rate: Option<f64>,
+    /// Potência mínima para arco estável (kW)
+    pub min_stable_power: f64,
+    /// Indica se a tocha já está acesa no início da simulação
+    #[serde(default)]
+    pub initially_running: bool,
+}
+
+impl TorchStartupDynamics {
+    /// Cria uma nova dinâmica de partida
+    pub fn new(ignition_delay: f64, max_ramp_rate: Option<f64>, min_stable_power: f64) -> Self {
+        Self {
+            ignition_delay,
+            max_ramp_rate,
+            min_stable_power,
+            initially_running: false,
+        }
+    }
+
+    /// Valida os parâmetros da dinâmica de partida
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ignition_delay < 0.0 {
+            return Err("Atraso de ignição não pode ser negativo".to_string());
+        }
+        if self.max_ramp_rate.is_some_and(|rate| rate <= 0.0) {
+            return Err("Taxa máxima de rampa deve ser positiva".to_string());
+        }
+        if self.min_stable_power < 0.0 {
+            return Err("Potência mínima estável não pode ser negativa".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Enumeração que representa os tipos de violação das restrições de partida
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleViolationKind {
+    /// Potência solicitada durante o atraso de ignição
+    IgnitionDelay,
+    /// Variação de potência acima da taxa máxima de rampa
+    RampRateLimit,
+    /// Potência solicitada abaixo da potência mínima estável
+    BelowMinimumStablePower,
+}
+
+/// Estrutura que representa um intervalo em que a programação não pôde ser executada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleViolation {
+    /// ID da tocha
+    pub torch_id: String,
+    /// Tipo de violação
+    pub kind: ScheduleViolationKind,
+    /// Início do intervalo (s)
+    pub start_time: f64,
+    /// Fim do intervalo (s)
+    pub end_time: f64,
+    /// Maior diferença entre a potência solicitada e a aplicada (kW)
+    pub max_deviation: f64,
+}
+
+/// Registra uma violação, estendendo a anterior se for contígua e do mesmo tipo
+fn record_violation(
+    violations: &mut Vec<ScheduleViolation>,
+    torch_id: &str,
+    kind: ScheduleViolationKind,
+    time: f64,
+    time_step: f64,
+    deviation: f64,
+) {
+    if let Some(last) = violations.last_mut() {
+        if last.kind == kind && (time - last.end_time - time_step).abs() < 1e-9 * time_step.max(1.0) {
+            last.end_time = time;
+            last.max_deviation = last.max_deviation.max(deviation);
+            return;
+        }
+    }
+    violations.push(ScheduleViolation {
+        torch_id: torch_id.to_string(),
+        kind,
+        start_time: time,
+        end_time: time,
+        max_deviation: deviation,
+    });
+}
+
+/// Curva de degradação definida por pontos (horas de operação, multiplicador)
+///
+/// Entre os pontos o multiplicador é interpolado linearmente; fora do intervalo
+/// definido o valor da extremidade mais próxima é mantido.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DegradationCurve {
+    /// Pontos (horas de operação (h), multiplicador) ordenados por horas
+    pub points: Vec<(f64, f64)>,
+}
+
+impl DegradationCurve {
+    /// Cria uma nova curva a partir dos pontos fornecidos (ordenados automaticamente)
+    pub fn new(mut points: Vec<(f64, f64)>) -> Self {
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        Self { points }
+    }
+
+    /// Cria uma curva com decaimento linear a partir de 1.0 até `final_value` em `hours`
+    pub fn linear(hours: f64, final_value: f64) -> Self {
+        Self::new(vec![(0.0, 1.0), (hours, final_value)])
+    }
+
+    /// Avalia o multiplicador para as horas de operação informadas
+    pub fn evaluate(&self, hours: f64) -> f64 {
+        if self.points.is_empty() {
+            return 1.0;
+        }
+
+        let first = self.points[0];
+        if hours <= first.0 {
+            return first.1;
+        }
+
+        for window in self.points.windows(2) {
+            let (h0, v0) = window[0];
+            let (h1, v1) = window[1];
+            if hours <= h1 {
+                if (h1 - h0).abs() < 1e-12 {
+                    return v1;
+                }
+                return v0 + (v1 - v0) * (hours - h0) / (h1 - h0);
+            }
+        }
+
+        self.points[self.points.len() - 1].1
+    }
+}
+
+/// Modelo de degradação da tocha com as horas de operação acumuladas
+///
+/// Cada curva descreve um multiplicador aplicado ao valor nominal da tocha.
+/// Curvas ausentes mantêm o parâmetro correspondente constante.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TorchDegradationModel {
+    /// Multiplicador da eficiência (potência entregue) em função das horas
+    pub efficiency_curve: Option<DegradationCurve>,
+    /// Multiplicador da temperatura do gás em função das horas
+    pub gas_temperature_curve: Option<DegradationCurve>,
+    /// Multiplicador do diâmetro da pluma (erosão do bocal) em função das horas
+    pub diameter_curve: Option<DegradationCurve>,
+    /// Horas de operação após as quais a manutenção é recomendada (h)
+    pub maintenance_interval: Option<f64>,
+}
+
+impl TorchDegradationModel {
+    /// Cria um modelo sem degradação
+    pub fn new() -> Self {
+        Self {
+            efficiency_curve: None,
+            gas_temperature_curve: None,
+            diameter_curve: None,
+            maintenance_interval: None,
+        }
+    }
+
+    /// Retorna o multiplicador de eficiência para as horas informadas
+    pub fn efficiency_factor(&self, hours: f64) -> f64 {
+        self.efficiency_curve.as_ref().map_or(1.0, |c| c.evaluate(hours))
+    }
+
+    /// Retorna o multiplicador de temperatura do gás para as horas informadas
+    pub fn gas_temperature_factor(&self, hours: f64) -> f64 {
+        self.gas_temperature_curve.as_ref().map_or(1.0, |c| c.evaluate(hours))
+    }
+
+    /// Retorna o multiplicador do diâmetro da pluma para as horas informadas
+    pub fn diameter_factor(&self, hours: f64) -> f64 {
+        self.diameter_curve.as_ref().map_or(1.0, |c| c.evaluate(hours))
+    }
+
+    /// Verifica se a manutenção é necessária para as horas informadas
+    pub fn requires_maintenance(&self, hours: f64) -> bool {
+        self.maintenance_interval.is_some_and(|interval| hours >= interval)
+    }
+}
+
+impl Default for TorchDegradationModel {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PlasmaTorch {
     /// Cria uma nova tocha de plasma com configuração básica
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: &str,
         r_position: f64,
@@ -130,10 +411,17 @@ impl PlasmaTorch {
             diameter: 0.05, // Valor padrão
             length: 0.2,    // Valor padrão
             gas_type: "Ar".to_string(), // Valor padrão
+            operating_hours: 0.0,
+            degradation: None,
+            power_schedule: None,
+            startup: None,
+            flux_profile: TorchFluxProfile::default(),
+            plasma_formulas: HashMap::new(),
         }
     }
 
     /// Cria uma nova tocha de plasma com configuração completa
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_details(
         id: &str,
         r_position: f64,
@@ -161,7 +449,133 @@ impl PlasmaTorch {
             diameter,
             length,
             gas_type: gas_type.to_string(),
+            operating_hours: 0.0,
+            degradation: None,
+            power_schedule: None,
+            startup: None,
+            flux_profile: TorchFluxProfile::default(),
+            plasma_formulas: HashMap::new(),
+        }
+    }
+
+    /// Define o modelo de degradação da tocha
+    pub fn set_degradation(&mut self, model: TorchDegradationModel) {
+        self.degradation = Some(model);
+    }
+
+    /// Retorna as horas de operação acumuladas após `elapsed_time` segundos de simulação
+    pub fn hours_at(&self, elapsed_time: f64) -> f64 {
+        self.operating_hours + elapsed_time.max(0.0) / 3600.0
+    }
+
+    /// Retorna uma cópia da tocha com os parâmetros degradados após `elapsed_time` segundos
+    ///
+    /// Sem modelo de degradação a tocha é retornada inalterada.
+    pub fn degraded_at(&self, elapsed_time: f64) -> PlasmaTorch {
+        let mut torch = self.clone();
+        if let Some(model) = &self.degradation {
+            let hours = self.hours_at(elapsed_time);
+            torch.power = self.power * model.efficiency_factor(hours);
+            torch.gas_temperature = self.gas_temperature * model.gas_temperature_factor(hours);
+            torch.diameter = self.diameter * model.diameter_factor(hours);
+        }
+        torch
+    }
+
+    /// Define a programação de potência da tocha
+    pub fn set_power_schedule(&mut self, schedule: PowerSchedule) {
+        self.power_schedule = Some(schedule);
+    }
+
+    /// Retorna uma cópia da tocha no estado correspondente a `elapsed_time` segundos
+    ///
+    /// Aplica a programação de potência (se houver) e em seguida a degradação.
+    pub fn at_time(&self, elapsed_time: f64) -> PlasmaTorch {
+        let mut torch = self.clone();
+        if let Some(power) = self.power_schedule.as_ref().and_then(|s| s.power_at(elapsed_time)) {
+            torch.power = power;
+        }
+        torch.degraded_at(elapsed_time)
+    }
+
+    /// Retorna a potência solicitada pela programação no tempo informado (kW)
+    pub fn requested_power(&self, elapsed_time: f64) -> f64 {
+        self.power_schedule.as_ref()
+            .and_then(|s| s.power_at(elapsed_time))
+            .unwrap_or(self.power)
+    }
+
+    /// Retorna uma cópia da tocha com a potência informada e a degradação aplicada
+    pub fn at_time_with_power(&self, elapsed_time: f64, power: f64) -> PlasmaTorch {
+        let mut torch = self.clone();
+        torch.power = power;
+        torch.degraded_at(elapsed_time)
+    }
+
+    /// Calcula a potência executável em cada passo (t = n·dt, n = 0..=time_steps)
+    ///
+    /// Aplica a dinâmica de partida (se houver) à potência solicitada e retorna as
+    /// potências aplicadas (kW, antes da degradação) e as violações encontradas.
+    pub fn constrained_power_profile(&self, time_step: f64, time_steps: usize) -> (Vec<f64>, Vec<ScheduleViolation>) {
+        let requested: Vec<f64> = (0..=time_steps)
+            .map(|n| self.requested_power(n as f64 * time_step))
+            .collect();
+        let dynamics = match &self.startup {
+            Some(dynamics) => dynamics,
+            None => return (requested, Vec::new()),
+        };
+
+        let mut applied = Vec::with_capacity(requested.len());
+        let mut violations = Vec::new();
+        let mut running = dynamics.initially_running;
+        let mut ignition_requested_at: Option<f64> = None;
+        let mut previous = 0.0;
+
+        for (n, &target) in requested.iter().enumerate() {
+            let time = n as f64 * time_step;
+            let mut power = 0.0;
+
+            if target <= 0.0 {
+                running = false;
+                ignition_requested_at = None;
+            } else if target < dynamics.min_stable_power {
+                // Arco instável: a tocha se apaga (ou não chega a acender)
+                running = false;
+                ignition_requested_at = None;
+                record_violation(&mut violations, &self.id, ScheduleViolationKind::BelowMinimumStablePower, time, time_step, target);
+            } else {
+                if !running {
+                    let requested_at = *ignition_requested_at.get_or_insert(time);
+                    if time - requested_at + 1e-9 >= dynamics.ignition_delay {
+                        running = true;
+                        previous = dynamics.min_stable_power;
+                    } else {
+                        record_violation(&mut violations, &self.id, ScheduleViolationKind::IgnitionDelay, time, time_step, target);
+                    }
+                } else if n == 0 {
+                    // Acesa desde o início: parte da potência solicitada
+                    previous = target;
+                }
+
+                if running {
+                    power = match dynamics.max_ramp_rate {
+                        Some(rate) => {
+                            let limit = rate * time_step;
+                            target.clamp(previous - limit, previous + limit).max(dynamics.min_stable_power)
+                        }
+                        None => target,
+                    };
+                    if (power - target).abs() > 1e-9 {
+                        record_violation(&mut violations, &self.id, ScheduleViolationKind::RampRateLimit, time, time_step, (power - target).abs());
+                    }
+                }
+            }
+
+            previous = power;
+            applied.push(power);
         }
+
+        (applied, violations)
     }
 
     /// Converte a posição da tocha para coordenadas cartesianas
@@ -230,41 +644,113 @@ impl PlasmaTorch {
         // Compatibilidade com a versão anterior (assumindo theta=0)
         self.view_factor_cylindrical(r, 0.0, z)
     }
+
+    /// Retorna a massa molar do gás de plasma (kg/mol)
+    pub fn gas_molar_mass(&self) -> f64 {
+        match self.gas_type.to_lowercase().as_str() {
+            "argon" | "argônio" => 0.039948,
+            "n2" | "nitrogen" | "nitrogênio" => 0.028014,
+            "he" | "helium" | "hélio" => 0.004003,
+            "h2" | "hydrogen" | "hidrogênio" => 0.002016,
+            "co2" => 0.04401,
+            "ar" | "air" => 0.028965,
+            _ => 0.028965,
+        }
+    }
+
+    /// Calcula a densidade do gás na saída da tocha pela lei dos gases ideais (kg/m³)
+    pub fn gas_density(&self) -> f64 {
+        let pressure = 101325.0;
+        let temperature_kelvin = (self.gas_temperature + 273.15).max(1.0);
+        pressure * self.gas_molar_mass() / (GAS_CONSTANT * temperature_kelvin)
+    }
+
+    /// Calcula a velocidade média do jato na saída do bocal (m/s)
+    pub fn jet_exit_velocity(&self) -> f64 {
+        let nozzle_area = PI * (self.diameter / 2.0).powi(2);
+        if nozzle_area < 1e-12 {
+            return 0.0;
+        }
+        self.gas_flow / (self.gas_density() * nozzle_area)
+    }
+
+    /// Calcula a meia-largura do jato a uma distância axial do bocal (m)
+    ///
+    /// Segue o espalhamento linear de jatos redondos turbulentos: b(s) = d/2 + C·s.
+    pub fn jet_half_width(&self, distance: f64) -> f64 {
+        self.diameter / 2.0 + JET_SPREAD_RATE * distance.max(0.0)
+    }
+
+    /// Calcula a razão entre a velocidade na linha de centro e a velocidade de saída
+    ///
+    /// Dentro do núcleo potencial a velocidade é preservada; além dele decai com 1/s.
+    pub fn jet_centerline_velocity_ratio(&self, distance: f64) -> f64 {
+        let core_length = JET_POTENTIAL_CORE_LENGTH * self.diameter;
+        if distance <= core_length {
+            1.0
+        } else {
+            core_length / distance
+        }
+    }
 }
 
 /// Estrutura que representa os termos fonte para a equação de calor
 #[derive(Debug, Clone)]
 pub struct HeatSources {
+    /// Termo fonte de deposição direta da potência das tochas (W/m³)
+    pub torches: Array2<f64>,
     /// Termo fonte de radiação (W/m³)
     pub radiation: Array2<f64>,
     /// Termo fonte de convecção (W/m³)
     pub convection: Array2<f64>,
     /// Termo fonte de mudança de fase (W/m³)
     pub phase_change: Array2<f64>,
+    /// Termo fonte do calor das reações da carga (W/m³)
+    pub reactions: Array2<f64>,
+    /// Termo advectivo do escoamento prescrito (W/m³)
+    pub advection: Array2<f64>,
+    /// Fonte volumétrica definida por fórmula do usuário (W/m³)
+    pub formula: Array2<f64>,
 }
 
 impl HeatSources {
     /// Cria uma nova instância de termos fonte com arrays zerados
     pub fn new(nr: usize, nz: usize) -> Self {
         Self {
+            torches: Array2::<f64>::zeros((nr, nz)),
             radiation: Array2::<f64>::zeros((nr, nz)),
             convection: Array2::<f64>::zeros((nr, nz)),
             phase_change: Array2::<f64>::zeros((nr, nz)),
+            reactions: Array2::<f64>::zeros((nr, nz)),
+            advection: Array2::<f64>::zeros((nr, nz)),
+            formula: Array2::<f64>::zeros((nr, nz)),
         }
     }
 
     /// Retorna a soma de todos os termos fonte
     pub fn total(&self) -> Array2<f64> {
-        &self.radiation + &self.convection + &self.phase_change
+        &self.torches + &self.radiation + &self.convection + &self.phase_change + &self.reactions + &self.advection + &self.formula
     }
 }
 
+/// Integra um campo de densidade de potência (W/m³) sobre a malha, retornando a potência total (W)
+pub fn integrate_source(mesh: &super::mesh::CylindricalMesh, field: &Array2<f64>) -> f64 {
+    field.iter()
+        .zip(mesh.cell_volumes.iter())
+        .map(|(&q, &volume)| q * volume)
+        .sum()
+}
+
 /// Calcula o termo fonte de radiação das tochas considerando múltiplas tochas e suas interações
+///
+/// A emissividade é a do material de cada célula (`cell_materials[cell_material_index[[i, j]]]`),
+/// de modo que cada zona absorve conforme o seu material.
 pub fn calculate_radiation_source(
     mesh: &super::mesh::CylindricalMesh,
     torches: &[PlasmaTorch],
     temperature: &Array2<f64>,
-    material: &MaterialProperties,
+    cell_materials: &[MaterialProperties],
+    cell_material_index: &Array2<usize>,
 ) -> Array2<f64> {
     let mut radiation_source = Array2::<f64>::zeros((mesh.nr, mesh.nz));
     
@@ -273,6 +759,7 @@ pub fn calculate_radiation_source(
         for j in 0..mesh.nz {
             let z = mesh.z_coords[j];
             let cell_temp = temperature[[i, j]];
+            let material = &cell_materials[cell_material_index[[i, j]]];
             
             // Contribuição de cada tocha
             for torch in torches {
@@ -288,7 +775,7 @@ pub fn calculate_radiation_source(
                 let cell_temp_kelvin = cell_temp + 273.15;
                 
                 // Equação de transferência de calor por radiação
-                let q_rad = material.emissivity * STEFAN_BOLTZMANN * avg_view_factor * 
+                let q_rad = material.get_emissivity(cell_temp) * STEFAN_BOLTZMANN * avg_view_factor * 
                             (torch_temp_kelvin.powi(4) - cell_temp_kelvin.powi(4));
                 
                 // Converter para densidade de potência (W/m³)
@@ -301,6 +788,11 @@ pub fn calculate_radiation_source(
 }
 
 /// Calcula o termo fonte de convecção considerando múltiplas tochas
+///
+/// O coeficiente `h_conv` vale no ponto de estagnação e é distribuído pela pegada
+/// relativa das correlações de impingimento (`jet_impingement::impingement_footprint`),
+/// de modo que a área afetada cresce com a altura e a inclinação da tocha em vez de ser
+/// uma fonte local fixa.
 pub fn calculate_convection_source(
     mesh: &super::mesh::CylindricalMesh,
     torches: &[PlasmaTorch],
@@ -309,47 +801,30 @@ pub fn calculate_convection_source(
 ) -> Array2<f64> {
     let mut convection_source = Array2::<f64>::zeros((mesh.nr, mesh.nz));
     
-    // Mapa de influência das tochas (para cada célula, qual tocha tem maior influência)
-    let mut torch_influence = Array2::<usize>::zeros((mesh.nr, mesh.nz));
-    let mut max_influence = Array2::<f64>::zeros((mesh.nr, mesh.nz));
-    
-    // Determinar a tocha com maior influência em cada célula
-    for i in 0..mesh.nr {
-        let r = mesh.r_coords[i];
-        for j in 0..mesh.nz {
-            let z = mesh.z_coords[j];
-            
-            for (idx, torch) in torches.iter().enumerate() {
-                let influence = torch.view_factor(r, z);
-                if influence > max_influence[[i, j]] {
-                    max_influence[[i, j]] = influence;
-                    torch_influence[[i, j]] = idx;
-                }
-            }
-        }
-    }
-    
-    // Calcular convecção baseada na tocha com maior influência
     for i in 0..mesh.nr {
         let r = mesh.r_coords[i];
         for j in 0..mesh.nz {
             let z = mesh.z_coords[j];
             let cell_temp = temperature[[i, j]];
             
-            // Obter a tocha com maior influência
-            let torch_idx = torch_influence[[i, j]];
-            if torch_idx < torches.len() {
-                let torch = &torches[torch_idx];
+            for torch in torches {
+                // Média angular da pegada do jato (simplificação 2D -> 3D)
+                let mut total_factor = 0.0;
+                for k in 0..mesh.ntheta {
+                    let theta = mesh.theta_coords[k];
+                    total_factor += jet_impingement::impingement_footprint(torch, r, theta, z);
+                }
+                let impingement_factor = total_factor / mesh.ntheta as f64;
                 
-                // Equação de transferência de calor por convecção
-                let q_conv = h_conv * (torch.gas_temperature - cell_temp);
+                if impingement_factor <= 0.0 {
+                    continue;
+                }
                 
-                // Ajustar pelo fator de visão para considerar a distância
-                let view_factor = torch.view_factor(r, z);
-                let adjusted_q_conv = q_conv * view_factor * 100.0; // Fator de escala para convecção
+                // Fluxo convectivo local (W/m²) ponderado pela pegada do jato
+                let q_conv = h_conv * (torch.gas_temperature - cell_temp) * impingement_factor;
                 
-                // Converter para densidade de potência (W/m³)
-                convection_source[[i, j]] = adjusted_q_conv / mesh.cell_volumes[[i, j]];
+                // Converter para densidade de potência (W/m³) usando a espessura da célula
+                convection_source[[i, j]] += q_conv / mesh.axial_cell_height(j);
             }
         }
     }
@@ -406,8 +881,8 @@ mod tests {
         
         // Para pitch=45°, yaw=90°, esperamos direção (0, 0.7071, 0.7071)
         assert_relative_eq!(dx, 0.0, epsilon = 1e-4);
-        assert_relative_eq!(dy, 0.7071, epsilon = 1e-4);
-        assert_relative_eq!(dz, 0.7071, epsilon = 1e-4);
+        assert_relative_eq!(dy, std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-4);
+        assert_relative_eq!(dz, std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-4);
     }
 
     #[test]
@@ -439,4 +914,105 @@ mod tests {
         // O fator de visão deve ser zero na direção oposta (cos < 0)
         assert_relative_eq!(vf3, 0.0);
     }
+
+    #[test]
+    fn test_jet_footprint_widens_with_height() {
+        let low_torch = PlasmaTorch::new("low", 0.0, 0.0, 0.2, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        let high_torch = PlasmaTorch::new("high", 0.0, 0.0, 1.0, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        
+        // A meia-largura cresce linearmente com a distância ao bocal
+        assert!(high_torch.jet_half_width(1.0) > low_torch.jet_half_width(0.2));
+        
+        // No eixo, a tocha mais alta produz uma pegada relativa menor
+        let low_center = jet_impingement::impingement_footprint(&low_torch, 0.0, 0.0, 0.0);
+        let high_center = jet_impingement::impingement_footprint(&high_torch, 0.0, 0.0, 0.0);
+        assert!(low_center > high_center);
+        
+        // Fora do eixo, a pegada relativa da tocha mais alta é mais larga
+        let low_ratio = jet_impingement::impingement_footprint(&low_torch, 0.1, 0.0, 0.0) / low_center;
+        let high_ratio = jet_impingement::impingement_footprint(&high_torch, 0.1, 0.0, 0.0) / high_center;
+        assert!(high_ratio > low_ratio);
+    }
+
+    #[test]
+    fn test_jet_exit_velocity_and_upstream_points() {
+        let torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        
+        // v = m / (rho * A)
+        let area = PI * (torch.diameter / 2.0).powi(2);
+        assert_relative_eq!(torch.jet_exit_velocity(), 0.01 / (torch.gas_density() * area), epsilon = 1e-10);
+        
+        // Pontos atrás do bocal não recebem o jato
+        assert_relative_eq!(jet_impingement::impingement_footprint(&torch, 0.0, 0.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_torch_degradation_curves() {
+        let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        torch.operating_hours = 100.0;
+        
+        let mut model = TorchDegradationModel::new();
+        model.efficiency_curve = Some(DegradationCurve::linear(200.0, 0.8));
+        model.diameter_curve = Some(DegradationCurve::new(vec![(200.0, 1.2), (0.0, 1.0)]));
+        model.maintenance_interval = Some(150.0);
+        torch.set_degradation(model);
+        
+        // 100 h iniciais: metade do decaimento
+        let degraded = torch.degraded_at(0.0);
+        assert_relative_eq!(degraded.power, 90.0, epsilon = 1e-10);
+        assert_relative_eq!(degraded.diameter, 0.05 * 1.1, epsilon = 1e-10);
+        assert_relative_eq!(degraded.gas_temperature, 5000.0, epsilon = 1e-10);
+        
+        // Após 200 h adicionais a curva satura no último ponto
+        let degraded = torch.degraded_at(200.0 * 3600.0);
+        assert_relative_eq!(degraded.power, 80.0, epsilon = 1e-10);
+        assert!(torch.degradation.as_ref().unwrap().requires_maintenance(torch.hours_at(200.0 * 3600.0)));
+    }
+
+    #[test]
+    fn test_power_schedule_interpolation() {
+        let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        torch.set_power_schedule(PowerSchedule::new(vec![(100.0, 50.0), (0.0, 0.0), (200.0, 50.0), (300.0, 0.0)]));
+        
+        assert_relative_eq!(torch.at_time(0.0).power, 0.0, epsilon = 1e-10);
+        assert_relative_eq!(torch.at_time(50.0).power, 25.0, epsilon = 1e-10);
+        assert_relative_eq!(torch.at_time(150.0).power, 50.0, epsilon = 1e-10);
+        assert_relative_eq!(torch.at_time(250.0).power, 25.0, epsilon = 1e-10);
+        assert_relative_eq!(torch.at_time(1000.0).power, 0.0, epsilon = 1e-10);
+        
+        // Sem programação a potência nominal é mantida
+        let nominal = PlasmaTorch::new("torch2", 0.0, 0.0, 0.5, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        assert_relative_eq!(nominal.at_time(500.0).power, 100.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_startup_dynamics_constrain_schedule() {
+        let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        torch.set_power_schedule(PowerSchedule::new(vec![(0.0, 100.0), (7.0, 100.0), (8.0, 10.0)]));
+        
+        // Sem dinâmica de partida a programação é executada como solicitada
+        let (unconstrained, violations) = torch.constrained_power_profile(1.0, 10);
+        assert_relative_eq!(unconstrained[0], 100.0);
+        assert!(violations.is_empty());
+        
+        torch.startup = Some(TorchStartupDynamics::new(3.0, Some(20.0), 30.0));
+        assert!(torch.startup.as_ref().unwrap().validate().is_ok());
+        let (applied, violations) = torch.constrained_power_profile(1.0, 10);
+        
+        assert_eq!(applied, vec![0.0, 0.0, 0.0, 50.0, 70.0, 90.0, 100.0, 100.0, 0.0, 0.0, 0.0]);
+        assert_eq!(violations.len(), 3);
+        assert_eq!(violations[0].kind, ScheduleViolationKind::IgnitionDelay);
+        assert_relative_eq!(violations[0].end_time, 2.0);
+        assert_eq!(violations[1].kind, ScheduleViolationKind::RampRateLimit);
+        assert_relative_eq!(violations[1].start_time, 3.0);
+        assert_relative_eq!(violations[1].max_deviation, 50.0);
+        assert_eq!(violations[2].kind, ScheduleViolationKind::BelowMinimumStablePower);
+        assert_relative_eq!(violations[2].end_time, 10.0);
+        
+        // Tocha já acesa no início: sem atraso nem rampa inicial
+        torch.startup.as_mut().unwrap().initially_running = true;
+        let (applied, violations) = torch.constrained_power_profile(1.0, 7);
+        assert!(applied.iter().all(|&p| (p - 100.0).abs() < 1e-9));
+        assert!(violations.is_empty());
+    }
 }
@@ -0,0 +1,217 @@
+// Implementação das correlações de transferência de calor por impingimento do jato de plasma
+//
+// O jato de cada tocha convecta calor para a carga com distribuição h(r) a partir do
+// ponto de estagnação: Nu₀ = 1,29·Re_D^0,5·Pr^0,4 no ponto de estagnação, com Re_D
+// baseado na velocidade de centro do jato no ponto, e decaimento de jato de parede
+// h/h₀ = (1 + (r/b)²)^(-0,55) com a distância r ao eixo, sendo b a meia-largura do jato.
+// As propriedades do gás são avaliadas na temperatura de filme.
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::{PlasmaTorch, GAS_CONSTANT};
+use crate::simulation::mesh::CylindricalMesh;
+
+/// Coeficiente da correlação de Nusselt no ponto de estagnação
+const STAGNATION_NUSSELT_COEFFICIENT: f64 = 1.29;
+
+/// Expoente do decaimento de h com (1 + (r/b)²) na região de jato de parede
+const WALL_JET_DECAY_EXPONENT: f64 = -0.55;
+
+/// Enumeração que representa o modelo do termo fonte de convecção das tochas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default)]
+pub enum ConvectionModel {
+    /// Coeficiente constante (`convection_coefficient`) ponderado pela pegada do jato
+    #[default]
+    Constant,
+    /// Correlações de impingimento a partir de `gas_flow` e `gas_temperature` de cada tocha
+    JetImpingement,
+}
+
+
+/// Estrutura que representa as propriedades de transporte de um gás de plasma
+///
+/// Viscosidade e condutividade seguem uma lei de potência em T a partir de 0 °C.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasTransportProperties {
+    /// Viscosidade dinâmica a 0 °C (Pa·s)
+    pub viscosity: f64,
+    /// Condutividade térmica a 0 °C (W/(m·K))
+    pub thermal_conductivity: f64,
+    /// Calor específico a pressão constante (J/(kg·K))
+    pub specific_heat: f64,
+    /// Expoente da dependência com a temperatura absoluta
+    pub temperature_exponent: f64,
+}
+
+impl GasTransportProperties {
+    /// Propriedades do gás pelo nome (os mesmos nomes de `PlasmaTorch::gas_molar_mass`)
+    pub fn for_gas(gas_type: &str) -> Self {
+        let (viscosity, thermal_conductivity, specific_heat, temperature_exponent) = match gas_type.to_lowercase().as_str() {
+            "argon" | "argônio" => (2.10e-5, 0.0163, 520.0, 0.72),
+            "n2" | "nitrogen" | "nitrogênio" => (1.66e-5, 0.0240, 1040.0, 0.70),
+            "he" | "helium" | "hélio" => (1.87e-5, 0.1420, 5193.0, 0.69),
+            "h2" | "hydrogen" | "hidrogênio" => (0.84e-5, 0.1680, 14300.0, 0.68),
+            "co2" => (1.37e-5, 0.0146, 850.0, 0.85),
+            _ => (1.72e-5, 0.0243, 1005.0, 0.70),
+        };
+        Self { viscosity, thermal_conductivity, specific_heat, temperature_exponent }
+    }
+
+    /// Fator de temperatura (T/273,15 K)^n, com T em °C
+    fn temperature_factor(&self, temperature: f64) -> f64 {
+        ((temperature + 273.15).max(1.0) / 273.15).powf(self.temperature_exponent)
+    }
+
+    /// Viscosidade dinâmica na temperatura (°C), em Pa·s
+    pub fn viscosity_at(&self, temperature: f64) -> f64 {
+        self.viscosity * self.temperature_factor(temperature)
+    }
+
+    /// Condutividade térmica na temperatura (°C), em W/(m·K)
+    pub fn thermal_conductivity_at(&self, temperature: f64) -> f64 {
+        self.thermal_conductivity * self.temperature_factor(temperature)
+    }
+
+    /// Número de Prandtl na temperatura (°C)
+    pub fn prandtl_at(&self, temperature: f64) -> f64 {
+        self.specific_heat * self.viscosity_at(temperature) / self.thermal_conductivity_at(temperature)
+    }
+}
+
+/// Posição de um ponto (coordenadas cilíndricas) em relação ao jato: (distância ao longo
+/// do eixo, distância ao eixo), em m
+fn jet_coordinates(torch: &PlasmaTorch, r: f64, theta: f64, z: f64) -> (f64, f64) {
+    let (torch_x, torch_y, torch_z) = torch.get_cartesian_position();
+    let (dir_x, dir_y, dir_z) = torch.get_direction_vector();
+    let dx = r * theta.cos() - torch_x;
+    let dy = r * theta.sin() - torch_y;
+    let dz = z - torch_z;
+    let axial_distance = dx * dir_x + dy * dir_y + dz * dir_z;
+    let radial_distance = (dx * dx + dy * dy + dz * dz - axial_distance * axial_distance).max(0.0).sqrt();
+    (axial_distance, radial_distance)
+}
+
+/// Coeficiente de transferência de calor no ponto de estagnação a uma distância axial do
+/// bocal (W/(m²·K)), com a superfície na temperatura `surface_temperature` (°C)
+pub fn stagnation_heat_transfer_coefficient(torch: &PlasmaTorch, axial_distance: f64, surface_temperature: f64) -> f64 {
+    if torch.gas_flow <= 0.0 || torch.diameter <= 0.0 {
+        return 0.0;
+    }
+    let gas = GasTransportProperties::for_gas(&torch.gas_type);
+    let film_temperature = (torch.gas_temperature + surface_temperature) / 2.0;
+    let film_density = 101325.0 * torch.gas_molar_mass() / (GAS_CONSTANT * (film_temperature + 273.15).max(1.0));
+    let velocity = torch.jet_exit_velocity() * torch.jet_centerline_velocity_ratio(axial_distance);
+    let reynolds = film_density * velocity * torch.diameter / gas.viscosity_at(film_temperature);
+    let nusselt = STAGNATION_NUSSELT_COEFFICIENT * reynolds.sqrt() * gas.prandtl_at(film_temperature).powf(0.4);
+    nusselt * gas.thermal_conductivity_at(film_temperature) / torch.diameter
+}
+
+/// Coeficiente de transferência de calor do jato em um ponto (coordenadas cilíndricas), em W/(m²·K)
+pub fn impingement_heat_transfer_coefficient(torch: &PlasmaTorch, r: f64, theta: f64, z: f64, surface_temperature: f64) -> f64 {
+    let (axial_distance, radial_distance) = jet_coordinates(torch, r, theta, z);
+    if axial_distance <= 0.0 {
+        return 0.0;
+    }
+    let half_width = torch.jet_half_width(axial_distance);
+    stagnation_heat_transfer_coefficient(torch, axial_distance, surface_temperature)
+        * (1.0 + (radial_distance / half_width).powi(2)).powf(WALL_JET_DECAY_EXPONENT)
+}
+
+/// Pegada relativa do jato em um ponto (coordenadas cilíndricas): h/h₀, com h₀ o
+/// coeficiente de estagnação no núcleo potencial
+///
+/// Com Nu₀ ∝ Re_D^0,5 e as propriedades na mesma temperatura de filme, a razão depende
+/// apenas do decaimento da velocidade de centro e do jato de parede. Retorna um valor
+/// no intervalo [0, 1].
+pub fn impingement_footprint(torch: &PlasmaTorch, r: f64, theta: f64, z: f64) -> f64 {
+    let (axial_distance, radial_distance) = jet_coordinates(torch, r, theta, z);
+    if axial_distance <= 0.0 {
+        return 0.0;
+    }
+    let half_width = torch.jet_half_width(axial_distance);
+    torch.jet_centerline_velocity_ratio(axial_distance).sqrt()
+        * (1.0 + (radial_distance / half_width).powi(2)).powf(WALL_JET_DECAY_EXPONENT)
+}
+
+/// Temperatura do jato em um ponto (°C): o excesso sobre o ambiente decai como a velocidade de centro
+fn local_jet_temperature(torch: &PlasmaTorch, axial_distance: f64, ambient_temperature: f64) -> f64 {
+    ambient_temperature + (torch.gas_temperature - ambient_temperature) * torch.jet_centerline_velocity_ratio(axial_distance)
+}
+
+/// Calcula o termo fonte de convecção pelas correlações de impingimento dos jatos (W/m³)
+pub fn calculate_jet_impingement_source(
+    mesh: &CylindricalMesh,
+    torches: &[PlasmaTorch],
+    temperature: &Array2<f64>,
+    ambient_temperature: f64,
+) -> Array2<f64> {
+    let mut convection_source = Array2::<f64>::zeros((mesh.nr, mesh.nz));
+
+    for i in 0..mesh.nr {
+        let r = mesh.r_coords[i];
+        for j in 0..mesh.nz {
+            let z = mesh.z_coords[j];
+            let cell_temp = temperature[[i, j]];
+
+            for torch in torches {
+                // Média angular do fluxo (simplificação 2D -> 3D)
+                let mut total_flux = 0.0;
+                for k in 0..mesh.ntheta {
+                    let theta = mesh.theta_coords[k];
+                    let (axial_distance, _) = jet_coordinates(torch, r, theta, z);
+                    if axial_distance <= 0.0 {
+                        continue;
+                    }
+                    let h = impingement_heat_transfer_coefficient(torch, r, theta, z, cell_temp);
+                    total_flux += h * (local_jet_temperature(torch, axial_distance, ambient_temperature) - cell_temp);
+                }
+
+                // Converter para densidade de potência (W/m³) usando a espessura da célula
+                convection_source[[i, j]] += total_flux / mesh.ntheta as f64 / mesh.axial_cell_height(j);
+            }
+        }
+    }
+
+    convection_source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::JET_POTENTIAL_CORE_LENGTH;
+
+    #[test]
+    fn test_jet_impingement_distribution() {
+        // Tocha no eixo a 0,8 m, apontada para a base
+        let torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.8, 180.0, 0.0, 100.0, 0.01, 5000.0);
+        let h0 = stagnation_heat_transfer_coefficient(&torch, 0.2, 25.0);
+        assert!(h0 > 0.0 && h0.is_finite());
+
+        // h ∝ Re^0,5: quadruplicar a vazão dobra h no núcleo potencial do jato
+        let mut strong = torch.clone();
+        strong.gas_flow = 0.04;
+        let core = 0.5 * JET_POTENTIAL_CORE_LENGTH * torch.diameter;
+        let ratio = stagnation_heat_transfer_coefficient(&strong, core, 25.0) / stagnation_heat_transfer_coefficient(&torch, core, 25.0);
+        assert!((ratio - 2.0).abs() < 1e-9);
+
+        // Decai com a distância ao bocal e com a distância ao eixo; nulo atrás do bocal
+        assert!(stagnation_heat_transfer_coefficient(&torch, 0.6, 25.0) < h0);
+        let on_axis = impingement_heat_transfer_coefficient(&torch, 0.0, 0.0, 0.6, 25.0);
+        let off_axis = impingement_heat_transfer_coefficient(&torch, 0.3, 0.0, 0.6, 25.0);
+        assert!(off_axis < on_axis && off_axis > 0.0);
+        assert_eq!(impingement_heat_transfer_coefficient(&torch, 0.0, 0.0, 0.9, 25.0), 0.0);
+
+        // A pegada relativa reproduz a razão entre as correlações e o valor no núcleo
+        let h_core = stagnation_heat_transfer_coefficient(&torch, core, 25.0);
+        let footprint = impingement_footprint(&torch, 0.3, 0.0, 0.6);
+        assert!((footprint - off_axis / h_core).abs() < 1e-9);
+        assert_eq!(impingement_footprint(&torch, 0.0, 0.0, 0.9), 0.0);
+
+        let mesh = CylindricalMesh::new(1.0, 0.5, 5, 10, 4);
+        let source = calculate_jet_impingement_source(&mesh, &[torch], &Array2::from_elem((5, 10), 25.0), 25.0);
+        assert!(source[[0, 5]] > source[[4, 5]] && source[[4, 5]] > 0.0);
+        assert_eq!(source[[0, 9]], 0.0);
+    }
+}
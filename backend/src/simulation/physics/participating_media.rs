@@ -0,0 +1,199 @@
+// Implementação da radiação em meios participantes (aproximações de Rosseland e P1)
+//
+// Em altas temperaturas o meio absorve e reemite radiação. Na aproximação de Rosseland
+// (meio opticamente espesso) a radiação age como condução com k_r = 16·n²·σ·T³/(3·β).
+// Na aproximação P1 resolve-se a radiação incidente G de
+// ∇·(Γ∇G) - κ·(G - 4·n²·σ·T⁴) = 0, com Γ = 1/(3·β), e o termo fonte é κ·(G - 4·n²·σ·T⁴).
+// As fronteiras são tratadas como paredes sem fluxo líquido de radiação, como na condução.
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::STEFAN_BOLTZMANN;
+use crate::simulation::mesh::{AxisTreatment, CylindricalMesh};
+use crate::simulation::solver::{axial_conductances, axial_diffusion, radial_conductances, radial_diffusion};
+
+/// Enumeração que representa a aproximação da radiação no meio participante
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParticipatingMediaModel {
+    /// Difusão de Rosseland (meio opticamente espesso, κ·L ≫ 1)
+    Rosseland,
+    /// Aproximação P1 (harmônicos esféricos de primeira ordem)
+    P1,
+}
+
+/// Estrutura que representa a configuração da radiação em meio participante
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipatingMediaConfig {
+    /// Aproximação utilizada
+    pub model: ParticipatingMediaModel,
+    /// Coeficiente de absorção (1/m)
+    pub absorption_coefficient: f64,
+    /// Coeficiente de espalhamento (1/m)
+    #[serde(default)]
+    pub scattering_coefficient: f64,
+    /// Índice de refração do meio
+    #[serde(default = "default_refractive_index")]
+    pub refractive_index: f64,
+    /// Máximo de iterações de Gauss-Seidel da equação P1
+    #[serde(default = "default_max_iterations")]
+    pub max_iterations: usize,
+    /// Tolerância relativa da equação P1
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f64,
+}
+
+fn default_refractive_index() -> f64 {
+    1.0
+}
+
+fn default_max_iterations() -> usize {
+    500
+}
+
+fn default_tolerance() -> f64 {
+    1e-8
+}
+
+impl ParticipatingMediaConfig {
+    /// Cria a configuração com a aproximação e o coeficiente de absorção informados
+    pub fn new(model: ParticipatingMediaModel, absorption_coefficient: f64) -> Self {
+        Self {
+            model,
+            absorption_coefficient,
+            scattering_coefficient: 0.0,
+            refractive_index: default_refractive_index(),
+            max_iterations: default_max_iterations(),
+            tolerance: default_tolerance(),
+        }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        if self.absorption_coefficient <= 0.0 || !self.absorption_coefficient.is_finite() {
+            return Err("Coeficiente de absorção do meio deve ser positivo".to_string());
+        }
+        if self.scattering_coefficient < 0.0 || !self.scattering_coefficient.is_finite() {
+            return Err("Coeficiente de espalhamento do meio não pode ser negativo".to_string());
+        }
+        if self.refractive_index < 1.0 || !self.refractive_index.is_finite() {
+            return Err("Índice de refração do meio deve ser pelo menos 1".to_string());
+        }
+        if self.max_iterations == 0 || self.tolerance <= 0.0 {
+            return Err("Iterações e tolerância da equação P1 devem ser positivas".to_string());
+        }
+        Ok(())
+    }
+
+    /// Coeficiente de extinção β = κ + σₛ (1/m)
+    pub fn extinction_coefficient(&self) -> f64 {
+        self.absorption_coefficient + self.scattering_coefficient
+    }
+
+    /// Poder emissivo de corpo negro no meio, 4·n²·σ·T⁴ (W/m²), com T em °C
+    fn blackbody_incident(&self, temperature: f64) -> f64 {
+        let kelvin = (temperature + 273.15).max(0.0);
+        4.0 * self.refractive_index.powi(2) * STEFAN_BOLTZMANN * kelvin.powi(4)
+    }
+
+    /// Condutividade radiativa de Rosseland (W/(m·K)), com T em °C
+    pub fn rosseland_conductivity(&self, temperature: f64) -> f64 {
+        let kelvin = (temperature + 273.15).max(0.0);
+        16.0 * self.refractive_index.powi(2) * STEFAN_BOLTZMANN * kelvin.powi(3) / (3.0 * self.extinction_coefficient())
+    }
+}
+
+/// Calcula o termo fonte da radiação no meio participante (W/m³)
+///
+/// A integral do termo sobre a malha é nula: a radiação apenas redistribui energia
+/// das regiões quentes para as frias.
+pub fn calculate_participating_media_source(
+    config: &ParticipatingMediaConfig,
+    mesh: &CylindricalMesh,
+    temperature: &Array2<f64>,
+) -> Array2<f64> {
+    match config.model {
+        ParticipatingMediaModel::Rosseland => rosseland_source(config, mesh, temperature),
+        ParticipatingMediaModel::P1 => p1_source(config, mesh, temperature),
+    }
+}
+
+/// Divergência do fluxo difusivo de Rosseland por volume
+fn rosseland_source(config: &ParticipatingMediaConfig, mesh: &CylindricalMesh, temperature: &Array2<f64>) -> Array2<f64> {
+    let conductivity = temperature.mapv(|t| config.rosseland_conductivity(t));
+    Array2::from_shape_fn((mesh.nr, mesh.nz), |(i, j)| {
+        let flux = radial_diffusion(mesh, &conductivity, temperature, i, j, AxisTreatment::FiniteVolume)
+            + axial_diffusion(mesh, &conductivity, temperature, i, j);
+        flux / mesh.cell_volumes[[i, j]]
+    })
+}
+
+/// Solução da equação P1 por Gauss-Seidel, partindo do equilíbrio local G = 4·n²·σ·T⁴
+fn p1_source(config: &ParticipatingMediaConfig, mesh: &CylindricalMesh, temperature: &Array2<f64>) -> Array2<f64> {
+    let kappa = config.absorption_coefficient;
+    let gamma = Array2::<f64>::from_elem((mesh.nr, mesh.nz), 1.0 / (3.0 * config.extinction_coefficient()));
+    let emission = temperature.mapv(|t| config.blackbody_incident(t));
+    let mut incident = emission.clone();
+    let scale = emission.iter().cloned().fold(0.0, f64::max).max(1e-12);
+
+    for _ in 0..config.max_iterations {
+        let mut max_change: f64 = 0.0;
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                let (west, east) = radial_conductances(mesh, &gamma, i, j, AxisTreatment::FiniteVolume);
+                let (south, north) = axial_conductances(mesh, &gamma, i, j);
+                let absorption = kappa * mesh.cell_volumes[[i, j]];
+                let mut numerator = absorption * emission[[i, j]];
+                if west > 0.0 {
+                    numerator += west * incident[[i - 1, j]];
+                }
+                if east > 0.0 {
+                    numerator += east * incident[[i + 1, j]];
+                }
+                if south > 0.0 {
+                    numerator += south * incident[[i, j - 1]];
+                }
+                if north > 0.0 {
+                    numerator += north * incident[[i, j + 1]];
+                }
+                let updated = numerator / (absorption + west + east + south + north);
+                max_change = max_change.max((updated - incident[[i, j]]).abs());
+                incident[[i, j]] = updated;
+            }
+        }
+        if max_change / scale < config.tolerance {
+            break;
+        }
+    }
+
+    (&incident - &emission) * kappa
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::integrate_source;
+
+    #[test]
+    fn test_participating_media_redistributes_energy() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 6, 8, 4);
+        let uniform = Array2::<f64>::from_elem((6, 8), 1200.0);
+        // Região quente junto à base
+        let hot_base = Array2::from_shape_fn((6, 8), |(_, j)| if j < 3 { 1500.0 } else { 500.0 });
+
+        for model in [ParticipatingMediaModel::Rosseland, ParticipatingMediaModel::P1] {
+            let config = ParticipatingMediaConfig::new(model, 5.0);
+            assert!(config.validate().is_ok());
+
+            let equilibrium = calculate_participating_media_source(&config, &mesh, &uniform);
+            assert!(equilibrium.iter().all(|&q| q.abs() < 1e-6), "{:?}", model);
+
+            let source = calculate_participating_media_source(&config, &mesh, &hot_base);
+            let emitted: f64 = integrate_source(&mesh, &source.mapv(|q| q.abs()));
+            assert!(source[[2, 2]] < 0.0 && source[[2, 3]] > 0.0, "{:?}", model);
+            assert!(integrate_source(&mesh, &source).abs() < 1e-4 * emitted, "{:?}", model);
+        }
+
+        assert!(ParticipatingMediaConfig::new(ParticipatingMediaModel::P1, 0.0).validate().is_err());
+    }
+}
@@ -0,0 +1,420 @@
+// Implementação das reações químicas da carga (pirólise/gaseificação com cinética de Arrhenius)
+//
+// A fração convertida α de cada célula reativa evolui por dα/dt = A·exp(-Eₐ/(R·T))·(1-α)ⁿ,
+// avaliada em Tⁿ. O calor de reação do incremento de conversão entra na equação da
+// entalpia como termo fonte volumétrico, constante no passo de tempo.
+//
+// A oxidação opcional do carvão queima o resíduo sólido formado pela pirólise: a fração
+// queimada β ≤ α evolui por dβ/dt = φ_O₂·k(T)·(α - β)ⁿ, liberando o calor de combustão.
+
+use ndarray::{Array2, Zip};
+use serde::{Deserialize, Serialize};
+
+use super::GAS_CONSTANT;
+use crate::simulation::materials::MaterialProperties;
+use crate::simulation::mesh::CylindricalMesh;
+
+/// Estrutura que representa a cinética de Arrhenius da decomposição da matéria orgânica
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArrheniusKinetics {
+    /// Fator pré-exponencial (1/s)
+    pub pre_exponential_factor: f64,
+    /// Energia de ativação (J/mol)
+    pub activation_energy: f64,
+    /// Ordem da reação em relação à fração não convertida
+    #[serde(default = "default_reaction_order")]
+    pub reaction_order: f64,
+}
+
+fn default_reaction_order() -> f64 {
+    1.0
+}
+
+impl ArrheniusKinetics {
+    /// Constante de taxa na temperatura (°C), em 1/s
+    pub fn rate_constant(&self, temperature: f64) -> f64 {
+        let kelvin = temperature + 273.15;
+        if kelvin <= 0.0 {
+            return 0.0;
+        }
+        self.pre_exponential_factor * (-self.activation_energy / (GAS_CONSTANT * kelvin)).exp()
+    }
+
+    /// Incremento da fração convertida em um passo `dt` a partir da conversão `alpha`
+    ///
+    /// Para primeira ordem usa a solução exata com a taxa constante no passo; para as
+    /// demais ordens, Euler explícito limitado à fração não convertida.
+    pub fn conversion_increment(&self, temperature: f64, alpha: f64, dt: f64) -> f64 {
+        self.increment(temperature, 1.0 - alpha, 1.0, dt)
+    }
+
+    /// Incremento em um passo `dt` a partir da fração `remaining` ainda disponível, com a
+    /// constante de taxa multiplicada por `rate_factor`
+    fn increment(&self, temperature: f64, remaining: f64, rate_factor: f64, dt: f64) -> f64 {
+        let remaining = remaining.max(0.0);
+        if remaining <= 0.0 {
+            return 0.0;
+        }
+        let k = self.rate_constant(temperature) * rate_factor;
+        if (self.reaction_order - 1.0).abs() < 1e-12 {
+            remaining * (1.0 - (-k * dt).exp())
+        } else {
+            (k * remaining.powf(self.reaction_order) * dt).min(remaining)
+        }
+    }
+
+    /// Valida os parâmetros cinéticos
+    pub fn validate(&self) -> Result<(), String> {
+        if self.pre_exponential_factor <= 0.0 || !self.pre_exponential_factor.is_finite() {
+            return Err("Fator pré-exponencial deve ser positivo".to_string());
+        }
+        if self.activation_energy < 0.0 || !self.activation_energy.is_finite() {
+            return Err("Energia de ativação não pode ser negativa".to_string());
+        }
+        if self.reaction_order < 0.0 || !self.reaction_order.is_finite() {
+            return Err("Ordem da reação não pode ser negativa".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Calor de combustão do carvão (carbono) a CO₂ (J/kg)
+pub const CHAR_HEAT_OF_COMBUSTION: f64 = 32.8e6;
+
+/// Estrutura que representa a configuração da oxidação do carvão formado pela pirólise
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharOxidationConfig {
+    /// Cinética da oxidação
+    pub kinetics: ArrheniusKinetics,
+    /// Massa de carvão formada por massa de material reativo convertido (kg/kg)
+    #[serde(default = "default_char_yield")]
+    pub char_yield: f64,
+    /// Disponibilidade de oxigênio (0: atmosfera inerte, 1: oxigênio em excesso), multiplica a taxa
+    #[serde(default = "default_oxygen_availability")]
+    pub oxygen_availability: f64,
+    /// Calor liberado por massa de carvão queimado (J/kg; positivo = exotérmica)
+    #[serde(default = "default_char_heat_of_combustion")]
+    pub heat_of_combustion: f64,
+}
+
+fn default_char_yield() -> f64 {
+    0.2
+}
+
+fn default_oxygen_availability() -> f64 {
+    1.0
+}
+
+fn default_char_heat_of_combustion() -> f64 {
+    CHAR_HEAT_OF_COMBUSTION
+}
+
+impl CharOxidationConfig {
+    /// Cria a configuração com a cinética e a disponibilidade de oxigênio informadas
+    pub fn new(kinetics: ArrheniusKinetics, oxygen_availability: f64) -> Self {
+        Self {
+            kinetics,
+            char_yield: default_char_yield(),
+            oxygen_availability,
+            heat_of_combustion: default_char_heat_of_combustion(),
+        }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        self.kinetics.validate()?;
+        if !(0.0..=1.0).contains(&self.char_yield) {
+            return Err("Rendimento de carvão deve estar entre 0 e 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.oxygen_availability) {
+            return Err("Disponibilidade de oxigênio deve estar entre 0 e 1".to_string());
+        }
+        if !self.heat_of_combustion.is_finite() {
+            return Err("Calor de combustão do carvão inválido".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa a configuração das reações da carga
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactionConfig {
+    /// Cinética da pirólise/gaseificação
+    pub pyrolysis: ArrheniusKinetics,
+    /// Calor de reação por massa de material reativo convertido (J/kg; positivo = endotérmica)
+    pub heat_of_reaction: f64,
+    /// Fração mássica reativa (orgânica) do material
+    #[serde(default = "default_reactive_fraction")]
+    pub reactive_fraction: f64,
+    /// Nomes dos materiais que reagem (vazio: todas as células)
+    #[serde(default)]
+    pub materials: Vec<String>,
+    /// Oxidação do carvão formado (None: somente pirólise)
+    #[serde(default)]
+    pub char_oxidation: Option<CharOxidationConfig>,
+}
+
+fn default_reactive_fraction() -> f64 {
+    1.0
+}
+
+impl ReactionConfig {
+    /// Cria a configuração com a cinética e o calor de reação informados
+    pub fn new(pyrolysis: ArrheniusKinetics, heat_of_reaction: f64) -> Self {
+        Self {
+            pyrolysis,
+            heat_of_reaction,
+            reactive_fraction: default_reactive_fraction(),
+            materials: Vec::new(),
+            char_oxidation: None,
+        }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        self.pyrolysis.validate()?;
+        if !self.heat_of_reaction.is_finite() {
+            return Err("Calor de reação da pirólise inválido".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.reactive_fraction) {
+            return Err("Fração reativa deve estar entre 0 e 1".to_string());
+        }
+        if let Some(char_oxidation) = &self.char_oxidation {
+            char_oxidation.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Indica se o material participa das reações
+    fn applies_to(&self, material: &MaterialProperties) -> bool {
+        self.materials.is_empty() || self.materials.iter().any(|name| name == &material.name)
+    }
+}
+
+/// Estrutura que representa o resultado das reações de uma execução
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactionInfo {
+    /// Fração convertida ao fim da execução (0 a 1; zero nas células não reativas)
+    pub conversion: Array2<f64>,
+    /// Massa inicial de material reativo (kg)
+    pub initial_reactive_mass: f64,
+    /// Massa total convertida (kg)
+    pub converted_mass: f64,
+    /// Calor total absorvido pela pirólise (J; negativo quando exotérmica)
+    pub reaction_heat: f64,
+    /// Massa acumulada convertida ao fim de cada passo (kg), a partir do passo 1
+    pub conversion_history: Vec<f64>,
+    /// Fração queimada ao fim da execução, relativa ao carvão máximo formável (0 a α),
+    /// se a oxidação do carvão estiver habilitada
+    #[serde(default)]
+    pub burned_fraction: Option<Array2<f64>>,
+    /// Massa total de carvão queimado (kg)
+    #[serde(default)]
+    pub burned_char_mass: f64,
+    /// Calor total liberado pela combustão do carvão (J)
+    #[serde(default)]
+    pub combustion_heat: f64,
+}
+
+/// Estrutura que acompanha a conversão de cada célula durante a execução
+#[derive(Debug, Clone)]
+pub struct ReactionModel {
+    config: ReactionConfig,
+    /// Fração convertida de cada célula
+    conversion: Array2<f64>,
+    /// Massa reativa por volume de cada célula (kg/m³; zero nas células não reativas)
+    reactive_density: Array2<f64>,
+    /// Volume de cada célula (m³)
+    cell_volumes: Array2<f64>,
+    /// Fração queimada do carvão de cada célula (oxidação do carvão, opcional)
+    burned_fraction: Option<Array2<f64>>,
+    reaction_heat: f64,
+    combustion_heat: f64,
+    conversion_history: Vec<f64>,
+}
+
+impl ReactionModel {
+    /// Cria o modelo com as células dos materiais reativos não convertidas
+    pub fn new(
+        config: &ReactionConfig,
+        mesh: &CylindricalMesh,
+        cell_materials: &[MaterialProperties],
+        cell_material_index: &Array2<usize>,
+        reference_temperature: f64,
+    ) -> Self {
+        let densities: Vec<f64> = cell_materials.iter()
+            .map(|m| if config.applies_to(m) { m.get_density(reference_temperature) * config.reactive_fraction } else { 0.0 })
+            .collect();
+        Self {
+            config: config.clone(),
+            conversion: Array2::<f64>::zeros(cell_material_index.dim()),
+            reactive_density: cell_material_index.mapv(|idx| densities[idx]),
+            cell_volumes: mesh.cell_volumes.clone(),
+            burned_fraction: config.char_oxidation.as_ref().map(|_| Array2::<f64>::zeros(cell_material_index.dim())),
+            reaction_heat: 0.0,
+            combustion_heat: 0.0,
+            conversion_history: Vec::new(),
+        }
+    }
+
+    /// Avança a conversão (e a queima do carvão) por `dt` em Tⁿ e retorna o termo fonte
+    /// do calor de reação (W/m³)
+    pub fn advance(&mut self, temperature: &Array2<f64>, dt: f64) -> Array2<f64> {
+        let mut source = Array2::<f64>::zeros(self.conversion.dim());
+        if dt <= 0.0 {
+            return source;
+        }
+        let kinetics = &self.config.pyrolysis;
+        let heat_of_reaction = self.config.heat_of_reaction;
+        Zip::from(&mut source)
+            .and(&mut self.conversion)
+            .and(&self.reactive_density)
+            .and(temperature)
+            .for_each(|q, alpha, &rho, &t| {
+                if rho > 0.0 {
+                    let increment = kinetics.conversion_increment(t, *alpha, dt);
+                    *alpha += increment;
+                    *q = -heat_of_reaction * rho * increment / dt;
+                }
+            });
+        self.reaction_heat -= (&source * &self.cell_volumes).sum() * dt;
+
+        // Oxidação do carvão já formado, incluindo o formado neste passo
+        if let (Some(char_oxidation), Some(burned)) = (&self.config.char_oxidation, self.burned_fraction.as_mut()) {
+            let mut combustion = Array2::<f64>::zeros(self.conversion.dim());
+            Zip::from(&mut combustion)
+                .and(burned)
+                .and(&self.conversion)
+                .and(&self.reactive_density)
+                .and(temperature)
+                .for_each(|q, beta, &alpha, &rho, &t| {
+                    if rho > 0.0 {
+                        let increment = char_oxidation.kinetics.increment(t, alpha - *beta, char_oxidation.oxygen_availability, dt);
+                        *beta += increment;
+                        *q = char_oxidation.heat_of_combustion * char_oxidation.char_yield * rho * increment / dt;
+                    }
+                });
+            self.combustion_heat += (&combustion * &self.cell_volumes).sum() * dt;
+            source += &combustion;
+        }
+        source
+    }
+
+    /// Fração convertida de cada célula
+    pub fn conversion(&self) -> &Array2<f64> {
+        &self.conversion
+    }
+
+    /// Massa reativa inicial de cada célula (kg)
+    pub fn reactive_mass(&self) -> Array2<f64> {
+        &self.reactive_density * &self.cell_volumes
+    }
+
+    /// Fração da massa convertida que permanece como carvão (zero sem oxidação do carvão)
+    pub fn char_yield(&self) -> f64 {
+        self.config.char_oxidation.as_ref().map_or(0.0, |char_oxidation| char_oxidation.char_yield)
+    }
+
+    /// Massa convertida desde o início (kg)
+    pub fn converted_mass(&self) -> f64 {
+        (&self.conversion * &self.reactive_density * &self.cell_volumes).sum()
+    }
+
+    /// Fração queimada do carvão de cada célula, se a oxidação estiver habilitada
+    pub fn burned_fraction(&self) -> Option<&Array2<f64>> {
+        self.burned_fraction.as_ref()
+    }
+
+    /// Massa de carvão queimado desde o início (kg)
+    pub fn burned_char_mass(&self) -> f64 {
+        match (&self.config.char_oxidation, &self.burned_fraction) {
+            (Some(char_oxidation), Some(burned)) => {
+                char_oxidation.char_yield * (burned * &self.reactive_density * &self.cell_volumes).sum()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Retoma a conversão e o histórico a partir de uma execução anterior
+    pub fn resume(&mut self, info: &ReactionInfo, history_len: usize) {
+        if info.conversion.dim() == self.conversion.dim() {
+            self.conversion.assign(&info.conversion);
+        }
+        if let (Some(burned), Some(previous)) = (self.burned_fraction.as_mut(), info.burned_fraction.as_ref()) {
+            if previous.dim() == burned.dim() {
+                burned.assign(previous);
+            }
+        }
+        self.reaction_heat = info.reaction_heat;
+        self.combustion_heat = info.combustion_heat;
+        self.conversion_history = info.conversion_history[..history_len.min(info.conversion_history.len())].to_vec();
+    }
+
+    /// Registra a massa convertida acumulada ao fim de um passo de saída
+    pub fn record_step(&mut self) {
+        let converted = self.converted_mass();
+        self.conversion_history.push(converted);
+    }
+
+    /// Resultado das reações
+    pub fn finish(&self) -> ReactionInfo {
+        ReactionInfo {
+            conversion: self.conversion.clone(),
+            initial_reactive_mass: (&self.reactive_density * &self.cell_volumes).sum(),
+            converted_mass: self.converted_mass(),
+            reaction_heat: self.reaction_heat,
+            conversion_history: self.conversion_history.clone(),
+            burned_fraction: self.burned_fraction.clone(),
+            burned_char_mass: self.burned_char_mass(),
+            combustion_heat: self.combustion_heat,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_arrhenius_conversion_increment() {
+        let kinetics = ArrheniusKinetics { pre_exponential_factor: 1e8, activation_energy: 1.2e5, reaction_order: 1.0 };
+        assert!(kinetics.rate_constant(500.0) > kinetics.rate_constant(300.0));
+        assert_eq!(kinetics.rate_constant(-300.0), 0.0);
+
+        // Primeira ordem: 1 - α = exp(-k·t), independente da divisão em passos
+        let k = kinetics.rate_constant(400.0);
+        let mut alpha = 0.0;
+        for _ in 0..10 {
+            alpha += kinetics.conversion_increment(400.0, alpha, 1.0);
+        }
+        assert_relative_eq!(alpha, 1.0 - (-10.0 * k).exp(), epsilon = 1e-12);
+
+        // Ordem diferente de um nunca ultrapassa a conversão completa
+        let second_order = ArrheniusKinetics { reaction_order: 2.0, ..kinetics.clone() };
+        assert!(second_order.conversion_increment(900.0, 0.5, 1e6) <= 0.5);
+        assert!(ArrheniusKinetics { pre_exponential_factor: 0.0, ..kinetics.clone() }.validate().is_err());
+
+        // Oxidação do carvão: queima limitada à conversão, nula sem oxigênio
+        let mesh = CylindricalMesh::new(1.0, 0.5, 3, 3, 4);
+        let materials = vec![MaterialProperties::new("Biomassa", 800.0, 1500.0, 0.5)];
+        let index = Array2::<usize>::zeros((3, 3));
+        let temperature = Array2::<f64>::from_elem((3, 3), 700.0);
+        let run = |oxygen_availability: f64| {
+            let mut config = ReactionConfig::new(kinetics.clone(), 0.0);
+            config.char_oxidation = Some(CharOxidationConfig::new(kinetics.clone(), oxygen_availability));
+            let mut model = ReactionModel::new(&config, &mesh, &materials, &index, 25.0);
+            let source = model.advance(&temperature, 10.0);
+            (source, model.finish())
+        };
+        let (inert_source, inert) = run(0.0);
+        assert!(inert_source.iter().all(|&q| q == 0.0));
+        assert!(inert.burned_fraction.as_ref().unwrap().iter().all(|&beta| beta == 0.0));
+        let (source, burning) = run(1.0);
+        let burned = burning.burned_fraction.as_ref().unwrap();
+        assert!(burned.iter().zip(burning.conversion.iter()).all(|(&beta, &alpha)| beta > 0.0 && beta <= alpha));
+        assert_relative_eq!(burning.combustion_heat, CHAR_HEAT_OF_COMBUSTION * burning.burned_char_mass, max_relative = 1e-9);
+        assert_relative_eq!((&source * &mesh.cell_volumes).sum() * 10.0, burning.combustion_heat, max_relative = 1e-9);
+        assert!(CharOxidationConfig::new(kinetics, 1.5).validate().is_err());
+    }
+}
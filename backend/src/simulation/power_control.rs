@@ -0,0 +1,242 @@
+// Implementação do controle em malha fechada da potência das tochas por fórmula
+//
+// Ao fim de cada passo de saída, a fórmula de controle recebe as temperaturas das sondas
+// (valor atual, valor do passo anterior e integral no tempo) e a potência aplicada no
+// passo, e retorna a potência das tochas controladas para o passo seguinte. Com a
+// integral e o valor anterior de cada sonda, leis do tipo PID são escritas diretamente:
+// para o setpoint Tsp, ∫(Tsp − T)dt = Tsp·t − integral e dT/dt ≈ (T − anterior)/dt.
+// A saída é limitada ao intervalo [min_power, max_power] e substitui a programação de
+// potência das tochas controladas a partir do passo seguinte.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use ndarray::Array2;
+
+use crate::formula::{Formula, FormulaCategory, FormulaManager, FunctionType, ParameterValue};
+use crate::simulation::mesh::CylindricalMesh;
+
+/// Variáveis fornecidas à fórmula de controle além das sondas, com suas unidades
+pub const POWER_CONTROL_VARIABLES: [(&str, &str); 3] = [
+    ("t", "s"),
+    ("dt", "s"),
+    ("power", "kW"),
+];
+
+/// Estrutura que representa uma sonda de temperatura usada pelo controle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlProbe {
+    /// Nome da variável da sonda na fórmula (identificador)
+    pub name: String,
+    /// Posição radial (m)
+    pub r: f64,
+    /// Posição axial (m)
+    pub z: f64,
+}
+
+/// Estrutura que representa a configuração do controle de potência por fórmula
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormulaPowerControl {
+    /// Fórmula da lei de controle (categoria `PhysicalModel`, resultado em kW)
+    pub formula: Formula,
+    /// Sondas de temperatura
+    pub probes: Vec<ControlProbe>,
+    /// IDs das tochas controladas (vazio: todas)
+    #[serde(default)]
+    pub torch_ids: Vec<String>,
+    /// Valores dos parâmetros constantes da fórmula (ganhos, setpoint)
+    #[serde(default)]
+    pub parameters: HashMap<String, f64>,
+    /// Potência mínima aplicada (kW)
+    #[serde(default)]
+    pub min_power: f64,
+    /// Potência máxima aplicada (kW)
+    pub max_power: f64,
+}
+
+impl FormulaPowerControl {
+    /// Nomes e unidades de todas as variáveis fornecidas pelo solucionador
+    fn variables(&self) -> Vec<(String, &'static str)> {
+        let mut variables: Vec<(String, &'static str)> = POWER_CONTROL_VARIABLES.iter()
+            .map(|(name, unit)| (name.to_string(), *unit))
+            .collect();
+        for probe in &self.probes {
+            variables.push((probe.name.clone(), "°C"));
+            variables.push((format!("{}_previous", probe.name), "°C"));
+            variables.push((format!("{}_integral", probe.name), "°C·s"));
+        }
+        variables
+    }
+
+    /// Valida a fórmula, as sondas, os parâmetros e os limites de potência
+    pub fn validate(&self) -> Result<(), String> {
+        if self.formula.category != FunctionType::PowerControl.to_category() {
+            return Err(format!(
+                "Controle de potência requer fórmula da categoria {:?}, encontrado {:?}",
+                FormulaCategory::PhysicalModel,
+                self.formula.category
+            ));
+        }
+        FunctionType::PowerControl.check_result_unit(&self.formula)?;
+        if self.probes.is_empty() {
+            return Err("Controle de potência requer ao menos uma sonda".to_string());
+        }
+        let variables = self.variables();
+        for (index, probe) in self.probes.iter().enumerate() {
+            let valid_name = probe.name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && probe.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid_name {
+                return Err(format!("Nome de sonda inválido: '{}'", probe.name));
+            }
+            if variables.iter().filter(|(name, _)| *name == probe.name).count() > 1
+                || self.probes[..index].iter().any(|other| other.name == probe.name)
+            {
+                return Err(format!("Nome de sonda repetido ou reservado: '{}'", probe.name));
+            }
+        }
+        for (name, value) in &self.parameters {
+            if variables.iter().any(|(variable, _)| variable == name) {
+                return Err(format!("Parâmetro '{}' do controle é fornecido pelo solucionador", name));
+            }
+            if !self.formula.parameters.iter().any(|param| &param.name == name) {
+                return Err(format!("Parâmetro '{}' não declarado pela fórmula de controle", name));
+            }
+            if !value.is_finite() {
+                return Err(format!("Parâmetro '{}' do controle deve ser finito", name));
+            }
+        }
+        if self.min_power < 0.0 || !self.max_power.is_finite() || self.max_power < self.min_power {
+            return Err("Limites de potência do controle devem satisfazer 0 ≤ mínimo ≤ máximo".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa o controlador de potência durante a execução
+pub struct PowerController {
+    /// Gerenciador com a fórmula de controle registrada
+    manager: FormulaManager,
+    /// Sondas de temperatura
+    probes: Vec<ControlProbe>,
+    /// Valores dos parâmetros constantes e das variáveis do passo
+    parameters: HashMap<String, ParameterValue>,
+    /// Temperatura de cada sonda no passo anterior (°C)
+    previous: Vec<f64>,
+    /// Integral no tempo da temperatura de cada sonda (°C·s)
+    integral: Vec<f64>,
+    /// Limites de potência (kW)
+    min_power: f64,
+    max_power: f64,
+    /// Potência comandada ao fim de cada passo (kW), a partir do passo 1
+    history: Vec<f64>,
+}
+
+impl PowerController {
+    /// Cria o controlador com as temperaturas iniciais das sondas
+    pub fn new(config: &FormulaPowerControl, mesh: &CylindricalMesh, temperature: &Array2<f64>) -> Result<Self, String> {
+        config.validate()?;
+        let variables = config.variables();
+        let variables: Vec<(&str, &str)> = variables.iter().map(|(name, unit)| (name.as_str(), *unit)).collect();
+        let mut manager = FormulaManager::new();
+        manager.register_power_control("power_control", config.formula.clone(), &variables)?;
+        let previous = config.probes.iter().map(|probe| mesh.interpolate(temperature, probe.r, probe.z)).collect();
+        Ok(Self {
+            manager,
+            probes: config.probes.clone(),
+            parameters: config.parameters.iter()
+                .map(|(name, &value)| (name.clone(), ParameterValue::Float(value)))
+                .collect(),
+            previous,
+            integral: vec![0.0; config.probes.len()],
+            min_power: config.min_power,
+            max_power: config.max_power,
+            history: Vec::new(),
+        })
+    }
+
+    /// Avalia a lei de controle ao fim de um passo e retorna a potência do passo seguinte (kW)
+    ///
+    /// `time` é o tempo ao fim do passo (s), `dt` a duração do passo (s) e `power` a
+    /// potência aplicada durante o passo (kW).
+    pub fn update(&mut self, mesh: &CylindricalMesh, temperature: &Array2<f64>, time: f64, dt: f64, power: f64) -> Result<f64, String> {
+        let float = |value: f64| ParameterValue::Float(value);
+        self.parameters.insert("t".to_string(), float(time));
+        self.parameters.insert("dt".to_string(), float(dt));
+        self.parameters.insert("power".to_string(), float(power));
+        for (index, probe) in self.probes.iter().enumerate() {
+            let current = mesh.interpolate(temperature, probe.r, probe.z);
+            self.integral[index] += 0.5 * (self.previous[index] + current) * dt;
+            self.parameters.insert(probe.name.clone(), float(current));
+            self.parameters.insert(format!("{}_previous", probe.name), float(self.previous[index]));
+            self.parameters.insert(format!("{}_integral", probe.name), float(self.integral[index]));
+            self.previous[index] = current;
+        }
+        let output = self.manager.evaluate_function(FunctionType::PowerControl, &self.parameters)?
+            .as_f64()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| format!("Lei de controle deve retornar uma potência finita (t = {} s)", time))?;
+        let power = output.clamp(self.min_power, self.max_power);
+        self.history.push(power);
+        Ok(power)
+    }
+
+    /// Potência comandada ao fim de cada passo (kW), a partir do passo 1
+    pub fn history(&self) -> &[f64] {
+        &self.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formula::{FormulaParameter, ParameterType};
+
+    #[test]
+    fn test_proportional_integral_control_law() {
+        let mesh = CylindricalMesh::new(1.0, 0.5, 3, 3, 4);
+        let parameter = |name: &str, value: f64| FormulaParameter {
+            name: name.to_string(),
+            description: String::new(),
+            param_type: ParameterType::Float,
+            default_value: ParameterValue::Float(value),
+            unit: String::new(),
+            min_value: None,
+            max_value: None,
+        };
+        let mut config = FormulaPowerControl {
+            formula: Formula {
+                name: "PI".to_string(),
+                description: String::new(),
+                source: "p0 + kp * (setpoint - core) + ki * (setpoint * t - core_integral)".to_string(),
+                ast: None,
+                parameters: vec![parameter("p0", 50.0), parameter("kp", 0.5), parameter("ki", 0.01), parameter("setpoint", 800.0)],
+                category: FormulaCategory::PhysicalModel,
+                result_unit: "kW".to_string(),
+                tests: Vec::new(),
+            },
+            probes: vec![ControlProbe { name: "core".to_string(), r: 0.0, z: 0.5 }],
+            torch_ids: Vec::new(),
+            parameters: HashMap::new(),
+            min_power: 0.0,
+            max_power: 150.0,
+        };
+        let temperature = Array2::<f64>::from_elem((3, 3), 700.0);
+        let mut controller = PowerController::new(&config, &mesh, &temperature).unwrap();
+
+        // Erro de 100 °C durante 10 s: P = 50 + 0,5·100 + 0,01·(100·10)
+        let power = controller.update(&mesh, &temperature, 10.0, 10.0, 50.0).unwrap();
+        assert!((power - 110.0).abs() < 1e-9);
+        let power = controller.update(&mesh, &temperature, 20.0, 10.0, power).unwrap();
+        assert!((power - 120.0).abs() < 1e-9);
+        // Saída limitada à potência máxima
+        let cold = Array2::<f64>::from_elem((3, 3), 0.0);
+        assert_eq!(controller.update(&mesh, &cold, 30.0, 10.0, power).unwrap(), 150.0);
+        assert_eq!(controller.history().len(), 3);
+
+        config.parameters.insert("core".to_string(), 1.0);
+        assert!(config.validate().is_err());
+        config.parameters.clear();
+        config.probes.push(ControlProbe { name: "t".to_string(), r: 0.0, z: 0.0 });
+        assert!(config.validate().is_err());
+    }
+}
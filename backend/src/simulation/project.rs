@@ -0,0 +1,420 @@
+// Implementação do formato de projeto (pasta única com índice, cenários, materiais, fórmulas, dados, execuções e relatórios)
+//
+// Estrutura da pasta do projeto:
+//
+// | Caminho              | Conteúdo                                         |
+// |----------------------|--------------------------------------------------|
+// | `project.json`       | Índice (`ProjectIndex`)                           |
+// | `scenarios/<id>.json`| Cenários (`ScenarioTemplate`)                     |
+// | `materials/<id>.json`| Materiais (`MaterialProperties`)                  |
+// | `formulas/<id>.json` | Fórmulas (`Formula`)                              |
+// | `reference/<id>.json`| Dados de referência (`ReferenceData`)             |
+// | `runs/<id>.results`  | Resultados de execuções (`simulation::archive`)   |
+// | `reports/<arquivo>`  | Relatórios e exportações copiados para o projeto  |
+
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::formula::engine::Formula;
+use crate::simulation::archive::{self, IntegrityReport};
+use crate::simulation::materials::MaterialProperties;
+use crate::simulation::scenarios::ScenarioTemplate;
+use crate::simulation::solver::SimulationResults;
+use crate::simulation::validation::ReferenceData;
+
+/// Nome do arquivo de índice do projeto
+pub const PROJECT_INDEX_FILE: &str = "project.json";
+
+/// Versão atual do formato de projeto
+pub const PROJECT_FORMAT_VERSION: u32 = 1;
+
+/// Enumeração que representa o tipo de um item do projeto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProjectItemKind {
+    /// Cenário operacional
+    Scenario,
+    /// Material
+    Material,
+    /// Fórmula
+    Formula,
+    /// Dados de referência
+    ReferenceData,
+    /// Resultados de uma execução
+    Run,
+    /// Relatório ou arquivo exportado
+    Report,
+}
+
+impl ProjectItemKind {
+    /// Subpasta do projeto onde os itens do tipo são gravados
+    pub fn directory(&self) -> &'static str {
+        match self {
+            ProjectItemKind::Scenario => "scenarios",
+            ProjectItemKind::Material => "materials",
+            ProjectItemKind::Formula => "formulas",
+            ProjectItemKind::ReferenceData => "reference",
+            ProjectItemKind::Run => "runs",
+            ProjectItemKind::Report => "reports",
+        }
+    }
+
+    /// Converte o nome do tipo (ex.: "scenario", "run") no tipo
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "scenario" => Some(ProjectItemKind::Scenario),
+            "material" => Some(ProjectItemKind::Material),
+            "formula" => Some(ProjectItemKind::Formula),
+            "reference_data" | "reference" => Some(ProjectItemKind::ReferenceData),
+            "run" => Some(ProjectItemKind::Run),
+            "report" => Some(ProjectItemKind::Report),
+            _ => None,
+        }
+    }
+}
+
+/// Estrutura que representa uma entrada do índice do projeto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectEntry {
+    /// Tipo do item
+    pub kind: ProjectItemKind,
+    /// Identificador do item (único por tipo)
+    pub id: String,
+    /// Caminho relativo à pasta do projeto
+    pub path: String,
+    /// Instante da última gravação (s desde a época Unix)
+    pub modified: u64,
+}
+
+/// Estrutura que representa o índice do projeto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectIndex {
+    /// Versão do formato
+    pub format_version: u32,
+    /// Nome do projeto
+    pub name: String,
+    /// Descrição do projeto
+    #[serde(default)]
+    pub description: String,
+    /// Instante de criação (s desde a época Unix)
+    pub created: u64,
+    /// Instante da última gravação do índice (s desde a época Unix)
+    pub modified: u64,
+    /// Itens do projeto
+    pub entries: Vec<ProjectEntry>,
+}
+
+impl ProjectIndex {
+    /// Retorna a entrada de um item
+    pub fn entry(&self, kind: ProjectItemKind, id: &str) -> Option<&ProjectEntry> {
+        self.entries.iter().find(|e| e.kind == kind && e.id == id)
+    }
+
+    /// Identificadores dos itens de um tipo
+    pub fn ids(&self, kind: ProjectItemKind) -> Vec<String> {
+        self.entries.iter().filter(|e| e.kind == kind).map(|e| e.id.clone()).collect()
+    }
+}
+
+/// Instante atual em segundos desde a época Unix
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Converte um identificador em nome de arquivo seguro
+///
+/// Identificadores já seguros são usados como estão; os demais têm os caracteres
+/// inválidos trocados por '_' e recebem o CRC-32 do identificador após um '~' (que
+/// nunca aparece em um identificador seguro), de modo que dois identificadores
+/// distintos não compartilham o mesmo arquivo.
+fn file_stem(id: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.';
+    if !id.is_empty() && id.chars().all(is_safe) && !id.chars().all(|c| c == '.') {
+        return id.to_string();
+    }
+    let stem: String = id.chars().map(|c| if is_safe(c) { c } else { '_' }).collect();
+    let stem = if stem.chars().all(|c| c == '.') { "_".to_string() } else { stem };
+    format!("{}~{:08x}", stem, archive::crc32(id.as_bytes()))
+}
+
+/// Caminho absoluto de uma entrada do índice, recusando caminhos fora da pasta do projeto
+fn entry_path(root: &Path, entry: &ProjectEntry) -> Result<PathBuf, String> {
+    let relative = Path::new(&entry.path);
+    if relative.as_os_str().is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("Caminho inválido no índice do projeto (absoluto ou fora da pasta): {}", entry.path));
+    }
+    Ok(root.join(relative))
+}
+
+/// Estrutura que representa um projeto aberto
+///
+/// Itens leves (cenários, materiais, fórmulas e dados de referência) são gravados em
+/// JSON; execuções em MessagePack, carregadas sob demanda. O índice só é atualizado
+/// em disco por `save`.
+#[derive(Debug, Clone)]
+pub struct ProjectBundle {
+    /// Pasta do projeto
+    root: PathBuf,
+    /// Índice do projeto
+    pub index: ProjectIndex,
+}
+
+impl ProjectBundle {
+    /// Cria um novo projeto vazio em uma pasta (criada se necessário)
+    pub fn create(root: &Path, name: &str) -> Result<Self, String> {
+        if root.join(PROJECT_INDEX_FILE).exists() {
+            return Err(format!("Já existe um projeto em {:?}", root));
+        }
+        fs::create_dir_all(root).map_err(|e| format!("Erro ao criar pasta do projeto {:?}: {}", root, e))?;
+        let timestamp = now();
+        let mut project = Self {
+            root: root.to_path_buf(),
+            index: ProjectIndex {
+                format_version: PROJECT_FORMAT_VERSION,
+                name: name.to_string(),
+                description: String::new(),
+                created: timestamp,
+                modified: timestamp,
+                entries: Vec::new(),
+            },
+        };
+        project.save()?;
+        info!("Projeto '{}' criado em {:?}", name, root);
+        Ok(project)
+    }
+
+    /// Abre um projeto existente
+    pub fn open(root: &Path) -> Result<Self, String> {
+        let path = root.join(PROJECT_INDEX_FILE);
+        let content = fs::read_to_string(&path).map_err(|e| format!("Erro ao ler índice do projeto {:?}: {}", path, e))?;
+        let index: ProjectIndex = serde_json::from_str(&content)
+            .map_err(|e| format!("Índice do projeto inválido {:?}: {}", path, e))?;
+        if index.format_version > PROJECT_FORMAT_VERSION {
+            return Err(format!("Versão do formato de projeto não suportada: {}", index.format_version));
+        }
+        for entry in &index.entries {
+            if !entry_path(root, entry)?.exists() {
+                return Err(format!("Arquivo do projeto ausente: {}", entry.path));
+            }
+        }
+        Ok(Self { root: root.to_path_buf(), index })
+    }
+
+    /// Pasta do projeto
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Grava o índice do projeto
+    pub fn save(&mut self) -> Result<(), String> {
+        self.index.modified = now();
+        let content = serde_json::to_string_pretty(&self.index)
+            .map_err(|e| format!("Erro ao serializar índice do projeto: {}", e))?;
+        // Gravação em arquivo temporário seguida de renomeação, para não corromper o índice
+        let path = self.root.join(PROJECT_INDEX_FILE);
+        let temporary = self.root.join(format!("{}.tmp", PROJECT_INDEX_FILE));
+        fs::write(&temporary, content).map_err(|e| format!("Erro ao gravar índice do projeto: {}", e))?;
+        fs::rename(&temporary, &path).map_err(|e| format!("Erro ao gravar índice do projeto: {}", e))
+    }
+
+    /// Registra (ou atualiza) uma entrada e retorna o caminho absoluto do arquivo
+    fn register(&mut self, kind: ProjectItemKind, id: &str, file_name: String) -> Result<PathBuf, String> {
+        if id.trim().is_empty() {
+            return Err("Identificador do item do projeto não pode ser vazio".to_string());
+        }
+        let directory = self.root.join(kind.directory());
+        fs::create_dir_all(&directory).map_err(|e| format!("Erro ao criar pasta {:?}: {}", directory, e))?;
+        let relative = format!("{}/{}", kind.directory(), file_name);
+        let modified = now();
+        match self.index.entries.iter_mut().find(|e| e.kind == kind && e.id == id) {
+            Some(entry) => {
+                entry.path = relative.clone();
+                entry.modified = modified;
+            }
+            None => self.index.entries.push(ProjectEntry { kind, id: id.to_string(), path: relative.clone(), modified }),
+        }
+        Ok(self.root.join(relative))
+    }
+
+    /// Grava um item leve em JSON
+    fn put_json<T: Serialize>(&mut self, kind: ProjectItemKind, id: &str, item: &T) -> Result<(), String> {
+        let path = self.register(kind, id, format!("{}.json", file_stem(id)))?;
+        let content = serde_json::to_string_pretty(item).map_err(|e| format!("Erro ao serializar item {}: {}", id, e))?;
+        fs::write(&path, content).map_err(|e| format!("Erro ao gravar item {:?}: {}", path, e))
+    }
+
+    /// Lê um item leve em JSON
+    fn get_json<T: DeserializeOwned>(&self, kind: ProjectItemKind, id: &str) -> Result<T, String> {
+        let path = self.item_path(kind, id)?;
+        let content = fs::read_to_string(&path).map_err(|e| format!("Erro ao ler item {:?}: {}", path, e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Item inválido {:?}: {}", path, e))
+    }
+
+    /// Caminho absoluto do arquivo de um item
+    pub fn item_path(&self, kind: ProjectItemKind, id: &str) -> Result<PathBuf, String> {
+        let entry = self.index.entry(kind, id)
+            .ok_or_else(|| format!("Item {:?} '{}' não encontrado no projeto", kind, id))?;
+        entry_path(&self.root, entry)
+    }
+
+    /// Adiciona ou substitui um cenário
+    pub fn put_scenario(&mut self, scenario: &ScenarioTemplate) -> Result<(), String> {
+        self.put_json(ProjectItemKind::Scenario, &scenario.id, scenario)
+    }
+
+    /// Lê um cenário
+    pub fn scenario(&self, id: &str) -> Result<ScenarioTemplate, String> {
+        self.get_json(ProjectItemKind::Scenario, id)
+    }
+
+    /// Adiciona ou substitui um material (identificado pelo nome)
+    pub fn put_material(&mut self, material: &MaterialProperties) -> Result<(), String> {
+        self.put_json(ProjectItemKind::Material, &material.name, material)
+    }
+
+    /// Lê um material
+    pub fn material(&self, id: &str) -> Result<MaterialProperties, String> {
+        self.get_json(ProjectItemKind::Material, id)
+    }
+
+    /// Adiciona ou substitui uma fórmula (identificada pelo nome)
+    pub fn put_formula(&mut self, formula: &Formula) -> Result<(), String> {
+        self.put_json(ProjectItemKind::Formula, &formula.name, formula)
+    }
+
+    /// Lê uma fórmula
+    pub fn formula(&self, id: &str) -> Result<Formula, String> {
+        self.get_json(ProjectItemKind::Formula, id)
+    }
+
+    /// Adiciona ou substitui um conjunto de dados de referência (identificado pelo nome)
+    pub fn put_reference_data(&mut self, data: &ReferenceData) -> Result<(), String> {
+        self.put_json(ProjectItemKind::ReferenceData, &data.name, data)
+    }
+
+    /// Lê um conjunto de dados de referência
+    pub fn reference_data(&self, id: &str) -> Result<ReferenceData, String> {
+        self.get_json(ProjectItemKind::ReferenceData, id)
+    }
+
+    /// Grava os resultados de uma execução (arquivo com somas de verificação por quadro)
+    pub fn put_run(&mut self, id: &str, results: &SimulationResults) -> Result<(), String> {
+        let path = self.register(ProjectItemKind::Run, id, format!("{}.results", file_stem(id)))?;
+        let bytes = archive::encode_results_archive(results)?;
+        fs::write(&path, bytes).map_err(|e| format!("Erro ao gravar execução {:?}: {}", path, e))
+    }
+
+    /// Carrega os resultados de uma execução
+    pub fn run(&self, id: &str) -> Result<SimulationResults, String> {
+        self.run_with_report(id).map(|(results, _)| results)
+    }
+
+    /// Carrega os resultados de uma execução com o relatório de integridade
+    ///
+    /// Quadros corrompidos são recuperados como NaN; execuções gravadas no formato
+    /// MessagePack anterior são lidas sem verificação.
+    pub fn run_with_report(&self, id: &str) -> Result<(SimulationResults, IntegrityReport), String> {
+        let path = self.item_path(ProjectItemKind::Run, id)?;
+        let bytes = fs::read(&path).map_err(|e| format!("Erro ao ler execução {:?}: {}", path, e))?;
+        if archive::is_results_archive(&bytes) {
+            let (results, report) = archive::decode_results_archive(&bytes)
+                .map_err(|e| format!("Execução inválida {:?}: {}", path, e))?;
+            if !report.is_intact() {
+                warn!("Execução {} danificada: quadros corrompidos {:?}, ausentes {:?}",
+                    id, report.corrupted_frames, report.missing_frames);
+            }
+            return Ok((results, report));
+        }
+        let results: SimulationResults = rmp_serde::from_slice(&bytes)
+            .map_err(|e| format!("Execução inválida {:?}: {}", path, e))?;
+        let report = IntegrityReport {
+            file_checksum_valid: true,
+            total_frames: results.temperature.dim().2,
+            ..IntegrityReport::default()
+        };
+        Ok((results, report))
+    }
+
+    /// Copia um relatório ou arquivo exportado para o projeto
+    pub fn add_report(&mut self, id: &str, source: &Path) -> Result<(), String> {
+        let extension = source.extension().and_then(|e| e.to_str()).map_or(String::new(), |e| format!(".{}", e));
+        let path = self.register(ProjectItemKind::Report, id, format!("{}{}", file_stem(id), extension))?;
+        fs::copy(source, &path).map(|_| ()).map_err(|e| format!("Erro ao copiar relatório {:?}: {}", source, e))
+    }
+
+    /// Remove um item do projeto e seu arquivo
+    pub fn remove(&mut self, kind: ProjectItemKind, id: &str) -> Result<bool, String> {
+        let position = match self.index.entries.iter().position(|e| e.kind == kind && e.id == id) {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+        let path = entry_path(&self.root, &self.index.entries[position])?;
+        self.index.entries.remove(position);
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Erro ao remover {:?}: {}", path, e))?;
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::scenarios::get_scenario_templates;
+
+    #[test]
+    fn test_project_bundle_roundtrip() {
+        let root = std::env::temp_dir().join(format!("project_bundle_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let mut project = ProjectBundle::create(&root, "Forno 1").unwrap();
+        let scenario = get_scenario_templates().into_iter().next().unwrap();
+        project.put_scenario(&scenario).unwrap();
+        let material = MaterialProperties::new("Escória / teste", 2800.0, 1000.0, 1.5);
+        project.put_material(&material).unwrap();
+
+        let report = root.join("relatorio.csv");
+        fs::write(&report, "a,b\n1,2\n").unwrap();
+        project.add_report("relatório final", &report).unwrap();
+        project.save().unwrap();
+        assert!(ProjectBundle::create(&root, "Outro").is_err());
+
+        let reopened = ProjectBundle::open(&root).unwrap();
+        assert_eq!(reopened.index.name, "Forno 1");
+        assert_eq!(reopened.index.ids(ProjectItemKind::Material), vec!["Escória / teste".to_string()]);
+        assert_eq!(reopened.scenario(&scenario.id).unwrap().name, scenario.name);
+        assert_eq!(reopened.material("Escória / teste").unwrap().density, 2800.0);
+        let report_path = reopened.item_path(ProjectItemKind::Report, "relatório final").unwrap();
+        let report_name = report_path.file_name().unwrap().to_str().unwrap();
+        assert!(report_name.starts_with("relat_rio_final~") && report_name.ends_with(".csv"));
+        assert!(reopened.run("inexistente").is_err());
+
+        // Identificadores distintos nunca compartilham o mesmo arquivo
+        assert_eq!(file_stem("forno-1.v2"), "forno-1.v2");
+        assert_ne!(file_stem("a/b"), file_stem("a b"));
+        assert_ne!(file_stem("a b"), "a_b");
+        assert_ne!(file_stem(".."), file_stem("."));
+
+        let mut reopened = reopened;
+        assert!(reopened.remove(ProjectItemKind::Material, "Escória / teste").unwrap());
+        assert!(reopened.index.entry(ProjectItemKind::Material, "Escória / teste").is_none());
+
+        // Caminhos absolutos ou com ".." no índice são recusados
+        let outside = root.with_extension("outside");
+        fs::write(&outside, "{}").unwrap();
+        for path in [outside.to_str().unwrap().to_string(), format!("../{}", outside.file_name().unwrap().to_str().unwrap())] {
+            reopened.index.entries.push(ProjectEntry { kind: ProjectItemKind::Report, id: "externo".to_string(), path, modified: 0 });
+            reopened.save().unwrap();
+            assert!(ProjectBundle::open(&root).is_err());
+            assert!(reopened.item_path(ProjectItemKind::Report, "externo").is_err());
+            assert!(reopened.remove(ProjectItemKind::Report, "externo").is_err());
+            assert!(outside.exists());
+            reopened.index.entries.retain(|e| e.id != "externo");
+        }
+        let _ = fs::remove_file(&outside);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,731 @@
+// Importação de propriedades termofísicas a partir de arquivos padronizados (JANAF, Shomate/NIST, ThermoML)
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::simulation::materials::MaterialProperties;
+
+/// Diferença entre as escalas Kelvin e Celsius
+const KELVIN_OFFSET: f64 = 273.15;
+
+/// Erro relativo máximo do ajuste polinomial antes de emitir um aviso
+const FIT_WARNING_THRESHOLD: f64 = 0.05;
+
+/// Enumeração que representa os formatos de arquivos de propriedades suportados
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropertyFileFormat {
+    /// Tabela NIST-JANAF (T(K), Cp, S, -[G-H(Tr)]/T, H-H(Tr), ...), valores molares
+    Janaf,
+    /// Coeficientes de Shomate do NIST WebBook (Cp = A + B·t + C·t² + D·t³ + E/t², t = T/1000)
+    Shomate,
+    /// Arquivo XML ThermoML (IUPAC), subconjunto de propriedades de substâncias puras
+    ThermoMl,
+}
+
+impl PropertyFileFormat {
+    /// Detecta o formato pela extensão do arquivo ou pelo conteúdo
+    pub fn detect(path: &Path, content: &str) -> Option<Self> {
+        let extension = path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase());
+        match extension.as_deref() {
+            Some("xml") => return Some(PropertyFileFormat::ThermoMl),
+            Some("janaf") => return Some(PropertyFileFormat::Janaf),
+            _ => {}
+        }
+        if content.contains("<DataReport") || content.contains("<PureOrMixtureData") {
+            Some(PropertyFileFormat::ThermoMl)
+        } else if content.lines().any(|l| l.trim_start().starts_with("T(K)")) {
+            Some(PropertyFileFormat::Janaf)
+        } else if content.lines().any(|l| l.trim_start().starts_with("Temperature (K)")) {
+            Some(PropertyFileFormat::Shomate)
+        } else {
+            None
+        }
+    }
+}
+
+/// Estrutura que representa as opções de importação de propriedades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyImportOptions {
+    /// Formato do arquivo (detectado automaticamente quando None)
+    pub format: Option<PropertyFileFormat>,
+    /// Massa molar (kg/mol), necessária para converter valores molares
+    pub molar_mass: Option<f64>,
+    /// Temperatura de referência dos polinômios (°C)
+    pub reference_temperature: f64,
+    /// Grau dos polinômios ajustados
+    pub polynomial_degree: usize,
+    /// Temperatura mínima considerada no ajuste (°C)
+    pub min_temperature: Option<f64>,
+    /// Temperatura máxima considerada no ajuste (°C)
+    pub max_temperature: Option<f64>,
+}
+
+impl Default for PropertyImportOptions {
+    fn default() -> Self {
+        Self {
+            format: None,
+            molar_mass: None,
+            reference_temperature: 25.0,
+            polynomial_degree: 3,
+            min_temperature: None,
+            max_temperature: None,
+        }
+    }
+}
+
+/// Estrutura que representa uma série tabelada de uma propriedade
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PropertySeries {
+    /// Temperaturas (°C)
+    pub temperatures: Vec<f64>,
+    /// Valores da propriedade (unidades SI por massa)
+    pub values: Vec<f64>,
+}
+
+impl PropertySeries {
+    /// Adiciona um ponto à série
+    pub fn push(&mut self, temperature: f64, value: f64) {
+        self.temperatures.push(temperature);
+        self.values.push(value);
+    }
+
+    /// Número de pontos
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Indica se a série está vazia
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Retorna a série restrita ao intervalo de temperatura informado
+    fn filtered(&self, min_temperature: Option<f64>, max_temperature: Option<f64>) -> PropertySeries {
+        let mut series = PropertySeries::default();
+        for (&t, &v) in self.temperatures.iter().zip(self.values.iter()) {
+            if min_temperature.is_none_or(|min| t >= min) && max_temperature.is_none_or(|max| t <= max) {
+                series.push(t, v);
+            }
+        }
+        series
+    }
+}
+
+/// Estrutura que representa uma transição de fase identificada no arquivo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseTransition {
+    /// Temperatura da transição (°C)
+    pub temperature: f64,
+    /// Calor latente (J/kg)
+    pub latent_heat: f64,
+}
+
+/// Estrutura que representa as propriedades importadas de um arquivo
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportedProperties {
+    /// Nome da substância, se informado no arquivo
+    pub name: Option<String>,
+    /// Capacidade térmica específica (J/(kg·K))
+    pub specific_heat: PropertySeries,
+    /// Entalpia relativa a 298,15 K (J/kg)
+    pub enthalpy: PropertySeries,
+    /// Condutividade térmica (W/(m·K))
+    pub thermal_conductivity: PropertySeries,
+    /// Densidade (kg/m³)
+    pub density: PropertySeries,
+    /// Transições de fase em ordem crescente de temperatura
+    pub transitions: Vec<PhaseTransition>,
+}
+
+/// Estrutura que representa um ajuste polinomial no formato de `MaterialProperties`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolynomialFit {
+    /// Coeficientes c0..cn em t = (T - T_ref) / 100
+    pub coefficients: Vec<f64>,
+    /// Maior erro relativo nos pontos tabelados
+    pub max_relative_error: f64,
+}
+
+/// Avalia um polinômio no formato de `MaterialProperties`
+fn evaluate_polynomial(coefficients: &[f64], temperature: f64, reference_temperature: f64) -> f64 {
+    let t_norm = (temperature - reference_temperature) / 100.0;
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * t_norm + c)
+}
+
+/// Ajusta por mínimos quadrados um polinômio em t = (T - T_ref) / 100
+///
+/// O grau é reduzido quando a série não tem pontos suficientes.
+pub fn fit_polynomial(series: &PropertySeries, reference_temperature: f64, degree: usize) -> Result<PolynomialFit, String> {
+    if series.is_empty() {
+        return Err("Série vazia: nenhum ponto para o ajuste polinomial".to_string());
+    }
+    let n = degree.min(series.len() - 1) + 1;
+
+    // Equações normais (AᵀA)·c = Aᵀy
+    let mut matrix = vec![vec![0.0; n + 1]; n];
+    for (&t, &y) in series.temperatures.iter().zip(series.values.iter()) {
+        let t_norm = (t - reference_temperature) / 100.0;
+        let powers: Vec<f64> = (0..n).map(|i| t_norm.powi(i as i32)).collect();
+        for row in 0..n {
+            for col in 0..n {
+                matrix[row][col] += powers[row] * powers[col];
+            }
+            matrix[row][n] += powers[row] * y;
+        }
+    }
+
+    // Eliminação de Gauss com pivoteamento parcial
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&a, &b| matrix[a][col].abs().partial_cmp(&matrix[b][col].abs()).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(col);
+        if matrix[pivot][col].abs() < 1e-300 {
+            return Err("Ajuste polinomial mal condicionado (temperaturas repetidas?)".to_string());
+        }
+        matrix.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = matrix[row][col] / matrix[col][col];
+            let (pivot_rows, lower_rows) = matrix.split_at_mut(row);
+            for (value, &pivot_value) in lower_rows[0][col..=n].iter_mut().zip(&pivot_rows[col][col..=n]) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+    let mut coefficients = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| matrix[row][k] * coefficients[k]).sum();
+        coefficients[row] = (matrix[row][n] - sum) / matrix[row][row];
+    }
+
+    let max_relative_error = series.temperatures.iter().zip(series.values.iter())
+        .filter(|(_, y)| y.abs() > 1e-12)
+        .map(|(&t, &y)| ((evaluate_polynomial(&coefficients, t, reference_temperature) - y) / y).abs())
+        .fold(0.0, f64::max);
+
+    Ok(PolynomialFit { coefficients, max_relative_error })
+}
+
+impl ImportedProperties {
+    /// Aplica as propriedades importadas a um material
+    ///
+    /// Cada propriedade disponível é ajustada como polinômio; a primeira transição
+    /// define a fusão e a segunda a vaporização. Retorna os ajustes realizados.
+    pub fn apply_to(&self, material: &mut MaterialProperties, options: &PropertyImportOptions) -> Result<Vec<(String, PolynomialFit)>, String> {
+        let t_ref = options.reference_temperature;
+        let has_other_coefficients = (self.specific_heat.is_empty() && material.specific_heat_coefficients.is_some())
+            || (self.thermal_conductivity.is_empty() && material.thermal_conductivity_coefficients.is_some())
+            || (self.density.is_empty() && material.density_coefficients.is_some());
+        if let Some(existing) = material.reference_temperature {
+            if has_other_coefficients && (existing - t_ref).abs() > 1e-9 {
+                return Err(format!(
+                    "Temperatura de referência {} °C difere da existente no material ({} °C)",
+                    t_ref, existing
+                ));
+            }
+        }
+
+        let mut fits = Vec::new();
+        let mut fit = |label: &str, series: &PropertySeries| -> Result<Option<Vec<f64>>, String> {
+            let series = series.filtered(options.min_temperature, options.max_temperature);
+            if series.is_empty() {
+                return Ok(None);
+            }
+            let result = fit_polynomial(&series, t_ref, options.polynomial_degree)?;
+            if result.max_relative_error > FIT_WARNING_THRESHOLD {
+                warn!("Ajuste de {} com erro relativo de {:.1}%", label, result.max_relative_error * 100.0);
+            }
+            let coefficients = result.coefficients.clone();
+            fits.push((label.to_string(), result));
+            Ok(Some(coefficients))
+        };
+
+        if let Some(coefficients) = fit("specific_heat", &self.specific_heat)? {
+            material.specific_heat = evaluate_polynomial(&coefficients, t_ref, t_ref).max(0.0);
+            material.specific_heat_coefficients = Some(coefficients);
+        }
+        if let Some(coefficients) = fit("thermal_conductivity", &self.thermal_conductivity)? {
+            material.thermal_conductivity = evaluate_polynomial(&coefficients, t_ref, t_ref).max(0.0);
+            material.thermal_conductivity_coefficients = Some(coefficients);
+        }
+        if let Some(coefficients) = fit("density", &self.density)? {
+            material.density = evaluate_polynomial(&coefficients, t_ref, t_ref).max(0.0);
+            material.density_coefficients = Some(coefficients);
+        }
+        if !fits.is_empty() {
+            material.reference_temperature = Some(t_ref);
+        }
+
+        if let Some(fusion) = self.transitions.first() {
+            material.melting_point = Some(fusion.temperature);
+            material.latent_heat_fusion = Some(fusion.latent_heat);
+        }
+        if let Some(vaporization) = self.transitions.get(1) {
+            material.vaporization_point = Some(vaporization.temperature);
+            material.latent_heat_vaporization = Some(vaporization.latent_heat);
+        }
+        if let Some(name) = &self.name {
+            if material.name.is_empty() {
+                material.name = name.clone();
+            }
+        }
+
+        Ok(fits)
+    }
+}
+
+/// Importa as propriedades de um arquivo
+pub fn import_property_file(path: &Path, options: &PropertyImportOptions) -> Result<ImportedProperties, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Erro ao ler arquivo de propriedades {:?}: {}", path, e))?;
+    let format = match options.format {
+        Some(format) => format,
+        None => PropertyFileFormat::detect(path, &content)
+            .ok_or_else(|| format!("Formato do arquivo de propriedades {:?} não reconhecido", path))?,
+    };
+    parse_property_data(&content, format, options)
+}
+
+/// Cria um material a partir de um arquivo de propriedades, partindo de um material base
+///
+/// Propriedades ausentes no arquivo (p. ex. condutividade em tabelas JANAF) são
+/// mantidas do material base.
+pub fn material_from_property_file(path: &Path, base: &MaterialProperties, options: &PropertyImportOptions) -> Result<MaterialProperties, String> {
+    let imported = import_property_file(path, options)?;
+    let mut material = base.clone();
+    imported.apply_to(&mut material, options)?;
+    Ok(material)
+}
+
+/// Interpreta o conteúdo de um arquivo de propriedades no formato especificado
+pub fn parse_property_data(content: &str, format: PropertyFileFormat, options: &PropertyImportOptions) -> Result<ImportedProperties, String> {
+    let imported = match format {
+        PropertyFileFormat::Janaf => parse_janaf(content, options)?,
+        PropertyFileFormat::Shomate => parse_shomate(content, options)?,
+        PropertyFileFormat::ThermoMl => parse_thermoml(content, options)?,
+    };
+    if imported.specific_heat.is_empty() && imported.thermal_conductivity.is_empty()
+        && imported.density.is_empty() && imported.transitions.is_empty()
+    {
+        return Err("Nenhuma propriedade reconhecida no arquivo".to_string());
+    }
+    Ok(imported)
+}
+
+/// Obtém a massa molar das opções, exigida para dados molares
+fn required_molar_mass(options: &PropertyImportOptions, source: &str) -> Result<f64, String> {
+    match options.molar_mass {
+        Some(m) if m > 0.0 => Ok(m),
+        Some(_) => Err("Massa molar deve ser positiva".to_string()),
+        None => Err(format!("Massa molar necessária para converter valores molares ({})", source)),
+    }
+}
+
+/// Interpreta uma tabela NIST-JANAF
+///
+/// Cp está em J/(K·mol) e H-H(Tr) em kJ/mol; linhas repetidas na mesma temperatura
+/// indicam uma transição de fase, cujo calor latente é o salto de entalpia.
+fn parse_janaf(content: &str, options: &PropertyImportOptions) -> Result<ImportedProperties, String> {
+    let molar_mass = required_molar_mass(options, "JANAF")?;
+    let mut imported = ImportedProperties::default();
+    let mut in_table = false;
+    let mut previous: Option<(f64, f64)> = None; // (T em K, H em kJ/mol)
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        if !in_table {
+            if line.trim_start().starts_with("T(K)") {
+                in_table = true;
+            } else if imported.name.is_none() {
+                imported.name = line.split('\t').next().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+            }
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split(|c: char| c == '\t' || c.is_whitespace()).filter(|t| !t.is_empty()).collect();
+        let number = |index: usize| tokens.get(index).and_then(|t| t.parse::<f64>().ok());
+        let (temperature_k, cp, enthalpy) = match (number(0), number(1), number(4)) {
+            (Some(t), Some(cp), Some(h)) => (t, cp, h),
+            _ => continue,
+        };
+        let temperature = temperature_k - KELVIN_OFFSET;
+
+        if let Some((previous_t, previous_h)) = previous {
+            if (temperature_k - previous_t).abs() < 1e-9 {
+                let latent_heat = (enthalpy - previous_h) * 1000.0 / molar_mass;
+                if latent_heat > 0.0 {
+                    imported.transitions.push(PhaseTransition { temperature, latent_heat });
+                }
+                previous = Some((temperature_k, enthalpy));
+                continue;
+            }
+        }
+        previous = Some((temperature_k, enthalpy));
+
+        if temperature_k > 0.0 && cp > 0.0 {
+            imported.specific_heat.push(temperature, cp / molar_mass);
+            imported.enthalpy.push(temperature, enthalpy * 1000.0 / molar_mass);
+        }
+    }
+
+    if !in_table {
+        return Err("Cabeçalho 'T(K)' da tabela JANAF não encontrado".to_string());
+    }
+    Ok(imported)
+}
+
+/// Coeficientes de Shomate válidos em um intervalo de temperatura
+struct ShomateRange {
+    /// Temperatura mínima (K)
+    t_min: f64,
+    /// Temperatura máxima (K)
+    t_max: f64,
+    /// Coeficientes A..H
+    coefficients: [f64; 8],
+}
+
+impl ShomateRange {
+    /// Capacidade térmica molar (J/(mol·K))
+    fn cp(&self, temperature_k: f64) -> f64 {
+        let [a, b, c, d, e, ..] = self.coefficients;
+        let t = temperature_k / 1000.0;
+        a + b * t + c * t * t + d * t.powi(3) + e / (t * t)
+    }
+
+    /// Entalpia H - H(298,15 K) (kJ/mol)
+    fn enthalpy(&self, temperature_k: f64) -> f64 {
+        let [a, b, c, d, e, f, _, h] = self.coefficients;
+        let t = temperature_k / 1000.0;
+        a * t + b * t * t / 2.0 + c * t.powi(3) / 3.0 + d * t.powi(4) / 4.0 - e / t + f - h
+    }
+}
+
+/// Interpreta coeficientes de Shomate no formato de tabela do NIST WebBook
+///
+/// A linha "Temperature (K)" lista os intervalos ("298. to 2327.") e as linhas A..H
+/// os coeficientes de cada intervalo. Cp é amostrado em cada intervalo e saltos de
+/// entalpia entre intervalos adjacentes são registrados como transições de fase.
+fn parse_shomate(content: &str, options: &PropertyImportOptions) -> Result<ImportedProperties, String> {
+    let molar_mass = required_molar_mass(options, "Shomate")?;
+    let mut imported = ImportedProperties::default();
+    let mut ranges: Vec<(f64, f64)> = Vec::new();
+    let mut coefficients: Vec<[f64; 8]> = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("Temperature (K)") {
+            let tokens: Vec<&str> = rest.split_whitespace().collect();
+            for window in tokens.windows(3) {
+                if window[1] == "to" {
+                    if let (Ok(a), Ok(b)) = (window[0].trim_end_matches('.').parse::<f64>(), window[2].trim_end_matches('.').parse::<f64>()) {
+                        ranges.push((a, b));
+                    }
+                }
+            }
+            coefficients = vec![[0.0; 8]; ranges.len()];
+            continue;
+        }
+
+        let mut tokens = trimmed.split_whitespace();
+        let label = match tokens.next() {
+            Some(label) => label,
+            None => continue,
+        };
+        let index = match label {
+            "A" => 0, "B" => 1, "C" => 2, "D" => 3, "E" => 4, "F" => 5, "G" => 6, "H" => 7,
+            _ => {
+                if ranges.is_empty() && imported.name.is_none() && !trimmed.is_empty() {
+                    imported.name = Some(trimmed.to_string());
+                }
+                continue;
+            }
+        };
+        for (range, token) in tokens.enumerate().take(coefficients.len()) {
+            coefficients[range][index] = token.parse::<f64>()
+                .map_err(|e| format!("Coeficiente de Shomate {} inválido '{}': {}", label, token, e))?;
+        }
+    }
+
+    if ranges.is_empty() {
+        return Err("Linha 'Temperature (K)' com os intervalos de Shomate não encontrada".to_string());
+    }
+    let ranges: Vec<ShomateRange> = ranges.into_iter().zip(coefficients)
+        .map(|((t_min, t_max), coefficients)| ShomateRange { t_min, t_max, coefficients })
+        .collect();
+
+    const SAMPLES_PER_RANGE: usize = 20;
+    for range in &ranges {
+        for i in 0..=SAMPLES_PER_RANGE {
+            let temperature_k = range.t_min + (range.t_max - range.t_min) * i as f64 / SAMPLES_PER_RANGE as f64;
+            imported.specific_heat.push(temperature_k - KELVIN_OFFSET, range.cp(temperature_k) / molar_mass);
+            imported.enthalpy.push(temperature_k - KELVIN_OFFSET, range.enthalpy(temperature_k) * 1000.0 / molar_mass);
+        }
+    }
+    for pair in ranges.windows(2) {
+        let boundary = pair[0].t_max;
+        let latent_heat = (pair[1].enthalpy(boundary) - pair[0].enthalpy(boundary)) * 1000.0 / molar_mass;
+        // Saltos pequenos são apenas descontinuidades do ajuste, não transições
+        if latent_heat > 1000.0 {
+            imported.transitions.push(PhaseTransition { temperature: boundary - KELVIN_OFFSET, latent_heat });
+        }
+    }
+
+    Ok(imported)
+}
+
+/// Retorna o conteúdo interno de todos os elementos XML com a marcação informada
+fn xml_elements<'a>(content: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut elements = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // Garantir que a marcação não é apenas um prefixo de outra (p. ex. <Property-MethodID>)
+        if !after.starts_with(|c: char| c == '>' || c.is_whitespace()) {
+            rest = after;
+            continue;
+        }
+        let body_start = match after.find('>') {
+            Some(i) => i + 1,
+            None => break,
+        };
+        let body = &after[body_start..];
+        match body.find(&close) {
+            Some(end) => {
+                elements.push(&body[..end]);
+                rest = &body[end + close.len()..];
+            }
+            None => break,
+        }
+    }
+    elements
+}
+
+/// Retorna o texto do primeiro elemento XML com a marcação informada
+fn xml_text<'a>(content: &'a str, tag: &str) -> Option<&'a str> {
+    xml_elements(content, tag).first().map(|s| s.trim())
+}
+
+/// Propriedades ThermoML reconhecidas (nome ePropName e conversão para SI por massa)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ThermoMlProperty {
+    SpecificHeat,
+    MolarHeatCapacity,
+    ThermalConductivity,
+    MassDensity,
+    MeltingTemperature,
+    MolarEnthalpyOfFusion,
+}
+
+impl ThermoMlProperty {
+    /// Identifica a propriedade pelo nome ePropName
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "Specific heat capacity at constant pressure, J/K/kg" => Some(ThermoMlProperty::SpecificHeat),
+            "Molar heat capacity at constant pressure, J/K/mol" => Some(ThermoMlProperty::MolarHeatCapacity),
+            "Thermal conductivity, W/m/K" => Some(ThermoMlProperty::ThermalConductivity),
+            "Mass density, kg/m3" => Some(ThermoMlProperty::MassDensity),
+            "Normal melting temperature, K" | "Triple point temperature, K" => Some(ThermoMlProperty::MeltingTemperature),
+            "Molar enthalpy of transition or fusion, kJ/mol" | "Molar enthalpy of fusion, kJ/mol" => Some(ThermoMlProperty::MolarEnthalpyOfFusion),
+            _ => None,
+        }
+    }
+}
+
+/// Interpreta um arquivo ThermoML (IUPAC)
+///
+/// Apenas conjuntos `PureOrMixtureData` de um componente são considerados. A
+/// temperatura deve ser a variável "Temperature, K"; demais variáveis (p. ex.
+/// pressão) são ignoradas.
+fn parse_thermoml(content: &str, options: &PropertyImportOptions) -> Result<ImportedProperties, String> {
+    let mut imported = ImportedProperties {
+        name: xml_text(content, "sCommonName").map(|s| s.to_string()),
+        ..Default::default()
+    };
+    let mut melting_temperature: Option<f64> = None;
+    let mut fusion_enthalpy: Option<f64> = None;
+
+    for data_set in xml_elements(content, "PureOrMixtureData") {
+        if xml_elements(data_set, "Component").len() > 1 {
+            continue;
+        }
+
+        let properties: Vec<(String, ThermoMlProperty)> = xml_elements(data_set, "Property").into_iter()
+            .filter_map(|p| {
+                let number = xml_text(p, "nPropNumber")?.to_string();
+                let kind = ThermoMlProperty::from_name(xml_text(p, "ePropName")?)?;
+                Some((number, kind))
+            })
+            .collect();
+        if properties.is_empty() {
+            continue;
+        }
+
+        let temperature_variable = xml_elements(data_set, "Variable").into_iter()
+            .find(|v| xml_text(v, "eTemperature").is_some_and(|t| t.starts_with("Temperature, K")))
+            .and_then(|v| xml_text(v, "nVarNumber").map(|s| s.to_string()));
+
+        for values in xml_elements(data_set, "NumValues") {
+            let temperature = temperature_variable.as_ref().and_then(|number| {
+                xml_elements(values, "VariableValue").into_iter()
+                    .find(|v| xml_text(v, "nVarNumber") == Some(number.as_str()))
+                    .and_then(|v| xml_text(v, "nVarValue")?.parse::<f64>().ok())
+                    .map(|t| t - KELVIN_OFFSET)
+            });
+
+            for value in xml_elements(values, "PropertyValue") {
+                let number = match xml_text(value, "nPropNumber") {
+                    Some(number) => number,
+                    None => continue,
+                };
+                let kind = match properties.iter().find(|(n, _)| n == number) {
+                    Some((_, kind)) => *kind,
+                    None => continue,
+                };
+                let raw = match xml_text(value, "nPropValue").and_then(|v| v.parse::<f64>().ok()) {
+                    Some(raw) => raw,
+                    None => continue,
+                };
+
+                match (kind, temperature) {
+                    (ThermoMlProperty::SpecificHeat, Some(t)) => imported.specific_heat.push(t, raw),
+                    (ThermoMlProperty::MolarHeatCapacity, Some(t)) => {
+                        imported.specific_heat.push(t, raw / required_molar_mass(options, "ThermoML")?);
+                    }
+                    (ThermoMlProperty::ThermalConductivity, Some(t)) => imported.thermal_conductivity.push(t, raw),
+                    (ThermoMlProperty::MassDensity, Some(t)) => imported.density.push(t, raw),
+                    (ThermoMlProperty::MeltingTemperature, _) => melting_temperature = Some(raw - KELVIN_OFFSET),
+                    (ThermoMlProperty::MolarEnthalpyOfFusion, _) => {
+                        fusion_enthalpy = Some(raw * 1000.0 / required_molar_mass(options, "ThermoML")?);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if let (Some(temperature), Some(latent_heat)) = (melting_temperature, fusion_enthalpy) {
+        imported.transitions.push(PhaseTransition { temperature, latent_heat });
+    }
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const ALUMINA_JANAF: &str = "Aluminum Oxide, Alpha (Al2O3)\tAl2O3(cr,l)\n\
+T(K)\tCp\tS\t-[G-H(Tr)]/T\tH-H(Tr)\tdelta-f H\tdelta-f G\tlog Kf\n\
+0\t0.\t0.\tINFINITE\t-10.020\t-1663.608\t-1663.608\tINFINITE\n\
+298.15\t79.033\t50.950\t50.950\t0.\t-1675.692\t-1582.275\t277.208\n\
+500\t109.602\t96.102\t62.154\t16.974\t-1676.927\t-1518.823\t158.669\n\
+1000\t125.077\t178.180\t102.242\t75.938\t-1675.767\t-1361.995\t71.142\n\
+2000\t134.230\t268.057\t165.596\t204.924\t-1691.104\t-1047.248\t27.352\n\
+2327\t136.720\t288.664\t181.085\t250.342\t-1687.564\t-946.252\t21.240\tCRYSTAL <--> LIQUID\n\
+2327\t192.464\t336.139\t181.085\t360.812\t-1577.094\t-946.252\t21.240\n\
+3000\t192.464\t385.010\t222.590\t490.342\t-1554.962\t-776.285\t13.516\n";
+
+    #[test]
+    fn test_janaf_import_with_transition() {
+        let options = PropertyImportOptions { molar_mass: Some(0.101961), ..Default::default() };
+        assert!(parse_property_data(ALUMINA_JANAF, PropertyFileFormat::Janaf, &PropertyImportOptions::default()).is_err());
+
+        let imported = parse_property_data(ALUMINA_JANAF, PropertyFileFormat::Janaf, &options).unwrap();
+        assert_eq!(imported.name.as_deref(), Some("Aluminum Oxide, Alpha (Al2O3)"));
+        assert_eq!(imported.specific_heat.len(), 6);
+        assert_eq!(imported.transitions.len(), 1);
+        assert_relative_eq!(imported.transitions[0].temperature, 2053.85, epsilon = 1e-9);
+        assert_relative_eq!(imported.transitions[0].latent_heat, 110.47 * 1000.0 / 0.101961, max_relative = 1e-9);
+
+        // Ajuste apenas da fase sólida
+        let options = PropertyImportOptions { max_temperature: Some(2000.0), ..options };
+        let mut material = MaterialProperties::new("Alumina", 3950.0, 880.0, 30.0);
+        let fits = imported.apply_to(&mut material, &options).unwrap();
+        assert_eq!(fits.len(), 1);
+        assert_eq!(material.melting_point, Some(imported.transitions[0].temperature));
+        assert_relative_eq!(material.get_specific_heat(24.85), 79.033 / 0.101961, max_relative = 0.1);
+        assert!(material.get_specific_heat(1726.85) > material.get_specific_heat(226.85));
+        // Propriedades ausentes na tabela são preservadas
+        assert_relative_eq!(material.thermal_conductivity, 30.0);
+    }
+
+    #[test]
+    fn test_shomate_import() {
+        // Coeficientes do NIST WebBook para Al2O3 (sólido e líquido)
+        let content = "Aluminum oxide\n\
+Temperature (K)\t298. to 2327.\t2327. to 4000.\n\
+A\t102.4290\t192.4640\n\
+B\t38.74980\t9.519856E-08\n\
+C\t-15.91090\t-2.858928E-08\n\
+D\t2.628181\t2.929147E-09\n\
+E\t-3.007551\t5.599405E-08\n\
+F\t-1717.930\t-1757.711\n\
+G\t146.9970\t208.8664\n\
+H\t-1675.690\t-1620.568\n";
+        let options = PropertyImportOptions { molar_mass: Some(0.101961), ..Default::default() };
+        let path = Path::new("alumina.txt");
+        assert_eq!(PropertyFileFormat::detect(path, content), Some(PropertyFileFormat::Shomate));
+
+        let imported = parse_property_data(content, PropertyFileFormat::Shomate, &options).unwrap();
+        assert_eq!(imported.name.as_deref(), Some("Aluminum oxide"));
+        assert_eq!(imported.transitions.len(), 1);
+        assert_relative_eq!(imported.transitions[0].temperature, 2053.85, epsilon = 1e-9);
+
+        // Cp a 298,15 K ≈ 79 J/(mol·K)
+        let cp_298 = imported.specific_heat.values[0] * 0.101961;
+        assert_relative_eq!(cp_298, 79.0, epsilon = 0.5);
+        // Entalpia nula na referência de 298,15 K
+        assert!(imported.enthalpy.values[0].abs() * 0.101961 < 100.0);
+    }
+
+    #[test]
+    fn test_thermoml_import() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DataReport xmlns="http://www.iupac.org/namespaces/ThermoML">
+  <Compound><nOrgNum>1</nOrgNum><sCommonName>copper</sCommonName></Compound>
+  <PureOrMixtureData>
+    <Component><RegNum><nOrgNum>1</nOrgNum></RegNum></Component>
+    <Property>
+      <nPropNumber>1</nPropNumber>
+      <Property-MethodID><PropertyGroup><TransportProp>
+        <ePropName>Thermal conductivity, W/m/K</ePropName>
+      </TransportProp></PropertyGroup></Property-MethodID>
+    </Property>
+    <Property>
+      <nPropNumber>2</nPropNumber>
+      <Property-MethodID><PropertyGroup><VolumetricProp>
+        <ePropName>Mass density, kg/m3</ePropName>
+      </VolumetricProp></PropertyGroup></Property-MethodID>
+    </Property>
+    <Variable>
+      <nVarNumber>1</nVarNumber>
+      <VariableID><VariableType><eTemperature>Temperature, K</eTemperature></VariableType></VariableID>
+    </Variable>
+    <NumValues>
+      <VariableValue><nVarNumber>1</nVarNumber><nVarValue>300</nVarValue></VariableValue>
+      <PropertyValue><nPropNumber>1</nPropNumber><nPropValue>401</nPropValue></PropertyValue>
+      <PropertyValue><nPropNumber>2</nPropNumber><nPropValue>8933</nPropValue></PropertyValue>
+    </NumValues>
+    <NumValues>
+      <VariableValue><nVarNumber>1</nVarNumber><nVarValue>600</nVarValue></VariableValue>
+      <PropertyValue><nPropNumber>1</nPropNumber><nPropValue>379</nPropValue></PropertyValue>
+      <PropertyValue><nPropNumber>2</nPropNumber><nPropValue>8800</nPropValue></PropertyValue>
+    </NumValues>
+  </PureOrMixtureData>
+</DataReport>"#;
+        let options = PropertyImportOptions { polynomial_degree: 1, ..Default::default() };
+        assert_eq!(PropertyFileFormat::detect(Path::new("copper.dat"), content), Some(PropertyFileFormat::ThermoMl));
+
+        let imported = parse_property_data(content, PropertyFileFormat::ThermoMl, &options).unwrap();
+        assert_eq!(imported.name.as_deref(), Some("copper"));
+        assert_eq!(imported.thermal_conductivity.len(), 2);
+        assert_eq!(imported.density.len(), 2);
+
+        let mut material = MaterialProperties::new("", 8960.0, 385.0, 400.0);
+        imported.apply_to(&mut material, &options).unwrap();
+        assert_eq!(material.name, "copper");
+        assert_relative_eq!(material.get_thermal_conductivity(326.85), 379.0, epsilon = 1e-6);
+        assert_relative_eq!(material.get_density(26.85), 8933.0, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,226 @@
+// Implementação da reexecução incremental baseada na diferença de parâmetros
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, atomic::AtomicBool};
+
+use crate::simulation::physics::PlasmaTorch;
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Campos dos parâmetros que afetam apenas o pós-processamento e o armazenamento
+const POST_PROCESSING_FIELDS: &[&str] = &[
+    "energy_check_tolerance",
+    "frame_storage",
+    "temporal_pyramid_strides",
+];
+
+/// Campos tratados separadamente na comparação (tochas e duração da simulação)
+const TIMELINE_FIELDS: &[&str] = &["torches", "total_time", "time_steps"];
+
+/// Estrutura que representa o plano de reexecução após uma alteração de parâmetros
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerunPlan {
+    /// Passo a partir do qual a simulação precisa ser recalculada
+    ///
+    /// Igual ao número de passos da nova simulação quando nada precisa ser recalculado.
+    pub restart_step: usize,
+    /// Indica se a simulação precisa ser recalculada desde o início
+    pub full_recompute: bool,
+    /// Motivos identificados na comparação
+    pub reasons: Vec<String>,
+}
+
+impl RerunPlan {
+    /// Indica se algum passo precisa ser recalculado
+    pub fn requires_recompute(&self, new_params: &SimulationParameters) -> bool {
+        self.restart_step < new_params.time_steps
+    }
+}
+
+/// Remove os campos informados de um objeto JSON
+fn strip_fields(mut value: Value, fields: &[&str]) -> Value {
+    if let Value::Object(map) = &mut value {
+        for field in fields {
+            map.remove(*field);
+        }
+    }
+    value
+}
+
+/// Serializa o estado efetivo de uma tocha em um instante, sem programação nem degradação
+fn effective_torch_state(torch: &PlasmaTorch, time: f64) -> Value {
+    let mut effective = torch.at_time(time);
+    effective.power_schedule = None;
+    effective.degradation = None;
+    effective.operating_hours = 0.0;
+    serde_json::to_value(&effective).unwrap_or(Value::Null)
+}
+
+/// Compara parâmetros e determina o passo mais antigo afetado pela alteração
+///
+/// Alterações de pós-processamento não exigem recálculo; alterações apenas na
+/// programação das tochas exigem recálculo a partir do primeiro passo em que o
+/// estado efetivo de alguma tocha diverge; qualquer outra alteração física
+/// exige recálculo completo.
+pub fn plan_rerun(previous: &SimulationResults, new_params: &SimulationParameters) -> RerunPlan {
+    let old_params = &previous.parameters;
+    let mut reasons = Vec::new();
+    let full = |reason: String| RerunPlan {
+        restart_step: 0,
+        full_recompute: true,
+        reasons: vec![reason],
+    };
+
+    let ignored: Vec<&str> = POST_PROCESSING_FIELDS.iter().chain(TIMELINE_FIELDS.iter()).cloned().collect();
+    let old_value = serde_json::to_value(old_params).map(|v| strip_fields(v, &ignored));
+    let new_value = serde_json::to_value(new_params).map(|v| strip_fields(v, &ignored));
+    match (old_value, new_value) {
+        (Ok(old_value), Ok(new_value)) => {
+            if let (Value::Object(old_map), Value::Object(new_map)) = (&old_value, &new_value) {
+                let mut changed: Vec<&String> = new_map.keys()
+                    .filter(|key| old_map.get(*key) != new_map.get(*key))
+                    .collect();
+                changed.sort();
+                if !changed.is_empty() {
+                    return full(format!("Parâmetros físicos alterados: {:?}", changed));
+                }
+            }
+        }
+        _ => return full("Não foi possível comparar os parâmetros".to_string()),
+    }
+
+    if POST_PROCESSING_FIELDS.iter().any(|field| {
+        let old_field = serde_json::to_value(old_params).ok().and_then(|v| v.get(*field).cloned());
+        let new_field = serde_json::to_value(new_params).ok().and_then(|v| v.get(*field).cloned());
+        old_field != new_field
+    }) {
+        reasons.push("Configurações de pós-processamento alteradas".to_string());
+    }
+
+    // Tochas: mesma configuração, diferindo no máximo na programação temporal
+    if old_params.torches.len() != new_params.torches.len()
+        || old_params.torches.iter().zip(new_params.torches.iter()).any(|(a, b)| a.id != b.id)
+    {
+        return full("Conjunto de tochas alterado".to_string());
+    }
+
+    let dt = new_params.time_step;
+    let compared_steps = previous.executed_steps.min(new_params.time_steps);
+    let mut restart_step = compared_steps;
+    for step in 0..compared_steps {
+        let time = step as f64 * dt;
+        let diverges = old_params.torches.iter().zip(new_params.torches.iter())
+            .any(|(a, b)| effective_torch_state(a, time) != effective_torch_state(b, time));
+        if diverges {
+            reasons.push(format!("Programação das tochas diverge a partir de t = {:.1} s", time));
+            restart_step = step;
+            break;
+        }
+    }
+
+    if restart_step == compared_steps && new_params.time_steps > previous.executed_steps {
+        reasons.push(format!(
+            "Simulação estendida de {} para {} passos",
+            previous.executed_steps, new_params.time_steps
+        ));
+    }
+
+    RerunPlan {
+        restart_step,
+        full_recompute: restart_step == 0 && new_params.time_steps > 0,
+        reasons,
+    }
+}
+
+/// Reexecuta a simulação recalculando apenas a partir do passo mais antigo afetado
+///
+/// Retorna os novos resultados e o plano utilizado. Sem histórico em precisão total
+/// nos resultados anteriores, a simulação é recalculada desde o início.
+pub fn rerun_incremental(
+    previous: &SimulationResults,
+    new_params: SimulationParameters,
+    progress_callback: Option<&dyn Fn(f32) -> bool>,
+    cancel_flag: Arc<AtomicBool>,
+) -> Result<(SimulationResults, RerunPlan), String> {
+    let mut plan = plan_rerun(previous, &new_params);
+
+    if !previous.has_full_precision_history() && plan.restart_step > 0 {
+        plan.restart_step = 0;
+        plan.full_recompute = true;
+        plan.reasons.push("Resultados anteriores sem histórico em precisão total".to_string());
+    }
+
+    let mut solver = if plan.restart_step == 0 {
+        HeatSolver::new(new_params)?
+    } else {
+        HeatSolver::resume_from(new_params, previous, plan.restart_step)?
+    };
+    let results = solver.run(progress_callback, cancel_flag)?;
+
+    Ok((results, plan))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PowerSchedule;
+
+    fn create_test_parameters() -> SimulationParameters {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 6;
+        params.time_step = 1.0;
+        params.total_time = 6.0;
+        let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0);
+        torch.set_power_schedule(PowerSchedule::constant(10.0));
+        params.add_torch(torch);
+        params
+    }
+
+    fn run(params: SimulationParameters) -> SimulationResults {
+        HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap()
+    }
+
+    #[test]
+    fn test_plan_rerun_classifies_changes() {
+        let base = create_test_parameters();
+        let previous = run(base.clone());
+
+        // Apenas pós-processamento: nada a recalcular
+        let mut post = base.clone();
+        post.temporal_pyramid_strides = vec![2];
+        let plan = plan_rerun(&previous, &post);
+        assert!(!plan.requires_recompute(&post));
+        assert!(!plan.full_recompute);
+
+        // Mudança tardia na programação: recalcular a partir do passo 4
+        let mut late = base.clone();
+        late.torches[0].set_power_schedule(PowerSchedule::new(vec![(0.0, 10.0), (3.5, 10.0), (4.0, 20.0)]));
+        let plan = plan_rerun(&previous, &late);
+        assert_eq!(plan.restart_step, 4);
+
+        // Mudança física: recalcular tudo
+        let mut physics = base.clone();
+        physics.convection_coefficient = 20.0;
+        let plan = plan_rerun(&previous, &physics);
+        assert!(plan.full_recompute);
+        assert_eq!(plan.restart_step, 0);
+    }
+
+    #[test]
+    fn test_incremental_rerun_matches_full_run() {
+        let base = create_test_parameters();
+        let previous = run(base.clone());
+
+        let mut late = base.clone();
+        late.torches[0].set_power_schedule(PowerSchedule::new(vec![(0.0, 10.0), (3.5, 10.0), (4.0, 20.0)]));
+
+        let (incremental, plan) = rerun_incremental(&previous, late.clone(), None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert_eq!(plan.restart_step, 4);
+
+        let full = run(late);
+        assert_eq!(incremental.executed_steps, full.executed_steps);
+        for (a, b) in incremental.temperature.iter().zip(full.temperature.iter()) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+}
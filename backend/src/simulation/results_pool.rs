@@ -0,0 +1,278 @@
+// Implementação do conjunto de resultados em memória com orçamento e descarte LRU para disco
+
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::simulation::solver::SimulationResults;
+
+/// Estrutura que representa a configuração do conjunto de resultados
+#[derive(Debug, Clone)]
+pub struct ResultsPoolConfig {
+    /// Orçamento de memória para resultados carregados (bytes)
+    pub memory_budget: usize,
+    /// Diretório onde os resultados descartados da memória são gravados
+    pub spill_directory: PathBuf,
+}
+
+impl ResultsPoolConfig {
+    /// Cria uma nova configuração
+    pub fn new(memory_budget: usize, spill_directory: PathBuf) -> Self {
+        Self {
+            memory_budget,
+            spill_directory,
+        }
+    }
+}
+
+/// Enumeração que representa onde um conjunto de resultados está armazenado
+#[derive(Debug, Clone)]
+enum PoolStorage {
+    /// Carregado em memória
+    InMemory(Arc<SimulationResults>),
+    /// Gravado em disco
+    OnDisk(PathBuf),
+}
+
+/// Estrutura que representa uma entrada do conjunto de resultados
+#[derive(Debug, Clone)]
+struct PoolEntry {
+    /// Armazenamento atual
+    storage: PoolStorage,
+    /// Tamanho estimado em memória (bytes)
+    size: usize,
+    /// Contador do último acesso (para LRU)
+    last_access: u64,
+}
+
+/// Estima a memória ocupada por um conjunto de resultados (bytes)
+///
+/// Considera os campos volumosos (históricos e quadros); metadados são desprezados.
+pub fn estimate_results_size(results: &SimulationResults) -> usize {
+    let f64_size = std::mem::size_of::<f64>();
+    let mut size = (results.temperature.len() + results.enthalpy.len()) * f64_size;
+
+    if let Some(info) = &results.phase_change_info {
+        size += info.melt_fraction.as_ref().map_or(0, |a| a.len() * f64_size);
+        size += info.vapor_fraction.as_ref().map_or(0, |a| a.len() * f64_size);
+    }
+    if let Some(frames) = &results.playback_frames {
+        size += frames.memory_bytes();
+    }
+    if let Some(pyramid) = &results.temporal_pyramid {
+        size += pyramid.levels.iter().map(|l| l.frames.memory_bytes()).sum::<usize>();
+    }
+
+    size
+}
+
+/// Estrutura que representa o conjunto gerenciado de resultados abertos
+///
+/// Mantém os resultados mais recentemente usados em memória dentro do orçamento e
+/// grava os demais em disco, recarregando-os de forma transparente quando acessados.
+pub struct ResultsPool {
+    /// Configuração do conjunto
+    config: ResultsPoolConfig,
+    /// Entradas por identificador
+    entries: HashMap<String, PoolEntry>,
+    /// Contador monotônico de acessos
+    access_counter: u64,
+}
+
+impl ResultsPool {
+    /// Cria um novo conjunto de resultados
+    pub fn new(config: ResultsPoolConfig) -> Result<Self, String> {
+        fs::create_dir_all(&config.spill_directory)
+            .map_err(|e| format!("Erro ao criar diretório de descarte {:?}: {}", config.spill_directory, e))?;
+
+        Ok(Self {
+            config,
+            entries: HashMap::new(),
+            access_counter: 0,
+        })
+    }
+
+    /// Adiciona (ou substitui) um conjunto de resultados
+    pub fn insert(&mut self, id: &str, results: SimulationResults) -> Result<Arc<SimulationResults>, String> {
+        self.remove(id)?;
+
+        let size = estimate_results_size(&results);
+        let results = Arc::new(results);
+        self.access_counter += 1;
+        self.entries.insert(id.to_string(), PoolEntry {
+            storage: PoolStorage::InMemory(results.clone()),
+            size,
+            last_access: self.access_counter,
+        });
+
+        self.enforce_budget(id)?;
+        Ok(results)
+    }
+
+    /// Obtém um conjunto de resultados, recarregando do disco se necessário
+    pub fn get(&mut self, id: &str) -> Result<Arc<SimulationResults>, String> {
+        self.access_counter += 1;
+        let counter = self.access_counter;
+
+        let entry = self.entries.get_mut(id)
+            .ok_or_else(|| format!("Resultados '{}' não encontrados no conjunto", id))?;
+        entry.last_access = counter;
+
+        let results = match &entry.storage {
+            PoolStorage::InMemory(results) => return Ok(results.clone()),
+            PoolStorage::OnDisk(path) => {
+                let file = File::open(path)
+                    .map_err(|e| format!("Erro ao abrir resultados descartados {:?}: {}", path, e))?;
+                let results: SimulationResults = rmp_serde::from_read(BufReader::new(file))
+                    .map_err(|e| format!("Erro ao ler resultados descartados {:?}: {}", path, e))?;
+                if let Err(e) = fs::remove_file(path) {
+                    warn!("Erro ao remover arquivo de descarte {:?}: {}", path, e);
+                }
+                Arc::new(results)
+            }
+        };
+
+        info!("Resultados '{}' recarregados do disco", id);
+        entry.storage = PoolStorage::InMemory(results.clone());
+        self.enforce_budget(id)?;
+        Ok(results)
+    }
+
+    /// Remove um conjunto de resultados (e o arquivo de descarte, se houver)
+    pub fn remove(&mut self, id: &str) -> Result<bool, String> {
+        match self.entries.remove(id) {
+            Some(entry) => {
+                if let PoolStorage::OnDisk(path) = entry.storage {
+                    if let Err(e) = fs::remove_file(&path) {
+                        warn!("Erro ao remover arquivo de descarte {:?}: {}", path, e);
+                    }
+                }
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Indica se um conjunto de resultados está carregado em memória
+    pub fn is_in_memory(&self, id: &str) -> bool {
+        matches!(self.entries.get(id).map(|e| &e.storage), Some(PoolStorage::InMemory(_)))
+    }
+
+    /// Retorna os identificadores dos resultados no conjunto
+    pub fn ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.entries.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Memória estimada ocupada pelos resultados carregados (bytes)
+    pub fn memory_usage(&self) -> usize {
+        self.entries.values()
+            .filter(|e| matches!(e.storage, PoolStorage::InMemory(_)))
+            .map(|e| e.size)
+            .sum()
+    }
+
+    /// Grava em disco os resultados menos recentemente usados até respeitar o orçamento
+    ///
+    /// A entrada `keep` (a recém-acessada) nunca é descartada, mesmo que sozinha
+    /// exceda o orçamento.
+    fn enforce_budget(&mut self, keep: &str) -> Result<(), String> {
+        while self.memory_usage() > self.config.memory_budget {
+            let victim = self.entries.iter()
+                .filter(|(id, e)| id.as_str() != keep && matches!(e.storage, PoolStorage::InMemory(_)))
+                .min_by_key(|(_, e)| e.last_access)
+                .map(|(id, _)| id.clone());
+
+            match victim {
+                Some(id) => self.spill(&id)?,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Grava um conjunto de resultados em disco e libera a memória
+    fn spill(&mut self, id: &str) -> Result<(), String> {
+        let path = self.spill_path(id);
+        let entry = match self.entries.get_mut(id) {
+            Some(entry) => entry,
+            None => return Ok(()),
+        };
+
+        if let PoolStorage::InMemory(results) = &entry.storage {
+            let file = File::create(&path)
+                .map_err(|e| format!("Erro ao criar arquivo de descarte {:?}: {}", path, e))?;
+            let mut writer = BufWriter::new(file);
+            rmp_serde::encode::write_named(&mut writer, results.as_ref())
+                .map_err(|e| format!("Erro ao gravar resultados '{}' em disco: {}", id, e))?;
+
+            info!("Resultados '{}' ({} bytes) descartados da memória para {:?}", id, entry.size, path);
+            entry.storage = PoolStorage::OnDisk(path);
+        }
+        Ok(())
+    }
+
+    /// Caminho do arquivo de descarte de um identificador
+    fn spill_path(&self, id: &str) -> PathBuf {
+        let safe_id: String = id.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.config.spill_directory.join(format!("{}.results.msgpack", safe_id))
+    }
+}
+
+impl Drop for ResultsPool {
+    fn drop(&mut self) {
+        for entry in self.entries.values() {
+            if let PoolStorage::OnDisk(path) = &entry.storage {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array3;
+    use crate::simulation::solver::SimulationParameters;
+
+    fn create_test_results(value: f64) -> SimulationResults {
+        let temperature = Array3::<f64>::from_elem((4, 4, 8), value);
+        SimulationResults::for_tests(SimulationParameters::new(1.0, 0.5, 4, 4), temperature)
+    }
+
+    #[test]
+    fn test_results_pool_lru_eviction() {
+        let size = estimate_results_size(&create_test_results(0.0));
+        let directory = std::env::temp_dir().join(format!("results_pool_test_{}", std::process::id()));
+        let mut pool = ResultsPool::new(ResultsPoolConfig::new(2 * size, directory.clone())).unwrap();
+
+        pool.insert("run_a", create_test_results(1.0)).unwrap();
+        pool.insert("run_b", create_test_results(2.0)).unwrap();
+        pool.get("run_a").unwrap();
+        pool.insert("run_c", create_test_results(3.0)).unwrap();
+
+        // run_b foi o menos recentemente usado
+        assert!(pool.is_in_memory("run_a"));
+        assert!(!pool.is_in_memory("run_b"));
+        assert!(pool.is_in_memory("run_c"));
+        assert!(pool.memory_usage() <= 2 * size);
+
+        // Recarregar run_b descarta o menos recente (run_a)
+        let reloaded = pool.get("run_b").unwrap();
+        assert_eq!(reloaded.temperature[[0, 0, 0]], 2.0);
+        assert!(!pool.is_in_memory("run_a"));
+
+        assert!(pool.remove("run_a").unwrap());
+        assert!(pool.get("run_a").is_err());
+        assert_eq!(pool.ids(), vec!["run_b".to_string(), "run_c".to_string()]);
+
+        drop(pool);
+        let _ = fs::remove_dir_all(directory);
+    }
+}
@@ -0,0 +1,205 @@
+// Implementação de modelos de cenários operacionais (partida, parada e desarme)
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::alarms::{AlarmCondition, AlarmRule, AlarmSeverity};
+use crate::simulation::materials::MaterialLibrary;
+use crate::simulation::physics::{PlasmaTorch, PowerSchedule};
+use crate::simulation::solver::SimulationParameters;
+
+/// Estrutura que representa um modelo de cenário operacional pronto para execução
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioTemplate {
+    /// Identificador do cenário
+    pub id: String,
+    /// Nome do cenário
+    pub name: String,
+    /// Descrição do cenário
+    pub description: String,
+    /// Parâmetros da simulação (incluindo programação de potência das tochas)
+    pub parameters: SimulationParameters,
+    /// Alarmes avaliados sobre os resultados
+    pub alarms: Vec<AlarmRule>,
+}
+
+/// Cria os parâmetros base compartilhados pelos cenários operacionais
+fn create_base_parameters(total_time: f64, time_step: f64) -> SimulationParameters {
+    let mut params = SimulationParameters::new(2.0, 1.0, 20, 40);
+
+    // Revestimento refratário como material principal
+    let library = MaterialLibrary::new();
+    if let Some(refractory) = library.get_material_clone("concrete") {
+        params.set_material(refractory);
+    }
+
+    params.total_time = total_time;
+    params.time_step = time_step;
+    params.time_steps = (total_time / time_step).round() as usize;
+    params.initial_temperature = 25.0;
+    params.ambient_temperature = 25.0;
+    params.convection_coefficient = 10.0;
+    params.enable_convection = true;
+    params.enable_radiation = true;
+    params
+}
+
+/// Cria uma tocha central apontada para o leito
+fn create_central_torch(id: &str, power: f64) -> PlasmaTorch {
+    PlasmaTorch::new(id, 0.0, 0.0, 1.8, 180.0, 0.0, power, 0.02, 5000.0)
+}
+
+/// Cria o cenário de partida a frio com limites de aquecimento do refratário
+///
+/// A potência sobe em degraus com patamares de encharque para respeitar a taxa
+/// máxima de aquecimento do revestimento.
+pub fn create_cold_start_template() -> ScenarioTemplate {
+    let mut params = create_base_parameters(8.0 * 3600.0, 60.0);
+
+    let mut torch = create_central_torch("torch1", 300.0);
+    torch.set_power_schedule(PowerSchedule::new(vec![
+        (0.0, 0.0),
+        (1800.0, 50.0),    // Rampa inicial até o primeiro patamar
+        (5400.0, 50.0),    // Encharque para secagem do refratário
+        (9000.0, 150.0),   // Segunda rampa
+        (16200.0, 150.0),  // Segundo encharque
+        (21600.0, 300.0),  // Rampa até a potência nominal
+    ]));
+    params.add_torch(torch);
+
+    let alarms = vec![
+        AlarmRule::new(
+            "refractory_heating_rate",
+            "Taxa de aquecimento do refratário acima de 50 °C/h",
+            AlarmCondition::HeatingRateAbove(50.0),
+            AlarmSeverity::Warning,
+        ),
+        AlarmRule::new(
+            "refractory_overtemperature",
+            "Temperatura do refratário acima de 1600 °C",
+            AlarmCondition::MaxTemperatureAbove(1600.0),
+            AlarmSeverity::Critical,
+        ),
+    ];
+
+    ScenarioTemplate {
+        id: "cold_start".to_string(),
+        name: "Partida a Frio".to_string(),
+        description: "Aquecimento a partir da temperatura ambiente com rampas e patamares de encharque limitados pela taxa de aquecimento do refratário".to_string(),
+        parameters: params,
+        alarms,
+    }
+}
+
+/// Cria o cenário de parada controlada
+///
+/// Parte de um forno em regime e reduz a potência gradualmente para limitar a
+/// taxa de resfriamento do revestimento.
+pub fn create_controlled_shutdown_template() -> ScenarioTemplate {
+    let mut params = create_base_parameters(6.0 * 3600.0, 60.0);
+    params.initial_temperature = 1200.0;
+
+    let mut torch = create_central_torch("torch1", 300.0);
+    torch.set_power_schedule(PowerSchedule::new(vec![
+        (0.0, 300.0),
+        (3600.0, 200.0),
+        (10800.0, 50.0),
+        (14400.0, 0.0),
+    ]));
+    params.add_torch(torch);
+
+    let alarms = vec![
+        AlarmRule::new(
+            "refractory_cooling_rate",
+            "Taxa de resfriamento do refratário acima de 100 °C/h",
+            AlarmCondition::CoolingRateAbove(100.0),
+            AlarmSeverity::Warning,
+        ),
+    ];
+
+    ScenarioTemplate {
+        id: "controlled_shutdown".to_string(),
+        name: "Parada Controlada".to_string(),
+        description: "Redução gradual da potência a partir do regime para limitar o choque térmico no revestimento".to_string(),
+        parameters: params,
+        alarms,
+    }
+}
+
+/// Cria o cenário de desarme de emergência (perda de tocha)
+///
+/// Duas tochas operam em regime e uma delas é desligada instantaneamente após
+/// 30 minutos; a outra permanece em potência nominal.
+pub fn create_emergency_trip_template() -> ScenarioTemplate {
+    let mut params = create_base_parameters(2.0 * 3600.0, 30.0);
+    params.initial_temperature = 1200.0;
+
+    let mut tripped = PlasmaTorch::new("torch1", 0.4, 0.0, 1.8, 180.0, 0.0, 200.0, 0.02, 5000.0);
+    tripped.set_power_schedule(PowerSchedule::new(vec![
+        (0.0, 200.0),
+        (1800.0, 200.0),
+        (1800.0 + 1.0, 0.0), // Desarme
+    ]));
+    params.add_torch(tripped);
+    params.add_torch(PlasmaTorch::new("torch2", 0.4, 180.0, 1.8, 180.0, 0.0, 200.0, 0.02, 5000.0));
+
+    let alarms = vec![
+        AlarmRule::new(
+            "bed_freeze",
+            "Temperatura média do leito abaixo de 900 °C",
+            AlarmCondition::MeanTemperatureBelow(900.0),
+            AlarmSeverity::Critical,
+        ),
+        AlarmRule::new(
+            "thermal_shock",
+            "Taxa de resfriamento acima de 200 °C/h",
+            AlarmCondition::CoolingRateAbove(200.0),
+            AlarmSeverity::Warning,
+        ),
+    ];
+
+    ScenarioTemplate {
+        id: "emergency_trip".to_string(),
+        name: "Desarme de Emergência".to_string(),
+        description: "Perda súbita de uma das tochas durante a operação em regime".to_string(),
+        parameters: params,
+        alarms,
+    }
+}
+
+/// Retorna todos os modelos de cenários disponíveis
+pub fn get_scenario_templates() -> Vec<ScenarioTemplate> {
+    vec![
+        create_cold_start_template(),
+        create_controlled_shutdown_template(),
+        create_emergency_trip_template(),
+    ]
+}
+
+/// Retorna um modelo de cenário pelo identificador
+pub fn get_scenario_template(id: &str) -> Option<ScenarioTemplate> {
+    get_scenario_templates().into_iter().find(|t| t.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenario_templates_are_valid() {
+        let templates = get_scenario_templates();
+        assert_eq!(templates.len(), 3);
+
+        for template in &templates {
+            assert!(template.parameters.validate().is_ok(), "Cenário inválido: {}", template.id);
+            assert!(!template.alarms.is_empty());
+        }
+
+        // A tocha desarmada deve estar desligada ao fim do cenário de emergência
+        let trip = get_scenario_template("emergency_trip").unwrap();
+        let torch = &trip.parameters.torches[0];
+        assert_eq!(torch.at_time(3600.0).power, 0.0);
+        assert_eq!(trip.parameters.torches[1].at_time(3600.0).power, 200.0);
+
+        assert!(get_scenario_template("unknown").is_none());
+    }
+}
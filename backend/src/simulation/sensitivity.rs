@@ -0,0 +1,248 @@
+// Implementação da sensibilidade local de uma simulação por diferenças finitas
+
+use log::info;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+use crate::simulation::sensor_placement::{apply_key_parameter, key_parameter_value, KeyParameter};
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Enumeração que representa uma métrica escalar dos resultados
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SensitivityMetric {
+    /// Temperatura máxima no último passo (°C)
+    FinalMaxTemperature,
+    /// Temperatura média no último passo (°C)
+    FinalMeanTemperature,
+    /// Temperatura mínima no último passo (°C)
+    FinalMinTemperature,
+    /// Maior temperatura registrada durante a simulação (°C)
+    PeakTemperature,
+    /// Energia armazenada no último passo, Σ ρ·H·V (J)
+    StoredEnergy,
+}
+
+impl SensitivityMetric {
+    /// Avalia a métrica sobre os resultados
+    pub fn evaluate(&self, results: &SimulationResults) -> Result<f64, String> {
+        let last = results.temperature_at(results.executed_steps)?;
+        match self {
+            SensitivityMetric::FinalMaxTemperature => Ok(last.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
+            SensitivityMetric::FinalMeanTemperature => Ok(last.iter().sum::<f64>() / last.len().max(1) as f64),
+            SensitivityMetric::FinalMinTemperature => Ok(last.iter().cloned().fold(f64::INFINITY, f64::min)),
+            SensitivityMetric::PeakTemperature => {
+                let mut peak = f64::NEG_INFINITY;
+                for step in 0..=results.executed_steps {
+                    peak = peak.max(results.temperature_at(step)?.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+                }
+                Ok(peak)
+            }
+            SensitivityMetric::StoredEnergy => {
+                let enthalpy_last = results.enthalpy.shape()[2] - 1;
+                let material = &results.parameters.material;
+                let mut energy = 0.0;
+                for i in 0..results.mesh.nr {
+                    for j in 0..results.mesh.nz {
+                        let density = material.get_density(last[[i, j]]);
+                        energy += density * results.enthalpy[[i, j, enthalpy_last]] * results.mesh.cell_volumes[[i, j]];
+                    }
+                }
+                Ok(energy)
+            }
+        }
+    }
+}
+
+/// Enumeração que representa o esquema de diferenças finitas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DifferenceScheme {
+    /// Diferença progressiva (uma simulação por parâmetro)
+    Forward,
+    /// Diferença central (duas simulações por parâmetro, erro de segunda ordem)
+    Central,
+}
+
+/// Estrutura que representa a configuração da análise de sensibilidade local
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitivityConfig {
+    /// Parâmetros da simulação de referência
+    pub base_parameters: SimulationParameters,
+    /// Parâmetros perturbados (nome e perturbação relativa)
+    pub parameters: Vec<KeyParameter>,
+    /// Métricas avaliadas
+    pub metrics: Vec<SensitivityMetric>,
+    /// Esquema de diferenças finitas
+    pub scheme: DifferenceScheme,
+}
+
+impl SensitivityConfig {
+    /// Cria uma nova configuração com diferenças centrais
+    pub fn new(base_parameters: SimulationParameters, parameters: Vec<KeyParameter>, metrics: Vec<SensitivityMetric>) -> Self {
+        Self {
+            base_parameters,
+            parameters,
+            metrics,
+            scheme: DifferenceScheme::Central,
+        }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        self.base_parameters.validate()?;
+        if self.parameters.is_empty() || self.metrics.is_empty() {
+            return Err("A análise de sensibilidade requer parâmetros e métricas".to_string());
+        }
+        for parameter in &self.parameters {
+            if parameter.relative_step <= 0.0 {
+                return Err(format!("Perturbação do parâmetro {} deve ser positiva", parameter.name));
+            }
+            let value = key_parameter_value(&self.base_parameters, &parameter.name)?;
+            if value == 0.0 {
+                return Err(format!("Parâmetro {} é nulo; perturbação relativa indefinida", parameter.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Estrutura que representa a sensibilidade de uma métrica a um parâmetro
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitivityEntry {
+    /// Nome do parâmetro
+    pub parameter: String,
+    /// Métrica avaliada
+    pub metric: SensitivityMetric,
+    /// Valor do parâmetro na referência
+    pub parameter_value: f64,
+    /// Valor da métrica na referência
+    pub metric_value: f64,
+    /// Derivada d(métrica)/d(parâmetro), em unidades absolutas
+    pub derivative: f64,
+    /// Sensibilidade normalizada (p/m)·d(métrica)/d(parâmetro) (adimensional)
+    pub normalized: f64,
+}
+
+/// Estrutura que representa o resultado da análise de sensibilidade local
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitivityResult {
+    /// Sensibilidades por parâmetro e métrica
+    pub entries: Vec<SensitivityEntry>,
+    /// Número de simulações executadas
+    pub simulations_run: usize,
+}
+
+impl SensitivityResult {
+    /// Retorna a sensibilidade de uma métrica a um parâmetro
+    pub fn get(&self, parameter: &str, metric: SensitivityMetric) -> Option<&SensitivityEntry> {
+        self.entries.iter().find(|e| e.parameter == parameter && e.metric == metric)
+    }
+}
+
+/// Executa uma simulação e avalia as métricas
+fn run_metrics(params: SimulationParameters, metrics: &[SensitivityMetric], cancel_flag: Arc<AtomicBool>) -> Result<Vec<f64>, String> {
+    if cancel_flag.load(Ordering::Relaxed) {
+        return Err("Análise de sensibilidade cancelada".to_string());
+    }
+    let results = HeatSolver::new(params)?.run(None, cancel_flag)?;
+    metrics.iter().map(|metric| metric.evaluate(&results)).collect()
+}
+
+/// Calcula as sensibilidades locais da simulação de referência por diferenças finitas
+///
+/// Cada parâmetro é multiplicado por (1 ± passo relativo) e as simulações perturbadas
+/// são executadas em paralelo; não exige um estudo paramétrico completo.
+pub fn calculate_local_sensitivities(config: &SensitivityConfig, cancel_flag: Arc<AtomicBool>) -> Result<SensitivityResult, String> {
+    config.validate()?;
+
+    // Fatores de perturbação por parâmetro: (fator superior, fator inferior)
+    let factors: Vec<(f64, f64)> = config.parameters.iter()
+        .map(|p| match config.scheme {
+            DifferenceScheme::Forward => (1.0 + p.relative_step, 1.0),
+            DifferenceScheme::Central => (1.0 + p.relative_step, 1.0 - p.relative_step),
+        })
+        .collect();
+
+    let mut runs: Vec<(Option<usize>, f64)> = vec![(None, 1.0)];
+    for (index, &(upper, lower)) in factors.iter().enumerate() {
+        runs.push((Some(index), upper));
+        if config.scheme == DifferenceScheme::Central {
+            runs.push((Some(index), lower));
+        }
+    }
+    info!("Sensibilidade local: {} simulações para {} parâmetros", runs.len(), config.parameters.len());
+
+    let outputs = runs.par_iter()
+        .map(|&(index, factor)| {
+            let mut params = config.base_parameters.clone();
+            if let Some(index) = index {
+                apply_key_parameter(&mut params, &config.parameters[index].name, factor)?;
+            }
+            run_metrics(params, &config.metrics, cancel_flag.clone())
+        })
+        .collect::<Result<Vec<Vec<f64>>, String>>()?;
+
+    let base = &outputs[0];
+    let mut entries = Vec::new();
+    for (index, parameter) in config.parameters.iter().enumerate() {
+        let value = key_parameter_value(&config.base_parameters, &parameter.name)?;
+        let (upper_output, lower_output, lower_factor) = match config.scheme {
+            DifferenceScheme::Forward => (&outputs[1 + index], base, 1.0),
+            DifferenceScheme::Central => (&outputs[1 + 2 * index], &outputs[2 + 2 * index], factors[index].1),
+        };
+        let delta = value * (factors[index].0 - lower_factor);
+
+        for (m, metric) in config.metrics.iter().enumerate() {
+            let derivative = (upper_output[m] - lower_output[m]) / delta;
+            let normalized = if base[m].abs() > 1e-12 { derivative * value / base[m] } else { 0.0 };
+            entries.push(SensitivityEntry {
+                parameter: parameter.name.clone(),
+                metric: *metric,
+                parameter_value: value,
+                metric_value: base[m],
+                derivative,
+                normalized,
+            });
+        }
+    }
+
+    Ok(SensitivityResult {
+        entries,
+        simulations_run: runs.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PlasmaTorch;
+
+    #[test]
+    fn test_local_sensitivities() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+
+        let config = SensitivityConfig::new(
+            params,
+            vec![KeyParameter::new("torch_power"), KeyParameter::new("specific_heat")],
+            vec![SensitivityMetric::FinalMaxTemperature, SensitivityMetric::StoredEnergy],
+        );
+        let result = calculate_local_sensitivities(&config, Arc::new(AtomicBool::new(false))).unwrap();
+        assert_eq!(result.simulations_run, 5);
+        assert_eq!(result.entries.len(), 4);
+
+        // Mais potência aquece; maior capacidade térmica reduz a temperatura máxima
+        let power = result.get("torch_power", SensitivityMetric::FinalMaxTemperature).unwrap();
+        assert!(power.derivative > 0.0);
+        assert!((power.parameter_value - 10.0).abs() < 1e-12);
+        let cp = result.get("specific_heat", SensitivityMetric::FinalMaxTemperature).unwrap();
+        assert!(cp.derivative < 0.0);
+
+        let mut invalid = config.clone();
+        invalid.parameters = vec![KeyParameter::new("unknown")];
+        assert!(calculate_local_sensitivities(&invalid, Arc::new(AtomicBool::new(false))).is_err());
+    }
+}
@@ -0,0 +1,445 @@
+// Implementação do otimizador de posicionamento de termopares baseado em sensibilidade
+
+use log::info;
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, atomic::AtomicBool};
+
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Regularização da matriz de informação (evita log(0) com poucos sensores)
+const INFORMATION_REGULARIZATION: f64 = 1e-9;
+
+/// Estrutura que representa um parâmetro-chave cuja identificação orienta a instrumentação
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyParameter {
+    /// Nome do parâmetro (ver `apply_key_parameter`)
+    pub name: String,
+    /// Perturbação relativa usada no cálculo da sensibilidade (ex.: 0,05 = 5%)
+    pub relative_step: f64,
+}
+
+impl KeyParameter {
+    /// Cria um novo parâmetro-chave com perturbação de 5%
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            relative_step: 0.05,
+        }
+    }
+}
+
+/// Estrutura que representa a configuração do otimizador de posicionamento
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorPlacementConfig {
+    /// Cenário operacional esperado
+    pub base_parameters: SimulationParameters,
+    /// Parâmetros-chave a serem identificados pelas leituras
+    pub key_parameters: Vec<KeyParameter>,
+    /// Número de termopares a posicionar
+    pub num_probes: usize,
+    /// Distância mínima entre termopares (m)
+    pub min_spacing: f64,
+    /// Peso da informação sobre os parâmetros frente à cobertura da variância (0-1)
+    pub information_weight: f64,
+    /// Restringir os candidatos às superfícies (parede lateral, base e topo)
+    pub surface_only: bool,
+}
+
+impl SensorPlacementConfig {
+    /// Cria uma nova configuração com valores padrão
+    pub fn new(base_parameters: SimulationParameters, key_parameters: Vec<KeyParameter>, num_probes: usize) -> Self {
+        Self {
+            base_parameters,
+            key_parameters,
+            num_probes,
+            min_spacing: 0.0,
+            information_weight: 0.8,
+            surface_only: false,
+        }
+    }
+
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        self.base_parameters.validate()?;
+        if self.num_probes == 0 {
+            return Err("Número de termopares deve ser positivo".to_string());
+        }
+        if self.key_parameters.is_empty() {
+            return Err("Pelo menos um parâmetro-chave deve ser informado".to_string());
+        }
+        for parameter in &self.key_parameters {
+            if parameter.relative_step <= 0.0 {
+                return Err(format!("Perturbação do parâmetro {} deve ser positiva", parameter.name));
+            }
+            let mut probe = self.base_parameters.clone();
+            apply_key_parameter(&mut probe, &parameter.name, 1.0)?;
+        }
+        if self.min_spacing < 0.0 {
+            return Err("Distância mínima entre termopares não pode ser negativa".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.information_weight) {
+            return Err("Peso da informação deve estar entre 0 e 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Multiplica os coeficientes de uma propriedade dependente da temperatura
+fn scale_coefficients(coefficients: &mut Option<Vec<f64>>, factor: f64) {
+    if let Some(coefficients) = coefficients.as_mut() {
+        for coefficient in coefficients.iter_mut() {
+            *coefficient *= factor;
+        }
+    }
+}
+
+/// Multiplica um parâmetro-chave pelo fator informado
+///
+/// Nomes suportados: thermal_conductivity, specific_heat, density, emissivity,
+/// torch_power, convection_coefficient, ambient_temperature e initial_temperature.
+/// As propriedades se referem ao material principal, incluindo os coeficientes
+/// de dependência com a temperatura.
+pub fn apply_key_parameter(params: &mut SimulationParameters, name: &str, factor: f64) -> Result<(), String> {
+    match name {
+        "thermal_conductivity" => {
+            params.material.thermal_conductivity *= factor;
+            scale_coefficients(&mut params.material.thermal_conductivity_coefficients, factor);
+        }
+        "specific_heat" => {
+            params.material.specific_heat *= factor;
+            scale_coefficients(&mut params.material.specific_heat_coefficients, factor);
+        }
+        "density" => {
+            params.material.density *= factor;
+            scale_coefficients(&mut params.material.density_coefficients, factor);
+        }
+        "emissivity" => params.material.emissivity = (params.material.emissivity * factor).min(1.0),
+        "torch_power" => {
+            for torch in params.torches.iter_mut() {
+                torch.power *= factor;
+                if let Some(schedule) = torch.power_schedule.as_mut() {
+                    for point in schedule.points.iter_mut() {
+                        point.1 *= factor;
+                    }
+                }
+            }
+        }
+        "convection_coefficient" => params.convection_coefficient *= factor,
+        "ambient_temperature" => params.ambient_temperature *= factor,
+        "initial_temperature" => params.initial_temperature *= factor,
+        _ => return Err(format!("Parâmetro-chave desconhecido: {}", name)),
+    }
+    Ok(())
+}
+
+/// Retorna o valor atual de um parâmetro-chave
+///
+/// Para `torch_power` retorna a soma das potências nominais das tochas (kW).
+pub fn key_parameter_value(params: &SimulationParameters, name: &str) -> Result<f64, String> {
+    match name {
+        "thermal_conductivity" => Ok(params.material.thermal_conductivity),
+        "specific_heat" => Ok(params.material.specific_heat),
+        "density" => Ok(params.material.density),
+        "emissivity" => Ok(params.material.emissivity),
+        "torch_power" => Ok(params.torches.iter().map(|torch| torch.power).sum()),
+        "convection_coefficient" => Ok(params.convection_coefficient),
+        "ambient_temperature" => Ok(params.ambient_temperature),
+        "initial_temperature" => Ok(params.initial_temperature),
+        _ => Err(format!("Parâmetro-chave desconhecido: {}", name)),
+    }
+}
+
+/// Estrutura que representa um termopar proposto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeProposal {
+    /// Ordem de seleção (0 = mais informativo)
+    pub rank: usize,
+    /// Índice radial do nó
+    pub radial_index: usize,
+    /// Índice axial do nó
+    pub axial_index: usize,
+    /// Posição radial (m)
+    pub r: f64,
+    /// Posição axial (m)
+    pub z: f64,
+    /// Ganho de informação (aumento de log det da matriz de informação)
+    pub information_gain: f64,
+    /// Sensibilidade RMS da leitura a cada parâmetro (°C por variação relativa unitária)
+    pub sensitivities: HashMap<String, f64>,
+    /// Desvio padrão temporal da temperatura no nó (°C)
+    pub temperature_std: f64,
+}
+
+/// Estrutura que representa o resultado do otimizador de posicionamento
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorPlacementResult {
+    /// Termopares propostos, em ordem de seleção
+    pub probes: Vec<ProbeProposal>,
+    /// Nomes dos parâmetros-chave, na ordem da matriz de informação
+    pub parameter_names: Vec<String>,
+    /// Matriz de informação de Fisher dos termopares selecionados
+    pub information_matrix: Vec<Vec<f64>>,
+    /// Indica se todos os parâmetros são identificáveis com os termopares propostos
+    pub identifiable: bool,
+    /// Número de simulações executadas
+    pub simulations_run: usize,
+}
+
+/// Sensibilidades de um nó candidato ao longo do tempo
+struct CandidateNode {
+    i: usize,
+    j: usize,
+    /// Sensibilidades por passo de tempo e parâmetro (passos × parâmetros)
+    jacobian: Vec<Vec<f64>>,
+    /// Desvio padrão temporal da temperatura do cenário base
+    temperature_std: f64,
+}
+
+/// Calcula o logaritmo do determinante de uma matriz simétrica positiva definida (Cholesky)
+fn log_determinant(matrix: &[Vec<f64>]) -> Option<f64> {
+    let n = matrix.len();
+    let mut l = vec![vec![0.0; n]; n];
+    let mut log_det = 0.0;
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let diagonal = matrix[i][i] - sum;
+                if diagonal <= 0.0 {
+                    return None;
+                }
+                l[i][i] = diagonal.sqrt();
+                log_det += 2.0 * l[i][i].ln();
+            } else {
+                l[i][j] = (matrix[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(log_det)
+}
+
+/// Soma à matriz de informação a contribuição de um nó (JᵀJ)
+fn add_information(matrix: &mut [Vec<f64>], jacobian: &[Vec<f64>]) {
+    for row in jacobian {
+        for a in 0..row.len() {
+            for b in 0..row.len() {
+                matrix[a][b] += row[a] * row[b];
+            }
+        }
+    }
+}
+
+/// Estrutura que representa o otimizador de posicionamento de termopares
+pub struct SensorPlacementOptimizer {
+    /// Configuração do otimizador
+    config: SensorPlacementConfig,
+}
+
+impl SensorPlacementOptimizer {
+    /// Cria um novo otimizador
+    pub fn new(config: SensorPlacementConfig) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
+    /// Executa o cenário base e os cenários perturbados e propõe as posições
+    pub fn optimize(&self, cancel_flag: Arc<AtomicBool>) -> Result<SensorPlacementResult, String> {
+        let base_results = HeatSolver::new(self.config.base_parameters.clone())?.run(None, cancel_flag.clone())?;
+        let base_history = Self::history(&base_results)?;
+
+        let mut sensitivity_histories = Vec::with_capacity(self.config.key_parameters.len());
+        for parameter in &self.config.key_parameters {
+            let mut params = self.config.base_parameters.clone();
+            apply_key_parameter(&mut params, &parameter.name, 1.0 + parameter.relative_step)?;
+            let results = HeatSolver::new(params)?.run(None, cancel_flag.clone())?;
+            let history = Self::history(&results)?;
+            if history.len() != base_history.len() {
+                return Err(format!("Simulação perturbada de {} interrompida antes do fim", parameter.name));
+            }
+            // Sensibilidade escalonada: dT / (dp/p)
+            let sensitivity: Vec<Array2<f64>> = history.iter().zip(base_history.iter())
+                .map(|(perturbed, base)| (perturbed - base) / parameter.relative_step)
+                .collect();
+            sensitivity_histories.push(sensitivity);
+        }
+
+        let candidates = self.build_candidates(&base_results, &base_history, &sensitivity_histories);
+        let result = self.select_probes(&base_results, candidates);
+        info!("Posicionamento de {} termopares proposto ({} simulações)",
+              result.probes.len(), result.simulations_run);
+        Ok(result)
+    }
+
+    /// Campos de temperatura dos passos executados (exceto o inicial)
+    fn history(results: &SimulationResults) -> Result<Vec<Array2<f64>>, String> {
+        (1..=results.executed_steps).map(|step| results.temperature_at(step)).collect()
+    }
+
+    /// Monta as sensibilidades e a variância de cada nó candidato
+    fn build_candidates(
+        &self,
+        results: &SimulationResults,
+        base_history: &[Array2<f64>],
+        sensitivity_histories: &[Vec<Array2<f64>>],
+    ) -> Vec<CandidateNode> {
+        let (nr, nz) = (results.mesh.nr, results.mesh.nz);
+        let mut candidates = Vec::new();
+        for i in 0..nr {
+            for j in 0..nz {
+                let on_surface = i == nr - 1 || j == 0 || j == nz - 1;
+                if self.config.surface_only && !on_surface {
+                    continue;
+                }
+
+                let jacobian: Vec<Vec<f64>> = (0..base_history.len())
+                    .map(|step| sensitivity_histories.iter().map(|s| s[step][[i, j]]).collect())
+                    .collect();
+
+                let n = base_history.len().max(1) as f64;
+                let mean = base_history.iter().map(|t| t[[i, j]]).sum::<f64>() / n;
+                let variance = base_history.iter().map(|t| (t[[i, j]] - mean).powi(2)).sum::<f64>() / n;
+
+                candidates.push(CandidateNode { i, j, jacobian, temperature_std: variance.sqrt() });
+            }
+        }
+        candidates
+    }
+
+    /// Seleção gulosa (critério D-ótimo ponderado pela cobertura da variância)
+    fn select_probes(&self, results: &SimulationResults, mut candidates: Vec<CandidateNode>) -> SensorPlacementResult {
+        let mesh = &results.mesh;
+        let parameter_count = self.config.key_parameters.len();
+        let mut information = vec![vec![0.0; parameter_count]; parameter_count];
+        for (a, row) in information.iter_mut().enumerate() {
+            row[a] = INFORMATION_REGULARIZATION;
+        }
+        let max_std = candidates.iter().map(|c| c.temperature_std).fold(0.0, f64::max);
+        let weight = self.config.information_weight;
+
+        let mut probes: Vec<ProbeProposal> = Vec::new();
+        while probes.len() < self.config.num_probes && !candidates.is_empty() {
+            let current = log_determinant(&information).unwrap_or(f64::NEG_INFINITY);
+            let gains: Vec<f64> = candidates.iter()
+                .map(|candidate| {
+                    let mut trial = information.clone();
+                    add_information(&mut trial, &candidate.jacobian);
+                    log_determinant(&trial).map_or(0.0, |value| (value - current).max(0.0))
+                })
+                .collect();
+            let max_gain = gains.iter().cloned().fold(0.0, f64::max);
+
+            let best = candidates.iter().zip(gains.iter()).enumerate()
+                .map(|(index, (candidate, gain))| {
+                    let information_score = if max_gain > 0.0 { gain / max_gain } else { 0.0 };
+                    let coverage_score = if max_std > 0.0 { candidate.temperature_std / max_std } else { 0.0 };
+                    (index, weight * information_score + (1.0 - weight) * coverage_score)
+                })
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(index, _)| index);
+            let index = match best {
+                Some(index) => index,
+                None => break,
+            };
+
+            let chosen = candidates.swap_remove(index);
+            let gain = gains[index];
+            add_information(&mut information, &chosen.jacobian);
+
+            let steps = chosen.jacobian.len().max(1) as f64;
+            let sensitivities = self.config.key_parameters.iter().enumerate()
+                .map(|(p, parameter)| {
+                    let rms = (chosen.jacobian.iter().map(|row| row[p] * row[p]).sum::<f64>() / steps).sqrt();
+                    (parameter.name.clone(), rms)
+                })
+                .collect();
+            let (r, z) = (mesh.r_coords[chosen.i], mesh.z_coords[chosen.j]);
+            probes.push(ProbeProposal {
+                rank: probes.len(),
+                radial_index: chosen.i,
+                axial_index: chosen.j,
+                r,
+                z,
+                information_gain: gain,
+                sensitivities,
+                temperature_std: chosen.temperature_std,
+            });
+
+            // Excluir candidatos próximos demais do termopar escolhido
+            let spacing = self.config.min_spacing;
+            candidates.retain(|c| {
+                let dr = mesh.r_coords[c.i] - r;
+                let dz = mesh.z_coords[c.j] - z;
+                (dr * dr + dz * dz).sqrt() >= spacing
+            });
+        }
+
+        // Identificável quando a informação domina a regularização em todas as direções
+        let threshold = parameter_count as f64 * (INFORMATION_REGULARIZATION * 1e3).ln();
+        let identifiable = log_determinant(&information).is_some_and(|value| value > threshold);
+
+        SensorPlacementResult {
+            probes,
+            parameter_names: self.config.key_parameters.iter().map(|p| p.name.clone()).collect(),
+            information_matrix: information,
+            identifiable,
+            simulations_run: 1 + parameter_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use crate::simulation::physics::PlasmaTorch;
+
+    fn create_config(num_probes: usize) -> SensorPlacementConfig {
+        let mut params = SimulationParameters::new(1.0, 0.5, 6, 6);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 100.0, 0.01, 5000.0));
+        SensorPlacementConfig::new(
+            params,
+            vec![KeyParameter::new("torch_power"), KeyParameter::new("convection_coefficient")],
+            num_probes,
+        )
+    }
+
+    #[test]
+    fn test_log_determinant() {
+        let matrix = vec![vec![4.0, 2.0], vec![2.0, 3.0]];
+        assert_relative_eq!(log_determinant(&matrix).unwrap(), 8.0f64.ln(), epsilon = 1e-12);
+        assert!(log_determinant(&[vec![1.0, 1.0], vec![1.0, 1.0]]).is_none());
+
+        let mut params = SimulationParameters::new(1.0, 0.5, 4, 4);
+        assert!(apply_key_parameter(&mut params, "unknown", 1.1).is_err());
+        apply_key_parameter(&mut params, "convection_coefficient", 2.0).unwrap();
+        assert_relative_eq!(params.convection_coefficient, 20.0);
+    }
+
+    #[test]
+    fn test_probe_placement_respects_spacing() {
+        let mut config = create_config(3);
+        config.min_spacing = 0.3;
+        let optimizer = SensorPlacementOptimizer::new(config).unwrap();
+        let result = optimizer.optimize(Arc::new(AtomicBool::new(false))).unwrap();
+
+        assert!(!result.probes.is_empty() && result.probes.len() <= 3);
+        assert_eq!(result.simulations_run, 3);
+        assert_eq!(result.parameter_names.len(), 2);
+        for (a, first) in result.probes.iter().enumerate() {
+            assert_eq!(first.rank, a);
+            for second in result.probes.iter().skip(a + 1) {
+                let distance = ((first.r - second.r).powi(2) + (first.z - second.z).powi(2)).sqrt();
+                assert!(distance >= 0.3);
+            }
+        }
+
+        // O primeiro termopar deve responder à potência da tocha
+        assert!(result.probes[0].sensitivities["torch_power"] > 0.0);
+        assert!(SensorPlacementOptimizer::new(create_config(0)).is_err());
+    }
+}
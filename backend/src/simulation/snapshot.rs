@@ -0,0 +1,367 @@
+// Implementação do pacote de instantâneo (miniatura PNG, métricas-chave e resumo dos parâmetros)
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::archive::crc32;
+use crate::simulation::solver::{SimulationParameters, SimulationResults};
+use crate::simulation::state::{SimulationState, SimulationStatus};
+use crate::simulation::visualization::ColorScale;
+
+/// Versão atual do formato do pacote de instantâneo
+pub const SNAPSHOT_PACKAGE_VERSION: u32 = 1;
+
+/// Estrutura que representa as opções do instantâneo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotOptions {
+    /// Maior dimensão da miniatura (pixels)
+    pub thumbnail_size: usize,
+    /// Escala de cores da miniatura
+    pub color_scale: ColorScale,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            thumbnail_size: 128,
+            color_scale: ColorScale::BlueToRed,
+        }
+    }
+}
+
+/// Estrutura que representa a miniatura do mapa de calor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotThumbnail {
+    /// Largura (pixels, direção radial)
+    pub width: usize,
+    /// Altura (pixels, direção axial, topo do domínio na primeira linha)
+    pub height: usize,
+    /// Passo de tempo representado
+    pub time_step: usize,
+    /// Temperatura associada à primeira cor da escala (°C)
+    pub min_temperature: f64,
+    /// Temperatura associada à última cor da escala (°C)
+    pub max_temperature: f64,
+    /// Imagem PNG (RGB, 8 bits) codificada em base64
+    pub png_base64: String,
+}
+
+/// Estrutura que representa as métricas-chave do instantâneo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMetrics {
+    /// Status da simulação
+    pub status: SimulationStatus,
+    /// Progresso (0.0 - 1.0)
+    pub progress: f32,
+    /// Tempo de execução (s)
+    pub execution_time: f64,
+    /// Mensagem de erro, se houver
+    pub error_message: Option<String>,
+    /// Passos executados (None sem resultados)
+    pub executed_steps: Option<usize>,
+    /// Tempo simulado (s)
+    pub simulated_time: Option<f64>,
+    /// Temperatura mínima no último passo (°C)
+    pub final_min_temperature: Option<f64>,
+    /// Temperatura média no último passo (°C)
+    pub final_mean_temperature: Option<f64>,
+    /// Temperatura máxima no último passo (°C)
+    pub final_max_temperature: Option<f64>,
+    /// Maior temperatura registrada (°C)
+    pub peak_temperature: Option<f64>,
+}
+
+/// Estrutura que representa o resumo dos parâmetros
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParametersSummary {
+    /// Raio (m)
+    pub radius: f64,
+    /// Altura (m)
+    pub height: f64,
+    /// Nós radiais
+    pub nr: usize,
+    /// Nós axiais
+    pub nz: usize,
+    /// Material principal
+    pub material: String,
+    /// Número de zonas de material adicionais
+    pub material_zones: usize,
+    /// Número de tochas
+    pub torch_count: usize,
+    /// Potência total das tochas (kW)
+    pub total_torch_power: f64,
+    /// Tempo total (s)
+    pub total_time: f64,
+    /// Passo de tempo (s)
+    pub time_step: f64,
+    /// Esquema de solução
+    pub solver_scheme: String,
+}
+
+impl ParametersSummary {
+    /// Cria o resumo a partir dos parâmetros
+    pub fn from_parameters(params: &SimulationParameters) -> Self {
+        Self {
+            radius: params.radius,
+            height: params.height,
+            nr: params.nr,
+            nz: params.nz,
+            material: params.material.name.clone(),
+            material_zones: params.material_zones.as_ref().map_or(0, |zones| zones.len()),
+            torch_count: params.torches.len(),
+            total_torch_power: params.torches.iter().map(|t| t.power).sum(),
+            total_time: params.total_time,
+            time_step: params.time_step,
+            solver_scheme: format!("{:?}", params.solver_scheme),
+        }
+    }
+}
+
+/// Estrutura que representa o pacote de instantâneo do estado atual
+///
+/// Permite pré-visualizações em navegadores de projeto e relatórios de falha sem
+/// carregar os resultados completos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotPackage {
+    /// Versão do formato
+    pub version: u32,
+    /// Miniatura do último campo de temperatura (None sem resultados)
+    pub thumbnail: Option<SnapshotThumbnail>,
+    /// Métricas-chave
+    pub metrics: SnapshotMetrics,
+    /// Resumo dos parâmetros
+    pub parameters: ParametersSummary,
+}
+
+impl SnapshotPackage {
+    /// Cria o pacote a partir do estado da simulação
+    pub fn from_state(state: &SimulationState, options: &SnapshotOptions) -> Result<Self, String> {
+        if options.thumbnail_size == 0 {
+            return Err("Tamanho da miniatura deve ser positivo".to_string());
+        }
+
+        let mut metrics = SnapshotMetrics {
+            status: state.status,
+            progress: state.progress,
+            execution_time: state.execution_time,
+            error_message: state.error_message.clone(),
+            executed_steps: None,
+            simulated_time: None,
+            final_min_temperature: None,
+            final_mean_temperature: None,
+            final_max_temperature: None,
+            peak_temperature: None,
+        };
+
+        let thumbnail = match &state.results {
+            Some(results) => {
+                let last = results.temperature_at(results.executed_steps)?;
+                let (min, max) = field_range(&last);
+                metrics.executed_steps = Some(results.executed_steps);
+                metrics.simulated_time = Some(results.executed_steps as f64 * results.parameters.time_step);
+                metrics.final_min_temperature = Some(min);
+                metrics.final_max_temperature = Some(max);
+                metrics.final_mean_temperature = Some(last.iter().sum::<f64>() / last.len().max(1) as f64);
+                metrics.peak_temperature = Some(peak_temperature(results)?);
+                Some(render_thumbnail(&last, results.executed_steps, options))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            version: SNAPSHOT_PACKAGE_VERSION,
+            thumbnail,
+            metrics,
+            parameters: ParametersSummary::from_parameters(&state.parameters),
+        })
+    }
+}
+
+/// Menor e maior valor de um campo
+pub(crate) fn field_range(field: &Array2<f64>) -> (f64, f64) {
+    field.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| (min.min(v), max.max(v)))
+}
+
+/// Maior temperatura registrada em todo o histórico
+fn peak_temperature(results: &SimulationResults) -> Result<f64, String> {
+    let mut peak = f64::NEG_INFINITY;
+    for step in 0..=results.executed_steps {
+        peak = peak.max(field_range(&results.temperature_at(step)?).1);
+    }
+    Ok(peak)
+}
+
+/// Converte um valor normalizado (0-1) em cor RGB
+fn color_for(value: f64, scale: ColorScale) -> [u8; 3] {
+    let t = if value.is_finite() { value.clamp(0.0, 1.0) } else { 0.0 };
+    let to_byte = |x: f64| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+    match scale {
+        ColorScale::Grayscale => [to_byte(t); 3],
+        ColorScale::Rainbow => {
+            // Matiz de 240° (azul) a 0° (vermelho) com saturação e brilho máximos
+            let hue = (1.0 - t) * 4.0;
+            let x = 1.0 - (hue % 2.0 - 1.0).abs();
+            let (r, g, b) = match hue as u32 {
+                0 => (1.0, x, 0.0),
+                1 => (x, 1.0, 0.0),
+                2 => (0.0, 1.0, x),
+                _ => (0.0, x, 1.0),
+            };
+            [to_byte(r), to_byte(g), to_byte(b)]
+        }
+        // Escalas personalizadas não têm paleta no backend; usa azul-vermelho
+        ColorScale::BlueToRed | ColorScale::Custom => [to_byte(t), to_byte(1.0 - (2.0 * t - 1.0).abs()), to_byte(1.0 - t)],
+    }
+}
+
+/// Rasteriza o campo em RGB com `size` pixels na maior dimensão, preservando a proporção
+/// da grade (r na horizontal, z na vertical) e normalizando pelo intervalo `(min, max)`
+pub(crate) fn render_field_rgb(field: &Array2<f64>, size: usize, range: (f64, f64), scale: ColorScale) -> (usize, usize, Vec<u8>) {
+    let (nr, nz) = field.dim();
+    let factor = size as f64 / nr.max(nz).max(1) as f64;
+    let width = ((nr as f64 * factor).round() as usize).max(1);
+    let height = ((nz as f64 * factor).round() as usize).max(1);
+    let (min, max) = range;
+    let span = if max > min { max - min } else { 1.0 };
+
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        let j = ((height - 1 - y) * nz / height).min(nz - 1);
+        for x in 0..width {
+            let i = (x * nr / width).min(nr - 1);
+            pixels.extend_from_slice(&color_for((field[[i, j]] - min) / span, scale));
+        }
+    }
+    (width, height, pixels)
+}
+
+/// Gera a miniatura do campo
+fn render_thumbnail(field: &Array2<f64>, time_step: usize, options: &SnapshotOptions) -> SnapshotThumbnail {
+    let (min, max) = field_range(field);
+    let (width, height, pixels) = render_field_rgb(field, options.thumbnail_size, (min, max), options.color_scale);
+
+    SnapshotThumbnail {
+        width,
+        height,
+        time_step,
+        min_temperature: min,
+        max_temperature: max,
+        png_base64: encode_base64(&encode_png_rgb(width, height, &pixels)),
+    }
+}
+
+/// Soma de verificação Adler-32 do fluxo zlib
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+/// Codifica uma imagem RGB de 8 bits em PNG
+///
+/// Usa blocos deflate sem compressão: as miniaturas são pequenas e isso evita
+/// dependências de compressão no backend.
+pub fn encode_png_rgb(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+    // Linhas com filtro 0 (nenhum)
+    let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+    for row in pixels.chunks(width * 3).take(height) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if raw.is_empty() { vec![&[]] } else { raw.chunks(65535).collect() };
+    for (index, block) in blocks.iter().enumerate() {
+        zlib.push(if index + 1 == blocks.len() { 1 } else { 0 });
+        let len = block.len() as u16;
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    for (kind, data) in [(b"IHDR", header.as_slice()), (b"IDAT", zlib.as_slice()), (b"IEND", &[][..])] {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(kind);
+        png.extend_from_slice(data);
+        let crc = crc32(&png[start..]);
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+    png
+}
+
+/// Codifica bytes em base64 (RFC 4648, com preenchimento)
+pub fn encode_base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for k in 0..4 {
+            if k <= chunk.len() {
+                output.push(ALPHABET[((n >> (18 - 6 * k)) & 0x3F) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PlasmaTorch;
+    use crate::simulation::solver::HeatSolver;
+    use std::sync::{Arc, atomic::AtomicBool};
+
+    #[test]
+    fn test_png_and_base64_encoding() {
+        assert_eq!(encode_base64(b"Man"), "TWFu");
+        assert_eq!(encode_base64(b"Ma"), "TWE=");
+        assert_eq!(encode_base64(b"M"), "TQ==");
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+
+        let png = encode_png_rgb(2, 1, &[255, 0, 0, 0, 0, 255]);
+        assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn test_snapshot_package() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+
+        let mut state = SimulationState::new(params.clone());
+        let package = SnapshotPackage::from_state(&state, &SnapshotOptions::default()).unwrap();
+        assert!(package.thumbnail.is_none());
+        assert_eq!(package.parameters.torch_count, 1);
+
+        state.results = Some(HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap());
+        let options = SnapshotOptions { thumbnail_size: 40, ..SnapshotOptions::default() };
+        let package = SnapshotPackage::from_state(&state, &options).unwrap();
+        let thumbnail = package.thumbnail.unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (40, 40));
+        assert!(thumbnail.png_base64.starts_with("iVBORw0KGgo"));
+        assert_eq!(package.metrics.executed_steps, Some(5));
+        assert!(package.metrics.peak_temperature.unwrap() >= package.metrics.final_max_temperature.unwrap());
+    }
+}
@@ -1,15 +1,336 @@
 // Integração do módulo de materiais com o solucionador
 
-use ndarray::{Array, Array2, Array3, Axis, s, Zip};
-use rayon::prelude::*;
+use ndarray::{Array1, Array2, Array3, s, Zip};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::time::Instant;
 use log::{info, warn, error};
 use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
 
-use super::mesh::CylindricalMesh;
-use super::physics::{PlasmaTorch, HeatSources, calculate_radiation_source, calculate_convection_source};
-use super::materials::{MaterialProperties, MaterialLibrary};
+use super::mesh::{AxisTreatment, CylindricalMesh, MeshGrading};
+use super::physics::{PlasmaTorch, ScheduleViolation, HeatSources, calculate_radiation_source, calculate_convection_source, integrate_source};
+use super::materials::{MaterialProperties, MaterialLibrary, PropertyCache, PropertyCacheConfig};
+use super::annotations::{AnnotationKind, TimelineAnnotation, insert_annotation};
+use super::frames::{FrameStorageConfig, LiveFrame, LiveFrameBuffer, QuantizedFrameHistory, TemporalPyramid};
+use super::averaging::{AveragedField, AveragingAccumulator, AveragingWindow};
+use super::moisture::{MoistureEvaporationConfig, MoistureInfo, MoistureModel};
+use super::advection::{AdvectionConfig, calculate_advection_source, courant_rate};
+use super::packed_bed::{BedGasExchange, calculate_bed_gas_exchange_source};
+use super::bed_level::{BedConsumptionConfig, BedLevelInfo, BedLevelModel};
+use super::mass_balance::{MassBalance, MassBalanceTracker};
+use super::power_control::{FormulaPowerControl, PowerController};
+use super::state::ProgressUpdate;
+use super::boundary::BoundaryConditions;
+use super::physics::jet_impingement::{ConvectionModel, calculate_jet_impingement_source};
+use super::physics::participating_media::{ParticipatingMediaConfig, calculate_participating_media_source};
+use super::physics::reactions::{ReactionConfig, ReactionInfo, ReactionModel};
+use crate::formula::integration::{FormulaManager, FormulaSourceTerm};
+
+/// Enumeração que representa o esquema de integração temporal do solucionador
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default)]
+pub enum SolverScheme {
+    /// Euler explícito (condicionalmente estável; adequado para malhas pequenas)
+    #[default]
+    Explicit,
+    /// Direções alternadas implícitas (ADI): varreduras radial e axial tridiagonais,
+    /// estável para passos de tempo grandes em malhas finas
+    Adi,
+}
+
+
+/// Estrutura que representa a configuração do controle adaptativo do passo de tempo
+///
+/// Cada passo de saída (`time_step`) é integrado em subpassos cujo tamanho é limitado
+/// pelo critério de estabilidade explícito (número de Fourier da célula) e ajustado pelo
+/// erro de truncamento local estimado por duplicação de passo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveTimeStepConfig {
+    /// Menor subpasso permitido (s)
+    pub min_time_step: f64,
+    /// Erro de truncamento local admissível por subpasso (°C)
+    pub tolerance: f64,
+    /// Fator de segurança aplicado ao limite de estabilidade e ao ajuste pelo erro (0-1)
+    pub safety_factor: f64,
+    /// Fator máximo de crescimento do subpasso entre subpassos aceitos
+    pub max_growth: f64,
+}
+
+impl Default for AdaptiveTimeStepConfig {
+    fn default() -> Self {
+        Self {
+            min_time_step: 1e-3,
+            tolerance: 0.5,
+            safety_factor: 0.9,
+            max_growth: 2.0,
+        }
+    }
+}
+
+impl AdaptiveTimeStepConfig {
+    /// Valida a configuração
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_time_step <= 0.0 {
+            return Err("Subpasso mínimo deve ser positivo".to_string());
+        }
+        if self.tolerance <= 0.0 {
+            return Err("Tolerância do erro local deve ser positiva".to_string());
+        }
+        if self.safety_factor <= 0.0 || self.safety_factor > 1.0 {
+            return Err("Fator de segurança deve estar entre 0 e 1".to_string());
+        }
+        if self.max_growth < 1.0 {
+            return Err("Fator máximo de crescimento deve ser pelo menos 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Enumeração que representa um critério de parada antecipada de uma execução transiente
+///
+/// Os critérios são avaliados ao fim de cada passo de saída; a execução termina no
+/// primeiro critério satisfeito.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ExitCriterion {
+    /// Todas as células da região acima da temperatura alvo
+    AllAboveTemperature {
+        /// Temperatura alvo (°C)
+        target: f64,
+        /// Zona avaliada (identificador da zona ou nome do material principal); None = domínio inteiro
+        zone: Option<String>,
+    },
+    /// Fração fundida média da região, ponderada pelo volume, maior ou igual ao limite
+    MeltFractionAtLeast {
+        /// Fração limite (0-1)
+        fraction: f64,
+        /// Zona avaliada; None = domínio inteiro
+        zone: Option<String>,
+    },
+    /// Regime permanente: max |dT/dt| abaixo do limite por passos consecutivos
+    SteadyState {
+        /// Taxa máxima de variação da temperatura (°C/s)
+        max_rate: f64,
+        /// Passos de saída consecutivos exigidos
+        consecutive_steps: usize,
+    },
+}
+
+impl ExitCriterion {
+    /// Zona avaliada pelo critério, se houver
+    fn zone(&self) -> Option<&str> {
+        match self {
+            ExitCriterion::AllAboveTemperature { zone, .. } | ExitCriterion::MeltFractionAtLeast { zone, .. } => zone.as_deref(),
+            ExitCriterion::SteadyState { .. } => None,
+        }
+    }
+
+    /// Valida o critério para os parâmetros informados
+    pub fn validate(&self, params: &SimulationParameters) -> Result<(), String> {
+        match self {
+            ExitCriterion::AllAboveTemperature { target, .. } if !target.is_finite() => {
+                return Err("Temperatura alvo do critério de parada deve ser finita".to_string());
+            }
+            ExitCriterion::MeltFractionAtLeast { fraction, .. } => {
+                if !(0.0..=1.0).contains(fraction) {
+                    return Err("Fração fundida do critério de parada deve estar entre 0 e 1".to_string());
+                }
+                if !params.enable_phase_changes {
+                    return Err("Critério de fração fundida requer mudanças de fase habilitadas".to_string());
+                }
+            }
+            ExitCriterion::SteadyState { max_rate, consecutive_steps } => {
+                if *max_rate <= 0.0 {
+                    return Err("Taxa limite do regime permanente deve ser positiva".to_string());
+                }
+                if *consecutive_steps == 0 {
+                    return Err("Regime permanente requer pelo menos um passo consecutivo".to_string());
+                }
+            }
+            _ => {}
+        }
+        if let Some(zone) = self.zone() {
+            zone_material_index(params, zone)?;
+        }
+        Ok(())
+    }
+
+    /// Descrição legível do critério
+    pub fn describe(&self) -> String {
+        let region = |zone: &Option<String>| zone.clone().unwrap_or_else(|| "domínio".to_string());
+        match self {
+            ExitCriterion::AllAboveTemperature { target, zone } => {
+                format!("{} inteiramente acima de {:.1} °C", region(zone), target)
+            }
+            ExitCriterion::MeltFractionAtLeast { fraction, zone } => {
+                format!("fração fundida de {} ≥ {:.3}", region(zone), fraction)
+            }
+            ExitCriterion::SteadyState { max_rate, consecutive_steps } => {
+                format!("regime permanente (|dT/dt| < {} °C/s por {} passos)", max_rate, consecutive_steps)
+            }
+        }
+    }
+}
+
+/// Enumeração que representa o motivo do fim de uma execução
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
+pub enum StopReason {
+    /// Todos os passos de tempo configurados foram integrados
+    #[default]
+    Completed,
+    /// Um critério de parada foi satisfeito
+    CriterionMet {
+        /// Índice do critério em `exit_criteria`
+        criterion: usize,
+        /// Descrição do critério
+        description: String,
+        /// Tempo simulado na parada (s)
+        time: f64,
+    },
+    /// A execução foi cancelada (sinalizador externo ou callback de progresso);
+    /// os resultados contêm apenas os passos integrados até o cancelamento
+    Cancelled {
+        /// Tempo simulado no cancelamento (s)
+        time: f64,
+    },
+}
+
+
+/// Enumeração que representa um limitador numérico acionado durante a integração
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LimiterKind {
+    /// Subpasso adaptativo reduzido pelo limite de estabilidade explícito
+    StabilityLimit,
+    /// Subpasso adaptativo rejeitado pelo controle de erro e refeito
+    RejectedSubstep,
+    /// Subpasso aceito no mínimo configurado com erro acima da tolerância
+    MinimumTimeStep,
+    /// Capacidade térmica aparente limitada (ADI) em células em mudança de fase
+    ApparentCapacityClamp,
+}
+
+/// Estrutura que registra um limitador acionado em um passo
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LimiterActivation {
+    /// Limitador acionado
+    pub kind: LimiterKind,
+    /// Número de acionamentos no passo (subpassos ou células)
+    pub count: usize,
+}
+
+/// Estrutura que registra a convergência de um passo de saída
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConvergenceRecord {
+    /// Passo concluído (1 = primeiro passo)
+    pub step: usize,
+    /// Tempo simulado ao fim do passo (s)
+    pub time: f64,
+    /// Maior variação de temperatura de uma célula no passo, max |ΔT| (°C)
+    pub max_temperature_change: f64,
+    /// Norma L2 (RMS) da taxa de variação da temperatura, |ΔT|/Δt (°C/s)
+    pub residual_l2: f64,
+    /// Norma máxima da taxa de variação da temperatura (°C/s)
+    pub residual_max: f64,
+    /// Variação relativa da entalpia, ||ΔH||₂ / ||H||₂
+    pub relative_enthalpy_change: f64,
+    /// Limitadores acionados no passo
+    pub limiter_activations: Vec<LimiterActivation>,
+}
+
+/// Estrutura que representa o monitor de resíduos e convergência da execução
+///
+/// Registra um `ConvergenceRecord` por passo de saída; o resíduo é a taxa de variação
+/// do campo de temperatura, que tende a zero no regime permanente. Ao retomar de um
+/// ponto de controle, os registros começam no passo retomado.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConvergenceMonitor {
+    /// Registros por passo, em ordem
+    pub records: Vec<ConvergenceRecord>,
+    /// Limitadores acionados no passo em andamento
+    #[serde(skip)]
+    pending: Vec<LimiterActivation>,
+}
+
+impl ConvergenceMonitor {
+    /// Cria um monitor vazio
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra `count` acionamentos de um limitador no passo em andamento
+    pub fn note_limiter(&mut self, kind: LimiterKind, count: usize) {
+        if count == 0 {
+            return;
+        }
+        match self.pending.iter_mut().find(|activation| activation.kind == kind) {
+            Some(activation) => activation.count += count,
+            None => self.pending.push(LimiterActivation { kind, count }),
+        }
+    }
+
+    /// Encerra o passo, comparando os campos antes e depois de um intervalo `dt`
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_step(
+        &mut self,
+        step: usize,
+        time: f64,
+        dt: f64,
+        previous_temperature: &Array2<f64>,
+        temperature: &Array2<f64>,
+        previous_enthalpy: &Array2<f64>,
+        enthalpy: &Array2<f64>,
+    ) -> &ConvergenceRecord {
+        let cells = temperature.len().max(1) as f64;
+        let mut max_change: f64 = 0.0;
+        let mut sum_squares = 0.0;
+        for (before, after) in previous_temperature.iter().zip(temperature.iter()) {
+            let change = (after - before).abs();
+            max_change = max_change.max(change);
+            sum_squares += change * change;
+        }
+        let enthalpy_change: f64 = previous_enthalpy.iter().zip(enthalpy.iter())
+            .map(|(before, after)| (after - before).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let enthalpy_norm = enthalpy.iter().map(|h| h * h).sum::<f64>().sqrt();
+
+        self.records.push(ConvergenceRecord {
+            step,
+            time,
+            max_temperature_change: max_change,
+            residual_l2: (sum_squares / cells).sqrt() / dt,
+            residual_max: max_change / dt,
+            relative_enthalpy_change: if enthalpy_norm > 0.0 { enthalpy_change / enthalpy_norm } else { 0.0 },
+            limiter_activations: std::mem::take(&mut self.pending),
+        });
+        self.records.last().unwrap()
+    }
+
+    /// Último registro
+    pub fn last(&self) -> Option<&ConvergenceRecord> {
+        self.records.last()
+    }
+
+    /// Total de acionamentos de um limitador na execução
+    pub fn total_activations(&self, kind: LimiterKind) -> usize {
+        self.records.iter()
+            .flat_map(|record| record.limiter_activations.iter())
+            .filter(|activation| activation.kind == kind)
+            .map(|activation| activation.count)
+            .sum()
+    }
+}
+
+/// Retorna o índice do material (em `resolve_cell_materials`) de uma zona pelo nome
+fn zone_material_index(params: &SimulationParameters, zone: &str) -> Result<usize, String> {
+    if let Some(position) = params.material_zones.iter().flatten().position(|(id, _)| id == zone) {
+        return Ok(position + 1);
+    }
+    if zone == params.material.name {
+        return Ok(0);
+    }
+    Err(format!("Zona desconhecida no critério de parada: {}", zone))
+}
 
 /// Estrutura que representa os parâmetros da simulação com suporte a materiais avançados
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +371,79 @@ pub struct SimulationParameters {
     pub time_steps: usize,
     /// Mapa de zonas (opcional)
     pub zone_map: Option<Array2<usize>>,
+    /// Tolerância relativa da verificação de energia dos termos fonte (0-1)
+    #[serde(default = "default_energy_check_tolerance")]
+    pub energy_check_tolerance: f64,
+    /// Configuração do cache de propriedades por faixas de temperatura (opcional; desabilitado por padrão)
+    #[serde(default)]
+    pub property_cache: Option<PropertyCacheConfig>,
+    /// Configuração de armazenamento dos quadros do histórico (quantização para reprodução)
+    #[serde(default)]
+    pub frame_storage: FrameStorageConfig,
+    /// Intervalos dos níveis grossos da pirâmide temporal (passos); vazio desabilita
+    #[serde(default = "default_temporal_pyramid_strides")]
+    pub temporal_pyramid_strides: Vec<usize>,
+    /// Tratamento do termo singular no eixo de simetria (r=0)
+    #[serde(default)]
+    pub axis_treatment: AxisTreatment,
+    /// Esquema de integração temporal
+    #[serde(default)]
+    pub solver_scheme: SolverScheme,
+    /// Controle adaptativo do passo de tempo (None usa passo fixo)
+    #[serde(default)]
+    pub adaptive_time_step: Option<AdaptiveTimeStepConfig>,
+    /// Distribuição dos nós na direção radial
+    #[serde(default)]
+    pub radial_grading: MeshGrading,
+    /// Distribuição dos nós na direção axial
+    #[serde(default)]
+    pub axial_grading: MeshGrading,
+    /// Critérios de parada antecipada (vazio integra todos os passos)
+    #[serde(default)]
+    pub exit_criteria: Vec<ExitCriterion>,
+    /// Janelas e ciclos de média temporal do campo de temperatura
+    #[serde(default)]
+    pub averaging_windows: Vec<AveragingWindow>,
+    /// Modelo de evaporação da umidade dos materiais (None desabilita)
+    #[serde(default)]
+    pub moisture_evaporation: Option<MoistureEvaporationConfig>,
+    /// Reações da carga: pirólise com cinética de Arrhenius (None desabilita)
+    #[serde(default)]
+    pub reactions: Option<ReactionConfig>,
+    /// Radiação no meio participante (Rosseland ou P1), somada à fonte de radiação (None desabilita)
+    #[serde(default)]
+    pub participating_media: Option<ParticipatingMediaConfig>,
+    /// Modelo da convecção das tochas (coeficiente constante ou impingimento dos jatos)
+    #[serde(default)]
+    pub convection_model: ConvectionModel,
+    /// Escoamento prescrito com transporte advectivo no espaço livre (None desabilita)
+    #[serde(default)]
+    pub advection: Option<AdvectionConfig>,
+    /// Escoamento de gás pelos interstícios dos leitos porosos (None desabilita)
+    #[serde(default)]
+    pub bed_gas_exchange: Option<BedGasExchange>,
+    /// Consumo do leito com rebaixamento da superfície (None mantém todas as células ativas)
+    #[serde(default)]
+    pub bed_consumption: Option<BedConsumptionConfig>,
+    /// Condições de contorno da parede, topo, base e eixo (padrão: todas adiabáticas)
+    #[serde(default)]
+    pub boundary_conditions: BoundaryConditions,
+    /// Fonte volumétrica experimental S(r, z, t, T) definida por fórmula (None desabilita)
+    #[serde(default)]
+    pub formula_source: Option<FormulaSourceTerm>,
+    /// Controle em malha fechada da potência das tochas por fórmula (None usa as programações)
+    #[serde(default)]
+    pub power_control: Option<FormulaPowerControl>,
+}
+
+/// Tolerância padrão da verificação de energia dos termos fonte
+fn default_energy_check_tolerance() -> f64 {
+    0.01
+}
+
+/// Intervalos padrão da pirâmide temporal (a cada 10 e 100 quadros)
+fn default_temporal_pyramid_strides() -> Vec<usize> {
+    vec![10, 100]
 }
 
 impl SimulationParameters {
@@ -81,6 +475,27 @@ impl SimulationParameters {
             time_step: 1.0,
             time_steps: 100,
             zone_map: None,
+            energy_check_tolerance: default_energy_check_tolerance(),
+            property_cache: None,
+            frame_storage: FrameStorageConfig::default(),
+            temporal_pyramid_strides: default_temporal_pyramid_strides(),
+            axis_treatment: AxisTreatment::default(),
+            solver_scheme: SolverScheme::default(),
+            adaptive_time_step: None,
+            radial_grading: MeshGrading::Uniform,
+            axial_grading: MeshGrading::Uniform,
+            exit_criteria: Vec::new(),
+            averaging_windows: Vec::new(),
+            moisture_evaporation: None,
+            reactions: None,
+            participating_media: None,
+            convection_model: ConvectionModel::Constant,
+            advection: None,
+            bed_gas_exchange: None,
+            bed_consumption: None,
+            boundary_conditions: BoundaryConditions::default(),
+            formula_source: None,
+            power_control: None,
         }
     }
 
@@ -138,8 +553,27 @@ impl SimulationParameters {
         self.zone_map = Some(zone_map);
     }
 
+    /// Verifica se as programações de potência das tochas são executáveis
+    ///
+    /// Retorna as violações das restrições de partida de todas as tochas, em ordem de tempo.
+    pub fn check_torch_schedules(&self) -> Vec<ScheduleViolation> {
+        let mut violations: Vec<ScheduleViolation> = self.torches.iter()
+            .flat_map(|torch| torch.constrained_power_profile(self.time_step, self.time_steps).1)
+            .collect();
+        violations.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap_or(std::cmp::Ordering::Equal));
+        violations
+    }
+
     /// Valida os parâmetros da simulação
     pub fn validate(&self) -> Result<(), String> {
+        if self.torches.is_empty() {
+            return Err("Pelo menos uma tocha deve ser definida".to_string());
+        }
+        self.validate_setup()
+    }
+
+    /// Valida os parâmetros sem exigir tochas, que podem ser adicionadas depois da criação
+    pub fn validate_setup(&self) -> Result<(), String> {
         if self.height <= 0.0 {
             return Err("Altura deve ser positiva".to_string());
         }
@@ -155,15 +589,71 @@ impl SimulationParameters {
         if self.ntheta < 4 {
             return Err("Número de nós angulares deve ser pelo menos 4".to_string());
         }
-        if self.torches.is_empty() {
-            return Err("Pelo menos uma tocha deve ser definida".to_string());
-        }
         if self.time_step <= 0.0 {
             return Err("Passo de tempo deve ser positivo".to_string());
         }
         if self.total_time <= 0.0 {
             return Err("Tempo total deve ser positivo".to_string());
         }
+        if self.energy_check_tolerance < 0.0 {
+            return Err("Tolerância da verificação de energia não pode ser negativa".to_string());
+        }
+        if !self.frame_storage.retain_full_precision && !self.frame_storage.quantize_playback {
+            return Err("Descartar o histórico em precisão total requer quadros de reprodução quantizados".to_string());
+        }
+        if let Some(adaptive) = &self.adaptive_time_step {
+            adaptive.validate()?;
+        }
+        self.radial_grading.validate()?;
+        self.axial_grading.validate()?;
+        for criterion in &self.exit_criteria {
+            criterion.validate(self)?;
+        }
+        for window in &self.averaging_windows {
+            window.validate()?;
+        }
+        if let Some(moisture) = &self.moisture_evaporation {
+            moisture.validate()?;
+        }
+        if let Some(reactions) = &self.reactions {
+            reactions.validate()?;
+        }
+        if let Some(media) = &self.participating_media {
+            media.validate()?;
+        }
+        if let Some(advection) = &self.advection {
+            advection.validate()?;
+        }
+        if let Some(exchange) = &self.bed_gas_exchange {
+            exchange.validate()?;
+        }
+        if let Some(consumption) = &self.bed_consumption {
+            consumption.validate()?;
+        }
+        self.boundary_conditions.validate()?;
+        if let Some(term) = &self.formula_source {
+            term.validate()?;
+        }
+        if let Some(control) = &self.power_control {
+            control.validate()?;
+            for id in &control.torch_ids {
+                if !self.torches.iter().any(|torch| &torch.id == id) {
+                    return Err(format!("Tocha {} do controle de potência não encontrada", id));
+                }
+            }
+            for probe in &control.probes {
+                if probe.r < 0.0 || probe.r > self.radius || probe.z < 0.0 || probe.z > self.height {
+                    return Err(format!("Sonda '{}' do controle de potência fora do domínio", probe.name));
+                }
+            }
+        }
+        self.material.validate_packed_bed()?;
+        for torch in &self.torches {
+            if let Some(startup) = &torch.startup {
+                startup.validate().map_err(|e| format!("Tocha {}: {}", torch.id, e))?;
+            }
+            torch.flux_profile.validate().map_err(|e| format!("Tocha {}: {}", torch.id, e))?;
+        }
         
         // Validar posição das tochas
         for torch in &self.torches {
@@ -203,6 +693,47 @@ impl SimulationParameters {
     }
 }
 
+/// Estrutura que representa a amostragem de um histórico de temperatura decimado
+///
+/// Cada dimensão é amostrada nos índices 0, intervalo, 2·intervalo, ... e sempre inclui o
+/// último índice (parede lateral, topo e último passo executado).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryLayout {
+    /// Índices radiais amostrados
+    pub r_indices: Vec<usize>,
+    /// Índices axiais amostrados
+    pub z_indices: Vec<usize>,
+    /// Passos de tempo amostrados
+    pub steps: Vec<usize>,
+    /// Coordenadas radiais dos nós amostrados (m)
+    pub r_coords: Vec<f64>,
+    /// Coordenadas axiais dos nós amostrados (m)
+    pub z_coords: Vec<f64>,
+    /// Tempos dos passos amostrados (s)
+    pub times: Vec<f64>,
+}
+
+impl HistoryLayout {
+    /// Número de valores do histórico decimado (passos × nós radiais × nós axiais)
+    pub fn len(&self) -> usize {
+        self.steps.len() * self.r_indices.len() * self.z_indices.len()
+    }
+
+    /// Indica se o histórico decimado é vazio
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Índices 0, intervalo, 2·intervalo, ... de uma dimensão com `n` valores, incluindo o último
+fn strided_indices(n: usize, stride: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..n).step_by(stride).collect();
+    if n > 0 && indices.last() != Some(&(n - 1)) {
+        indices.push(n - 1);
+    }
+    indices
+}
+
 /// Estrutura que representa os resultados da simulação com suporte a materiais avançados
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulationResults {
@@ -220,6 +751,80 @@ pub struct SimulationResults {
     pub phase_change_info: Option<PhaseChangeInfo>,
     /// Número de passos de tempo efetivamente executados
     pub executed_steps: usize,
+    /// Verificações de energia dos termos fonte dos últimos passos (no máximo
+    /// `MAX_ENERGY_SOURCE_CHECKS`)
+    #[serde(default)]
+    pub energy_source_checks: VecDeque<EnergySourceCheck>,
+    /// Anotações da linha do tempo (eventos do solucionador e do usuário), ordenadas por tempo
+    #[serde(default)]
+    pub annotations: Vec<TimelineAnnotation>,
+    /// Quadros de reprodução quantizados em 16 bits (opcional)
+    #[serde(default)]
+    pub playback_frames: Option<QuantizedFrameHistory>,
+    /// Pirâmide temporal para navegação rápida na linha do tempo (opcional)
+    #[serde(default)]
+    pub temporal_pyramid: Option<TemporalPyramid>,
+    /// Violações das restrições de partida das tochas (potência solicitada não executável)
+    #[serde(default)]
+    pub schedule_violations: Vec<ScheduleViolation>,
+    /// Sequência dos subpassos efetivamente usados (s); vazia com passo fixo
+    #[serde(default)]
+    pub time_step_sequence: Vec<f64>,
+    /// Motivo do fim da execução
+    #[serde(default)]
+    pub stop_reason: StopReason,
+    /// Campos médios por janela e por ciclo
+    #[serde(default)]
+    pub averaged_fields: Vec<AveragedField>,
+    /// Resíduos, variações e limitadores acionados por passo
+    #[serde(default)]
+    pub convergence: ConvergenceMonitor,
+    /// Secagem da carga (umidade remanescente e água evaporada), se o modelo estiver habilitado
+    #[serde(default)]
+    pub moisture: Option<MoistureInfo>,
+    /// Conversão das reações da carga e calor de reação, se o modelo estiver habilitado
+    #[serde(default)]
+    pub reactions: Option<ReactionInfo>,
+    /// Nível do leito ao longo do tempo e células consumidas, se o consumo estiver habilitado
+    #[serde(default)]
+    pub bed_level: Option<BedLevelInfo>,
+    /// Massas de umidade, voláteis, carvão e cinzas e gás de exaustão liberado, se a
+    /// umidade ou as reações da carga estiverem habilitadas
+    #[serde(default)]
+    pub mass_balance: Option<MassBalance>,
+    /// Potência comandada pelo controle de potência ao fim de cada passo (kW), a partir do
+    /// passo 1, se o controle estiver habilitado
+    #[serde(default)]
+    pub controlled_power_history: Option<Vec<f64>>,
+}
+
+/// Número máximo de verificações de energia dos termos fonte mantidas (as mais antigas são descartadas)
+pub const MAX_ENERGY_SOURCE_CHECKS: usize = 1000;
+
+/// Estrutura que registra a verificação de energia dos termos fonte em um passo
+///
+/// Compara a integral do termo de deposição das tochas sobre a malha com a soma das
+/// potências nominais (após programação e degradação), detectando perdas ou excessos
+/// introduzidos pela discretização. Radiação e convecção são termos de troca que
+/// dependem do campo e são registrados apenas para referência.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnergySourceCheck {
+    /// Passo de tempo
+    pub step: usize,
+    /// Tempo (s)
+    pub time: f64,
+    /// Soma das potências nominais das tochas após a eficiência (programação e degradação aplicadas) (W)
+    pub nominal_power: f64,
+    /// Integral do termo de deposição das tochas (W)
+    pub integrated_torch_power: f64,
+    /// Integral do termo de radiação (W)
+    pub integrated_radiation: f64,
+    /// Integral do termo de convecção (W)
+    pub integrated_convection: f64,
+    /// Erro relativo entre a potência integrada das tochas e a nominal
+    pub relative_error: f64,
+    /// Indica se o erro excedeu a tolerância
+    pub flagged: bool,
 }
 
 /// Estrutura que armazena informações sobre mudanças de fase
@@ -232,6 +837,51 @@ pub struct PhaseChangeInfo {
 }
 
 impl SimulationResults {
+    /// Resultados mínimos para testes: malha dos parâmetros, entalpia igual à temperatura
+    /// e todos os passos do histórico `(nr, nz, passos)` executados
+    #[cfg(test)]
+    pub(crate) fn for_tests(parameters: SimulationParameters, temperature: Array3<f64>) -> Self {
+        let mesh = CylindricalMesh::new_graded(
+            parameters.height,
+            parameters.radius,
+            parameters.nr,
+            parameters.nz,
+            parameters.ntheta,
+            parameters.radial_grading,
+            parameters.axial_grading,
+        );
+        Self {
+            mesh,
+            enthalpy: temperature.clone(),
+            executed_steps: temperature.dim().2.saturating_sub(1),
+            temperature,
+            parameters,
+            execution_time: 0.0,
+            phase_change_info: None,
+            energy_source_checks: VecDeque::new(),
+            annotations: Vec::new(),
+            playback_frames: None,
+            temporal_pyramid: None,
+            schedule_violations: Vec::new(),
+            time_step_sequence: Vec::new(),
+            stop_reason: StopReason::Completed,
+            averaged_fields: Vec::new(),
+            convergence: ConvergenceMonitor::default(),
+            moisture: None,
+            reactions: None,
+            bed_level: None,
+            mass_balance: None,
+            controlled_power_history: None,
+        }
+    }
+
+    /// Adiciona uma anotação à linha do tempo dos resultados, mantendo a ordem por tempo
+    pub fn add_annotation(&mut self, annotation: TimelineAnnotation) -> Result<(), String> {
+        annotation.validate()?;
+        insert_annotation(&mut self.annotations, annotation);
+        Ok(())
+    }
+
     /// Gera dados de temperatura 3D para um passo de tempo específico
     pub fn generate_3d_temperature(&self, time_step: usize) -> Result<Array3<f64>, String> {
         if time_step > self.executed_steps {
@@ -245,7 +895,7 @@ impl SimulationResults {
 
         let mut temp_3d = Array3::<f64>::zeros((nr, ntheta, nz));
 
-        let temp_2d = self.temperature.slice(s![.., .., time_step]);
+        let temp_2d = self.temperature_at(time_step)?;
 
         for i in 0..nr {
             for k in 0..ntheta {
@@ -257,6 +907,153 @@ impl SimulationResults {
 
         Ok(temp_3d)
     }
+
+    /// Indica se o histórico de temperatura está completo em precisão total
+    pub fn has_full_precision_history(&self) -> bool {
+        self.temperature.shape()[2] > self.executed_steps
+    }
+
+    /// Retorna o campo de temperatura de um passo de tempo
+    ///
+    /// Usa o histórico em precisão total quando disponível; caso contrário,
+    /// reconstrói o quadro a partir da reprodução quantizada.
+    pub fn temperature_at(&self, step: usize) -> Result<Array2<f64>, String> {
+        if step > self.executed_steps {
+            return Err(format!("Passo de tempo {} fora dos limites [0, {}] executados",
+                               step, self.executed_steps));
+        }
+
+        if self.has_full_precision_history() {
+            return Ok(self.temperature.slice(s![.., .., step]).to_owned());
+        }
+
+        // Apenas o estado final é mantido em precisão total
+        if step == self.executed_steps {
+            let last = self.temperature.shape()[2] - 1;
+            return Ok(self.temperature.slice(s![.., .., last]).to_owned());
+        }
+
+        match &self.playback_frames {
+            Some(frames) => frames.frame(step),
+            None => Err(format!("Quadro {} não disponível: histórico em precisão total descartado", step)),
+        }
+    }
+
+    /// Temperatura em um ponto (r, z) e instante quaisquer (termopar virtual)
+    ///
+    /// Interpola bilinearmente no espaço, entre os nós da malha, e linearmente no tempo,
+    /// entre os passos de saída vizinhos. Pontos fora do domínio e instantes fora do
+    /// intervalo simulado [0, passos executados · dt] são rejeitados.
+    pub fn temperature_at_point(&self, r: f64, z: f64, time: f64) -> Result<f64, String> {
+        const TOLERANCE: f64 = 1e-9;
+        if !(r.is_finite() && z.is_finite() && time.is_finite()) {
+            return Err("Coordenadas e tempo da consulta devem ser finitos".to_string());
+        }
+        if r < -TOLERANCE || r > self.mesh.radius + TOLERANCE || z < -TOLERANCE || z > self.mesh.height + TOLERANCE {
+            return Err(format!("Ponto (r = {} m, z = {} m) fora do domínio", r, z));
+        }
+        let dt = self.parameters.time_step;
+        let end_time = self.executed_steps as f64 * dt;
+        if time < -TOLERANCE || time > end_time + TOLERANCE {
+            return Err(format!("Tempo {} s fora do intervalo simulado [0, {}] s", time, end_time));
+        }
+
+        let position = (time / dt).clamp(0.0, self.executed_steps as f64);
+        let before = position.floor() as usize;
+        let fraction = position - before as f64;
+        let value_before = self.mesh.interpolate(&self.temperature_at(before)?, r, z);
+        if fraction <= TOLERANCE || before == self.executed_steps {
+            return Ok(value_before);
+        }
+        let value_after = self.mesh.interpolate(&self.temperature_at(before + 1)?, r, z);
+        Ok(value_before + (value_after - value_before) * fraction)
+    }
+
+    /// Calcula o fluxo de calor q = −k∇T (W/m²) de um passo de tempo
+    ///
+    /// Retorna as componentes radial e axial nos nós (nr, nz). A condutividade de cada
+    /// nó é a do seu material na temperatura local; o gradiente usa diferenças centradas
+    /// no interior (válidas em malhas graduadas) e unilaterais nas bordas. No eixo, a
+    /// componente radial é nula por simetria.
+    pub fn heat_flux_at(&self, step: usize) -> Result<(Array2<f64>, Array2<f64>), String> {
+        let temperature = self.temperature_at(step)?;
+        let (materials, material_index) = resolve_cell_materials(&self.parameters);
+        let r = &self.mesh.r_coords;
+        let z = &self.mesh.z_coords;
+        let (nr, nz) = temperature.dim();
+
+        let derivative = |coords: &Array1<f64>, values: &dyn Fn(usize) -> f64, n: usize, k: usize| -> f64 {
+            if n < 2 {
+                return 0.0;
+            }
+            let (a, b) = if k == 0 { (0, 1) } else if k == n - 1 { (n - 2, n - 1) } else { (k - 1, k + 1) };
+            (values(b) - values(a)) / (coords[b] - coords[a])
+        };
+
+        let mut radial = Array2::<f64>::zeros((nr, nz));
+        let mut axial = Array2::<f64>::zeros((nr, nz));
+        for i in 0..nr {
+            for j in 0..nz {
+                let k = materials[material_index[[i, j]]].get_thermal_conductivity(temperature[[i, j]]);
+                if i > 0 {
+                    radial[[i, j]] = -k * derivative(r, &|index| temperature[[index, j]], nr, i);
+                }
+                axial[[i, j]] = -k * derivative(z, &|index| temperature[[i, index]], nz, j);
+            }
+        }
+        Ok((radial, axial))
+    }
+
+    /// Retorna o quadro mais próximo (anterior ou igual) ao passo no nível de intervalo informado
+    ///
+    /// O intervalo 1 corresponde ao histórico completo; os demais vêm da pirâmide temporal.
+    pub fn pyramid_frame(&self, stride: usize, step: usize) -> Result<(usize, Array2<f64>), String> {
+        if stride <= 1 {
+            let step = step.min(self.executed_steps);
+            return Ok((step, self.temperature_at(step)?));
+        }
+
+        match &self.temporal_pyramid {
+            Some(pyramid) => pyramid.nearest_frame(stride, step.min(self.executed_steps)),
+            None => Err("Pirâmide temporal não disponível nestes resultados".to_string()),
+        }
+    }
+
+    /// Retorna a amostragem do histórico decimado pelos intervalos em r, z e tempo (≥ 1)
+    pub fn history_layout(&self, stride_r: usize, stride_z: usize, stride_t: usize) -> Result<HistoryLayout, String> {
+        if stride_r == 0 || stride_z == 0 || stride_t == 0 {
+            return Err(format!("Intervalos de decimação devem ser positivos: r = {}, z = {}, t = {}", stride_r, stride_z, stride_t));
+        }
+        let r_indices = strided_indices(self.mesh.nr, stride_r);
+        let z_indices = strided_indices(self.mesh.nz, stride_z);
+        let steps = strided_indices(self.executed_steps + 1, stride_t);
+        Ok(HistoryLayout {
+            r_coords: r_indices.iter().map(|&i| self.mesh.r_coords[i]).collect(),
+            z_coords: z_indices.iter().map(|&j| self.mesh.z_coords[j]).collect(),
+            times: steps.iter().map(|&step| step as f64 * self.parameters.time_step).collect(),
+            r_indices,
+            z_indices,
+            steps,
+        })
+    }
+
+    /// Histórico de temperatura decimado (passos, nós radiais, nós axiais) em uma única cópia
+    ///
+    /// Útil para linhas do tempo na interface, que não precisam de todos os passos nem de
+    /// todos os nós. Passos fora do histórico em precisão total vêm da reprodução quantizada.
+    pub fn decimated_history(&self, stride_r: usize, stride_z: usize, stride_t: usize) -> Result<(HistoryLayout, Array3<f64>), String> {
+        let layout = self.history_layout(stride_r, stride_z, stride_t)?;
+        let mut history = Array3::<f64>::zeros((layout.steps.len(), layout.r_indices.len(), layout.z_indices.len()));
+        for (k, &step) in layout.steps.iter().enumerate() {
+            let field = self.temperature_at(step)?;
+            for (a, &i) in layout.r_indices.iter().enumerate() {
+                for (b, &j) in layout.z_indices.iter().enumerate() {
+                    history[[k, a, b]] = field[[i, j]];
+                }
+            }
+        }
+        Ok((layout, history))
+    }
 }
 
 /// Estrutura que representa o solucionador da equação de calor com suporte a materiais avançados
@@ -283,8 +1080,65 @@ pub struct HeatSolver {
     vapor_fraction_history: Option<Array3<f64>>,
     /// Passo de tempo atual
     current_step: usize,
-    /// Biblioteca de materiais (para acesso fácil às propriedades)
-    material_library: MaterialLibrary,
+    /// Verificações de energia dos termos fonte registradas durante a execução
+    energy_source_checks: VecDeque<EnergySourceCheck>,
+    /// Materiais distintos usados na malha (índice 0 = material principal)
+    cell_materials: Vec<MaterialProperties>,
+    /// Índice do material de cada célula em `cell_materials` (nr, nz)
+    cell_material_index: Array2<usize>,
+    /// Caches de propriedades por material, alinhados a `cell_materials` (opcional)
+    property_caches: Option<Vec<PropertyCache>>,
+    /// Calores específicos por fase das conversões H ↔ T, alinhados a `cell_materials`
+    phase_specific_heats: Vec<PhaseSpecificHeats>,
+    /// Anotações da linha do tempo registradas durante a execução
+    annotations: Vec<TimelineAnnotation>,
+    /// Potências efetivas das tochas no passo anterior (kW), para detectar mudanças
+    previous_torch_powers: Vec<f64>,
+    /// Pirâmide temporal construída durante o armazenamento do histórico (opcional)
+    temporal_pyramid: Option<TemporalPyramid>,
+    /// Passo inicial do laço de simulação (diferente de zero ao retomar de um ponto de controle)
+    start_step: usize,
+    /// Potência executável de cada tocha por passo (kW), após as restrições de partida
+    torch_power_profiles: Vec<Vec<f64>>,
+    /// Violações das restrições de partida das tochas
+    schedule_violations: Vec<ScheduleViolation>,
+    /// Subpasso corrente do controle adaptativo (s)
+    adaptive_dt: f64,
+    /// Subpassos aceitos pelo controle adaptativo (s)
+    time_step_sequence: Vec<f64>,
+    /// Passos consecutivos em que cada critério de regime permanente foi satisfeito
+    steady_state_counters: Vec<usize>,
+    /// Acumulador das médias temporais
+    averaging: AveragingAccumulator,
+    /// Monitor de resíduos e convergência
+    convergence: ConvergenceMonitor,
+    /// Umidade remanescente das células (modelo de evaporação, opcional)
+    moisture: Option<MoistureModel>,
+    /// Fração convertida das reações da carga (opcional)
+    reactions: Option<ReactionModel>,
+    /// Componentes (u_r, u_z) do escoamento prescrito nos nós (opcional)
+    advection_velocity: Option<(Array2<f64>, Array2<f64>)>,
+    /// Células consumidas do leito e altura da superfície (opcional)
+    bed_level: Option<BedLevelModel>,
+    /// Gás de exaustão liberado pela carga (com umidade ou reações)
+    mass_balance: Option<MassBalanceTracker>,
+    /// Motor com a fórmula da fonte volumétrica do usuário (opcional)
+    formula_source: Option<FormulaManager>,
+    /// Controlador de potência das tochas por fórmula e índices das tochas controladas (opcional)
+    power_control: Option<(PowerController, Vec<usize>)>,
+    /// Buffer de quadros ao vivo compartilhado e quadro de escrita do solucionador (opcional)
+    live_frames: Option<(Arc<LiveFrameBuffer>, LiveFrame)>,
+}
+
+/// Cópia do estado evolutivo do solucionador, usada para rejeitar subpassos
+struct SolverStateSnapshot {
+    enthalpy: Array2<f64>,
+    temperature: Array2<f64>,
+    melt_fraction: Option<Array2<f64>>,
+    vapor_fraction: Option<Array2<f64>>,
+    moisture: Option<Array2<f64>>,
+    reactions: Option<ReactionModel>,
+    bed_level: Option<BedLevelModel>,
 }
 
 impl HeatSolver {
@@ -294,12 +1148,14 @@ impl HeatSolver {
         params.validate()?;
         
         // Criar malha
-        let mesh = CylindricalMesh::new(
+        let mesh = CylindricalMesh::new_graded(
             params.height, 
             params.radius, 
             params.nr, 
             params.nz, 
-            params.ntheta
+            params.ntheta,
+            params.radial_grading,
+            params.axial_grading,
         );
         
         // Inicializar campo de temperatura (será sobrescrito pelo cálculo da entalpia)
@@ -312,10 +1168,14 @@ impl HeatSolver {
         let mut enthalpy = Array2::<f64>::zeros((params.nr, params.nz));
         let mut enthalpy_history = Array3::<f64>::zeros((params.nr, params.nz, params.time_steps + 1));
         
+        // Resolver o material de cada célula a partir do mapa de zonas
+        let (cell_materials, cell_material_index) = resolve_cell_materials(&params);
+        
         // Inicializar arrays de mudança de fase se necessário
-        let (mut melt_fraction, melt_fraction_history, mut vapor_fraction, vapor_fraction_history) = 
-            if params.enable_phase_changes && 
-               (params.material.melting_point.is_some() || params.material.vaporization_point.is_some()) {
+        let has_phase_change = cell_materials.iter()
+            .any(|m| m.melting_point.is_some() || m.vaporization_point.is_some());
+        let (melt_fraction, mut melt_fraction_history, vapor_fraction, mut vapor_fraction_history) = 
+            if params.enable_phase_changes && has_phase_change {
                 
                 let mut melt_fraction = Array2::<f64>::zeros((params.nr, params.nz));
                 let melt_fraction_history = Array3::<f64>::zeros((params.nr, params.nz, params.time_steps + 1));
@@ -323,16 +1183,22 @@ impl HeatSolver {
                 let mut vapor_fraction = Array2::<f64>::zeros((params.nr, params.nz));
                 let vapor_fraction_history = Array3::<f64>::zeros((params.nr, params.nz, params.time_steps + 1));
                 
-                if let Some(tm) = params.material.melting_point {
-                    if params.initial_temperature >= tm {
-                        melt_fraction.fill(1.0);
-                        if let Some(tv) = params.material.vaporization_point {
-                             if params.initial_temperature >= tv {
-                                vapor_fraction.fill(1.0);
-                             }
+                Zip::from(&mut melt_fraction)
+                    .and(&mut vapor_fraction)
+                    .and(&cell_material_index)
+                    .for_each(|mf, vf, &idx| {
+                        let material = &cell_materials[idx];
+                        if let Some(tm) = material.melting_point {
+                            if params.initial_temperature >= tm {
+                                *mf = 1.0;
+                                if let Some(tv) = material.vaporization_point {
+                                    if params.initial_temperature >= tv {
+                                        *vf = 1.0;
+                                    }
+                                }
+                            }
                         }
-                    }
-                }
+                    });
 
                 (Some(melt_fraction), Some(melt_fraction_history), 
                  Some(vapor_fraction), Some(vapor_fraction_history))
@@ -340,8 +1206,21 @@ impl HeatSolver {
                 (None, None, None, None)
             };
         
-        // Criar biblioteca de materiais para uso interno
-        let material_library = MaterialLibrary::new();
+        // Tabelar as propriedades de cada material por faixas de temperatura
+        let property_caches = match &params.property_cache {
+            Some(config) => Some(
+                cell_materials.iter()
+                    .map(|material| PropertyCache::new(material, config))
+                    .collect::<Result<Vec<_>, String>>()?
+            ),
+            None => None,
+        };
+        let phase_specific_heats: Vec<PhaseSpecificHeats> = match &property_caches {
+            Some(caches) => caches.iter()
+                .map(|cache| PhaseSpecificHeats::new(cache.material(), 0.0, |t| cache.get_specific_heat(t)))
+                .collect(),
+            None => cell_materials.iter().map(|material| PhaseSpecificHeats::of(material, 0.0)).collect(),
+        };
         
         // Calcular entalpia inicial a partir da temperatura inicial
         let initial_melt_fraction = melt_fraction.as_ref().cloned().unwrap_or_else(|| Array2::zeros((params.nr, params.nz)));
@@ -350,20 +1229,28 @@ impl HeatSolver {
         Zip::from(&mut enthalpy)
             .and(&initial_melt_fraction)
             .and(&initial_vapor_fraction)
-            .par_for_each(|h, &mf, &vf| {
-                *h = calculate_enthalpy_from_temperature(
+            .and(&cell_material_index)
+            .par_for_each(|h, &mf, &vf, &idx| {
+                *h = calculate_enthalpy_with_specific_heats(
                     params.initial_temperature,
                     mf,
                     vf,
-                    &params.material,
+                    &cell_materials[idx],
+                    &phase_specific_heats[idx],
                     0.0
                 );
             });
 
         Zip::from(&mut temperature)
             .and(&enthalpy)
-            .par_for_each(|t, &h| {
-                let (temp, _, _) = calculate_temperature_and_fractions(h, &params.material, 0.0);
+            .and(&cell_material_index)
+            .par_for_each(|t, &h, &idx| {
+                let (temp, _, _) = calculate_temperature_and_fractions_with_specific_heats(
+                    h,
+                    &cell_materials[idx],
+                    &phase_specific_heats[idx],
+                    0.0,
+                );
                 *t = temp;
             });
 
@@ -381,6 +1268,47 @@ impl HeatSolver {
             }
         }
 
+        // Potências executáveis das tochas sob as restrições de partida
+        let torch_power_profiles: Vec<Vec<f64>> = params.torches.iter()
+            .map(|torch| torch.constrained_power_profile(params.time_step, params.time_steps).0)
+            .collect();
+        let schedule_violations = params.check_torch_schedules();
+        for violation in &schedule_violations {
+            warn!("Tocha {}: programação não executável ({:?}) entre {:.1} s e {:.1} s (desvio máximo {:.1} kW)",
+                  violation.torch_id, violation.kind, violation.start_time, violation.end_time, violation.max_deviation);
+        }
+
+        let exit_criteria_count = params.exit_criteria.len();
+        let averaging = AveragingAccumulator::new(&params.averaging_windows);
+        let moisture = params.moisture_evaporation.as_ref().map(|config| {
+            MoistureModel::new(config, &mesh, &cell_materials, &cell_material_index, |t, material| {
+                let melted = material.melting_point.map_or(0.0, |tm| if t >= tm { 1.0 } else { 0.0 });
+                let vaporized = material.vaporization_point.map_or(0.0, |tv| if t >= tv { 1.0 } else { 0.0 });
+                calculate_enthalpy_from_temperature(t, melted, vaporized, material, 0.0)
+            })
+        });
+        let advection_velocity = params.advection.as_ref().map(|config| config.velocity_at_nodes(&mesh));
+        let bed_level = params.bed_consumption.as_ref().map(|config| BedLevelModel::new(config, &mesh));
+        let reactions = params.reactions.as_ref().map(|config| {
+            ReactionModel::new(config, &mesh, &cell_materials, &cell_material_index, params.initial_temperature)
+        });
+        let mass_balance = (moisture.is_some() || reactions.is_some()).then(|| {
+            MassBalanceTracker::new(&mesh, &cell_materials, &cell_material_index, params.initial_temperature, params.time_step)
+        });
+        let formula_source = params.formula_source.as_ref()
+            .map(FormulaManager::from_source_term)
+            .transpose()?;
+        let power_control = match &params.power_control {
+            Some(config) => {
+                let controlled = params.torches.iter().enumerate()
+                    .filter(|(_, torch)| config.torch_ids.is_empty() || config.torch_ids.contains(&torch.id))
+                    .map(|(index, _)| index)
+                    .collect();
+                Some((PowerController::new(config, &mesh, &temperature)?, controlled))
+            }
+            None => None,
+        };
+
         // Configurar mapa de zonas, se fornecido
         let mut solver = Self {
             params,
@@ -394,8 +1322,44 @@ impl HeatSolver {
             vapor_fraction,
             vapor_fraction_history,
             current_step: 0,
-            material_library,
+            energy_source_checks: VecDeque::new(),
+            cell_materials,
+            cell_material_index,
+            property_caches,
+            phase_specific_heats,
+            annotations: Vec::new(),
+            previous_torch_powers: Vec::new(),
+            temporal_pyramid: None,
+            start_step: 0,
+            torch_power_profiles,
+            schedule_violations,
+            adaptive_dt: 0.0,
+            time_step_sequence: Vec::new(),
+            steady_state_counters: vec![0; exit_criteria_count],
+            averaging,
+            convergence: ConvergenceMonitor::new(),
+            moisture,
+            reactions,
+            advection_velocity,
+            bed_level,
+            mass_balance,
+            formula_source,
+            power_control,
+            live_frames: None,
         };
+        solver.adaptive_dt = solver.params.time_step;
+
+        if !solver.params.temporal_pyramid_strides.is_empty() {
+            let mut pyramid = TemporalPyramid::new(
+                solver.params.nr,
+                solver.params.nz,
+                solver.params.time_step,
+                &solver.params.temporal_pyramid_strides,
+            );
+            pyramid.record(0, solver.temperature.view());
+            solver.temporal_pyramid = Some(pyramid);
+        }
+        solver.averaging.record(0.0, &solver.temperature);
         
         if let Some(zone_map) = &solver.params.zone_map {
             solver.mesh.set_zones(zone_map.clone());
@@ -404,24 +1368,176 @@ impl HeatSolver {
         Ok(solver)
     }
     
+    /// Publica o campo de temperatura de cada passo concluído no buffer compartilhado,
+    /// para visualização durante a execução; o quadro do passo corrente é publicado já
+    pub fn set_live_frame_buffer(&mut self, buffer: Arc<LiveFrameBuffer>) {
+        let mut frame = LiveFrame::new(self.params.nr, self.params.nz);
+        frame.step = self.start_step;
+        frame.time = self.start_step as f64 * self.params.time_step;
+        frame.temperature.assign(&self.temperature);
+        buffer.publish(&mut frame);
+        self.live_frames = Some((buffer, frame));
+    }
+
+    /// Publica o campo de temperatura atual como quadro do passo `step`
+    fn publish_live_frame(&mut self, step: usize) {
+        if let Some((buffer, frame)) = self.live_frames.as_mut() {
+            frame.step = step;
+            frame.time = step as f64 * self.params.time_step;
+            frame.temperature.assign(&self.temperature);
+            buffer.publish(frame);
+        }
+    }
+
+    /// Cria um solucionador que retoma a partir de um passo de resultados anteriores
+    ///
+    /// O histórico até `restart_step` (inclusive) é copiado dos resultados anteriores,
+    /// que precisam ter o histórico completo em precisão total, e o laço de simulação
+    /// continua a partir desse passo com os novos parâmetros.
+    pub fn resume_from(
+        params: SimulationParameters,
+        previous: &SimulationResults,
+        restart_step: usize,
+    ) -> Result<Self, String> {
+        if !previous.has_full_precision_history() {
+            return Err("Retomada requer resultados anteriores com histórico em precisão total".to_string());
+        }
+        if previous.parameters.nr != params.nr || previous.parameters.nz != params.nz {
+            return Err("Retomada requer a mesma malha dos resultados anteriores".to_string());
+        }
+        if (previous.parameters.time_step - params.time_step).abs() > 1e-12 {
+            return Err("Retomada requer o mesmo passo de tempo dos resultados anteriores".to_string());
+        }
+        let restart_step = restart_step.min(previous.executed_steps).min(params.time_steps);
+
+        let mut solver = Self::new(params)?;
+        if restart_step == 0 {
+            return Ok(solver);
+        }
+
+        let copied = s![.., .., 0..=restart_step];
+        solver.temperature_history.slice_mut(copied).assign(&previous.temperature.slice(copied));
+        solver.enthalpy_history.slice_mut(copied).assign(&previous.enthalpy.slice(copied));
+        if let Some(info) = &previous.phase_change_info {
+            if let (Some(history), Some(previous_history)) = (solver.melt_fraction_history.as_mut(), info.melt_fraction.as_ref()) {
+                history.slice_mut(copied).assign(&previous_history.slice(copied));
+            }
+            if let (Some(history), Some(previous_history)) = (solver.vapor_fraction_history.as_mut(), info.vapor_fraction.as_ref()) {
+                history.slice_mut(copied).assign(&previous_history.slice(copied));
+            }
+        }
+
+        // Estado atual a partir da entalpia do ponto de retomada
+        solver.enthalpy.assign(&previous.enthalpy.slice(s![.., .., restart_step]));
+        solver.update_temperature_and_fractions_from_enthalpy()?;
+
+        // A umidade não é armazenada por passo: células acima do patamar de evaporação
+        // no ponto de retomada são consideradas secas
+        if let Some(model) = solver.moisture.as_mut() {
+            model.mark_dry_above(&solver.temperature);
+            if let Some(info) = &previous.moisture {
+                model.resume_history(&info.evaporation_history[..restart_step.min(info.evaporation_history.len())]);
+            }
+        }
+
+        // A conversão também não é armazenada por passo: só é retomada a partir do fim
+        // da execução anterior; antes disso a carga recomeça não convertida
+        if let Some(model) = solver.reactions.as_mut() {
+            match &previous.reactions {
+                Some(info) if restart_step == previous.executed_steps => model.resume(info, restart_step),
+                Some(_) => warn!("Retomada no passo {}: conversão das reações reiniciada a partir de zero", restart_step),
+                None => {}
+            }
+        }
+        if let Some(model) = solver.bed_level.as_mut() {
+            match &previous.bed_level {
+                Some(info) if restart_step == previous.executed_steps => model.resume(info, restart_step + 1),
+                Some(_) => warn!("Retomada no passo {}: leito reiniciado sem células consumidas", restart_step),
+                None => {}
+            }
+        }
+        if let (Some(tracker), Some(info)) = (solver.mass_balance.as_mut(), previous.mass_balance.as_ref()) {
+            tracker.resume_history(&info.off_gas_history[..restart_step.min(info.off_gas_history.len())]);
+        }
+
+        if let Some(pyramid) = solver.temporal_pyramid.as_mut() {
+            for step in 1..=restart_step {
+                pyramid.record(step, previous.temperature.slice(s![.., .., step]));
+            }
+        }
+
+        // Médias temporais reconstruídas a partir do histórico copiado
+        solver.averaging = AveragingAccumulator::new(&solver.params.averaging_windows);
+        for step in 0..=restart_step {
+            let field = previous.temperature.slice(s![.., .., step]).to_owned();
+            solver.averaging.record(step as f64 * solver.params.time_step, &field);
+        }
+
+        // Registros anteriores ao ponto de retomada continuam válidos
+        solver.energy_source_checks = previous.energy_source_checks.iter()
+            .filter(|check| check.step < restart_step)
+            .cloned()
+            .collect();
+        solver.annotations = previous.annotations.iter()
+            .filter(|annotation| annotation.step.is_none_or(|step| step < restart_step))
+            .cloned()
+            .collect();
+        let restart_time = restart_step as f64 * solver.params.time_step;
+        let mut elapsed = 0.0;
+        solver.time_step_sequence = previous.time_step_sequence.iter()
+            .take_while(|&&dt| {
+                elapsed += dt;
+                elapsed <= restart_time + 1e-9
+            })
+            .cloned()
+            .collect();
+        // Potências do último passo copiado, para as anotações de mudança de potência
+        solver.previous_torch_powers = solver.torches_at_step(restart_step - 1).iter().map(|torch| torch.power).collect();
+        solver.start_step = restart_step;
+        solver.current_step = restart_step;
+
+        Ok(solver)
+    }
+
     /// Executa a simulação completa
     /// `progress_callback`: Fn(progress: f32) -> bool (return false to cancel)
     /// `cancel_flag`: Atomic flag checked for external cancellation requests
+    ///
+    /// O cancelamento é verificado a cada passo de tempo e não é um erro: a execução
+    /// retorna os resultados parciais com `StopReason::Cancelled`.
     pub fn run(
         &mut self,
         progress_callback: Option<&dyn Fn(f32) -> bool>,
         cancel_flag: Arc<AtomicBool>,
+    ) -> Result<SimulationResults, String> {
+        match progress_callback {
+            Some(callback) => self.run_with_updates(Some(&|update: &ProgressUpdate| callback(update.progress)), cancel_flag),
+            None => self.run_with_updates(None, cancel_flag),
+        }
+    }
+
+    /// Executa a simulação completa, notificando a cada passo o passo concluído e o
+    /// tempo decorrido desde o início da execução
+    ///
+    /// Em uma execução retomada (`resume_from`), `current_step` é o passo absoluto da
+    /// simulação, a partir do passo de reinício. O callback retorna `false` para
+    /// cancelar, como em `run`.
+    pub fn run_with_updates(
+        &mut self,
+        progress_callback: Option<&dyn Fn(&ProgressUpdate) -> bool>,
+        cancel_flag: Arc<AtomicBool>,
     ) -> Result<SimulationResults, String> {
         let start_time = Instant::now();
 
         info!("Iniciando simulação com {} passos de tempo, {} tochas e material: {}",
               self.params.time_steps, self.params.torches.len(), self.params.material.name);
 
-        let mut executed_steps = 0;
+        let mut executed_steps = self.start_step;
         let mut cancelled = false;
+        let mut stop_reason = StopReason::Completed;
 
         // Loop principal de simulação
-        for step in 0..self.params.time_steps {
+        for step in self.start_step..self.params.time_steps {
             // Check for external cancellation request
             if cancel_flag.load(Ordering::Relaxed) {
                 warn!("Cancelamento solicitado externamente no passo {}", step);
@@ -431,20 +1547,66 @@ impl HeatSolver {
 
             self.current_step = step;
             executed_steps = step + 1; // Track completed steps
+            let previous_temperature = self.temperature.clone();
+            let previous_enthalpy = self.enthalpy.clone();
 
             // Calcular termos fonte (baseado na temperatura do passo anterior T^n)
             let sources = self.calculate_sources();
+            self.verify_source_energy(&sources);
+            self.annotate_power_changes();
+
+            if let Some(adaptive) = self.params.adaptive_time_step.clone() {
+                // Subpassos adaptativos até completar o passo de saída
+                if let Err(e) = self.advance_adaptive(&adaptive) {
+                    error!("Erro ao resolver passo de tempo {}: {}", step, e);
+                    return Err(format!("Erro no passo {}: {}", step, e));
+                }
+            } else {
+                // Resolver um passo de tempo para a Entalpia H^{n+1}
+                let mut sources = sources;
+                self.add_reaction_sources(&mut sources, self.params.time_step);
+                if let Err(e) = self.add_formula_sources(&mut sources, step as f64 * self.params.time_step) {
+                    error!("Erro ao avaliar a fonte volumétrica no passo {}: {}", step, e);
+                    return Err(format!("Erro no passo {}: {}", step, e));
+                }
+                if let Err(e) = self.solve_enthalpy_time_step(&sources, self.params.time_step) {
+                    error!("Erro ao resolver passo de tempo {}: {}", step, e);
+                    return Err(format!("Erro no passo {}: {}", step, e));
+                }
+                self.apply_moisture_evaporation();
 
-            // Resolver um passo de tempo para a Entalpia H^{n+1}
-            if let Err(e) = self.solve_enthalpy_time_step(&sources) {
-                error!("Erro ao resolver passo de tempo {}: {}", step, e);
-                return Err(format!("Erro no passo {}: {}", step, e));
+                // Atualizar Temperatura e Frações de Fase a partir da Entalpia H^{n+1}
+                if let Err(e) = self.update_temperature_and_fractions_from_enthalpy() {
+                     error!("Erro ao atualizar temperatura/fração no passo {}: {}", step, e);
+                     return Err(format!("Erro na atualização T/fração no passo {}: {}", step, e));
+                }
+                self.apply_bed_consumption();
             }
 
-            // Atualizar Temperatura e Frações de Fase a partir da Entalpia H^{n+1}
-            if let Err(e) = self.update_temperature_and_fractions_from_enthalpy() {
-                 error!("Erro ao atualizar temperatura/fração no passo {}: {}", step, e);
-                 return Err(format!("Erro na atualização T/fração no passo {}: {}", step, e));
+            self.convergence.record_step(
+                step + 1,
+                (step + 1) as f64 * self.params.time_step,
+                self.params.time_step,
+                &previous_temperature,
+                &self.temperature,
+                &previous_enthalpy,
+                &self.enthalpy,
+            );
+            if let Some(model) = self.moisture.as_mut() {
+                model.record_step();
+            }
+            if let Some(model) = self.reactions.as_mut() {
+                model.record_step();
+            }
+            if let Some(model) = self.bed_level.as_mut() {
+                model.record_step(&self.mesh);
+            }
+            if let Some(tracker) = self.mass_balance.as_mut() {
+                tracker.record_step(self.moisture.as_ref(), self.reactions.as_ref());
+            }
+            if let Err(e) = self.apply_power_control(step) {
+                error!("Erro ao avaliar o controle de potência no passo {}: {}", step, e);
+                return Err(format!("Erro no passo {}: {}", step, e));
             }
 
             // Armazenar resultado no histórico
@@ -452,6 +1614,12 @@ impl HeatSolver {
             if step + 1 < self.enthalpy_history.shape()[2] {
                  self.enthalpy_history.slice_mut(s![.., .., step + 1]).assign(&self.enthalpy);
                  self.temperature_history.slice_mut(s![.., .., step + 1]).assign(&self.temperature);
+                 if let Some(pyramid) = self.temporal_pyramid.as_mut() {
+                     pyramid.record(step + 1, self.temperature.view());
+                 }
+                 if !self.averaging.is_empty() {
+                     self.averaging.record((step + 1) as f64 * self.params.time_step, &self.temperature);
+                 }
 
                  // Armazenar frações de mudança de fase no histórico, se necessário
                  if let Some(melt_fraction) = &self.melt_fraction {
@@ -473,10 +1641,17 @@ impl HeatSolver {
                  warn!("Índice do histórico ({}) fora dos limites ({}) no passo {}", step + 1, self.enthalpy_history.shape()[2], step);
             }
 
+            self.publish_live_frame(step + 1);
+
             // Reportar progresso e check for cancellation from callback
             if let Some(callback) = progress_callback {
-                let progress = (step + 1) as f32 / self.params.time_steps as f32;
-                if !callback(progress) { // Callback returns false to signal cancellation
+                let update = ProgressUpdate {
+                    progress: (step + 1) as f32 / self.params.time_steps as f32,
+                    current_step: step + 1,
+                    total_steps: self.params.time_steps,
+                    elapsed_seconds: start_time.elapsed().as_secs_f64(),
+                };
+                if !callback(&update) { // Callback returns false to signal cancellation
                     warn!("Cancelamento solicitado pelo callback no passo {}", step);
                     cancelled = true;
                     break;
@@ -486,14 +1661,38 @@ impl HeatSolver {
             if (step + 1) % 10 == 0 || step + 1 == self.params.time_steps {
                  info!("Passo de tempo {}/{} concluído", step + 1, self.params.time_steps);
             }
-        }
-
-        let execution_time = start_time.elapsed().as_secs_f64();
+
+            // Critérios de parada antecipada
+            if let Some(reason) = self.check_exit_criteria(step + 1) {
+                if let StopReason::CriterionMet { description, time, .. } = &reason {
+                    info!("Critério de parada satisfeito no passo {}: {}", step + 1, description);
+                    let annotation = TimelineAnnotation::from_solver(
+                        step + 1,
+                        *time,
+                        AnnotationKind::Note,
+                        &format!("Parada antecipada: {}", description),
+                    );
+                    insert_annotation(&mut self.annotations, annotation);
+                }
+                stop_reason = reason;
+                break;
+            }
+        }
+
+        let execution_time = start_time.elapsed().as_secs_f64();
 
         if cancelled {
              warn!("Simulação cancelada após {} passos. Tempo de execução: {:.2} segundos", executed_steps, execution_time);
-             // Return Err to indicate cancellation, state will be updated by the calling thread
-             return Err("Simulation cancelled".to_string());
+             // Resultados parciais com os passos já integrados
+             let time = executed_steps as f64 * self.params.time_step;
+             let annotation = TimelineAnnotation::from_solver(
+                 executed_steps,
+                 time,
+                 AnnotationKind::Note,
+                 "Simulação cancelada",
+             );
+             insert_annotation(&mut self.annotations, annotation);
+             stop_reason = StopReason::Cancelled { time };
         } else {
              info!("Simulação concluída em {:.2} segundos após {} passos", execution_time, executed_steps);
         }
@@ -520,6 +1719,21 @@ impl HeatSolver {
             None
         };
 
+        // Quadros de reprodução quantizados e descarte opcional do histórico em precisão total
+        let playback_frames = if self.params.frame_storage.quantize_playback {
+            Some(QuantizedFrameHistory::from_history(&temp_history, self.params.time_step))
+        } else {
+            None
+        };
+        let (temp_history, enthalpy_history) = if self.params.frame_storage.retain_full_precision {
+            (temp_history, enthalpy_history)
+        } else {
+            (
+                temp_history.slice(s![.., .., executed_steps..final_history_steps]).to_owned(),
+                enthalpy_history.slice(s![.., .., executed_steps..final_history_steps]).to_owned(),
+            )
+        };
+
         // Criar resultados
         let results = SimulationResults {
             parameters: self.params.clone(),
@@ -528,7 +1742,21 @@ impl HeatSolver {
             enthalpy: enthalpy_history,
             execution_time,
             phase_change_info,
-            executed_steps: executed_steps,
+            executed_steps,
+            energy_source_checks: self.energy_source_checks.clone(),
+            annotations: self.annotations.clone(),
+            playback_frames,
+            temporal_pyramid: self.temporal_pyramid.clone(),
+            schedule_violations: self.schedule_violations.clone(),
+            time_step_sequence: self.time_step_sequence.clone(),
+            stop_reason,
+            averaged_fields: self.averaging.finish(),
+            convergence: self.convergence.clone(),
+            moisture: self.moisture.as_ref().map(|model| model.finish()),
+            reactions: self.reactions.as_ref().map(|model| model.finish()),
+            bed_level: self.bed_level.as_ref().map(|model| model.finish(&self.mesh)),
+            mass_balance: self.mass_balance.as_ref().map(|tracker| tracker.finish(self.moisture.as_ref(), self.reactions.as_ref())),
+            controlled_power_history: self.power_control.as_ref().map(|(controller, _)| controller.history().to_vec()),
         };
 
         Ok(results)
@@ -538,50 +1766,481 @@ impl HeatSolver {
     fn calculate_sources(&self) -> HeatSources {
         let mut sources = HeatSources::new(self.params.nr, self.params.nz);
         
+        // Tochas com potência programada e parâmetros degradados para o tempo atual
+        let torches = self.effective_torches();
+        
         // Calcular termo fonte de radiação
         if self.params.enable_radiation {
             sources.radiation = calculate_radiation_source(
                 &self.mesh,
-                &self.params.torches,
+                &torches,
                 &self.temperature,
-                &self.params.material,
+                &self.cell_materials,
+                &self.cell_material_index,
             );
+            // Absorção e reemissão pelo meio participante
+            if let Some(media) = &self.params.participating_media {
+                sources.radiation += &calculate_participating_media_source(media, &self.mesh, &self.temperature);
+            }
         }
         
         // Calcular termo fonte de convecção
         if self.params.enable_convection {
-            sources.convection = calculate_convection_source(
-                &self.mesh,
-                &self.params.torches,
+            sources.convection = match self.params.convection_model {
+                ConvectionModel::Constant => calculate_convection_source(
+                    &self.mesh,
+                    &torches,
+                    &self.temperature,
+                    self.params.convection_coefficient,
+                ),
+                ConvectionModel::JetImpingement => calculate_jet_impingement_source(
+                    &self.mesh,
+                    &torches,
+                    &self.temperature,
+                    self.params.ambient_temperature,
+                ),
+            };
+        }
+        
+        // Troca de calor com o gás que atravessa os leitos porosos
+        if let Some(exchange) = &self.params.bed_gas_exchange {
+            sources.convection += &calculate_bed_gas_exchange_source(
+                exchange,
+                &self.cell_materials,
+                &self.cell_material_index,
                 &self.temperature,
-                self.params.convection_coefficient,
             );
         }
-        
+
         // Calcular termo fonte das tochas (assumido constante no passo de tempo)
-        sources.torches = self.mesh.distribute_torch_heat(&self.params.torches);
+        sources.torches = self.mesh.distribute_torch_heat(&torches);
+
+        // Transporte advectivo pelo escoamento prescrito (entalpia H^n a montante)
+        if let Some((radial_velocity, axial_velocity)) = &self.advection_velocity {
+            let (rho, _, _) = self.cell_properties(&self.temperature);
+            sources.advection = calculate_advection_source(&self.mesh, radial_velocity, axial_velocity, &self.enthalpy, &rho);
+        }
+
+        // Potência recebida pelas células consumidas vai para a superfície exposta do leito
+        if let Some(model) = &self.bed_level {
+            for source in [&mut sources.torches, &mut sources.radiation, &mut sources.convection, &mut sources.advection] {
+                model.fold_source(&self.mesh, source);
+            }
+        }
         
         sources
     }
     
-    /// Resolve um passo de tempo para a entalpia usando o método de Crank-Nicolson (aproximado)
-    /// e um solver SOR (Successive Over-Relaxation) para o sistema linear.
+    /// Verifica a conservação de energia dos termos fonte no passo atual
+    ///
+    /// A integral do termo de deposição das tochas é comparada à potência nominal após a
+    /// eficiência, de modo que uma discretização que perde ou cria energia é sinalizada.
+    fn verify_source_energy(&mut self, sources: &HeatSources) {
+        let nominal_power: f64 = self.effective_torches().iter()
+            .map(|torch| torch.power.max(0.0) * 1000.0)
+            .sum();
+        let integrated_torch_power = integrate_source(&self.mesh, &sources.torches);
+        let integrated_radiation = integrate_source(&self.mesh, &sources.radiation);
+        let integrated_convection = integrate_source(&self.mesh, &sources.convection);
+
+        let relative_error = if nominal_power > 0.0 {
+            (integrated_torch_power - nominal_power) / nominal_power
+        } else if integrated_torch_power.abs() > 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        let flagged = relative_error.abs() > self.params.energy_check_tolerance;
+
+        if flagged {
+            warn!("Balanço de energia das fontes no passo {}: nominal {:.3e} W, integrado {:.3e} W (erro {:.2}%)",
+                  self.current_step, nominal_power, integrated_torch_power, relative_error * 100.0);
+
+            // Registrar apenas a primeira verificação reprovada de uma sequência
+            let previously_flagged = self.energy_source_checks.back().is_some_and(|c| c.flagged);
+            if !previously_flagged {
+                let annotation = TimelineAnnotation::from_solver(
+                    self.current_step,
+                    self.current_step as f64 * self.params.time_step,
+                    AnnotationKind::Alarm,
+                    "Balanço de energia das fontes fora da tolerância",
+                ).with_description(&format!("Erro relativo de {:.2}%", relative_error * 100.0));
+                insert_annotation(&mut self.annotations, annotation);
+            }
+        }
+
+        if self.energy_source_checks.len() == MAX_ENERGY_SOURCE_CHECKS {
+            self.energy_source_checks.pop_front();
+        }
+        self.energy_source_checks.push_back(EnergySourceCheck {
+            step: self.current_step,
+            time: self.current_step as f64 * self.params.time_step,
+            nominal_power,
+            integrated_torch_power,
+            integrated_radiation,
+            integrated_convection,
+            relative_error,
+            flagged,
+        });
+    }
+
+    /// Retorna as verificações de energia dos termos fonte dos últimos passos
+    pub fn get_energy_source_checks(&self) -> &VecDeque<EnergySourceCheck> {
+        &self.energy_source_checks
+    }
+
+    /// Registra mudanças na potência efetiva das tochas em relação ao passo anterior
+    fn annotate_power_changes(&mut self) {
+        let torches = self.effective_torches();
+        let powers: Vec<f64> = torches.iter().map(|torch| torch.power).collect();
+
+        if !self.previous_torch_powers.is_empty() {
+            let time = self.current_step as f64 * self.params.time_step;
+            for (torch, (&old, &new)) in torches.iter().zip(self.previous_torch_powers.iter().zip(powers.iter())) {
+                // Ignorar variações pequenas de rampas contínuas (menos de 1% da potência)
+                if (new - old).abs() > 0.01 * old.abs().max(new.abs()).max(1.0) {
+                    let annotation = TimelineAnnotation::from_solver(
+                        self.current_step,
+                        time,
+                        AnnotationKind::PowerChange,
+                        &format!("Tocha {}: {:.1} -> {:.1} kW", torch.id, old, new),
+                    ).with_metadata("torch_id", &torch.id);
+                    insert_annotation(&mut self.annotations, annotation);
+                }
+            }
+        }
+
+        self.previous_torch_powers = powers;
+    }
+
+    /// Avalia os critérios de parada ao fim do passo de saída `completed_step`
+    fn check_exit_criteria(&mut self, completed_step: usize) -> Option<StopReason> {
+        let dt = self.params.time_step;
+        let mut met = None;
+
+        for (index, criterion) in self.params.exit_criteria.iter().enumerate() {
+            let zone_index = criterion.zone().and_then(|zone| zone_material_index(&self.params, zone).ok());
+            let in_region = |i: usize, j: usize| zone_index.is_none_or(|zone| self.cell_material_index[[i, j]] == zone);
+
+            let satisfied = match criterion {
+                ExitCriterion::AllAboveTemperature { target, .. } => {
+                    let mut cells = self.temperature.indexed_iter().filter(|((i, j), _)| in_region(*i, *j)).peekable();
+                    cells.peek().is_some() && cells.all(|(_, &t)| t >= *target)
+                }
+                ExitCriterion::MeltFractionAtLeast { fraction, .. } => match &self.melt_fraction {
+                    Some(melt_fraction) => {
+                        let (mut melted, mut volume) = (0.0, 0.0);
+                        for ((i, j), &f) in melt_fraction.indexed_iter() {
+                            if in_region(i, j) {
+                                melted += f * self.mesh.cell_volumes[[i, j]];
+                                volume += self.mesh.cell_volumes[[i, j]];
+                            }
+                        }
+                        volume > 0.0 && melted / volume >= *fraction
+                    }
+                    None => false,
+                },
+                ExitCriterion::SteadyState { max_rate, consecutive_steps } => {
+                    let previous = self.temperature_history.slice(s![.., .., completed_step - 1]);
+                    let rate = self.temperature.iter().zip(previous.iter())
+                        .map(|(t, t_prev)| (t - t_prev).abs() / dt)
+                        .fold(0.0, f64::max);
+                    self.steady_state_counters[index] = if rate < *max_rate { self.steady_state_counters[index] + 1 } else { 0 };
+                    self.steady_state_counters[index] >= *consecutive_steps
+                }
+            };
+
+            if satisfied && met.is_none() {
+                met = Some(StopReason::CriterionMet {
+                    criterion: index,
+                    description: criterion.describe(),
+                    time: completed_step as f64 * dt,
+                });
+            }
+        }
+
+        met
+    }
+
+    /// Adiciona uma anotação à linha do tempo da execução
+    pub fn annotate(&mut self, annotation: TimelineAnnotation) -> Result<(), String> {
+        annotation.validate()?;
+        insert_annotation(&mut self.annotations, annotation);
+        Ok(())
+    }
+
+    /// Retorna as anotações da linha do tempo
+    pub fn get_annotations(&self) -> &[TimelineAnnotation] {
+        &self.annotations
+    }
+    
+    /// Retorna as tochas no tempo atual (programação de potência, restrições de partida e degradação aplicadas)
+    fn effective_torches(&self) -> Vec<PlasmaTorch> {
+        self.torches_at_step(self.current_step)
+    }
+
+    /// Retorna as tochas em um passo de tempo (programação de potência, restrições de partida e degradação aplicadas)
+    fn torches_at_step(&self, step: usize) -> Vec<PlasmaTorch> {
+        let elapsed_time = step as f64 * self.params.time_step;
+        self.params.torches.iter()
+            .zip(self.torch_power_profiles.iter())
+            .map(|(torch, profile)| match profile.get(step) {
+                Some(&power) => torch.at_time_with_power(elapsed_time, power),
+                None => torch.at_time(elapsed_time),
+            })
+            .collect()
+    }
+
+    /// Retorna as violações das restrições de partida das tochas
+    pub fn get_schedule_violations(&self) -> &[ScheduleViolation] {
+        &self.schedule_violations
+    }
+    
+    /// Resolve um passo de tempo `dt` para a entalpia com o esquema selecionado em `solver_scheme`.
     /// Atualiza `self.enthalpy` para H^{n+1}.
-    fn solve_enthalpy_time_step(&mut self, sources: &HeatSources) -> Result<(), String> {
-        // Solver parameters
-        let max_iterations = 1000; // Max iterations for SOR (Not used by Explicit Euler)
-        let tolerance = 1e-4;      // Convergence tolerance for SOR (Not used by Explicit Euler)
-        let omega = 1.5;           // SOR relaxation factor (Not used by Explicit Euler)
-
-        // Usa self.temperature (T^n) e self.enthalpy (H^n) como estado inicial
-        // e atualiza self.enthalpy (para H^{n+1}) in-place.
-        self.solve_linear_system_explicit_enthalpy(
-            max_iterations,
-            tolerance,
-            omega,
-            sources,
-            &self.temperature,
-        )
+    fn solve_enthalpy_time_step(&mut self, sources: &HeatSources, dt: f64) -> Result<(), String> {
+        let temperature_n = self.temperature.clone();
+        match self.params.solver_scheme {
+            SolverScheme::Explicit => self.solve_linear_system_explicit_enthalpy(sources, &temperature_n, dt),
+            SolverScheme::Adi => self.solve_enthalpy_adi(sources, &temperature_n, dt),
+        }
+    }
+
+    /// Copia o estado evolutivo atual
+    fn snapshot(&self) -> SolverStateSnapshot {
+        SolverStateSnapshot {
+            enthalpy: self.enthalpy.clone(),
+            temperature: self.temperature.clone(),
+            melt_fraction: self.melt_fraction.clone(),
+            vapor_fraction: self.vapor_fraction.clone(),
+            moisture: self.moisture.as_ref().map(|model| model.moisture().clone()),
+            reactions: self.reactions.clone(),
+            bed_level: self.bed_level.clone(),
+        }
+    }
+
+    /// Restaura um estado evolutivo copiado
+    fn restore(&mut self, snapshot: &SolverStateSnapshot) {
+        self.enthalpy.assign(&snapshot.enthalpy);
+        self.temperature.assign(&snapshot.temperature);
+        self.melt_fraction = snapshot.melt_fraction.clone();
+        self.vapor_fraction = snapshot.vapor_fraction.clone();
+        if let (Some(model), Some(moisture)) = (self.moisture.as_mut(), snapshot.moisture.as_ref()) {
+            model.set_moisture(moisture);
+        }
+        if snapshot.reactions.is_some() {
+            self.reactions = snapshot.reactions.clone();
+        }
+        if snapshot.bed_level.is_some() {
+            self.bed_level = snapshot.bed_level.clone();
+        }
+    }
+
+    /// Executa um subpasso completo (fontes, entalpia e temperatura) de duração `dt`,
+    /// iniciado no tempo `time` (s)
+    fn substep(&mut self, dt: f64, time: f64) -> Result<(), String> {
+        let mut sources = self.calculate_sources();
+        self.add_reaction_sources(&mut sources, dt);
+        self.add_formula_sources(&mut sources, time)?;
+        self.solve_enthalpy_time_step(&sources, dt)?;
+        self.apply_moisture_evaporation();
+        self.update_temperature_and_fractions_from_enthalpy()?;
+        self.apply_bed_consumption();
+        Ok(())
+    }
+
+    /// Avança as reações da carga (modelo opcional) em T^n e adiciona o calor de reação às fontes
+    fn add_reaction_sources(&mut self, sources: &mut HeatSources, dt: f64) {
+        if let Some(model) = self.reactions.as_mut() {
+            sources.reactions = model.advance(&self.temperature, dt);
+        }
+    }
+
+    /// Avalia a fonte volumétrica do usuário (opcional) em T^n no tempo `time` (s)
+    fn add_formula_sources(&self, sources: &mut HeatSources, time: f64) -> Result<(), String> {
+        if let (Some(manager), Some(term)) = (&self.formula_source, &self.params.formula_source) {
+            sources.formula = manager.evaluate_volumetric_source(&self.mesh, &self.temperature, time, &term.constants())?;
+            if let Some(model) = &self.bed_level {
+                model.fold_source(&self.mesh, &mut sources.formula);
+            }
+        }
+        Ok(())
+    }
+
+    /// Avalia o controle de potência (opcional) ao fim do passo `step` e aplica a potência
+    /// comandada às tochas controladas a partir do passo seguinte
+    fn apply_power_control(&mut self, step: usize) -> Result<(), String> {
+        let Some((controller, controlled)) = self.power_control.as_mut() else {
+            return Ok(());
+        };
+        let Some(&first) = controlled.first() else {
+            return Ok(());
+        };
+        let dt = self.params.time_step;
+        let applied = self.torch_power_profiles[first].get(step).copied().unwrap_or(self.params.torches[first].power);
+        let power = controller.update(&self.mesh, &self.temperature, (step + 1) as f64 * dt, dt, applied)?;
+        for &index in controlled.iter() {
+            if let Some(next) = self.torch_power_profiles[index].get_mut(step + 1) {
+                *next = power;
+            }
+        }
+        Ok(())
+    }
+
+    /// Consome o calor latente da evaporação da umidade (modelo opcional) em H^{n+1}
+    fn apply_moisture_evaporation(&mut self) {
+        if let Some(model) = self.moisture.as_mut() {
+            model.apply(&mut self.enthalpy);
+        }
+    }
+
+    /// Desativa as células consumidas do topo do leito (modelo opcional) e iguala seu
+    /// estado ao da superfície exposta, que passa a ser a nova fronteira superior
+    fn apply_bed_consumption(&mut self) {
+        let Some(model) = self.bed_level.as_mut() else {
+            return;
+        };
+        let conversion = self.reactions.as_ref().map(|reactions| reactions.conversion());
+        model.update(self.vapor_fraction.as_ref(), conversion);
+        model.fill_consumed(&mut self.enthalpy);
+        model.fill_consumed(&mut self.temperature);
+        if let Some(melt) = self.melt_fraction.as_mut() {
+            model.fill_consumed(melt);
+        }
+        if let Some(vapor) = self.vapor_fraction.as_mut() {
+            model.fill_consumed(vapor);
+        }
+    }
+
+    /// Calcula o maior passo estável do esquema explícito no estado atual (s)
+    ///
+    /// Para cada célula, dt ≤ ρ·cp·V / Σ condutâncias (número de Fourier da célula
+    /// limitado a 1), somando o número de Courant do escoamento prescrito quando houver;
+    /// retorna infinito para o esquema ADI.
+    pub fn stable_time_step(&self) -> f64 {
+        if self.params.solver_scheme == SolverScheme::Adi {
+            return f64::INFINITY;
+        }
+        let (rho, cp, k) = self.cell_properties(&self.temperature);
+        let mut stable = f64::INFINITY;
+        for i in 0..self.params.nr {
+            for j in 0..self.params.nz {
+                let (west, east) = radial_conductances(&self.mesh, &k, i, j, self.params.axis_treatment);
+                let (south, north) = axial_conductances(&self.mesh, &k, i, j);
+                let boundary = self.params.boundary_conditions.exchange(&self.mesh, &k, i, j).conductance();
+                let conductance = west + east + south + north + boundary;
+                let courant = self.advection_velocity.as_ref()
+                    .map_or(0.0, |(radial, axial)| courant_rate(&self.mesh, radial, axial, i, j));
+                if conductance > 0.0 || courant > 0.0 {
+                    let capacity = rho[[i, j]] * cp[[i, j]] * self.mesh.cell_volumes[[i, j]];
+                    stable = stable.min(1.0 / (conductance / capacity + courant));
+                }
+            }
+        }
+        stable
+    }
+
+    /// Integra um passo de saída com subpassos adaptativos
+    ///
+    /// Cada subpasso é limitado pelo passo estável e comparado com dois meios subpassos
+    /// (duplicação de passo); a maior diferença de temperatura estima o erro local. O
+    /// subpasso é aceito (com a solução dos meios subpassos) quando o erro está dentro da
+    /// tolerância ou o subpasso mínimo foi atingido; caso contrário é reduzido e refeito.
+    fn advance_adaptive(&mut self, config: &AdaptiveTimeStepConfig) -> Result<(), String> {
+        let output_dt = self.params.time_step;
+        let mut elapsed = 0.0;
+        // Subpasso proposto pelo controle (carregado entre passos de saída)
+        let mut proposed = self.adaptive_dt.clamp(config.min_time_step.min(output_dt), output_dt);
+        let mut rejected = 0;
+
+        while elapsed < output_dt - 1e-12 {
+            let stable = config.safety_factor * self.stable_time_step();
+            let limited = proposed.min(stable).max(config.min_time_step);
+            if stable < proposed {
+                self.convergence.note_limiter(LimiterKind::StabilityLimit, 1);
+            }
+            let remaining = output_dt - elapsed;
+            let truncated = limited > remaining;
+            let dt = limited.min(remaining);
+
+            let time = self.current_step as f64 * output_dt + elapsed;
+            let initial = self.snapshot();
+            self.substep(dt, time)?;
+            let single = self.temperature.clone();
+            self.restore(&initial);
+            self.substep(dt / 2.0, time)?;
+            self.substep(dt / 2.0, time + dt / 2.0)?;
+
+            let error = single.iter().zip(self.temperature.iter())
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+            if !error.is_finite() && dt <= config.min_time_step {
+                return Err(format!("Solução divergiu com o subpasso mínimo de {:.3e} s", dt));
+            }
+            let factor = if error == 0.0 {
+                config.max_growth
+            } else if error.is_finite() {
+                (config.safety_factor * (config.tolerance / error).sqrt()).clamp(0.2, config.max_growth)
+            } else {
+                0.2
+            };
+
+            if error <= config.tolerance || dt <= config.min_time_step {
+                if error > config.tolerance {
+                    self.convergence.note_limiter(LimiterKind::MinimumTimeStep, 1);
+                }
+                elapsed += dt;
+                self.time_step_sequence.push(dt);
+                // Um subpasso truncado pelo fim do passo de saída só pode reduzir a proposta
+                proposed = if truncated { proposed.min(dt * factor).max(dt) } else { dt * factor };
+            } else {
+                self.restore(&initial);
+                rejected += 1;
+                self.convergence.note_limiter(LimiterKind::RejectedSubstep, 1);
+                proposed = (dt * factor.min(1.0)).max(config.min_time_step);
+            }
+        }
+
+        self.adaptive_dt = proposed;
+        if rejected > 0 {
+            info!("Passo {}: {} subpassos rejeitados pelo controle de erro", self.current_step, rejected);
+        }
+        Ok(())
+    }
+
+    /// Calcula densidade, calor específico e condutividade de cada célula em T^n,
+    /// resolvendo o material pelo mapa de zonas (via cache, quando habilitado)
+    fn cell_properties(&self, temperature_n: &Array2<f64>) -> (Array2<f64>, Array2<f64>, Array2<f64>) {
+        let nr = self.params.nr;
+        let nz = self.params.nz;
+        let mut rho_n = Array2::<f64>::zeros((nr, nz));
+        let mut cp_n = Array2::<f64>::zeros((nr, nz));
+        let mut k_n = Array2::<f64>::zeros((nr, nz));
+
+        let property_caches = self.property_caches.as_ref();
+        let cell_materials = &self.cell_materials;
+        Zip::from(&mut rho_n)
+            .and(&mut cp_n)
+            .and(&mut k_n)
+            .and(temperature_n)
+            .and(&self.cell_material_index)
+            .par_for_each(|rho, cp, k, &temp_n, &idx| {
+                match property_caches {
+                    Some(caches) => {
+                        *rho = caches[idx].get_density(temp_n);
+                        *cp = caches[idx].get_specific_heat(temp_n);
+                        *k = caches[idx].get_thermal_conductivity(temp_n);
+                    }
+                    None => {
+                        let props = &cell_materials[idx];
+                        *rho = props.get_density(temp_n);
+                        *cp = props.get_specific_heat(temp_n);
+                        *k = props.get_thermal_conductivity(temp_n);
+                    }
+                }
+            });
+
+        (rho_n, cp_n, k_n)
     }
 
     /// Implementação do solver **Explícito de Euler** para a equação da entalpia.
@@ -590,33 +2249,17 @@ impl HeatSolver {
     /// **AVISO:** Este método pode ser instável para passos de tempo grandes.
     fn solve_linear_system_explicit_enthalpy(
         &mut self,
-        _max_iterations: usize,
-        _tolerance: f64,
-        _omega: f64,
         sources: &HeatSources,
         temperature_n: &Array2<f64>,
+        dt: f64,
     ) -> Result<(), String> {
-        let nr = self.params.nr;
-        let nz = self.params.nz;
-        let dt = self.params.time_step;
 
         // H^{n+1} (será calculado), H^n (valor atual em self.enthalpy)
         let mut enthalpy_np1 = self.enthalpy.clone();
         let enthalpy_n = &self.enthalpy;
 
-        // Pré-calcular volumes e propriedades dependentes de T^n
-        let mut rho_n = Array2::<f64>::zeros((nr, nz));
-        let mut k_n = Array2::<f64>::zeros((nr, nz));
-
-        // Preencher propriedades baseadas em T^n
-        Zip::from(&mut rho_n)
-            .and(&mut k_n)
-            .and(temperature_n)
-            .par_for_each(|rho, k, &temp_n| {
-                let props = &self.params.material;
-                *rho = props.get_density(temp_n);
-                *k = props.get_thermal_conductivity(temp_n);
-            });
+        // Pré-calcular propriedades dependentes de T^n
+        let (rho_n, _, k_n) = self.cell_properties(temperature_n);
 
         // --- Atualização Explícita de Euler para H ---
         // H_ij^{n+1} = H_ij^n + (dt / (rho_ij^n * V_ij)) * [ Sum(Fluxos @ T^n) + S_ij V_ij ]
@@ -628,65 +2271,30 @@ impl HeatSolver {
         let temperature_n_ref = &temperature_n;
         let sources_ref = sources;
         let rho_n_ref = &rho_n;
+        let axis_treatment = self.params.axis_treatment;
+        let boundary_conditions = &self.params.boundary_conditions;
 
-        Zip::indexed(&mut enthalpy_np1).par_apply(|(i, j), h_np1| {
-            let r = mesh_ref.r_nodes[i];
-            let dr = mesh_ref.dr;
-            let dz = mesh_ref.dz;
+        Zip::indexed(&mut enthalpy_np1).par_for_each(|(i, j), h_np1| {
             let vol = mesh_ref.cell_volumes[[i, j]];
 
             // Densidade no passo n (T^n)
             let rho_ij_n = rho_n_ref[[i, j]];
 
-            // Termo fonte total S = S_torch + S_rad + S_conv (W/m³)
+            // Termo fonte total S = S_torch + S_rad + S_conv + S_reac + S_adv + S_form (W/m³)
             let source_term_volumetric = sources_ref.torches[[i, j]]
                                            + sources_ref.radiation[[i, j]]
-                                           + sources_ref.convection[[i, j]];
+                                           + sources_ref.convection[[i, j]]
+                                           + sources_ref.reactions[[i, j]]
+                                           + sources_ref.advection[[i, j]]
+                                           + sources_ref.formula[[i, j]];
             let source_term = source_term_volumetric * vol;
 
             // Termos de difusão (baseados em T^n) - V * nabla.(k^n nabla T^n) (W)
-            let mut diffusion_term_tn = 0.0;
-
-            // Termo radial: (Flux_e - Flux_w)
-            if i == 0 {
-                let k_face_e = (k_n_ref[[0, j]] + k_n_ref[[1, j]]) / 2.0;
-                let area_e = mesh_ref.face_areas_r[[0]];
-                let grad_t_e = (temperature_n_ref[[1, j]] - temperature_n_ref[[0, j]]) / dr;
-                diffusion_term_tn += k_face_e * area_e * grad_t_e;
-
-            } else {
-                let k_face_w = (k_n_ref[[i, j]] + k_n_ref[[i - 1, j]]) / 2.0;
-                let area_w = mesh_ref.face_areas_r[[i - 1]];
-                let grad_t_w = (temperature_n_ref[[i, j]] - temperature_n_ref[[i - 1, j]]) / dr;
-                diffusion_term_tn -= k_face_w * area_w * grad_t_w;
-
-                if i < nr - 1 {
-                    let k_face_e = (k_n_ref[[i, j]] + k_n_ref[[i + 1, j]]) / 2.0;
-                    let area_e = mesh_ref.face_areas_r[[i]];
-                    let grad_t_e = (temperature_n_ref[[i + 1, j]] - temperature_n_ref[[i, j]]) / dr;
-                    diffusion_term_tn += k_face_e * area_e * grad_t_e;
-                } else {
-                    // Borda externa (r=R)
-                }
-            }
-
-            // Termo axial: (Flux_n - Flux_s)
-            if j > 0 {
-                let k_face_s = (k_n_ref[[i, j]] + k_n_ref[[i, j - 1]]) / 2.0;
-                let area_s = mesh_ref.face_areas_z[[i]];
-                let grad_t_s = (temperature_n_ref[[i, j]] - temperature_n_ref[[i, j - 1]]) / dz;
-                diffusion_term_tn -= k_face_s * area_s * grad_t_s;
-            } else {
-                // Base (z=0) - Condição de contorno
-            }
-            if j < nz - 1 {
-                let k_face_n = (k_n_ref[[i, j]] + k_n_ref[[i, j + 1]]) / 2.0;
-                let area_n = mesh_ref.face_areas_z[[i]];
-                let grad_t_n = (temperature_n_ref[[i, j + 1]] - temperature_n_ref[[i, j]]) / dz;
-                diffusion_term_tn += k_face_n * area_n * grad_t_n;
-            } else {
-                // Topo (z=H) - Condição de contorno
-            }
+            // Termo radial com o tratamento do eixo selecionado, termo axial e troca
+            // com as fronteiras conforme as condições de contorno
+            let diffusion_term_tn = radial_diffusion(mesh_ref, k_n_ref, temperature_n_ref, i, j, axis_treatment)
+                + axial_diffusion(mesh_ref, k_n_ref, temperature_n_ref, i, j)
+                + boundary_conditions.exchange(mesh_ref, k_n_ref, i, j).heat_flow(temperature_n_ref[[i, j]]);
 
             // Atualização Explícita
             let h_old = enthalpy_n_ref[[i, j]];
@@ -705,51 +2313,148 @@ impl HeatSolver {
         Ok(())
     }
 
-    /// Atualiza os campos de temperatura e fração de fase a partir do campo de entalpia atual.
-    fn update_temperature_and_fractions_from_enthalpy(&mut self) -> Result<(), String> {
+    /// Implementação do esquema **ADI** (Peaceman-Rachford) para a equação da entalpia.
+    /// Atualiza `self.enthalpy` para H^{n+1}.
+    ///
+    /// Cada passo é dividido em duas meias etapas: varredura radial implícita (axial
+    /// explícita) seguida de varredura axial implícita (radial explícita), resolvendo
+    /// sistemas tridiagonais por linha/coluna. A mudança de fase entra pela capacidade
+    /// térmica aparente dH/dT em T^n, e a entalpia é atualizada de forma conservativa
+    /// por H^{n+1} = H^n + c_ap·(T^{n+1} - T^n). Incondicionalmente estável para a parte
+    /// difusiva; os termos fonte são avaliados em T^n. A troca com as fronteiras radiais
+    /// (eixo e parede) é implícita na varredura radial e a das fronteiras axiais (base e
+    /// topo) na varredura axial.
+    fn solve_enthalpy_adi(&mut self, sources: &HeatSources, temperature_n: &Array2<f64>, dt: f64) -> Result<(), String> {
         let nr = self.params.nr;
         let nz = self.params.nz;
-
-        // Arrays temporários para evitar borrows múltiplos de self
-        let mut temp_updated = Array2::<f64>::zeros((nr, nz));
-        let mut melt_frac_updated = self.melt_fraction.as_ref().map(|_| Array2::zeros((nr, nz)));
-        let mut vapor_frac_updated = self.vapor_fraction.as_ref().map(|_| Array2::zeros((nr, nz)));
-
-        let enthalpy_ref = &self.enthalpy;
-        let params_ref = &self.params;
-
-        Zip::indexed(enthalpy_ref).par_apply(|(i, j), &h| {
-            let props = &params_ref.material;
-            let (t, fm, fv) = calculate_temperature_and_fractions(h, props, 0.0);
-            temp_updated[[i, j]] = t;
-            if let Some(mf_arr) = melt_frac_updated.as_mut() {
-                if params_ref.enable_phase_changes {
-                    mf_arr[[i, j]] = fm;
+        let half_dt = dt / 2.0;
+        let axis_treatment = self.params.axis_treatment;
+
+        let (rho_n, cp_n, k_n) = self.cell_properties(temperature_n);
+        let mesh = &self.mesh;
+        let boundary_conditions = &self.params.boundary_conditions;
+
+        // Capacidade térmica aparente (J/(kg·K)) e capacidade de cada célula por meia etapa (W/K)
+        let mut apparent_cp = Array2::<f64>::zeros((nr, nz));
+        let mut capacity = Array2::<f64>::zeros((nr, nz));
+        let mut source = Array2::<f64>::zeros((nr, nz));
+        let mut clamped_cells = 0;
+        for i in 0..nr {
+            for j in 0..nz {
+                let idx = self.cell_material_index[[i, j]];
+                let (props, phase_cp) = (&self.cell_materials[idx], &self.phase_specific_heats[idx]);
+                let cp = cp_n[[i, j]].max(1e-6);
+                let h = self.enthalpy[[i, j]];
+                let (t0, _, _) = calculate_temperature_and_fractions_with_specific_heats(h, props, phase_cp, 0.0);
+                let (t1, _, _) = calculate_temperature_and_fractions_with_specific_heats(h + cp, props, phase_cp, 0.0);
+                // Durante a mudança de fase dT/dH → 0; limita a capacidade aparente
+                let slope = (t1 - t0) / cp;
+                if slope < 1e-3 / cp {
+                    clamped_cells += 1;
                 }
+                let dt_dh = slope.max(1e-3 / cp);
+                apparent_cp[[i, j]] = 1.0 / dt_dh;
+
+                let vol = mesh.cell_volumes[[i, j]];
+                capacity[[i, j]] = rho_n[[i, j]] * apparent_cp[[i, j]] * vol / half_dt;
+                source[[i, j]] = (sources.torches[[i, j]] + sources.radiation[[i, j]] + sources.convection[[i, j]] + sources.reactions[[i, j]] + sources.advection[[i, j]] + sources.formula[[i, j]]) * vol;
             }
-            if let Some(vf_arr) = vapor_frac_updated.as_mut() {
-                if params_ref.enable_phase_changes {
-                    vf_arr[[i, j]] = fv;
-                }
+        }
+
+        self.convergence.note_limiter(LimiterKind::ApparentCapacityClamp, clamped_cells);
+
+        // Meia etapa 1: radial implícita, axial explícita
+        let mut temperature_half = temperature_n.clone();
+        for j in 0..nz {
+            let mut lower = vec![0.0; nr];
+            let mut diagonal = vec![0.0; nr];
+            let mut upper = vec![0.0; nr];
+            let mut rhs = vec![0.0; nr];
+            for i in 0..nr {
+                let (west, east) = radial_conductances(mesh, &k_n, i, j, axis_treatment);
+                let boundary = boundary_conditions.exchange(mesh, &k_n, i, j);
+                lower[i] = -west;
+                upper[i] = -east;
+                diagonal[i] = capacity[[i, j]] + west + east + boundary.radial.0;
+                rhs[i] = capacity[[i, j]] * temperature_n[[i, j]]
+                    + axial_diffusion(mesh, &k_n, temperature_n, i, j)
+                    + boundary.radial.1
+                    + boundary.axial.1 - boundary.axial.0 * temperature_n[[i, j]]
+                    + source[[i, j]];
             }
-        });
+            let row = solve_tridiagonal(&lower, &diagonal, &upper, &rhs)?;
+            for (i, value) in row.into_iter().enumerate() {
+                temperature_half[[i, j]] = value;
+            }
+        }
 
-        // Atualizar os campos do struct HeatSolver
-        self.temperature.assign(&temp_updated);
-        if let Some(mf_arr) = melt_frac_updated {
-            if let Some(mf_field) = self.melt_fraction.as_mut() {
-                mf_field.assign(&mf_arr);
+        // Meia etapa 2: axial implícita, radial explícita
+        let mut temperature_np1 = temperature_half.clone();
+        for i in 0..nr {
+            let mut lower = vec![0.0; nz];
+            let mut diagonal = vec![0.0; nz];
+            let mut upper = vec![0.0; nz];
+            let mut rhs = vec![0.0; nz];
+            for j in 0..nz {
+                let (south, north) = axial_conductances(mesh, &k_n, i, j);
+                let boundary = boundary_conditions.exchange(mesh, &k_n, i, j);
+                lower[j] = -south;
+                upper[j] = -north;
+                diagonal[j] = capacity[[i, j]] + south + north + boundary.axial.0;
+                rhs[j] = capacity[[i, j]] * temperature_half[[i, j]]
+                    + radial_diffusion(mesh, &k_n, &temperature_half, i, j, axis_treatment)
+                    + boundary.axial.1
+                    + boundary.radial.1 - boundary.radial.0 * temperature_half[[i, j]]
+                    + source[[i, j]];
+            }
+            let column = solve_tridiagonal(&lower, &diagonal, &upper, &rhs)?;
+            for (j, value) in column.into_iter().enumerate() {
+                temperature_np1[[i, j]] = value;
             }
         }
-        if let Some(vf_arr) = vapor_frac_updated {
-            if let Some(vf_field) = self.vapor_fraction.as_mut() {
-                vf_field.assign(&vf_arr);
+
+        // Atualização conservativa da entalpia
+        for i in 0..nr {
+            for j in 0..nz {
+                if rho_n[[i, j]] > 1e-6 && mesh.cell_volumes[[i, j]] > 1e-9 {
+                    self.enthalpy[[i, j]] += apparent_cp[[i, j]] * (temperature_np1[[i, j]] - temperature_n[[i, j]]);
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Atualiza os campos de temperatura e fração de fase a partir do campo de entalpia atual.
+    fn update_temperature_and_fractions_from_enthalpy(&mut self) -> Result<(), String> {
+        let cell_materials = &self.cell_materials;
+        let phase_specific_heats = &self.phase_specific_heats;
+        let enable_phase_changes = self.params.enable_phase_changes;
+
+        // Calcula (T, fm, fv) de cada célula em paralelo antes de escrever nos campos de self
+        let updated = Zip::from(&self.enthalpy)
+            .and(&self.cell_material_index)
+            .par_map_collect(|&h, &idx| {
+                calculate_temperature_and_fractions_with_specific_heats(h, &cell_materials[idx], &phase_specific_heats[idx], 0.0)
+            });
+
+        // Atualizar os campos do struct HeatSolver
+        self.temperature.zip_mut_with(&updated, |t, &(temp, _, _)| *t = temp);
+        if let Some(mf_field) = self.melt_fraction.as_mut() {
+            mf_field.zip_mut_with(&updated, |mf, &(_, fm, _)| *mf = if enable_phase_changes { fm } else { 0.0 });
+        }
+        if let Some(vf_field) = self.vapor_fraction.as_mut() {
+            vf_field.zip_mut_with(&updated, |vf, &(_, _, fv)| *vf = if enable_phase_changes { fv } else { 0.0 });
+        }
+
+        Ok(())
+    }
+
+    /// Retorna o material da célula (i, j), resolvido pelo mapa de zonas
+    pub fn material_at(&self, i: usize, j: usize) -> &MaterialProperties {
+        &self.cell_materials[self.cell_material_index[[i, j]]]
+    }
+
     /// Retorna o campo de temperatura atual
     pub fn get_temperature(&self) -> &Array2<f64> {
         &self.temperature
@@ -780,6 +2485,171 @@ impl HeatSolver {
     }
 }
 
+/// Calcula as condutâncias radiais (oeste, leste) de um nó (W/K)
+///
+/// O termo de difusão radial é `a_w·(T[i-1] - T[i]) + a_e·(T[i+1] - T[i])`. No eixo
+/// (i = 0) o termo singular é tratado conforme `treatment`; a troca pela face externa
+/// (r = R) é dada pelas condições de contorno (`BoundaryConditions`).
+pub(crate) fn radial_conductances(
+    mesh: &CylindricalMesh,
+    conductivity: &Array2<f64>,
+    i: usize,
+    j: usize,
+    treatment: AxisTreatment,
+) -> (f64, f64) {
+    let nr = mesh.nr;
+
+    if i == 0 {
+        let dr = mesh.radial_spacing(0);
+        let east = match treatment {
+            AxisTreatment::FiniteVolume => {
+                let k_face_e = (conductivity[[0, j]] + conductivity[[1, j]]) / 2.0;
+                k_face_e * mesh.radial_face_area(0, j) / dr
+            }
+            AxisTreatment::LHopital => {
+                // 2·k·∂²T/∂r² com T(-dr) = T(dr): 2·k·2·(T1 - T0)/dr², multiplicado pelo volume
+                4.0 * conductivity[[0, j]] / (dr * dr) * mesh.cell_volumes[[0, j]]
+            }
+        };
+        return (0.0, east);
+    }
+
+    let k_face_w = (conductivity[[i, j]] + conductivity[[i - 1, j]]) / 2.0;
+    let west = k_face_w * mesh.radial_face_area(i - 1, j) / mesh.radial_spacing(i - 1);
+
+    let east = if i < nr - 1 {
+        let k_face_e = (conductivity[[i, j]] + conductivity[[i + 1, j]]) / 2.0;
+        k_face_e * mesh.radial_face_area(i, j) / mesh.radial_spacing(i)
+    } else {
+        0.0
+    };
+
+    (west, east)
+}
+
+/// Calcula as condutâncias axiais (sul, norte) de um nó (W/K)
+///
+/// A troca pelas faces externas da base (z = 0) e do topo (z = H) é dada pelas
+/// condições de contorno (`BoundaryConditions`).
+pub(crate) fn axial_conductances(mesh: &CylindricalMesh, conductivity: &Array2<f64>, i: usize, j: usize) -> (f64, f64) {
+    let area = mesh.axial_face_area(i);
+    let south = if j > 0 {
+        (conductivity[[i, j]] + conductivity[[i, j - 1]]) / 2.0 * area / mesh.axial_spacing(j - 1)
+    } else {
+        0.0
+    };
+    let north = if j < mesh.nz - 1 {
+        (conductivity[[i, j]] + conductivity[[i, j + 1]]) / 2.0 * area / mesh.axial_spacing(j)
+    } else {
+        0.0
+    };
+    (south, north)
+}
+
+/// Calcula o termo de difusão radial (fluxo leste - fluxo oeste) de um nó (W)
+pub(crate) fn radial_diffusion(
+    mesh: &CylindricalMesh,
+    conductivity: &Array2<f64>,
+    temperature: &Array2<f64>,
+    i: usize,
+    j: usize,
+    treatment: AxisTreatment,
+) -> f64 {
+    let (west, east) = radial_conductances(mesh, conductivity, i, j, treatment);
+    let mut diffusion = 0.0;
+    if west > 0.0 {
+        diffusion += west * (temperature[[i - 1, j]] - temperature[[i, j]]);
+    }
+    if east > 0.0 {
+        diffusion += east * (temperature[[i + 1, j]] - temperature[[i, j]]);
+    }
+    diffusion
+}
+
+/// Calcula o termo de difusão axial (fluxo norte - fluxo sul) de um nó (W)
+pub(crate) fn axial_diffusion(mesh: &CylindricalMesh, conductivity: &Array2<f64>, temperature: &Array2<f64>, i: usize, j: usize) -> f64 {
+    let (south, north) = axial_conductances(mesh, conductivity, i, j);
+    let mut diffusion = 0.0;
+    if south > 0.0 {
+        diffusion += south * (temperature[[i, j - 1]] - temperature[[i, j]]);
+    }
+    if north > 0.0 {
+        diffusion += north * (temperature[[i, j + 1]] - temperature[[i, j]]);
+    }
+    diffusion
+}
+
+/// Resolve um sistema tridiagonal pelo algoritmo de Thomas
+///
+/// `lower[k]·x[k-1] + diagonal[k]·x[k] + upper[k]·x[k+1] = rhs[k]`; os coeficientes
+/// `lower[0]` e `upper[n-1]` são ignorados.
+fn solve_tridiagonal(lower: &[f64], diagonal: &[f64], upper: &[f64], rhs: &[f64]) -> Result<Vec<f64>, String> {
+    let n = diagonal.len();
+    let mut c_prime = vec![0.0; n];
+    let mut d_prime = vec![0.0; n];
+    for k in 0..n {
+        let lower_k = if k > 0 { lower[k] } else { 0.0 };
+        let denominator = diagonal[k] - if k > 0 { lower_k * c_prime[k - 1] } else { 0.0 };
+        if denominator.abs() < 1e-300 {
+            return Err(format!("Sistema tridiagonal singular na linha {}", k));
+        }
+        c_prime[k] = if k + 1 < n { upper[k] / denominator } else { 0.0 };
+        d_prime[k] = (rhs[k] - if k > 0 { lower_k * d_prime[k - 1] } else { 0.0 }) / denominator;
+    }
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        x[k] = d_prime[k] - if k + 1 < n { c_prime[k] * x[k + 1] } else { 0.0 };
+    }
+    Ok(x)
+}
+
+/// Resolve os materiais distintos da malha e o índice do material de cada célula.
+/// O índice 0 corresponde ao material principal; com `zone_map` e `material_zones`
+/// definidos, a zona `z` usa o material `material_zones[z]` (índice `z + 1`).
+pub(crate) fn resolve_cell_materials(params: &SimulationParameters) -> (Vec<MaterialProperties>, Array2<usize>) {
+    let mut materials = vec![params.material.clone()];
+    let mut index = Array2::<usize>::zeros((params.nr, params.nz));
+
+    if let (Some(zone_map), Some(zones)) = (&params.zone_map, &params.material_zones) {
+        materials.extend(zones.iter().map(|(_, material)| material.clone()));
+        Zip::from(&mut index)
+            .and(zone_map)
+            .for_each(|idx, &zone| {
+                *idx = if zone < zones.len() { zone + 1 } else { 0 };
+            });
+    }
+
+    (materials, index)
+}
+
+/// Calores específicos (J/(kg·K)) assumidos constantes em cada fase nas conversões H ↔ T
+#[derive(Debug, Clone, Copy)]
+struct PhaseSpecificHeats {
+    solid: f64,
+    liquid: f64,
+    gas: f64,
+}
+
+impl PhaseSpecificHeats {
+    /// Avalia cp no meio da faixa de cada fase com `specific_heat` (material ou cache)
+    fn new(props: &MaterialProperties, t_ref: f64, specific_heat: impl Fn(f64) -> f64) -> Self {
+        let solid = specific_heat(props.melting_point.map_or(t_ref, |tm| (tm + t_ref) / 2.0));
+        let liquid = props.melting_point.map_or(solid, |tm| {
+            props.vaporization_point.map_or(
+                specific_heat(tm + 1.0),
+                |tv| specific_heat((tm + tv) / 2.0)
+            )
+        });
+        let gas = props.vaporization_point.map_or(liquid, |tv| specific_heat(tv + 1.0));
+        Self { solid, liquid, gas }
+    }
+
+    /// Avalia cp diretamente no material
+    fn of(props: &MaterialProperties, t_ref: f64) -> Self {
+        Self::new(props, t_ref, |t| props.get_specific_heat(t))
+    }
+}
+
 /// Calcula a entalpia específica (J/kg) a partir da temperatura, frações de fase e propriedades.
 /// Assume T_ref como a temperatura de referência para H=0 no estado sólido.
 /// Simplificação: Assume Cp constante em cada fase (usa get_specific_heat na temperatura dada).
@@ -790,14 +2660,19 @@ fn calculate_enthalpy_from_temperature(
     props: &MaterialProperties,
     t_ref: f64,
 ) -> f64 {
-    let cp_solid = props.get_specific_heat(props.melting_point.map_or(t_ref, |tm| (tm + t_ref) / 2.0));
-    let cp_liquid = props.melting_point.map_or(cp_solid, |tm| {
-        props.vaporization_point.map_or(
-            props.get_specific_heat(tm + 1.0),
-            |tv| props.get_specific_heat((tm + tv) / 2.0)
-        )
-    });
-    let cp_gas = props.vaporization_point.map_or(cp_liquid, |tv| props.get_specific_heat(tv + 1.0));
+    calculate_enthalpy_with_specific_heats(temperature, melt_fraction, vapor_fraction, props, &PhaseSpecificHeats::of(props, t_ref), t_ref)
+}
+
+/// Calcula a entalpia específica (J/kg) com os calores específicos por fase já avaliados
+fn calculate_enthalpy_with_specific_heats(
+    temperature: f64,
+    melt_fraction: f64,
+    vapor_fraction: f64,
+    props: &MaterialProperties,
+    phase_cp: &PhaseSpecificHeats,
+    t_ref: f64,
+) -> f64 {
+    let PhaseSpecificHeats { solid: cp_solid, liquid: cp_liquid, gas: cp_gas } = *phase_cp;
 
     let tm = props.melting_point;
     let tv = props.vaporization_point;
@@ -835,8 +2710,7 @@ fn calculate_enthalpy_from_temperature(
         } else {
             enthalpy += cp_liquid * (vaporization_point - melting_point);
         }
-    } else if tm.is_some() && tv.is_none() {
-        let current_melting_point = tm.unwrap();
+    } else if let (Some(current_melting_point), None) = (tm, tv) {
         enthalpy += cp_liquid * (temperature - current_melting_point);
         return enthalpy.max(0.0);
     }
@@ -864,19 +2738,23 @@ fn calculate_enthalpy_from_temperature(
 /// Retorna (temperatura, fração_fusão, fração_vapor)
 /// Assume T_ref como a temperatura de referência usada para calcular a entalpia.
 /// Simplificação: Assume Cp constante em cada fase.
+#[cfg(test)]
 fn calculate_temperature_and_fractions(
     enthalpy: f64,
     props: &MaterialProperties,
     t_ref: f64,
 ) -> (f64, f64, f64) {
-    let cp_solid = props.get_specific_heat(props.melting_point.map_or(t_ref, |tm| (tm + t_ref) / 2.0));
-    let cp_liquid = props.melting_point.map_or(cp_solid, |tm| {
-        props.vaporization_point.map_or(
-            props.get_specific_heat(tm + 1.0),
-            |tv| props.get_specific_heat((tm + tv) / 2.0)
-        )
-    });
-    let cp_gas = props.vaporization_point.map_or(cp_liquid, |tv| props.get_specific_heat(tv + 1.0));
+    calculate_temperature_and_fractions_with_specific_heats(enthalpy, props, &PhaseSpecificHeats::of(props, t_ref), t_ref)
+}
+
+/// Calcula (temperatura, fração_fusão, fração_vapor) com os calores específicos por fase já avaliados
+fn calculate_temperature_and_fractions_with_specific_heats(
+    enthalpy: f64,
+    props: &MaterialProperties,
+    phase_cp: &PhaseSpecificHeats,
+    t_ref: f64,
+) -> (f64, f64, f64) {
+    let PhaseSpecificHeats { solid: cp_solid, liquid: cp_liquid, gas: cp_gas } = *phase_cp;
 
     let tm = props.melting_point;
     let tv = props.vaporization_point;
@@ -933,8 +2811,7 @@ fn calculate_temperature_and_fractions(
              temperature = vaporization_point;
              melt_fraction = 1.0;
          }
-    } else if tm.is_some() && tv.is_none() {
-        let current_melting_point = tm.unwrap();
+    } else if let (Some(current_melting_point), None) = (tm, tv) {
          if temperature >= current_melting_point {
              temperature = current_melting_point + (enthalpy - h_lower_bound) / cp_liquid.max(1e-9);
              return (temperature, 1.0, 0.0);
@@ -969,7 +2846,14 @@ fn calculate_temperature_and_fractions(
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
-
+    use crate::simulation::moisture::WATER_LATENT_HEAT;
+    use crate::simulation::physics::reactions::ArrheniusKinetics;
+    use crate::simulation::boundary::BoundaryCondition;
+    use crate::simulation::power_control::ControlProbe;
+    use std::collections::HashMap;
+    use crate::formula::engine::{Formula, FormulaCategory, FormulaParameter, ParameterType, ParameterValue};
+
+    #[allow(clippy::too_many_arguments)]
     fn create_test_material_const_cp(name: &str, melting_point: Option<f64>, latent_heat_fusion: Option<f64>,
                              vaporization_point: Option<f64>, latent_heat_vaporization: Option<f64>,
                              cp: f64, rho: f64, k: f64) -> MaterialProperties {
@@ -978,12 +2862,6 @@ mod tests {
         mat.latent_heat_fusion = latent_heat_fusion;
         mat.vaporization_point = vaporization_point;
         mat.latent_heat_vaporization = latent_heat_vaporization;
-        let cp_val = cp;
-        mat.specific_heat = Box::new(move |_| cp_val);
-        let rho_val = rho;
-        mat.density = Box::new(move |_| rho_val);
-         let k_val = k;
-        mat.thermal_conductivity = Box::new(move |_| k_val);
         mat
     }
 
@@ -1024,6 +2902,7 @@ mod tests {
         let (t, fm, fv) = calculate_temperature_and_fractions(h, &mat, t_ref);
         assert_relative_eq!(t, 300.0, epsilon = 1e-6);
         assert_relative_eq!(fm, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(fv, 0.0, epsilon = 1e-6);
         assert_relative_eq!(calculate_enthalpy_from_temperature(t, 1.0, 0.0, &mat, t_ref), h, epsilon = 1e-6);
     }
 
@@ -1062,7 +2941,6 @@ mod tests {
 
     #[test]
     fn test_simulation_with_material_properties() {
-        let library = MaterialLibrary::new();
         let test_mat = create_test_material_const_cp("TestSimple", None, None, None, None, 100.0, 1000.0, 10.0);
 
         let mut params = SimulationParameters::new(0.1, 0.05, 5, 5);
@@ -1076,18 +2954,802 @@ mod tests {
             0.0, 0.0, 0.05, 90.0, 0.0, 10.0, 0.001, 1000.0
         ));
 
-        let mut solver = HeatSolver::new(params).unwrap();
+        let mut solver = HeatSolver::new(params.clone()).unwrap();
         let results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
 
         assert_eq!(results.parameters.material.name, "TestSimple");
         assert_eq!(results.executed_steps, 2);
         assert_eq!(results.temperature.shape(), &[5, 5, 3]);
         assert_eq!(results.enthalpy.shape(), &[5, 5, 3]);
+
+        // A deposição das tochas deve conservar a potência nominal em cada passo
+        assert_eq!(results.energy_source_checks.len(), 2);
+        assert!(results.energy_source_checks.iter().all(|c| !c.flagged));
+
+        // Carga mais quente que o gás: a convecção retira energia, mas é uma troca com o
+        // campo e não entra no balanço da deposição das tochas
+        params.convection_coefficient = 1000.0;
+        params.initial_temperature = 1500.0;
+        let mut solver = HeatSolver::new(params).unwrap();
+        let results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        let check = &results.energy_source_checks[0];
+        assert!(check.integrated_convection < 0.0);
+        assert!(!check.flagged);
+        assert_relative_eq!(check.integrated_torch_power, check.nominal_power, max_relative = 1e-9);
+        assert!(!results.annotations.iter().any(|a| a.kind == AnnotationKind::Alarm));
+
+        // O histórico de verificações é limitado aos passos mais recentes
+        let sources = solver.calculate_sources();
+        for step in 0..MAX_ENERGY_SOURCE_CHECKS + 5 {
+            solver.current_step = step;
+            solver.verify_source_energy(&sources);
+        }
+        let checks = solver.get_energy_source_checks();
+        assert_eq!(checks.len(), MAX_ENERGY_SOURCE_CHECKS);
+        assert_eq!(checks.front().unwrap().step, 5);
+        assert_eq!(checks.back().unwrap().step, MAX_ENERGY_SOURCE_CHECKS + 4);
+    }
+
+    fn create_two_zone_parameters() -> SimulationParameters {
+        let mut params = SimulationParameters::new(0.1, 0.05, 4, 4);
+        params.material = MaterialProperties::new("Base", 1000.0, 100.0, 1.0);
+        params.time_steps = 2;
+        params.total_time = 2.0;
+        params.time_step = 1.0;
+        params.enable_phase_changes = false;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.05, 90.0, 0.0, 1.0, 0.001, 1000.0));
+
+        // Metade inferior com material isolante, metade superior com material condutor
+        params.add_material_zone("insulation".to_string(), MaterialProperties::new("Insulation", 200.0, 1000.0, 0.1));
+        params.add_material_zone("metal".to_string(), MaterialProperties::new("Metal", 8000.0, 500.0, 50.0));
+        let mut zone_map = Array2::<usize>::zeros((4, 4));
+        zone_map.slice_mut(s![.., 2..]).fill(1);
+        params.set_zone_map(zone_map);
+        params
+    }
+
+    #[test]
+    fn test_zone_aware_material_lookup() {
+        let params = create_two_zone_parameters();
+        let solver = HeatSolver::new(params).unwrap();
+
+        assert_eq!(solver.material_at(0, 0).name, "Insulation");
+        assert_eq!(solver.material_at(3, 1).name, "Insulation");
+        assert_eq!(solver.material_at(0, 2).name, "Metal");
+        assert_eq!(solver.material_at(3, 3).name, "Metal");
+
+        // A entalpia inicial usa o calor específico de cada zona
+        let enthalpy = solver.get_enthalpy();
+        assert_relative_eq!(enthalpy[[0, 0]], 1000.0 * 25.0, epsilon = 1e-6);
+        assert_relative_eq!(enthalpy[[0, 3]], 500.0 * 25.0, epsilon = 1e-6);
+
+        // A temperatura inicial é recuperada em ambas as zonas
+        assert!(solver.get_temperature().iter().all(|&t| (t - 25.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_zone_map_without_material_zones_uses_main_material() {
+        let mut params = create_two_zone_parameters();
+        params.material_zones = None;
+        params.zone_map = Some(Array2::<usize>::zeros((4, 4)));
+        let solver = HeatSolver::new(params).unwrap();
+
+        assert_eq!(solver.material_at(0, 0).name, "Base");
+        assert_eq!(solver.material_at(3, 3).name, "Base");
+    }
+
+    #[test]
+    fn test_two_zone_simulation_runs() {
+        let params = create_two_zone_parameters();
+        let mut solver = HeatSolver::new(params).unwrap();
+        let results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        assert_eq!(results.executed_steps, 2);
+        assert!(results.temperature.iter().all(|t| t.is_finite()));
+    }
+
+    #[test]
+    fn test_radiation_uses_zone_emissivity() {
+        let mut params = create_two_zone_parameters();
+        params.material.emissivity = 0.5;
+        for (_, material) in params.material_zones.as_mut().unwrap().iter_mut() {
+            material.emissivity = if material.name == "Insulation" { 0.2 } else { 0.9 };
+        }
+        let zoned = HeatSolver::new(params.clone()).unwrap();
+
+        params.material_zones = None;
+        params.zone_map = None;
+        let uniform = HeatSolver::new(params).unwrap();
+
+        // Mesma geometria e temperatura: a radiação de cada célula escala com a emissividade do seu material
+        let zoned_radiation = zoned.calculate_sources().radiation;
+        let uniform_radiation = uniform.calculate_sources().radiation;
+        for ((i, j), &q) in zoned_radiation.indexed_iter() {
+            let expected = uniform_radiation[[i, j]] * zoned.material_at(i, j).emissivity / 0.5;
+            assert_relative_eq!(q, expected, max_relative = 1e-12);
+        }
+        assert!(zoned_radiation.iter().any(|&q| q != 0.0));
+    }
+
+    #[test]
+    fn test_power_change_annotations() {
+        use crate::simulation::physics::PowerSchedule;
+
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 4;
+        params.time_step = 1.0;
+        params.total_time = 4.0;
+        let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0);
+        // Degrau de potência entre os passos 1 e 2
+        torch.set_power_schedule(PowerSchedule::new(vec![(0.0, 10.0), (1.0, 10.0), (1.5, 20.0)]));
+        params.add_torch(torch);
+
+        let mut solver = HeatSolver::new(params).unwrap();
+        solver.annotate(TimelineAnnotation::new(3.0, AnnotationKind::FeedAdded, "Carga de sucata")).unwrap();
+        let mut results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        let power_changes: Vec<_> = results.annotations.iter()
+            .filter(|a| a.kind == AnnotationKind::PowerChange)
+            .collect();
+        assert_eq!(power_changes.len(), 1);
+        assert_eq!(power_changes[0].step, Some(2));
+
+        results.add_annotation(TimelineAnnotation::new(0.5, AnnotationKind::Note, "Início")).unwrap();
+        assert_eq!(results.annotations[0].title, "Início");
+        assert!(results.annotations.windows(2).all(|w| w[0].time <= w[1].time));
+        assert!(results.annotations.iter().any(|a| a.kind == AnnotationKind::FeedAdded));
+    }
+
+    #[test]
+    fn test_quantized_playback_without_full_precision() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 3;
+        params.time_step = 1.0;
+        params.total_time = 3.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        params.frame_storage.retain_full_precision = false;
+        assert!(params.validate().is_err());
+        params.frame_storage.quantize_playback = true;
+
+        let mut solver = HeatSolver::new(params).unwrap();
+        let results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        // Apenas o estado final em precisão total; quadros intermediários quantizados
+        assert_eq!(results.temperature.shape(), &[5, 5, 1]);
+        assert!(!results.has_full_precision_history());
+        assert_eq!(results.playback_frames.as_ref().unwrap().len(), 4);
+
+        let last = results.temperature_at(3).unwrap();
+        assert_eq!(last, results.temperature.slice(s![.., .., 0]).to_owned());
+        assert!(results.temperature_at(1).is_ok());
+        assert!(results.temperature_at(4).is_err());
+
+        // Pirâmide temporal: nível de intervalo 10 contém apenas o estado inicial
+        assert_eq!(results.pyramid_frame(10, 3).unwrap().0, 0);
+        assert_eq!(results.pyramid_frame(1, 2).unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_startup_dynamics_applied_in_run() {
+        use crate::simulation::physics::{ScheduleViolationKind, TorchStartupDynamics};
+
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 4;
+        params.time_step = 1.0;
+        params.total_time = 4.0;
+        let mut torch = PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0);
+        torch.startup = Some(TorchStartupDynamics::new(2.0, None, 1.0));
+        params.add_torch(torch);
+
+        let violations = params.check_torch_schedules();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, ScheduleViolationKind::IgnitionDelay);
+
+        let mut solver = HeatSolver::new(params).unwrap();
+        let results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert_eq!(results.schedule_violations.len(), 1);
+
+        // Nenhuma potência depositada durante o atraso de ignição
+        let checks = &results.energy_source_checks;
+        assert_eq!(checks[0].nominal_power, 0.0);
+        assert_eq!(checks[1].nominal_power, 0.0);
+        assert_relative_eq!(checks[2].nominal_power, 10_000.0);
+    }
+
+    #[test]
+    fn test_axis_treatments_reproduce_laplacian() {
+        // T = r² tem laplaciano cilíndrico constante: (1/r)·d/dr(r·2r) = 4
+        let mesh = CylindricalMesh::new(1.0, 0.5, 41, 3, 4);
+        let conductivity = Array2::<f64>::from_elem((mesh.nr, mesh.nz), 2.0);
+        let temperature = Array2::from_shape_fn((mesh.nr, mesh.nz), |(i, _)| mesh.r_coords[i].powi(2));
+
+        for treatment in [AxisTreatment::FiniteVolume, AxisTreatment::LHopital] {
+            for i in 0..mesh.nr - 1 {
+                let laplacian = radial_diffusion(&mesh, &conductivity, &temperature, i, 1, treatment)
+                    / mesh.cell_volumes[[i, 1]];
+                assert_relative_eq!(laplacian, 8.0, max_relative = 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_graded_mesh_diffusion_uses_local_spacing() {
+        // T = r² + z²: o fluxo entre nós vizinhos é exato em qualquer espaçamento, e o
+        // laplaciano (4 + 2)·k deve ser recuperado nos nós internos da malha graduada
+        let mesh = CylindricalMesh::new_graded(
+            1.0, 0.5, 15, 11, 4,
+            MeshGrading::Geometric { ratio: 0.85 },
+            MeshGrading::Hyperbolic { stretching: 1.5 },
+        );
+        let conductivity = Array2::<f64>::from_elem((mesh.nr, mesh.nz), 2.0);
+        let temperature = Array2::from_shape_fn((mesh.nr, mesh.nz), |(i, j)| {
+            mesh.r_coords[i].powi(2) + mesh.z_coords[j].powi(2)
+        });
+
+        for treatment in [AxisTreatment::FiniteVolume, AxisTreatment::LHopital] {
+            for i in 0..mesh.nr - 1 {
+                for j in 1..mesh.nz - 1 {
+                    let laplacian = (radial_diffusion(&mesh, &conductivity, &temperature, i, j, treatment)
+                        + axial_diffusion(&mesh, &conductivity, &temperature, i, j))
+                        / mesh.cell_volumes[[i, j]];
+                    assert_relative_eq!(laplacian, 12.0, max_relative = 1e-9);
+                }
+            }
+        }
+
+        let mut params = SimulationParameters::new(1.0, 0.5, 7, 7);
+        params.radial_grading = MeshGrading::Geometric { ratio: -1.0 };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_axis_treatments_agree_on_centerline() {
+        let run_with = |treatment: AxisTreatment| {
+            let mut params = SimulationParameters::new(1.0, 0.5, 9, 5);
+            params.time_steps = 5;
+            params.time_step = 1.0;
+            params.total_time = 5.0;
+            params.axis_treatment = treatment;
+            params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+            HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap()
+        };
+        let finite_volume = run_with(AxisTreatment::FiniteVolume);
+        let lhopital = run_with(AxisTreatment::LHopital);
+
+        let rise_fv = finite_volume.temperature[[0, 2, 5]] - 25.0;
+        let rise_lh = lhopital.temperature[[0, 2, 5]] - 25.0;
+        assert!(rise_fv > 0.0 && rise_fv.is_finite());
+        assert_relative_eq!(rise_fv, rise_lh, max_relative = 0.05);
+    }
+
+    #[test]
+    fn test_tridiagonal_solver() {
+        // [2 -1 0; -1 2 -1; 0 -1 2]·x = [1, 0, 1] → x = [1, 1, 1]
+        let x = solve_tridiagonal(&[0.0, -1.0, -1.0], &[2.0, 2.0, 2.0], &[-1.0, -1.0, 0.0], &[1.0, 0.0, 1.0]).unwrap();
+        for value in x {
+            assert_relative_eq!(value, 1.0, epsilon = 1e-12);
+        }
+    }
+
+    fn create_scheme_parameters(scheme: SolverScheme, n: usize, time_step: f64) -> SimulationParameters {
+        let mut params = SimulationParameters::new(1.0, 0.5, n, n);
+        params.time_steps = 5;
+        params.time_step = time_step;
+        params.total_time = 5.0 * time_step;
+        params.enable_convection = false;
+        params.enable_radiation = false;
+        params.solver_scheme = scheme;
+        params.set_material(MaterialProperties::new("Constant", 7850.0, 490.0, 45.0));
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        params
+    }
+
+    #[test]
+    fn test_adi_matches_explicit_for_small_steps() {
+        let run_with = |scheme: SolverScheme| {
+            let params = create_scheme_parameters(scheme, 9, 1.0);
+            HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap()
+        };
+        let explicit = run_with(SolverScheme::Explicit);
+        let adi = run_with(SolverScheme::Adi);
+
+        let rise_explicit = explicit.temperature[[0, 4, 5]] - 25.0;
+        let rise_adi = adi.temperature[[0, 4, 5]] - 25.0;
+        assert!(rise_explicit > 0.0);
+        assert_relative_eq!(rise_adi, rise_explicit, max_relative = 0.05);
+
+        // Sem perdas, a energia armazenada é a mesma nos dois esquemas
+        let stored = |results: &SimulationResults| {
+            let last = results.enthalpy.shape()[2] - 1;
+            results.enthalpy.slice(s![.., .., last]).iter().zip(results.mesh.cell_volumes.iter())
+                .map(|(h, v)| h * v)
+                .sum::<f64>()
+        };
+        assert_relative_eq!(stored(&adi), stored(&explicit), max_relative = 1e-6);
+    }
+
+    #[test]
+    fn test_adi_stable_for_large_steps() {
+        // Passo muito acima do limite de estabilidade explícito para esta malha
+        let params = create_scheme_parameters(SolverScheme::Adi, 41, 500.0);
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        let last = results.temperature.shape()[2] - 1;
+        let final_field = results.temperature.slice(s![.., .., last]);
+        assert!(final_field.iter().all(|t| t.is_finite() && *t >= 25.0 - 1e-6));
+        assert!(final_field.iter().cloned().fold(f64::NEG_INFINITY, f64::max) > 25.0);
+    }
+
+    #[test]
+    fn test_property_cache_matches_direct_specific_heat() {
+        let config = PropertyCacheConfig::default();
+        for scheme in [SolverScheme::Explicit, SolverScheme::Adi] {
+            let run_with = |cache: Option<PropertyCacheConfig>| {
+                let mut params = create_scheme_parameters(scheme, 9, 1.0);
+                let mut material = MaterialProperties::new("CpVariavel", 7850.0, 490.0, 45.0);
+                material.specific_heat_coefficients = Some(vec![490.0, 20.0, 1.0]);
+                material.reference_temperature = Some(25.0);
+                params.set_material(material);
+                params.property_cache = cache;
+                HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap()
+            };
+            let direct = run_with(None);
+            let cached = run_with(Some(config.clone()));
+
+            // O cp tabelado difere do direto em no máximo `max_relative_error`
+            let rise_direct = direct.temperature.mapv(|t| t - 25.0);
+            let rise_cached = cached.temperature.mapv(|t| t - 25.0);
+            let peak = rise_direct.iter().cloned().fold(0.0, f64::max);
+            assert!(peak > 0.0);
+            for (c, d) in rise_cached.iter().zip(rise_direct.iter()) {
+                assert!((c - d).abs() <= 10.0 * config.max_relative_error * peak, "{:?}: {} vs {}", scheme, c, d);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adaptive_time_step_respects_stability_limit() {
+        let mut params = create_scheme_parameters(SolverScheme::Explicit, 21, 50.0);
+        params.adaptive_time_step = Some(AdaptiveTimeStepConfig::default());
+        let mut solver = HeatSolver::new(params).unwrap();
+        let stable = solver.stable_time_step();
+        assert!(stable < 50.0);
+
+        let results = solver.run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        let sequence = &results.time_step_sequence;
+        assert!(sequence.len() > results.executed_steps);
+        assert!(sequence.iter().all(|&dt| dt > 0.0 && dt <= 50.0 + 1e-9));
+        assert_relative_eq!(sequence.iter().sum::<f64>(), 250.0, epsilon = 1e-6);
+
+        let last = results.temperature.shape()[2] - 1;
+        let final_field = results.temperature.slice(s![.., .., last]);
+        assert!(final_field.iter().all(|t| t.is_finite() && *t >= 25.0 - 1e-6 && *t < 1e5));
+
+        // Passo fixo não registra subpassos
+        let fixed = HeatSolver::new(create_scheme_parameters(SolverScheme::Explicit, 5, 1.0)).unwrap()
+            .run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert!(fixed.time_step_sequence.is_empty());
+    }
+
+    #[test]
+    fn test_convergence_monitor_records_steps() {
+        let fixed = HeatSolver::new(create_scheme_parameters(SolverScheme::Explicit, 5, 1.0)).unwrap()
+            .run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        let records = &fixed.convergence.records;
+        assert_eq!(records.len(), fixed.executed_steps);
+        assert_eq!(records.iter().map(|r| r.step).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+
+        let change = (&fixed.temperature.slice(s![.., .., 3]) - &fixed.temperature.slice(s![.., .., 2]))
+            .iter()
+            .fold(0.0, |max: f64, d| max.max(d.abs()));
+        assert_relative_eq!(records[2].max_temperature_change, change, epsilon = 1e-12);
+        assert_relative_eq!(records[2].residual_max, change / 1.0, epsilon = 1e-12);
+        assert!(records[2].residual_l2 > 0.0 && records[2].residual_l2 <= records[2].residual_max);
+        assert!(records.iter().all(|r| r.limiter_activations.is_empty()));
+
+        // Passo de saída acima do limite de estabilidade aciona o limitador
+        let mut params = create_scheme_parameters(SolverScheme::Explicit, 21, 50.0);
+        params.adaptive_time_step = Some(AdaptiveTimeStepConfig::default());
+        let adaptive = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert!(adaptive.convergence.total_activations(LimiterKind::StabilityLimit) > 0);
+    }
+
+    #[test]
+    fn test_moisture_evaporation_consumes_latent_heat() {
+        // Carga úmida acima de 100 °C, sem fontes nem trocas: o excesso de entalpia evapora água
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        params.enable_radiation = false;
+        params.enable_convection = false;
+        params.initial_temperature = 150.0;
+        let mut waste = MaterialProperties::new("RSU úmido", 800.0, 1500.0, 0.5);
+        waste.moisture_content = 40.0;
+        params.set_material(waste);
+
+        let dry = HeatSolver::new(params.clone()).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert!(dry.moisture.is_none());
+        assert!(dry.temperature.slice(s![.., .., 5]).iter().all(|&t| (t - 150.0).abs() < 1e-6));
+
+        params.moisture_evaporation = Some(MoistureEvaporationConfig::default());
+        let wet = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert!(wet.temperature.slice(s![.., .., 5]).iter().all(|&t| (t - 100.0).abs() < 1e-6));
+
+        let info = wet.moisture.as_ref().unwrap();
+        let mass = 800.0 * wet.mesh.cell_volumes.sum();
+        assert_relative_eq!(info.initial_water_mass, 0.4 * mass, epsilon = 1e-9);
+        assert_relative_eq!(info.evaporated_water, mass * 1500.0 * 50.0 / WATER_LATENT_HEAT, epsilon = 1e-9);
+        assert_eq!(info.evaporation_history.len(), 5);
+        assert_relative_eq!(info.evaporation_history[4], info.evaporated_water, epsilon = 1e-12);
+        assert!(info.remaining_moisture.iter().all(|&m| m > 0.0 && m < 0.4));
+    }
+
+    #[test]
+    fn test_pyrolysis_reaction_heat_balances_enthalpy() {
+        // Carga adiabática a 400 °C com pirólise endotérmica: o calor de reação esfria a carga
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        params.enable_radiation = false;
+        params.enable_convection = false;
+        params.enable_phase_changes = false;
+        params.initial_temperature = 400.0;
+        params.set_material(MaterialProperties::new("Biomassa", 800.0, 1500.0, 0.5));
+        let kinetics = ArrheniusKinetics { pre_exponential_factor: 1e5, activation_energy: 1e5, reaction_order: 1.0 };
+        let mut config = ReactionConfig::new(kinetics, 1e6);
+        config.reactive_fraction = 0.7;
+        params.reactions = Some(config);
+
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        let info = results.reactions.as_ref().unwrap();
+        assert!(info.conversion.iter().all(|&alpha| alpha > 0.0 && alpha < 1.0));
+        assert!(results.temperature.slice(s![.., .., 5]).iter().all(|&t| t < 400.0));
+        assert_eq!(info.conversion_history.len(), 5);
+        assert_relative_eq!(info.initial_reactive_mass, 0.7 * 800.0 * results.mesh.cell_volumes.sum(), epsilon = 1e-9);
+
+        // A queda de entalpia da carga é o calor absorvido pela reação
+        let enthalpy_drop: f64 = (0..5).flat_map(|i| (0..5).map(move |j| (i, j)))
+            .map(|(i, j)| 800.0 * results.mesh.cell_volumes[[i, j]]
+                * (results.enthalpy[[i, j, 0]] - results.enthalpy[[i, j, 5]]))
+            .sum();
+        assert!(info.reaction_heat > 0.0);
+        assert_relative_eq!(enthalpy_drop, info.reaction_heat, max_relative = 1e-9);
+        assert_relative_eq!(info.reaction_heat, 1e6 * info.converted_mass, max_relative = 1e-9);
+
+        // Sem oxidação do carvão, toda a massa convertida sai como voláteis no gás de exaustão
+        let balance = results.mass_balance.as_ref().unwrap();
+        assert_eq!(balance.off_gas_history.len(), 5);
+        assert_relative_eq!(balance.released_volatiles, info.converted_mass, max_relative = 1e-12);
+        assert_relative_eq!(balance.off_gas_history[4], balance.off_gas_mass(), max_relative = 1e-12);
+        assert!(balance.off_gas_flow_history.iter().all(|&flow| flow > 0.0));
+    }
+
+    #[test]
+    fn test_boundary_conditions_drive_wall_and_top_exchange() {
+        // Carga a 500 °C sem fontes: a parede em Robin perde calor para o meio externo
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        params.enable_radiation = false;
+        params.enable_convection = false;
+        params.enable_phase_changes = false;
+        params.initial_temperature = 500.0;
+
+        let adiabatic = HeatSolver::new(params.clone()).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert!(adiabatic.temperature.slice(s![.., .., 5]).iter().all(|&t| (t - 500.0).abs() < 1e-9));
+
+        for scheme in [SolverScheme::Explicit, SolverScheme::Adi] {
+            let mut cooled = params.clone();
+            cooled.solver_scheme = scheme;
+            cooled.boundary_conditions.wall = BoundaryCondition::Robin { coefficient: 50.0, reference_temperature: 25.0 };
+            cooled.boundary_conditions.top = BoundaryCondition::Dirichlet { temperature: 900.0 };
+            let results = HeatSolver::new(cooled).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+            let final_temperature = results.temperature.index_axis(ndarray::Axis(2), 5);
+            assert!(final_temperature[[4, 0]] < 500.0);
+            assert!(final_temperature[[0, 4]] > 500.0);
+            assert!((final_temperature[[0, 0]] - 500.0).abs() < 1.0);
+        }
+
+        params.boundary_conditions.bottom = BoundaryCondition::Robin { coefficient: -1.0, reference_temperature: 25.0 };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_formula_source_adds_energy_per_step() {
+        // Carga adiabática sem tochas ativas aquecida por S = q0·t
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        params.enable_radiation = false;
+        params.enable_convection = false;
+        params.enable_phase_changes = false;
+        params.set_material(MaterialProperties::new("Carga", 800.0, 1500.0, 0.5));
+        let formula = Formula {
+            name: "Aquecimento em rampa".to_string(),
+            description: "Fonte uniforme crescente no tempo".to_string(),
+            source: "q0 * t".to_string(),
+            ast: None,
+            parameters: Vec::new(),
+            category: FormulaCategory::HeatSource,
+            result_unit: "W/m³".to_string(),
+            tests: Vec::new(),
+        };
+        let mut term = FormulaSourceTerm::new(formula);
+        term.parameters.insert("q0".to_string(), 1000.0);
+        params.formula_source = Some(term.clone());
+        assert!(HeatSolver::new(params.clone()).is_err());
+
+        // Parâmetros constantes precisam ser declarados pela fórmula
+        term.formula.parameters.push(FormulaParameter {
+            name: "q0".to_string(),
+            description: "Taxa de crescimento da fonte".to_string(),
+            param_type: ParameterType::Float,
+            default_value: ParameterValue::Float(0.0),
+            unit: "W/(m³·s)".to_string(),
+            min_value: None,
+            max_value: None,
+        });
+        params.formula_source = Some(term);
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        // Fonte avaliada em t = 0, 1, 2, 3 e 4 s
+        let energy_gain: f64 = (0..5).flat_map(|i| (0..5).map(move |j| (i, j)))
+            .map(|(i, j)| 800.0 * results.mesh.cell_volumes[[i, j]]
+                * (results.enthalpy[[i, j, 5]] - results.enthalpy[[i, j, 0]]))
+            .sum();
+        assert_relative_eq!(energy_gain, 1000.0 * 10.0 * results.mesh.cell_volumes.sum(), max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_power_control_drives_torch_power() {
+        // Lei de controle que dobra a potência aplicada a cada passo, limitada a 40 kW
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 4;
+        params.time_step = 1.0;
+        params.total_time = 4.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        let formula = Formula {
+            name: "Dobra".to_string(),
+            description: String::new(),
+            source: "2.0 * power".to_string(),
+            ast: None,
+            parameters: Vec::new(),
+            category: FormulaCategory::PhysicalModel,
+            result_unit: "kW".to_string(),
+            tests: Vec::new(),
+        };
+        let mut control = FormulaPowerControl {
+            formula,
+            probes: vec![ControlProbe { name: "core".to_string(), r: 0.0, z: 0.5 }],
+            torch_ids: vec!["torch2".to_string()],
+            parameters: HashMap::new(),
+            min_power: 0.0,
+            max_power: 40.0,
+        };
+        params.power_control = Some(control.clone());
+        assert!(params.validate().is_err());
+
+        control.torch_ids = vec!["torch1".to_string()];
+        params.power_control = Some(control);
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert_eq!(results.controlled_power_history, Some(vec![20.0, 40.0, 40.0, 40.0]));
+    }
+
+    #[test]
+    fn test_exit_criteria_stop_run_early() {
+        let base = || {
+            let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+            params.time_steps = 20;
+            params.time_step = 1.0;
+            params.total_time = 20.0;
+            params
+        };
+
+        // Tocha desligada e sem trocas: o campo não varia, regime permanente após 3 passos
+        let mut params = base();
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        params.enable_radiation = false;
+        params.enable_convection = false;
+        params.exit_criteria = vec![ExitCriterion::SteadyState { max_rate: 1e-6, consecutive_steps: 3 }];
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert_eq!(results.executed_steps, 3);
+        assert_eq!(results.temperature.shape()[2], 4);
+        match &results.stop_reason {
+            StopReason::CriterionMet { criterion, time, .. } => {
+                assert_eq!(*criterion, 0);
+                assert_relative_eq!(*time, 3.0);
+            }
+            other => panic!("Motivo de parada inesperado: {:?}", other),
+        }
+        assert!(results.annotations.iter().any(|a| a.kind == AnnotationKind::Note));
+
+        // Critério inalcançável: todos os passos são integrados
+        let mut params = base();
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        params.exit_criteria = vec![ExitCriterion::AllAboveTemperature { target: 5000.0, zone: None }];
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        assert_eq!(results.executed_steps, 20);
+        assert_eq!(results.stop_reason, StopReason::Completed);
+
+        let mut params = base();
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        params.exit_criteria = vec![ExitCriterion::AllAboveTemperature { target: 100.0, zone: Some("Leito".to_string()) }];
+        assert!(params.validate().is_err());
+        params.exit_criteria = vec![ExitCriterion::MeltFractionAtLeast { fraction: 0.5, zone: None }];
+        params.enable_phase_changes = false;
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_heat_flux_from_temperature_gradient() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 6);
+        params.time_steps = 1;
+        params.time_step = 1.0;
+        params.total_time = 1.0;
+        params.material = MaterialProperties::new("Base", 1000.0, 100.0, 2.0);
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        let mut results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        // T = 100 + 50·z + 20·r²: q_z = −2·50 em todos os nós; q_r = −2·40·r nos nós internos
+        let mesh = results.mesh.clone();
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                let (r, z) = (mesh.r_coords[i], mesh.z_coords[j]);
+                results.temperature[[i, j, 0]] = 100.0 + 50.0 * z + 20.0 * r * r;
+            }
+        }
+        let (radial, axial) = results.heat_flux_at(0).unwrap();
+        assert!(axial.iter().all(|&q| (q + 100.0).abs() < 1e-9));
+        assert!(radial.column(2).iter().take(4).enumerate().all(|(i, &q)| {
+            (i == 0 && q == 0.0) || (q + 80.0 * mesh.r_coords[i]).abs() < 1e-9
+        }));
+        assert!(results.heat_flux_at(5).is_err());
+    }
+
+    #[test]
+    fn test_temperature_at_point_interpolates_space_and_time() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 6);
+        params.time_steps = 2;
+        params.time_step = 10.0;
+        params.total_time = 20.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        let mut results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        // Campo bilinear em (r, z) que cresce linearmente no tempo: T = 100 + 10·r·z + t
+        let mesh = results.mesh.clone();
+        for step in 0..=2 {
+            for i in 0..mesh.nr {
+                for j in 0..mesh.nz {
+                    let (r, z) = (mesh.r_coords[i], mesh.z_coords[j]);
+                    results.temperature[[i, j, step]] = 100.0 + 10.0 * r * z + 10.0 * step as f64;
+                }
+            }
+        }
+        let expected = |r: f64, z: f64, t: f64| 100.0 + 10.0 * r * z + t;
+        for &(r, z, t) in &[(0.1, 0.3, 0.0), (0.33, 0.71, 4.0), (0.5, 1.0, 20.0), (0.0, 0.55, 15.5)] {
+            assert_relative_eq!(results.temperature_at_point(r, z, t).unwrap(), expected(r, z, t), epsilon = 1e-9);
+        }
+
+        assert!(results.temperature_at_point(0.6, 0.5, 1.0).is_err());
+        assert!(results.temperature_at_point(0.2, -0.1, 1.0).is_err());
+        assert!(results.temperature_at_point(0.2, 0.5, 20.5).is_err());
+        assert!(results.temperature_at_point(0.2, 0.5, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_decimated_history_strides() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 6);
+        params.time_steps = 5;
+        params.time_step = 2.0;
+        params.total_time = 10.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 0.0, 0.01, 5000.0));
+        let mut results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        for ((i, j, step), value) in results.temperature.indexed_iter_mut() {
+            *value = 1000.0 * step as f64 + 10.0 * i as f64 + j as f64;
+        }
+
+        let (layout, history) = results.decimated_history(2, 4, 2).unwrap();
+        assert_eq!(layout.r_indices, vec![0, 2, 4]);
+        assert_eq!(layout.z_indices, vec![0, 4, 5]);
+        assert_eq!(layout.steps, vec![0, 2, 4, 5]);
+        assert_eq!(layout.times, vec![0.0, 4.0, 8.0, 10.0]);
+        assert_eq!(history.dim(), (4, 3, 3));
+        assert_eq!(layout.len(), history.len());
+        assert_eq!(history[[3, 1, 2]], 5000.0 + 20.0 + 5.0);
+        assert_eq!(history[[1, 2, 1]], 2000.0 + 40.0 + 4.0);
+
+        // Intervalo 1 reproduz o histórico completo
+        let (full, _) = results.decimated_history(1, 1, 1).unwrap();
+        assert_eq!(full.len(), 5 * 6 * 6);
+        assert!(results.history_layout(0, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_cancellation_returns_partial_results() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 10;
+        params.time_step = 1.0;
+        params.total_time = 10.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+
+        // Cancelamento pelo callback ao fim do terceiro passo
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let callback = |progress: f32| progress < 0.3 - 1e-6;
+        let results = HeatSolver::new(params.clone()).unwrap().run(Some(&callback), cancel_flag.clone()).unwrap();
+        assert_eq!(results.executed_steps, 3);
+        assert_eq!(results.temperature.shape()[2], 4);
+        assert_eq!(results.stop_reason, StopReason::Cancelled { time: 3.0 });
+        assert!(results.annotations.iter().any(|a| a.kind == AnnotationKind::Note));
+
+        // Sinalizador externo já ativo: nenhum passo é integrado
+        cancel_flag.store(true, Ordering::Relaxed);
+        let results = HeatSolver::new(params).unwrap().run(None, cancel_flag).unwrap();
+        assert_eq!(results.executed_steps, 0);
+        assert_eq!(results.temperature.shape()[2], 1);
+        assert_eq!(results.stop_reason, StopReason::Cancelled { time: 0.0 });
+    }
+
+    #[test]
+    fn test_progress_updates_report_solver_steps() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 6;
+        params.time_step = 1.0;
+        params.total_time = 6.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+
+        let updates = std::cell::RefCell::new(Vec::new());
+        let callback = |update: &ProgressUpdate| {
+            updates.borrow_mut().push(*update);
+            true
+        };
+        let previous = HeatSolver::new(params.clone()).unwrap()
+            .run_with_updates(Some(&callback), Arc::new(AtomicBool::new(false))).unwrap();
+        let steps: Vec<usize> = updates.borrow().iter().map(|update| update.current_step).collect();
+        assert_eq!(steps, vec![1, 2, 3, 4, 5, 6]);
+        assert!(updates.borrow().windows(2).all(|pair| pair[0].elapsed_seconds <= pair[1].elapsed_seconds));
+
+        // Execução retomada: passos absolutos a partir do passo de reinício
+        updates.borrow_mut().clear();
+        HeatSolver::resume_from(params, &previous, 4).unwrap()
+            .run_with_updates(Some(&callback), Arc::new(AtomicBool::new(false))).unwrap();
+        let updates = updates.into_inner();
+        assert_eq!(updates.iter().map(|update| update.current_step).collect::<Vec<_>>(), vec![5, 6]);
+        assert!(updates.iter().all(|update| update.total_steps == 6));
+        assert!((updates[0].progress - 5.0 / 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cycle_averages_accumulated_during_run() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 6;
+        params.time_step = 1.0;
+        params.total_time = 6.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        params.averaging_windows = vec![AveragingWindow::Cycles { name: "ciclo".to_string(), start_time: 0.0, period: 2.0 }];
+
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+        let fields = &results.averaged_fields;
+        assert_eq!(fields.len(), 4);
+        assert!(fields[..3].iter().all(|f| f.complete && f.samples == 2));
+        assert!(!fields[3].complete);
+
+        // Ciclo 1: quadros em t = 2 s e t = 3 s
+        let expected = (results.temperature[[0, 0, 2]] + results.temperature[[0, 0, 3]]) / 2.0;
+        assert_relative_eq!(fields[1].mean[[0, 0]], expected, epsilon = 1e-9);
+        assert!(fields[1].amplitude[[0, 0]] >= 0.0);
     }
 
     #[test]
     fn test_phase_change_tracking_enthalpy() {
-        let material = create_test_material_const_cp("MatPhase", Some(100.0), Some(1000.0), None, None, 10.0, 1.0, 1.0);
+        let material = create_test_material_const_cp("MatPhase", Some(100.0), Some(1.0e5), None, None, 1000.0, 1000.0, 1.0);
 
         let mut params = SimulationParameters::new(0.01, 0.005, 3, 3);
         params.material = material;
@@ -1097,9 +3759,10 @@ mod tests {
         params.enable_phase_changes = true;
         params.initial_temperature = 90.0;
 
+        // 1 kW por 0,05 s funde parte dos ~0,8 g do domínio (calor de fusão de 100 kJ/kg)
         params.add_torch(PlasmaTorch::new(
              "torch1",
-             0.0, 0.0, 0.005, 90.0, 0.0, 1000.0, 0.001, 50000.0
+             0.0, 0.0, 0.005, 90.0, 0.0, 1.0, 0.001, 5000.0
         ));
 
         let mut solver = HeatSolver::new(params).unwrap();
@@ -1113,7 +3776,7 @@ mod tests {
 
             let final_melt_fraction = info.melt_fraction.unwrap().slice(s![.., .., res.executed_steps]).to_owned();
             assert!(final_melt_fraction.iter().any(|&f| f > 1e-6), "Nenhuma fusão detectada (verificar dt, potência da tocha, duração). Fração final: {:?}", final_melt_fraction);
-            assert!(final_melt_fraction.iter().all(|&f| f >= -1e-6 && f <= 1.0 + 1e-6), "Fração de fusão fora do intervalo [0, 1]. Fração final: {:?}", final_melt_fraction);
+            assert!(final_melt_fraction.iter().all(|&f| (-1e-6..=1.0 + 1e-6).contains(&f)), "Fração de fusão fora do intervalo [0, 1]. Fração final: {:?}", final_melt_fraction);
         }
     }
 }
@@ -5,8 +5,10 @@ use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread::{self, JoinHandle};
+use std::panic::{self, AssertUnwindSafe};
 
-use super::solver::{SimulationParameters, SimulationResults, HeatSolver};
+use super::frames::LiveFrameBuffer;
+use super::solver::{SimulationParameters, SimulationResults, HeatSolver, StopReason};
 
 /// Enumeração que representa o status da simulação
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +23,8 @@ pub enum SimulationStatus {
     Completed,
     /// Simulação falhou
     Failed,
+    /// Simulação cancelada (com resultados parciais)
+    Cancelled,
 }
 
 /// Estrutura que representa o estado da simulação
@@ -63,6 +67,9 @@ impl SimulationState {
         if self.status == SimulationStatus::Running {
             return Err("Simulação já está em execução".to_string());
         }
+        if self.status == SimulationStatus::Completed {
+            return Err("Simulação já foi concluída; use restart para executá-la novamente".to_string());
+        }
 
         self.status = SimulationStatus::Running;
         self.progress = 0.0;
@@ -72,6 +79,16 @@ impl SimulationState {
         Ok(())
     }
 
+    /// Inicia a simulação novamente, inclusive após a conclusão
+    ///
+    /// Os resultados anteriores são mantidos até serem substituídos pelos da nova execução.
+    pub fn restart(&mut self) -> Result<(), String> {
+        if self.status == SimulationStatus::Completed {
+            self.status = SimulationStatus::NotStarted;
+        }
+        self.start()
+    }
+
     /// Pausa a simulação
     pub fn pause(&mut self) -> Result<(), String> {
         if self.status != SimulationStatus::Running {
@@ -110,6 +127,21 @@ impl SimulationState {
         }
     }
 
+    /// Encerra a simulação cancelada com os resultados parciais
+    pub fn cancel(&mut self, results: SimulationResults) {
+        self.status = SimulationStatus::Cancelled;
+        self.results = Some(results);
+
+        if let Some(start_time) = self.start_time {
+            self.execution_time = start_time.elapsed().as_secs_f64();
+        }
+    }
+
+    /// Verifica se a simulação foi cancelada
+    pub fn is_cancelled(&self) -> bool {
+        self.status == SimulationStatus::Cancelled
+    }
+
     /// Marca a simulação como falha
     pub fn fail(&mut self, error_message: String) {
         self.status = SimulationStatus::Failed;
@@ -136,14 +168,35 @@ impl SimulationState {
     }
 }
 
+/// Estrutura que representa uma notificação de progresso enviada aos observadores
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProgressUpdate {
+    /// Progresso da simulação (0.0 - 1.0)
+    pub progress: f32,
+    /// Passos de tempo concluídos
+    pub current_step: usize,
+    /// Número total de passos de tempo
+    pub total_steps: usize,
+    /// Tempo decorrido desde o início da execução (s)
+    pub elapsed_seconds: f64,
+}
+
+/// Observador de progresso, chamado pela thread da simulação a cada passo concluído
+pub type ProgressObserver = Box<dyn Fn(&ProgressUpdate) + Send>;
+
 /// Estrutura thread-safe para compartilhar o estado da simulação
 pub struct SharedSimulationState {
     /// Estado da simulação
-    state: Arc<Mutex<SimulationState>>,
+    pub(crate) state: Arc<Mutex<SimulationState>>,
     /// Flag para solicitar cancelamento da simulação
     cancel_flag: Arc<AtomicBool>,
     /// Handle para a thread da simulação (se estiver rodando)
-    simulation_thread: Mutex<Option<JoinHandle<()>>>,
+    simulation_thread: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Observador de progresso (chamado com o mutex travado, para que a remoção
+    /// aguarde uma chamada em andamento)
+    progress_observer: Arc<Mutex<Option<ProgressObserver>>>,
+    /// Quadro de temperatura mais recente da execução (buffer duplo)
+    live_frames: Arc<LiveFrameBuffer>,
 }
 
 impl SharedSimulationState {
@@ -152,7 +205,9 @@ impl SharedSimulationState {
         Self {
             state: Arc::new(Mutex::new(SimulationState::new(parameters))),
             cancel_flag: Arc::new(AtomicBool::new(false)),
-            simulation_thread: Mutex::new(None),
+            simulation_thread: Arc::new(Mutex::new(None)),
+            progress_observer: Arc::new(Mutex::new(None)),
+            live_frames: Arc::new(LiveFrameBuffer::new()),
         }
     }
 
@@ -164,6 +219,34 @@ impl SharedSimulationState {
         }
     }
 
+    /// Clears the poisoned flag of the internal mutexes after a caught panic,
+    /// so the shared state remains usable by later FFI calls.
+    pub fn clear_poison(&self) {
+        self.state.clear_poison();
+        self.simulation_thread.clear_poison();
+        self.progress_observer.clear_poison();
+    }
+
+    /// Define (ou remove, com `None`) o observador de progresso. Ao retornar, nenhuma
+    /// chamada ao observador anterior está em andamento nem ocorrerá depois; por isso o
+    /// próprio observador não deve chamar este método.
+    pub fn set_progress_observer(&self, observer: Option<ProgressObserver>) -> Result<(), String> {
+        let mut current = self.progress_observer.lock()
+            .map_err(|e| format!("Failed to lock progress observer mutex: {}", e))?;
+        *current = observer;
+        Ok(())
+    }
+
+    /// Buffer com o quadro de temperatura mais recente da execução em andamento (ou da última)
+    pub fn live_frames(&self) -> &LiveFrameBuffer {
+        &self.live_frames
+    }
+
+    /// Indica se há um observador de progresso registrado
+    pub fn has_progress_observer(&self) -> bool {
+        self.progress_observer.lock().map(|observer| observer.is_some()).unwrap_or(false)
+    }
+
     /// Requests cancellation of the running simulation.
     pub fn request_cancellation(&self) {
         self.cancel_flag.store(true, Ordering::Relaxed);
@@ -193,7 +276,7 @@ impl SharedSimulationState {
     pub fn run_simulation(&self) -> Result<(), String> {
         // Check if already running
         {
-            let mut handle_guard = self.simulation_thread.lock()
+            let handle_guard = self.simulation_thread.lock()
                 .map_err(|e| format!("Failed to lock thread handle mutex: {}", e))?;
             if handle_guard.is_some() {
                 return Err("Simulation thread handle already exists. Call destroy_simulation or wait for completion.".to_string());
@@ -210,35 +293,46 @@ impl SharedSimulationState {
         // Iniciar simulação state
         {
             let mut state = self.state.lock().map_err(|e| format!("Failed to lock state mutex: {}", e))?;
-            state.start()?;
+            state.restart()?;
         }
 
         // Criar clones para a thread
         let state_clone = self.state.clone();
         let cancel_flag_clone = self.cancel_flag.clone();
         let simulation_thread_mutex_clone = self.simulation_thread.clone();
+        let progress_observer_clone = self.progress_observer.clone();
+        let live_frames_clone = self.live_frames.clone();
+        self.live_frames.clear();
 
         // Executar simulação em uma thread separada
         let handle = thread::spawn(move || {
             // Criar solucionador
             let solver_result = HeatSolver::new(parameters);
 
-            let final_status = match solver_result {
+            let (final_status, results) = match solver_result {
                 Err(err) => {
                     eprintln!("Solver initialization failed: {}", err);
-                    SimulationStatus::Failed
+                    (SimulationStatus::Failed, None)
                 }
                 Ok(mut solver) => {
+                    solver.set_live_frame_buffer(live_frames_clone);
+
                     // Definir callback de progresso (adaptado para checar cancelamento)
-                    let progress_callback = |progress: f32| {
+                    let progress_callback = |update: &ProgressUpdate| {
                         if cancel_flag_clone.load(Ordering::Relaxed) {
                             return false;
                         }
+                        // Notificar o observador de progresso, sem o estado travado
+                        if let Ok(observer) = progress_observer_clone.lock() {
+                            if let Some(observer) = observer.as_ref() {
+                                observer(update);
+                            }
+                        }
                         if let Ok(mut state) = state_clone.lock() {
-                            state.update_progress(progress);
+                            state.update_progress(update.progress);
                             if state.status == SimulationStatus::Paused {
                                 drop(state);
-                                while cancel_flag_clone.load(Ordering::Relaxed) == false {
+                                while !cancel_flag_clone.load(Ordering::Relaxed) {
                                     if let Ok(current_state) = state_clone.lock() {
                                         if current_state.status != SimulationStatus::Paused {
                                             break;
@@ -260,16 +354,30 @@ impl SharedSimulationState {
                         true
                     };
 
-                    // Executar simulação
-                    let result = solver.run(Some(&progress_callback), cancel_flag_clone.clone());
+                    // Executar simulação; um pânico do solucionador vira falha da simulação
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                        solver.run_with_updates(Some(&progress_callback), cancel_flag_clone.clone())
+                    })).unwrap_or_else(|payload| {
+                        let message = payload.downcast_ref::<&str>().map(|m| m.to_string())
+                            .or_else(|| payload.downcast_ref::<String>().cloned())
+                            .unwrap_or_else(|| "unknown panic payload".to_string());
+                        Err(format!("Solver panicked: {}", message))
+                    });
 
                     // Retorna o status final baseado no resultado
                     match result {
-                        Ok(_) => SimulationStatus::Completed,
-                        Err(err) if err == "Simulation cancelled" => SimulationStatus::Failed,
+                        Ok(results) if matches!(results.stop_reason, StopReason::Cancelled { .. }) => {
+                            (SimulationStatus::Cancelled, Some(results))
+                        }
+                        Ok(results) => (SimulationStatus::Completed, Some(results)),
                         Err(err) => {
                             eprintln!("Simulation run failed: {}", err);
-                            SimulationStatus::Failed
+                            // O estado pode ter sido envenenado por um pânico em outra thread
+                            state_clone.clear_poison();
+                            if let Ok(mut state) = state_clone.lock() {
+                                state.error_message = Some(err);
+                            }
+                            (SimulationStatus::Failed, None)
                         }
                     }
                 }
@@ -277,25 +385,19 @@ impl SharedSimulationState {
 
             // Atualizar estado final (outside solver Result match)
             if let Ok(mut state) = state_clone.lock() {
-                match final_status {
-                    SimulationStatus::Completed => {
-                        if state.status != SimulationStatus::Failed {
-                            state.status = SimulationStatus::Completed;
-                            state.progress = 1.0;
-                            if let Some(start_time) = state.start_time {
-                                state.execution_time = start_time.elapsed().as_secs_f64();
-                            }
+                match (final_status, results) {
+                    (SimulationStatus::Completed, Some(results))
+                        if state.status != SimulationStatus::Failed => {
+                            state.complete(results);
                         }
+                    (SimulationStatus::Cancelled, Some(results)) => {
+                        state.cancel(results);
                     }
-                    SimulationStatus::Failed => {
-                        if state.status != SimulationStatus::Failed {
-                            state.fail(if cancel_flag_clone.load(Ordering::Relaxed) {
-                                "Simulation cancelled by user".to_string()
-                            } else {
-                                state.error_message.clone().unwrap_or_else(|| "Simulation failed".to_string())
-                            });
+                    (SimulationStatus::Failed, _)
+                        if state.status != SimulationStatus::Failed => {
+                            let message = state.error_message.clone().unwrap_or_else(|| "Simulation failed".to_string());
+                            state.fail(message);
                         }
-                    }
                     _ => {}
                 }
             } else {
@@ -326,12 +428,11 @@ impl SharedSimulationState {
 mod tests {
     use super::*;
     use super::super::physics::PlasmaTorch;
-    use std::time::Duration;
 
     #[test]
     fn test_simulation_state_flow() {
         let mut params = SimulationParameters::new(1.0, 0.5, 10, 20);
-        params.add_torch(PlasmaTorch::new(0.0, 0.0, 90.0, 0.0, 100.0, 0.01, 5000.0));
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.0, 90.0, 0.0, 100.0, 0.01, 5000.0));
 
         let shared_state = SharedSimulationState::new(params.clone());
 
@@ -366,13 +467,7 @@ mod tests {
         // Complete (mocked)
         {
              let mut state_guard = shared_state.state.lock().unwrap();
-             let dummy_results = SimulationResults {
-                 parameters: params.clone(),
-                 mesh: super::super::mesh::CylindricalMesh::new(params.height, params.radius, params.nr, params.nz, params.ntheta).unwrap(),
-                 temperature: ndarray::Array3::zeros((params.nr, params.nz, 1)),
-                 execution_time: 0.0,
-                 phase_change_info: None,
-             };
+             let dummy_results = SimulationResults::for_tests(params.clone(), ndarray::Array3::zeros((params.nr, params.nz, 1)));
              state_guard.complete(dummy_results);
             assert_eq!(state_guard.status, SimulationStatus::Completed);
             assert_eq!(state_guard.progress, 1.0);
@@ -392,6 +487,44 @@ mod tests {
          }
     }
 
+    #[test]
+    fn test_restart_after_completion() {
+        let params = SimulationParameters::new(1.0, 0.5, 10, 20);
+        let mut state = SimulationState::new(params.clone());
+        state.start().unwrap();
+        state.complete(SimulationResults::for_tests(params.clone(), ndarray::Array3::zeros((params.nr, params.nz, 1))));
+
+        // Uma simulação concluída só é executada de novo explicitamente, mantendo os resultados
+        assert!(state.start().is_err());
+        state.restart().unwrap();
+        assert_eq!(state.status, SimulationStatus::Running);
+        assert!(state.results.is_some());
+        assert!(state.restart().is_err());
+    }
+
+    #[test]
+    fn test_progress_observer_registration() {
+        let shared_state = SharedSimulationState::new(SimulationParameters::new(1.0, 0.5, 10, 20));
+        assert!(!shared_state.has_progress_observer());
+
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let sink = updates.clone();
+        shared_state.set_progress_observer(Some(Box::new(move |update: &ProgressUpdate| {
+            sink.lock().unwrap().push(*update);
+        }))).unwrap();
+        assert!(shared_state.has_progress_observer());
+
+        // O observador é chamado como a thread da simulação o chama
+        let update = ProgressUpdate { progress: 0.5, current_step: 5, total_steps: 10, elapsed_seconds: 0.1 };
+        if let Some(observer) = shared_state.progress_observer.lock().unwrap().as_ref() {
+            observer(&update);
+        }
+        assert_eq!(updates.lock().unwrap().as_slice(), &[update]);
+
+        shared_state.set_progress_observer(None).unwrap();
+        assert!(!shared_state.has_progress_observer());
+    }
+
     // Note: Testing run_simulation requires more setup, possibly mocking HeatSolver::run
     // or running a very short dummy simulation.
     // Testing cancellation and join requires careful thread synchronization.
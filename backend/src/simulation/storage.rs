@@ -0,0 +1,350 @@
+// Implementação dos backends de armazenamento de resultados (sistema de arquivos local e object stores S3)
+//
+// Os resultados são gravados no formato de `simulation::archive` como `<chave>.results`,
+// de modo que execuções gravadas por um backend (ex.: CLI em uma máquina na nuvem) podem
+// ser lidas por outro (ex.: aplicativo desktop apontando para o mesmo bucket).
+// O backend S3 está disponível apenas com a feature `s3`.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::simulation::archive::{self, IntegrityReport};
+use crate::simulation::solver::SimulationResults;
+
+/// Extensão dos arquivos de resultados nos backends
+pub const RESULTS_EXTENSION: &str = "results";
+
+/// Interface de um backend de armazenamento de resultados
+///
+/// As chaves identificam execuções (ex.: "estudo-a/run_001") e podem conter letras,
+/// números, '-', '_', '.' e '/' como separador de pastas.
+pub trait ResultsStorage: Send + Sync {
+    /// Descrição do backend (ex.: caminho ou URL do bucket)
+    fn describe(&self) -> String;
+
+    /// Grava os bytes de uma execução
+    fn write(&self, key: &str, bytes: &[u8]) -> Result<(), String>;
+
+    /// Lê os bytes de uma execução
+    fn read(&self, key: &str) -> Result<Vec<u8>, String>;
+
+    /// Lista as chaves das execuções armazenadas, em ordem
+    fn list(&self) -> Result<Vec<String>, String>;
+
+    /// Remove uma execução; retorna false se ela não existe
+    fn delete(&self, key: &str) -> Result<bool, String>;
+
+    /// Grava os resultados de uma execução (arquivo com somas de verificação)
+    fn save_results(&self, key: &str, results: &SimulationResults) -> Result<(), String> {
+        validate_key(key)?;
+        self.write(key, &archive::encode_results_archive(results)?)
+    }
+
+    /// Lê os resultados de uma execução com o relatório de integridade
+    fn load_results(&self, key: &str) -> Result<(SimulationResults, IntegrityReport), String> {
+        validate_key(key)?;
+        archive::decode_results_archive(&self.read(key)?)
+            .map_err(|e| format!("Execução inválida {} em {}: {}", key, self.describe(), e))
+    }
+}
+
+/// Valida uma chave de execução
+pub fn validate_key(key: &str) -> Result<(), String> {
+    let valid_chars = key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+    let valid_parts = key.split('/').all(|part| !part.is_empty() && part != "." && part != "..");
+    if key.is_empty() || !valid_chars || !valid_parts {
+        return Err(format!("Chave de execução inválida: {}", key));
+    }
+    Ok(())
+}
+
+/// Estrutura que representa o armazenamento em um diretório local (ou montagem de rede)
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    /// Diretório raiz
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Cria o armazenamento no diretório informado (criado se necessário)
+    pub fn new(root: &Path) -> Result<Self, String> {
+        fs::create_dir_all(root).map_err(|e| format!("Erro ao criar diretório de resultados {:?}: {}", root, e))?;
+        Ok(Self { root: root.to_path_buf() })
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, String> {
+        validate_key(key)?;
+        Ok(self.root.join(format!("{}.{}", key, RESULTS_EXTENSION)))
+    }
+
+    fn collect_keys(&self, dir: &Path, prefix: &str, keys: &mut Vec<String>) -> Result<(), String> {
+        let entries = fs::read_dir(dir).map_err(|e| format!("Erro ao ler diretório {:?}: {}", dir, e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| format!("Erro ao ler diretório {:?}: {}", dir, e))?.path();
+            let name = match path.file_name().and_then(|n| n.to_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+            if path.is_dir() {
+                self.collect_keys(&path, &format!("{}{}/", prefix, name), keys)?;
+            } else if let Some(stem) = name.strip_suffix(&format!(".{}", RESULTS_EXTENSION)) {
+                keys.push(format!("{}{}", prefix, stem));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ResultsStorage for LocalStorage {
+    fn describe(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn write(&self, key: &str, bytes: &[u8]) -> Result<(), String> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Erro ao criar diretório {:?}: {}", parent, e))?;
+        }
+        // Gravação atômica: leitores nunca veem um arquivo parcial
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, bytes).map_err(|e| format!("Erro ao gravar {:?}: {}", temporary, e))?;
+        fs::rename(&temporary, &path).map_err(|e| format!("Erro ao gravar {:?}: {}", path, e))
+    }
+
+    fn read(&self, key: &str) -> Result<Vec<u8>, String> {
+        let path = self.path(key)?;
+        fs::read(&path).map_err(|e| format!("Erro ao ler {:?}: {}", path, e))
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let mut keys = Vec::new();
+        self.collect_keys(&self.root, "", &mut keys)?;
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn delete(&self, key: &str) -> Result<bool, String> {
+        let path = self.path(key)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(&path).map(|_| true).map_err(|e| format!("Erro ao remover {:?}: {}", path, e))
+    }
+}
+
+/// Estrutura que representa a configuração de um object store compatível com S3
+/// (AWS S3, MinIO, Ceph, R2...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3StorageConfig {
+    /// URL do serviço (ex.: "https://s3.us-east-1.amazonaws.com" ou "http://minio:9000")
+    pub endpoint: String,
+    /// Nome do bucket
+    pub bucket: String,
+    /// Região
+    #[serde(default = "default_region")]
+    pub region: String,
+    /// Prefixo dos objetos (ex.: "plasma/resultados/")
+    #[serde(default)]
+    pub prefix: String,
+    /// Chave de acesso (padrão: variável `AWS_ACCESS_KEY_ID`)
+    #[serde(default)]
+    pub access_key: Option<String>,
+    /// Chave secreta (padrão: variável `AWS_SECRET_ACCESS_KEY`)
+    #[serde(default)]
+    pub secret_key: Option<String>,
+    /// Endereça o bucket como subdomínio em vez de caminho (AWS); MinIO usa caminho
+    #[serde(default)]
+    pub virtual_host_style: bool,
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+/// Enumeração que representa a configuração de um backend de armazenamento
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StorageConfig {
+    /// Diretório local
+    Local {
+        /// Diretório raiz
+        root: String,
+    },
+    /// Object store compatível com S3 (requer a feature `s3`)
+    S3(S3StorageConfig),
+}
+
+/// Abre o backend de armazenamento descrito pela configuração
+pub fn open_storage(config: &StorageConfig) -> Result<Box<dyn ResultsStorage>, String> {
+    match config {
+        StorageConfig::Local { root } => Ok(Box::new(LocalStorage::new(Path::new(root))?)),
+        #[cfg(feature = "s3")]
+        StorageConfig::S3(config) => Ok(Box::new(S3Storage::new(config)?)),
+        #[cfg(not(feature = "s3"))]
+        StorageConfig::S3(config) => Err(format!(
+            "Armazenamento S3 ({}) indisponível: biblioteca compilada sem a feature `s3`",
+            config.bucket
+        )),
+    }
+}
+
+/// Estrutura que representa o armazenamento em um object store compatível com S3
+///
+/// As requisições usam URLs pré-assinadas (AWS Signature V4) com validade curta.
+#[cfg(feature = "s3")]
+pub struct S3Storage {
+    bucket: rusty_s3::Bucket,
+    credentials: rusty_s3::Credentials,
+    prefix: String,
+}
+
+#[cfg(feature = "s3")]
+impl S3Storage {
+    /// Validade das URLs assinadas
+    const SIGNATURE_DURATION: std::time::Duration = std::time::Duration::from_secs(300);
+
+    /// Cria o backend a partir da configuração (credenciais do ambiente se omitidas)
+    pub fn new(config: &S3StorageConfig) -> Result<Self, String> {
+        let endpoint = url::Url::parse(&config.endpoint)
+            .map_err(|e| format!("URL do serviço S3 inválida {}: {}", config.endpoint, e))?;
+        let style = if config.virtual_host_style { rusty_s3::UrlStyle::VirtualHost } else { rusty_s3::UrlStyle::Path };
+        let bucket = rusty_s3::Bucket::new(endpoint, style, config.bucket.clone(), config.region.clone())
+            .map_err(|e| format!("Bucket S3 inválido {}: {}", config.bucket, e))?;
+        let credential = |value: &Option<String>, variable: &str| {
+            value.clone()
+                .or_else(|| std::env::var(variable).ok())
+                .ok_or_else(|| format!("Credencial S3 ausente (configuração ou variável {})", variable))
+        };
+        let credentials = rusty_s3::Credentials::new(
+            credential(&config.access_key, "AWS_ACCESS_KEY_ID")?,
+            credential(&config.secret_key, "AWS_SECRET_ACCESS_KEY")?,
+        );
+        let mut prefix = config.prefix.trim_start_matches('/').to_string();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        Ok(Self { bucket, credentials, prefix })
+    }
+
+    fn object_name(&self, key: &str) -> Result<String, String> {
+        validate_key(key)?;
+        Ok(format!("{}{}.{}", self.prefix, key, RESULTS_EXTENSION))
+    }
+
+    fn request_error(&self, operation: &str, key: &str, error: ureq::Error) -> String {
+        format!("Erro S3 ao {} {} em {}: {}", operation, key, self.describe(), error)
+    }
+}
+
+#[cfg(feature = "s3")]
+impl ResultsStorage for S3Storage {
+    fn describe(&self) -> String {
+        format!("s3://{}/{}", self.bucket.name(), self.prefix)
+    }
+
+    fn write(&self, key: &str, bytes: &[u8]) -> Result<(), String> {
+        use rusty_s3::S3Action;
+        let url = self.bucket.put_object(Some(&self.credentials), &self.object_name(key)?).sign(Self::SIGNATURE_DURATION);
+        ureq::put(url.as_str())
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(bytes)
+            .map(|_| ())
+            .map_err(|e| self.request_error("gravar", key, e))
+    }
+
+    fn read(&self, key: &str) -> Result<Vec<u8>, String> {
+        use rusty_s3::S3Action;
+        use std::io::Read;
+        let url = self.bucket.get_object(Some(&self.credentials), &self.object_name(key)?).sign(Self::SIGNATURE_DURATION);
+        let response = ureq::get(url.as_str()).call().map_err(|e| self.request_error("ler", key, e))?;
+        let mut bytes = Vec::new();
+        response.into_reader()
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Erro S3 ao ler {} em {}: {}", key, self.describe(), e))?;
+        Ok(bytes)
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        use rusty_s3::actions::ListObjectsV2;
+        use rusty_s3::S3Action;
+        let suffix = format!(".{}", RESULTS_EXTENSION);
+        let mut keys = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let mut action = self.bucket.list_objects_v2(Some(&self.credentials));
+            action.with_prefix(self.prefix.clone());
+            if let Some(token) = &continuation {
+                action.with_continuation_token(token.clone());
+            }
+            let url = action.sign(Self::SIGNATURE_DURATION);
+            let body = ureq::get(url.as_str())
+                .call()
+                .map_err(|e| self.request_error("listar", "", e))?
+                .into_string()
+                .map_err(|e| format!("Erro S3 ao listar {}: {}", self.describe(), e))?;
+            let page = ListObjectsV2::parse_response(&body)
+                .map_err(|e| format!("Resposta S3 inválida ao listar {}: {}", self.describe(), e))?;
+            keys.extend(page.contents.iter().filter_map(|object| {
+                object.key.strip_prefix(&self.prefix)
+                    .and_then(|key| key.strip_suffix(&suffix))
+                    .map(|key| key.to_string())
+            }));
+            match page.next_continuation_token {
+                Some(token) => continuation = Some(token),
+                None => break,
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn delete(&self, key: &str) -> Result<bool, String> {
+        use rusty_s3::S3Action;
+        // DELETE do S3 é idempotente; a existência é verificada antes
+        let head = self.bucket.head_object(Some(&self.credentials), &self.object_name(key)?).sign(Self::SIGNATURE_DURATION);
+        match ureq::head(head.as_str()).call() {
+            Ok(_) => {}
+            Err(ureq::Error::Status(404, _)) => return Ok(false),
+            Err(e) => return Err(self.request_error("consultar", key, e)),
+        }
+        let url = self.bucket.delete_object(Some(&self.credentials), &self.object_name(key)?).sign(Self::SIGNATURE_DURATION);
+        ureq::delete(url.as_str()).call().map(|_| true).map_err(|e| self.request_error("remover", key, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PlasmaTorch;
+    use crate::simulation::solver::{HeatSolver, SimulationParameters};
+    use std::sync::{Arc, atomic::AtomicBool};
+
+    #[test]
+    fn test_local_storage_round_trip() {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 5;
+        params.time_step = 1.0;
+        params.total_time = 5.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+        let results = HeatSolver::new(params).unwrap().run(None, Arc::new(AtomicBool::new(false))).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("plasma_storage_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let config: StorageConfig = serde_json::from_str(&format!("{{\"Local\":{{\"root\":{:?}}}}}", dir.display().to_string())).unwrap();
+        let storage = open_storage(&config).unwrap();
+
+        storage.save_results("estudo-a/run_001", &results).unwrap();
+        storage.save_results("run_002", &results).unwrap();
+        assert_eq!(storage.list().unwrap(), vec!["estudo-a/run_001".to_string(), "run_002".to_string()]);
+
+        let (loaded, report) = storage.load_results("estudo-a/run_001").unwrap();
+        assert!(report.is_intact());
+        assert_eq!(loaded.temperature, results.temperature);
+
+        assert!(storage.save_results("../fora", &results).is_err());
+        assert!(storage.delete("run_002").unwrap());
+        assert!(!storage.delete("run_002").unwrap());
+        assert!(storage.load_results("run_002").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
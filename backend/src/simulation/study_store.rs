@@ -0,0 +1,381 @@
+// Implementação do armazenamento dos casos de estudos paramétricos em SQLite
+//
+// Cada caso concluído (valores dos parâmetros, métricas e tempo de execução) é gravado em
+// um banco SQLite embutido, de modo que estudos grandes podem ser filtrados, ordenados e
+// paginados sem manter todos os casos em memória. O caso completo é guardado em JSON na
+// tabela `cases`; os valores dos parâmetros e das métricas adicionais são repetidos na
+// tabela `case_values`, indexada por nome e valor, usada pelos filtros e pela ordenação.
+// O banco está disponível apenas com a feature `sqlite`; os tipos de consulta são sempre
+// compilados.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "sqlite")]
+use rusqlite::types::Value;
+#[cfg(feature = "sqlite")]
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+
+use crate::simulation::parametric::OptimizationGoal;
+#[cfg(feature = "sqlite")]
+use crate::simulation::parametric::{ParametricSimulationResult, ParametricStudyConfig, ParametricStudyResult};
+
+/// Enumeração que representa um campo dos casos usado em filtros e na ordenação
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseField {
+    /// Valor da métrica alvo
+    TargetMetric,
+    /// Tempo de execução (s)
+    ExecutionTime,
+    /// Identificador da simulação
+    SimulationId,
+    /// Valor de um parâmetro (variado, derivado ou índice de categoria)
+    Parameter(String),
+    /// Valor de uma métrica adicional
+    Metric(String),
+}
+
+/// Estrutura que representa um filtro por faixa de valores de um campo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseFilter {
+    /// Campo filtrado
+    pub field: CaseField,
+    /// Valor mínimo (inclusivo, opcional)
+    #[serde(default)]
+    pub min_value: Option<f64>,
+    /// Valor máximo (inclusivo, opcional)
+    #[serde(default)]
+    pub max_value: Option<f64>,
+}
+
+/// Estrutura que representa uma consulta aos casos de um estudo
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaseQuery {
+    /// Filtros, todos obrigatórios
+    #[serde(default)]
+    pub filters: Vec<CaseFilter>,
+    /// Campo de ordenação (padrão: identificador da simulação)
+    #[serde(default)]
+    pub sort_by: Option<CaseField>,
+    /// Ordem decrescente
+    #[serde(default)]
+    pub descending: bool,
+    /// Número máximo de casos retornados
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Número de casos ignorados no início (paginação)
+    #[serde(default)]
+    pub offset: usize,
+}
+
+/// Estrutura que representa um estudo gravado no banco
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredStudy {
+    /// Nome do estudo
+    pub name: String,
+    /// Métrica alvo
+    pub target_metric: String,
+    /// Objetivo da otimização
+    pub optimization_goal: OptimizationGoal,
+    /// Número de casos gravados
+    pub case_count: usize,
+}
+
+/// Esquema do banco
+#[cfg(feature = "sqlite")]
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS studies (
+        name TEXT PRIMARY KEY,
+        target_metric TEXT NOT NULL,
+        optimization_goal TEXT NOT NULL,
+        config TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS cases (
+        study TEXT NOT NULL,
+        simulation_id INTEGER NOT NULL,
+        target_metric_value REAL,
+        execution_time REAL,
+        result TEXT NOT NULL,
+        PRIMARY KEY (study, simulation_id)
+    );
+    CREATE TABLE IF NOT EXISTS case_values (
+        study TEXT NOT NULL,
+        simulation_id INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        name TEXT NOT NULL,
+        value REAL,
+        PRIMARY KEY (study, simulation_id, kind, name)
+    );
+    CREATE INDEX IF NOT EXISTS case_values_by_value ON case_values (study, kind, name, value);
+";
+
+/// Converte erros do SQLite em mensagens
+#[cfg(feature = "sqlite")]
+fn sql_error(context: &str) -> impl Fn(rusqlite::Error) -> String + '_ {
+    move |e| format!("Erro SQLite ao {}: {}", context, e)
+}
+
+/// Estrutura que representa o banco SQLite de casos de estudos paramétricos
+#[cfg(feature = "sqlite")]
+pub struct StudyStore {
+    connection: Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl StudyStore {
+    /// Abre (ou cria) o banco no arquivo indicado
+    pub fn open(path: &std::path::Path) -> Result<Self, String> {
+        let connection = Connection::open(path)
+            .map_err(|e| format!("Erro ao abrir o banco de estudos {}: {}", path.display(), e))?;
+        Self::initialize(connection)
+    }
+
+    /// Cria um banco em memória
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::initialize(Connection::open_in_memory().map_err(sql_error("criar o banco em memória"))?)
+    }
+
+    fn initialize(connection: Connection) -> Result<Self, String> {
+        connection.execute_batch(SCHEMA).map_err(sql_error("criar o esquema"))?;
+        Ok(Self { connection })
+    }
+
+    /// Registra (ou atualiza) a configuração de um estudo
+    pub fn register_study(&self, config: &ParametricStudyConfig) -> Result<(), String> {
+        let config_json = serde_json::to_string(config)
+            .map_err(|e| format!("Erro ao serializar configuração do estudo: {}", e))?;
+        let goal = serde_json::to_string(&config.optimization_goal)
+            .map_err(|e| format!("Erro ao serializar objetivo do estudo: {}", e))?;
+        self.connection.execute(
+            "INSERT OR REPLACE INTO studies (name, target_metric, optimization_goal, config) VALUES (?1, ?2, ?3, ?4)",
+            params![config.name, config.target_metric, goal, config_json],
+        ).map_err(sql_error("registrar o estudo"))?;
+        Ok(())
+    }
+
+    /// Grava um caso de um estudo, substituindo o caso de mesmo identificador
+    pub fn insert_case(&mut self, study: &str, result: &ParametricSimulationResult) -> Result<(), String> {
+        let result_json = serde_json::to_string(result)
+            .map_err(|e| format!("Erro ao serializar caso {}: {}", result.simulation_id, e))?;
+        let simulation_id = result.simulation_id as i64;
+
+        let transaction = self.connection.transaction().map_err(sql_error("iniciar transação"))?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO cases (study, simulation_id, target_metric_value, execution_time, result) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![study, simulation_id, result.target_metric_value, result.execution_time, result_json],
+        ).map_err(sql_error("gravar o caso"))?;
+        transaction.execute(
+            "DELETE FROM case_values WHERE study = ?1 AND simulation_id = ?2",
+            params![study, simulation_id],
+        ).map_err(sql_error("gravar o caso"))?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT INTO case_values (study, simulation_id, kind, name, value) VALUES (?1, ?2, ?3, ?4, ?5)",
+            ).map_err(sql_error("gravar o caso"))?;
+            let values = result.parameter_values.iter().map(|(name, value)| ("parameter", name, value))
+                .chain(result.additional_metrics.iter().map(|(name, value)| ("metric", name, value)));
+            for (kind, name, value) in values {
+                statement.execute(params![study, simulation_id, kind, name, value])
+                    .map_err(sql_error("gravar o caso"))?;
+            }
+        }
+        transaction.commit().map_err(sql_error("concluir transação"))
+    }
+
+    /// Grava a configuração e todos os casos de um resultado de estudo
+    pub fn insert_study_result(&mut self, result: &ParametricStudyResult) -> Result<(), String> {
+        self.register_study(&result.config)?;
+        for case in &result.simulation_results {
+            self.insert_case(&result.config.name, case)?;
+        }
+        Ok(())
+    }
+
+    /// Lista os estudos gravados, em ordem de nome
+    pub fn list_studies(&self) -> Result<Vec<StoredStudy>, String> {
+        let mut statement = self.connection.prepare(
+            "SELECT s.name, s.target_metric, s.optimization_goal,
+                    (SELECT COUNT(*) FROM cases c WHERE c.study = s.name)
+             FROM studies s ORDER BY s.name",
+        ).map_err(sql_error("listar os estudos"))?;
+        let rows = statement.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, i64>(3)?))
+        }).map_err(sql_error("listar os estudos"))?;
+
+        let mut studies = Vec::new();
+        for row in rows {
+            let (name, target_metric, goal, case_count) = row.map_err(sql_error("listar os estudos"))?;
+            studies.push(StoredStudy {
+                optimization_goal: serde_json::from_str(&goal)
+                    .map_err(|e| format!("Objetivo inválido no estudo {}: {}", name, e))?,
+                name,
+                target_metric,
+                case_count: case_count as usize,
+            });
+        }
+        Ok(studies)
+    }
+
+    /// Consulta os casos de um estudo
+    pub fn query_cases(&self, study: &str, query: &CaseQuery) -> Result<Vec<ParametricSimulationResult>, String> {
+        let mut joins = Vec::new();
+        let mut join_values = Vec::new();
+        let mut conditions = vec!["c.study = ?".to_string()];
+        let mut condition_values = vec![Value::Text(study.to_string())];
+
+        for filter in &query.filters {
+            if filter.min_value.is_none() && filter.max_value.is_none() {
+                return Err(format!("Filtro de {:?} sem valor mínimo ou máximo", filter.field));
+            }
+            let expression = field_expression(&filter.field, "JOIN", &mut joins, &mut join_values);
+            if let Some(min_value) = filter.min_value {
+                conditions.push(format!("{} >= ?", expression));
+                condition_values.push(Value::Real(min_value));
+            }
+            if let Some(max_value) = filter.max_value {
+                conditions.push(format!("{} <= ?", expression));
+                condition_values.push(Value::Real(max_value));
+            }
+        }
+
+        // Casos sem o campo de ordenação (ou com valor nulo) ficam no fim
+        let sort = query.sort_by.as_ref().unwrap_or(&CaseField::SimulationId);
+        let sort_expression = field_expression(sort, "LEFT JOIN", &mut joins, &mut join_values);
+        let direction = if query.descending { "DESC" } else { "ASC" };
+
+        let sql = format!(
+            "SELECT c.result FROM cases c {} WHERE {} ORDER BY {sort} IS NULL, {sort} {}, c.simulation_id LIMIT ? OFFSET ?",
+            joins.join(" "),
+            conditions.join(" AND "),
+            direction,
+            sort = sort_expression,
+        );
+        let limit = query.limit.map_or(-1, |limit| limit as i64);
+        let values: Vec<Value> = join_values.into_iter()
+            .chain(condition_values)
+            .chain([Value::Integer(limit), Value::Integer(query.offset as i64)])
+            .collect();
+
+        let mut statement = self.connection.prepare(&sql).map_err(sql_error("consultar os casos"))?;
+        let rows = statement.query_map(params_from_iter(values.iter()), |row| row.get::<_, String>(0))
+            .map_err(sql_error("consultar os casos"))?;
+        let mut cases = Vec::new();
+        for row in rows {
+            let json = row.map_err(sql_error("consultar os casos"))?;
+            cases.push(serde_json::from_str(&json).map_err(|e| format!("Caso inválido no estudo {}: {}", study, e))?);
+        }
+        Ok(cases)
+    }
+
+    /// Os `count` melhores casos de um estudo pela métrica alvo, segundo o objetivo do estudo
+    pub fn top_cases(&self, study: &str, count: usize) -> Result<Vec<ParametricSimulationResult>, String> {
+        let goal: Option<String> = self.connection.query_row(
+            "SELECT optimization_goal FROM studies WHERE name = ?1",
+            params![study],
+            |row| row.get(0),
+        ).optional().map_err(sql_error("consultar o estudo"))?;
+        let goal = goal.ok_or_else(|| format!("Estudo não encontrado no banco: {}", study))?;
+        let goal: OptimizationGoal = serde_json::from_str(&goal)
+            .map_err(|e| format!("Objetivo inválido no estudo {}: {}", study, e))?;
+
+        self.query_cases(study, &CaseQuery {
+            sort_by: Some(CaseField::TargetMetric),
+            descending: goal == OptimizationGoal::Maximize,
+            limit: Some(count),
+            ..CaseQuery::default()
+        })
+    }
+
+    /// Remove um estudo e seus casos; retorna false se o estudo não existe
+    pub fn delete_study(&mut self, study: &str) -> Result<bool, String> {
+        let transaction = self.connection.transaction().map_err(sql_error("iniciar transação"))?;
+        transaction.execute("DELETE FROM case_values WHERE study = ?1", params![study])
+            .map_err(sql_error("remover o estudo"))?;
+        let cases = transaction.execute("DELETE FROM cases WHERE study = ?1", params![study])
+            .map_err(sql_error("remover o estudo"))?;
+        let studies = transaction.execute("DELETE FROM studies WHERE name = ?1", params![study])
+            .map_err(sql_error("remover o estudo"))?;
+        transaction.commit().map_err(sql_error("concluir transação"))?;
+        Ok(cases + studies > 0)
+    }
+}
+
+/// Expressão SQL de um campo, com a junção a `case_values` para parâmetros e métricas
+#[cfg(feature = "sqlite")]
+fn field_expression(field: &CaseField, join: &str, joins: &mut Vec<String>, join_values: &mut Vec<Value>) -> String {
+    let (kind, name) = match field {
+        CaseField::TargetMetric => return "c.target_metric_value".to_string(),
+        CaseField::ExecutionTime => return "c.execution_time".to_string(),
+        CaseField::SimulationId => return "c.simulation_id".to_string(),
+        CaseField::Parameter(name) => ("parameter", name),
+        CaseField::Metric(name) => ("metric", name),
+    };
+    let alias = format!("v{}", joins.len());
+    joins.push(format!(
+        "{join} case_values {a} ON {a}.study = c.study AND {a}.simulation_id = c.simulation_id AND {a}.kind = ? AND {a}.name = ?",
+        join = join,
+        a = alias,
+    ));
+    join_values.push(Value::Text(kind.to_string()));
+    join_values.push(Value::Text(name.clone()));
+    format!("{}.value", alias)
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn case(id: usize, power: f64, metric: f64) -> ParametricSimulationResult {
+        ParametricSimulationResult {
+            parameter_values: HashMap::from([("torch_power".to_string(), power)]),
+            target_metric_value: metric,
+            additional_metrics: HashMap::from([("energy_efficiency".to_string(), metric / 1000.0)]),
+            execution_time: 0.5,
+            simulation_id: id,
+            categorical_values: HashMap::new(),
+            early_stopped: false,
+        }
+    }
+
+    #[test]
+    fn test_study_store_queries() {
+        let mut config = crate::simulation::parametric::ParametricStudyManager::create_max_temperature_study();
+        config.name = "estudo".to_string();
+        let mut store = StudyStore::open_in_memory().unwrap();
+        store.register_study(&config).unwrap();
+        for (id, power) in [50.0, 100.0, 150.0, 200.0].iter().enumerate() {
+            store.insert_case("estudo", &case(id, *power, 5.0 * power)).unwrap();
+        }
+        // Regravar um caso o substitui
+        store.insert_case("estudo", &case(3, 200.0, 990.0)).unwrap();
+
+        let studies = store.list_studies().unwrap();
+        assert_eq!(studies.len(), 1);
+        assert_eq!(studies[0].case_count, 4);
+
+        // Filtro por parâmetro e ordenação decrescente por métrica adicional
+        let cases = store.query_cases("estudo", &CaseQuery {
+            filters: vec![CaseFilter {
+                field: CaseField::Parameter("torch_power".to_string()),
+                min_value: Some(100.0),
+                max_value: None,
+            }],
+            sort_by: Some(CaseField::Metric("energy_efficiency".to_string())),
+            descending: true,
+            ..CaseQuery::default()
+        }).unwrap();
+        let ids: Vec<usize> = cases.iter().map(|c| c.simulation_id).collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+
+        // Paginação e melhores casos pelo objetivo do estudo (maximizar)
+        let page = store.query_cases("estudo", &CaseQuery { limit: Some(2), offset: 1, ..CaseQuery::default() }).unwrap();
+        assert_eq!(page.iter().map(|c| c.simulation_id).collect::<Vec<_>>(), vec![1, 2]);
+        let top = store.top_cases("estudo", 2).unwrap();
+        assert_eq!(top.iter().map(|c| c.simulation_id).collect::<Vec<_>>(), vec![3, 2]);
+        assert!(store.top_cases("outro", 2).is_err());
+
+        assert!(store.delete_study("estudo").unwrap());
+        assert!(store.query_cases("estudo", &CaseQuery::default()).unwrap().is_empty());
+    }
+}
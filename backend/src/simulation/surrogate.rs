@@ -0,0 +1,185 @@
+// Implementação do modelo substituto (processo gaussiano) usado na otimização bayesiana
+//
+// O processo gaussiano interpola as métricas das simulações já executadas em
+// coordenadas relativas x ∈ [0, 1]^d, com núcleo exponencial quadrático isotrópico e
+// saídas normalizadas (média zero, desvio unitário). O comprimento de correlação é
+// escolhido entre valores candidatos pela máxima verossimilhança marginal. A previsão
+// fornece média e desvio padrão, usados pela melhoria esperada (expected improvement)
+// para propor a próxima simulação.
+
+use serde::{Deserialize, Serialize};
+
+/// Comprimentos de correlação candidatos (coordenadas relativas)
+const LENGTH_SCALES: [f64; 6] = [0.05, 0.1, 0.2, 0.35, 0.6, 1.0];
+
+/// Estrutura que representa um processo gaussiano treinado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GaussianProcess {
+    /// Pontos de treinamento em coordenadas relativas
+    pub points: Vec<Vec<f64>>,
+    /// Média das saídas de treinamento
+    pub output_mean: f64,
+    /// Desvio padrão das saídas de treinamento
+    pub output_std: f64,
+    /// Comprimento de correlação do núcleo
+    pub length_scale: f64,
+    /// Variância do ruído (relativa à variância das saídas)
+    pub noise: f64,
+    /// Pesos K⁻¹·y das saídas normalizadas
+    weights: Vec<f64>,
+    /// Fator de Cholesky inferior de K
+    cholesky: Vec<Vec<f64>>,
+}
+
+/// Núcleo exponencial quadrático
+fn kernel(a: &[f64], b: &[f64], length_scale: f64) -> f64 {
+    let distance2: f64 = a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum();
+    (-0.5 * distance2 / (length_scale * length_scale)).exp()
+}
+
+/// Fator de Cholesky inferior de uma matriz simétrica positiva definida
+fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut lower = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| lower[i][k] * lower[j][k]).sum();
+            if i == j {
+                let diagonal = matrix[i][i] - sum;
+                if diagonal <= 0.0 {
+                    return None;
+                }
+                lower[i][j] = diagonal.sqrt();
+            } else {
+                lower[i][j] = (matrix[i][j] - sum) / lower[j][j];
+            }
+        }
+    }
+    Some(lower)
+}
+
+/// Resolve L·x = b por substituição progressiva
+fn solve_lower(lower: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let mut x = vec![0.0; b.len()];
+    for i in 0..b.len() {
+        let sum: f64 = (0..i).map(|k| lower[i][k] * x[k]).sum();
+        x[i] = (b[i] - sum) / lower[i][i];
+    }
+    x
+}
+
+/// Resolve Lᵀ·x = b por substituição regressiva
+fn solve_upper(lower: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|k| lower[k][i] * x[k]).sum();
+        x[i] = (b[i] - sum) / lower[i][i];
+    }
+    x
+}
+
+/// Função erro (Abramowitz e Stegun 7.1.26, erro absoluto < 1,5·10⁻⁷)
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let polynomial = t * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let value = 1.0 - polynomial * (-x * x).exp();
+    if x >= 0.0 { value } else { -value }
+}
+
+/// Melhoria esperada, na minimização, de uma previsão (média, desvio) sobre o melhor valor
+pub fn expected_improvement(mean: f64, std: f64, best: f64, xi: f64) -> f64 {
+    let improvement = best - mean - xi;
+    if std <= 0.0 {
+        return improvement.max(0.0);
+    }
+    let z = improvement / std;
+    let cdf = 0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2));
+    let pdf = (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt();
+    improvement * cdf + std * pdf
+}
+
+impl GaussianProcess {
+    /// Treina o processo com os pontos e saídas fornecidos
+    pub fn fit(points: Vec<Vec<f64>>, outputs: &[f64], noise: f64) -> Result<Self, String> {
+        if points.is_empty() || points.len() != outputs.len() {
+            return Err("Processo gaussiano requer pontos e saídas em mesmo número".to_string());
+        }
+        if noise < 0.0 || outputs.iter().any(|y| !y.is_finite()) {
+            return Err("Saídas finitas e ruído não negativo são necessários para o processo gaussiano".to_string());
+        }
+
+        let n = outputs.len() as f64;
+        let output_mean = outputs.iter().sum::<f64>() / n;
+        let output_std = (outputs.iter().map(|y| (y - output_mean).powi(2)).sum::<f64>() / n).sqrt();
+        let output_std = if output_std > 0.0 { output_std } else { 1.0 };
+        let normalized: Vec<f64> = outputs.iter().map(|y| (y - output_mean) / output_std).collect();
+        // Ruído mínimo para estabilidade numérica da fatoração
+        let noise = noise.max(1e-10);
+
+        let mut best: Option<(f64, Self)> = None;
+        for &length_scale in &LENGTH_SCALES {
+            let matrix: Vec<Vec<f64>> = points.iter().enumerate()
+                .map(|(i, a)| points.iter().enumerate()
+                    .map(|(j, b)| kernel(a, b, length_scale) + if i == j { noise } else { 0.0 })
+                    .collect())
+                .collect();
+            let Some(lower) = cholesky(&matrix) else { continue };
+            let weights = solve_upper(&lower, &solve_lower(&lower, &normalized));
+
+            // Log da verossimilhança marginal, sem a constante
+            let log_likelihood = -0.5 * normalized.iter().zip(&weights).map(|(y, w)| y * w).sum::<f64>()
+                - (0..lower.len()).map(|i| lower[i][i].ln()).sum::<f64>();
+            if best.as_ref().is_none_or(|(value, _)| log_likelihood > *value) {
+                best = Some((log_likelihood, Self {
+                    points: points.clone(),
+                    output_mean,
+                    output_std,
+                    length_scale,
+                    noise,
+                    weights,
+                    cholesky: lower,
+                }));
+            }
+        }
+        best.map(|(_, process)| process)
+            .ok_or_else(|| "Falha na fatoração da matriz de covariância do processo gaussiano".to_string())
+    }
+
+    /// Prevê a média e o desvio padrão da saída em um ponto
+    pub fn predict(&self, point: &[f64]) -> (f64, f64) {
+        let k: Vec<f64> = self.points.iter().map(|p| kernel(p, point, self.length_scale)).collect();
+        let mean: f64 = k.iter().zip(&self.weights).map(|(a, b)| a * b).sum();
+        let v = solve_lower(&self.cholesky, &k);
+        let variance = (1.0 - v.iter().map(|x| x * x).sum::<f64>()).max(0.0);
+        (self.output_mean + self.output_std * mean, self.output_std * variance.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gaussian_process_interpolates_and_expected_improvement() {
+        let points: Vec<Vec<f64>> = (0..9).map(|i| vec![i as f64 / 8.0]).collect();
+        let outputs: Vec<f64> = points.iter().map(|p| (6.0 * p[0]).sin() + 10.0).collect();
+        let process = GaussianProcess::fit(points.clone(), &outputs, 1e-8).unwrap();
+
+        // Interpola os pontos de treinamento, com incerteza quase nula neles
+        for (point, output) in points.iter().zip(&outputs) {
+            let (mean, std) = process.predict(point);
+            assert!((mean - output).abs() < 1e-3);
+            assert!(std < 1e-2);
+        }
+        // Entre os pontos, previsão próxima da função e incerteza positiva
+        let (mean, std) = process.predict(&[0.3125]);
+        assert!((mean - ((6.0_f64 * 0.3125).sin() + 10.0)).abs() < 0.05);
+        assert!(std > 0.0);
+
+        // Melhoria esperada: positiva com incerteza, nula sem incerteza e sem melhoria
+        assert!((erf(1.0) - 0.842_700_79).abs() < 1e-6);
+        assert!(expected_improvement(1.0, 0.5, 1.0, 0.0) > 0.19);
+        assert_eq!(expected_improvement(2.0, 0.0, 1.0, 0.0), 0.0);
+    }
+}
@@ -0,0 +1,394 @@
+// Implementação do gerador de experimentos sintéticos (sensores, ruído e verdade de referência)
+
+use log::info;
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, atomic::AtomicBool};
+
+use crate::simulation::random::SplitMix64;
+use crate::simulation::sensor_placement::{KeyParameter, SensorPlacementConfig, SensorPlacementOptimizer};
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Enumeração que representa o modelo de ruído de um sensor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NoiseModel {
+    /// Leituras exatas
+    None,
+    /// Ruído gaussiano aditivo com desvio padrão absoluto (°C)
+    Gaussian {
+        /// Desvio padrão (°C)
+        std_dev: f64,
+    },
+    /// Ruído gaussiano proporcional à leitura (fração, ex.: 0,0075 para termopar tipo K)
+    Proportional {
+        /// Desvio padrão relativo
+        fraction: f64,
+    },
+    /// Deriva linear do sensor somada a ruído gaussiano
+    Drift {
+        /// Taxa de deriva (°C/h)
+        rate: f64,
+        /// Desvio padrão do ruído (°C)
+        std_dev: f64,
+    },
+}
+
+/// Estrutura que representa um sensor do experimento
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticSensor {
+    /// Identificador do sensor
+    pub id: String,
+    /// Posição radial (m)
+    pub r: f64,
+    /// Posição axial (m)
+    pub z: f64,
+    /// Intervalo de amostragem (s)
+    pub sampling_interval: f64,
+    /// Modelo de ruído
+    pub noise: NoiseModel,
+    /// Resolução do conversor (°C); None sem quantização
+    #[serde(default)]
+    pub resolution: Option<f64>,
+    /// Probabilidade de leitura perdida (0-1)
+    #[serde(default)]
+    pub dropout_probability: f64,
+}
+
+impl SyntheticSensor {
+    /// Cria um novo sensor com ruído gaussiano de 1 °C
+    pub fn new(id: &str, r: f64, z: f64, sampling_interval: f64) -> Self {
+        Self {
+            id: id.to_string(),
+            r,
+            z,
+            sampling_interval,
+            noise: NoiseModel::Gaussian { std_dev: 1.0 },
+            resolution: None,
+            dropout_probability: 0.0,
+        }
+    }
+}
+
+/// Enumeração que representa a forma de escolher o arranjo de sensores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SensorLayout {
+    /// Sensores informados explicitamente
+    Explicit(Vec<SyntheticSensor>),
+    /// Grade regular com o modelo de sensor informado
+    Grid {
+        /// Número de posições radiais
+        radial: usize,
+        /// Número de posições axiais
+        axial: usize,
+        /// Modelo aplicado a todos os sensores (id e posição são substituídos)
+        template: SyntheticSensor,
+    },
+    /// Posições propostas pelo otimizador de posicionamento de termopares
+    Optimized {
+        /// Parâmetros-chave a identificar
+        key_parameters: Vec<KeyParameter>,
+        /// Número de sensores
+        num_probes: usize,
+        /// Modelo aplicado a todos os sensores (id e posição são substituídos)
+        template: SyntheticSensor,
+    },
+}
+
+/// Estrutura que representa a configuração do experimento sintético
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticExperimentConfig {
+    /// Nome do experimento
+    pub name: String,
+    /// Parâmetros da simulação "verdade"
+    pub truth_parameters: SimulationParameters,
+    /// Arranjo de sensores
+    pub layout: SensorLayout,
+    /// Semente do gerador pseudoaleatório
+    pub seed: u64,
+}
+
+/// Estrutura que representa o carimbo do gerador de um sensor (permite retomar a amostragem)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RngStamp {
+    /// Semente do experimento
+    pub seed: u64,
+    /// Fluxo do sensor (índice no arranjo)
+    pub stream: u64,
+    /// Sorteios consumidos ao fim da geração
+    pub draws: u64,
+}
+
+/// Estrutura que representa a série registrada por um sensor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorRecord {
+    /// Sensor
+    pub sensor: SyntheticSensor,
+    /// Instantes das leituras (s)
+    pub times: Vec<f64>,
+    /// Leituras com ruído (°C); None para leituras perdidas
+    pub readings: Vec<Option<f64>>,
+    /// Temperatura verdadeira no sensor (°C)
+    pub truth: Vec<f64>,
+    /// Carimbo do gerador
+    pub rng_stamp: RngStamp,
+}
+
+/// Estrutura que representa o experimento sintético gerado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticExperiment {
+    /// Configuração usada (inclui a semente)
+    pub config: SyntheticExperimentConfig,
+    /// Séries dos sensores
+    pub records: Vec<SensorRecord>,
+}
+
+/// Temperatura verdadeira em (r, z) no instante informado, interpolada no espaço e no tempo
+fn truth_at(results: &SimulationResults, fields: &[Array2<f64>], r: f64, z: f64, time: f64) -> f64 {
+    let position = (time / results.parameters.time_step).clamp(0.0, (fields.len() - 1) as f64);
+    let step = (position.floor() as usize).min(fields.len().saturating_sub(2));
+    let fraction = position - step as f64;
+    let next = (step + 1).min(fields.len() - 1);
+    let a = results.mesh.interpolate(&fields[step], r, z);
+    let b = results.mesh.interpolate(&fields[next], r, z);
+    a + fraction * (b - a)
+}
+
+/// Aplica o modelo de ruído, a quantização e a perda de leitura a um valor verdadeiro
+fn observe(sensor: &SyntheticSensor, truth: f64, time: f64, rng: &mut SplitMix64) -> Option<f64> {
+    // Sorteios em número fixo por leitura, para que o carimbo seja previsível
+    let dropout_draw = rng.next_f64();
+    let noise_draw = rng.standard_normal();
+
+    let mut value = match &sensor.noise {
+        NoiseModel::None => truth,
+        NoiseModel::Gaussian { std_dev } => truth + std_dev * noise_draw,
+        NoiseModel::Proportional { fraction } => truth * (1.0 + fraction * noise_draw),
+        NoiseModel::Drift { rate, std_dev } => truth + rate * time / 3600.0 + std_dev * noise_draw,
+    };
+    if let Some(resolution) = sensor.resolution.filter(|r| *r > 0.0) {
+        value = (value / resolution).round() * resolution;
+    }
+    if dropout_draw <= sensor.dropout_probability {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Estrutura que representa o gerador de experimentos sintéticos
+pub struct SyntheticExperimentGenerator {
+    /// Configuração do experimento
+    config: SyntheticExperimentConfig,
+}
+
+impl SyntheticExperimentGenerator {
+    /// Cria um novo gerador
+    pub fn new(config: SyntheticExperimentConfig) -> Result<Self, String> {
+        config.truth_parameters.validate()?;
+        Ok(Self { config })
+    }
+
+    /// Resolve o arranjo de sensores
+    pub fn resolve_sensors(&self, cancel_flag: Arc<AtomicBool>) -> Result<Vec<SyntheticSensor>, String> {
+        let params = &self.config.truth_parameters;
+        let place = |template: &SyntheticSensor, index: usize, r: f64, z: f64| SyntheticSensor {
+            id: format!("TC{:02}", index + 1),
+            r,
+            z,
+            ..template.clone()
+        };
+
+        let sensors = match &self.config.layout {
+            SensorLayout::Explicit(sensors) => sensors.clone(),
+            SensorLayout::Grid { radial, axial, template } => {
+                let (radial, axial) = ((*radial).max(1), (*axial).max(1));
+                let mut sensors = Vec::with_capacity(radial * axial);
+                for a in 0..axial {
+                    for b in 0..radial {
+                        let r = params.radius * (b as f64 + 0.5) / radial as f64;
+                        let z = params.height * (a as f64 + 0.5) / axial as f64;
+                        sensors.push(place(template, sensors.len(), r, z));
+                    }
+                }
+                sensors
+            }
+            SensorLayout::Optimized { key_parameters, num_probes, template } => {
+                let placement = SensorPlacementConfig::new(params.clone(), key_parameters.clone(), *num_probes);
+                let result = SensorPlacementOptimizer::new(placement)?.optimize(cancel_flag)?;
+                result.probes.iter().enumerate()
+                    .map(|(index, probe)| place(template, index, probe.r, probe.z))
+                    .collect()
+            }
+        };
+
+        for sensor in &sensors {
+            if sensor.sampling_interval <= 0.0 {
+                return Err(format!("Intervalo de amostragem do sensor {} deve ser positivo", sensor.id));
+            }
+            if sensor.r < 0.0 || sensor.r > params.radius || sensor.z < 0.0 || sensor.z > params.height {
+                return Err(format!("Sensor {} fora do domínio", sensor.id));
+            }
+        }
+        Ok(sensors)
+    }
+
+    /// Executa a simulação verdade
+    pub fn run_truth(&self, cancel_flag: Arc<AtomicBool>) -> Result<SimulationResults, String> {
+        HeatSolver::new(self.config.truth_parameters.clone())?.run(None, cancel_flag)
+    }
+
+    /// Amostra os sensores sobre uma simulação verdade já executada
+    ///
+    /// Cada sensor tem um fluxo pseudoaleatório próprio derivado da semente; a mesma
+    /// configuração reproduz exatamente as mesmas leituras sem reexecutar a simulação.
+    pub fn sample(&self, truth: &SimulationResults, sensors: &[SyntheticSensor]) -> Result<SyntheticExperiment, String> {
+        let fields = (0..=truth.executed_steps)
+            .map(|step| truth.temperature_at(step))
+            .collect::<Result<Vec<_>, String>>()?;
+        let end_time = truth.executed_steps as f64 * truth.parameters.time_step;
+
+        let records = sensors.iter().enumerate()
+            .map(|(index, sensor)| {
+                let mut rng = SplitMix64::stream(self.config.seed, index as u64);
+                let samples = (end_time / sensor.sampling_interval + 1e-9).floor() as usize + 1;
+                let mut times = Vec::with_capacity(samples);
+                let mut readings = Vec::with_capacity(samples);
+                let mut truth_values = Vec::with_capacity(samples);
+                for k in 0..samples {
+                    let time = k as f64 * sensor.sampling_interval;
+                    let value = truth_at(truth, &fields, sensor.r, sensor.z, time);
+                    times.push(time);
+                    truth_values.push(value);
+                    readings.push(observe(sensor, value, time, &mut rng));
+                }
+                SensorRecord {
+                    sensor: sensor.clone(),
+                    times,
+                    readings,
+                    truth: truth_values,
+                    rng_stamp: RngStamp { seed: self.config.seed, stream: index as u64, draws: rng.draws() },
+                }
+            })
+            .collect();
+
+        Ok(SyntheticExperiment {
+            config: self.config.clone(),
+            records,
+        })
+    }
+
+    /// Gera o experimento completo: arranjo de sensores, simulação verdade e leituras
+    pub fn generate(&self, cancel_flag: Arc<AtomicBool>) -> Result<(SyntheticExperiment, SimulationResults), String> {
+        let sensors = self.resolve_sensors(cancel_flag.clone())?;
+        let truth = self.run_truth(cancel_flag)?;
+        let experiment = self.sample(&truth, &sensors)?;
+        info!("Experimento sintético '{}' gerado com {} sensores (semente {})",
+              self.config.name, sensors.len(), self.config.seed);
+        Ok((experiment, truth))
+    }
+}
+
+impl SyntheticExperiment {
+    /// Grava o pacote do experimento em um diretório
+    ///
+    /// - `dataset.csv`: leituras com ruído (time,sensor_id,r,z,value), sem a verdade
+    /// - `ground_truth.csv`: temperaturas verdadeiras nos sensores
+    /// - `manifest.json`: configuração (semente, arranjo, parâmetros verdade) e carimbos
+    pub fn write_bundle(&self, directory: &Path) -> Result<(), String> {
+        fs::create_dir_all(directory)
+            .map_err(|e| format!("Erro ao criar diretório do experimento {:?}: {}", directory, e))?;
+
+        let write_csv = |file_name: &str, header: &str, rows: Vec<String>| -> Result<(), String> {
+            let path = directory.join(file_name);
+            let mut file = fs::File::create(&path).map_err(|e| format!("Erro ao criar {:?}: {}", path, e))?;
+            writeln!(file, "{}", header).map_err(|e| format!("Erro ao escrever {:?}: {}", path, e))?;
+            for row in rows {
+                writeln!(file, "{}", row).map_err(|e| format!("Erro ao escrever {:?}: {}", path, e))?;
+            }
+            Ok(())
+        };
+
+        let mut dataset = Vec::new();
+        let mut ground_truth = Vec::new();
+        for record in &self.records {
+            let sensor = &record.sensor;
+            for ((time, reading), truth) in record.times.iter().zip(record.readings.iter()).zip(record.truth.iter()) {
+                let value = reading.map_or(String::new(), |v| format!("{:.4}", v));
+                dataset.push(format!("{},{},{},{},{}", time, sensor.id, sensor.r, sensor.z, value));
+                ground_truth.push(format!("{},{},{:.6}", time, sensor.id, truth));
+            }
+        }
+        write_csv("dataset.csv", "time,sensor_id,r,z,value", dataset)?;
+        write_csv("ground_truth.csv", "time,sensor_id,temperature", ground_truth)?;
+
+        let stamps: Vec<(&str, &RngStamp)> = self.records.iter().map(|r| (r.sensor.id.as_str(), &r.rng_stamp)).collect();
+        let manifest = serde_json::json!({
+            "name": self.config.name,
+            "seed": self.config.seed,
+            "config": self.config,
+            "sensors": self.records.iter().map(|r| &r.sensor).collect::<Vec<_>>(),
+            "rng_stamps": stamps,
+        });
+        let manifest = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| format!("Erro ao serializar manifesto do experimento: {}", e))?;
+        fs::write(directory.join("manifest.json"), manifest)
+            .map_err(|e| format!("Erro ao gravar manifesto do experimento: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::physics::PlasmaTorch;
+
+    fn create_config(seed: u64) -> SyntheticExperimentConfig {
+        let mut params = SimulationParameters::new(1.0, 0.5, 5, 5);
+        params.time_steps = 6;
+        params.time_step = 1.0;
+        params.total_time = 6.0;
+        params.add_torch(PlasmaTorch::new("torch1", 0.0, 0.0, 0.5, 90.0, 0.0, 10.0, 0.01, 5000.0));
+
+        let mut template = SyntheticSensor::new("template", 0.0, 0.0, 2.0);
+        template.noise = NoiseModel::Gaussian { std_dev: 0.5 };
+        template.resolution = Some(0.1);
+        SyntheticExperimentConfig {
+            name: "teste".to_string(),
+            truth_parameters: params,
+            layout: SensorLayout::Grid { radial: 2, axial: 2, template },
+            seed,
+        }
+    }
+
+    #[test]
+    fn test_synthetic_experiment_is_reproducible() {
+        let generator = SyntheticExperimentGenerator::new(create_config(11)).unwrap();
+        let (experiment, truth) = generator.generate(Arc::new(AtomicBool::new(false))).unwrap();
+
+        assert_eq!(experiment.records.len(), 4);
+        let record = &experiment.records[0];
+        assert_eq!(record.times, vec![0.0, 2.0, 4.0, 6.0]);
+        assert!((record.truth[0] - 25.0).abs() < 1e-9);
+        // Quantização de 0,1 °C
+        let value = record.readings[1].unwrap();
+        assert!(((value * 10.0).round() - value * 10.0).abs() < 1e-6);
+
+        // Mesma semente, mesmas leituras, sem reexecutar a verdade
+        let sensors: Vec<SyntheticSensor> = experiment.records.iter().map(|r| r.sensor.clone()).collect();
+        let again = generator.sample(&truth, &sensors).unwrap();
+        assert_eq!(again.records[2].readings, experiment.records[2].readings);
+        assert_eq!(again.records[2].rng_stamp.draws, 3 * 4);
+
+        let other = SyntheticExperimentGenerator::new(create_config(12)).unwrap().sample(&truth, &sensors).unwrap();
+        assert_ne!(other.records[0].readings, experiment.records[0].readings);
+
+        let directory = std::env::temp_dir().join(format!("synthetic_experiment_{}", std::process::id()));
+        experiment.write_bundle(&directory).unwrap();
+        let dataset = fs::read_to_string(directory.join("dataset.csv")).unwrap();
+        assert_eq!(dataset.lines().count(), 1 + 4 * 4);
+        assert!(directory.join("manifest.json").exists());
+        let _ = fs::remove_dir_all(directory);
+    }
+}
@@ -0,0 +1,222 @@
+// Implementação dos modelos didáticos do modo de ensino (casos curtos com objetivos de aprendizagem)
+//
+// Cada modelo é pequeno o bastante para executar em poucos segundos e pode ter
+// variantes (ex.: convecção × radiação) que isolam um fenômeno para comparação.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, atomic::AtomicBool};
+
+use crate::simulation::boundary::BoundaryCondition;
+use crate::simulation::materials::MaterialLibrary;
+use crate::simulation::physics::{PlasmaTorch, STEFAN_BOLTZMANN};
+use crate::simulation::solver::{HeatSolver, SimulationParameters, SimulationResults};
+
+/// Estrutura que representa uma variante executável de um modelo didático
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeachingVariant {
+    /// Rótulo da variante (ex.: "Somente radiação")
+    pub label: String,
+    /// O que observar nesta variante
+    pub description: String,
+    /// Parâmetros da simulação
+    pub parameters: SimulationParameters,
+}
+
+/// Estrutura que representa um modelo didático do modo de ensino
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeachingTemplate {
+    /// Identificador do modelo
+    pub id: String,
+    /// Título
+    pub title: String,
+    /// Descrição do experimento
+    pub description: String,
+    /// Objetivos de aprendizagem
+    pub learning_goals: Vec<String>,
+    /// Tempo de execução esperado de todas as variantes em um computador típico (s)
+    pub expected_runtime_seconds: f64,
+    /// Variantes executáveis (uma para experimentos simples)
+    pub variants: Vec<TeachingVariant>,
+}
+
+/// Estrutura que representa o resultado de uma variante executada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeachingRun {
+    /// Rótulo da variante
+    pub label: String,
+    /// Resultados da simulação
+    pub results: SimulationResults,
+}
+
+/// Cria os parâmetros base dos modelos didáticos (malha grossa e poucos passos)
+fn create_base_parameters(material_id: &str, total_time: f64, time_steps: usize) -> SimulationParameters {
+    let mut params = SimulationParameters::new(1.0, 0.5, 10, 20);
+    if let Some(material) = MaterialLibrary::new().get_material_clone(material_id) {
+        params.set_material(material);
+    }
+    params.total_time = total_time;
+    params.time_steps = time_steps;
+    params.time_step = total_time / time_steps as f64;
+    params.temporal_pyramid_strides = Vec::new();
+    params
+}
+
+/// Tocha única no eixo, apontada para a base
+fn create_axis_torch(power: f64) -> PlasmaTorch {
+    PlasmaTorch::new("torch1", 0.0, 0.0, 0.9, 180.0, 0.0, power, 0.01, 5000.0)
+}
+
+/// Cria o modelo de condução pura com uma tocha
+pub fn create_single_torch_conduction_template() -> TeachingTemplate {
+    let mut params = create_base_parameters("steel", 600.0, 60);
+    params.enable_convection = false;
+    params.enable_radiation = false;
+    params.enable_phase_changes = false;
+    params.add_torch(create_axis_torch(50.0));
+
+    TeachingTemplate {
+        id: "single_torch_conduction".to_string(),
+        title: "Uma Tocha, Somente Condução".to_string(),
+        description: "Uma tocha aquece um cilindro de aço isolado; sem perdas, toda a energia se espalha por condução".to_string(),
+        learning_goals: vec![
+            "Observar a frente térmica avançando a partir da tocha".to_string(),
+            "Relacionar a velocidade da frente com a difusividade k/(ρ·cp)".to_string(),
+            "Verificar que, sem perdas, a temperatura média cresce linearmente com a energia fornecida".to_string(),
+        ],
+        expected_runtime_seconds: 1.0,
+        variants: vec![TeachingVariant {
+            label: "Condução".to_string(),
+            description: "Campo de temperatura sem convecção, radiação ou mudança de fase".to_string(),
+            parameters: params,
+        }],
+    }
+}
+
+/// Cria o modelo de demonstração de mudança de fase (fusão do alumínio)
+pub fn create_phase_change_demo_template() -> TeachingTemplate {
+    // Passo de 2 s: a alta difusividade do alumínio limita o passo do esquema explícito
+    let mut params = create_base_parameters("aluminum", 600.0, 300);
+    params.initial_temperature = 600.0;
+    params.enable_convection = false;
+    params.enable_radiation = false;
+    params.add_torch(create_axis_torch(150.0));
+
+    TeachingTemplate {
+        id: "phase_change_demo".to_string(),
+        title: "Demonstração de Mudança de Fase".to_string(),
+        description: "Um bloco de alumínio pré-aquecido a 600 °C funde junto à tocha; o calor latente segura a temperatura no ponto de fusão".to_string(),
+        learning_goals: vec![
+            "Identificar o patamar de temperatura em 660 °C durante a fusão".to_string(),
+            "Acompanhar a fração fundida avançando a partir da tocha".to_string(),
+            "Comparar o calor sensível e o calor latente necessários para fundir o material".to_string(),
+        ],
+        expected_runtime_seconds: 1.5,
+        variants: vec![TeachingVariant {
+            label: "Fusão".to_string(),
+            description: "Campos de temperatura e de fração fundida".to_string(),
+            parameters: params,
+        }],
+    }
+}
+
+/// Cria o modelo de comparação entre perdas por convecção e por radiação
+pub fn create_convection_vs_radiation_template() -> TeachingTemplate {
+    let variant = |label: &str, description: &str, convection: bool, radiation: bool| {
+        let mut params = create_base_parameters("concrete", 1800.0, 60);
+        params.initial_temperature = 1000.0;
+        params.ambient_temperature = 25.0;
+        params.convection_coefficient = 10.0;
+        // Tocha desligada: as perdas ocorrem pela parede e pelo topo, não pela troca com o gás
+        params.enable_convection = false;
+        params.enable_radiation = false;
+        params.enable_phase_changes = false;
+        params.add_torch(create_axis_torch(0.0));
+
+        // Radiação para o ambiente linearizada na temperatura inicial:
+        // h_rad = ε·σ·(T² + T∞²)·(T + T∞), com temperaturas em K
+        let (surface, ambient) = (params.initial_temperature + 273.15, params.ambient_temperature + 273.15);
+        let radiation_coefficient = params.material.get_emissivity(params.initial_temperature) * STEFAN_BOLTZMANN
+            * (surface.powi(2) + ambient.powi(2)) * (surface + ambient);
+        let coefficient = if convection { params.convection_coefficient } else { 0.0 }
+            + if radiation { radiation_coefficient } else { 0.0 };
+        let loss = BoundaryCondition::Robin { coefficient, reference_temperature: params.ambient_temperature };
+        params.boundary_conditions.wall = loss.clone();
+        params.boundary_conditions.top = loss;
+        TeachingVariant {
+            label: label.to_string(),
+            description: description.to_string(),
+            parameters: params,
+        }
+    };
+
+    TeachingTemplate {
+        id: "convection_vs_radiation".to_string(),
+        title: "Convecção × Radiação".to_string(),
+        description: "Um cilindro de concreto a 1000 °C esfria com a tocha desligada, com cada mecanismo de perda isolado".to_string(),
+        learning_goals: vec![
+            "Comparar o resfriamento da superfície com cada mecanismo de perda".to_string(),
+            "Verificar que a radiação (∝ T⁴) domina em altas temperaturas".to_string(),
+            "Observar que a convecção (∝ ΔT) perde importância mais devagar à medida que a peça esfria".to_string(),
+        ],
+        expected_runtime_seconds: 3.0,
+        variants: vec![
+            variant("Somente convecção", "Perdas pela superfície com h = 10 W/(m²·K)", true, false),
+            variant("Somente radiação", "Perdas por radiação com a emissividade do concreto (coeficiente linearizado a 1000 °C)", false, true),
+            variant("Convecção e radiação", "Os dois mecanismos combinados", true, true),
+        ],
+    }
+}
+
+/// Retorna todos os modelos didáticos
+pub fn get_teaching_templates() -> Vec<TeachingTemplate> {
+    vec![
+        create_single_torch_conduction_template(),
+        create_phase_change_demo_template(),
+        create_convection_vs_radiation_template(),
+    ]
+}
+
+/// Retorna um modelo didático pelo identificador
+pub fn get_teaching_template(id: &str) -> Option<TeachingTemplate> {
+    get_teaching_templates().into_iter().find(|t| t.id == id)
+}
+
+/// Executa todas as variantes de um modelo didático, em ordem
+pub fn run_teaching_template(id: &str, cancel_flag: Arc<AtomicBool>) -> Result<Vec<TeachingRun>, String> {
+    let template = get_teaching_template(id).ok_or_else(|| format!("Modelo didático não encontrado: {}", id))?;
+    template.variants.into_iter()
+        .map(|variant| {
+            let results = HeatSolver::new(variant.parameters)?.run(None, cancel_flag.clone())?;
+            Ok(TeachingRun { label: variant.label, results })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_teaching_templates_run() {
+        let templates = get_teaching_templates();
+        assert_eq!(templates.len(), 3);
+        for template in &templates {
+            assert!(!template.learning_goals.is_empty() && template.expected_runtime_seconds > 0.0);
+            for variant in &template.variants {
+                assert!(variant.parameters.validate().is_ok(), "Variante inválida: {} / {}", template.id, variant.label);
+            }
+        }
+
+        // Radiação a 1000 °C resfria a superfície mais que a convecção
+        let runs = run_teaching_template("convection_vs_radiation", Arc::new(AtomicBool::new(false))).unwrap();
+        assert_eq!(runs.len(), 3);
+        let surface = |run: &TeachingRun| {
+            let last = run.results.temperature.shape()[2] - 1;
+            run.results.temperature[[run.results.parameters.nr - 1, run.results.parameters.nz / 2, last]]
+        };
+        assert!(surface(&runs[1]) < surface(&runs[0]));
+        assert!(surface(&runs[2]) <= surface(&runs[1]));
+
+        assert!(run_teaching_template("unknown", Arc::new(AtomicBool::new(false))).is_err());
+    }
+}
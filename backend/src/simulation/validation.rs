@@ -1,15 +1,17 @@
 // Implementação do módulo de validação de modelos para o simulador de fornalha de plasma
 
-use ndarray::{Array1, Array2, Array3, ArrayView3};
+use ndarray::Array1;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Write};
+use std::io::Write;
 use std::path::Path;
 
 use crate::simulation::state::SimulationState;
 use crate::simulation::mesh::CylindricalMesh;
-use crate::simulation::metrics::{SimulationMetrics, MetricsAnalyzer};
+use crate::simulation::metrics::TemperatureHistory;
+use crate::simulation::random::SplitMix64;
+use crate::simulation::solver::{SimulationParameters, SimulationResults};
 
 /// Estrutura que representa os dados de referência para validação
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +30,9 @@ pub struct ReferenceData {
     pub values: Vec<f64>,
     /// Incerteza nos valores (opcional)
     pub uncertainties: Option<Vec<f64>>,
+    /// Instantes das medições (s), se importados (opcional)
+    #[serde(default)]
+    pub times: Option<Vec<f64>>,
     /// Metadados adicionais
     pub metadata: HashMap<String, String>,
 }
@@ -83,6 +88,189 @@ pub enum ImportFormat {
     Custom,
 }
 
+impl ImportFormat {
+    /// Obtém o formato pelo nome ("CSV", "JSON" ou "Custom", sem distinção de maiúsculas)
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(ImportFormat::CSV),
+            "json" => Ok(ImportFormat::JSON),
+            "custom" => Ok(ImportFormat::Custom),
+            other => Err(format!("Formato de importação desconhecido: {}", other)),
+        }
+    }
+}
+
+/// Diferença entre as escalas Kelvin e Celsius
+const KELVIN_OFFSET: f64 = 273.15;
+
+/// Enumeração que representa uma coluna do CSV, pelo índice (a partir de 0) ou pelo nome no cabeçalho
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CsvColumn {
+    /// Índice da coluna
+    Index(usize),
+    /// Nome da coluna no cabeçalho (sem distinção de maiúsculas)
+    Name(String),
+}
+
+impl CsvColumn {
+    /// Resolve o índice da coluna a partir do cabeçalho (se houver)
+    fn resolve(&self, header: Option<&[String]>) -> Result<usize, String> {
+        match self {
+            CsvColumn::Index(index) => Ok(*index),
+            CsvColumn::Name(name) => {
+                let header = header.ok_or_else(|| format!("Coluna \"{}\" referenciada por nome, mas o CSV não tem cabeçalho", name))?;
+                header.iter()
+                    .position(|field| field.eq_ignore_ascii_case(name.trim()))
+                    .ok_or_else(|| format!("Coluna \"{}\" não encontrada no cabeçalho", name))
+            }
+        }
+    }
+}
+
+/// Estrutura que representa o mapeamento das colunas do CSV para os dados de referência
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    /// Coordenada radial
+    pub r: CsvColumn,
+    /// Coordenada axial
+    pub z: CsvColumn,
+    /// Instante da medição (opcional)
+    pub time: Option<CsvColumn>,
+    /// Valor medido
+    pub value: CsvColumn,
+    /// Incerteza do valor (opcional)
+    pub uncertainty: Option<CsvColumn>,
+}
+
+impl Default for CsvColumnMapping {
+    fn default() -> Self {
+        Self {
+            r: CsvColumn::Name("r".to_string()),
+            z: CsvColumn::Name("z".to_string()),
+            time: None,
+            value: CsvColumn::Name("value".to_string()),
+            uncertainty: None,
+        }
+    }
+}
+
+/// Enumeração que representa as unidades de comprimento aceitas no CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LengthUnit {
+    /// Metro
+    #[default]
+    Meter,
+    /// Centímetro
+    Centimeter,
+    /// Milímetro
+    Millimeter,
+}
+
+impl LengthUnit {
+    /// Converte um comprimento para metros
+    pub fn to_meters(self, value: f64) -> f64 {
+        match self {
+            LengthUnit::Meter => value,
+            LengthUnit::Centimeter => value * 1e-2,
+            LengthUnit::Millimeter => value * 1e-3,
+        }
+    }
+}
+
+/// Enumeração que representa as unidades de tempo aceitas no CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimeUnit {
+    /// Segundo
+    #[default]
+    Second,
+    /// Minuto
+    Minute,
+    /// Hora
+    Hour,
+}
+
+impl TimeUnit {
+    /// Converte um instante para segundos
+    pub fn to_seconds(self, value: f64) -> f64 {
+        match self {
+            TimeUnit::Second => value,
+            TimeUnit::Minute => value * 60.0,
+            TimeUnit::Hour => value * 3600.0,
+        }
+    }
+}
+
+/// Enumeração que representa as unidades de temperatura aceitas no CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TemperatureUnit {
+    /// Grau Celsius
+    #[default]
+    Celsius,
+    /// Kelvin
+    Kelvin,
+    /// Grau Fahrenheit
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Converte uma temperatura para °C
+    pub fn to_celsius(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Kelvin => value - KELVIN_OFFSET,
+            TemperatureUnit::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+        }
+    }
+
+    /// Converte uma diferença de temperatura (incerteza) para °C, sem o deslocamento da escala
+    pub fn difference_to_celsius(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius | TemperatureUnit::Kelvin => value,
+            TemperatureUnit::Fahrenheit => value * 5.0 / 9.0,
+        }
+    }
+}
+
+/// Estrutura que representa as unidades das colunas do CSV (convertidas para m, s e °C)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct CsvUnits {
+    /// Unidade das coordenadas r e z
+    pub length: LengthUnit,
+    /// Unidade dos instantes
+    pub time: TimeUnit,
+    /// Unidade dos valores e das incertezas
+    pub temperature: TemperatureUnit,
+}
+
+/// Estrutura que representa as opções de importação de CSV
+///
+/// Linhas vazias e linhas iniciadas por `#` (comentários dos registradores de
+/// termopares) são ignoradas; campos entre aspas têm as aspas removidas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvImportOptions {
+    /// Delimitador dos campos
+    pub delimiter: char,
+    /// Indica se a primeira linha de dados é o cabeçalho
+    pub has_header: bool,
+    /// Mapeamento das colunas
+    pub columns: CsvColumnMapping,
+    /// Unidades das colunas
+    pub units: CsvUnits,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+            columns: CsvColumnMapping::default(),
+            units: CsvUnits::default(),
+        }
+    }
+}
+
 /// Estrutura que representa as opções de importação
 #[derive(Debug, Clone)]
 pub struct ImportOptions {
@@ -90,22 +278,118 @@ pub struct ImportOptions {
     pub format: ImportFormat,
     /// Caminho do arquivo de entrada
     pub input_path: String,
-    /// Delimitador (para CSV)
-    pub delimiter: Option<char>,
-    /// Tem cabeçalho (para CSV)
-    pub has_header: bool,
-    /// Colunas para coordenadas (para CSV)
-    pub coordinate_columns: Option<(usize, usize, usize)>,
-    /// Coluna para valores (para CSV)
-    pub value_column: Option<usize>,
-    /// Coluna para incertezas (para CSV)
-    pub uncertainty_column: Option<usize>,
+    /// Opções de CSV (ignoradas nos demais formatos)
+    pub csv: CsvImportOptions,
+}
+
+/// Importa dados de referência a partir de um arquivo
+pub fn import_data(options: &ImportOptions) -> Result<ReferenceData, String> {
+    let path = Path::new(&options.input_path);
+    match options.format {
+        ImportFormat::CSV => {
+            let content = std::fs::read_to_string(path).map_err(|e| format!("Erro ao abrir arquivo CSV: {}", e))?;
+            let file_name = path.file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown");
+            let mut reference_data = parse_reference_csv(&content, &options.csv)?;
+            reference_data.name = file_name.to_string();
+            reference_data.description = format!("Dados importados de {}", options.input_path);
+            Ok(reference_data)
+        }
+        ImportFormat::JSON => {
+            let file = File::open(path).map_err(|e| format!("Erro ao abrir arquivo JSON: {}", e))?;
+            serde_json::from_reader(file).map_err(|e| format!("Erro ao ler arquivo JSON: {}", e))
+        }
+        // Implementação simplificada - em um ambiente real, isso seria adaptado para o formato específico
+        ImportFormat::Custom => Err("Importação de formato personalizado não implementada".to_string()),
+    }
+}
+
+/// Converte o conteúdo de um CSV em dados de referência (coordenadas em m, instantes em s,
+/// valores e incertezas em °C)
+///
+/// A coordenada angular é zero: o modelo é axissimétrico.
+pub fn parse_reference_csv(content: &str, options: &CsvImportOptions) -> Result<ReferenceData, String> {
+    let split = |line: &str| -> Vec<String> {
+        line.split(options.delimiter)
+            .map(|field| field.trim().trim_matches('"').trim().to_string())
+            .collect()
+    };
+    let mut lines = content.trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let header = if options.has_header {
+        let (_, line) = lines.next().ok_or_else(|| "Arquivo CSV sem cabeçalho".to_string())?;
+        Some(split(line))
+    } else {
+        None
+    };
+    let columns = &options.columns;
+    let resolve_optional = |column: &Option<CsvColumn>| column.as_ref().map(|c| c.resolve(header.as_deref())).transpose();
+    let r_column = columns.r.resolve(header.as_deref())?;
+    let z_column = columns.z.resolve(header.as_deref())?;
+    let value_column = columns.value.resolve(header.as_deref())?;
+    let time_column = resolve_optional(&columns.time)?;
+    let uncertainty_column = resolve_optional(&columns.uncertainty)?;
+
+    let units = options.units;
+    let mut coordinates = Vec::new();
+    let mut values = Vec::new();
+    let mut times = time_column.map(|_| Vec::new());
+    let mut uncertainties = uncertainty_column.map(|_| Vec::new());
+
+    for (line_number, line) in lines {
+        let fields = split(line);
+        let field = |column: usize, label: &str| -> Result<f64, String> {
+            let text = fields.get(column)
+                .ok_or_else(|| format!("Linha {} não tem a coluna {} ({})", line_number, column, label))?;
+            text.parse::<f64>()
+                .map_err(|e| format!("Erro ao converter {} na linha {}: \"{}\" ({})", label, line_number, text, e))
+        };
+
+        let r = units.length.to_meters(field(r_column, "r")?);
+        let z = units.length.to_meters(field(z_column, "z")?);
+        coordinates.push((r, 0.0, z));
+        values.push(units.temperature.to_celsius(field(value_column, "valor")?));
+        if let (Some(column), Some(times)) = (time_column, times.as_mut()) {
+            times.push(units.time.to_seconds(field(column, "tempo")?));
+        }
+        if let (Some(column), Some(uncertainties)) = (uncertainty_column, uncertainties.as_mut()) {
+            let uncertainty = units.temperature.difference_to_celsius(field(column, "incerteza")?);
+            if uncertainty < 0.0 {
+                return Err(format!("Incerteza negativa na linha {}", line_number));
+            }
+            uncertainties.push(uncertainty);
+        }
+    }
+
+    if values.is_empty() {
+        return Err("Arquivo CSV sem pontos de dados".to_string());
+    }
+
+    Ok(ReferenceData {
+        name: "CSV Import".to_string(),
+        description: "Dados importados de CSV".to_string(),
+        source: "CSV Import".to_string(),
+        data_type: "Temperature".to_string(), // Assumindo temperatura por padrão
+        coordinates,
+        values,
+        uncertainties,
+        times,
+        metadata: HashMap::new(),
+    })
 }
 
+/// Semente dos dados de referência sintéticos
+const SYNTHETIC_DATA_SEED: u64 = 0x5EED_DA7A;
+
 /// Estrutura que representa o validador de modelos
 pub struct ModelValidator {
-    /// Estado da simulação
-    simulation_state: SimulationState,
+    /// Histórico de temperatura extraído dos resultados da simulação
+    history: TemperatureHistory,
     /// Dados de referência
     reference_data: Option<ReferenceData>,
     /// Resultado da validação
@@ -116,7 +400,7 @@ impl ModelValidator {
     /// Cria um novo validador de modelos
     pub fn new(simulation_state: SimulationState) -> Self {
         Self {
-            simulation_state,
+            history: TemperatureHistory::from_state(&simulation_state),
             reference_data: None,
             validation_result: None,
         }
@@ -124,125 +408,8 @@ impl ModelValidator {
     
     /// Importa dados de referência a partir de um arquivo
     pub fn import_reference_data(&mut self, options: &ImportOptions) -> Result<&ReferenceData, String> {
-        match options.format {
-            ImportFormat::CSV => self.import_from_csv(options),
-            ImportFormat::JSON => self.import_from_json(options),
-            ImportFormat::Custom => self.import_from_custom(options),
-        }
-    }
-    
-    /// Importa dados de referência a partir de um arquivo CSV
-    fn import_from_csv(&mut self, options: &ImportOptions) -> Result<&ReferenceData, String> {
-        let path = Path::new(&options.input_path);
-        let file = File::open(path).map_err(|e| format!("Erro ao abrir arquivo CSV: {}", e))?;
-        let reader = BufReader::new(file);
-        
-        let delimiter = options.delimiter.unwrap_or(',');
-        let has_header = options.has_header;
-        
-        let coordinate_columns = options.coordinate_columns.ok_or_else(|| 
-            "Colunas de coordenadas não especificadas".to_string()
-        )?;
-        
-        let value_column = options.value_column.ok_or_else(|| 
-            "Coluna de valores não especificada".to_string()
-        )?;
-        
-        let mut coordinates = Vec::new();
-        let mut values = Vec::new();
-        let mut uncertainties = if options.uncertainty_column.is_some() {
-            Some(Vec::new())
-        } else {
-            None
-        };
-        
-        for (i, line_result) in reader.lines().enumerate() {
-            // Pular cabeçalho se necessário
-            if i == 0 && has_header {
-                continue;
-            }
-            
-            let line = line_result.map_err(|e| format!("Erro ao ler linha {}: {}", i + 1, e))?;
-            let fields: Vec<&str> = line.split(delimiter).collect();
-            
-            if fields.len() <= value_column || fields.len() <= coordinate_columns.0 || 
-               fields.len() <= coordinate_columns.1 || fields.len() <= coordinate_columns.2 {
-                return Err(format!("Linha {} não tem campos suficientes", i + 1));
-            }
-            
-            // Extrair coordenadas
-            let r = fields[coordinate_columns.0].trim().parse::<f64>()
-                .map_err(|e| format!("Erro ao converter coordenada r na linha {}: {}", i + 1, e))?;
-            
-            let theta = fields[coordinate_columns.1].trim().parse::<f64>()
-                .map_err(|e| format!("Erro ao converter coordenada theta na linha {}: {}", i + 1, e))?;
-            
-            let z = fields[coordinate_columns.2].trim().parse::<f64>()
-                .map_err(|e| format!("Erro ao converter coordenada z na linha {}: {}", i + 1, e))?;
-            
-            coordinates.push((r, theta, z));
-            
-            // Extrair valor
-            let value = fields[value_column].trim().parse::<f64>()
-                .map_err(|e| format!("Erro ao converter valor na linha {}: {}", i + 1, e))?;
-            
-            values.push(value);
-            
-            // Extrair incerteza se disponível
-            if let Some(uncertainty_column) = options.uncertainty_column {
-                if fields.len() <= uncertainty_column {
-                    return Err(format!("Linha {} não tem campo de incerteza", i + 1));
-                }
-                
-                let uncertainty = fields[uncertainty_column].trim().parse::<f64>()
-                    .map_err(|e| format!("Erro ao converter incerteza na linha {}: {}", i + 1, e))?;
-                
-                if let Some(ref mut uncertainties_vec) = uncertainties {
-                    uncertainties_vec.push(uncertainty);
-                }
-            }
-        }
-        
-        // Extrair nome do arquivo para usar como nome do conjunto de dados
-        let file_name = path.file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-        
-        // Criar conjunto de dados de referência
-        let reference_data = ReferenceData {
-            name: file_name,
-            description: format!("Dados importados de {}", options.input_path),
-            source: "CSV Import".to_string(),
-            data_type: "Temperature".to_string(), // Assumindo temperatura por padrão
-            coordinates,
-            values,
-            uncertainties,
-            metadata: HashMap::new(),
-        };
-        
-        self.reference_data = Some(reference_data);
-        
-        Ok(self.reference_data.as_ref().unwrap())
-    }
-    
-    /// Importa dados de referência a partir de um arquivo JSON
-    fn import_from_json(&mut self, options: &ImportOptions) -> Result<&ReferenceData, String> {
-        let path = Path::new(&options.input_path);
-        let file = File::open(path).map_err(|e| format!("Erro ao abrir arquivo JSON: {}", e))?;
-        
-        let reference_data: ReferenceData = serde_json::from_reader(file)
-            .map_err(|e| format!("Erro ao ler arquivo JSON: {}", e))?;
-        
-        self.reference_data = Some(reference_data);
-        
-        Ok(self.reference_data.as_ref().unwrap())
-    }
-    
-    /// Importa dados de referência a partir de um formato personalizado
-    fn import_from_custom(&mut self, options: &ImportOptions) -> Result<&ReferenceData, String> {
-        // Implementação simplificada - em um ambiente real, isso seria adaptado para o formato específico
-        Err("Importação de formato personalizado não implementada".to_string())
+        let reference_data = import_data(options)?;
+        Ok(self.set_reference_data(reference_data))
     }
     
     /// Define dados de referência diretamente
@@ -259,13 +426,13 @@ impl ModelValidator {
         
         let reference_data = self.reference_data.as_ref().unwrap();
         
-        if self.simulation_state.temperature_history.is_empty() {
+        if self.history.temperatures.is_empty() {
             return Err("Histórico de temperatura vazio".to_string());
         }
         
         // Obter a temperatura final
-        let final_temperature = &self.simulation_state.temperature_history[self.simulation_state.temperature_history.len() - 1];
-        let mesh = &self.simulation_state.mesh;
+        let final_temperature = &self.history.temperatures[self.history.temperatures.len() - 1];
+        let mesh = &self.history.mesh;
         
         // Interpolar valores simulados nos pontos de referência
         let mut simulated_values = Vec::with_capacity(reference_data.coordinates.len());
@@ -313,7 +480,7 @@ impl ModelValidator {
         // Encontrar os índices da célula que contém o ponto
         let i_r = self.find_index(r, &mesh.r_coords);
         let i_theta = (theta / mesh.dtheta).floor() as usize;
-        let i_z = (z / mesh.dz).floor() as usize;
+        let i_z = self.find_index(z, &mesh.z_coords);
         
         // Verificar se os índices estão dentro dos limites
         if i_r >= mesh.nr - 1 || i_theta >= mesh.ntheta - 1 || i_z >= mesh.nz - 1 {
@@ -323,7 +490,7 @@ impl ModelValidator {
         // Calcular as frações para interpolação
         let fr = (r - mesh.r_coords[i_r]) / (mesh.r_coords[i_r + 1] - mesh.r_coords[i_r]);
         let ftheta = (theta - i_theta as f64 * mesh.dtheta) / mesh.dtheta;
-        let fz = (z - i_z as f64 * mesh.dz) / mesh.dz;
+        let fz = (z - mesh.z_coords[i_z]) / (mesh.z_coords[i_z + 1] - mesh.z_coords[i_z]);
         
         // Obter os valores nos vértices da célula
         let v000 = self.get_temperature_at(i_r, i_theta, i_z, temperature, mesh);
@@ -348,12 +515,12 @@ impl ModelValidator {
     }
     
     /// Encontra o índice do elemento mais próximo em um vetor ordenado
-    fn find_index(&self, value: f64, coords: &[f64]) -> usize {
+    fn find_index(&self, value: f64, coords: &Array1<f64>) -> usize {
         let mut left = 0;
         let mut right = coords.len() - 1;
         
         while left < right {
-            let mid = (left + right + 1) / 2;
+            let mid = (left + right).div_ceil(2);
             
             if coords[mid] <= value {
                 left = mid;
@@ -428,7 +595,7 @@ impl ModelValidator {
         };
         
         // Calcular erro máximo absoluto
-        let mut max_abs_error = 0.0;
+        let mut max_abs_error: f64 = 0.0;
         for i in 0..reference.len() {
             let abs_error = (reference[i] - simulated[i]).abs();
             max_abs_error = max_abs_error.max(abs_error);
@@ -460,9 +627,9 @@ impl ModelValidator {
         
         // Exemplo: dividir em regiões radiais (centro, meio, periferia)
         let regions = vec![
-            ("Centro", 0.0, self.simulation_state.mesh.r_max / 3.0),
-            ("Meio", self.simulation_state.mesh.r_max / 3.0, 2.0 * self.simulation_state.mesh.r_max / 3.0),
-            ("Periferia", 2.0 * self.simulation_state.mesh.r_max / 3.0, self.simulation_state.mesh.r_max),
+            ("Centro", 0.0, self.history.mesh.radius / 3.0),
+            ("Meio", self.history.mesh.radius / 3.0, 2.0 * self.history.mesh.radius / 3.0),
+            ("Periferia", 2.0 * self.history.mesh.radius / 3.0, self.history.mesh.radius),
         ];
         
         for (name, r_min, r_max) in regions {
@@ -521,228 +688,35 @@ impl ModelValidator {
     
     /// Gera um relatório de validação
     pub fn generate_validation_report(&self, output_path: &str) -> Result<(), String> {
-        if self.validation_result.is_none() {
-            return Err("Resultado de validação não disponível".to_string());
-        }
-        
-        let validation_result = self.validation_result.as_ref().unwrap();
-        
-        let path = Path::new(output_path);
-        let mut file = File::create(path).map_err(|e| format!("Erro ao criar arquivo de relatório: {}", e))?;
-        
-        // Escrever cabeçalho do relatório
-        let header = format!(
-            "# Relatório de Validação: {}\n\n{}\n\n",
-            validation_result.name,
-            validation_result.description
-        );
-        
-        file.write_all(header.as_bytes()).map_err(|e| format!("Erro ao escrever cabeçalho do relatório: {}", e))?;
-        
-        // Escrever informações sobre os dados de referência
-        let reference_info = format!(
-            "## Dados de Referência\n\n\
-             - Nome: {}\n\
-             - Descrição: {}\n\
-             - Fonte: {}\n\
-             - Tipo de dados: {}\n\
-             - Número de pontos: {}\n\n",
-            validation_result.reference_data.name,
-            validation_result.reference_data.description,
-            validation_result.reference_data.source,
-            validation_result.reference_data.data_type,
-            validation_result.reference_data.coordinates.len()
-        );
-        
-        file.write_all(reference_info.as_bytes()).map_err(|e| format!("Erro ao escrever informações de referência: {}", e))?;
-        
-        // Escrever métricas de validação
-        let metrics = &validation_result.metrics;
-        
-        let metrics_info = format!(
-            "## Métricas de Validação\n\n\
-             - Erro Médio Absoluto (MAE): {:.4} °C\n\
-             - Erro Quadrático Médio (MSE): {:.4} °C²\n\
-             - Raiz do Erro Quadrático Médio (RMSE): {:.4} °C\n\
-             - Erro Percentual Absoluto Médio (MAPE): {:.4} %\n\
-             - Coeficiente de Determinação (R²): {:.4}\n\
-             - Erro Máximo Absoluto: {:.4} °C\n\
-             - Erro Médio (ME): {:.4} °C\n\
-             - Erro Normalizado (NRMSE): {:.4}\n\n",
-            metrics.mean_absolute_error,
-            metrics.mean_squared_error,
-            metrics.root_mean_squared_error,
-            metrics.mean_absolute_percentage_error,
-            metrics.r_squared,
-            metrics.max_absolute_error,
-            metrics.mean_error,
-            metrics.normalized_rmse
-        );
-        
-        file.write_all(metrics_info.as_bytes()).map_err(|e| format!("Erro ao escrever métricas de validação: {}", e))?;
-        
-        // Escrever métricas por região
-        let region_header = "## Métricas por Região\n\n";
-        file.write_all(region_header.as_bytes()).map_err(|e| format!("Erro ao escrever cabeçalho de métricas por região: {}", e))?;
-        
-        for (name, region_metrics) in &metrics.region_metrics {
-            let region_metrics_info = format!(
-                "### Região: {}\n\n\
-                 - Erro Médio Absoluto (MAE): {:.4} °C\n\
-                 - Erro Quadrático Médio (MSE): {:.4} °C²\n\
-                 - Raiz do Erro Quadrático Médio (RMSE): {:.4} °C\n\
-                 - Erro Percentual Absoluto Médio (MAPE): {:.4} %\n\
-                 - Coeficiente de Determinação (R²): {:.4}\n\
-                 - Erro Máximo Absoluto: {:.4} °C\n\
-                 - Erro Médio (ME): {:.4} °C\n\
-                 - Erro Normalizado (NRMSE): {:.4}\n\n",
-                name,
-                region_metrics.mean_absolute_error,
-                region_metrics.mean_squared_error,
-                region_metrics.root_mean_squared_error,
-                region_metrics.mean_absolute_percentage_error,
-                region_metrics.r_squared,
-                region_metrics.max_absolute_error,
-                region_metrics.mean_error,
-                region_metrics.normalized_rmse
-            );
-            
-            file.write_all(region_metrics_info.as_bytes()).map_err(|e| format!("Erro ao escrever métricas da região {}: {}", name, e))?;
-        }
-        
-        // Escrever análise de resultados
-        let analysis = format!(
-            "## Análise de Resultados\n\n\
-             A validação do modelo apresentou um RMSE de {:.4} °C, o que representa {:.2}% da faixa de temperatura dos dados de referência. \
-             O coeficiente de determinação (R²) de {:.4} indica que o modelo {}. \
-             O erro médio de {:.4} °C sugere que o modelo {}.\n\n\
-             A região com melhor desempenho foi {}, com RMSE de {:.4} °C, \
-             enquanto a região com pior desempenho foi {}, com RMSE de {:.4} °C.\n\n",
-            metrics.root_mean_squared_error,
-            metrics.normalized_rmse * 100.0,
-            metrics.r_squared,
-            if metrics.r_squared > 0.9 {
-                "explica muito bem a variação dos dados"
-            } else if metrics.r_squared > 0.7 {
-                "explica razoavelmente bem a variação dos dados"
-            } else {
-                "não explica adequadamente a variação dos dados"
-            },
-            metrics.mean_error,
-            if metrics.mean_error.abs() < 0.1 * metrics.root_mean_squared_error {
-                "não apresenta viés significativo"
-            } else if metrics.mean_error > 0.0 {
-                "tende a subestimar os valores reais"
-            } else {
-                "tende a superestimar os valores reais"
-            },
-            self.find_best_region(&metrics.region_metrics),
-            self.get_region_rmse(&metrics.region_metrics, &self.find_best_region(&metrics.region_metrics)),
-            self.find_worst_region(&metrics.region_metrics),
-            self.get_region_rmse(&metrics.region_metrics, &self.find_worst_region(&metrics.region_metrics))
-        );
-        
-        file.write_all(analysis.as_bytes()).map_err(|e| format!("Erro ao escrever análise de resultados: {}", e))?;
-        
-        // Escrever conclusões
-        let conclusion = format!(
-            "## Conclusões\n\n\
-             Com base nas métricas de validação, o modelo {}. \
-             O erro médio absoluto de {:.4} °C e o erro percentual médio de {:.2}% indicam que {}. \
-             Recomenda-se {} para melhorar a precisão do modelo.\n\n",
-            if metrics.r_squared > 0.9 && metrics.normalized_rmse < 0.1 {
-                "apresenta excelente concordância com os dados de referência"
-            } else if metrics.r_squared > 0.7 && metrics.normalized_rmse < 0.2 {
-                "apresenta boa concordância com os dados de referência"
-            } else {
-                "apresenta concordância limitada com os dados de referência"
-            },
-            metrics.mean_absolute_error,
-            metrics.mean_absolute_percentage_error,
-            if metrics.mean_absolute_percentage_error < 5.0 {
-                "o modelo é adequado para aplicações de alta precisão"
-            } else if metrics.mean_absolute_percentage_error < 10.0 {
-                "o modelo é adequado para a maioria das aplicações práticas"
-            } else {
-                "o modelo pode ser inadequado para aplicações que exigem alta precisão"
-            },
-            if metrics.r_squared < 0.7 {
-                "revisar os parâmetros físicos e refinar a malha de discretização"
-            } else if metrics.mean_error.abs() > 0.1 * metrics.root_mean_squared_error {
-                "ajustar os parâmetros do modelo para reduzir o viés sistemático"
-            } else {
-                "realizar validações adicionais com outros conjuntos de dados"
-            }
-        );
-        
-        file.write_all(conclusion.as_bytes()).map_err(|e| format!("Erro ao escrever conclusões: {}", e))?;
-        
-        Ok(())
-    }
-    
-    /// Encontra a região com melhor desempenho (menor RMSE)
-    fn find_best_region(&self, region_metrics: &HashMap<String, ValidationMetrics>) -> String {
-        let mut best_region = String::new();
-        let mut best_rmse = f64::INFINITY;
-        
-        for (name, metrics) in region_metrics {
-            if metrics.root_mean_squared_error < best_rmse {
-                best_rmse = metrics.root_mean_squared_error;
-                best_region = name.clone();
-            }
-        }
-        
-        best_region
-    }
-    
-    /// Encontra a região com pior desempenho (maior RMSE)
-    fn find_worst_region(&self, region_metrics: &HashMap<String, ValidationMetrics>) -> String {
-        let mut worst_region = String::new();
-        let mut worst_rmse = 0.0;
-        
-        for (name, metrics) in region_metrics {
-            if metrics.root_mean_squared_error > worst_rmse {
-                worst_rmse = metrics.root_mean_squared_error;
-                worst_region = name.clone();
-            }
-        }
-        
-        worst_region
-    }
-    
-    /// Obtém o RMSE de uma região específica
-    fn get_region_rmse(&self, region_metrics: &HashMap<String, ValidationMetrics>, region_name: &str) -> f64 {
-        if let Some(metrics) = region_metrics.get(region_name) {
-            metrics.root_mean_squared_error
-        } else {
-            0.0
-        }
+        let validation_result = self.validation_result.as_ref()
+            .ok_or_else(|| "Resultado de validação não disponível".to_string())?;
+        write_validation_report(validation_result, output_path)
     }
     
     /// Cria dados de referência sintéticos para testes
     pub fn create_synthetic_reference_data(&self, num_points: usize, error_level: f64) -> ReferenceData {
-        let mesh = &self.simulation_state.mesh;
+        let mesh = &self.history.mesh;
         
         let mut coordinates = Vec::with_capacity(num_points);
         let mut values = Vec::with_capacity(num_points);
         
-        // Gerar pontos aleatórios dentro do domínio
-        let mut rng = rand::thread_rng();
+        // Gerar pontos aleatórios dentro do domínio (semente fixa: dados reprodutíveis)
+        let mut rng = SplitMix64::new(SYNTHETIC_DATA_SEED);
         
         for _ in 0..num_points {
             // Coordenadas aleatórias
-            let r = mesh.r_min + (mesh.r_max - mesh.r_min) * rand::random::<f64>();
-            let theta = mesh.dtheta * mesh.ntheta as f64 * rand::random::<f64>();
-            let z = mesh.dz * mesh.nz as f64 * rand::random::<f64>();
+            let r = mesh.radius * rng.next_f64();
+            let theta = mesh.dtheta * mesh.ntheta as f64 * rng.next_f64();
+            let z = mesh.height * rng.next_f64();
             
             coordinates.push((r, theta, z));
             
             // Valor de referência (simplificado)
             // Em uma implementação real, isso seria baseado em uma solução analítica ou dados experimentais
-            let reference_value = 100.0 + 400.0 * (1.0 - r / mesh.r_max);
+            let reference_value = 100.0 + 400.0 * (1.0 - r / mesh.radius);
             
             // Adicionar ruído para simular erro experimental
-            let noise = error_level * (2.0 * rand::random::<f64>() - 1.0) * reference_value;
+            let noise = error_level * (2.0 * rng.next_f64() - 1.0) * reference_value;
             values.push(reference_value + noise);
         }
         
@@ -754,45 +728,246 @@ impl ModelValidator {
             coordinates,
             values,
             uncertainties: None,
+            times: None,
             metadata: HashMap::new(),
         }
     }
 }
 
+/// Valida os resultados de uma simulação contra dados de referência
+pub fn validate(results: &SimulationResults, reference_data: &ReferenceData, name: String, description: String) -> Result<ValidationResult, String> {
+    let mut state = SimulationState::new(results.parameters.clone());
+    state.complete(results.clone());
+    let mut validator = ModelValidator::new(state);
+    validator.set_reference_data(reference_data.clone());
+    validator.validate(&name, &description).cloned()
+}
+
+/// Cria dados de referência sintéticos no domínio dos parâmetros informados
+pub fn create_synthetic(parameters: &SimulationParameters, num_points: usize, error_level: f64) -> Result<ReferenceData, String> {
+    if !(0.0..=1.0).contains(&error_level) {
+        return Err(format!("Nível de erro deve estar em [0, 1]: {}", error_level));
+    }
+    let validator = ModelValidator::new(SimulationState::new(parameters.clone()));
+    Ok(validator.create_synthetic_reference_data(num_points, error_level))
+}
+
+/// Gera o relatório de um resultado de validação em Markdown
+pub fn write_validation_report(validation_result: &ValidationResult, output_path: &str) -> Result<(), String> {
+    let path = Path::new(output_path);
+    let mut file = File::create(path).map_err(|e| format!("Erro ao criar arquivo de relatório: {}", e))?;
+
+    // Escrever cabeçalho do relatório
+    let header = format!(
+        "# Relatório de Validação: {}\n\n{}\n\n",
+        validation_result.name,
+        validation_result.description
+    );
+
+    file.write_all(header.as_bytes()).map_err(|e| format!("Erro ao escrever cabeçalho do relatório: {}", e))?;
+
+    // Escrever informações sobre os dados de referência
+    let reference_info = format!(
+        "## Dados de Referência\n\n\
+         - Nome: {}\n\
+         - Descrição: {}\n\
+         - Fonte: {}\n\
+         - Tipo de dados: {}\n\
+         - Número de pontos: {}\n\n",
+        validation_result.reference_data.name,
+        validation_result.reference_data.description,
+        validation_result.reference_data.source,
+        validation_result.reference_data.data_type,
+        validation_result.reference_data.coordinates.len()
+    );
+
+    file.write_all(reference_info.as_bytes()).map_err(|e| format!("Erro ao escrever informações de referência: {}", e))?;
+
+    // Escrever métricas de validação
+    let metrics = &validation_result.metrics;
+
+    let metrics_info = format!(
+        "## Métricas de Validação\n\n\
+         - Erro Médio Absoluto (MAE): {:.4} °C\n\
+         - Erro Quadrático Médio (MSE): {:.4} °C²\n\
+         - Raiz do Erro Quadrático Médio (RMSE): {:.4} °C\n\
+         - Erro Percentual Absoluto Médio (MAPE): {:.4} %\n\
+         - Coeficiente de Determinação (R²): {:.4}\n\
+         - Erro Máximo Absoluto: {:.4} °C\n\
+         - Erro Médio (ME): {:.4} °C\n\
+         - Erro Normalizado (NRMSE): {:.4}\n\n",
+        metrics.mean_absolute_error,
+        metrics.mean_squared_error,
+        metrics.root_mean_squared_error,
+        metrics.mean_absolute_percentage_error,
+        metrics.r_squared,
+        metrics.max_absolute_error,
+        metrics.mean_error,
+        metrics.normalized_rmse
+    );
+
+    file.write_all(metrics_info.as_bytes()).map_err(|e| format!("Erro ao escrever métricas de validação: {}", e))?;
+
+    // Escrever métricas por região
+    let region_header = "## Métricas por Região\n\n";
+    file.write_all(region_header.as_bytes()).map_err(|e| format!("Erro ao escrever cabeçalho de métricas por região: {}", e))?;
+
+    for (name, region_metrics) in &metrics.region_metrics {
+        let region_metrics_info = format!(
+            "### Região: {}\n\n\
+             - Erro Médio Absoluto (MAE): {:.4} °C\n\
+             - Erro Quadrático Médio (MSE): {:.4} °C²\n\
+             - Raiz do Erro Quadrático Médio (RMSE): {:.4} °C\n\
+             - Erro Percentual Absoluto Médio (MAPE): {:.4} %\n\
+             - Coeficiente de Determinação (R²): {:.4}\n\
+             - Erro Máximo Absoluto: {:.4} °C\n\
+             - Erro Médio (ME): {:.4} °C\n\
+             - Erro Normalizado (NRMSE): {:.4}\n\n",
+            name,
+            region_metrics.mean_absolute_error,
+            region_metrics.mean_squared_error,
+            region_metrics.root_mean_squared_error,
+            region_metrics.mean_absolute_percentage_error,
+            region_metrics.r_squared,
+            region_metrics.max_absolute_error,
+            region_metrics.mean_error,
+            region_metrics.normalized_rmse
+        );
+
+        file.write_all(region_metrics_info.as_bytes()).map_err(|e| format!("Erro ao escrever métricas da região {}: {}", name, e))?;
+    }
+
+    // Escrever análise de resultados
+    let analysis = format!(
+        "## Análise de Resultados\n\n\
+         A validação do modelo apresentou um RMSE de {:.4} °C, o que representa {:.2}% da faixa de temperatura dos dados de referência. \
+         O coeficiente de determinação (R²) de {:.4} indica que o modelo {}. \
+         O erro médio de {:.4} °C sugere que o modelo {}.\n\n\
+         A região com melhor desempenho foi {}, com RMSE de {:.4} °C, \
+         enquanto a região com pior desempenho foi {}, com RMSE de {:.4} °C.\n\n",
+        metrics.root_mean_squared_error,
+        metrics.normalized_rmse * 100.0,
+        metrics.r_squared,
+        if metrics.r_squared > 0.9 {
+            "explica muito bem a variação dos dados"
+        } else if metrics.r_squared > 0.7 {
+            "explica razoavelmente bem a variação dos dados"
+        } else {
+            "não explica adequadamente a variação dos dados"
+        },
+        metrics.mean_error,
+        if metrics.mean_error.abs() < 0.1 * metrics.root_mean_squared_error {
+            "não apresenta viés significativo"
+        } else if metrics.mean_error > 0.0 {
+            "tende a subestimar os valores reais"
+        } else {
+            "tende a superestimar os valores reais"
+        },
+        find_best_region(&metrics.region_metrics),
+        get_region_rmse(&metrics.region_metrics, &find_best_region(&metrics.region_metrics)),
+        find_worst_region(&metrics.region_metrics),
+        get_region_rmse(&metrics.region_metrics, &find_worst_region(&metrics.region_metrics))
+    );
+
+    file.write_all(analysis.as_bytes()).map_err(|e| format!("Erro ao escrever análise de resultados: {}", e))?;
+
+    // Escrever conclusões
+    let conclusion = format!(
+        "## Conclusões\n\n\
+         Com base nas métricas de validação, o modelo {}. \
+         O erro médio absoluto de {:.4} °C e o erro percentual médio de {:.2}% indicam que {}. \
+         Recomenda-se {} para melhorar a precisão do modelo.\n\n",
+        if metrics.r_squared > 0.9 && metrics.normalized_rmse < 0.1 {
+            "apresenta excelente concordância com os dados de referência"
+        } else if metrics.r_squared > 0.7 && metrics.normalized_rmse < 0.2 {
+            "apresenta boa concordância com os dados de referência"
+        } else {
+            "apresenta concordância limitada com os dados de referência"
+        },
+        metrics.mean_absolute_error,
+        metrics.mean_absolute_percentage_error,
+        if metrics.mean_absolute_percentage_error < 5.0 {
+            "o modelo é adequado para aplicações de alta precisão"
+        } else if metrics.mean_absolute_percentage_error < 10.0 {
+            "o modelo é adequado para a maioria das aplicações práticas"
+        } else {
+            "o modelo pode ser inadequado para aplicações que exigem alta precisão"
+        },
+        if metrics.r_squared < 0.7 {
+            "revisar os parâmetros físicos e refinar a malha de discretização"
+        } else if metrics.mean_error.abs() > 0.1 * metrics.root_mean_squared_error {
+            "ajustar os parâmetros do modelo para reduzir o viés sistemático"
+        } else {
+            "realizar validações adicionais com outros conjuntos de dados"
+        }
+    );
+
+    file.write_all(conclusion.as_bytes()).map_err(|e| format!("Erro ao escrever conclusões: {}", e))?;
+
+    Ok(())
+}
+
+/// Encontra a região com melhor desempenho (menor RMSE)
+fn find_best_region(region_metrics: &HashMap<String, ValidationMetrics>) -> String {
+    let mut best_region = String::new();
+    let mut best_rmse = f64::INFINITY;
+
+    for (name, metrics) in region_metrics {
+        if metrics.root_mean_squared_error < best_rmse {
+            best_rmse = metrics.root_mean_squared_error;
+            best_region = name.clone();
+        }
+    }
+
+    best_region
+}
+
+/// Encontra a região com pior desempenho (maior RMSE)
+fn find_worst_region(region_metrics: &HashMap<String, ValidationMetrics>) -> String {
+    let mut worst_region = String::new();
+    let mut worst_rmse = 0.0;
+
+    for (name, metrics) in region_metrics {
+        if metrics.root_mean_squared_error > worst_rmse {
+            worst_rmse = metrics.root_mean_squared_error;
+            worst_region = name.clone();
+        }
+    }
+
+    worst_region
+}
+
+/// Obtém o RMSE de uma região específica
+fn get_region_rmse(region_metrics: &HashMap<String, ValidationMetrics>, region_name: &str) -> f64 {
+    if let Some(metrics) = region_metrics.get(region_name) {
+        metrics.root_mean_squared_error
+    } else {
+        0.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::simulation::mesh::CylindricalMesh;
+    use crate::simulation::mesh::MeshGrading;
     use crate::simulation::state::SimulationState;
     
     fn create_test_simulation_state() -> SimulationState {
-        // Criar malha de teste
-        let mesh = CylindricalMesh::new(10, 8, 10, 0.1, 1.0);
-        
-        // Criar estado de simulação
-        let mut state = SimulationState::new(mesh);
-        
-        // Adicionar dados de temperatura
-        let mut temperature = vec![25.0; mesh.total_cells()];
-        
-        // Adicionar temperatura com distribuição radial
-        for i in 0..mesh.nr {
-            for j in 0..mesh.ntheta {
-                for k in 0..mesh.nz {
-                    let idx = i * mesh.ntheta * mesh.nz + j * mesh.nz + k;
-                    let r = mesh.r_coords[i];
-                    
-                    // Temperatura mais alta no centro, diminuindo com o raio
-                    temperature[idx] = 500.0 * (1.0 - r / mesh.r_max) + 25.0;
-                }
-            }
-        }
+        // Malha de teste: 10 x 10 células e 8 setores angulares
+        let mut params = SimulationParameters::new(1.0, 1.0, 10, 10);
+        params.ntheta = 8;
         
-        state.temperature_history.push(temperature);
+        let mut results = SimulationResults::for_tests(params.clone(), ndarray::Array3::zeros((10, 10, 1)));
         
-        // Adicionar passos de tempo
-        state.time_steps = vec![10.0];
+        // Temperatura mais alta no centro, diminuindo com o raio
+        for i in 0..params.nr {
+            let r = results.mesh.r_coords[i];
+            results.temperature.slice_mut(ndarray::s![i, .., 0]).fill(500.0 * (1.0 - r / params.radius) + 25.0);
+        }
+        results.enthalpy = results.temperature.clone();
         
+        let mut state = SimulationState::new(params);
+        state.complete(results);
         state
     }
     
@@ -818,6 +993,7 @@ mod tests {
             coordinates,
             values,
             uncertainties: None,
+            times: None,
             metadata: HashMap::new(),
         }
     }
@@ -844,6 +1020,30 @@ mod tests {
         assert_eq!(validation_result.simulated_values.len(), validation_result.reference_data.values.len());
     }
     
+    #[test]
+    fn test_interpolation_on_graded_mesh() {
+        let mut params = SimulationParameters::new(1.0, 1.0, 6, 9);
+        params.ntheta = 8;
+        params.radial_grading = MeshGrading::Geometric { ratio: 1.4 };
+        params.axial_grading = MeshGrading::Hyperbolic { stretching: 2.0 };
+        let mut results = SimulationResults::for_tests(params.clone(), ndarray::Array3::zeros((6, 9, 1)));
+        for i in 0..params.nr {
+            for j in 0..params.nz {
+                results.temperature[[i, j, 0]] = 100.0 + 200.0 * results.mesh.r_coords[i] + 300.0 * results.mesh.z_coords[j];
+            }
+        }
+        let mut state = SimulationState::new(params);
+        state.complete(results);
+        let validator = ModelValidator::new(state);
+
+        // A célula é localizada nas coordenadas reais: campo linear interpolado exatamente
+        let temperature = validator.history.temperatures.last().unwrap();
+        for (r, z) in [(0.05, 0.03), (0.42, 0.5), (0.9, 0.97)] {
+            let value = validator.interpolate_value(r, 0.3, z, temperature, &validator.history.mesh);
+            assert!((value - (100.0 + 200.0 * r + 300.0 * z)).abs() < 1e-9, "r = {}, z = {}: {}", r, z, value);
+        }
+    }
+    
     #[test]
     fn test_synthetic_data() {
         let state = create_test_simulation_state();
@@ -880,4 +1080,68 @@ mod tests {
         // Limpar
         std::fs::remove_file(path).unwrap();
     }
+
+    #[test]
+    fn test_csv_import_column_mapping_and_units() {
+        // Registro de termopares em mm, minutos e Kelvin, com comentário e colunas fora de ordem
+        let content = "\u{feff}# Termopares do ensaio 3\n\
+                       T;sigma;t_min;z_mm;r_mm\n\
+                       \n\
+                       373.15;2.0;0.5;100;50\n\
+                       \"473.15\";1.5;1.0;200;150\n";
+        let options = CsvImportOptions {
+            delimiter: ';',
+            has_header: true,
+            columns: CsvColumnMapping {
+                r: CsvColumn::Name("R_MM".to_string()),
+                z: CsvColumn::Name("z_mm".to_string()),
+                time: Some(CsvColumn::Index(2)),
+                value: CsvColumn::Name("T".to_string()),
+                uncertainty: Some(CsvColumn::Name("sigma".to_string())),
+            },
+            units: CsvUnits {
+                length: LengthUnit::Millimeter,
+                time: TimeUnit::Minute,
+                temperature: TemperatureUnit::Kelvin,
+            },
+        };
+
+        let data = parse_reference_csv(content, &options).unwrap();
+        assert_eq!(data.coordinates.len(), 2);
+        assert!((data.coordinates[1].0 - 0.15).abs() < 1e-12);
+        assert!((data.coordinates[1].2 - 0.2).abs() < 1e-12);
+        assert!((data.values[0] - 100.0).abs() < 1e-9);
+        assert!((data.values[1] - 200.0).abs() < 1e-9);
+        assert_eq!(data.times, Some(vec![30.0, 60.0]));
+        assert_eq!(data.uncertainties, Some(vec![2.0, 1.5]));
+
+        // Fahrenheit: incertezas convertidas sem o deslocamento da escala
+        let mut fahrenheit = options.clone();
+        fahrenheit.units.temperature = TemperatureUnit::Fahrenheit;
+        let data = parse_reference_csv("T;sigma;t_min;z_mm;r_mm\n212;9;0;0;0\n", &fahrenheit).unwrap();
+        assert!((data.values[0] - 100.0).abs() < 1e-9);
+        assert!((data.uncertainties.unwrap()[0] - 5.0).abs() < 1e-9);
+
+        // Colunas por nome exigem cabeçalho; campos não numéricos indicam a linha
+        let mut headerless = options.clone();
+        headerless.has_header = false;
+        assert!(parse_reference_csv("373.15;2;0;0;0\n", &headerless).unwrap_err().contains("cabeçalho"));
+        assert!(parse_reference_csv("T;sigma;t_min;z_mm;r_mm\n300;x;0;0;0\n", &options).unwrap_err().contains("linha 2"));
+        assert!(parse_reference_csv("T;sigma;t_min;z_mm;r_mm\n", &options).is_err());
+
+        // Importação pelo arquivo, com as colunas padrão (r, z, value)
+        let path = std::env::temp_dir().join(format!("plasma_reference_{}.csv", std::process::id()));
+        std::fs::write(&path, "r,z,value\n0.1,0.2,850\n").unwrap();
+        let import_options = ImportOptions {
+            format: ImportFormat::from_name("csv").unwrap(),
+            input_path: path.to_string_lossy().to_string(),
+            csv: CsvImportOptions::default(),
+        };
+        let data = import_data(&import_options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(data.coordinates, vec![(0.1, 0.0, 0.2)]);
+        assert_eq!(data.values, vec![850.0]);
+        assert!(data.times.is_none() && data.uncertainties.is_none());
+        assert!(data.name.ends_with(".csv"));
+    }
 }
@@ -7,7 +7,7 @@ mod tests {
     use crate::simulation::state::SimulationState;
     use crate::simulation::parametric::{
         ParametricStudyManager, ParametricStudyConfig, ParametricParameter,
-        ScaleType, OptimizationGoal
+        ScaleType, SamplingMethod, OptimizationGoal
     };
     use std::collections::HashMap;
 
@@ -84,6 +84,8 @@ mod tests {
                 num_points: 3,
                 scale_type: ScaleType::Linear,
                 specific_values: None,
+                categories: None,
+                category_presets: HashMap::new(),
             },
             ParametricParameter {
                 name: "thermal_conductivity".to_string(),
@@ -94,6 +96,8 @@ mod tests {
                 num_points: 2,
                 scale_type: ScaleType::Linear,
                 specific_values: None,
+                categories: None,
+                category_presets: HashMap::new(),
             },
         ];
         
@@ -104,6 +108,11 @@ mod tests {
             parameters,
             target_metric: "max_temperature".to_string(),
             optimization_goal: OptimizationGoal::Maximize,
+            sampling_method: SamplingMethod::Grid,
+            design: None,
+            derived_parameters: Vec::new(),
+            constraints: Vec::new(),
+            early_stopping: Vec::new(),
             max_simulations: 10,
             max_execution_time: Some(60.0),
             use_parallel: false,
@@ -0,0 +1,120 @@
+// Modelo para as anotações da linha do tempo da simulação (eventos e notas)
+
+// Tipos de anotação (espelha `AnnotationKind` do backend)
+enum AnnotationKind {
+  tapEvent,
+  powerChange,
+  alarm,
+  feedAdded,
+  note,
+  custom,
+}
+
+// Origem da anotação (espelha `AnnotationSource` do backend)
+enum AnnotationSource {
+  solver,
+  user,
+}
+
+class TimelineAnnotation {
+  // Tempo da anotação (s)
+  final double time;
+  // Passo de tempo correspondente (opcional)
+  final int? step;
+  final AnnotationKind kind;
+  // Rótulo do tipo definido pelo usuário (apenas para AnnotationKind.custom)
+  final String? customKind;
+  // Título curto exibido no marcador
+  final String title;
+  final String description;
+  final AnnotationSource source;
+  final Map<String, String> metadata;
+
+  TimelineAnnotation({
+    required this.time,
+    this.step,
+    required this.kind,
+    this.customKind,
+    required this.title,
+    this.description = '',
+    this.source = AnnotationSource.user,
+    this.metadata = const {},
+  });
+
+  // Nomes das variantes serializadas pelo serde
+  static const Map<AnnotationKind, String> _kindNames = {
+    AnnotationKind.tapEvent: 'TapEvent',
+    AnnotationKind.powerChange: 'PowerChange',
+    AnnotationKind.alarm: 'Alarm',
+    AnnotationKind.feedAdded: 'FeedAdded',
+    AnnotationKind.note: 'Note',
+  };
+
+  factory TimelineAnnotation.fromJson(Map<String, dynamic> json) {
+    // Variantes unitárias chegam como texto; `Custom` como {"Custom": "rótulo"}
+    final rawKind = json['kind'];
+    AnnotationKind kind = AnnotationKind.note;
+    String? customKind;
+    if (rawKind is Map && rawKind.containsKey('Custom')) {
+      kind = AnnotationKind.custom;
+      customKind = rawKind['Custom'] as String;
+    } else if (rawKind is String) {
+      kind = _kindNames.entries
+          .firstWhere((entry) => entry.value == rawKind,
+              orElse: () => const MapEntry(AnnotationKind.note, 'Note'))
+          .key;
+    }
+
+    return TimelineAnnotation(
+      time: (json['time'] as num).toDouble(),
+      step: json['step'] as int?,
+      kind: kind,
+      customKind: customKind,
+      title: json['title'],
+      description: json['description'] ?? '',
+      source: json['source'] == 'Solver'
+          ? AnnotationSource.solver
+          : AnnotationSource.user,
+      metadata: Map<String, String>.from(json['metadata'] ?? const {}),
+    );
+  }
+
+  Map<String, dynamic> toJson() {
+    return {
+      'time': time,
+      'step': step,
+      'kind': kind == AnnotationKind.custom
+          ? {'Custom': customKind ?? ''}
+          : _kindNames[kind],
+      'title': title,
+      'description': description,
+      'source': source == AnnotationSource.solver ? 'Solver' : 'User',
+      'metadata': metadata,
+    };
+  }
+
+  // Passo de tempo do marcador: o passo informado ou o mais próximo do tempo
+  int stepFor(double timeStep) {
+    if (step != null) return step!;
+    if (timeStep <= 0) return 0;
+    return (time / timeStep).round();
+  }
+
+  // Rótulo legível do tipo da anotação
+  String get kindLabel {
+    switch (kind) {
+      case AnnotationKind.tapEvent:
+        return 'Vazamento';
+      case AnnotationKind.powerChange:
+        return 'Mudança de potência';
+      case AnnotationKind.alarm:
+        return 'Alarme';
+      case AnnotationKind.feedAdded:
+        return 'Carga adicionada';
+      case AnnotationKind.note:
+        return 'Nota';
+      case AnnotationKind.custom:
+        return customKind ?? 'Personalizado';
+    }
+  }
+}
@@ -1,4 +1,5 @@
 import 'package:flutter/foundation.dart';
+import 'annotation.dart';
 
 // Modelo para os resultados da simulação
 class SimulationResults {
@@ -13,12 +14,20 @@ class SimulationResults {
   // Tempo de execução
   final double executionTime;
   
+  // Passo de tempo da simulação (s), usado para posicionar as anotações
+  final double timeStep;
+  
+  // Anotações da linha do tempo, ordenadas por tempo
+  final List<TimelineAnnotation> annotations;
+  
   SimulationResults({
     required this.nr,
     required this.nz,
     required this.timeSteps,
     required this.temperatureData,
     required this.executionTime,
+    this.timeStep = 1.0,
+    this.annotations = const [],
   });
   
   // Obtém os dados de temperatura para um passo de tempo específico
@@ -83,6 +92,7 @@ enum SimulationStatus {
   paused,
   completed,
   failed,
+  cancelled,
 }
 
 // Modelo para o estado da simulação
@@ -138,4 +148,7 @@ class SimulationState {
   
   // Verifica se a simulação falhou
   bool get isFailed => status == SimulationStatus.failed;
+
+  // Verifica se a simulação foi cancelada (resultados parciais)
+  bool get isCancelled => status == SimulationStatus.cancelled;
 }
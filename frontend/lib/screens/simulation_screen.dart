@@ -3,6 +3,7 @@ import 'package:flutter_riverpod/flutter_riverpod.dart';
 import 'package:fl_chart/fl_chart.dart';
 import '../models/simulation_results.dart';
 import '../state/simulation_state.dart';
+import '../widgets/visualization/timeline_annotation_markers.dart';
 
 class SimulationScreen extends ConsumerStatefulWidget {
   const SimulationScreen({Key? key}) : super(key: key);
@@ -72,6 +73,10 @@ class _SimulationScreenState extends ConsumerState<SimulationScreen> {
         statusText = 'Falha: ${state.errorMessage ?? "Erro desconhecido"}';
         statusColor = Colors.red;
         break;
+      case SimulationStatus.cancelled:
+        statusText = 'Cancelada (${(state.progress * 100).toStringAsFixed(1)}%)';
+        statusColor = Colors.orange;
+        break;
     }
 
     return Container(
@@ -357,6 +362,17 @@ class _SimulationScreenState extends ConsumerState<SimulationScreen> {
               ),
             ],
           ),
+          // Marcadores das anotações (vazamentos, mudanças de potência, alarmes...)
+          TimelineAnnotationMarkers(
+            annotations: results.annotations,
+            maxTimeStep: maxTimeStep,
+            timeStep: results.timeStep,
+            onSelected: (step) {
+              setState(() {
+                _currentTimeStep = step;
+              });
+            },
+          ),
           Slider(
             value: _currentTimeStep.toDouble(),
             min: 0,
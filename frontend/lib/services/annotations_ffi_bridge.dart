@@ -0,0 +1,48 @@
+import 'dart:convert';
+import 'dart:ffi';
+import 'package:ffi/ffi.dart';
+import '../models/annotation.dart';
+import 'ffi_bridge.dart';
+
+// --- FFI Function Typedefs for Timeline Annotations (JSON based) ---
+
+typedef GetAnnotationsJsonNative = Pointer<Utf8> Function();
+typedef GetAnnotationsJsonDart = Pointer<Utf8> Function();
+
+typedef AddAnnotationJsonNative = Int32 Function(Pointer<Utf8>);
+typedef AddAnnotationJsonDart = int Function(Pointer<Utf8>);
+
+// Extensão da ponte FFI para as anotações da linha do tempo
+extension AnnotationsFFIBridge on FFIBridge {
+  // Obtém as anotações da simulação concluída, ordenadas por tempo
+  Future<List<TimelineAnnotation>> getAnnotations() async {
+    try {
+      final func = dylib
+          .lookup<NativeFunction<GetAnnotationsJsonNative>>(
+              'get_annotations_json')
+          .asFunction<GetAnnotationsJsonDart>();
+      final jsonString = callJsonReturningFunction(func);
+      final List<dynamic> jsonList = json.decode(jsonString);
+      return jsonList
+          .map((annotation) => TimelineAnnotation.fromJson(annotation))
+          .toList();
+    } catch (e) {
+      print("Error in getAnnotations: $e");
+      throw Exception('Erro ao obter anotações: $e');
+    }
+  }
+
+  // Adiciona uma anotação do usuário à linha do tempo da simulação concluída
+  Future<void> addAnnotation(TimelineAnnotation annotation) async {
+    try {
+      final func = dylib
+          .lookup<NativeFunction<AddAnnotationJsonNative>>(
+              'add_annotation_json')
+          .asFunction<AddAnnotationJsonDart>();
+      callVoidReturningFunction1Arg(func, json.encode(annotation.toJson()));
+    } catch (e) {
+      print("Error in addAnnotation: $e");
+      throw Exception('Erro ao adicionar anotação: $e');
+    }
+  }
+}
@@ -67,6 +67,14 @@ final class PlasmaTorch extends Struct {
 
   @Double()
   external double gas_temperature;
+
+  // 0: Gaussiano, 1: Exponencial, 2: Cartola
+  @Int32()
+  external int flux_profile;
+
+  // σ, λ ou raio (m); <= 0 usa o diâmetro da tocha
+  @Double()
+  external double flux_profile_length;
 }
 
 // Propriedades do material
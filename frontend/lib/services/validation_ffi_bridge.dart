@@ -10,6 +10,7 @@ import 'dart:typed_data'; // For Float64List etc.
 final class FFIImportOptions extends Struct {
   external Pointer<Utf8> input_path;
   external Pointer<Utf8> format;
+  external Pointer<Utf8> options_json; // CsvImportOptions JSON; nullptr = defaults
 }
 
 final class FFIVector_f64 extends Struct {
@@ -52,6 +53,7 @@ final class FFIReferenceData extends Struct {
   external FFIVector_Coordinate coordinates;
   external FFIVector_f64 values;
   external FFIVector_f64 uncertainties;
+  external FFIVector_f64 times; // nullptr when the measurements have no times
   external FFIMap_String_String metadata;
 }
 
@@ -240,10 +242,12 @@ class ValidationFFIBridge {
     final optionsPtr = calloc<FFIImportOptions>();
     final pathPtr = options.inputPath.toNativeUtf8();
     final formatPtr = options.format.toNativeUtf8();
+    final csvOptionsPtr = jsonEncode(_csvImportOptionsJson(options)).toNativeUtf8();
 
     try {
       optionsPtr.ref.input_path = pathPtr;
       optionsPtr.ref.format = formatPtr;
+      optionsPtr.ref.options_json = csvOptionsPtr;
 
       // Call FFI function
       final resultPtr = _importReferenceData(optionsPtr);
@@ -268,9 +272,31 @@ class ValidationFFIBridge {
       calloc.free(optionsPtr);
       calloc.free(pathPtr);
       calloc.free(formatPtr);
+      calloc.free(csvOptionsPtr);
     }
   }
 
+  // Opções de CSV no formato do CsvImportOptions do Rust; colunas por índice
+  // (primeira coordenada = r, última = z) e, se ausentes, as colunas padrão
+  Map<String, dynamic> _csvImportOptionsJson(ImportOptions options) {
+    final coordinates = options.coordinateColumns;
+    return {
+      if (options.delimiter != null && options.delimiter!.length == 1)
+        'delimiter': options.delimiter,
+      'has_header': options.hasHeader,
+      if (coordinates != null &&
+          coordinates.length >= 2 &&
+          options.valueColumn != null)
+        'columns': {
+          'r': coordinates.first,
+          'z': coordinates.last,
+          'time': null,
+          'value': options.valueColumn,
+          'uncertainty': options.uncertaintyColumn,
+        },
+    };
+  }
+
   // Cria dados de referência sintéticos para testes
   Future<ReferenceData> createSyntheticReferenceData(
       int numPoints, double errorLevel) async {
@@ -6,6 +6,8 @@ import 'package:ffi/ffi.dart';
 import '../models/simulation_parameters.dart' as models;
 import '../models/simulation_results.dart';
 import '../services/ffi_bridge.dart' as ffi;
+import '../services/annotations_ffi_bridge.dart';
+import '../models/annotation.dart';
 
 // Provider para o serviço de simulação
 final simulationServiceProvider = Provider<SimulationService>((ref) {
@@ -316,6 +318,8 @@ class SimulationService {
         return SimulationStatus.completed;
       case 4:
         return SimulationStatus.failed;
+      case 5:
+        return SimulationStatus.cancelled;
       default:
         print("Aviso: Status FFI desconhecido recebido: $ffiStatus");
         return SimulationStatus.failed; // Default to failed for unknown status
@@ -370,6 +374,15 @@ class SimulationService {
       final currentState = await getSimulationState();
       final lastExecutionTime = currentState.executionTime;
 
+      // Anotações da linha do tempo (marcadores do controle de tempo); a falha
+      // ao obtê-las não impede a exibição dos resultados
+      List<TimelineAnnotation> annotations = const [];
+      try {
+        annotations = await _ffiBridge.getAnnotations();
+      } catch (e) {
+        print("Aviso: anotações indisponíveis: $e");
+      }
+
       return SimulationResults(
         // Adapt as needed for the actual SimulationResults model definition
         nr: nr,
@@ -379,6 +392,8 @@ class SimulationService {
           formattedTemperatureData
         ], // Wrap the single step data in a list
         executionTime: lastExecutionTime,
+        timeStep: _currentParameters!.timeStep,
+        annotations: annotations,
       );
     } catch (e) {
       print("Erro ao obter resultados da simulação: $e");
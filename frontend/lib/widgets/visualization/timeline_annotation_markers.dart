@@ -0,0 +1,93 @@
+import 'package:flutter/material.dart';
+import '../../models/annotation.dart';
+
+// Marcadores das anotações alinhados ao controle deslizante de passos de tempo
+class TimelineAnnotationMarkers extends StatelessWidget {
+  final List<TimelineAnnotation> annotations;
+  final int maxTimeStep;
+  // Passo de tempo da simulação (s), para anotações sem passo definido
+  final double timeStep;
+  final ValueChanged<int> onSelected;
+  // Margem horizontal do trilho do Slider padrão do Material
+  final double horizontalPadding;
+
+  const TimelineAnnotationMarkers({
+    Key? key,
+    required this.annotations,
+    required this.maxTimeStep,
+    required this.timeStep,
+    required this.onSelected,
+    this.horizontalPadding = 24.0,
+  }) : super(key: key);
+
+  static const double _markerSize = 18.0;
+
+  @override
+  Widget build(BuildContext context) {
+    if (annotations.isEmpty) return const SizedBox.shrink();
+
+    return SizedBox(
+      height: _markerSize + 4,
+      child: LayoutBuilder(
+        builder: (context, constraints) {
+          final trackWidth = constraints.maxWidth - 2 * horizontalPadding;
+          return Stack(
+            clipBehavior: Clip.none,
+            children: annotations.map((annotation) {
+              final step = annotation.stepFor(timeStep).clamp(0, maxTimeStep);
+              final fraction = maxTimeStep > 0 ? step / maxTimeStep : 0.0;
+              return Positioned(
+                left: horizontalPadding + fraction * trackWidth - _markerSize / 2,
+                top: 0,
+                child: Tooltip(
+                  message: _tooltipText(annotation),
+                  child: InkWell(
+                    onTap: () => onSelected(step),
+                    child: Icon(
+                      _iconFor(annotation.kind),
+                      size: _markerSize,
+                      color: _colorFor(annotation),
+                    ),
+                  ),
+                ),
+              );
+            }).toList(),
+          );
+        },
+      ),
+    );
+  }
+
+  String _tooltipText(TimelineAnnotation annotation) {
+    final buffer = StringBuffer(
+        '${annotation.kindLabel} — ${annotation.title} (t = ${annotation.time.toStringAsFixed(1)} s)');
+    if (annotation.description.isNotEmpty) {
+      buffer.write('\n${annotation.description}');
+    }
+    return buffer.toString();
+  }
+
+  IconData _iconFor(AnnotationKind kind) {
+    switch (kind) {
+      case AnnotationKind.tapEvent:
+        return Icons.water_drop;
+      case AnnotationKind.powerChange:
+        return Icons.bolt;
+      case AnnotationKind.alarm:
+        return Icons.warning_amber;
+      case AnnotationKind.feedAdded:
+        return Icons.add_box;
+      case AnnotationKind.note:
+        return Icons.sticky_note_2;
+      case AnnotationKind.custom:
+        return Icons.label;
+    }
+  }
+
+  Color _colorFor(TimelineAnnotation annotation) {
+    if (annotation.kind == AnnotationKind.alarm) return Colors.red;
+    return annotation.source == AnnotationSource.solver
+        ? Colors.blueGrey
+        : Colors.deepPurple;
+  }
+}